@@ -0,0 +1,401 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+
+use super::{
+    delete_managed_skill_blocking, missing_dependencies_after_install, sync_skill_to_tool_blocking,
+    sync_target_path, to_install_dto, to_update_dto, unsync_skill_from_tool_blocking,
+    SkillsHubError,
+};
+use crate::core::installer::{
+    install_git_skill_from_selection_with_options, install_git_skill_with_options,
+    install_local_skill_with_options, update_managed_skill_from_source_with_force, InstallOptions,
+    InstallResult, OnConflict,
+};
+use crate::core::skill_store::SkillStore;
+use crate::core::tool_adapters::adapter_by_key;
+
+/// A skill named directly, or the skill installed by an earlier step of the same batch.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillRef {
+    Id(String),
+    Step(usize),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchStep {
+    InstallLocal {
+        source_path: String,
+        name: Option<String>,
+        #[serde(default)]
+        force: bool,
+        #[serde(default)]
+        on_conflict: OnConflict,
+    },
+    InstallGit {
+        repo_url: String,
+        name: Option<String>,
+        #[serde(default)]
+        force: bool,
+        #[serde(default)]
+        on_conflict: OnConflict,
+    },
+    InstallGitSelection {
+        repo_url: String,
+        subpath: String,
+        name: Option<String>,
+        #[serde(default)]
+        force: bool,
+        #[serde(default)]
+        on_conflict: OnConflict,
+    },
+    SyncToTool {
+        skill: SkillRef,
+        tool: String,
+        #[serde(default)]
+        overwrite: bool,
+        #[serde(default)]
+        force: bool,
+    },
+    UnsyncFromTool {
+        skill: SkillRef,
+        tool: String,
+    },
+    Update {
+        skill: SkillRef,
+        #[serde(default)]
+        force: bool,
+    },
+    Delete {
+        skill: SkillRef,
+        #[serde(default)]
+        force: bool,
+    },
+}
+
+impl BatchStep {
+    fn op(&self) -> &'static str {
+        match self {
+            Self::InstallLocal { .. } => "install_local",
+            Self::InstallGit { .. } => "install_git",
+            Self::InstallGitSelection { .. } => "install_git_selection",
+            Self::SyncToTool { .. } => "sync_to_tool",
+            Self::UnsyncFromTool { .. } => "unsync_from_tool",
+            Self::Update { .. } => "update",
+            Self::Delete { .. } => "delete",
+        }
+    }
+
+    fn is_install(&self) -> bool {
+        matches!(
+            self,
+            Self::InstallLocal { .. } | Self::InstallGit { .. } | Self::InstallGitSelection { .. }
+        )
+    }
+
+    /// Fresh installs and syncs can be undone; unsync, update, delete, installs that adopt or
+    /// replace an existing skill, and syncs allowed to overwrite an existing target cannot.
+    fn is_reversible(&self) -> bool {
+        match self {
+            Self::InstallLocal { on_conflict, .. }
+            | Self::InstallGit { on_conflict, .. }
+            | Self::InstallGitSelection { on_conflict, .. } => {
+                matches!(on_conflict, OnConflict::Fail | OnConflict::InstallAsNewName)
+            }
+            Self::SyncToTool { overwrite, .. } => !overwrite,
+            _ => false,
+        }
+    }
+
+    fn skill_ref(&self) -> Option<&SkillRef> {
+        match self {
+            Self::SyncToTool { skill, .. }
+            | Self::UnsyncFromTool { skill, .. }
+            | Self::Update { skill, .. }
+            | Self::Delete { skill, .. } => Some(skill),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to the remaining steps once one fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchPolicy {
+    /// Skip the remaining steps, keep what already succeeded.
+    #[default]
+    StopOnError,
+    /// Run every step regardless; steps referring to a failed install are skipped.
+    Continue,
+    /// Skip the remaining steps and undo the succeeded ones in reverse order.
+    Rollback,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepState {
+    Succeeded,
+    Failed,
+    Skipped,
+    RolledBack,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchStepResult {
+    pub index: usize,
+    pub op: String,
+    pub state: StepState,
+    pub skill_id: Option<String>,
+    /// Same payload the single-step command returns.
+    pub result: Option<Value>,
+    pub error: Option<SkillsHubError>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchResult {
+    /// True when every step succeeded.
+    pub ok: bool,
+    pub steps: Vec<BatchStepResult>,
+}
+
+/// Run `steps` in order and report each one. See `BatchPolicy` for failure handling.
+#[tauri::command]
+pub async fn run_batch(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    steps: Vec<BatchStep>,
+    policy: Option<BatchPolicy>,
+) -> Result<BatchResult, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        run_batch_blocking(&app, &store, &steps, policy.unwrap_or_default())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Refuses malformed batches before anything runs: step references must point at an earlier
+/// install, and the rollback policy only accepts steps that can be undone.
+fn validate(steps: &[BatchStep], policy: BatchPolicy) -> anyhow::Result<()> {
+    for (idx, step) in steps.iter().enumerate() {
+        if let Some(SkillRef::Step(target)) = step.skill_ref() {
+            if *target >= idx || !steps[*target].is_install() {
+                anyhow::bail!(
+                    "INVALID_BATCH|step {} refers to step {}, which is not an earlier install",
+                    idx,
+                    target
+                );
+            }
+        }
+        if policy == BatchPolicy::Rollback && !step.is_reversible() {
+            anyhow::bail!(
+                "INVALID_BATCH|step {} ({}) cannot be rolled back",
+                idx,
+                step.op()
+            );
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn run_batch_blocking<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    steps: &[BatchStep],
+    policy: BatchPolicy,
+) -> anyhow::Result<BatchResult> {
+    validate(steps, policy)?;
+
+    let mut results: Vec<BatchStepResult> = Vec::with_capacity(steps.len());
+    // Whether each sync step's target was already there; rollback leaves those alone.
+    let mut target_existed = vec![false; steps.len()];
+    let mut failed = false;
+    for (index, step) in steps.iter().enumerate() {
+        let mut entry = BatchStepResult {
+            index,
+            op: step.op().to_string(),
+            state: StepState::Skipped,
+            skill_id: None,
+            result: None,
+            error: None,
+        };
+        let skill_id = match step.skill_ref() {
+            Some(SkillRef::Id(id)) => Some(id.clone()),
+            Some(SkillRef::Step(target)) => results[*target].skill_id.clone(),
+            None => None,
+        };
+        // Stop after a failure unless told to continue; a reference to a failed install has
+        // nothing to act on either way.
+        let unresolved = matches!(step.skill_ref(), Some(SkillRef::Step(_))) && skill_id.is_none();
+        if (failed && policy != BatchPolicy::Continue) || unresolved {
+            results.push(entry);
+            continue;
+        }
+
+        entry.skill_id = skill_id.clone();
+        if let (BatchStep::SyncToTool { tool, .. }, Some(id)) = (step, skill_id.as_deref()) {
+            target_existed[index] = sync_target_exists(store, id, tool);
+        }
+        match run_step(app, store, step, skill_id.as_deref()) {
+            Ok((id, value)) => {
+                entry.state = StepState::Succeeded;
+                entry.skill_id = entry.skill_id.or(id);
+                entry.result = Some(value);
+            }
+            Err(err) => {
+                entry.state = StepState::Failed;
+                entry.error = Some(SkillsHubError::from(err));
+                failed = true;
+            }
+        }
+        results.push(entry);
+    }
+
+    if failed && policy == BatchPolicy::Rollback {
+        for (entry, step) in results.iter_mut().zip(steps).rev() {
+            if entry.state != StepState::Succeeded {
+                continue;
+            }
+            if target_existed[entry.index] {
+                log::info!(
+                    "[batch] step {} synced to an existing target; leaving it in place",
+                    entry.index
+                );
+                continue;
+            }
+            match undo_step(store, step, entry.skill_id.as_deref()) {
+                Ok(()) => entry.state = StepState::RolledBack,
+                Err(err) => {
+                    log::warn!("[batch] rollback of step {} failed: {:#}", entry.index, err);
+                    entry.error = Some(SkillsHubError::from(err));
+                }
+            }
+        }
+    }
+
+    Ok(BatchResult {
+        ok: !failed,
+        steps: results,
+    })
+}
+
+/// Returns the skill the step created (for installs) and its serialized result.
+fn run_step<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    step: &BatchStep,
+    skill_id: Option<&str>,
+) -> anyhow::Result<(Option<String>, Value)> {
+    let skill_id = || skill_id.ok_or_else(|| anyhow::anyhow!("step has no skill"));
+    let options = |force, on_conflict| InstallOptions { force, on_conflict };
+    let installed = |result: InstallResult| {
+        let missing = missing_dependencies_after_install(store, &result.skill_id);
+        let dto = to_install_dto(result, missing);
+        Ok((Some(dto.skill_id.clone()), serde_json::to_value(dto)?))
+    };
+    match step {
+        BatchStep::InstallLocal {
+            source_path,
+            name,
+            force,
+            on_conflict,
+        } => installed(install_local_skill_with_options(
+            app,
+            store,
+            source_path.as_ref(),
+            name.clone(),
+            options(*force, *on_conflict),
+        )?),
+        BatchStep::InstallGit {
+            repo_url,
+            name,
+            force,
+            on_conflict,
+        } => installed(install_git_skill_with_options(
+            app,
+            store,
+            repo_url,
+            name.clone(),
+            options(*force, *on_conflict),
+        )?),
+        BatchStep::InstallGitSelection {
+            repo_url,
+            subpath,
+            name,
+            force,
+            on_conflict,
+        } => installed(install_git_skill_from_selection_with_options(
+            app,
+            store,
+            repo_url,
+            subpath,
+            name.clone(),
+            options(*force, *on_conflict),
+        )?),
+        BatchStep::SyncToTool {
+            tool,
+            overwrite,
+            force,
+            ..
+        } => {
+            let skill = store
+                .get_skill_by_id(skill_id()?)?
+                .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+            let result = sync_skill_to_tool_blocking(
+                store,
+                &skill.central_path,
+                &skill.id,
+                tool,
+                &skill.name,
+                *overwrite,
+                *force,
+                *force,
+            )?;
+            Ok((None, serde_json::to_value(result)?))
+        }
+        BatchStep::UnsyncFromTool { tool, .. } => {
+            unsync_skill_from_tool_blocking(store, skill_id()?, tool)?;
+            Ok((None, Value::Null))
+        }
+        BatchStep::Update { force, .. } => {
+            let res = update_managed_skill_from_source_with_force(app, store, skill_id()?, *force)?;
+            Ok((None, serde_json::to_value(to_update_dto(res))?))
+        }
+        BatchStep::Delete { force, .. } => {
+            delete_managed_skill_blocking(store, skill_id()?, *force)?;
+            Ok((None, Value::Null))
+        }
+    }
+}
+
+/// True when the tool already has this skill, either recorded or present on disk. Unknown
+/// states count as existing so rollback never removes something the batch did not create.
+pub(super) fn sync_target_exists(store: &SkillStore, skill_id: &str, tool: &str) -> bool {
+    if !matches!(store.get_skill_target(skill_id, tool), Ok(None)) {
+        return true;
+    }
+    let Some(adapter) = adapter_by_key(tool) else {
+        return false;
+    };
+    let Ok(Some(skill)) = store.get_skill_by_id(skill_id) else {
+        return true;
+    };
+    match sync_target_path(store, &adapter, &skill.name) {
+        Ok(target) => std::fs::symlink_metadata(target).is_ok(),
+        Err(_) => true,
+    }
+}
+
+fn undo_step(store: &SkillStore, step: &BatchStep, skill_id: Option<&str>) -> anyhow::Result<()> {
+    let skill_id = skill_id.ok_or_else(|| anyhow::anyhow!("step has no skill"))?;
+    match step {
+        BatchStep::SyncToTool { tool, .. } => {
+            unsync_skill_from_tool_blocking(store, skill_id, tool)
+        }
+        step if step.is_install() => delete_managed_skill_blocking(store, skill_id, true),
+        _ => anyhow::bail!("{} cannot be rolled back", step.op()),
+    }
+}
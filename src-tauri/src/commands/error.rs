@@ -0,0 +1,499 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::core::locale::{message_locale, MessageLocale};
+
+/// Error returned by every command. `code` is stable and meant for branching/localization;
+/// `details` carries the code's params and `message` is a fallback rendered in the backend
+/// locale.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SkillsHubError {
+    pub code: String,
+    pub message: String,
+    pub details: Option<Value>,
+}
+
+pub const INTERNAL: &str = "INTERNAL";
+pub const GIT_FETCH_FAILED: &str = "GIT_FETCH_FAILED";
+
+/// Prefixes core code puts in front of `anyhow` messages (`CODE|arg|arg`).
+const CODED_PREFIXES: [&str; 25] = [
+    "MULTI_SKILLS",
+    "TARGET_EXISTS",
+    "TARGET_ACCESS",
+    "TOOL_NOT_INSTALLED",
+    "SKILL_LINT_FAILED",
+    "HAS_DEPENDENTS",
+    "DEPENDENCY_CONFLICT",
+    "TOOL_INCOMPATIBLE",
+    "SKILL_INVALID",
+    "INVALID_PATH",
+    "INVALID_BATCH",
+    "GIT_TIMEOUT",
+    "GIT_CLI_FAILED",
+    "CLEANUP_FAILED",
+    "OPERATION_IN_PROGRESS",
+    "SKILL_EXISTS",
+    "SECRETS_FOUND",
+    "EXECUTABLES_UNCONFIRMED",
+    "EXECUTABLES_BLOCKED",
+    "GIT_HOST_BLOCKED",
+    "SIGNATURE_REQUIRED",
+    "SIGNATURE_INVALID",
+    "SIGNATURE_UNTRUSTED",
+    "SYNC_CONFLICT",
+    "SYNC_REJECTED",
+];
+
+impl SkillsHubError {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Error whose message is rendered from the catalogue in the backend locale.
+    fn coded(code: &str, details: Value) -> Self {
+        let message = localized_message(code, &details, message_locale());
+        Self::new(code, message).with_details(details)
+    }
+}
+
+impl std::fmt::Display for SkillsHubError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl From<String> for SkillsHubError {
+    fn from(message: String) -> Self {
+        Self::new(INTERNAL, message)
+    }
+}
+
+impl From<tauri::Error> for SkillsHubError {
+    fn from(err: tauri::Error) -> Self {
+        Self::new(INTERNAL, err.to_string())
+    }
+}
+
+impl From<anyhow::Error> for SkillsHubError {
+    fn from(err: anyhow::Error) -> Self {
+        // The innermost coded message wins, so added context does not hide the code.
+        if let Some(coded) = err
+            .chain()
+            .rev()
+            .find_map(|cause| from_coded_message(&cause.to_string()))
+        {
+            return coded;
+        }
+
+        // Include the full error chain (causes), not just the top context.
+        let mut full = format!("{:#}", err);
+
+        // Redact noisy temp paths from clone context (we care about the cause, not the dest).
+        // Example: `clone https://... into "/Users/.../skills-hub-git-<uuid>"`
+        if let Some(head) = full.lines().next() {
+            if head.starts_with("clone ") {
+                if let Some(pos) = head.find(" into ") {
+                    let omitted = match message_locale() {
+                        MessageLocale::En => "(temp dir omitted)",
+                        MessageLocale::Zh => "(已省略临时目录)",
+                    };
+                    let head_redacted = format!("{} {}", &head[..pos], omitted);
+                    let rest: String = full.lines().skip(1).collect::<Vec<_>>().join("\n");
+                    full = if rest.is_empty() {
+                        head_redacted
+                    } else {
+                        format!("{}\n{}", head_redacted, rest)
+                    };
+                }
+            }
+        }
+
+        let root = err.root_cause().to_string();
+        let lower = full.to_lowercase();
+
+        // Heuristic-friendly messaging for GitHub clone failures.
+        if lower.contains("github.com")
+            && (lower.contains("clone ") || lower.contains("remote") || lower.contains("fetch"))
+        {
+            return Self::coded(
+                GIT_FETCH_FAILED,
+                json!({ "reason": github_reason(&lower), "cause": root }),
+            );
+        }
+
+        Self::new(INTERNAL, full)
+    }
+}
+
+fn github_reason(lower: &str) -> &'static str {
+    if lower.contains("securetransport") {
+        "tls"
+    } else if lower.contains("authentication")
+        || lower.contains("permission denied")
+        || lower.contains("credentials")
+    {
+        "auth"
+    } else if lower.contains("not found") {
+        "not_found"
+    } else if lower.contains("failed to resolve")
+        || lower.contains("could not resolve")
+        || lower.contains("dns")
+    {
+        "dns"
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        "timeout"
+    } else if lower.contains("connection refused") || lower.contains("connection reset") {
+        "connection"
+    } else {
+        "unknown"
+    }
+}
+
+/// Turn a `CODE|arg|...` message from core into a structured error.
+fn from_coded_message(text: &str) -> Option<SkillsHubError> {
+    let (code, rest) = text.split_once('|')?;
+    if !CODED_PREFIXES.contains(&code) {
+        return None;
+    }
+    let list = |raw: &str| -> Vec<String> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let details = match code {
+        "TARGET_EXISTS" | "INVALID_PATH" => json!({ "path": rest }),
+        "TOOL_NOT_INSTALLED" => json!({ "tool": rest }),
+        "TARGET_ACCESS" => {
+            let (reason, path) = rest.split_once('|').unwrap_or((rest, ""));
+            json!({ "reason": reason, "path": path })
+        }
+        "GIT_HOST_BLOCKED" => json!({ "host": rest }),
+        "EXECUTABLES_UNCONFIRMED"
+        | "EXECUTABLES_BLOCKED"
+        | "SIGNATURE_REQUIRED"
+        | "SIGNATURE_INVALID" => json!({ "skill": rest }),
+        "SIGNATURE_UNTRUSTED" => {
+            let (skill, key_id) = rest.rsplit_once('|').unwrap_or((rest, ""));
+            json!({ "skill": skill, "key_id": key_id })
+        }
+        "SKILL_EXISTS" => {
+            let (path, managed) = rest.rsplit_once('|').unwrap_or((rest, "false"));
+            json!({ "path": path, "managed": managed == "true" })
+        }
+        "HAS_DEPENDENTS" => json!({ "dependents": list(rest) }),
+        "SYNC_CONFLICT" => json!({ "skills": list(rest) }),
+        "SYNC_REJECTED" => json!({ "branch": rest }),
+        "TOOL_INCOMPATIBLE" => {
+            let (tool, compatible) = rest.split_once('|').unwrap_or((rest, ""));
+            json!({ "tool": tool, "compatible_tools": list(compatible) })
+        }
+        "SKILL_INVALID" => {
+            let (reason, issues) = match rest.split_once('|') {
+                Some((reason, issues)) => (reason, serde_json::from_str::<Value>(issues).ok()),
+                None => (rest, None),
+            };
+            json!({ "reason": reason, "issues": issues })
+        }
+        "MULTI_SKILLS" => {
+            let (repo_url, count) = rest.split_once('|').unwrap_or((rest, ""));
+            json!({ "repo_url": repo_url, "count": count.parse::<u64>().ok() })
+        }
+        "GIT_TIMEOUT" => {
+            let (secs, output) = rest.split_once('|').unwrap_or((rest, ""));
+            json!({ "secs": secs.parse::<u64>().ok(), "output": output })
+        }
+        "GIT_CLI_FAILED" => {
+            // Keep the specific GitHub hint (auth, dns, ...) when the failure names GitHub.
+            let lower = rest.to_lowercase();
+            if lower.contains("github.com") {
+                return Some(SkillsHubError::coded(
+                    GIT_FETCH_FAILED,
+                    json!({ "reason": github_reason(&lower), "cause": rest }),
+                ));
+            }
+            json!({ "cause": rest })
+        }
+        "CLEANUP_FAILED" => json!({ "failures": rest.split('\n').collect::<Vec<_>>() }),
+        "OPERATION_IN_PROGRESS" => {
+            let (skill_id, operation) = rest.split_once('|').unwrap_or((rest, ""));
+            json!({ "skill_id": skill_id, "operation": operation })
+        }
+        // SKILL_LINT_FAILED, DEPENDENCY_CONFLICT, SECRETS_FOUND, INVALID_BATCH carry a readable
+        // summary.
+        _ => json!({ "summary": rest }),
+    };
+    Some(SkillsHubError::coded(code, details))
+}
+
+/// Readable text for `code` in `locale`, filled in from `details`. The frontend translates by
+/// code itself; this is the fallback it shows for unknown codes and what ends up in logs.
+pub fn localized_message(code: &str, details: &Value, locale: MessageLocale) -> String {
+    let arg = |key: &str| -> String {
+        match &details[key] {
+            Value::String(s) => s.clone(),
+            Value::Array(items) => items
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| v.to_string())
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    };
+    let (en, zh) = match code {
+        "TARGET_EXISTS" => (
+            format!("target already exists: {}", arg("path")),
+            format!("目标目录已存在：{}", arg("path")),
+        ),
+        "TARGET_ACCESS" if details["reason"] == "other_owner" => (
+            format!(
+                "{} is owned by another user; take ownership back (e.g. sudo chown -R \"$USER\" \
+                 <dir>) and sync again",
+                arg("path")
+            ),
+            format!(
+                "{} 属于其他用户，请先取回所有权（例如 sudo chown -R \"$USER\" <目录>）再同步",
+                arg("path")
+            ),
+        ),
+        "TARGET_ACCESS" => (
+            format!(
+                "{} is read-only; make it writable and sync again",
+                arg("path")
+            ),
+            format!("{} 为只读，请改为可写后再同步", arg("path")),
+        ),
+        "INVALID_PATH" => (
+            format!("path is outside the skill directory: {}", arg("path")),
+            format!("路径超出了 Skill 目录：{}", arg("path")),
+        ),
+        "SKILL_EXISTS" => (
+            format!("skill already exists in central repo: {}", arg("path")),
+            format!("中央仓库中已存在同名 Skill：{}", arg("path")),
+        ),
+        "TOOL_NOT_INSTALLED" => (
+            format!("tool not installed: {}", arg("tool")),
+            format!("未安装该工具：{}", arg("tool")),
+        ),
+        "HAS_DEPENDENTS" => (
+            format!("other skills depend on this one: {}", arg("dependents")),
+            format!("以下 Skills 依赖于它：{}", arg("dependents")),
+        ),
+        "TOOL_INCOMPATIBLE" => (
+            format!(
+                "skill does not support {} (supports: {})",
+                arg("tool"),
+                arg("compatible_tools")
+            ),
+            format!(
+                "该 Skill 不支持 {}（支持：{}）",
+                arg("tool"),
+                arg("compatible_tools")
+            ),
+        ),
+        "SKILL_INVALID" => (
+            format!("invalid skill: {}", arg("reason")),
+            format!("Skill 无效：{}", arg("reason")),
+        ),
+        "MULTI_SKILLS" => (
+            "This repository contains multiple skills; copy the folder URL of one skill \
+             (e.g. GitHub /tree/<branch>/skills/<name>) and import that."
+                .to_string(),
+            "该仓库包含多个 Skills，请复制具体 Skill 文件夹链接（例如 GitHub 的 \
+             /tree/<branch>/skills/<name>），再导入。"
+                .to_string(),
+        ),
+        "SKILL_LINT_FAILED" => (
+            format!("skill failed lint: {}", arg("summary")),
+            format!("Skill 校验未通过：{}", arg("summary")),
+        ),
+        "DEPENDENCY_CONFLICT" => (
+            format!("dependency conflict: {}", arg("summary")),
+            format!("依赖冲突：{}", arg("summary")),
+        ),
+        "EXECUTABLES_UNCONFIRMED" => (
+            format!(
+                "{} contains scripts or binaries; confirm before syncing it to tools",
+                arg("skill")
+            ),
+            format!(
+                "{} 包含脚本或可执行文件，同步到工具前需要确认",
+                arg("skill")
+            ),
+        ),
+        "EXECUTABLES_BLOCKED" => (
+            format!(
+                "{} contains scripts or binaries, which the executable policy does not allow \
+                 syncing",
+                arg("skill")
+            ),
+            format!("{} 包含脚本或可执行文件，当前策略不允许同步", arg("skill")),
+        ),
+        "GIT_HOST_BLOCKED" => (
+            format!("git host not allowed by policy: {}", arg("host")),
+            format!("Git 主机不在允许范围内：{}", arg("host")),
+        ),
+        "SIGNATURE_REQUIRED" => (
+            format!("{} is not signed by a trusted key", arg("skill")),
+            format!("{} 没有受信任密钥的签名", arg("skill")),
+        ),
+        "SIGNATURE_INVALID" => (
+            format!("signature of {} does not match its content", arg("skill")),
+            format!("{} 的签名与内容不符", arg("skill")),
+        ),
+        "SIGNATURE_UNTRUSTED" => (
+            format!(
+                "{} is signed by an untrusted key: {}",
+                arg("skill"),
+                arg("key_id")
+            ),
+            format!("{} 的签名密钥不受信任：{}", arg("skill"), arg("key_id")),
+        ),
+        "SYNC_CONFLICT" => (
+            format!(
+                "the remote library changed the same files; resolve these skills in the \
+                 central repo first: {}",
+                arg("skills")
+            ),
+            format!(
+                "远端与本地修改了相同的文件，请先在中央仓库中解决这些 Skills 的冲突：{}",
+                arg("skills")
+            ),
+        ),
+        "SYNC_REJECTED" => (
+            format!(
+                "the remote {} has newer changes; pull before pushing",
+                arg("branch")
+            ),
+            format!("远端 {} 有更新的提交，请先拉取再推送", arg("branch")),
+        ),
+        "SECRETS_FOUND" => (
+            format!(
+                "skill looks like it contains credentials: {}",
+                arg("summary")
+            ),
+            format!("Skill 中疑似包含凭据：{}", arg("summary")),
+        ),
+        "INVALID_BATCH" => (
+            format!("invalid batch: {}", arg("summary")),
+            format!("批量操作无效：{}", arg("summary")),
+        ),
+        "GIT_TIMEOUT" => (
+            format!(
+                "git timed out ({}s). Check that GitHub is reachable through your network or \
+                 proxy, or raise SKILLS_HUB_GIT_TIMEOUT_SECS.\n{}",
+                arg("secs"),
+                arg("output")
+            ),
+            format!(
+                "git 操作超时（{}s）。请检查网络/代理是否可访问 GitHub；也可设置环境变量 \
+                 SKILLS_HUB_GIT_TIMEOUT_SECS 增大超时。\n{}",
+                arg("secs"),
+                arg("output")
+            ),
+        ),
+        "GIT_CLI_FAILED" => (
+            format!(
+                "git failed (not falling back to the built-in git to avoid hanging). Check \
+                 git, network and proxy, or set SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1.\n{}",
+                arg("cause")
+            ),
+            format!(
+                "git 命令执行失败（为避免卡死，已停止并不再回退到内置 git）。请检查系统 \
+                 git/网络/代理；或设置环境变量 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允许回退。\n{}",
+                arg("cause")
+            ),
+        ),
+        "CLEANUP_FAILED" => {
+            let failures = details["failures"]
+                .as_array()
+                .map(|items| items.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .join("\n- ");
+            (
+                format!(
+                    "skill removed, but some tool directories could not be cleaned:\n- {}",
+                    failures
+                ),
+                format!("已删除托管记录，但清理部分工具目录失败：\n- {}", failures),
+            )
+        }
+        "OPERATION_IN_PROGRESS" => (
+            format!(
+                "another operation ({}) is in progress on this skill; try again when it finishes",
+                arg("operation")
+            ),
+            format!(
+                "该 Skill 正在执行其他操作（{}），请稍后重试",
+                arg("operation")
+            ),
+        ),
+        GIT_FETCH_FAILED => {
+            let (en, zh) = github_hint(&arg("reason"));
+            (
+                format!("{}\n\nDetails: {}", en, arg("cause")),
+                format!("{}\n\n详细：{}", zh, arg("cause")),
+            )
+        }
+        _ => (arg("summary"), arg("summary")),
+    };
+    match locale {
+        MessageLocale::En => en,
+        MessageLocale::Zh => zh,
+    }
+}
+
+fn github_hint(reason: &str) -> (&'static str, &'static str) {
+    match reason {
+        "tls" => (
+            "Could not fetch from GitHub: TLS/certificate verification failed (macOS \
+             SecureTransport).\n\nTry:\n- check whether a network/proxy intercepts HTTPS\n- on \
+             a corporate network, install the company root certificate or use a trusted \
+             proxy\n- check that `git clone https://github.com/<owner>/<repo>` works in a \
+             terminal",
+            "无法从 GitHub 拉取仓库：TLS/证书校验失败（macOS SecureTransport）。\n\n建议：\n- \
+             检查网络/代理是否拦截 HTTPS\n- 如在公司网络，可能需要安装公司根证书或使用可信代理\n\
+             - 也可在终端确认 `git clone https://github.com/<owner>/<repo>` 是否可用",
+        ),
+        "auth" => (
+            "Cannot access this repository: it may be private or need authentication.",
+            "无法访问该仓库：可能是私有仓库/权限不足/需要鉴权。",
+        ),
+        "not_found" => (
+            "Repository not found or not accessible (GitHub returned not found).",
+            "仓库不存在或无权限访问（GitHub 返回 not found）。",
+        ),
+        "dns" => (
+            "Could not resolve GitHub (DNS). Check your network or proxy.",
+            "无法解析 GitHub 域名（DNS）。请检查网络/代理。",
+        ),
+        "timeout" => (
+            "Connecting to GitHub timed out. Check your network or proxy.",
+            "连接 GitHub 超时。请检查网络/代理。",
+        ),
+        "connection" => (
+            "Connecting to GitHub failed (refused or reset). Check your network or proxy.",
+            "连接 GitHub 失败（连接被拒绝/重置）。请检查网络/代理。",
+        ),
+        _ => (
+            "Could not fetch from GitHub. Check your network or proxy, or try again later.",
+            "无法从 GitHub 拉取仓库。请检查网络/代理，或稍后重试。",
+        ),
+    }
+}
@@ -0,0 +1,555 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::State;
+
+use super::batch::{run_batch_blocking, BatchPolicy, BatchStep, SkillRef, StepState};
+use super::{get_managed_skills_impl, SkillsHubError};
+use crate::core::installer::OnConflict;
+use crate::core::skill_store::{SkillProjection, SkillStore};
+
+pub const LOCAL_API_ENABLED_KEY: &str = "local_api_enabled";
+pub const LOCAL_API_PORT_KEY: &str = "local_api_port";
+const LOCAL_API_TOKEN_KEY: &str = "local_api_token";
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47821;
+/// Request bodies are small JSON documents; anything bigger is refused unread.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_HEADER_LINES: usize = 100;
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// Connections served at once; more are answered `503` right away.
+const MAX_CONNECTIONS: usize = 8;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    /// Base URL while the server is running.
+    pub url: Option<String>,
+    /// Sent by clients as `Authorization: Bearer <token>`.
+    pub token: String,
+}
+
+struct RunningServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    /// The accept loop; it owns the listener, so the port is free once it has been joined.
+    listener: JoinHandle<()>,
+}
+
+/// Counts a connection as served until dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The running server, if any; managed as Tauri state.
+#[derive(Clone, Default)]
+pub struct LocalApiState {
+    server: Arc<Mutex<Option<RunningServer>>>,
+}
+
+impl LocalApiState {
+    fn running_port(&self) -> Option<u16> {
+        self.server
+            .lock()
+            .ok()
+            .and_then(|server| server.as_ref().map(|s| s.port))
+    }
+
+    /// Bind `127.0.0.1:port` and serve on a background thread, each connection on its own
+    /// thread (up to `MAX_CONNECTIONS`) so a long install does not block listing. Returns the
+    /// bound port.
+    fn start<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        store: &SkillStore,
+        port: u16,
+    ) -> anyhow::Result<u16> {
+        self.stop();
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .map_err(|err| anyhow::anyhow!("bind 127.0.0.1:{}: {}", port, err))?;
+        let port = listener.local_addr()?.port();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (app, store, flag) = (app.clone(), store.clone(), stop.clone());
+        let listener = std::thread::spawn(move || {
+            let active = Arc::new(AtomicUsize::new(0));
+            for stream in listener.incoming() {
+                if flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let busy = ApiResponse::error(503, "SERVER_BUSY", "too many requests at once");
+                    write_response(&mut stream, &busy);
+                    continue;
+                }
+                let slot = ConnectionSlot(active.clone());
+                let (app, store) = (app.clone(), store.clone());
+                std::thread::spawn(move || {
+                    serve_connection(&app, &store, &mut stream);
+                    // Free the slot before the client sees the connection close.
+                    drop(slot);
+                });
+            }
+            log::info!("[local-api] stopped listening on port {}", port);
+        });
+        if let Ok(mut server) = self.server.lock() {
+            *server = Some(RunningServer {
+                port,
+                stop,
+                listener,
+            });
+        }
+        log::info!("[local-api] listening on 127.0.0.1:{}", port);
+        Ok(port)
+    }
+
+    /// Stop accepting and wait for the accept loop to drop the listener, so the port can be
+    /// bound again at once. Requests already being served finish on their own threads.
+    fn stop(&self) {
+        let Some(server) = self.server.lock().ok().and_then(|mut s| s.take()) else {
+            return;
+        };
+        server.stop.store(true, Ordering::SeqCst);
+        // Wake the blocking accept so the loop sees the flag.
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port));
+        if server.listener.join().is_err() {
+            log::warn!("[local-api] listener thread panicked");
+        }
+    }
+}
+
+fn enabled(store: &SkillStore) -> bool {
+    matches!(
+        store
+            .get_setting(LOCAL_API_ENABLED_KEY)
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    )
+}
+
+fn configured_port(store: &SkillStore) -> u16 {
+    store
+        .get_setting(LOCAL_API_PORT_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| raw.parse().ok())
+        .unwrap_or(DEFAULT_LOCAL_API_PORT)
+}
+
+fn new_token() -> anyhow::Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|err| anyhow::anyhow!("generate local API token: {}", err))?;
+    Ok(hex::encode(bytes))
+}
+
+/// The token, created on first use.
+fn token(store: &SkillStore) -> anyhow::Result<String> {
+    if let Some(token) = store.get_setting(LOCAL_API_TOKEN_KEY)? {
+        return Ok(token);
+    }
+    let token = new_token()?;
+    store.set_setting(LOCAL_API_TOKEN_KEY, &token)?;
+    Ok(token)
+}
+
+fn status(store: &SkillStore, state: &LocalApiState) -> anyhow::Result<LocalApiStatus> {
+    let running = state.running_port();
+    Ok(LocalApiStatus {
+        enabled: enabled(store),
+        running: running.is_some(),
+        port: running.unwrap_or_else(|| configured_port(store)),
+        url: running.map(|port| format!("http://127.0.0.1:{}", port)),
+        token: token(store)?,
+    })
+}
+
+/// Start the server at launch when it was left enabled. A port that is taken is logged and
+/// the app starts without it.
+pub fn start_if_enabled<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    state: &LocalApiState,
+) {
+    if !enabled(store) {
+        return;
+    }
+    if let Err(err) = token(store).and_then(|_| state.start(app, store, configured_port(store))) {
+        log::warn!("[local-api] not started: {:#}", err);
+    }
+}
+
+#[tauri::command]
+pub async fn get_local_api_status(
+    store: State<'_, SkillStore>,
+    state: State<'_, LocalApiState>,
+) -> Result<LocalApiStatus, SkillsHubError> {
+    let (store, state) = (store.inner().clone(), state.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || status(&store, &state))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Turn the localhost API on or off; `port` changes where it listens (0 picks a free port).
+#[tauri::command]
+pub async fn set_local_api_enabled(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    state: State<'_, LocalApiState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<LocalApiStatus, SkillsHubError> {
+    let (store, state) = (store.inner().clone(), state.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut port = port.unwrap_or_else(|| configured_port(&store));
+        if enabled {
+            token(&store)?;
+            port = state.start(&app, &store, port)?;
+        } else {
+            state.stop();
+        }
+        store.set_setting(LOCAL_API_PORT_KEY, &port.to_string())?;
+        store.set_setting(
+            LOCAL_API_ENABLED_KEY,
+            if enabled { "true" } else { "false" },
+        )?;
+        status(&store, &state)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Replace the token; clients holding the old one are refused from the next request on.
+#[tauri::command]
+pub async fn regenerate_local_api_token(
+    store: State<'_, SkillStore>,
+    state: State<'_, LocalApiState>,
+) -> Result<LocalApiStatus, SkillsHubError> {
+    let (store, state) = (store.inner().clone(), state.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        store.set_setting(LOCAL_API_TOKEN_KEY, &new_token()?)?;
+        status(&store, &state)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ApiRequest {
+    pub method: String,
+    pub path: String,
+    /// Names lowercased.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl ApiRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, code: &str, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": SkillsHubError::new(code, message) }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct InstallBody {
+    /// A local directory or a git URL.
+    source: String,
+    name: Option<String>,
+    /// Folder inside a git repository; pick it when the repository holds several skills.
+    subpath: Option<String>,
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    on_conflict: OnConflict,
+}
+
+#[derive(Deserialize)]
+struct SyncBody {
+    tool: String,
+    #[serde(default)]
+    overwrite: bool,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Default, Deserialize)]
+struct UpdateBody {
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Deserialize)]
+struct BatchBody {
+    steps: Vec<BatchStep>,
+    #[serde(default)]
+    policy: BatchPolicy,
+}
+
+fn serve_connection<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    stream: &mut TcpStream,
+) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let response = match read_request(stream) {
+        Ok(request) => handle_request(app, store, &request),
+        Err(err) => ApiResponse::error(400, "BAD_REQUEST", &err.to_string()),
+    };
+    write_response(stream, &response);
+}
+
+fn write_response(stream: &mut TcpStream, response: &ApiResponse) {
+    let body = response.body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    let _ = stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body.as_bytes()));
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Just enough HTTP/1.1 for JSON clients: request line, headers, `Content-Length` body.
+fn read_request(stream: &mut TcpStream) -> anyhow::Result<ApiRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("malformed request line");
+    };
+    let mut request = ApiRequest {
+        method: method.to_string(),
+        path: target.split('?').next().unwrap_or(target).to_string(),
+        ..ApiRequest::default()
+    };
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if request.headers.len() == MAX_HEADER_LINES {
+            anyhow::bail!("too many headers");
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            request
+                .headers
+                .push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let length: usize = request
+        .header("content-length")
+        .map(|v| v.parse())
+        .transpose()?
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        anyhow::bail!("body larger than {} bytes", MAX_BODY_BYTES);
+    }
+    request.body = vec![0; length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+/// Same-length comparison that does not stop at the first differing byte.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Authenticate and dispatch one request. Only `localhost`/`127.0.0.1` Host headers are served,
+/// so a web page cannot reach the API through DNS rebinding.
+pub(super) fn handle_request<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    request: &ApiRequest,
+) -> ApiResponse {
+    let host = request.header("host").unwrap_or_default();
+    let host = host.rsplit_once(':').map_or(host, |(name, _)| name);
+    if !matches!(host, "127.0.0.1" | "localhost") {
+        return ApiResponse::error(403, "FORBIDDEN", "only localhost requests are served");
+    }
+    let expected = match token(store) {
+        Ok(token) => token,
+        Err(err) => return ApiResponse::error(500, "INTERNAL", &format!("{:#}", err)),
+    };
+    let given = request
+        .header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !token_matches(given.trim(), &expected) {
+        return ApiResponse::error(401, "UNAUTHORIZED", "missing or wrong bearer token");
+    }
+
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "skills"]) => {
+            return match get_managed_skills_impl(store, SkillProjection::Full) {
+                Ok(skills) => ApiResponse::ok(json!(skills)),
+                Err(err) => ApiResponse::error(500, "INTERNAL", &err),
+            };
+        }
+        ("POST", ["v1", "skills"]) => parse_body(request).map(|body: InstallBody| {
+            let local = std::path::Path::new(&body.source).exists();
+            vec![match body.subpath {
+                _ if local => BatchStep::InstallLocal {
+                    source_path: body.source,
+                    name: body.name,
+                    force: body.force,
+                    on_conflict: body.on_conflict,
+                },
+                Some(subpath) => BatchStep::InstallGitSelection {
+                    repo_url: body.source,
+                    subpath,
+                    name: body.name,
+                    force: body.force,
+                    on_conflict: body.on_conflict,
+                },
+                None => BatchStep::InstallGit {
+                    repo_url: body.source,
+                    name: body.name,
+                    force: body.force,
+                    on_conflict: body.on_conflict,
+                },
+            }]
+        }),
+        ("POST", ["v1", "skills", id, "sync"]) => parse_body(request).map(|body: SyncBody| {
+            vec![BatchStep::SyncToTool {
+                skill: SkillRef::Id(id.to_string()),
+                tool: body.tool,
+                overwrite: body.overwrite,
+                force: body.force,
+            }]
+        }),
+        ("POST", ["v1", "skills", id, "update"]) => {
+            let body = if request.body.is_empty() {
+                Ok(UpdateBody::default())
+            } else {
+                parse_body(request)
+            };
+            body.map(|body| {
+                vec![BatchStep::Update {
+                    skill: SkillRef::Id(id.to_string()),
+                    force: body.force,
+                }]
+            })
+        }
+        ("POST", ["v1", "batch"]) => {
+            return match parse_body::<BatchBody>(request) {
+                Ok(body) => match run_batch_blocking(app, store, &body.steps, body.policy) {
+                    Ok(result) => ApiResponse::ok(json!(result)),
+                    Err(err) => failed(SkillsHubError::from(err)),
+                },
+                Err(response) => response,
+            };
+        }
+        (_, ["v1", "skills"])
+        | (_, ["v1", "skills", _, "sync" | "update"])
+        | (_, ["v1", "batch"]) => {
+            return ApiResponse::error(405, "METHOD_NOT_ALLOWED", &request.method)
+        }
+        _ => return ApiResponse::error(404, "NOT_FOUND", &request.path),
+    };
+    let steps = match result {
+        Ok(steps) => steps,
+        Err(response) => return response,
+    };
+    run_single(app, store, &steps)
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(request: &ApiRequest) -> Result<T, ApiResponse> {
+    serde_json::from_slice(&request.body)
+        .map_err(|err| ApiResponse::error(400, "BAD_REQUEST", &err.to_string()))
+}
+
+fn failed(error: SkillsHubError) -> ApiResponse {
+    ApiResponse {
+        status: 422,
+        body: json!({ "error": error }),
+    }
+}
+
+/// Run one step the way `run_batch` would and answer with that command's result or error.
+fn run_single<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    steps: &[BatchStep],
+) -> ApiResponse {
+    let step = match run_batch_blocking(app, store, steps, BatchPolicy::StopOnError) {
+        Ok(mut result) => result.steps.remove(0),
+        Err(err) => return failed(SkillsHubError::from(err)),
+    };
+    match (step.state, step.error) {
+        (StepState::Succeeded, _) => ApiResponse::ok(step.result.unwrap_or(Value::Null)),
+        (_, Some(error)) => failed(error),
+        _ => ApiResponse::error(500, "INTERNAL", "step did not run"),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/local_api.rs"]
+mod tests;
@@ -1,96 +1,206 @@
+use std::collections::BTreeSet;
+
 use anyhow::Context;
 use serde::Serialize;
 use tauri::State;
 
+use self::batch::{run_batch_blocking, BatchPolicy, BatchResult, BatchStep, SkillRef};
+use crate::core::app_logs::{log_file_path, read_recent_logs, RecentLogs};
+use crate::core::app_stats::{collect_app_stats, AppStats};
+use crate::core::background_mode::{
+    background_mode_status, set_background_mode as set_background_mode_core, BackgroundLaunch,
+    BackgroundModeStatus,
+};
+use crate::core::backup::{
+    create_backup as create_backup_core, restore_backup as restore_backup_core, BackupManifest,
+    RestoreReport, RestoreStrategy,
+};
 use crate::core::cache_cleanup::{
     cleanup_git_cache_dirs, get_git_cache_cleanup_days as get_git_cache_cleanup_days_core,
+    get_git_cache_cleanup_delay_secs as get_git_cache_cleanup_delay_secs_core,
     get_git_cache_ttl_secs as get_git_cache_ttl_secs_core,
     set_git_cache_cleanup_days as set_git_cache_cleanup_days_core,
+    set_git_cache_cleanup_delay_secs as set_git_cache_cleanup_delay_secs_core,
     set_git_cache_ttl_secs as set_git_cache_ttl_secs_core,
 };
-use crate::core::central_repo::{ensure_central_repo, resolve_central_repo_path};
+use crate::core::central_git::{
+    get_central_git_enabled as get_central_git_enabled_core,
+    get_central_remote as get_central_remote_core, pull_central_repo as pull_central_repo_core,
+    push_central_repo as push_central_repo_core,
+    set_central_git_enabled as set_central_git_enabled_core,
+    set_central_remote as set_central_remote_core, PullReport,
+};
+use crate::core::central_repo::{
+    add_central_repo as add_central_repo_core, ensure_central_repo,
+    list_central_repos as list_central_repos_core, move_central_repo as move_central_repo_core,
+    move_skill_to_repo as move_skill_to_repo_core, remove_central_repo as remove_central_repo_core,
+    resolve_central_repo_path, scan_central_repo as scan_central_repo_core,
+    set_install_repo as set_install_repo_core, CentralRepoMove, CentralRepoView, OrphanDir,
+};
+use crate::core::claude_plugins::{
+    install_plugin_package as install_plugin_package_core, PluginImportReport,
+};
+use crate::core::clipboard::{
+    parse_install_text, read_clipboard, ClipboardInstall, ClipboardWatcher,
+};
+use crate::core::cloud_sync::{scan_cloud_sync as scan_cloud_sync_core, CloudSyncReport};
+use crate::core::content_hash::{
+    get_hash_max_file_mb as get_hash_max_file_mb_core,
+    set_hash_max_file_mb as set_hash_max_file_mb_core,
+};
+use crate::core::cursor_rules::{
+    import_cursor_rules as import_cursor_rules_core, RuleImportReport,
+};
+use crate::core::db_encryption::{encryption_status, EncryptionStatus};
+use crate::core::deep_link::{DeepLinkInstall, DeepLinkQueue};
+use crate::core::deployment_report::{
+    export_deployment_report as export_deployment_report_core, ReportFormat,
+};
+use crate::core::disk_usage::{cached_disk_usage, largest_skills, SkillDiskUsage};
+use crate::core::doctor::{audit_dir_access, check_github, run_local_checks, DoctorReport};
+use crate::core::environments::{
+    delete_environment as delete_environment_core, list_environments as list_environments_core,
+    save_environment as save_environment_core, switch_environment as switch_environment_core,
+    Environment, EnvironmentList, EnvironmentSettings,
+};
+use crate::core::git_fetcher::{RemoteBranch, RemoteTag};
+use crate::core::git_host_policy::{
+    get_git_host_policy as get_git_host_policy_core,
+    set_git_host_policy as set_git_host_policy_core, GitHostPolicy,
+};
 use crate::core::github_search::{search_github_repos, RepoSummary};
 use crate::core::installer::{
-    install_git_skill, install_git_skill_from_selection, install_local_skill,
-    install_local_skill_from_selection, list_git_skills, list_local_skills,
-    update_managed_skill_from_source, GitSkillCandidate, InstallResult, LocalSkillCandidate,
+    adopt_orphan as adopt_orphan_core, classify_drop_path,
+    get_normalize_on_install as get_normalize_on_install_core,
+    get_skill_changelog as get_skill_changelog_core, git_url_with_branch, has_rollback_snapshot,
+    install_git_skill_from_selection_with_options, install_git_skill_with_options,
+    install_local_skill_from_selection_with_options, install_local_skill_with_options,
+    list_git_branches as list_git_branches_core, list_git_skills,
+    list_git_tags as list_git_tags_core, list_local_skills,
+    migrate_to_successor as migrate_to_successor_core, normalize_skill as normalize_skill_core,
+    record_change, remove_rollback_snapshot, rollback_update as rollback_update_core,
+    set_normalize_on_install as set_normalize_on_install_core,
+    update_managed_skill_from_source_with_force,
+    update_skill_metadata as update_skill_metadata_core,
+    write_skill_content as write_skill_content_core, DropPathInfo, GitSkillCandidate,
+    InstallOptions, InstallResult, LocalSkillCandidate, OnConflict, SkillChangelog,
+    SuccessorMigration, UpdateResult,
+};
+use crate::core::locale::{
+    get_app_language as get_app_language_core, get_backend_locale as get_backend_locale_core,
+    set_app_language as set_app_language_core, set_backend_locale as set_backend_locale_core,
+};
+use crate::core::lockfile::{
+    apply_lock, export_lock, read_lock, ApplyOptions, ApplyReport, SkillsLock,
+};
+use crate::core::machine_overrides::{
+    set_machine_override as set_machine_override_core, MachineOverrides,
+};
+use crate::core::onboarding::{
+    build_onboarding_plan, execute_onboarding_plan as execute_onboarding_plan_core,
+    get_project_roots as get_project_roots_core,
+    import_existing_skill as import_existing_skill_core, scan_tools_now as scan_tools_now_core,
+    set_project_roots as set_project_roots_core, DiscoveryScan, OnboardingImportResult,
+    OnboardingPlan, OnboardingSelection,
+};
+use crate::core::operations::{
+    spawn_operation, OperationHandle, OperationRegistry, OperationStatus,
+};
+use crate::core::profiles::{
+    export_profile as export_profile_core, import_profile as import_profile_core,
+    list_profiles as list_profiles_core, plan_profile_activation, preview_profile_import,
+    read_profile_file, save_profile as save_profile_core, ProfileFile, ProfileImportPreview,
+    ProfileImportReport, ProfileView,
+};
+use crate::core::project_config::{
+    apply_project_config as apply_project_config_core, scan_project as scan_project_core,
+    ProjectApplyReport, ProjectScan,
+};
+use crate::core::reveal::{open_terminal, resolve_reveal_path};
+use crate::core::scheduler::{JobStatus, Scheduler};
+use crate::core::self_repair::{SelfRepairState, SelfRepairSummary};
+use crate::core::setup_snapshots::{
+    create_setup_snapshot as create_setup_snapshot_core,
+    delete_setup_snapshot as delete_setup_snapshot_core,
+    diff_setup_snapshots as diff_setup_snapshots_core,
+    list_setup_snapshots as list_setup_snapshots_core, SetupDiff, SetupSnapshot,
+};
+use crate::core::skill_convert::{
+    convert_skill as convert_skill_core, render_skill_snippet, ConvertedSkill, SkillFormat,
+    SkillSnippet, SNIPPET_DEFAULT_MAX_BYTES,
+};
+use crate::core::skill_deps::{
+    check_dependency_health as check_dependency_health_core, install_missing_dependencies,
+    record_dependencies_after_install, DependencyOutcome, DependencyViolation,
+};
+use crate::core::skill_diff::{diff_skill_dirs, SkillDiff};
+use crate::core::skill_executables::{
+    ensure_sync_allowed, get_executable_policy as get_executable_policy_core,
+    set_executable_policy as set_executable_policy_core,
+};
+use crate::core::skill_export::{export_skill_archive, ExportManifest};
+use crate::core::skill_files::{
+    list_skill_files as list_skill_files_core, read_skill_file, SkillFileContent, SkillFileEntry,
+};
+use crate::core::skill_grep::{grep_skills as grep_skills_core, GrepOptions, GrepResult};
+use crate::core::skill_license::is_permissive;
+use crate::core::skill_lint::{lint_skill_dir, LintReport};
+use crate::core::skill_lock::lock_skill;
+use crate::core::skill_metadata::SkillMetadataPatch;
+use crate::core::skill_signing::{
+    get_signing_policy as get_signing_policy_core, set_signing_policy as set_signing_policy_core,
+    sign_skill_dir, signing_key_path, SigningKeyPair, SigningPolicy,
+};
+use crate::core::skill_store::{
+    CentralRepoRecord, JobRunRecord, MachineOverrideRecord, ProfileRecord, SkillProjection,
+    SkillStore, SkillTargetRecord, WorkspaceRecord,
+};
+use crate::core::skill_sync::set_target_enabled;
+use crate::core::skill_templates::{
+    add_template_source as add_template_source_core, create_skill as create_skill_core,
+    is_git_source, list_skill_templates as list_skill_templates_core,
+    list_template_sources as list_template_sources_core,
+    remove_template_source as remove_template_source_core, CreateSkillOptions, SkillTemplateInfo,
+};
+use crate::core::sync_coalesce::{SyncCoalescer, DEFAULT_SYNC_DEBOUNCE};
+use crate::core::sync_engine::{sync_dir_for_tool_with_overwrite, sync_dir_hybrid};
+use crate::core::team_profiles::{
+    check_team_profile as check_team_profile_core,
+    get_team_profile_check_hours as get_team_profile_check_hours_core,
+    list_team_profiles as list_team_profiles_core,
+    reconcile_team_profile as reconcile_team_profile_core,
+    set_team_profile_check_hours as set_team_profile_check_hours_core,
+    track_team_profile as track_team_profile_core, TeamProfileStatus,
+};
+use crate::core::token_estimate::{
+    context_budget, estimate_skill_tokens as estimate_skill_tokens_core,
+    get_context_budget_tokens as get_context_budget_tokens_core,
+    set_context_budget_tokens as set_context_budget_tokens_core, tool_token_totals,
+    ContextBudgetReport, TokenEstimate, ToolTokenTotal,
+};
+use crate::core::tool_adapters::{
+    adapter_by_key, installed_tool_dir, is_tool_installed, resolve_default_path, ToolAdapter,
+};
+use crate::core::tray::{
+    check_skill_updates, quick_sync_all as quick_sync_all_core, tray_summary, QuickSyncReport,
+    TraySummary, UpdateCheck,
+};
+use crate::core::url_skill::install_url_skill as install_url_skill_core;
+use crate::core::webhooks::{
+    get_webhook_urls as get_webhook_urls_core, notify_webhooks,
+    set_webhook_urls as set_webhook_urls_core, WebhookEvent,
 };
-use crate::core::onboarding::{build_onboarding_plan, OnboardingPlan};
-use crate::core::skill_store::{SkillStore, SkillTargetRecord};
-use crate::core::sync_engine::{
-    copy_dir_recursive, sync_dir_for_tool_with_overwrite, sync_dir_hybrid, SyncMode,
+use crate::core::workspaces::{
+    add_workspace as add_workspace_core,
+    apply_profile_to_workspace as apply_profile_to_workspace_core,
+    list_workspaces as list_workspaces_core, WorkspaceProfileReport, WorkspaceView,
 };
-use crate::core::tool_adapters::{adapter_by_key, is_tool_installed, resolve_default_path};
 use uuid::Uuid;
 
-fn format_anyhow_error(err: anyhow::Error) -> String {
-    let first = err.to_string();
-    // Frontend relies on these prefixes for special flows.
-    if first.starts_with("MULTI_SKILLS|")
-        || first.starts_with("TARGET_EXISTS|")
-        || first.starts_with("TOOL_NOT_INSTALLED|")
-    {
-        return first;
-    }
-
-    // Include the full error chain (causes), not just the top context.
-    let mut full = format!("{:#}", err);
-
-    // Redact noisy temp paths from clone context (we care about the cause, not the dest).
-    // Example: `clone https://... into "/Users/.../skills-hub-git-<uuid>"`
-    if let Some(head) = full.lines().next() {
-        if head.starts_with("clone ") {
-            if let Some(pos) = head.find(" into ") {
-                let head_redacted = format!("{} (已省略临时目录)", &head[..pos]);
-                let rest: String = full.lines().skip(1).collect::<Vec<_>>().join("\n");
-                full = if rest.is_empty() {
-                    head_redacted
-                } else {
-                    format!("{}\n{}", head_redacted, rest)
-                };
-            }
-        }
-    }
-
-    let root = err.root_cause().to_string();
-    let lower = full.to_lowercase();
-
-    // Heuristic-friendly messaging for GitHub clone failures.
-    if lower.contains("github.com")
-        && (lower.contains("clone ") || lower.contains("remote") || lower.contains("fetch"))
-    {
-        if lower.contains("securetransport") {
-            return format!(
-        "无法从 GitHub 拉取仓库：TLS/证书校验失败（macOS SecureTransport）。\n\n建议：\n- 检查网络/代理是否拦截 HTTPS\n- 如在公司网络，可能需要安装公司根证书或使用可信代理\n- 也可在终端确认 `git clone {}` 是否可用\n\n详细：{}",
-        "https://github.com/<owner>/<repo>",
-        root
-      );
-        }
-        let hint = if lower.contains("authentication")
-            || lower.contains("permission denied")
-            || lower.contains("credentials")
-        {
-            "无法访问该仓库：可能是私有仓库/权限不足/需要鉴权。"
-        } else if lower.contains("not found") {
-            "仓库不存在或无权限访问（GitHub 返回 not found）。"
-        } else if lower.contains("failed to resolve")
-            || lower.contains("could not resolve")
-            || lower.contains("dns")
-        {
-            "无法解析 GitHub 域名（DNS）。请检查网络/代理。"
-        } else if lower.contains("timed out") || lower.contains("timeout") {
-            "连接 GitHub 超时。请检查网络/代理。"
-        } else if lower.contains("connection refused") || lower.contains("connection reset") {
-            "连接 GitHub 失败（连接被拒绝/重置）。请检查网络/代理。"
-        } else {
-            "无法从 GitHub 拉取仓库。请检查网络/代理，或稍后重试。"
-        };
-
-        return format!("{}\n\n详细：{}", hint, root);
-    }
-
-    full
-}
+pub mod batch;
+mod error;
+pub mod local_api;
+pub use error::SkillsHubError;
 
 #[derive(Debug, Serialize)]
 pub struct ToolInfoDto {
@@ -108,9 +218,13 @@ pub struct ToolStatusDto {
 }
 
 #[tauri::command]
-pub async fn get_tool_status(store: State<'_, SkillStore>) -> Result<ToolStatusDto, String> {
+pub async fn get_tool_status(
+    store: State<'_, SkillStore>,
+) -> Result<ToolStatusDto, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
+        // The status view is where users look after installing a tool, so always re-check.
+        crate::core::tool_adapters::invalidate_tool_install_cache();
         let adapters = crate::core::tool_adapters::default_tool_adapters();
         let mut tools: Vec<ToolInfoDto> = Vec::new();
         let mut installed: Vec<String> = Vec::new();
@@ -158,75 +272,188 @@ pub async fn get_tool_status(store: State<'_, SkillStore>) -> Result<ToolStatusD
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 pub async fn get_onboarding_plan(
     app: tauri::AppHandle,
     store: State<'_, SkillStore>,
-) -> Result<OnboardingPlan, String> {
+) -> Result<OnboardingPlan, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || build_onboarding_plan(&app, &store))
         .await
         .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn execute_onboarding_plan(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    selections: Vec<OnboardingSelection>,
+) -> Result<Vec<OnboardingImportResult>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        execute_onboarding_plan_core(&app, &store, &selections)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_project_roots(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(
+            get_project_roots_core(&store)
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn set_project_roots(
+    store: State<'_, SkillStore>,
+    roots: Vec<String>,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let roots = roots
+            .iter()
+            .map(|r| expand_home_path(r))
+            .collect::<Result<Vec<_>, _>>()?;
+        let saved = set_project_roots_core(&store, roots)?;
+        Ok::<_, anyhow::Error>(
+            saved
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn scan_tools_now(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<DiscoveryScan, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || scan_tools_now_core(&app, &store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn diff_onboarding_variants(
+    pathA: String,
+    pathB: String,
+) -> Result<SkillDiff, SkillsHubError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        diff_skill_dirs(std::path::Path::new(&pathA), std::path::Path::new(&pathB))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-pub async fn get_git_cache_cleanup_days(store: State<'_, SkillStore>) -> Result<i64, String> {
+pub async fn get_git_cache_cleanup_days(
+    store: State<'_, SkillStore>,
+) -> Result<i64, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         Ok::<_, anyhow::Error>(get_git_cache_cleanup_days_core(&store))
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 pub async fn set_git_cache_cleanup_days(
     store: State<'_, SkillStore>,
     days: i64,
-) -> Result<i64, String> {
+) -> Result<i64, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || set_git_cache_cleanup_days_core(&store, days))
         .await
         .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-pub async fn clear_git_cache_now(app: tauri::AppHandle) -> Result<usize, String> {
+pub async fn get_git_cache_cleanup_delay_secs(
+    store: State<'_, SkillStore>,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_git_cache_cleanup_delay_secs_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Seconds after launch before stale git caches are cleaned up; takes effect without restart.
+#[tauri::command]
+pub async fn set_git_cache_cleanup_delay_secs(
+    store: State<'_, SkillStore>,
+    secs: i64,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        set_git_cache_cleanup_delay_secs_core(&store, secs)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn clear_git_cache_now(app: tauri::AppHandle) -> Result<usize, SkillsHubError> {
     tauri::async_runtime::spawn_blocking(move || {
         cleanup_git_cache_dirs(&app, std::time::Duration::from_secs(0))
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-pub async fn get_git_cache_ttl_secs(store: State<'_, SkillStore>) -> Result<i64, String> {
+pub async fn get_git_cache_ttl_secs(store: State<'_, SkillStore>) -> Result<i64, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         Ok::<_, anyhow::Error>(get_git_cache_ttl_secs_core(&store))
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 pub async fn set_git_cache_ttl_secs(
     store: State<'_, SkillStore>,
     secs: i64,
-) -> Result<i64, String> {
+) -> Result<i64, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || set_git_cache_ttl_secs_core(&store, secs))
         .await
         .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(SkillsHubError::from)
 }
 
 #[derive(Debug, Serialize)]
@@ -235,6 +462,8 @@ pub struct InstallResultDto {
     pub name: String,
     pub central_path: String,
     pub content_hash: Option<String>,
+    /// Declared dependencies that no managed skill satisfies yet.
+    pub missing_dependencies: Vec<String>,
 }
 
 fn expand_home_path(input: &str) -> Result<std::path::PathBuf, anyhow::Error> {
@@ -257,7 +486,7 @@ fn expand_home_path(input: &str) -> Result<std::path::PathBuf, anyhow::Error> {
 pub async fn get_central_repo_path(
     app: tauri::AppHandle,
     store: State<'_, SkillStore>,
-) -> Result<String, String> {
+) -> Result<String, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let path = resolve_central_repo_path(&app, &store)?;
@@ -266,7 +495,7 @@ pub async fn get_central_repo_path(
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
@@ -274,58 +503,136 @@ pub async fn set_central_repo_path(
     app: tauri::AppHandle,
     store: State<'_, SkillStore>,
     path: String,
-) -> Result<String, String> {
+) -> Result<String, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let new_base = expand_home_path(&path)?;
-        if !new_base.is_absolute() {
-            anyhow::bail!("storage path must be absolute");
-        }
-        ensure_central_repo(&new_base)?;
+        Ok::<_, anyhow::Error>(move_central_repo_core(&app, &store, &new_base, now_ms())?.path)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
 
-        let current_base = resolve_central_repo_path(&app, &store)?;
-        let skills = store.list_skills()?;
-        if current_base == new_base {
-            store.set_setting("central_repo_path", new_base.to_string_lossy().as_ref())?;
-            return Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string());
-        }
+/// Like `set_central_repo_path`, with the report of what was moved.
+#[tauri::command]
+pub async fn move_central_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    path: String,
+) -> Result<CentralRepoMove, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let new_base = expand_home_path(&path)?;
+        move_central_repo_core(&app, &store, &new_base, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
 
-        if !skills.is_empty() {
-            for skill in skills {
-                let old_path = std::path::PathBuf::from(&skill.central_path);
-                if !old_path.exists() {
-                    anyhow::bail!("central path not found: {:?}", old_path);
-                }
-                let file_name = old_path
-                    .file_name()
-                    .ok_or_else(|| anyhow::anyhow!("invalid central path: {:?}", old_path))?;
-                let new_path = new_base.join(file_name);
-                if new_path.exists() {
-                    anyhow::bail!("target path already exists: {:?}", new_path);
-                }
+#[tauri::command]
+pub async fn list_central_repos(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<Vec<CentralRepoView>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_central_repos_core(&app, &store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
 
-                if let Err(err) = std::fs::rename(&old_path, &new_path) {
-                    copy_dir_recursive(&old_path, &new_path)
-                        .with_context(|| format!("copy {:?} -> {:?}", old_path, new_path))?;
-                    std::fs::remove_dir_all(&old_path)
-                        .with_context(|| format!("cleanup {:?}", old_path))?;
-                    // Surface rename error in logs for troubleshooting.
-                    eprintln!("rename failed, fallback used: {}", err);
-                }
+#[tauri::command]
+pub async fn add_central_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    name: String,
+    path: String,
+) -> Result<CentralRepoRecord, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = expand_home_path(&path)?;
+        add_central_repo_core(&app, &store, &name, &path, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
 
-                let mut updated = skill.clone();
-                updated.central_path = new_path.to_string_lossy().to_string();
-                updated.updated_at = now_ms();
-                store.upsert_skill(&updated)?;
-            }
-        }
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn remove_central_repo(
+    store: State<'_, SkillStore>,
+    repoId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || remove_central_repo_core(&store, &repoId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Repo new installs go to; `default` is the one at `central_repo_path`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_install_repo(
+    store: State<'_, SkillStore>,
+    repoId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_install_repo_core(&store, &repoId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn move_skill_to_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+    repoId: String,
+) -> Result<CentralRepoMove, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        move_skill_to_repo_core(&app, &store, &skillId, &repoId, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn scan_central_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<Vec<OrphanDir>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || scan_central_repo_core(&app, &store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
 
-        store.set_setting("central_repo_path", new_base.to_string_lossy().as_ref())?;
-        Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string())
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn adopt_orphan(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    name: String,
+    repoId: Option<String>,
+) -> Result<InstallResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = adopt_orphan_core(&app, &store, &name, repoId.as_deref())?;
+        let missing = missing_dependencies_after_install(&store, &result.skill_id);
+        Ok::<_, anyhow::Error>(to_install_dto(result, missing))
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
@@ -335,27 +642,44 @@ pub async fn install_local(
     store: State<'_, SkillStore>,
     sourcePath: String,
     name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<InstallResultDto, SkillsHubError> {
     let store = store.inner().clone();
+    let options = install_options(force, onConflict);
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_local_skill(&app, &store, sourcePath.as_ref(), name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        install_local_impl(&app, &store, &sourcePath, name, options)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn list_local_skills_cmd(basePath: String) -> Result<Vec<LocalSkillCandidate>, String> {
+pub async fn list_local_skills_cmd(
+    basePath: String,
+) -> Result<Vec<LocalSkillCandidate>, SkillsHubError> {
     tauri::async_runtime::spawn_blocking(move || {
         let path = std::path::PathBuf::from(basePath);
         list_local_skills(&path)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
+}
+
+/// Classify drag-and-dropped paths so the UI can pick the matching install flow.
+#[tauri::command]
+pub async fn validate_drop_paths(paths: Vec<String>) -> Result<Vec<DropPathInfo>, SkillsHubError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .iter()
+            .map(|path| classify_drop_path(std::path::Path::new(path)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|err| err.to_string().into())
 }
 
 #[tauri::command]
@@ -366,17 +690,27 @@ pub async fn install_local_selection(
     basePath: String,
     subpath: String,
     name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<InstallResultDto, SkillsHubError> {
     let store = store.inner().clone();
+    let options = install_options(force, onConflict);
     tauri::async_runtime::spawn_blocking(move || {
         let base = std::path::PathBuf::from(basePath);
-        let result =
-            install_local_skill_from_selection(&app, &store, base.as_ref(), &subpath, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        let result = install_local_skill_from_selection_with_options(
+            &app,
+            &store,
+            base.as_ref(),
+            &subpath,
+            name,
+            options,
+        )?;
+        let missing = missing_dependencies_after_install(&store, &result.skill_id);
+        Ok::<_, anyhow::Error>(to_install_dto(result, missing))
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
@@ -386,15 +720,19 @@ pub async fn install_git(
     store: State<'_, SkillStore>,
     repoUrl: String,
     name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+    branch: Option<String>,
+) -> Result<InstallResultDto, SkillsHubError> {
     let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    let repoUrl = repo_url_on_branch(repoUrl, branch);
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_git_skill(&app, &store, &repoUrl, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        install_git_impl(&app, &store, &repoUrl, name, options)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
@@ -403,34 +741,87 @@ pub async fn list_git_skills_cmd(
     app: tauri::AppHandle,
     store: State<'_, SkillStore>,
     repoUrl: String,
-) -> Result<Vec<GitSkillCandidate>, String> {
+    branch: Option<String>,
+) -> Result<Vec<GitSkillCandidate>, SkillsHubError> {
     let store = store.inner().clone();
+    let repoUrl = repo_url_on_branch(repoUrl, branch);
     tauri::async_runtime::spawn_blocking(move || list_git_skills(&app, &store, &repoUrl))
         .await
         .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::too_many_arguments)]
 pub async fn install_git_selection(
     app: tauri::AppHandle,
     store: State<'_, SkillStore>,
     repoUrl: String,
     subpath: String,
     name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+    branch: Option<String>,
+) -> Result<InstallResultDto, SkillsHubError> {
     let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    let repoUrl = repo_url_on_branch(repoUrl, branch);
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_git_skill_from_selection(&app, &store, &repoUrl, &subpath, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        install_git_selection_impl(&app, &store, &repoUrl, &subpath, name, options)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
-#[derive(Debug, Serialize)]
+/// Branches of `repoUrl`, default first, so the install dialog can offer a branch picker.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_git_branches(
+    store: State<'_, SkillStore>,
+    repoUrl: String,
+) -> Result<Vec<RemoteBranch>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_git_branches_core(&store, &repoUrl))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Tags of `repoUrl`, newest first; one can be passed as `branch` to install that release.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_git_tags(
+    store: State<'_, SkillStore>,
+    repoUrl: String,
+) -> Result<Vec<RemoteTag>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_git_tags_core(&store, &repoUrl))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn lint_skill(
+    store: State<'_, SkillStore>,
+    pathOrId: String,
+) -> Result<LintReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = match store.get_skill_by_id(&pathOrId)? {
+            Some(skill) => std::path::PathBuf::from(skill.central_path),
+            None => expand_home_path(&pathOrId)?,
+        };
+        lint_skill_dir(&path)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct SyncResultDto {
     pub mode_used: String,
     pub target_path: String,
@@ -440,27 +831,24 @@ pub struct SyncResultDto {
 pub async fn sync_skill_dir(
     source_path: String,
     target_path: String,
-) -> Result<SyncResultDto, String> {
+) -> Result<SyncResultDto, SkillsHubError> {
     tauri::async_runtime::spawn_blocking(move || {
         let result = sync_dir_hybrid(source_path.as_ref(), target_path.as_ref())?;
         Ok::<_, anyhow::Error>(SyncResultDto {
-            mode_used: match result.mode_used {
-                SyncMode::Auto => "auto",
-                SyncMode::Symlink => "symlink",
-                SyncMode::Junction => "junction",
-                SyncMode::Copy => "copy",
-            }
-            .to_string(),
+            mode_used: result.mode_used.as_key().to_string(),
             target_path: result.target_path.to_string_lossy().to_string(),
         })
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
+/// Repeated syncs of a skill to a tool, e.g. from quick UI clicks, collapse into one pass.
+static SYNC_COALESCER: SyncCoalescer<SyncResultDto> = SyncCoalescer::new(DEFAULT_SYNC_DEBOUNCE);
+
 #[tauri::command]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::too_many_arguments)]
 pub async fn sync_skill_to_tool(
     store: State<'_, SkillStore>,
     sourcePath: String,
@@ -468,116 +856,240 @@ pub async fn sync_skill_to_tool(
     tool: String,
     name: String,
     overwrite: Option<bool>,
-) -> Result<SyncResultDto, String> {
+    force: Option<bool>,
+    allowExecutables: Option<bool>,
+) -> Result<SyncResultDto, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let adapter = adapter_by_key(&tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
-        if !is_tool_installed(&adapter)? {
-            anyhow::bail!("TOOL_NOT_INSTALLED|{}", adapter.id.as_key());
-        }
-        let tool_root = resolve_default_path(&adapter)?;
-        let target = tool_root.join(&name);
-        let overwrite = overwrite.unwrap_or(false);
-        let result =
-            sync_dir_for_tool_with_overwrite(&tool, sourcePath.as_ref(), &target, overwrite)
-                .map_err(|err| {
-                    let msg = err.to_string();
-                    if msg.contains("target already exists") {
-                        anyhow::anyhow!("TARGET_EXISTS|{}", target.to_string_lossy())
-                    } else {
-                        anyhow::anyhow!(msg)
-                    }
-                })?;
-
-        // Some tools share the same global skills directory; keep DB records consistent across them.
-        let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
-        for a in group {
-            if !is_tool_installed(&a)? {
-                continue;
-            }
-            let record = SkillTargetRecord {
-                id: Uuid::new_v4().to_string(),
-                skill_id: skillId.clone(),
-                tool: a.id.as_key().to_string(),
-                target_path: result.target_path.to_string_lossy().to_string(),
-                mode: match result.mode_used {
-                    SyncMode::Auto => "auto",
-                    SyncMode::Symlink => "symlink",
-                    SyncMode::Junction => "junction",
-                    SyncMode::Copy => "copy",
-                }
-                .to_string(),
-                status: "ok".to_string(),
-                last_error: None,
-                synced_at: Some(now_ms()),
-            };
-            store.upsert_skill_target(&record)?;
-        }
-
-        Ok::<_, anyhow::Error>(SyncResultDto {
-            mode_used: match result.mode_used {
-                SyncMode::Auto => "auto",
-                SyncMode::Symlink => "symlink",
-                SyncMode::Junction => "junction",
-                SyncMode::Copy => "copy",
-            }
-            .to_string(),
-            target_path: result.target_path.to_string_lossy().to_string(),
+        let (overwrite, force) = (overwrite.unwrap_or(false), force.unwrap_or(false));
+        let allow_executables = force || allowExecutables.unwrap_or(false);
+        let key = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            skillId, tool, sourcePath, name, overwrite, force, allow_executables
+        );
+        SYNC_COALESCER.run(&key, || {
+            sync_skill_to_tool_blocking(
+                &store,
+                &sourcePath,
+                &skillId,
+                &tool,
+                &name,
+                overwrite,
+                force,
+                allow_executables,
+            )
         })
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
-#[tauri::command]
-#[allow(non_snake_case)]
-pub async fn unsync_skill_from_tool(
-    store: State<'_, SkillStore>,
-    skillId: String,
-    tool: String,
-) -> Result<(), String> {
-    let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        // Some tools share the same global skills directory; unsync should update all of them.
-        let group_tool_keys: Vec<String> = if let Some(adapter) = adapter_by_key(&tool) {
-            let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
-            // If none of the group tools are installed, do nothing (treat as already not effective).
-            let mut any_installed = false;
-            for a in &group {
-                if is_tool_installed(a)? {
-                    any_installed = true;
-                    break;
-                }
+/// Errors the UI answers with a confirmation and a retry; they are not reported as failures.
+const SYNC_CONFIRMATION_CODES: [&str; 3] = [
+    "TARGET_EXISTS|",
+    "TOOL_INCOMPATIBLE|",
+    "EXECUTABLES_UNCONFIRMED|",
+];
+
+/// `force` skips the compatibility and executables checks; `allow_executables` only the latter,
+/// for a user who confirmed syncing a skill that ships scripts or binaries.
+#[allow(clippy::too_many_arguments)]
+fn sync_skill_to_tool_blocking(
+    store: &SkillStore,
+    source_path: &str,
+    skill_id: &str,
+    tool: &str,
+    name: &str,
+    overwrite: bool,
+    force: bool,
+    allow_executables: bool,
+) -> anyhow::Result<SyncResultDto> {
+    let result = sync_skill_to_tool_checked(
+        store,
+        source_path,
+        skill_id,
+        tool,
+        name,
+        overwrite,
+        force,
+        allow_executables,
+    );
+    if let Err(err) = &result {
+        let message = format!("{:#}", err);
+        if !SYNC_CONFIRMATION_CODES
+            .iter()
+            .any(|code| message.starts_with(code))
+        {
+            notify_webhooks(
+                store,
+                WebhookEvent::sync_failed(skill_id, name, tool, &message),
+            );
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sync_skill_to_tool_checked(
+    store: &SkillStore,
+    source_path: &str,
+    skill_id: &str,
+    tool: &str,
+    name: &str,
+    overwrite: bool,
+    force: bool,
+    allow_executables: bool,
+) -> anyhow::Result<SyncResultDto> {
+    let _lock = lock_skill(skill_id, &format!("sync:{}", tool))?;
+    let adapter = adapter_by_key(tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
+    if !is_tool_installed(&adapter)? {
+        anyhow::bail!("TOOL_NOT_INSTALLED|{}", adapter.id.as_key());
+    }
+    let skill = store.get_skill_by_id(skill_id)?;
+    if !force {
+        if let Some(meta) = skill.as_ref().and_then(|s| s.metadata.as_ref()) {
+            if !meta.supports_tool(adapter.id.as_key()) {
+                anyhow::bail!(
+                    "TOOL_INCOMPATIBLE|{}|{}",
+                    adapter.id.as_key(),
+                    meta.compatible_tools.join(",")
+                );
             }
-            if !any_installed {
-                return Ok::<_, anyhow::Error>(());
+        }
+    }
+    if let Some(skill) = &skill {
+        ensure_sync_allowed(store, skill, force || allow_executables)?;
+    }
+    let target = sync_target_path(store, &adapter, name)?;
+    let result = sync_dir_for_tool_with_overwrite(tool, source_path.as_ref(), &target, overwrite)
+        .map_err(|err| {
+        let msg = err.to_string();
+        if msg.contains("target already exists") {
+            return anyhow::anyhow!("TARGET_EXISTS|{}", target.to_string_lossy());
+        }
+        // Name the permission problem instead of surfacing a bare IO error.
+        match audit_dir_access(&target).filter(|issue| issue.blocks_writes()) {
+            Some(issue) => {
+                anyhow::anyhow!("TARGET_ACCESS|{}|{}", issue.key(), target.to_string_lossy())
             }
-            group
-                .into_iter()
-                .map(|a| a.id.as_key().to_string())
-                .collect()
-        } else {
-            vec![tool.clone()]
+            None => anyhow::anyhow!(msg),
+        }
+    })?;
+
+    // Some tools share the same global skills directory; keep DB records consistent across them.
+    let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
+    for a in group {
+        if !is_tool_installed(&a)? {
+            continue;
+        }
+        let record = SkillTargetRecord {
+            id: Uuid::new_v4().to_string(),
+            skill_id: skill_id.to_string(),
+            tool: a.id.as_key().to_string(),
+            target_path: result.target_path.to_string_lossy().to_string(),
+            mode: result.mode_used.as_key().to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(now_ms()),
         };
+        store.upsert_skill_target(&record)?;
+    }
 
-        // Remove filesystem target once (shared dir => shared target path).
-        let mut removed = false;
-        for k in &group_tool_keys {
-            if let Some(target) = store.get_skill_target(&skillId, k)? {
-                if !removed {
-                    remove_path_any(&target.target_path).map_err(anyhow::Error::msg)?;
-                    removed = true;
-                }
-                store.delete_skill_target(&skillId, k)?;
+    Ok(SyncResultDto {
+        mode_used: result.mode_used.as_key().to_string(),
+        target_path: result.target_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Where `sync_skill_to_tool_blocking` places a skill named `name` for `adapter`: the tool's
+/// skills dir, unless this machine overrides the path.
+fn sync_target_path(
+    store: &SkillStore,
+    adapter: &ToolAdapter,
+    name: &str,
+) -> anyhow::Result<std::path::PathBuf> {
+    let default = resolve_default_path(adapter)?.join(name);
+    Ok(MachineOverrides::load(store)?.target_path(name, adapter.id.as_key(), default))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn unsync_skill_from_tool(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    tool: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        unsync_skill_from_tool_blocking(&store, &skillId, &tool)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+fn unsync_skill_from_tool_blocking(
+    store: &SkillStore,
+    skill_id: &str,
+    tool: &str,
+) -> anyhow::Result<()> {
+    let _lock = lock_skill(skill_id, &format!("unsync:{}", tool))?;
+    // Some tools share the same global skills directory; unsync should update all of them.
+    let group_tool_keys: Vec<String> = if let Some(adapter) = adapter_by_key(tool) {
+        let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
+        // If none of the group tools are installed, do nothing (treat as already not effective).
+        let mut any_installed = false;
+        for a in &group {
+            if is_tool_installed(a)? {
+                any_installed = true;
+                break;
+            }
+        }
+        if !any_installed {
+            return Ok(());
+        }
+        group
+            .into_iter()
+            .map(|a| a.id.as_key().to_string())
+            .collect()
+    } else {
+        vec![tool.to_string()]
+    };
+
+    // Remove filesystem target once (shared dir => shared target path).
+    let mut removed = false;
+    for k in &group_tool_keys {
+        if let Some(target) = store.get_skill_target(skill_id, k)? {
+            if !removed {
+                remove_path_any(&target.target_path).map_err(anyhow::Error::msg)?;
+                removed = true;
             }
+            store.delete_skill_target(skill_id, k)?;
         }
+    }
 
-        Ok::<_, anyhow::Error>(())
+    Ok(())
+}
+
+/// Turn a synced skill off or on for a tool without removing its target; returns the tools
+/// whose target moved.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_skill_target_enabled(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    tool: String,
+    enabled: bool,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _lock = lock_skill(&skillId, &format!("toggle:{}", tool))?;
+        set_target_enabled(&store, &skillId, &tool, enabled)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[derive(Debug, Serialize)]
@@ -591,125 +1103,2018 @@ pub struct UpdateResultDto {
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn update_managed_skill(
-    app: tauri::AppHandle,
+pub async fn update_managed_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+    force: Option<bool>,
+) -> Result<UpdateResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let res = update_managed_skill_from_source_with_force(
+            &app,
+            &store,
+            &skillId,
+            force.unwrap_or(false),
+        )?;
+        Ok::<_, anyhow::Error>(to_update_dto(res))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Run `work` as a background operation; failures are stored as `SkillsHubError`. Clone and
+/// copy progress from the installer is reported on the operation automatically.
+fn start_operation<T, F>(
+    app: &tauri::AppHandle,
+    operations: &OperationRegistry,
+    kind: &str,
+    work: F,
+) -> String
+where
+    T: Serialize,
+    F: FnOnce(&OperationHandle<tauri::Wry>) -> anyhow::Result<T> + Send + 'static,
+{
+    spawn_operation(app, operations, kind, move |op| {
+        work(op).map_err(SkillsHubError::from)
+    })
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn start_install_local(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    operations: State<'_, OperationRegistry>,
+    sourcePath: String,
+    name: Option<String>,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    let handle = app.clone();
+    let options = install_options(force, onConflict);
+    Ok(start_operation(
+        &app,
+        &operations,
+        "install_local",
+        move |_| install_local_impl(&handle, &store, &sourcePath, name, options),
+    ))
+}
+
+#[tauri::command]
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub async fn start_install_git(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    operations: State<'_, OperationRegistry>,
+    repoUrl: String,
+    name: Option<String>,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+    branch: Option<String>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    let handle = app.clone();
+    let options = install_options(force, onConflict);
+    let repoUrl = repo_url_on_branch(repoUrl, branch);
+    Ok(start_operation(
+        &app,
+        &operations,
+        "install_git",
+        move |_| install_git_impl(&handle, &store, &repoUrl, name, options),
+    ))
+}
+
+#[tauri::command]
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub async fn start_install_git_selection(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    operations: State<'_, OperationRegistry>,
+    repoUrl: String,
+    subpath: String,
+    name: Option<String>,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+    branch: Option<String>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    let handle = app.clone();
+    let options = install_options(force, onConflict);
+    let repoUrl = repo_url_on_branch(repoUrl, branch);
+    Ok(start_operation(
+        &app,
+        &operations,
+        "install_git_selection",
+        move |_| install_git_selection_impl(&handle, &store, &repoUrl, &subpath, name, options),
+    ))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn start_update_managed_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    operations: State<'_, OperationRegistry>,
+    skillId: String,
+    force: Option<bool>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    let handle = app.clone();
+    Ok(start_operation(
+        &app,
+        &operations,
+        "update_managed_skill",
+        move |_| {
+            let res = update_managed_skill_from_source_with_force(
+                &handle,
+                &store,
+                &skillId,
+                force.unwrap_or(false),
+            )?;
+            Ok(to_update_dto(res))
+        },
+    ))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolSyncOutcomeDto {
+    pub tool: String,
+    pub result: Option<SyncResultDto>,
+    pub error: Option<SkillsHubError>,
+}
+
+/// Sync a managed skill to several tools, reporting progress per tool. A failing tool does not
+/// stop the others; its error is returned in the matching outcome.
+#[tauri::command]
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub async fn start_sync_skill_to_tools(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    operations: State<'_, OperationRegistry>,
+    skillId: String,
+    tools: Vec<String>,
+    overwrite: Option<bool>,
+    force: Option<bool>,
+    allowExecutables: Option<bool>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    Ok(start_operation(
+        &app,
+        &operations,
+        "sync_skill_to_tools",
+        move |op| {
+            let skill = store
+                .get_skill_by_id(&skillId)?
+                .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+            let total = tools.len() as u64;
+            let mut outcomes = Vec::with_capacity(tools.len());
+            for (idx, tool) in tools.into_iter().enumerate() {
+                op.progress(&format!("syncing:{}", tool), idx as u64, Some(total));
+                let outcome = sync_skill_to_tool_blocking(
+                    &store,
+                    &skill.central_path,
+                    &skill.id,
+                    &tool,
+                    &skill.name,
+                    overwrite.unwrap_or(false),
+                    force.unwrap_or(false),
+                    allowExecutables.unwrap_or(false),
+                );
+                outcomes.push(match outcome {
+                    Ok(result) => ToolSyncOutcomeDto {
+                        tool,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(err) => ToolSyncOutcomeDto {
+                        tool,
+                        result: None,
+                        error: Some(SkillsHubError::from(err)),
+                    },
+                });
+            }
+            Ok(outcomes)
+        },
+    ))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_operation_status(
+    operations: State<'_, OperationRegistry>,
+    operationId: String,
+) -> Result<Option<OperationStatus>, SkillsHubError> {
+    Ok(operations.get(&operationId))
+}
+
+#[tauri::command]
+pub async fn list_operations(
+    operations: State<'_, OperationRegistry>,
+) -> Result<Vec<OperationStatus>, SkillsHubError> {
+    Ok(operations.list())
+}
+
+/// Install links received from the OS since the last call. The UI confirms each one before
+/// running the regular git install flow.
+#[tauri::command]
+pub async fn take_pending_deep_links(
+    queue: State<'_, DeepLinkQueue>,
+) -> Result<Vec<DeepLinkInstall>, SkillsHubError> {
+    Ok(queue.take())
+}
+
+/// The GitHub skill source in `text`, or in the system clipboard when `text` is omitted; `None`
+/// when there is nothing installable. The user asked, so an `owner/repo` shorthand counts.
+#[tauri::command]
+pub async fn parse_clipboard_for_install(
+    text: Option<String>,
+) -> Result<Option<ClipboardInstall>, SkillsHubError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let text = match text {
+            Some(text) => text,
+            None => read_clipboard()?,
+        };
+        Ok::<_, anyhow::Error>(parse_install_text(&text, true))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_clipboard_watch(
+    watcher: State<'_, ClipboardWatcher>,
+) -> Result<bool, SkillsHubError> {
+    Ok(watcher.enabled())
+}
+
+/// Opt-in: while on, each newly copied installable link is sent to the UI as a
+/// `clipboard-install` event.
+#[tauri::command]
+pub async fn set_clipboard_watch(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    watcher: State<'_, ClipboardWatcher>,
+    enabled: bool,
+) -> Result<bool, SkillsHubError> {
+    watcher
+        .set_enabled(&app, &store, enabled)
+        .map_err(SkillsHubError::from)
+}
+
+/// Result of the startup self-repair pass, once; `None` if already taken or still running.
+#[tauri::command]
+pub async fn take_self_repair_summary(
+    state: State<'_, SelfRepairState>,
+) -> Result<Option<SelfRepairSummary>, SkillsHubError> {
+    Ok(state.take())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn update_skill_metadata(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    patch: SkillMetadataPatch,
+) -> Result<UpdateResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let res = update_skill_metadata_core(&store, &skillId, &patch)?;
+        Ok::<_, anyhow::Error>(to_update_dto(res))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_skill_files(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillFileEntry>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        list_skill_files_core(std::path::Path::new(&skill.central_path))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Regex or literal search through the files of every managed skill, with file and line hits.
+#[tauri::command]
+pub async fn grep_skills(
+    store: State<'_, SkillStore>,
+    pattern: String,
+    options: Option<GrepOptions>,
+) -> Result<GrepResult, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        grep_skills_core(&store, &pattern, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn write_skill_content(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    relativePath: String,
+    content: String,
+) -> Result<UpdateResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let res = write_skill_content_core(&store, &skillId, &relativePath, &content)?;
+        Ok::<_, anyhow::Error>(to_update_dto(res))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn normalize_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<UpdateResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let res = normalize_skill_core(&store, &skillId)?;
+        Ok::<_, anyhow::Error>(to_update_dto(res))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_app_language(
+    store: State<'_, SkillStore>,
+) -> Result<Option<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_app_language_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn set_app_language(
+    store: State<'_, SkillStore>,
+    language: String,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_app_language_core(&store, &language))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_backend_locale(
+    store: State<'_, SkillStore>,
+) -> Result<Option<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_backend_locale_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Language of messages rendered by the backend (error fallbacks, logs); empty resets to English.
+#[tauri::command]
+pub async fn set_backend_locale(
+    store: State<'_, SkillStore>,
+    locale: String,
+) -> Result<Option<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_backend_locale_core(&store, &locale))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_normalize_on_install(
+    store: State<'_, SkillStore>,
+) -> Result<bool, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_normalize_on_install_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn set_normalize_on_install(
+    store: State<'_, SkillStore>,
+    enabled: bool,
+) -> Result<bool, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_normalize_on_install_core(&store, enabled))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_hash_max_file_mb(store: State<'_, SkillStore>) -> Result<u64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_hash_max_file_mb_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Files above this many MiB are not read when hashing; 0 hashes every file in full.
+#[tauri::command]
+pub async fn set_hash_max_file_mb(
+    store: State<'_, SkillStore>,
+    mb: u64,
+) -> Result<u64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_hash_max_file_mb_core(&store, mb))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_git_host_policy(
+    store: State<'_, SkillStore>,
+) -> Result<GitHostPolicy, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_git_host_policy_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Hosts git installs and updates may fetch from; see `GitHostPolicy`.
+#[tauri::command]
+pub async fn set_git_host_policy(
+    store: State<'_, SkillStore>,
+    policy: GitHostPolicy,
+) -> Result<GitHostPolicy, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_git_host_policy_core(&store, policy))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_signing_policy(
+    store: State<'_, SkillStore>,
+) -> Result<SigningPolicy, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_signing_policy_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Trusted minisign keys, and whether installs must carry a signature by one of them.
+#[tauri::command]
+pub async fn set_signing_policy(
+    store: State<'_, SkillStore>,
+    policy: SigningPolicy,
+) -> Result<SigningPolicy, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_signing_policy_core(&store, policy))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// This installation's public key, for teammates to add to their trusted keys. The key pair is
+/// created on first use.
+#[tauri::command]
+pub async fn get_signing_public_key(app: tauri::AppHandle) -> Result<String, SkillsHubError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(
+            SigningKeyPair::load_or_create(&signing_key_path(&app)?)?.public_key(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Write `SKILL.minisig` into the skill's central dir, signed with this installation's key, so
+/// copies shared from it can be verified on install.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn sign_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        let pair = SigningKeyPair::load_or_create(&signing_key_path(&app)?)?;
+        let _lock = lock_skill(&skill.id, "sign")?;
+        let comment = format!("timestamp:{}\tskill:{}", now_ms() / 1000, skill.name);
+        sign_skill_dir(std::path::Path::new(&skill.central_path), &pair, &comment)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Zip the skill's central dir to `destPath` with a manifest, for sharing without git.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn export_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    destPath: String,
+) -> Result<ExportManifest, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        let _lock = lock_skill(&skill.id, "export")?;
+        export_skill_archive(&skill, std::path::Path::new(&destPath), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// One archive with the database and the whole Central Repo, for moving to another machine.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn create_backup(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    destPath: String,
+) -> Result<BackupManifest, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        create_backup_core(&store, &central, std::path::Path::new(&destPath), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// `strategy` is `skip` (keep local skills on conflicts) or `overwrite`. Tool targets are
+/// re-created in this machine's tool dirs.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn restore_backup(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    srcPath: String,
+    strategy: String,
+) -> Result<RestoreReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let strategy = RestoreStrategy::from_key(&strategy)
+            .ok_or_else(|| anyhow::anyhow!("unknown restore strategy: {}", strategy))?;
+        let central = resolve_central_repo_path(&app, &store)?;
+        restore_backup_core(
+            &store,
+            &central,
+            std::path::Path::new(&srcPath),
+            strategy,
+            now_ms(),
+            &installed_tool_dir,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Write `skills.lock` to `destPath`: every managed skill's source, revision, hash and tools,
+/// sorted and free of machine-specific paths, for committing to a dotfiles repo.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn export_manifest(
+    store: State<'_, SkillStore>,
+    destPath: String,
+) -> Result<SkillsLock, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        export_lock(&store, std::path::Path::new(&destPath))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Install, update and sync managed skills to match the `skills.lock` at `path` (a file, or a
+/// directory containing one).
+#[tauri::command]
+pub async fn apply_manifest(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    path: String,
+    options: Option<ApplyOptions>,
+) -> Result<ApplyReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let lock = read_lock(std::path::Path::new(&path))?;
+        apply_lock(
+            &app,
+            &store,
+            &lock,
+            options.unwrap_or_default(),
+            now_ms(),
+            &installed_tool_dir,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_central_git_enabled(store: State<'_, SkillStore>) -> Result<bool, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_central_git_enabled_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Version the Central Repo with git: enabling initializes it and commits the current library;
+/// installs, updates, rollbacks and deletes then commit automatically.
+#[tauri::command]
+pub async fn set_central_git_enabled(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    enabled: bool,
+) -> Result<bool, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        set_central_git_enabled_core(&store, &central, enabled)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_central_remote(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<Option<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        Ok::<_, anyhow::Error>(get_central_remote_core(&central))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Remote URL the versioned Central Repo syncs with; an empty URL removes it.
+#[tauri::command]
+pub async fn set_central_remote(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    url: String,
+) -> Result<Option<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        set_central_remote_core(&store, &central, &url)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn push_central_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        push_central_repo_core(&store, &central)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Merge the remote library into this one; conflicting edits fail as `SYNC_CONFLICT` per skill.
+#[tauri::command]
+pub async fn pull_central_repo(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<PullReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        pull_central_repo_core(&store, &central)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Placeholders and conflict copies in a Central Repo kept in Dropbox, iCloud, OneDrive or
+/// Google Drive.
+#[tauri::command]
+pub async fn scan_cloud_sync(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<CloudSyncReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        scan_cloud_sync_core(&central)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Convert Cursor `.mdc` rules (one file, a rules dir, or a project with `.cursor/rules`) into
+/// skills and install them.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_cursor_rules(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    rulesPath: String,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<RuleImportReport, SkillsHubError> {
+    let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = expand_home_path(&rulesPath)?;
+        import_cursor_rules_core(&app, &store, &path, options)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Install the skills bundled in a Claude Code plugin or marketplace (local path or git URL);
+/// `plugins` picks marketplace entries by name.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_plugin_package(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    source: String,
+    plugins: Option<Vec<String>>,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<PluginImportReport, SkillsHubError> {
+    let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = match expand_home_path(&source)? {
+            path if path.exists() => path.to_string_lossy().to_string(),
+            _ => source.trim().to_string(),
+        };
+        install_plugin_package_core(&app, &store, &source, &plugins.unwrap_or_default(), options)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Render a skill as SKILL.md, a Cursor rule or a plain prompt, into the skill dir or `outputPath`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn convert_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    targetFormat: SkillFormat,
+    outputPath: Option<String>,
+) -> Result<ConvertedSkill, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = match outputPath.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => Some(expand_home_path(path)?),
+            _ => None,
+        };
+        convert_skill_core(&store, &skillId, targetFormat, output.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// One markdown document with SKILL.md and its small linked files, for the clipboard.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_snippet(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    maxBytes: Option<usize>,
+) -> Result<SkillSnippet, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        render_skill_snippet(
+            std::path::Path::new(&skill.central_path),
+            maxBytes.unwrap_or(SNIPPET_DEFAULT_MAX_BYTES),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_webhook_urls(store: State<'_, SkillStore>) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_webhook_urls_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// URLs that receive a JSON POST on installs, updates and sync failures; empty turns them off.
+#[tauri::command]
+pub async fn set_webhook_urls(
+    store: State<'_, SkillStore>,
+    urls: Vec<String>,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_webhook_urls_core(&store, urls))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Download a raw SKILL.md or markdown file and manage it as a `url` skill.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_url_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    url: String,
+    name: Option<String>,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<InstallResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = install_url_skill_core(&app, &store, &url, name, options)?;
+        let missing = missing_dependencies_after_install(&store, &result.skill_id);
+        Ok::<_, anyhow::Error>(to_install_dto(result, missing))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Built-in templates and the user's own; git sources are fetched (or served from the cache).
+#[tauri::command]
+pub async fn list_skill_templates(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SkillTemplateInfo>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_skill_templates_core(&app, &store))
+        .await
+        .map_err(|err| err.to_string().into())
+}
+
+#[tauri::command]
+pub async fn list_template_sources(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<String>, SkillsHubError> {
+    Ok(list_template_sources_core(&store))
+}
+
+/// Register a directory of templates, or a git URL of one, for `create_skill`.
+#[tauri::command]
+pub async fn add_template_source(
+    store: State<'_, SkillStore>,
+    source: String,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = if is_git_source(&source) {
+            source
+        } else {
+            expand_home_path(&source)?.to_string_lossy().to_string()
+        };
+        add_template_source_core(&store, &source)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn remove_template_source(
+    store: State<'_, SkillStore>,
+    source: String,
+) -> Result<Vec<String>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || remove_template_source_core(&store, &source))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateSkillResultDto {
+    #[serde(flatten)]
+    pub install: InstallResultDto,
+    /// One entry per tool in `options.tools`; a failing tool does not undo the creation.
+    pub synced: Vec<ToolSyncOutcomeDto>,
+}
+
+/// Create a skill from a template (see `list_skill_templates`) in the central repo, manage it
+/// as a local skill and sync it to `options.tools`.
+#[tauri::command]
+pub async fn create_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    name: String,
+    template: String,
+    options: Option<CreateSkillOptions>,
+) -> Result<CreateSkillResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = create_skill_core(&app, &store, &name, &template, &options, now_ms())?;
+        let source_path = result.central_path.to_string_lossy().to_string();
+        let synced = options
+            .tools
+            .into_iter()
+            .map(|tool| {
+                let outcome = sync_skill_to_tool_blocking(
+                    &store,
+                    &source_path,
+                    &result.skill_id,
+                    &tool,
+                    &result.name,
+                    false,
+                    false,
+                    false,
+                );
+                match outcome {
+                    Ok(result) => ToolSyncOutcomeDto {
+                        tool,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(err) => ToolSyncOutcomeDto {
+                        tool,
+                        result: None,
+                        error: Some(SkillsHubError::from(err)),
+                    },
+                }
+            })
+            .collect();
+        let missing = missing_dependencies_after_install(&store, &result.skill_id);
+        Ok::<_, anyhow::Error>(CreateSkillResultDto {
+            install: to_install_dto(result, missing),
+            synced,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Markdown or JSON listing every tool's deployed skills with mode, last sync and whether the
+/// deployed content still matches the Central Repo. With `destPath` it is also written there.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn export_deployment_report(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    format: ReportFormat,
+    destPath: Option<String>,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        export_deployment_report_core(
+            &store,
+            &central,
+            format,
+            destPath.as_deref().map(std::path::Path::new),
+            now_ms(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_profiles(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<ProfileView>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_profiles_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Create a profile, or with `id` rename it and replace its members.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn save_profile(
+    store: State<'_, SkillStore>,
+    id: Option<String>,
+    name: String,
+    skillIds: Vec<String>,
+) -> Result<ProfileRecord, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        save_profile_core(&store, id.as_deref(), &name, &skillIds, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Skills and their targets are kept; only the profile goes.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_profile(
+    store: State<'_, SkillStore>,
+    profileId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || store.delete_profile(&profileId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Sync the profile's skills to `tool` and unsync every other skill from it, as one batch
+/// that runs every step. The profile is recorded as the tool's active one once all succeed.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn activate_profile(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    profileId: String,
+    tool: String,
+    force: Option<bool>,
+) -> Result<BatchResult, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        activate_profile_blocking(&app, &store, &profileId, &tool, force.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+fn activate_profile_blocking(
+    app: &tauri::AppHandle,
+    store: &SkillStore,
+    profile_id: &str,
+    tool: &str,
+    force: bool,
+) -> anyhow::Result<BatchResult> {
+    if adapter_by_key(tool).is_none() {
+        anyhow::bail!("unknown tool: {}", tool);
+    }
+    let plan = plan_profile_activation(store, profile_id, tool)?;
+    let steps: Vec<BatchStep> = plan
+        .unsync
+        .into_iter()
+        .map(|id| BatchStep::UnsyncFromTool {
+            skill: SkillRef::Id(id),
+            tool: tool.to_string(),
+        })
+        .chain(plan.sync.into_iter().map(|id| BatchStep::SyncToTool {
+            skill: SkillRef::Id(id),
+            tool: tool.to_string(),
+            overwrite: false,
+            force,
+        }))
+        .collect();
+    let result = run_batch_blocking(app, store, &steps, BatchPolicy::Continue)?;
+    if result.ok {
+        store.set_active_profile(tool, profile_id, now_ms())?;
+    }
+    Ok(result)
+}
+
+/// Read the project's `.skillshub.toml`/`.skillshub.json` and report which required skills are
+/// not installed or not yet synced into the project's tool dirs.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn scan_project(
+    store: State<'_, SkillStore>,
+    projectPath: String,
+) -> Result<ProjectScan, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        scan_project_core(&store, std::path::Path::new(&projectPath))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Install the project's missing skills and sync all of them into the project's tool dirs.
+/// `force` also confirms skills that ship executables.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn apply_project_config(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    projectPath: String,
+    force: Option<bool>,
+    onConflict: Option<OnConflict>,
+) -> Result<ProjectApplyReport, SkillsHubError> {
+    let store = store.inner().clone();
+    let options = install_options(force, onConflict);
+    tauri::async_runtime::spawn_blocking(move || {
+        apply_project_config_core(
+            &app,
+            &store,
+            std::path::Path::new(&projectPath),
+            options,
+            options.force,
+            now_ms(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_workspaces(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<WorkspaceView>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_workspaces_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn add_workspace(
+    store: State<'_, SkillStore>,
+    path: String,
+    name: Option<String>,
+) -> Result<WorkspaceRecord, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        add_workspace_core(&store, std::path::Path::new(&path), name, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Forget a workspace; skills synced into the project are left in place.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn remove_workspace(
+    store: State<'_, SkillStore>,
+    workspaceId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || store.delete_workspace(&workspaceId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Sync the profile's skills into the workspace and remove the others from it, for `tools`
+/// or the tools whose directory exists there. `force` confirms skills that ship executables.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn apply_profile_to_workspace(
+    store: State<'_, SkillStore>,
+    workspaceId: String,
+    profileId: String,
+    tools: Option<Vec<String>>,
+    force: Option<bool>,
+) -> Result<WorkspaceProfileReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        apply_profile_to_workspace_core(
+            &store,
+            &workspaceId,
+            &profileId,
+            &tools.unwrap_or_default(),
+            force.unwrap_or(false),
+            now_ms(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Write the profile's members, with their sources and pinned revisions, to `destPath`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn export_profile(
+    store: State<'_, SkillStore>,
+    profileId: String,
+    destPath: String,
+) -> Result<ProfileFile, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        export_profile_core(&store, &profileId, std::path::Path::new(&destPath))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Dry run of `import_profile`: what each entry would do, nothing is installed.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn preview_profile_file(
+    store: State<'_, SkillStore>,
+    srcPath: String,
+) -> Result<ProfileImportPreview, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = read_profile_file(std::path::Path::new(&srcPath))?;
+        preview_profile_import(&store, &file)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_profile(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    srcPath: String,
+    force: Option<bool>,
+) -> Result<ProfileImportReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = read_profile_file(std::path::Path::new(&srcPath))?;
+        import_profile_core(&app, &store, &file, force.unwrap_or(false), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_team_profiles(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<TeamProfileStatus>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_team_profiles_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Import the profile file at `url` (a GitHub blob URL or `<repo>#<path>`) and keep following it.
+#[tauri::command]
+pub async fn track_team_profile(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    url: String,
+    force: Option<bool>,
+) -> Result<ProfileImportReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        track_team_profile_core(&app, &store, &url, force.unwrap_or(false), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn check_team_profile(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    profileId: String,
+) -> Result<TeamProfileStatus, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        check_team_profile_core(&app, &store, &profileId, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamReconcileDto {
+    #[serde(flatten)]
+    pub import: ProfileImportReport,
+    /// Activation re-run for each tool the profile is active for.
+    pub activations: Vec<ProfileActivationDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileActivationDto {
+    pub tool: String,
+    pub result: BatchResult,
+}
+
+/// Bring the local skills and the profile in line with the team file, then activate the profile
+/// again for every tool it is active for.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn reconcile_team_profile(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    profileId: String,
+    force: Option<bool>,
+) -> Result<TeamReconcileDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let force = force.unwrap_or(false);
+        let import = reconcile_team_profile_core(&app, &store, &profileId, force, now_ms())?;
+        let mut activations = Vec::new();
+        for (tool, active) in store.list_active_profiles()? {
+            if active == profileId {
+                let result = activate_profile_blocking(&app, &store, &profileId, &tool, force)?;
+                activations.push(ProfileActivationDto { tool, result });
+            }
+        }
+        Ok::<_, anyhow::Error>(TeamReconcileDto {
+            import,
+            activations,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Stop following the URL; the profile stays as a local one.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn untrack_team_profile(
+    store: State<'_, SkillStore>,
+    profileId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || store.delete_team_profile(&profileId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_team_profile_check_hours(
+    store: State<'_, SkillStore>,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_team_profile_check_hours_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn set_team_profile_check_hours(
+    store: State<'_, SkillStore>,
+    hours: i64,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_team_profile_check_hours_core(&store, hours))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_machine_overrides(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<MachineOverrideRecord>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || store.list_machine_overrides())
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Save this machine's override for a skill name (and tool); returns `None` when it was cleared.
+#[tauri::command]
+pub async fn set_machine_override(
+    store: State<'_, SkillStore>,
+    record: MachineOverrideRecord,
+) -> Result<Option<MachineOverrideRecord>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_machine_override_core(&store, record))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn delete_machine_override(
+    store: State<'_, SkillStore>,
+    skill: String,
+    tool: Option<String>,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        store.delete_machine_override(&skill, tool.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_setup_snapshots(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SetupSnapshot>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_setup_snapshots_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Record what is synced where right now.
+#[tauri::command]
+pub async fn create_setup_snapshot(
+    store: State<'_, SkillStore>,
+    label: Option<String>,
+) -> Result<SetupSnapshot, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        create_setup_snapshot_core(&store, label.as_deref(), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_setup_snapshot(
+    store: State<'_, SkillStore>,
+    snapshotId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || delete_setup_snapshot_core(&store, &snapshotId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Changes from one snapshot to another, or to the current setup without `toId`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn diff_setup_snapshots(
+    store: State<'_, SkillStore>,
+    fromId: String,
+    toId: Option<String>,
+) -> Result<SetupDiff, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        diff_setup_snapshots_core(&store, &fromId, toId.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_environments(
+    store: State<'_, SkillStore>,
+) -> Result<EnvironmentList, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_environments_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Create an environment, or with `id` rename it and replace its settings.
+#[tauri::command]
+pub async fn save_environment(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    id: Option<String>,
+    name: String,
+    settings: EnvironmentSettings,
+) -> Result<Environment, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        save_environment_core(&app, &store, id.as_deref(), &name, settings, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_environment(
+    store: State<'_, SkillStore>,
+    environmentId: String,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || delete_environment_core(&store, &environmentId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Save the live settings into the current environment and apply `environmentId`'s.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn switch_environment(
+    store: State<'_, SkillStore>,
+    environmentId: String,
+) -> Result<Environment, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        switch_environment_core(&store, &environmentId, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_executable_policy(store: State<'_, SkillStore>) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_executable_policy_core(&store).as_key().to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// `allow`, `confirm` or `block`: how syncs of skills that ship scripts or binaries are handled.
+#[tauri::command]
+pub async fn set_executable_policy(
+    store: State<'_, SkillStore>,
+    policy: String,
+) -> Result<String, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        set_executable_policy_core(&store, &policy).map(|p| p.as_key().to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn migrate_to_successor(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+    force: Option<bool>,
+) -> Result<SuccessorMigration, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        migrate_to_successor_core(&app, &store, &skillId, force.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn rollback_update(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<UpdateResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let res = rollback_update_core(&store, &skillId)?;
+        Ok::<_, anyhow::Error>(to_update_dto(res))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_changelog(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<SkillChangelog, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || get_skill_changelog_core(&app, &store, &skillId))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+fn to_update_dto(res: UpdateResult) -> UpdateResultDto {
+    UpdateResultDto {
+        skill_id: res.skill_id,
+        name: res.name,
+        content_hash: res.content_hash,
+        source_revision: res.source_revision,
+        updated_targets: res.updated_targets,
+    }
+}
+
+#[tauri::command]
+pub async fn search_github(
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<RepoSummary>, SkillsHubError> {
+    let limit = limit.unwrap_or(10) as usize;
+    tauri::async_runtime::spawn_blocking(move || search_github_repos(&query, limit))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_existing_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    sourcePath: String,
+    name: Option<String>,
+    adoptTool: Option<String>,
+) -> Result<InstallResultDto, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(tool) = adoptTool.as_deref() {
+            if adapter_by_key(tool).is_none() {
+                anyhow::bail!("unknown tool");
+            }
+        }
+        let result = import_existing_skill_core(
+            &app,
+            &store,
+            sourcePath.as_ref(),
+            name,
+            adoptTool.as_deref(),
+        )?;
+        let missing = missing_dependencies_after_install(&store, &result.skill_id);
+        Ok::<_, anyhow::Error>(to_install_dto(result, missing))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManagedSkillDto {
+    pub id: String,
+    pub name: String,
+    /// Name/description in the app language when the manifest provides translations.
+    pub display_name: String,
+    pub description: Option<String>,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub central_path: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_sync_at: Option<i64>,
+    pub status: String,
+    pub license: Option<String>,
+    /// Tool keys the skill declares support for; empty means every tool.
+    pub compatible_tools: Vec<String>,
+    pub token_estimate: Option<i64>,
+    /// Scripts or binaries ship with the skill; `None` until it has been scanned.
+    pub contains_executables: Option<bool>,
+    /// Central directory plus copy targets, as last measured; `None` until measured.
+    pub disk_bytes: Option<u64>,
+    pub deprecated: bool,
+    pub successor: Option<String>,
+    pub can_rollback: bool,
+    pub targets: Vec<SkillTargetDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillTargetDto {
+    pub tool: String,
+    pub mode: String,
+    pub status: String,
+    pub target_path: String,
+    pub synced_at: Option<i64>,
+}
+
+/// `licenses` keeps skills whose license is one of the given SPDX ids (case-insensitive);
+/// `permissiveOnly` keeps permissively licensed skills. Unlicensed skills never pass a filter.
+/// `projection: "summary"` skips skill metadata, so display names fall back to the skill name
+/// and description, deprecation and compatible tools are left empty.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn get_managed_skills(
+    store: State<'_, SkillStore>,
+    licenses: Option<Vec<String>>,
+    permissiveOnly: Option<bool>,
+    projection: Option<SkillProjection>,
+) -> Result<Vec<ManagedSkillDto>, SkillsHubError> {
+    let mut skills = get_managed_skills_impl(store.inner(), projection.unwrap_or_default())?;
+    let licenses = licenses.unwrap_or_default();
+    let permissive_only = permissiveOnly.unwrap_or(false);
+    if !licenses.is_empty() || permissive_only {
+        skills.retain(|skill| {
+            let Some(license) = skill.license.as_deref() else {
+                return false;
+            };
+            (licenses.is_empty() || licenses.iter().any(|l| l.eq_ignore_ascii_case(license)))
+                && (!permissive_only || is_permissive(license))
+        });
+    }
+    Ok(skills)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_managed_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    force: Option<bool>,
+) -> Result<(), SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // 便于排查“按钮点了没反应”：确认前端确实触发了命令
+        println!("[delete_managed_skill] skillId={}", skillId);
+        delete_managed_skill_blocking(&store, &skillId, force.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+fn delete_managed_skill_blocking(
+    store: &SkillStore,
+    skill_id: &str,
+    force: bool,
+) -> anyhow::Result<()> {
+    let _lock = lock_skill(skill_id, "delete")?;
+    if !force {
+        let dependents = dependent_skill_names(store, skill_id)?;
+        if !dependents.is_empty() {
+            anyhow::bail!("HAS_DEPENDENTS|{}", dependents.join(","));
+        }
+    }
+
+    // 先删除已同步到各工具目录的副本/软链接
+    // 注意：如果先删 skills 行，会触发 skill_targets cascade，导致无法再拿到 target_path
+    let targets = store.list_skill_targets(skill_id)?;
+    let project_targets = store.list_skill_project_targets(skill_id)?;
+
+    let mut remove_failures: Vec<String> = Vec::new();
+    let target_paths = targets
+        .into_iter()
+        .map(|t| t.target_path)
+        .chain(project_targets.into_iter().map(|t| t.target_path));
+    for target_path in target_paths {
+        if let Err(err) = remove_path_any(&target_path) {
+            remove_failures.push(format!("{}: {}", target_path, err));
+        }
+    }
+
+    let record = store.get_skill_by_id(skill_id)?;
+    if let Some(skill) = record {
+        let path = std::path::PathBuf::from(skill.central_path);
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+        if let Err(err) = remove_rollback_snapshot(&path, skill_id) {
+            remove_failures.push(format!("{:#}", err));
+        }
+        store.delete_skill(skill_id)?;
+        record_change(store, &path, &format!("Delete {}", skill.name));
+    }
+
+    if !remove_failures.is_empty() {
+        anyhow::bail!("CLEANUP_FAILED|{}", remove_failures.join("\n"));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillDependentDto {
+    pub skill_id: String,
+    pub name: String,
+    pub dependency: String,
+}
+
+fn dependent_skill_names(store: &SkillStore, skill_id: &str) -> anyhow::Result<Vec<String>> {
+    let mut names = BTreeSet::new();
+    for edge in store.list_skill_dependents(skill_id)? {
+        if let Some(skill) = store.get_skill_by_id(&edge.skill_id)? {
+            names.insert(skill.name);
+        }
+    }
+    Ok(names.into_iter().collect())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_dependents(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillDependentDto>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut out = Vec::new();
+        for edge in store.list_skill_dependents(&skillId)? {
+            if let Some(skill) = store.get_skill_by_id(&edge.skill_id)? {
+                out.push(SkillDependentDto {
+                    skill_id: skill.id,
+                    name: skill.name,
+                    dependency: edge.dependency,
+                });
+            }
+        }
+        Ok::<_, anyhow::Error>(out)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillDependencyDto {
+    pub dependency: String,
+    pub skill_id: Option<String>,
+    pub installed: bool,
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_dependencies(
     store: State<'_, SkillStore>,
     skillId: String,
-) -> Result<UpdateResultDto, String> {
+) -> Result<Vec<SkillDependencyDto>, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let res = update_managed_skill_from_source(&app, &store, &skillId)?;
-        Ok::<_, anyhow::Error>(UpdateResultDto {
-            skill_id: res.skill_id,
-            name: res.name,
-            content_hash: res.content_hash,
-            source_revision: res.source_revision,
-            updated_targets: res.updated_targets,
-        })
+        let edges = store.list_skill_dependencies(&skillId)?;
+        Ok::<_, anyhow::Error>(
+            edges
+                .into_iter()
+                .map(|edge| SkillDependencyDto {
+                    installed: edge.dependency_skill_id.is_some(),
+                    dependency: edge.dependency,
+                    skill_id: edge.dependency_skill_id,
+                })
+                .collect(),
+        )
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-pub async fn search_github(query: String, limit: Option<u32>) -> Result<Vec<RepoSummary>, String> {
-    let limit = limit.unwrap_or(10) as usize;
-    tauri::async_runtime::spawn_blocking(move || search_github_repos(&query, limit))
+pub async fn get_tool_token_totals(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<ToolTokenTotal>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || tool_token_totals(&store))
         .await
         .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn import_existing_skill(
-    app: tauri::AppHandle,
+pub async fn estimate_skill_tokens(
     store: State<'_, SkillStore>,
-    sourcePath: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    skillId: String,
+) -> Result<TokenEstimate, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_local_skill(&app, &store, sourcePath.as_ref(), name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        let estimate = estimate_skill_tokens_core(std::path::Path::new(&skill.central_path))?;
+        store.set_skill_token_estimate(&skill.id, Some(estimate.total as i64))?;
+        Ok::<_, anyhow::Error>(estimate)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct ManagedSkillDto {
-    pub id: String,
-    pub name: String,
-    pub source_type: String,
-    pub source_ref: Option<String>,
-    pub central_path: String,
-    pub created_at: i64,
-    pub updated_at: i64,
-    pub last_sync_at: Option<i64>,
-    pub status: String,
-    pub targets: Vec<SkillTargetDto>,
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_context_budget(
+    store: State<'_, SkillStore>,
+    tool: Option<String>,
+    profileId: Option<String>,
+) -> Result<ContextBudgetReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        context_budget(&store, tool.as_deref(), profileId.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct SkillTargetDto {
-    pub tool: String,
-    pub mode: String,
-    pub status: String,
-    pub target_path: String,
-    pub synced_at: Option<i64>,
+#[tauri::command]
+pub async fn get_context_budget_tokens(
+    store: State<'_, SkillStore>,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_context_budget_tokens_core(&store))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn set_context_budget_tokens(
+    store: State<'_, SkillStore>,
+    tokens: i64,
+) -> Result<i64, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_context_budget_tokens_core(&store, tokens))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
-pub fn get_managed_skills(store: State<'_, SkillStore>) -> Result<Vec<ManagedSkillDto>, String> {
-    get_managed_skills_impl(store.inner())
+pub async fn check_dependency_health(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<DependencyViolation>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || check_dependency_health_core(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn delete_managed_skill(
+pub async fn install_skill_dependencies(
+    app: tauri::AppHandle,
     store: State<'_, SkillStore>,
     skillId: String,
-) -> Result<(), String> {
+) -> Result<Vec<DependencyOutcome>, SkillsHubError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        // 便于排查“按钮点了没反应”：确认前端确实触发了命令
-        println!("[delete_managed_skill] skillId={}", skillId);
-
-        // 先删除已同步到各工具目录的副本/软链接
-        // 注意：如果先删 skills 行，会触发 skill_targets cascade，导致无法再拿到 target_path
-        let targets = store.list_skill_targets(&skillId)?;
-
-        let mut remove_failures: Vec<String> = Vec::new();
-        for target in targets {
-            if let Err(err) = remove_path_any(&target.target_path) {
-                remove_failures.push(format!("{}: {}", target.target_path, err));
-            }
-        }
-
-        let record = store.get_skill_by_id(&skillId)?;
-        if let Some(skill) = record {
-            let path = std::path::PathBuf::from(skill.central_path);
-            if path.exists() {
-                std::fs::remove_dir_all(&path)?;
-            }
-            store.delete_skill(&skillId)?;
-        }
-
-        if !remove_failures.is_empty() {
-            anyhow::bail!(
-                "已删除托管记录，但清理部分工具目录失败：\n- {}",
-                remove_failures.join("\n- ")
-            );
-        }
-
-        Ok::<_, anyhow::Error>(())
+        install_missing_dependencies(&app, &store, &skillId)
     })
     .await
     .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(SkillsHubError::from)
 }
 
 fn remove_path_any(path: &str) -> Result<(), String> {
@@ -736,12 +3141,82 @@ fn remove_path_any(path: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn to_install_dto(result: InstallResult) -> InstallResultDto {
+/// Dependency bookkeeping must not fail an install that already succeeded.
+fn missing_dependencies_after_install(store: &SkillStore, skill_id: &str) -> Vec<String> {
+    record_dependencies_after_install(store, skill_id).unwrap_or_else(|err| {
+        log::warn!(
+            "[dependencies] failed to record for {}: {:#}",
+            skill_id,
+            err
+        );
+        Vec::new()
+    })
+}
+
+fn install_options(force: Option<bool>, on_conflict: Option<OnConflict>) -> InstallOptions {
+    InstallOptions {
+        force: force.unwrap_or(false),
+        on_conflict: on_conflict.unwrap_or_default(),
+    }
+}
+
+/// Pin `repo_url` to the branch picked in the install dialog, if any.
+fn repo_url_on_branch(repo_url: String, branch: Option<String>) -> String {
+    match branch {
+        Some(branch) => git_url_with_branch(&repo_url, &branch),
+        None => repo_url,
+    }
+}
+
+/// Shared by `install_local` and `start_install_local`.
+fn install_local_impl(
+    app: &tauri::AppHandle,
+    store: &SkillStore,
+    source_path: &str,
+    name: Option<String>,
+    options: InstallOptions,
+) -> anyhow::Result<InstallResultDto> {
+    let result = install_local_skill_with_options(app, store, source_path.as_ref(), name, options)?;
+    let missing = missing_dependencies_after_install(store, &result.skill_id);
+    Ok(to_install_dto(result, missing))
+}
+
+/// Shared by `install_git` and `start_install_git`.
+fn install_git_impl(
+    app: &tauri::AppHandle,
+    store: &SkillStore,
+    repo_url: &str,
+    name: Option<String>,
+    options: InstallOptions,
+) -> anyhow::Result<InstallResultDto> {
+    let result = install_git_skill_with_options(app, store, repo_url, name, options)?;
+    let missing = missing_dependencies_after_install(store, &result.skill_id);
+    Ok(to_install_dto(result, missing))
+}
+
+/// Shared by `install_git_selection` and `start_install_git_selection`.
+fn install_git_selection_impl(
+    app: &tauri::AppHandle,
+    store: &SkillStore,
+    repo_url: &str,
+    subpath: &str,
+    name: Option<String>,
+    options: InstallOptions,
+) -> anyhow::Result<InstallResultDto> {
+    let result = install_git_skill_from_selection_with_options(
+        app, store, repo_url, subpath, name, options,
+    )?;
+    let missing = missing_dependencies_after_install(store, &result.skill_id);
+    Ok(to_install_dto(result, missing))
+}
+
+fn to_install_dto(result: InstallResult, missing_dependencies: Vec<String>) -> InstallResultDto {
     InstallResultDto {
         skill_id: result.skill_id,
         name: result.name,
         central_path: result.central_path.to_string_lossy().to_string(),
         content_hash: result.content_hash,
+        missing_dependencies,
     }
 }
 
@@ -752,8 +3227,15 @@ fn now_ms() -> i64 {
     now.as_millis() as i64
 }
 
-fn get_managed_skills_impl(store: &SkillStore) -> Result<Vec<ManagedSkillDto>, String> {
-    let skills = store.list_skills().map_err(|err| err.to_string())?;
+fn get_managed_skills_impl(
+    store: &SkillStore,
+    projection: SkillProjection,
+) -> Result<Vec<ManagedSkillDto>, String> {
+    let skills = store
+        .list_skills_with(projection)
+        .map_err(|err| err.to_string())?;
+    let lang = get_app_language_core(store);
+    let disk_usage = cached_disk_usage(store).map_err(|err| err.to_string())?;
     Ok(skills
         .into_iter()
         .map(|skill| {
@@ -769,10 +3251,26 @@ fn get_managed_skills_impl(store: &SkillStore) -> Result<Vec<ManagedSkillDto>, S
                     synced_at: target.synced_at,
                 })
                 .collect();
+            let can_rollback =
+                has_rollback_snapshot(std::path::Path::new(&skill.central_path), &skill.id);
+            let disk_bytes = disk_usage.get(&skill.id).copied();
+
+            let display_name = skill
+                .metadata
+                .as_ref()
+                .map(|m| m.localized_name(lang.as_deref()).to_string())
+                .unwrap_or_else(|| skill.name.clone());
+            let description = skill
+                .metadata
+                .as_ref()
+                .and_then(|m| m.localized_description(lang.as_deref()))
+                .map(str::to_string);
 
             ManagedSkillDto {
                 id: skill.id,
                 name: skill.name,
+                display_name,
+                description,
                 source_type: skill.source_type,
                 source_ref: skill.source_ref,
                 central_path: skill.central_path,
@@ -780,21 +3278,322 @@ fn get_managed_skills_impl(store: &SkillStore) -> Result<Vec<ManagedSkillDto>, S
                 updated_at: skill.updated_at,
                 last_sync_at: skill.last_sync_at,
                 status: skill.status,
+                license: skill.license,
+                deprecated: skill.metadata.as_ref().is_some_and(|m| m.deprecated),
+                successor: skill.metadata.as_ref().and_then(|m| m.successor.clone()),
+                compatible_tools: skill
+                    .metadata
+                    .map(|m| m.compatible_tools)
+                    .unwrap_or_default(),
+                token_estimate: skill.token_estimate,
+                contains_executables: skill.contains_executables,
+                disk_bytes,
+                can_rollback,
                 targets,
             }
         })
         .collect())
 }
 
-/// Read the SKILL.md content from a skill's central_path
+/// Open a skill's central directory (or its target for `tool`) in the file manager or a terminal.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn reveal_skill(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+    skillId: String,
+    revealIn: Option<String>,
+    tool: Option<String>,
+) -> Result<(), SkillsHubError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = resolve_reveal_path(&store, &skillId, tool.as_deref())?;
+        match revealIn.as_deref().unwrap_or("file_manager") {
+            "file_manager" => app
+                .opener()
+                .open_path(path.to_string_lossy(), None::<&str>)?,
+            "terminal" => open_terminal(&path)?,
+            other => anyhow::bail!("unknown reveal target: {}", other),
+        }
+        Ok::<_, anyhow::Error>(())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Counts and disk usage for the dashboard, gathered in a single call.
+#[tauri::command]
+pub async fn get_app_stats(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<AppStats, SkillsHubError> {
+    use tauri::Manager;
+
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        let cache_dir = app
+            .path()
+            .app_cache_dir()
+            .context("failed to resolve app cache dir")?;
+        collect_app_stats(&store, &central, &cache_dir)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Skills by on-disk size, largest first. Sizes over an hour old are measured again; `refresh`
+/// measures all of them.
+#[tauri::command]
+pub async fn get_largest_skills(
+    store: State<'_, SkillStore>,
+    limit: Option<usize>,
+    refresh: Option<bool>,
+) -> Result<Vec<SkillDiskUsage>, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        largest_skills(&store, limit, refresh.unwrap_or(false), now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn list_scheduled_jobs(
+    store: State<'_, SkillStore>,
+    scheduler: State<'_, Scheduler>,
+) -> Result<Vec<JobStatus>, SkillsHubError> {
+    let (store, scheduler) = (store.inner().clone(), scheduler.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || scheduler.list(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Newest runs first, 20 unless `limit` says otherwise.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_job_history(
+    store: State<'_, SkillStore>,
+    scheduler: State<'_, Scheduler>,
+    jobId: String,
+    limit: Option<usize>,
+) -> Result<Vec<JobRunRecord>, SkillsHubError> {
+    let (store, scheduler) = (store.inner().clone(), scheduler.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        scheduler.history(&store, &jobId, limit.unwrap_or(20))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn run_scheduled_job(
+    store: State<'_, SkillStore>,
+    scheduler: State<'_, Scheduler>,
+    jobId: String,
+) -> Result<JobRunRecord, SkillsHubError> {
+    let (store, scheduler) = (store.inner().clone(), scheduler.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || scheduler.run_now(&store, &jobId, now_ms()))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// `intervalSecs` omitted goes back to the job's default interval.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_job_schedule(
+    store: State<'_, SkillStore>,
+    scheduler: State<'_, Scheduler>,
+    jobId: String,
+    enabled: bool,
+    intervalSecs: Option<i64>,
+) -> Result<JobStatus, SkillsHubError> {
+    let (store, scheduler) = (store.inner().clone(), scheduler.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        scheduler.set_schedule(&store, &jobId, enabled, intervalSecs, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Tray action: re-create every enabled global target from the central repo.
+#[tauri::command]
+pub async fn quick_sync_all(
+    store: State<'_, SkillStore>,
+) -> Result<QuickSyncReport, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || quick_sync_all_core(&store, now_ms()))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Tray action: compare git and local skills with their sources now instead of waiting for the
+/// `update_check` job.
+#[tauri::command]
+pub async fn quick_check_updates(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<UpdateCheck, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || check_skill_updates(&app, &store, now_ms()))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_tray_summary(store: State<'_, SkillStore>) -> Result<TraySummary, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || tray_summary(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+#[tauri::command]
+pub async fn get_background_mode(
+    store: State<'_, SkillStore>,
+    launch: State<'_, BackgroundLaunch>,
+) -> Result<BackgroundModeStatus, SkillsHubError> {
+    let (store, launch) = (store.inner().clone(), *launch.inner());
+    tauri::async_runtime::spawn_blocking(move || background_mode_status(&store, launch))
+        .await
+        .map_err(|err| err.to_string().into())
+}
+
+/// Start hidden at login and keep running when the window is closed, so scheduled jobs and
+/// watchers run without the UI open.
+#[tauri::command]
+pub async fn set_background_mode(
+    store: State<'_, SkillStore>,
+    launch: State<'_, BackgroundLaunch>,
+    enabled: bool,
+) -> Result<BackgroundModeStatus, SkillsHubError> {
+    let (store, launch) = (store.inner().clone(), *launch.inner());
+    tauri::async_runtime::spawn_blocking(move || {
+        let exe = std::env::current_exe().context("failed to resolve the app executable")?;
+        set_background_mode_core(&store, enabled, &exe, launch)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Whether the database file is encrypted and which credential settings are stored encrypted.
+#[tauri::command]
+pub async fn get_encryption_status(
+    store: State<'_, SkillStore>,
+) -> Result<EncryptionStatus, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || encryption_status(&store))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(SkillsHubError::from)
+}
+
+/// Health check of the database, storage, tool directories, synced targets, cache and GitHub
+/// connectivity, with a suggested fix for each problem.
+#[tauri::command]
+pub async fn run_doctor(
+    app: tauri::AppHandle,
+    store: State<'_, SkillStore>,
+) -> Result<DoctorReport, SkillsHubError> {
+    use tauri::Manager;
+
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&app, &store)?;
+        let cache_dir = app
+            .path()
+            .app_cache_dir()
+            .context("failed to resolve app cache dir")?;
+        let mut tool_dirs: Vec<(String, std::path::PathBuf)> = Vec::new();
+        for adapter in crate::core::tool_adapters::default_tool_adapters() {
+            if !is_tool_installed(&adapter)? {
+                continue;
+            }
+            let dir = resolve_default_path(&adapter)?;
+            if !tool_dirs.iter().any(|(_, d)| *d == dir) {
+                tool_dirs.push((adapter.id.as_key().to_string(), dir));
+            }
+        }
+        let mut checks = run_local_checks(&store, &central, &cache_dir, &tool_dirs);
+        checks.push(check_github());
+        Ok::<_, anyhow::Error>(DoctorReport::new(checks))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Tail of the application log, for pasting into bug reports.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_recent_logs(
+    app: tauri::AppHandle,
+    lines: Option<usize>,
+    levelFilter: Option<String>,
+) -> Result<RecentLogs, SkillsHubError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = log_file_path(&app)?;
+        read_recent_logs(&path, lines, levelFilter.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+/// Read a file (SKILL.md by default) from a managed skill, optionally as a byte range. The
+/// skill root comes from the store, never from the caller.
 #[tauri::command]
-pub async fn read_skill_content(central_path: String) -> Result<String, String> {
-    let skill_md_path = std::path::Path::new(&central_path).join("SKILL.md");
-    if !skill_md_path.exists() {
-        return Ok(String::new());
+#[allow(non_snake_case)]
+pub async fn read_skill_content(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    relativePath: Option<String>,
+    offset: Option<u64>,
+    length: Option<u64>,
+) -> Result<SkillFileContent, SkillsHubError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        read_skill_content_impl(&store, &skillId, relativePath, offset, length)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(SkillsHubError::from)
+}
+
+fn read_skill_content_impl(
+    store: &SkillStore,
+    skill_id: &str,
+    relative_path: Option<String>,
+    offset: Option<u64>,
+    length: Option<u64>,
+) -> anyhow::Result<SkillFileContent> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let root = std::path::Path::new(&skill.central_path);
+    let relative = relative_path.unwrap_or_else(|| "SKILL.md".to_string());
+    if relative == "SKILL.md" && !root.join("SKILL.md").exists() {
+        return Ok(SkillFileContent {
+            path: relative,
+            ..Default::default()
+        });
     }
-    std::fs::read_to_string(&skill_md_path)
-        .map_err(|e| format!("Failed to read SKILL.md: {}", e))
+    // `read_skill_file` resolves through `resolve_in_skill`, so `..` and symlinks cannot escape.
+    read_skill_file(root, &relative, offset.unwrap_or(0), length)
 }
 
 /// Skill from the skills.sh registry
@@ -803,55 +3602,59 @@ pub struct RegistrySkill {
     pub name: String,
     pub owner: String,
     pub repo: String,
-    pub subpath: String,  // Actual directory path in the repo
+    pub subpath: String, // Actual directory path in the repo
     pub url: String,
     pub install_cmd: String,
 }
 
 /// Search the skills.sh registry using npx skills find
 #[tauri::command]
-pub async fn search_skills_registry(query: String) -> Result<Vec<RegistrySkill>, String> {
-    use std::process::Command;
+pub async fn search_skills_registry(query: String) -> Result<Vec<RegistrySkill>, SkillsHubError> {
     use regex::Regex;
-    
+    use std::process::Command;
+
     // Function to strip ANSI escape codes
     fn strip_ansi_codes(s: &str) -> String {
         // Match ANSI escape sequences: ESC[ ... m
         let re = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
         re.replace_all(s, "").to_string()
     }
-    
+
     let output = Command::new("npx")
         .args(["skills", "find", &query])
         .output()
         .map_err(|e| format!("Failed to run npx skills find: {}", e))?;
-    
+
     let stdout_raw = String::from_utf8_lossy(&output.stdout);
     let stdout = strip_ansi_codes(&stdout_raw);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    
+
     // Parse the output format:
     // Install with npx skills add <owner/repo@skill>
-    // 
+    //
     // vercel-labs/agent-skills@vercel-react-best-practices
     // └ https://skills.sh/vercel-labs/agent-skills/vercel-react-best-practices
-    
+
     let mut skills = Vec::new();
     let lines: Vec<&str> = stdout.lines().collect();
-    
+
     for (i, line) in lines.iter().enumerate() {
         // Look for lines like "vercel-labs/agent-skills@skill-name"
-        if line.contains('/') && line.contains('@') && !line.starts_with("Install") && !line.starts_with("└") {
+        if line.contains('/')
+            && line.contains('@')
+            && !line.starts_with("Install")
+            && !line.starts_with("└")
+        {
             let line = line.trim();
             // Parse owner/repo@skill-name
             if let Some(at_pos) = line.find('@') {
                 let owner_repo = &line[..at_pos];
                 let skill_name = &line[at_pos + 1..];
-                
+
                 if let Some(slash_pos) = owner_repo.find('/') {
                     let owner = &owner_repo[..slash_pos];
                     let repo = &owner_repo[slash_pos + 1..];
-                    
+
                     // Get the URL from the next line if available
                     let url = if i + 1 < lines.len() {
                         let next_line = lines[i + 1].trim();
@@ -863,7 +3666,7 @@ pub async fn search_skills_registry(query: String) -> Result<Vec<RegistrySkill>,
                     } else {
                         format!("https://skills.sh/{}/{}/{}", owner, repo, skill_name)
                     };
-                    
+
                     // Extract subpath from URL (last segment after owner/repo)
                     // URL format: https://skills.sh/owner/repo/subpath
                     let subpath = url
@@ -872,7 +3675,7 @@ pub async fn search_skills_registry(query: String) -> Result<Vec<RegistrySkill>,
                         .next()
                         .unwrap_or(skill_name)
                         .to_string();
-                    
+
                     skills.push(RegistrySkill {
                         name: skill_name.to_string(),
                         owner: owner.to_string(),
@@ -885,33 +3688,32 @@ pub async fn search_skills_registry(query: String) -> Result<Vec<RegistrySkill>,
             }
         }
     }
-    
+
     if skills.is_empty() && !stderr.is_empty() {
-        return Err(format!("Search failed: {}", stderr));
+        return Err(format!("Search failed: {}", stderr).into());
     }
-    
+
     Ok(skills)
 }
 
 /// Install a skill from the registry using npx skills add
 #[tauri::command]
-pub async fn install_from_registry(package: String) -> Result<String, String> {
+pub async fn install_from_registry(package: String) -> Result<String, SkillsHubError> {
     use std::process::Command;
-    
+
     let output = Command::new("npx")
         .args(["skills", "add", &package, "-g", "-y"])
         .output()
         .map_err(|e| format!("Failed to run npx skills add: {}", e))?;
-    
+
     if output.status.success() {
         Ok(format!("Successfully installed {}", package))
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Installation failed: {}", stderr))
+        Err(format!("Installation failed: {}", stderr).into())
     }
 }
 
 #[cfg(test)]
 #[path = "tests/commands.rs"]
 mod tests;
-
@@ -1,4 +1,5 @@
 use super::*;
+use crate::core::locale::MessageLocale;
 use crate::core::skill_store::SkillRecord;
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
@@ -9,24 +10,94 @@ fn make_store() -> (tempfile::TempDir, SkillStore) {
 }
 
 #[test]
-fn format_anyhow_error_passthrough_prefixes() {
-    let err = anyhow::anyhow!("MULTI_SKILLS|abc");
-    assert_eq!(format_anyhow_error(err), "MULTI_SKILLS|abc");
+fn skills_hub_error_parses_coded_prefixes() {
+    let err = SkillsHubError::from(anyhow::anyhow!("MULTI_SKILLS|https://github.com/a/b|3"));
+    assert_eq!(err.code, "MULTI_SKILLS");
+    assert_eq!(
+        err.details,
+        Some(serde_json::json!({ "repo_url": "https://github.com/a/b", "count": 3 }))
+    );
+
+    let err = SkillsHubError::from(anyhow::anyhow!(
+        "TOOL_INCOMPATIBLE|codex|cursor,claude_code"
+    ));
+    assert_eq!(err.code, "TOOL_INCOMPATIBLE");
+    assert_eq!(
+        err.details,
+        Some(serde_json::json!({ "tool": "codex", "compatible_tools": ["cursor", "claude_code"] }))
+    );
+
+    let err = SkillsHubError::from(anyhow::anyhow!(
+        "SKILL_INVALID|missing_name|[{{\"code\":\"missing_name\",\"line\":2}}]"
+    ));
+    let details = err.details.unwrap();
+    assert_eq!(details["reason"], "missing_name");
+    assert_eq!(details["issues"][0]["line"], 2);
+
+    let err = SkillsHubError::from(anyhow::anyhow!("EXECUTABLES_UNCONFIRMED|pdf-tools"));
+    assert_eq!(err.code, "EXECUTABLES_UNCONFIRMED");
+    assert_eq!(
+        err.details,
+        Some(serde_json::json!({ "skill": "pdf-tools" }))
+    );
+
+    let err = SkillsHubError::from(anyhow::anyhow!("SIGNATURE_UNTRUSTED|pdf-tools|0A1B"));
+    assert_eq!(
+        err.details,
+        Some(serde_json::json!({ "skill": "pdf-tools", "key_id": "0A1B" }))
+    );
+
+    let err = SkillsHubError::from(anyhow::anyhow!("SYNC_CONFLICT|pdf,docs"));
+    assert_eq!(
+        err.details,
+        Some(serde_json::json!({ "skills": ["pdf", "docs"] }))
+    );
+
+    let err = SkillsHubError::from(anyhow::anyhow!("UNKNOWN_PREFIX|x"));
+    assert_eq!(err.code, "INTERNAL");
+    assert_eq!(err.message, "UNKNOWN_PREFIX|x");
 }
 
 #[test]
-fn format_anyhow_error_redacts_clone_temp_path() {
+fn skills_hub_error_redacts_clone_temp_path() {
     let err = anyhow::anyhow!("clone https://example.com/a/b into /tmp/skills-hub-git-123");
-    let msg = format_anyhow_error(err);
-    assert!(msg.contains("已省略临时目录"));
-    assert!(!msg.contains("/tmp/skills-hub-git-123"));
+    let err = SkillsHubError::from(err);
+    assert_eq!(err.code, "INTERNAL");
+    assert!(err.message.starts_with("clone https://example.com/a/b ("));
+    assert!(!err.message.contains("/tmp/skills-hub-git-123"));
 }
 
 #[test]
-fn format_anyhow_error_github_hint_auth() {
+fn skills_hub_error_github_hint_auth() {
     let err = anyhow::anyhow!("git clone https://github.com/a/b failed: authentication failed");
-    let msg = format_anyhow_error(err);
-    assert!(msg.contains("无法访问该仓库"));
+    let err = SkillsHubError::from(err);
+    assert_eq!(err.code, "GIT_FETCH_FAILED");
+    let details = err.details.unwrap();
+    assert_eq!(details["reason"], "auth");
+    let zh = error::localized_message(&err.code, &details, MessageLocale::Zh);
+    assert!(zh.contains("无法访问该仓库"));
+    let en = error::localized_message(&err.code, &details, MessageLocale::En);
+    assert!(en.contains("Cannot access this repository"));
+}
+
+#[test]
+fn skills_hub_error_keeps_codes_behind_context() {
+    let err = anyhow::anyhow!("GIT_TIMEOUT|30|fatal: early EOF").context("fetch origin");
+    let err = SkillsHubError::from(err);
+    assert_eq!(err.code, "GIT_TIMEOUT");
+    assert_eq!(err.details.as_ref().unwrap()["secs"], 30);
+
+    let err = SkillsHubError::from(anyhow::anyhow!(
+        "GIT_CLI_FAILED|git clone https://github.com/a/b: Could not resolve host"
+    ));
+    assert_eq!(err.code, "GIT_FETCH_FAILED");
+    assert_eq!(err.details.unwrap()["reason"], "dns");
+
+    let err = SkillsHubError::from(anyhow::anyhow!("CLEANUP_FAILED|/a: busy\n/b: denied"));
+    let details = err.details.unwrap();
+    assert_eq!(details["failures"][1], "/b: denied");
+    let zh = error::localized_message("CLEANUP_FAILED", &details, MessageLocale::Zh);
+    assert!(zh.ends_with("- /a: busy\n- /b: denied"));
 }
 
 #[test]
@@ -90,6 +161,11 @@ fn get_managed_skills_impl_maps_targets() {
         last_sync_at: None,
         last_seen_at: 1,
         status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
     };
     store.upsert_skill(&skill).unwrap();
 
@@ -105,8 +181,219 @@ fn get_managed_skills_impl_maps_targets() {
     };
     store.upsert_skill_target(&target).unwrap();
 
-    let out = get_managed_skills_impl(&store).unwrap();
+    let out = get_managed_skills_impl(&store, SkillProjection::Full).unwrap();
     assert_eq!(out.len(), 1);
     assert_eq!(out[0].targets.len(), 1);
     assert_eq!(out[0].targets[0].tool, "cursor");
 }
+
+fn batch_steps(value: serde_json::Value) -> Vec<batch::BatchStep> {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn run_batch_rolls_back_installs_after_failure() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
+    let missing = source.path().join("missing");
+
+    let steps = batch_steps(serde_json::json!([
+        { "op": "install_local", "source_path": source.path(), "name": "first" },
+        { "op": "install_local", "source_path": missing, "name": "second" },
+        { "op": "sync_to_tool", "skill": { "step": 0 }, "tool": "cursor" },
+    ]));
+    let out = batch::run_batch_blocking(app.handle(), &store, &steps, batch::BatchPolicy::Rollback)
+        .unwrap();
+    assert!(!out.ok);
+    let states: Vec<_> = out.steps.iter().map(|s| s.state).collect();
+    assert_eq!(
+        states,
+        vec![
+            batch::StepState::RolledBack,
+            batch::StepState::Failed,
+            batch::StepState::Skipped
+        ]
+    );
+    assert!(out.steps[1].error.is_some());
+    assert!(store.list_skills().unwrap().is_empty());
+    assert!(!central.path().join("first").exists());
+}
+
+#[test]
+fn run_batch_stops_or_continues_after_failure() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
+
+    let steps = batch_steps(serde_json::json!([
+        { "op": "update", "skill": { "id": "no-such-skill" } },
+        { "op": "install_local", "source_path": source.path(), "name": "kept" },
+    ]));
+    let out = batch::run_batch_blocking(
+        app.handle(),
+        &store,
+        &steps,
+        batch::BatchPolicy::StopOnError,
+    )
+    .unwrap();
+    assert_eq!(out.steps[0].state, batch::StepState::Failed);
+    assert_eq!(out.steps[1].state, batch::StepState::Skipped);
+    assert!(store.list_skills().unwrap().is_empty());
+
+    let out = batch::run_batch_blocking(app.handle(), &store, &steps, batch::BatchPolicy::Continue)
+        .unwrap();
+    assert!(!out.ok);
+    assert_eq!(out.steps[1].state, batch::StepState::Succeeded);
+    assert_eq!(
+        out.steps[1].skill_id.as_deref(),
+        Some(store.list_skills().unwrap()[0].id.as_str())
+    );
+
+    // Updates cannot be undone, so a rollback batch containing one is refused upfront.
+    let err = batch::run_batch_blocking(app.handle(), &store, &steps, batch::BatchPolicy::Rollback)
+        .unwrap_err();
+    assert_eq!(SkillsHubError::from(err).code, "INVALID_BATCH");
+}
+
+#[test]
+fn run_batch_rollback_leaves_existing_sync_targets_alone() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+
+    // A sync that may overwrite an existing target cannot be undone.
+    let steps = batch_steps(serde_json::json!([
+        { "op": "sync_to_tool", "skill": { "id": "s1" }, "tool": "cursor", "overwrite": true },
+    ]));
+    let err = batch::run_batch_blocking(app.handle(), &store, &steps, batch::BatchPolicy::Rollback)
+        .unwrap_err();
+    assert_eq!(SkillsHubError::from(err).code, "INVALID_BATCH");
+
+    assert!(!batch::sync_target_exists(&store, "s1", "no-such-tool"));
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "S1".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: "/tmp/central".to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "s1".to_string(),
+            tool: "no-such-tool".to_string(),
+            target_path: "/tmp/target".to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+    assert!(batch::sync_target_exists(&store, "s1", "no-such-tool"));
+}
+
+#[test]
+fn delete_removes_rollback_snapshot() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
+
+    let res = install_local_skill_with_options(
+        app.handle(),
+        &store,
+        source.path(),
+        None,
+        InstallOptions::default(),
+    )
+    .unwrap();
+    std::fs::write(source.path().join("a.txt"), b"v2").unwrap();
+    update_managed_skill_from_source_with_force(app.handle(), &store, &res.skill_id, false)
+        .unwrap();
+    assert!(has_rollback_snapshot(&res.central_path, &res.skill_id));
+
+    delete_managed_skill_blocking(&store, &res.skill_id, false).unwrap();
+    assert!(!res.central_path.exists());
+    assert!(!has_rollback_snapshot(&res.central_path, &res.skill_id));
+    assert!(!central
+        .path()
+        .join(".skills-hub-rollback")
+        .join(&res.skill_id)
+        .exists());
+}
+
+#[test]
+fn read_skill_content_stays_inside_the_stored_skill() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    let central = root.path().join("skill");
+    std::fs::create_dir_all(&central).unwrap();
+    std::fs::write(central.join("SKILL.md"), b"# hello").unwrap();
+    std::fs::write(root.path().join("secret.txt"), b"secret").unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "skill".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+
+    let file = read_skill_content_impl(&store, "s1", None, None, None).unwrap();
+    assert_eq!(file.content, "# hello");
+
+    let err = read_skill_content_impl(&store, "s1", Some("../secret.txt".into()), None, None)
+        .unwrap_err();
+    assert_eq!(SkillsHubError::from(err).code, "INVALID_PATH");
+    assert!(read_skill_content_impl(&store, "missing", None, None, None).is_err());
+}
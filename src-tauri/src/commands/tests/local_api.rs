@@ -0,0 +1,141 @@
+use serde_json::json;
+
+use super::*;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    (dir, store)
+}
+
+fn request(store: &SkillStore, method: &str, path: &str, body: Option<Value>) -> ApiRequest {
+    ApiRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers: vec![
+            ("host".to_string(), "127.0.0.1:47821".to_string()),
+            (
+                "authorization".to_string(),
+                format!("Bearer {}", token(store).unwrap()),
+            ),
+        ],
+        body: body.map(|b| b.to_string().into_bytes()).unwrap_or_default(),
+    }
+}
+
+#[test]
+fn requests_need_the_token_and_a_localhost_host() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+
+    let mut req = request(&store, "GET", "/v1/skills", None);
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 200);
+
+    req.headers[1].1 = "Bearer wrong".to_string();
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 401);
+    req.headers.remove(1);
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 401);
+
+    let mut req = request(&store, "GET", "/v1/skills", None);
+    req.headers[0].1 = "attacker.example:47821".to_string();
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 403);
+
+    let req = request(&store, "GET", "/v1/nothing", None);
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 404);
+    let req = request(&store, "DELETE", "/v1/skills", None);
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 405);
+}
+
+#[test]
+fn install_list_and_sync_go_through_the_batch_steps() {
+    let app = tauri::test::mock_app();
+    let (dir, store) = make_store();
+    let source = dir.path().join("source");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::write(source.join("SKILL.md"), "---\nname: notes\n---\n").unwrap();
+
+    let req = request(
+        &store,
+        "POST",
+        "/v1/skills",
+        Some(json!({ "source": source, "name": "notes" })),
+    );
+    let res = handle_request(app.handle(), &store, &req);
+    assert_eq!(res.status, 200, "{}", res.body);
+    let skill_id = res.body["skill_id"].as_str().unwrap().to_string();
+
+    let res = handle_request(
+        app.handle(),
+        &store,
+        &request(&store, "GET", "/v1/skills", None),
+    );
+    assert_eq!(res.body[0]["name"], "notes");
+
+    // The same install again is refused with the command's coded error.
+    let res = handle_request(app.handle(), &store, &req);
+    assert_eq!(res.status, 422);
+    assert_eq!(res.body["error"]["code"], "SKILL_EXISTS");
+
+    let req = request(
+        &store,
+        "POST",
+        &format!("/v1/skills/{}/sync", skill_id),
+        Some(json!({ "tool": "no-such-tool" })),
+    );
+    let res = handle_request(app.handle(), &store, &req);
+    assert_eq!(res.status, 422);
+    assert!(res.body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown tool"));
+
+    let req = request(&store, "POST", "/v1/skills", Some(json!({ "name": "x" })));
+    assert_eq!(handle_request(app.handle(), &store, &req).status, 400);
+}
+
+#[test]
+fn the_server_answers_over_http_until_stopped() {
+    let app = tauri::test::mock_app();
+    let (_dir, store) = make_store();
+    let state = LocalApiState::default();
+    let port = state.start(app.handle(), &store, 0).unwrap();
+    assert_eq!(state.running_port(), Some(port));
+
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+    write!(
+        stream,
+        "GET /v1/skills HTTP/1.1\r\nHost: localhost:{}\r\nAuthorization: Bearer {}\r\n\r\n",
+        port,
+        token(&store).unwrap()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.ends_with("\r\n\r\n[]"), "{}", response);
+
+    // Connections past the cap are turned away instead of getting a thread each.
+    let idle: Vec<TcpStream> = (0..MAX_CONNECTIONS)
+        .map(|_| TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap())
+        .collect();
+    let mut extra = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+    let mut response = String::new();
+    extra.read_to_string(&mut response).unwrap();
+    assert!(
+        response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"),
+        "{}",
+        response
+    );
+    drop(idle);
+
+    state.stop();
+    assert_eq!(state.running_port(), None);
+    // The listener is gone once `stop` returns, so the same port can be bound again.
+    assert_eq!(state.start(app.handle(), &store, port).unwrap(), port);
+    state.stop();
+}
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tauri::Manager;
+
+pub const DEFAULT_LOG_LINES: usize = 200;
+const MAX_LOG_LINES: usize = 5000;
+const LEVELS: [log::Level; 5] = [
+    log::Level::Error,
+    log::Level::Warn,
+    log::Level::Info,
+    log::Level::Debug,
+    log::Level::Trace,
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RecentLogs {
+    /// Log file that was read, so users can attach the whole file if needed.
+    pub path: String,
+    pub lines: Vec<String>,
+}
+
+/// File written by the `LogDir` target set up in `lib.rs` (no custom file name).
+pub fn log_file_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .context("failed to resolve app log dir")?;
+    Ok(dir.join(format!("{}.log", app.package_info().name)))
+}
+
+/// Last `lines` lines of the log at `path` (capped at `MAX_LOG_LINES`). With `level_filter`,
+/// only entries at that level or more severe are kept; continuation lines of a multi-line
+/// message follow their entry. A missing log file yields no lines.
+pub fn read_recent_logs(
+    path: &Path,
+    lines: Option<usize>,
+    level_filter: Option<&str>,
+) -> Result<RecentLogs> {
+    let max_level = match level_filter.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(raw) => Some(
+            log::Level::from_str(raw).map_err(|_| anyhow::anyhow!("unknown log level: {}", raw))?,
+        ),
+        None => None,
+    };
+    let wanted = lines.unwrap_or(DEFAULT_LOG_LINES).min(MAX_LOG_LINES);
+
+    let text = match std::fs::read(path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("read {:?}", path)),
+    };
+
+    let mut current = None;
+    let mut kept: Vec<&str> = text
+        .lines()
+        .filter(|line| {
+            if let Some(level) = line_level(line) {
+                current = Some(level);
+            }
+            match (max_level, current) {
+                (None, _) => true,
+                (Some(max), Some(level)) => level <= max,
+                (Some(_), None) => false,
+            }
+        })
+        .collect();
+    let skip = kept.len().saturating_sub(wanted);
+    kept.drain(..skip);
+
+    Ok(RecentLogs {
+        path: path.to_string_lossy().to_string(),
+        lines: kept.into_iter().map(str::to_string).collect(),
+    })
+}
+
+/// Level of a line in the plugin's default `[date][time][target][LEVEL] message` format.
+fn line_level(line: &str) -> Option<log::Level> {
+    LEVELS
+        .into_iter()
+        .find(|level| line.contains(&format!("][{}] ", level)))
+}
+
+#[cfg(test)]
+#[path = "tests/app_logs.rs"]
+mod tests;
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::cache_cleanup::CACHE_DIR_NAME;
+use super::skill_store::{SkillProjection, SkillStore};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AppStats {
+    pub total_skills: u64,
+    /// Keyed by `source_type` (`local`, `git`, ...).
+    pub skills_by_source_type: BTreeMap<String, u64>,
+    /// Keyed by `status` (`ok`, `missing`, ...).
+    pub skills_by_status: BTreeMap<String, u64>,
+    /// Number of synced targets per tool key.
+    pub targets_by_tool: BTreeMap<String, u64>,
+    pub central_repo_bytes: u64,
+    pub git_cache_bytes: u64,
+    /// Database file plus its WAL/SHM side files, if present.
+    pub db_bytes: u64,
+}
+
+/// Gather dashboard numbers in one pass. Missing directories count as zero bytes.
+pub fn collect_app_stats(
+    store: &SkillStore,
+    central_repo: &Path,
+    cache_dir: &Path,
+) -> Result<AppStats> {
+    let mut stats = AppStats::default();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        stats.total_skills += 1;
+        *stats
+            .skills_by_source_type
+            .entry(skill.source_type)
+            .or_default() += 1;
+    }
+    stats.skills_by_status = store.count_skills_by_status()?;
+    stats.targets_by_tool = store.count_skill_targets_by_tool()?;
+    stats.central_repo_bytes = dir_size(central_repo);
+    stats.git_cache_bytes = dir_size(&cache_dir.join(CACHE_DIR_NAME));
+
+    let db_path = store.db_path();
+    stats.db_bytes = ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut name = db_path.as_os_str().to_owned();
+            name.push(suffix);
+            std::fs::metadata(name).ok()
+        })
+        .map(|meta| meta.len())
+        .sum();
+    Ok(stats)
+}
+
+/// Total size of regular files below `root`; symlinks are not followed so synced targets
+/// pointing back into the central repo are not counted twice.
+pub fn dir_size(root: &Path) -> u64 {
+    WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+#[cfg(test)]
+#[path = "tests/app_stats.rs"]
+mod tests;
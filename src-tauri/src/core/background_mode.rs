@@ -0,0 +1,205 @@
+use std::path::Path;
+#[cfg(not(windows))]
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tauri::Manager;
+
+use super::skill_store::SkillStore;
+
+pub const BACKGROUND_MODE_KEY: &str = "background_mode";
+/// Passed by the login item: start with the window hidden.
+pub const BACKGROUND_ARG: &str = "--background";
+#[cfg(not(windows))]
+const APP_ID: &str = "com.qufei1993.skillshub";
+#[cfg(windows)]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+#[cfg(windows)]
+const RUN_VALUE: &str = "Skills Hub";
+
+/// Whether this launch came from the login item.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BackgroundLaunch(pub bool);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BackgroundModeStatus {
+    pub enabled: bool,
+    /// The login item is in place; false when it was removed outside the app.
+    pub autostart_registered: bool,
+    pub launched_in_background: bool,
+}
+
+pub fn background_mode_enabled(store: &SkillStore) -> bool {
+    store
+        .get_setting(BACKGROUND_MODE_KEY)
+        .ok()
+        .flatten()
+        .is_some_and(|v| v == "true")
+}
+
+pub fn launched_in_background(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == BACKGROUND_ARG)
+}
+
+pub fn background_mode_status(
+    store: &SkillStore,
+    launch: BackgroundLaunch,
+) -> BackgroundModeStatus {
+    BackgroundModeStatus {
+        enabled: background_mode_enabled(store),
+        autostart_registered: autostart_registered(),
+        launched_in_background: launch.0,
+    }
+}
+
+/// Turning it on registers a login item that starts the app hidden, so the scheduler and
+/// watchers run from login; closing the window then hides it instead of quitting. Turning it
+/// off removes the login item.
+pub fn set_background_mode(
+    store: &SkillStore,
+    enabled: bool,
+    exe: &Path,
+    launch: BackgroundLaunch,
+) -> Result<BackgroundModeStatus> {
+    if enabled {
+        register_autostart(exe)?;
+    } else {
+        unregister_autostart()?;
+    }
+    store.set_setting(BACKGROUND_MODE_KEY, if enabled { "true" } else { "false" })?;
+    Ok(background_mode_status(store, launch))
+}
+
+/// Bring the hidden or minimized main window to the front.
+pub fn show_main_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// The freedesktop autostart entry on Linux, the LaunchAgent on macOS.
+#[cfg(not(windows))]
+fn login_item_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        dirs::home_dir().map(|home| {
+            home.join("Library/LaunchAgents")
+                .join(format!("{}.plist", APP_ID))
+        })
+    } else {
+        dirs::config_dir()
+            .map(|config| config.join("autostart").join(format!("{}.desktop", APP_ID)))
+    }
+}
+
+#[cfg(not(windows))]
+fn login_item(exe: &Path) -> String {
+    let exe = exe.to_string_lossy();
+    if cfg!(target_os = "macos") {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{}</string>
+    <string>{}</string>
+  </array>
+  <key>RunAtLoad</key>
+  <true/>
+</dict>
+</plist>
+"#,
+            APP_ID,
+            xml_escape(&exe),
+            BACKGROUND_ARG
+        )
+    } else {
+        format!(
+            "[Desktop Entry]\nType=Application\nName=Skills Hub\nExec=\"{}\" {}\nX-GNOME-Autostart-enabled=true\nNoDisplay=true\n",
+            exe.replace('\\', "\\\\").replace('"', "\\\""),
+            BACKGROUND_ARG
+        )
+    }
+}
+
+#[cfg(not(windows))]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(not(windows))]
+fn autostart_registered() -> bool {
+    login_item_path().is_some_and(|path| path.is_file())
+}
+
+#[cfg(not(windows))]
+fn register_autostart(exe: &Path) -> Result<()> {
+    let path = login_item_path().context("failed to resolve autostart directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {:?}", parent))?;
+    }
+    std::fs::write(&path, login_item(exe)).with_context(|| format!("failed to write {:?}", path))
+}
+
+#[cfg(not(windows))]
+fn unregister_autostart() -> Result<()> {
+    match login_item_path() {
+        Some(path) if path.exists() => {
+            std::fs::remove_file(&path).with_context(|| format!("failed to remove {:?}", path))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<bool> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let status = std::process::Command::new("reg")
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .context("failed to run reg.exe")?;
+    Ok(status.success())
+}
+
+#[cfg(windows)]
+fn autostart_registered() -> bool {
+    reg(&["query", RUN_KEY, "/v", RUN_VALUE]).unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn register_autostart(exe: &Path) -> Result<()> {
+    let command = format!("\"{}\" {}", exe.display(), BACKGROUND_ARG);
+    let args = [
+        "add", RUN_KEY, "/v", RUN_VALUE, "/t", "REG_SZ", "/d", &command, "/f",
+    ];
+    if !reg(&args)? {
+        anyhow::bail!("failed to add the login item");
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn unregister_autostart() -> Result<()> {
+    if autostart_registered() && !reg(&["delete", RUN_KEY, "/v", RUN_VALUE, "/f"])? {
+        anyhow::bail!("failed to remove the login item");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/background_mode.rs"]
+mod tests;
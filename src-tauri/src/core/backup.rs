@@ -0,0 +1,602 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::background_mode::BACKGROUND_MODE_KEY;
+use super::central_repo::INSTALL_REPO_KEY;
+use super::db_encryption::is_encrypted_value;
+use super::environments::{ACTIVE_ENVIRONMENT_KEY, ENVIRONMENTS_KEY, PROXY_URL_KEY};
+use super::installer::move_dir;
+use super::onboarding::PROJECT_ROOTS_KEY;
+use super::skill_executables::{contains_executables, ensure_sync_allowed};
+use super::skill_files::ensure_plain_name;
+use super::skill_lock::lock_skill;
+use super::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use super::sync_engine::{remove_path_any, sync_dir_for_tool_with_overwrite};
+
+pub const BACKUP_MANIFEST_FILE: &str = "skills-hub-backup.json";
+const BACKUP_DB_FILE: &str = "skills_hub.db";
+/// Archive folder holding the Central Repo.
+const CENTRAL_FOLDER: &str = "central";
+/// Archive folder holding the other Central Repos, each under its id.
+const REPOS_FOLDER: &str = "repos";
+/// 2 added the other Central Repos; older apps would restore such a backup without them.
+const BACKUP_FORMAT: u32 = 2;
+/// Machine-specific settings the restoring installation keeps its own value for.
+const LOCAL_SETTINGS: [&str; 10] = [
+    "central_repo_path",
+    INSTALL_REPO_KEY,
+    PROJECT_ROOTS_KEY,
+    ENVIRONMENTS_KEY,
+    ACTIVE_ENVIRONMENT_KEY,
+    PROXY_URL_KEY,
+    BACKGROUND_MODE_KEY,
+    "local_api_enabled",
+    "local_api_port",
+    "local_api_token",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    pub created_at: i64,
+    pub app_version: String,
+    pub schema_version: i32,
+    /// Central Repo location on the machine that made the backup.
+    pub central_repo_path: String,
+    pub skill_count: usize,
+    /// The Central Repos besides the default one.
+    #[serde(default)]
+    pub repos: Vec<BackupRepo>,
+}
+
+/// An added Central Repo in a backup, archived under `repos/<id>/`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupRepo {
+    pub id: String,
+    pub name: String,
+    /// Location on the machine that made the backup.
+    pub path: String,
+}
+
+/// A backed-up skill to restore: its dir in the archive and where it goes.
+struct PlannedSkill {
+    skill: SkillRecord,
+    archived: String,
+    new_path: PathBuf,
+}
+
+/// What happens to skills that already exist locally under the same id, name or directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreStrategy {
+    /// Keep the local skill and leave the backed-up one out.
+    Skip,
+    /// Replace the local skill with the backed-up one.
+    Overwrite,
+}
+
+impl RestoreStrategy {
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "skip" => Some(RestoreStrategy::Skip),
+            "overwrite" => Some(RestoreStrategy::Overwrite),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UnlinkedTarget {
+    pub skill: String,
+    pub tool: String,
+    /// `tool_not_installed`, or the sync error.
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RestoreReport {
+    pub restored: Vec<String>,
+    /// Backed-up skills left out because a local one was kept (`skip` strategy).
+    pub skipped: Vec<String>,
+    pub relinked: usize,
+    pub unlinked: Vec<UnlinkedTarget>,
+}
+
+/// Write one zip with a snapshot of the database, the whole Central Repo under `central/`, each
+/// added repo under `repos/<id>/` and a `skills-hub-backup.json` manifest. Symlinks inside the
+/// repos are skipped.
+pub fn create_backup(
+    store: &SkillStore,
+    central: &Path,
+    dest: &Path,
+    now: i64,
+) -> Result<BackupManifest> {
+    let snapshot = std::env::temp_dir().join(format!("skills-hub-backup-{}.db", Uuid::new_v4()));
+    store.backup_to(&snapshot)?;
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        created_at: now,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: store.schema_version()?,
+        central_repo_path: central.to_string_lossy().to_string(),
+        skill_count: store.list_skills()?.len(),
+        repos: store
+            .list_central_repos()?
+            .into_iter()
+            .map(|repo| BackupRepo {
+                id: repo.id,
+                name: repo.name,
+                path: repo.path,
+            })
+            .collect(),
+    };
+
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    let tmp = dest.with_extension("zip.tmp");
+    let result = write_backup(&tmp, &manifest, &snapshot, central)
+        .and_then(|_| std::fs::rename(&tmp, dest).with_context(|| format!("write {:?}", dest)));
+    let _ = std::fs::remove_file(&snapshot);
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result?;
+    Ok(manifest)
+}
+
+fn write_backup(path: &Path, manifest: &BackupManifest, db: &Path, central: &Path) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("create {:?}", path))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    zip.start_file(BACKUP_MANIFEST_FILE, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    zip.start_file(BACKUP_DB_FILE, options)?;
+    std::io::copy(&mut File::open(db)?, &mut zip).context("add db to backup")?;
+
+    add_repo_dir(&mut zip, central, CENTRAL_FOLDER, options)?;
+    for repo in &manifest.repos {
+        let folder = format!("{}/{}", REPOS_FOLDER, repo.id);
+        add_repo_dir(&mut zip, Path::new(&repo.path), &folder, options)?;
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+/// Add the contents of the repo at `root` under `folder/`.
+fn add_repo_dir(
+    zip: &mut ZipWriter<BufWriter<File>>,
+    root: &Path,
+    folder: &str,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.add_directory(format!("{}/", folder), options)?;
+    if root.is_dir() {
+        for entry in WalkDir::new(root)
+            .follow_links(false)
+            .sort_by_file_name()
+            .min_depth(1)
+        {
+            let entry = entry?;
+            let relative = entry
+                .path()
+                .strip_prefix(root)
+                .with_context(|| format!("strip prefix {:?}", entry.path()))?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let name = format!("{}/{}", folder, relative);
+            let file_type = entry.file_type();
+            if file_type.is_symlink() {
+                log::warn!("[backup] skipping symlink {:?}", entry.path());
+            } else if file_type.is_dir() {
+                zip.add_directory(format!("{}/", name), options)?;
+            } else {
+                #[cfg(unix)]
+                let options = {
+                    use std::os::unix::fs::PermissionsExt;
+                    options.unix_permissions(entry.metadata()?.permissions().mode() & 0o777)
+                };
+                zip.start_file(name, options)?;
+                let mut input =
+                    File::open(entry.path()).with_context(|| format!("open {:?}", entry.path()))?;
+                std::io::copy(&mut input, zip)
+                    .with_context(|| format!("read {:?}", entry.path()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Restore a backup into this installation's store and Central Repo (`central`). Skills keep
+/// their ids; their directories move under `central`, or into the added repo with the same id
+/// when this installation has it, and their tool targets are re-created in this machine's tool
+/// dirs via `tool_dir` (see `tool_adapters::installed_tool_dir`), subject to this
+/// installation's executable policy. Settings are merged last, except machine-specific ones.
+pub fn restore_backup(
+    store: &SkillStore,
+    central: &Path,
+    src: &Path,
+    strategy: RestoreStrategy,
+    now: i64,
+    tool_dir: &dyn Fn(&str) -> Option<PathBuf>,
+) -> Result<RestoreReport> {
+    let file = File::open(src).with_context(|| format!("open {:?}", src))?;
+    let mut archive =
+        ZipArchive::new(file).with_context(|| format!("not a zip archive: {:?}", src))?;
+    let manifest: BackupManifest = {
+        let entry = archive
+            .by_name(BACKUP_MANIFEST_FILE)
+            .map_err(|_| anyhow::anyhow!("not a Skills Hub backup: {:?}", src))?;
+        serde_json::from_reader(entry).context("read backup manifest")?
+    };
+    if manifest.format > BACKUP_FORMAT {
+        anyhow::bail!(
+            "backup format {} is newer than this app supports",
+            manifest.format
+        );
+    }
+
+    let db_copy = std::env::temp_dir().join(format!("skills-hub-restore-{}.db", Uuid::new_v4()));
+    let result = (|| {
+        let mut entry = archive
+            .by_name(BACKUP_DB_FILE)
+            .context("backup has no database")?;
+        let mut out = File::create(&db_copy).with_context(|| format!("create {:?}", db_copy))?;
+        std::io::copy(&mut entry, &mut out).context("extract backup database")?;
+        drop(entry);
+        let backup = SkillStore::new(db_copy.clone());
+        // Migrates backups from older versions and refuses ones from newer versions.
+        backup.ensure_schema()?;
+        restore_from(
+            store,
+            &backup,
+            &mut archive,
+            &manifest,
+            central,
+            strategy,
+            now,
+            tool_dir,
+        )
+    })();
+    let _ = std::fs::remove_file(&db_copy);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn restore_from(
+    store: &SkillStore,
+    backup: &SkillStore,
+    archive: &mut ZipArchive<File>,
+    manifest: &BackupManifest,
+    central: &Path,
+    strategy: RestoreStrategy,
+    now: i64,
+    tool_dir: &dyn Fn(&str) -> Option<PathBuf>,
+) -> Result<RestoreReport> {
+    std::fs::create_dir_all(central).with_context(|| format!("create {:?}", central))?;
+    let preexisting: BTreeSet<String> = std::fs::read_dir(central)
+        .with_context(|| format!("read {:?}", central))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    let local_repos = store.list_central_repos()?;
+    let existing = store.list_skills()?;
+    let mut report = RestoreReport::default();
+    let mut managed_tops: BTreeSet<String> = BTreeSet::new();
+    let mut planned: Vec<PlannedSkill> = Vec::new();
+
+    for mut skill in backup.list_skills()? {
+        let (folder, old_root) = match skill.repo_id.as_deref() {
+            None => (
+                CENTRAL_FOLDER.to_string(),
+                PathBuf::from(&manifest.central_repo_path),
+            ),
+            Some(id) => match manifest
+                .repos
+                .iter()
+                .find(|repo| repo.id == id && ensure_plain_name(&repo.id).is_ok())
+            {
+                Some(repo) => (
+                    format!("{}/{}", REPOS_FOLDER, repo.id),
+                    PathBuf::from(&repo.path),
+                ),
+                None => {
+                    log::warn!(
+                        "[backup] skipping {}: its repo is not in the backup",
+                        skill.name
+                    );
+                    report.skipped.push(skill.name.clone());
+                    continue;
+                }
+            },
+        };
+        let Some(relative) = relative_central_dir(&old_root, Path::new(&skill.central_path)) else {
+            log::warn!(
+                "[backup] skipping {}: unusable central path {:?}",
+                skill.name,
+                skill.central_path
+            );
+            report.skipped.push(skill.name.clone());
+            continue;
+        };
+        if folder == CENTRAL_FOLDER {
+            if let Some(top) = relative.split('/').next() {
+                managed_tops.insert(top.to_string());
+            }
+        }
+        let local_repo = skill
+            .repo_id
+            .as_deref()
+            .and_then(|id| local_repos.iter().find(|repo| repo.id == id));
+        skill.repo_id = local_repo.map(|repo| repo.id.clone());
+        let new_path = match local_repo {
+            Some(repo) => Path::new(&repo.path).join(&relative),
+            None => central.join(&relative),
+        };
+        let conflicts = existing.iter().any(|e| {
+            e.id == skill.id || e.name == skill.name || Path::new(&e.central_path) == new_path
+        });
+        if (conflicts || new_path.exists()) && strategy == RestoreStrategy::Skip {
+            report.skipped.push(skill.name.clone());
+            continue;
+        }
+        planned.push(PlannedSkill {
+            skill,
+            archived: format!("{}/{}", folder, relative),
+            new_path,
+        });
+    }
+
+    // Extract into a staging dir next to the Central Repo (same filesystem, so moving into
+    // place is a rename, except into added repos elsewhere). Local skills are only replaced
+    // once the archive read back in full.
+    let staging = central.with_file_name(format!(
+        ".{}-restore-{}",
+        central
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "skills-hub".to_string()),
+        Uuid::new_v4()
+    ));
+    let result = (|| {
+        let unmanaged = extract_central(archive, &staging, &planned, &managed_tops, &preexisting)?;
+        let restored = swap_in_restored(store, &staging, &existing, planned, &mut report)?;
+        for top in unmanaged {
+            let from = staging.join(CENTRAL_FOLDER).join(&top);
+            let to = central.join(&top);
+            if from.exists() && !to.exists() {
+                std::fs::rename(&from, &to)
+                    .with_context(|| format!("move {:?} to {:?}", from, to))?;
+            }
+        }
+        Ok::<_, anyhow::Error>(restored)
+    })();
+    let _ = remove_path_any(&staging);
+    let restored = result?;
+
+    for skill in &restored {
+        store.upsert_skill(skill)?;
+    }
+    for skill in &restored {
+        let mut dependencies = backup.list_skill_dependencies(&skill.id)?;
+        for dep in &mut dependencies {
+            if let Some(id) = &dep.dependency_skill_id {
+                if store.get_skill_by_id(id)?.is_none() {
+                    dep.dependency_skill_id = None;
+                }
+            }
+        }
+        store.replace_skill_dependencies(&skill.id, &dependencies)?;
+
+        for target in backup.list_skill_targets(&skill.id)? {
+            let Some(dir) = tool_dir(&target.tool) else {
+                report.unlinked.push(UnlinkedTarget {
+                    skill: skill.name.clone(),
+                    tool: target.tool.clone(),
+                    reason: "tool_not_installed".to_string(),
+                });
+                continue;
+            };
+            let link_name = Path::new(&target.target_path)
+                .file_name()
+                .map(|n| n.to_os_string())
+                .unwrap_or_else(|| skill.name.clone().into());
+            let overwrite = strategy == RestoreStrategy::Overwrite;
+            // Same gate as a manual sync, under this installation's policy: settings from the
+            // backup are merged only afterwards.
+            let synced =
+                lock_skill(&skill.id, &format!("sync:{}", target.tool)).and_then(|_lock| {
+                    ensure_sync_allowed(store, skill, false)?;
+                    sync_dir_for_tool_with_overwrite(
+                        &target.tool,
+                        Path::new(&skill.central_path),
+                        &dir.join(link_name),
+                        overwrite,
+                    )
+                });
+            match synced {
+                Ok(outcome) => {
+                    store.upsert_skill_target(&SkillTargetRecord {
+                        target_path: outcome.target_path.to_string_lossy().to_string(),
+                        mode: outcome.mode_used.as_key().to_string(),
+                        status: "ok".to_string(),
+                        last_error: None,
+                        synced_at: Some(now),
+                        ..target
+                    })?;
+                    report.relinked += 1;
+                }
+                Err(err) => report.unlinked.push(UnlinkedTarget {
+                    skill: skill.name.clone(),
+                    tool: target.tool.clone(),
+                    reason: err.to_string(),
+                }),
+            }
+        }
+        report.restored.push(skill.name.clone());
+    }
+
+    merge_settings(store, backup, strategy)?;
+    log::info!(
+        "[backup] restored {} skills, skipped {}, relinked {} targets, {} not relinked",
+        report.restored.len(),
+        report.skipped.len(),
+        report.relinked,
+        report.unlinked.len()
+    );
+    Ok(report)
+}
+
+/// Extract the planned skills' dirs, plus anything unmanaged this Central Repo does not have
+/// yet, under `staging` in the archive's layout. Returns the unmanaged top-level entries of
+/// `central/` extracted.
+fn extract_central(
+    archive: &mut ZipArchive<File>,
+    staging: &Path,
+    planned: &[PlannedSkill],
+    managed_tops: &BTreeSet<String>,
+    preexisting: &BTreeSet<String>,
+) -> Result<BTreeSet<String>> {
+    std::fs::create_dir_all(staging).with_context(|| format!("create {:?}", staging))?;
+    let mut unmanaged = BTreeSet::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name() else {
+            log::warn!("[backup] skipping unsafe entry {:?}", entry.name());
+            continue;
+        };
+        let name_str = name.to_string_lossy().replace('\\', "/");
+        let is_planned = planned
+            .iter()
+            .any(|p| name_str == p.archived || name_str.starts_with(&format!("{}/", p.archived)));
+        let unmanaged_top = name_str
+            .strip_prefix(&format!("{}/", CENTRAL_FOLDER))
+            .and_then(|relative| relative.split('/').next())
+            .filter(|top| !top.is_empty())
+            .filter(|top| !managed_tops.contains(*top) && !preexisting.contains(*top));
+        if !is_planned && unmanaged_top.is_none() {
+            continue;
+        }
+        if let Some(top) = unmanaged_top {
+            unmanaged.insert(top.to_string());
+        }
+        let out = staging.join(&name);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out).with_context(|| format!("create {:?}", out))?;
+            continue;
+        }
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+        }
+        let mut file = File::create(&out).with_context(|| format!("create {:?}", out))?;
+        std::io::copy(&mut entry, &mut file).with_context(|| format!("write {:?}", out))?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&out, std::fs::Permissions::from_mode(mode & 0o777))
+                .with_context(|| format!("chmod {:?}", out))?;
+        }
+    }
+    Ok(unmanaged)
+}
+
+/// Replace conflicting local skills with the staged ones. The archive's executables flag is
+/// not trusted: each restored dir is scanned again.
+fn swap_in_restored(
+    store: &SkillStore,
+    staging: &Path,
+    existing: &[SkillRecord],
+    planned: Vec<PlannedSkill>,
+    report: &mut RestoreReport,
+) -> Result<Vec<SkillRecord>> {
+    let mut restored = Vec::new();
+    for PlannedSkill {
+        mut skill,
+        archived,
+        new_path,
+    } in planned
+    {
+        let staged = staging.join(&archived);
+        if !staged.is_dir() {
+            log::warn!(
+                "[backup] skipping {}: its dir is not in the backup",
+                skill.name
+            );
+            report.skipped.push(skill.name.clone());
+            continue;
+        }
+        for conflict in existing.iter().filter(|e| {
+            e.id == skill.id || e.name == skill.name || Path::new(&e.central_path) == new_path
+        }) {
+            store.delete_skill(&conflict.id)?;
+            remove_path_any(Path::new(&conflict.central_path))?;
+        }
+        remove_path_any(&new_path)?;
+        if let Some(parent) = new_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+        }
+        move_dir(&staged, &new_path)?;
+        skill.central_path = new_path.to_string_lossy().to_string();
+        skill.contains_executables = contains_executables(&new_path);
+        restored.push(skill);
+    }
+    Ok(restored)
+}
+
+fn merge_settings(
+    store: &SkillStore,
+    backup: &SkillStore,
+    strategy: RestoreStrategy,
+) -> Result<()> {
+    for (key, value) in backup.list_settings()? {
+        if LOCAL_SETTINGS.contains(&key.as_str()) {
+            continue;
+        }
+        // Credentials are encrypted with the key of the installation that made the backup; ones
+        // from another installation can't be read and are left out.
+        let value = if is_encrypted_value(&value) {
+            match store.db_key().map(|db_key| db_key.decrypt(&key, &value)) {
+                Some(Ok(value)) => value,
+                _ => continue,
+            }
+        } else {
+            value
+        };
+        if strategy == RestoreStrategy::Overwrite || store.get_setting(&key)?.is_none() {
+            store.set_setting(&key, &value)?;
+        }
+    }
+    Ok(())
+}
+
+/// The skill's dir relative to the old Central Repo, `/`-separated; its last component when it
+/// lived elsewhere. `None` for paths that would not stay inside the new repo.
+fn relative_central_dir(old_root: &Path, central_path: &Path) -> Option<String> {
+    let relative = match central_path.strip_prefix(old_root) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => PathBuf::from(central_path.file_name()?),
+    };
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+#[path = "tests/backup.rs"]
+mod tests;
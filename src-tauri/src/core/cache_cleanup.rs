@@ -1,13 +1,14 @@
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use tauri::Manager;
 
+use super::operations::{OperationRegistry, OperationState};
 use super::skill_store::SkillStore;
 
-const CACHE_DIR_NAME: &str = "skills-hub-git-cache";
+pub const CACHE_DIR_NAME: &str = "skills-hub-git-cache";
 const CACHE_META_FILE: &str = ".skills-hub-cache.json";
 pub const GIT_CACHE_CLEANUP_DAYS_KEY: &str = "git_cache_cleanup_days";
 pub const DEFAULT_GIT_CACHE_CLEANUP_DAYS: i64 = 30;
@@ -15,6 +16,13 @@ const MAX_GIT_CACHE_CLEANUP_DAYS: i64 = 3650;
 pub const GIT_CACHE_TTL_SECS_KEY: &str = "git_cache_ttl_secs";
 pub const DEFAULT_GIT_CACHE_TTL_SECS: i64 = 60;
 const MAX_GIT_CACHE_TTL_SECS: i64 = 3600;
+pub const GIT_CACHE_CLEANUP_DELAY_SECS_KEY: &str = "git_cache_cleanup_delay_secs";
+pub const DEFAULT_GIT_CACHE_CLEANUP_DELAY_SECS: i64 = 120;
+const MAX_GIT_CACHE_CLEANUP_DELAY_SECS: i64 = 24 * 60 * 60;
+/// Pause after each removed cache dir so the startup cleanup never saturates the disk.
+const STARTUP_CLEANUP_PAUSE: Duration = Duration::from_millis(200);
+/// How often a waiting startup cleanup re-reads its delay and checks for running operations.
+const STARTUP_CLEANUP_POLL: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Deserialize)]
 struct RepoCacheMeta {
@@ -53,6 +61,92 @@ pub fn set_git_cache_ttl_secs(store: &SkillStore, secs: i64) -> Result<i64> {
     Ok(secs)
 }
 
+pub fn get_git_cache_cleanup_delay_secs(store: &SkillStore) -> i64 {
+    let raw = store
+        .get_setting(GIT_CACHE_CLEANUP_DELAY_SECS_KEY)
+        .ok()
+        .flatten();
+    parse_cleanup_delay_secs(raw).unwrap_or(DEFAULT_GIT_CACHE_CLEANUP_DELAY_SECS)
+}
+
+pub fn set_git_cache_cleanup_delay_secs(store: &SkillStore, secs: i64) -> Result<i64> {
+    if !(0..=MAX_GIT_CACHE_CLEANUP_DELAY_SECS).contains(&secs) {
+        anyhow::bail!(
+            "cleanup delay seconds must be between 0 and {}",
+            MAX_GIT_CACHE_CLEANUP_DELAY_SECS
+        );
+    }
+    store.set_setting(GIT_CACHE_CLEANUP_DELAY_SECS_KEY, &secs.to_string())?;
+    Ok(secs)
+}
+
+/// Run the age-based cleanup on a background thread once the app has been up for the
+/// configured delay and no operation is running. Settings are re-read while it waits, so
+/// changing the delay or disabling cleanup (0 days) still applies to this launch.
+pub fn schedule_startup_cleanup<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    store: SkillStore,
+    operations: OperationRegistry,
+) {
+    let spawned = std::thread::Builder::new()
+        .name("git-cache-cleanup".to_string())
+        .spawn(move || {
+            let started = Instant::now();
+            loop {
+                let delay = Duration::from_secs(get_git_cache_cleanup_delay_secs(&store) as u64);
+                let elapsed = started.elapsed();
+                if elapsed < delay {
+                    std::thread::sleep((delay - elapsed).min(STARTUP_CLEANUP_POLL));
+                } else if operations
+                    .list()
+                    .iter()
+                    .any(|op| op.state == OperationState::Running)
+                {
+                    std::thread::sleep(STARTUP_CLEANUP_POLL);
+                } else {
+                    break;
+                }
+            }
+
+            let days = get_git_cache_cleanup_days(&store);
+            if days == 0 {
+                return;
+            }
+            let max_age = Duration::from_secs(days as u64 * 24 * 60 * 60);
+            let removed = app
+                .path()
+                .app_cache_dir()
+                .context("failed to resolve app cache dir")
+                .and_then(|dir| cleanup_git_cache_dirs_in(&dir, max_age, STARTUP_CLEANUP_PAUSE));
+            match removed {
+                Ok(0) => {}
+                Ok(removed) => log::info!("cleaned up {} git cache dirs", removed),
+                Err(err) => log::warn!("[cache] startup cleanup failed: {:#}", err),
+            }
+        });
+    if let Err(err) = spawned {
+        log::warn!("[cache] failed to start cleanup thread: {}", err);
+    }
+}
+
+/// The age-based cleanup at the configured number of days (none when it is 0), throttled like
+/// the startup one. Run by the scheduler.
+pub fn cleanup_stale_git_caches<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<usize> {
+    let days = get_git_cache_cleanup_days(store);
+    if days == 0 {
+        return Ok(0);
+    }
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .context("failed to resolve app cache dir")?;
+    let max_age = Duration::from_secs(days as u64 * 24 * 60 * 60);
+    cleanup_git_cache_dirs_in(&cache_dir, max_age, STARTUP_CLEANUP_PAUSE)
+}
+
 pub fn cleanup_git_cache_dirs<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     max_age: Duration,
@@ -61,10 +155,15 @@ pub fn cleanup_git_cache_dirs<R: tauri::Runtime>(
         .path()
         .app_cache_dir()
         .context("failed to resolve app cache dir")?;
-    cleanup_git_cache_dirs_in(&cache_dir, max_age)
+    cleanup_git_cache_dirs_in(&cache_dir, max_age, Duration::ZERO)
 }
 
-fn cleanup_git_cache_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usize> {
+/// `pause` is slept after each removal to throttle IO; zero for user-requested cleanups.
+fn cleanup_git_cache_dirs_in(
+    cache_dir: &Path,
+    max_age: Duration,
+    pause: Duration,
+) -> Result<usize> {
     let cache_root = cache_dir.join(CACHE_DIR_NAME);
     if !cache_root.exists() {
         return Ok(0);
@@ -121,6 +220,9 @@ fn cleanup_git_cache_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usiz
 
         if should_remove && std::fs::remove_dir_all(&path).is_ok() {
             removed += 1;
+            if !pause.is_zero() {
+                std::thread::sleep(pause);
+            }
         }
     }
 
@@ -145,9 +247,22 @@ fn parse_cache_ttl_secs(raw: Option<String>) -> Option<i64> {
     }
 }
 
+fn parse_cleanup_delay_secs(raw: Option<String>) -> Option<i64> {
+    let value = raw?.trim().parse::<i64>().ok()?;
+    if !(0..=MAX_GIT_CACHE_CLEANUP_DELAY_SECS).contains(&value) {
+        None
+    } else {
+        Some(value)
+    }
+}
+
 fn now_ms() -> i64 {
     let now = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap_or_default();
     now.as_millis() as i64
 }
+
+#[cfg(test)]
+#[path = "tests/cache_cleanup.rs"]
+mod tests;
@@ -0,0 +1,323 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use git2::build::CheckoutBuilder;
+use git2::{Commit, Index, IndexAddOption, Oid, Repository, Signature, Tree};
+use serde::Serialize;
+
+use super::git_fetcher::run_git_in;
+use super::git_host_policy::ensure_git_host_allowed;
+use super::installer::{adopt_central_dir, refresh_after_edit};
+use super::self_repair::STATUS_MISSING;
+use super::skill_store::SkillStore;
+
+pub const CENTRAL_GIT_KEY: &str = "central_repo_git";
+/// Staging, snapshot and probe dirs Skills Hub creates inside the Central Repo.
+const GITIGNORE: &str = ".skills-hub-*\n";
+const AUTHOR_NAME: &str = "Skills Hub";
+const AUTHOR_EMAIL: &str = "skills-hub@localhost";
+/// The one remote the library is pushed to and pulled from.
+const REMOTE: &str = "origin";
+
+/// What a pull changed, by top-level skill directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PullReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// Local and remote history had both moved on and were merged, rather than fast-forwarded.
+    pub merged: bool,
+}
+
+pub fn get_central_git_enabled(store: &SkillStore) -> bool {
+    store
+        .get_setting(CENTRAL_GIT_KEY)
+        .ok()
+        .flatten()
+        .is_some_and(|v| v == "true")
+}
+
+/// Turning it on initializes `central` as a git repository (if it is not one yet) and commits
+/// the current library, so history starts from what is there now.
+pub fn set_central_git_enabled(store: &SkillStore, central: &Path, enabled: bool) -> Result<bool> {
+    if enabled {
+        commit_all(central, "Start tracking skill library")?;
+    }
+    store.set_setting(CENTRAL_GIT_KEY, if enabled { "true" } else { "false" })?;
+    Ok(enabled)
+}
+
+/// Commit everything that changed in the Central Repo, if versioning is enabled. Failures are
+/// logged rather than returned: the install, update or delete itself already succeeded.
+pub fn record_central_change(store: &SkillStore, central: &Path, message: &str) {
+    if !get_central_git_enabled(store) {
+        return;
+    }
+    if let Err(err) = commit_all(central, message) {
+        log::warn!("[central-git] commit {:?} failed: {:#}", message, err);
+    }
+}
+
+pub fn get_central_remote(central: &Path) -> Option<String> {
+    let repo = Repository::open(central).ok()?;
+    let remote = repo.find_remote(REMOTE).ok()?;
+    remote.url().map(str::to_string)
+}
+
+/// Point the library at `url`, or forget the remote when `url` is empty.
+pub fn set_central_remote(store: &SkillStore, central: &Path, url: &str) -> Result<Option<String>> {
+    ensure_enabled(store)?;
+    let repo = open_or_init(central)?;
+    let url = url.trim();
+    if url.is_empty() {
+        if repo.find_remote(REMOTE).is_ok() {
+            repo.remote_delete(REMOTE)?;
+        }
+        return Ok(None);
+    }
+    ensure_git_host_allowed(store, url)?;
+    if repo.find_remote(REMOTE).is_ok() {
+        repo.remote_set_url(REMOTE, url)?;
+    } else {
+        repo.remote(REMOTE, url)?;
+    }
+    Ok(Some(url.to_string()))
+}
+
+/// Commit pending changes and push the current branch. A remote that has moved on is reported
+/// as `SYNC_REJECTED` so the user pulls first. Returns the pushed branch.
+pub fn push_central_repo(store: &SkillStore, central: &Path) -> Result<String> {
+    let (_repo, branch) = prepare_sync(store, central)?;
+    let refspec = format!("HEAD:refs/heads/{}", branch);
+    let out = run_git_in(central, &["push", REMOTE, &refspec])?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        if stderr.contains("[rejected]") || stderr.contains("non-fast-forward") {
+            anyhow::bail!("SYNC_REJECTED|{}", branch);
+        }
+        anyhow::bail!("git push failed: {}", stderr.trim());
+    }
+    Ok(branch)
+}
+
+/// Commit pending changes, fetch the remote branch and fast-forward or merge it. The merge is
+/// computed in memory first: when both sides changed the same files it fails with
+/// `SYNC_CONFLICT|<skills>` and the library is left untouched. Pulled skills are then adopted,
+/// refreshed or flagged missing in the database.
+pub fn pull_central_repo(store: &SkillStore, central: &Path) -> Result<PullReport> {
+    let (repo, branch) = prepare_sync(store, central)?;
+    let out = run_git_in(central, &["fetch", "--no-tags", REMOTE])?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "git fetch failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    let remote_ref = format!("refs/remotes/{}/{}", REMOTE, branch);
+    let Ok(theirs) = repo
+        .find_reference(&remote_ref)
+        .and_then(|r| r.peel_to_commit())
+    else {
+        // Nothing has been pushed to this branch yet.
+        return Ok(PullReport::default());
+    };
+    let ours = repo.head()?.peel_to_commit()?;
+    if ours.id() == theirs.id() || repo.graph_descendant_of(ours.id(), theirs.id())? {
+        return Ok(PullReport::default());
+    }
+
+    let fast_forward = repo.merge_base(ours.id(), theirs.id()).ok() == Some(ours.id());
+    let new_head = if fast_forward {
+        theirs.id()
+    } else {
+        let mut index = repo.merge_commits(&ours, &theirs, None)?;
+        if index.has_conflicts() {
+            anyhow::bail!("SYNC_CONFLICT|{}", conflicted_skills(&index)?.join(","));
+        }
+        let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+        let signature = signature(&repo)?;
+        repo.commit(
+            None,
+            &signature,
+            &signature,
+            &format!("Merge {}/{}", REMOTE, branch),
+            &tree,
+            &[&ours, &theirs],
+        )?
+    };
+
+    let target = repo.find_commit(new_head)?;
+    repo.checkout_tree(target.as_object(), Some(CheckoutBuilder::new().force()))?;
+    repo.reference(
+        &format!("refs/heads/{}", branch),
+        new_head,
+        true,
+        "skills-hub: pull",
+    )?;
+
+    let mut report = diff_skills(&repo, &ours, &target)?;
+    report.merged = !fast_forward;
+    reconcile_pulled(store, central, &mut report)?;
+    log::info!(
+        "[central-git] pulled {}: {} added, {} updated, {} removed",
+        branch,
+        report.added.len(),
+        report.updated.len(),
+        report.removed.len()
+    );
+    Ok(report)
+}
+
+fn ensure_enabled(store: &SkillStore) -> Result<()> {
+    if !get_central_git_enabled(store) {
+        anyhow::bail!("git versioning of the central repo is not enabled");
+    }
+    Ok(())
+}
+
+/// Check the remote is set and allowed, and commit local work so it takes part in the sync.
+fn prepare_sync(store: &SkillStore, central: &Path) -> Result<(Repository, String)> {
+    ensure_enabled(store)?;
+    let url = get_central_remote(central)
+        .ok_or_else(|| anyhow::anyhow!("no remote is configured for the central repo"))?;
+    ensure_git_host_allowed(store, &url)?;
+    commit_all(central, "Sync local changes")?;
+    let repo = Repository::open(central)?;
+    let branch = repo
+        .head()?
+        .shorthand()
+        .context("central repo HEAD is not on a branch")?
+        .to_string();
+    Ok((repo, branch))
+}
+
+/// Top-level directories of conflicted paths: each one is a skill.
+fn conflicted_skills(index: &Index) -> Result<Vec<String>> {
+    let mut skills = BTreeSet::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            let path = String::from_utf8_lossy(&entry.path).to_string();
+            skills.insert(path.split('/').next().unwrap_or(&path).to_string());
+        }
+    }
+    Ok(skills.into_iter().collect())
+}
+
+/// Classify the skill directories that differ between `old` and `new`. Top-level files such as
+/// `.gitignore` are not skills.
+fn diff_skills(repo: &Repository, old: &Commit, new: &Commit) -> Result<PullReport> {
+    let (old_tree, new_tree): (Tree, Tree) = (old.tree()?, new.tree()?);
+    let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+    let mut names = BTreeSet::new();
+    for delta in diff.deltas() {
+        let path = delta.new_file().path().or_else(|| delta.old_file().path());
+        let mut components = path.into_iter().flat_map(|p| p.components());
+        if let (Some(first), Some(_)) = (components.next(), components.next()) {
+            names.insert(first.as_os_str().to_string_lossy().to_string());
+        }
+    }
+    let mut report = PullReport::default();
+    for name in names {
+        match (
+            old_tree.get_name(&name).is_some(),
+            new_tree.get_name(&name).is_some(),
+        ) {
+            (false, _) => report.added.push(name),
+            (true, true) => report.updated.push(name),
+            (true, false) => report.removed.push(name),
+        }
+    }
+    Ok(report)
+}
+
+/// Bring the database in line with pulled content. Directories the other machine added are
+/// adopted as local skills; skills it removed are flagged missing, not deleted, so their
+/// targets can still be cleaned up here.
+fn reconcile_pulled(store: &SkillStore, central: &Path, report: &mut PullReport) -> Result<()> {
+    let skills = store.list_skills()?;
+    let managed = |name: &str| {
+        let path = central.join(name);
+        skills
+            .iter()
+            .find(|s| Path::new(&s.central_path) == path)
+            .cloned()
+    };
+    for name in report.added.iter().chain(&report.updated) {
+        let path = central.join(name);
+        let result = match managed(name) {
+            Some(record) => refresh_after_edit(store, record, path).map(|_| ()),
+            None if path.join("SKILL.md").exists() => {
+                adopt_central_dir(store, name.clone(), &path).map(|_| ())
+            }
+            None => Ok(()),
+        };
+        if let Err(err) = result {
+            log::warn!(
+                "[central-git] refreshing pulled skill {} failed: {:#}",
+                name,
+                err
+            );
+        }
+    }
+    for name in &report.removed {
+        if let Some(record) = managed(name) {
+            store.set_skill_status(&record.id, STATUS_MISSING)?;
+        }
+    }
+    Ok(())
+}
+
+fn open_or_init(central: &Path) -> Result<Repository> {
+    if let Ok(repo) = Repository::open(central) {
+        return Ok(repo);
+    }
+    std::fs::create_dir_all(central).with_context(|| format!("create {:?}", central))?;
+    let repo = Repository::init(central).with_context(|| format!("git init {:?}", central))?;
+    let gitignore = central.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(&gitignore, GITIGNORE).with_context(|| format!("write {:?}", gitignore))?;
+    }
+    log::info!("[central-git] initialized repository in {:?}", central);
+    Ok(repo)
+}
+
+/// Stage all additions, edits and deletions and commit them. `None` when nothing changed.
+fn commit_all(central: &Path, message: &str) -> Result<Option<Oid>> {
+    let repo = open_or_init(central)?;
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    index.write()?;
+    let tree_id = index.write_tree()?;
+
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+    let tree = repo.find_tree(tree_id)?;
+    let signature = signature(&repo)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?;
+    Ok(Some(oid))
+}
+
+/// The user's git identity when they have one, so history reads like their own.
+fn signature(repo: &Repository) -> Result<Signature<'static>> {
+    Ok(repo
+        .signature()
+        .or_else(|_| Signature::now(AUTHOR_NAME, AUTHOR_EMAIL))?)
+}
+
+#[cfg(test)]
+#[path = "tests/central_git.rs"]
+mod tests;
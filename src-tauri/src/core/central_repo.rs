@@ -1,12 +1,48 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use anyhow::{Context, Result};
 use dirs::home_dir;
+use serde::Serialize;
 use tauri::Manager;
+use uuid::Uuid;
 
-use super::skill_store::SkillStore;
+use super::installer::ROLLBACK_DIR_NAME;
+use super::skill_lock::lock_skill;
+use super::skill_store::{
+    CentralRepoRecord, ProjectTargetRecord, SkillRecord, SkillStore, SkillTargetRecord,
+};
+use super::skill_sync::enabled_status;
+use super::sync_engine::{copy_dir_recursive, remove_path_any, sync_dir_for_tool_with_overwrite};
+use super::tool_adapters::{default_tool_adapters, resolve_default_path};
 
 const CENTRAL_DIR_NAME: &str = ".skillshub";
+/// Id of the Central Repo at `central_repo_path`; its skills have no `repo_id`.
+pub const DEFAULT_REPO_ID: &str = "default";
+/// Setting naming the repo new installs go to.
+pub const INSTALL_REPO_KEY: &str = "install_repo";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CentralRepoView {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    /// Whether new installs go here.
+    pub install_target: bool,
+    pub skills: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CentralRepoMove {
+    pub path: String,
+    /// Skills now served from the new location.
+    pub moved: Vec<String>,
+    /// Targets pointed at the new location, as `name:tool` (`name:tool@project` inside projects).
+    pub retargeted: Vec<String>,
+    /// Targets that could not be re-created, with the error.
+    pub failed: Vec<String>,
+    /// Old skill directories left in place because a target still depends on them.
+    pub kept: Vec<String>,
+}
 
 pub fn resolve_central_repo_path<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
@@ -32,6 +68,564 @@ pub fn ensure_central_repo(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// A directory in a Central Repo that no skill is recorded at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OrphanDir {
+    pub name: String,
+    pub path: String,
+    pub repo_id: String,
+    /// Only directories with a SKILL.md can be adopted.
+    pub has_skill_md: bool,
+}
+
+/// The default repo first, then the added ones by name.
+pub fn list_central_repos<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<Vec<CentralRepoView>> {
+    let skills = store.list_skills()?;
+    let install = install_repo_id(store)?;
+    let count = |id: Option<&str>| skills.iter().filter(|s| s.repo_id.as_deref() == id).count();
+    let mut repos = vec![CentralRepoView {
+        id: DEFAULT_REPO_ID.to_string(),
+        name: "Default".to_string(),
+        path: resolve_central_repo_path(app, store)?
+            .to_string_lossy()
+            .to_string(),
+        install_target: install.is_none(),
+        skills: count(None),
+    }];
+    for record in store.list_central_repos()? {
+        repos.push(CentralRepoView {
+            install_target: install.as_deref() == Some(record.id.as_str()),
+            skills: count(Some(&record.id)),
+            id: record.id,
+            name: record.name,
+            path: record.path,
+        });
+    }
+    Ok(repos)
+}
+
+/// Add another Central Repo at `path`, which may not overlap an existing one or anything
+/// `check_central_repo_location` refuses.
+pub fn add_central_repo<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    name: &str,
+    path: &Path,
+    now: i64,
+) -> Result<CentralRepoRecord> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("repo name is empty");
+    }
+    if !path.is_absolute() {
+        anyhow::bail!("storage path must be absolute");
+    }
+    check_central_repo_location(app, path)?;
+    let existing = store.list_central_repos()?;
+    if existing.iter().any(|r| r.name.eq_ignore_ascii_case(name)) {
+        anyhow::bail!("a repo named {:?} already exists", name);
+    }
+    check_repo_overlap(app, store, path, None)?;
+    ensure_central_repo(path)?;
+    ensure_writable(path)?;
+    let record = CentralRepoRecord {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        created_at: now,
+    };
+    store.insert_central_repo(&record)?;
+    Ok(record)
+}
+
+/// Forget an added repo. Its skills must be moved out first; the directory is left on disk.
+pub fn remove_central_repo(store: &SkillStore, repo_id: &str) -> Result<()> {
+    if repo_id == DEFAULT_REPO_ID {
+        anyhow::bail!("the default repo cannot be removed");
+    }
+    get_central_repo(store, repo_id)?;
+    if store
+        .list_skills()?
+        .iter()
+        .any(|s| s.repo_id.as_deref() == Some(repo_id))
+    {
+        anyhow::bail!("the repo still holds skills; move them to another repo first");
+    }
+    store.delete_central_repo(repo_id)?;
+    if install_repo_id(store)?.as_deref() == Some(repo_id) {
+        store.delete_setting(INSTALL_REPO_KEY)?;
+    }
+    Ok(())
+}
+
+/// Send new installs to `repo_id`.
+pub fn set_install_repo(store: &SkillStore, repo_id: &str) -> Result<()> {
+    if repo_id == DEFAULT_REPO_ID {
+        return store.delete_setting(INSTALL_REPO_KEY);
+    }
+    get_central_repo(store, repo_id)?;
+    store.set_setting(INSTALL_REPO_KEY, repo_id)
+}
+
+/// Where the repo with `repo_id` lives; `None` and `DEFAULT_REPO_ID` are the default repo.
+pub fn resolve_repo_path<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    repo_id: Option<&str>,
+) -> Result<PathBuf> {
+    match repo_id.filter(|id| *id != DEFAULT_REPO_ID) {
+        Some(id) => Ok(PathBuf::from(get_central_repo(store, id)?.path)),
+        None => resolve_central_repo_path(app, store),
+    }
+}
+
+/// Where new installs go: the chosen repo, or the default one.
+pub fn resolve_install_repo_path<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<PathBuf> {
+    resolve_repo_path(app, store, install_repo_id(store)?.as_deref())
+}
+
+/// Every Central Repo, the default one first.
+pub fn central_repo_paths<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![resolve_central_repo_path(app, store)?];
+    paths.extend(
+        store
+            .list_central_repos()?
+            .into_iter()
+            .map(|r| PathBuf::from(r.path)),
+    );
+    Ok(paths)
+}
+
+/// The added repo `central_path` sits directly in; `None` for the default one.
+pub(crate) fn repo_id_for_path(store: &SkillStore, central_path: &Path) -> Result<Option<String>> {
+    let Some(parent) = central_path.parent() else {
+        return Ok(None);
+    };
+    Ok(store
+        .list_central_repos()?
+        .into_iter()
+        .find(|r| Path::new(&r.path) == parent)
+        .map(|r| r.id))
+}
+
+/// Move one skill into another repo, re-creating its targets like `move_central_repo`. The old
+/// directory stays if a target could not be moved off it.
+pub fn move_skill_to_repo<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    skill_id: &str,
+    repo_id: &str,
+    now: i64,
+) -> Result<CentralRepoMove> {
+    let new_base = resolve_repo_path(app, store, Some(repo_id))?;
+    let repo_id = Some(repo_id.to_string()).filter(|id| id != DEFAULT_REPO_ID);
+    let _lock = lock_skill(skill_id, "move_skill_to_repo")?;
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    if skill.repo_id == repo_id {
+        anyhow::bail!("skill is already in that repo");
+    }
+    let old_path = PathBuf::from(&skill.central_path);
+    if !old_path.is_dir() {
+        anyhow::bail!("central path not found: {:?}", old_path);
+    }
+    let file_name = old_path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path: {:?}", old_path))?;
+    let new_path = new_base.join(file_name);
+    if std::fs::symlink_metadata(&new_path).is_ok() {
+        anyhow::bail!("target path already exists: {:?}", new_path);
+    }
+    ensure_central_repo(&new_base)?;
+    if let Err(err) = copy_dir_recursive(&old_path, &new_path) {
+        let _ = remove_path_any(&new_path);
+        return Err(err.context(format!("copy {:?} -> {:?}", old_path, new_path)));
+    }
+
+    let mut report = CentralRepoMove {
+        path: new_base.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    store.upsert_skill(&SkillRecord {
+        central_path: new_path.to_string_lossy().to_string(),
+        repo_id,
+        updated_at: now,
+        ..skill.clone()
+    })?;
+    report.moved.push(skill.name.clone());
+    let stranded = retarget_skill(store, &skill, &new_path, now, &mut report)?;
+    move_rollback_snapshot(&old_path, &new_base, &skill.id);
+    if stranded {
+        report.kept.push(old_path.to_string_lossy().to_string());
+    } else if let Err(err) = remove_path_any(&old_path) {
+        log::warn!("[central-repo] failed to remove {:?}: {:#}", old_path, err);
+        report.kept.push(old_path.to_string_lossy().to_string());
+    }
+    Ok(report)
+}
+
+/// Directories in the Central Repos without a skill record, such as manual copies or what a
+/// crashed install left behind. Hidden entries (rollback snapshots, install staging, git data)
+/// are not skills and are skipped.
+pub fn scan_central_repo<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<Vec<OrphanDir>> {
+    let managed: Vec<PathBuf> = store
+        .list_skills()?
+        .into_iter()
+        .map(|s| PathBuf::from(s.central_path))
+        .collect();
+    let mut repos = vec![(
+        DEFAULT_REPO_ID.to_string(),
+        resolve_central_repo_path(app, store)?,
+    )];
+    repos.extend(
+        store
+            .list_central_repos()?
+            .into_iter()
+            .map(|r| (r.id, PathBuf::from(r.path))),
+    );
+    let mut orphans = Vec::new();
+    for (repo_id, base) in repos {
+        let Ok(entries) = std::fs::read_dir(&base) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if name.starts_with('.') || !entry.file_type()?.is_dir() || managed.contains(&path) {
+                continue;
+            }
+            orphans.push(OrphanDir {
+                has_skill_md: path.join("SKILL.md").is_file(),
+                path: path.to_string_lossy().to_string(),
+                repo_id: repo_id.clone(),
+                name,
+            });
+        }
+    }
+    orphans.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+    Ok(orphans)
+}
+
+fn get_central_repo(store: &SkillStore, repo_id: &str) -> Result<CentralRepoRecord> {
+    store
+        .list_central_repos()?
+        .into_iter()
+        .find(|r| r.id == repo_id)
+        .ok_or_else(|| anyhow::anyhow!("repo not found"))
+}
+
+/// The added repo new installs go to, if it still exists.
+fn install_repo_id(store: &SkillStore) -> Result<Option<String>> {
+    let Some(id) = store.get_setting(INSTALL_REPO_KEY)? else {
+        return Ok(None);
+    };
+    Ok(store
+        .list_central_repos()?
+        .into_iter()
+        .any(|r| r.id == id)
+        .then_some(id))
+}
+
+/// Refuse a repo location inside or around another repo's, other than the one with `except`.
+fn check_repo_overlap<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    path: &Path,
+    except: Option<&str>,
+) -> Result<()> {
+    let path = resolve_existing_prefix(path);
+    let mut repos = vec![(
+        DEFAULT_REPO_ID.to_string(),
+        resolve_central_repo_path(app, store)?,
+    )];
+    repos.extend(
+        store
+            .list_central_repos()?
+            .into_iter()
+            .map(|r| (r.id, PathBuf::from(r.path))),
+    );
+    for (id, other) in repos {
+        if except == Some(id.as_str()) {
+            continue;
+        }
+        let other = resolve_existing_prefix(&other);
+        if path.starts_with(&other) || other.starts_with(&path) {
+            anyhow::bail!("storage path overlaps another Central Repo: {:?}", other);
+        }
+    }
+    Ok(())
+}
+
+/// Move the library to `new_base`. Every skill is copied first; if any copy fails the copies are
+/// removed and nothing else changes. Then each skill's `central_path` is updated, symlink targets
+/// are re-created and copy targets re-copied, and the setting is switched. Only then are the old
+/// directories removed, except those a target could not be moved off. The old Central Repo
+/// itself goes once it is empty, so anything else in it (e.g. git history) stays. Skills in
+/// added repos stay where they are.
+pub fn move_central_repo<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    new_base: &Path,
+    now: i64,
+) -> Result<CentralRepoMove> {
+    if !new_base.is_absolute() {
+        anyhow::bail!("storage path must be absolute");
+    }
+    check_central_repo_location(app, new_base)?;
+    let old_base = resolve_central_repo_path(app, store)?;
+    if resolve_existing_prefix(new_base).starts_with(resolve_existing_prefix(&old_base))
+        && new_base != old_base
+    {
+        anyhow::bail!("storage path cannot be inside the current one");
+    }
+    check_repo_overlap(app, store, new_base, Some(DEFAULT_REPO_ID))?;
+    ensure_central_repo(new_base)?;
+    ensure_writable(new_base)?;
+    let mut report = CentralRepoMove {
+        path: new_base.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    if new_base == old_base {
+        store.set_setting("central_repo_path", &report.path)?;
+        return Ok(report);
+    }
+
+    let skills: Vec<SkillRecord> = store
+        .list_skills()?
+        .into_iter()
+        .filter(|s| s.repo_id.is_none())
+        .collect();
+    let _locks = skills
+        .iter()
+        .map(|skill| lock_skill(&skill.id, "move_central_repo"))
+        .collect::<Result<Vec<_>>>()?;
+    let mut plan = Vec::new();
+    for skill in skills {
+        let old_path = PathBuf::from(&skill.central_path);
+        if !old_path.is_dir() {
+            anyhow::bail!("central path not found: {:?}", old_path);
+        }
+        let file_name = old_path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("invalid central path: {:?}", old_path))?;
+        let new_path = new_base.join(file_name);
+        if std::fs::symlink_metadata(&new_path).is_ok() {
+            anyhow::bail!("target path already exists: {:?}", new_path);
+        }
+        plan.push((skill, old_path, new_path));
+    }
+
+    let mut copied: Vec<&Path> = Vec::new();
+    for (_, old_path, new_path) in &plan {
+        if let Err(err) = copy_dir_recursive(old_path, new_path) {
+            for dir in copied.iter().chain([&new_path.as_path()]) {
+                let _ = remove_path_any(dir);
+            }
+            return Err(err.context(format!("copy {:?} -> {:?}", old_path, new_path)));
+        }
+        copied.push(new_path);
+    }
+
+    let mut retire = Vec::new();
+    for (skill, old_path, new_path) in &plan {
+        store.upsert_skill(&SkillRecord {
+            central_path: new_path.to_string_lossy().to_string(),
+            updated_at: now,
+            ..skill.clone()
+        })?;
+        report.moved.push(skill.name.clone());
+        let stranded = retarget_skill(store, skill, new_path, now, &mut report)?;
+        move_rollback_snapshot(old_path, new_base, &skill.id);
+        if stranded {
+            report.kept.push(old_path.to_string_lossy().to_string());
+        } else {
+            retire.push(old_path);
+        }
+    }
+    store.set_setting("central_repo_path", &report.path)?;
+
+    for old_path in retire {
+        if let Err(err) = remove_path_any(old_path) {
+            log::warn!("[central-repo] failed to remove {:?}: {:#}", old_path, err);
+            report.kept.push(old_path.to_string_lossy().to_string());
+        }
+    }
+    let _ = std::fs::remove_dir(old_base.join(ROLLBACK_DIR_NAME));
+    let _ = std::fs::remove_dir(&old_base);
+    log::info!(
+        "[central-repo] moved {} skills to {:?}",
+        report.moved.len(),
+        new_base
+    );
+    Ok(report)
+}
+
+/// Re-create the skill's global and project targets from `new_path`. Returns whether any could
+/// not be, leaving it on the old directory.
+fn retarget_skill(
+    store: &SkillStore,
+    skill: &SkillRecord,
+    new_path: &Path,
+    now: i64,
+    report: &mut CentralRepoMove,
+) -> Result<bool> {
+    let mut stranded = false;
+    for target in store.list_skill_targets(&skill.id)? {
+        let label = format!("{}:{}", skill.name, target.tool);
+        let path = PathBuf::from(&target.target_path);
+        match sync_dir_for_tool_with_overwrite(&target.tool, new_path, &path, true) {
+            Ok(outcome) => {
+                store.upsert_skill_target(&SkillTargetRecord {
+                    mode: outcome.mode_used.as_key().to_string(),
+                    status: enabled_status(&target.status).to_string(),
+                    last_error: None,
+                    synced_at: Some(now),
+                    ..target
+                })?;
+                report.retargeted.push(label);
+            }
+            Err(err) => {
+                stranded = true;
+                report.failed.push(format!("{}: {:#}", label, err));
+            }
+        }
+    }
+    for target in store.list_skill_project_targets(&skill.id)? {
+        let label = format!("{}:{}@{}", skill.name, target.tool, target.project);
+        let path = PathBuf::from(&target.target_path);
+        match sync_dir_for_tool_with_overwrite(&target.tool, new_path, &path, true) {
+            Ok(outcome) => {
+                store.upsert_project_target(&ProjectTargetRecord {
+                    mode: outcome.mode_used.as_key().to_string(),
+                    synced_at: now,
+                    ..target
+                })?;
+                report.retargeted.push(label);
+            }
+            Err(err) => {
+                stranded = true;
+                report.failed.push(format!("{}: {:#}", label, err));
+            }
+        }
+    }
+    Ok(stranded)
+}
+
+/// Carry the skill's rollback snapshot along; without it the last update cannot be undone, which
+/// is not worth failing the move over.
+fn move_rollback_snapshot(old_path: &Path, new_base: &Path, skill_id: &str) {
+    let Some(old_root) = old_path.parent() else {
+        return;
+    };
+    let from = old_root.join(ROLLBACK_DIR_NAME).join(skill_id);
+    if !from.is_dir() {
+        return;
+    }
+    let to = new_base.join(ROLLBACK_DIR_NAME).join(skill_id);
+    match copy_dir_recursive(&from, &to) {
+        Ok(()) => {
+            let _ = remove_path_any(&from);
+        }
+        Err(err) => log::warn!("[central-repo] rollback snapshot not moved: {:#}", err),
+    }
+}
+
+/// Refuse Central Repo locations that overlap something else Skills Hub manages: the filesystem
+/// root, the home dir itself, a tool's skills dir (syncs would write into the repo they read
+/// from) or the app cache holding git clones (cleanup would delete managed skills).
+pub fn check_central_repo_location<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    path: &Path,
+) -> Result<()> {
+    let tool_dirs: Vec<PathBuf> = default_tool_adapters()
+        .iter()
+        .filter_map(|adapter| resolve_default_path(adapter).ok())
+        .collect();
+    let cache_dir = app.path().app_cache_dir().ok();
+    check_location_in(
+        path,
+        home_dir().as_deref(),
+        &tool_dirs,
+        cache_dir.as_deref(),
+    )
+}
+
+fn check_location_in(
+    path: &Path,
+    home: Option<&Path>,
+    tool_dirs: &[PathBuf],
+    cache_dir: Option<&Path>,
+) -> Result<()> {
+    let path = resolve_existing_prefix(path);
+    if path.parent().is_none() {
+        anyhow::bail!("storage path cannot be the filesystem root");
+    }
+    if home.is_some_and(|home| path == resolve_existing_prefix(home)) {
+        anyhow::bail!("storage path cannot be the home directory itself");
+    }
+    if let Some(dir) = tool_dirs
+        .iter()
+        .find(|dir| path.starts_with(resolve_existing_prefix(dir)))
+    {
+        anyhow::bail!(
+            "storage path cannot be inside a tool skills directory: {:?}",
+            dir
+        );
+    }
+    if let Some(cache_dir) = cache_dir.filter(|dir| path.starts_with(resolve_existing_prefix(dir)))
+    {
+        anyhow::bail!(
+            "storage path cannot be inside the app cache: {:?}",
+            cache_dir
+        );
+    }
+    Ok(())
+}
+
+/// Canonical form of the deepest existing ancestor with the rest re-appended (its `..` applied
+/// lexically), so neither links nor `..` hide an overlap with a directory that already exists.
+fn resolve_existing_prefix(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(mut resolved) = ancestor.canonicalize() {
+            let rest = path.strip_prefix(ancestor).unwrap_or(Path::new(""));
+            for component in rest.components() {
+                match component {
+                    Component::ParentDir => {
+                        resolved.pop();
+                    }
+                    Component::CurDir => {}
+                    other => resolved.push(other),
+                }
+            }
+            return resolved;
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Fail unless files can be created in `path`, which must already exist.
+pub fn ensure_writable(path: &Path) -> Result<()> {
+    let probe = path.join(".skills-hub-write-test");
+    std::fs::write(&probe, b"")
+        .with_context(|| format!("storage path is not writable: {:?}", path))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
 #[cfg(test)]
 #[path = "tests/central_repo.rs"]
 mod tests;
@@ -0,0 +1,351 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::installer::{
+    fetch_git_checkout, install_git_folder, install_local_skill_with_options, InstallOptions,
+    InstallResult,
+};
+use super::skill_files::resolve_in_skill;
+use super::skill_store::SkillStore;
+
+const PLUGIN_MANIFEST: &str = ".claude-plugin/plugin.json";
+const MARKETPLACE_MANIFEST: &str = ".claude-plugin/marketplace.json";
+/// Where a plugin keeps its skills unless its manifest names other paths as well.
+const DEFAULT_SKILLS_DIR: &str = "skills";
+
+/// `"./path"` or `["./a", "./b"]` in plugin and marketplace manifests.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum PathList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl PathList {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(path) => vec![path],
+            Self::Many(paths) => paths,
+        }
+    }
+}
+
+/// The parts of `plugin.json` that locate skills; agents, commands and hooks are not imported.
+#[derive(Clone, Debug, Default, Deserialize)]
+struct PluginManifest {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    skills: Option<PathList>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct MarketplaceMetadata {
+    #[serde(default, rename = "pluginRoot")]
+    plugin_root: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MarketplaceEntry {
+    name: String,
+    source: serde_json::Value,
+    #[serde(default)]
+    skills: Option<PathList>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MarketplaceManifest {
+    #[serde(default)]
+    metadata: MarketplaceMetadata,
+    #[serde(default)]
+    plugins: Vec<MarketplaceEntry>,
+}
+
+/// A skill directory shipped inside a plugin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginSkill {
+    pub plugin: String,
+    pub name: String,
+    /// Relative to the package root, `/`-separated.
+    pub subpath: String,
+}
+
+/// A marketplace plugin hosted elsewhere (a GitHub repo or git URL); install it by its source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ExternalPlugin {
+    pub name: String,
+    pub source: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PluginScan {
+    pub plugins: Vec<String>,
+    pub skills: Vec<PluginSkill>,
+    pub external: Vec<ExternalPlugin>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PluginImportFailure {
+    pub plugin: String,
+    pub skill: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PluginImportReport {
+    pub plugins: Vec<String>,
+    /// Names of the skills installed.
+    pub installed: Vec<String>,
+    pub failed: Vec<PluginImportFailure>,
+    pub external: Vec<ExternalPlugin>,
+}
+
+/// Find the skills of the plugin or marketplace at `root`. `selected` limits a marketplace to
+/// the named plugins; empty takes all of them.
+pub fn scan_plugin_package(root: &Path, selected: &[String]) -> Result<PluginScan> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", root))?;
+    let mut scan = PluginScan::default();
+    let marketplace = root.join(MARKETPLACE_MANIFEST);
+    if marketplace.is_file() {
+        let manifest: MarketplaceManifest = read_json(&marketplace)?;
+        for entry in manifest.plugins {
+            if !selected.is_empty() && !selected.contains(&entry.name) {
+                continue;
+            }
+            let Some(source) = entry.source.as_str() else {
+                scan.external.push(ExternalPlugin {
+                    source: external_source(&entry.source),
+                    name: entry.name,
+                });
+                continue;
+            };
+            let relative = match (source.starts_with("./"), &manifest.metadata.plugin_root) {
+                (false, Some(plugin_root)) => format!("{}/{}", plugin_root, source),
+                _ => source.to_string(),
+            };
+            let plugin_dir = resolve_dir(&root, &relative)?;
+            let manifest = read_plugin_manifest(&plugin_dir)?;
+            let mut paths = manifest.skills.map(PathList::into_vec).unwrap_or_default();
+            paths.extend(entry.skills.map(PathList::into_vec).unwrap_or_default());
+            collect_plugin(&root, &plugin_dir, &entry.name, &paths, &mut scan)?;
+        }
+        return Ok(scan);
+    }
+    if !root.join(PLUGIN_MANIFEST).is_file() {
+        anyhow::bail!(
+            "no {} or {} found in {:?}",
+            PLUGIN_MANIFEST,
+            MARKETPLACE_MANIFEST,
+            root
+        );
+    }
+    let manifest = read_plugin_manifest(&root)?;
+    let name = manifest.name.unwrap_or_else(|| dir_name(&root));
+    let paths = manifest.skills.map(PathList::into_vec).unwrap_or_default();
+    collect_plugin(&root, &root, &name, &paths, &mut scan)?;
+    Ok(scan)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("read {:?}", path))?;
+    serde_json::from_str(&text).with_context(|| format!("parse {:?}", path))
+}
+
+/// Plugins in a marketplace may omit `plugin.json` and be described by the entry alone.
+fn read_plugin_manifest(plugin_dir: &Path) -> Result<PluginManifest> {
+    let path = plugin_dir.join(PLUGIN_MANIFEST);
+    if path.is_file() {
+        read_json(&path)
+    } else {
+        Ok(PluginManifest::default())
+    }
+}
+
+fn external_source(source: &serde_json::Value) -> String {
+    let field = |key: &str| source.get(key).and_then(|v| v.as_str());
+    match (field("source"), field("repo"), field("url")) {
+        (Some("github"), Some(repo), _) => format!("https://github.com/{}", repo),
+        (_, _, Some(url)) => url.to_string(),
+        _ => source.to_string(),
+    }
+}
+
+/// Manifest paths stay inside the package: `..`, absolute paths and escaping links are refused.
+fn resolve_dir(root: &Path, relative: &str) -> Result<PathBuf> {
+    let trimmed = relative.trim().trim_end_matches('/');
+    if matches!(trimmed, "" | ".") {
+        return Ok(root.to_path_buf());
+    }
+    resolve_in_skill(root, trimmed)
+}
+
+fn collect_plugin(
+    root: &Path,
+    plugin_dir: &Path,
+    plugin: &str,
+    extra_paths: &[String],
+    scan: &mut PluginScan,
+) -> Result<()> {
+    scan.plugins.push(plugin.to_string());
+    let mut dirs = vec![plugin_dir.join(DEFAULT_SKILLS_DIR)];
+    for path in extra_paths {
+        dirs.push(resolve_dir(plugin_dir, path)?);
+    }
+    for dir in dirs {
+        for skill_dir in skill_dirs_in(&dir) {
+            let subpath = skill_dir
+                .strip_prefix(root)
+                .unwrap_or(&skill_dir)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            if scan.skills.iter().any(|s| s.subpath == subpath) {
+                continue;
+            }
+            scan.skills.push(PluginSkill {
+                plugin: plugin.to_string(),
+                name: dir_name(&skill_dir),
+                subpath,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// `dir` itself when it is a skill, otherwise its immediate subdirectories that are.
+fn skill_dirs_in(dir: &Path) -> Vec<PathBuf> {
+    if dir.join("SKILL.md").is_file() {
+        return vec![dir.to_path_buf()];
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("SKILL.md").is_file())
+        .collect();
+    found.sort();
+    found
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "plugin".to_string())
+}
+
+/// Where a package being installed was found.
+enum Package {
+    Local(PathBuf),
+    /// A git checkout; `base` is the folder a GitHub folder URL pointed at.
+    Git {
+        repo_dir: PathBuf,
+        base: Option<String>,
+    },
+}
+
+impl Package {
+    fn root(&self) -> Result<PathBuf> {
+        match self {
+            Self::Local(root) => Ok(root.clone()),
+            Self::Git {
+                repo_dir,
+                base: Some(base),
+            } => resolve_dir(repo_dir, base),
+            Self::Git { repo_dir, .. } => Ok(repo_dir.clone()),
+        }
+    }
+
+    fn install<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        store: &SkillStore,
+        source: &str,
+        skill: &PluginSkill,
+        options: InstallOptions,
+    ) -> Result<InstallResult> {
+        match self {
+            Self::Local(root) => install_local_skill_with_options(
+                app,
+                store,
+                &root.join(&skill.subpath),
+                Some(skill.name.clone()),
+                options,
+            ),
+            Self::Git { repo_dir, base } => {
+                let subpath = match base {
+                    Some(base) => format!("{}/{}", base.trim_end_matches('/'), skill.subpath),
+                    None => skill.subpath.clone(),
+                };
+                install_git_folder(
+                    app,
+                    store,
+                    source,
+                    repo_dir,
+                    &subpath,
+                    skill.name.clone(),
+                    options,
+                )
+            }
+        }
+    }
+}
+
+/// Install the skills of a Claude Code plugin or marketplace from a local path or a git URL.
+/// Each skill goes through the usual install gates; one that fails is reported and the rest
+/// still install.
+pub fn install_plugin_package<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source: &str,
+    selected: &[String],
+    options: InstallOptions,
+) -> Result<PluginImportReport> {
+    let local = Path::new(source);
+    let package = if local.exists() {
+        Package::Local(
+            local
+                .canonicalize()
+                .with_context(|| format!("resolve {:?}", local))?,
+        )
+    } else {
+        let (repo_dir, base) = fetch_git_checkout(app, store, source)?;
+        Package::Git { repo_dir, base }
+    };
+    let scan = scan_plugin_package(&package.root()?, selected)?;
+
+    let mut report = PluginImportReport {
+        plugins: scan.plugins.clone(),
+        external: scan.external.clone(),
+        ..PluginImportReport::default()
+    };
+    for skill in &scan.skills {
+        match package.install(app, store, source, skill, options) {
+            Ok(installed) => report.installed.push(installed.name),
+            Err(err) => report.failed.push(PluginImportFailure {
+                plugin: skill.plugin.clone(),
+                skill: skill.name.clone(),
+                error: format!("{:#}", err),
+            }),
+        }
+    }
+    log::info!(
+        "[plugins] {:?}: {} skills installed from {} plugins, {} failed, {} external",
+        source,
+        report.installed.len(),
+        report.plugins.len(),
+        report.failed.len(),
+        report.external.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "tests/claude_plugins.rs"]
+mod tests;
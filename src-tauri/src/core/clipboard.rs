@@ -0,0 +1,248 @@
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::deep_link::{github_repo_url, parse_deep_link, validate_subpath, DEEP_LINK_SCHEME};
+use super::installer::parse_github_url;
+use super::skill_store::SkillStore;
+
+pub const CLIPBOARD_WATCH_KEY: &str = "clipboard_watch";
+/// Emitted with a `ClipboardInstall` when a new installable link is copied.
+pub const CLIPBOARD_INSTALL_EVENT: &str = "clipboard-install";
+/// Each check starts a clipboard tool; PowerShell is slow to start, so Windows checks less often.
+const CLIPBOARD_POLL: Duration = if cfg!(windows) {
+    Duration::from_secs(5)
+} else {
+    Duration::from_secs(2)
+};
+/// Clipboard text longer than this is not looked at: it is a document, not a link.
+const MAX_CLIPBOARD_CHARS: usize = 4096;
+
+/// A GitHub skill source recognized in copied text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClipboardInstall {
+    /// The URL, shorthand or link as it appeared in the text.
+    pub text: String,
+    pub repo_url: String,
+    pub branch: Option<String>,
+    /// Skill folder inside the repo; `None` lets the user pick from every skill found.
+    pub subpath: Option<String>,
+}
+
+/// The first GitHub repo URL or `skillshub://install` link in `text`, or, with
+/// `allow_shorthand`, the whole text when it is an `owner/repo` shorthand. A link to a SKILL.md
+/// points at its folder. The clipboard watcher leaves shorthands out: too much copied text looks
+/// like one.
+pub fn parse_install_text(text: &str, allow_shorthand: bool) -> Option<ClipboardInstall> {
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > MAX_CLIPBOARD_CHARS {
+        return None;
+    }
+    let re = LINK_RE.get_or_init(|| {
+        Regex::new(&format!(
+            r#"(?:https?://(?:www\.)?github\.com/|{}:)[^\s<>()\[\]"'`]+"#,
+            DEEP_LINK_SCHEME
+        ))
+        .unwrap()
+    });
+    let found = re
+        .find_iter(text)
+        .map(|m| m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']))
+        .find_map(parse_link);
+    if found.is_some() {
+        return found;
+    }
+    (allow_shorthand && is_github_shorthand(text))
+        .then(|| parse_link(text))
+        .flatten()
+}
+
+/// `owner/repo` within GitHub's naming rules: owners are letters, digits and inner single
+/// hyphens, at most 39 long; repos are letters, digits, `-`, `_` and `.`, but not `.` or `..`.
+fn is_github_shorthand(text: &str) -> bool {
+    static SHORTHAND_RE: OnceLock<Regex> = OnceLock::new();
+    let re = SHORTHAND_RE.get_or_init(|| {
+        Regex::new(r"^([A-Za-z0-9](?:-?[A-Za-z0-9]){0,38})/([A-Za-z0-9._-]{1,100})$").unwrap()
+    });
+    re.captures(text)
+        .is_some_and(|caps| !matches!(&caps[2], "." | ".."))
+}
+
+fn parse_link(link: &str) -> Option<ClipboardInstall> {
+    if link.starts_with(&format!("{}:", DEEP_LINK_SCHEME)) {
+        let request = parse_deep_link(link).ok()?;
+        return Some(ClipboardInstall {
+            text: link.to_string(),
+            repo_url: request.repo_url,
+            branch: None,
+            subpath: request.subpath,
+        });
+    }
+    let link = link.split(['?', '#']).next().unwrap_or(link);
+    // `parse_github_url` only knows the canonical https://github.com/ form.
+    let canonical = link
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .strip_prefix("github.com/")
+        .map(|rest| format!("https://github.com/{}", rest));
+    let parsed = parse_github_url(canonical.as_deref().unwrap_or(link));
+    let repo_url = github_repo_url(&parsed.clone_url).ok()?;
+    let subpath = match parsed.subpath.as_deref() {
+        Some(path) => {
+            let path = path.strip_suffix("SKILL.md").unwrap_or(path);
+            Some(validate_subpath(path).ok()?).filter(|p| !p.is_empty())
+        }
+        None => None,
+    };
+    Some(ClipboardInstall {
+        text: link.to_string(),
+        repo_url,
+        branch: parsed.branch,
+        subpath,
+    })
+}
+
+/// Text on the system clipboard, read through the platform's clipboard tool.
+pub fn read_clipboard() -> Result<String> {
+    let mut last_err = None;
+    for mut cmd in paste_commands() {
+        let output = cmd.stdin(Stdio::null()).stderr(Stdio::null()).output();
+        match output {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+            Ok(output) => last_err = Some(format!("{:?} exited with {}", cmd, output.status)),
+            Err(err) => last_err = Some(err.to_string()),
+        }
+    }
+    anyhow::bail!(
+        "could not read the clipboard: {}",
+        last_err.unwrap_or_else(|| "no clipboard tool on this platform".to_string())
+    )
+}
+
+/// Candidates tried in order; the first one that succeeds wins.
+fn paste_commands() -> Vec<Command> {
+    let mut out = Vec::new();
+    if cfg!(target_os = "macos") {
+        out.push(Command::new("pbpaste"));
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-NonInteractive", "-Command", "Get-Clipboard"]);
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+        out.push(cmd);
+    } else {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            let mut cmd = Command::new("wl-paste");
+            cmd.args(["--no-newline", "--type", "text"]);
+            out.push(cmd);
+        }
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "clipboard", "-out"]);
+        out.push(cmd);
+        let mut cmd = Command::new("xsel");
+        cmd.args(["--clipboard", "--output"]);
+        out.push(cmd);
+    }
+    out
+}
+
+/// Opt-in background check of the clipboard. While on, each newly copied installable link is
+/// announced with `CLIPBOARD_INSTALL_EVENT`; what was on the clipboard when it was turned on is
+/// not.
+#[derive(Clone, Default)]
+pub struct ClipboardWatcher {
+    enabled: Arc<AtomicBool>,
+    started: Arc<AtomicBool>,
+}
+
+impl ClipboardWatcher {
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        store: &SkillStore,
+        enabled: bool,
+    ) -> Result<bool> {
+        store.set_setting(CLIPBOARD_WATCH_KEY, if enabled { "true" } else { "false" })?;
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if enabled {
+            self.start(app);
+        }
+        Ok(enabled)
+    }
+
+    /// Resume watching at launch when it was left on.
+    pub fn start_if_enabled<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        store: &SkillStore,
+    ) {
+        let enabled = store
+            .get_setting(CLIPBOARD_WATCH_KEY)
+            .ok()
+            .flatten()
+            .is_some_and(|v| v == "true");
+        if enabled {
+            self.enabled.store(true, Ordering::Relaxed);
+            self.start(app);
+        }
+    }
+
+    /// One polling thread for the app's life; it idles while watching is off.
+    fn start<R: tauri::Runtime>(&self, app: &tauri::AppHandle<R>) {
+        if self.started.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let (watcher, app) = (self.clone(), app.clone());
+        let spawned = std::thread::Builder::new()
+            .name("clipboard-watch".to_string())
+            .spawn(move || {
+                // `None` until the first read after watching starts, which only sets a baseline.
+                let mut last: Option<String> = None;
+                loop {
+                    std::thread::sleep(CLIPBOARD_POLL);
+                    if !watcher.enabled() {
+                        last = None;
+                        continue;
+                    }
+                    let Ok(text) = read_clipboard() else {
+                        continue;
+                    };
+                    let changed = last.as_ref().is_some_and(|prev| *prev != text);
+                    if changed {
+                        if let Some(install) = parse_install_text(&text, false) {
+                            if let Err(err) = app.emit(CLIPBOARD_INSTALL_EVENT, &install) {
+                                log::warn!("[clipboard] failed to emit event: {}", err);
+                            }
+                        }
+                    }
+                    last = Some(text);
+                }
+            });
+        if let Err(err) = spawned {
+            self.started.store(false, Ordering::Relaxed);
+            log::warn!("[clipboard] failed to start watcher thread: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/clipboard.rs"]
+mod tests;
@@ -0,0 +1,184 @@
+use std::fs::Metadata;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+/// Services whose sync clients are known to replace files with on-demand placeholders and to
+/// leave conflict copies next to the original.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudProvider {
+    Dropbox,
+    ICloud,
+    OneDrive,
+    GoogleDrive,
+}
+
+impl CloudProvider {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dropbox => "Dropbox",
+            Self::ICloud => "iCloud Drive",
+            Self::OneDrive => "OneDrive",
+            Self::GoogleDrive => "Google Drive",
+        }
+    }
+}
+
+/// A file or directory in the Central Repo, relative to it, and the skill it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CloudFile {
+    pub skill: String,
+    pub path: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CloudSyncReport {
+    pub provider: Option<CloudProvider>,
+    /// Files whose content is still in the cloud; they look empty or missing until downloaded.
+    pub placeholders: Vec<CloudFile>,
+    /// Copies the sync client made when two machines edited the same file.
+    pub conflict_copies: Vec<CloudFile>,
+}
+
+/// Windows cloud-files attributes: content is fetched when the file is opened or read.
+#[cfg(windows)]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+/// macOS File Provider flag for files whose data has not been materialized.
+#[cfg(target_os = "macos")]
+const SF_DATALESS: u32 = 0x4000_0000;
+
+/// The provider whose synced folder contains `path`, judged by the folder names each client
+/// creates (including the macOS `~/Library/CloudStorage/<Provider>-<account>` layout).
+pub fn detect_cloud_provider(path: &Path) -> Option<CloudProvider> {
+    path.components().find_map(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        if name == "Dropbox" || name.starts_with("Dropbox (") || name.starts_with("Dropbox-") {
+            Some(CloudProvider::Dropbox)
+        } else if name == "Mobile Documents"
+            || name == "iCloud Drive"
+            || name.ends_with("CloudDocs")
+        {
+            Some(CloudProvider::ICloud)
+        } else if name.starts_with("OneDrive") {
+            Some(CloudProvider::OneDrive)
+        } else if name == "Google Drive" || name.starts_with("GoogleDrive") || name == "My Drive" {
+            Some(CloudProvider::GoogleDrive)
+        } else {
+            None
+        }
+    })
+}
+
+/// Whether the file at `path` is a placeholder whose content has not been downloaded. Reading
+/// one either fails or blocks on a download, so it must not be hashed or copied.
+pub fn is_placeholder(path: &Path, meta: &Metadata) -> bool {
+    if is_icloud_stub(path) {
+        return true;
+    }
+    platform_placeholder(meta)
+}
+
+/// iCloud evicts `name` by replacing it with a hidden `.name.icloud` stub.
+fn is_icloud_stub(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .is_some_and(|name| name.starts_with('.') && name.ends_with(".icloud") && name.len() > 8)
+}
+
+#[cfg(windows)]
+fn platform_placeholder(meta: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    meta.file_attributes()
+        & (FILE_ATTRIBUTE_OFFLINE
+            | FILE_ATTRIBUTE_RECALL_ON_OPEN
+            | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(target_os = "macos")]
+fn platform_placeholder(meta: &Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    meta.st_flags() & SF_DATALESS != 0
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn platform_placeholder(_meta: &Metadata) -> bool {
+    false
+}
+
+/// Whether the content of `path` is in the cloud only: it is a placeholder, or it is gone and
+/// iCloud left its `.name.icloud` stub behind.
+pub fn is_offline(path: &Path) -> bool {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) => is_placeholder(path, &meta),
+        Err(_) => path.file_name().is_some_and(|name| {
+            path.with_file_name(format!(".{}.icloud", name.to_string_lossy()))
+                .is_file()
+        }),
+    }
+}
+
+/// Names sync clients give conflict copies: Dropbox's `name (conflicted copy 2024-01-02)` and
+/// `(Case Conflict)`, and Syncthing's `name.sync-conflict-<date>-<device>`.
+pub fn is_conflict_copy(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("conflicted copy")
+        || name.contains("(case conflict")
+        || name.contains(".sync-conflict-")
+}
+
+/// Walk the Central Repo for placeholders and conflict copies. Skills Hub's own scratch dirs
+/// and a versioning `.git` are skipped.
+pub fn scan_cloud_sync(central: &Path) -> Result<CloudSyncReport> {
+    let mut report = CloudSyncReport {
+        provider: detect_cloud_provider(central),
+        ..CloudSyncReport::default()
+    };
+    if !central.is_dir() {
+        return Ok(report);
+    }
+    let walker = WalkDir::new(central)
+        .min_depth(1)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            name != ".git" && !name.starts_with(".skills-hub-")
+        });
+    for entry in walker {
+        let entry = entry?;
+        let Ok(relative) = entry.path().strip_prefix(central) else {
+            continue;
+        };
+        let file = || CloudFile {
+            skill: relative
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: relative.to_string_lossy().to_string(),
+        };
+        if is_conflict_copy(&entry.file_name().to_string_lossy()) {
+            report.conflict_copies.push(file());
+        }
+        if entry.file_type().is_file() {
+            let meta = entry.metadata()?;
+            if is_placeholder(entry.path(), &meta) {
+                report.placeholders.push(file());
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "tests/cloud_sync.rs"]
+mod tests;
@@ -1,18 +1,86 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
 use walkdir::{DirEntry, WalkDir};
 
-const IGNORE_NAMES: [&str; 4] = [".git", ".DS_Store", "Thumbs.db", ".gitignore"];
+use super::cloud_sync::is_placeholder;
+use super::skill_signing::SIGNATURE_FILE;
+use super::skill_store::SkillStore;
 
-fn is_ignored(entry: &DirEntry) -> bool {
+const IGNORE_NAMES: [&str; 5] = [
+    ".git",
+    ".DS_Store",
+    "Thumbs.db",
+    ".gitignore",
+    SIGNATURE_FILE,
+];
+
+/// Files are hashed through a buffer of this size rather than read whole.
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+pub const HASH_MAX_FILE_MB_KEY: &str = "hash_max_file_mb";
+pub const DEFAULT_HASH_MAX_FILE_MB: u64 = 256;
+const MB: u64 = 1024 * 1024;
+
+/// Files larger than this are not read; 0 hashes everything. Process-wide so `hash_dir`
+/// callers need no store access.
+static MAX_HASHED_FILE_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_HASH_MAX_FILE_MB * MB);
+
+/// Files modified this recently may still change within the same mtime tick, so their hashes
+/// are not cached.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// A file's digest together with the size and mtime it had when it was read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedFileHash {
+    pub size: u64,
+    pub mtime_ms: i64,
+    pub hash: String,
+}
+
+/// Per-skill cache keyed by the file's path relative to the skill dir.
+pub type FileHashCache = BTreeMap<String, CachedFileHash>;
+
+pub(crate) fn is_ignored(entry: &DirEntry) -> bool {
     let file_name = entry.file_name().to_string_lossy();
     IGNORE_NAMES.iter().any(|name| name == &file_name.as_ref())
 }
 
+/// Digest over each entry's relative path and, for files, the hex sha256 of its content. This
+/// differs from the pre-V6 digest over raw bytes; the V6 migration clears hashes stored before.
 pub fn hash_dir(path: &Path) -> Result<String> {
+    hash_dir_cached(path, &mut FileHashCache::new())
+}
+
+/// Same digest as `hash_dir`, but files whose size and mtime match `cache` are not re-read.
+/// The cache is updated in place: changed files are refreshed and vanished ones dropped.
+pub fn hash_dir_cached(path: &Path, cache: &mut FileHashCache) -> Result<String> {
+    let max = match MAX_HASHED_FILE_BYTES.load(Ordering::Relaxed) {
+        0 => None,
+        bytes => Some(bytes),
+    };
+    hash_dir_limited(path, cache, max)
+}
+
+/// Files above `max_file_size` contribute only their size to the digest, so an edit that keeps
+/// the size is not detected. They are logged and never cached, so raising the limit takes
+/// effect on the next hash.
+pub(crate) fn hash_dir_limited(
+    path: &Path,
+    cache: &mut FileHashCache,
+    max_file_size: Option<u64>,
+) -> Result<String> {
     let mut hasher = Sha256::new();
+    let mut seen = BTreeSet::new();
+    let racy_after = SystemTime::now()
+        .checked_sub(RACY_WINDOW)
+        .map(mtime_ms)
+        .unwrap_or(0);
 
     for entry in WalkDir::new(path)
         .follow_links(false)
@@ -28,19 +96,106 @@ pub fn hash_dir(path: &Path) -> Result<String> {
             .path()
             .strip_prefix(path)
             .with_context(|| format!("strip prefix {:?}", entry.path()))?;
-        hasher.update(relative.to_string_lossy().as_bytes());
+        let key = relative.to_string_lossy().to_string();
+        hasher.update(key.as_bytes());
 
         if entry.file_type().is_file() {
-            let bytes = std::fs::read(entry.path())
-                .with_context(|| format!("read file {:?}", entry.path()))?;
-            hasher.update(bytes);
+            let meta = entry
+                .metadata()
+                .with_context(|| format!("stat file {:?}", entry.path()))?;
+            // A digest over stub content would look like an edit once the real file arrives.
+            if is_placeholder(entry.path(), &meta) {
+                anyhow::bail!(
+                    "{:?} is a cloud placeholder that has not been downloaded",
+                    entry.path()
+                );
+            }
+            let size = meta.len();
+            let mtime = meta.modified().map(mtime_ms).unwrap_or(0);
+            let file_hash = if max_file_size.is_some_and(|max| size > max) {
+                log::warn!(
+                    "[hash] {:?} is {} bytes, above the hash limit; only its size is hashed",
+                    entry.path(),
+                    size
+                );
+                cache.remove(&key);
+                format!("oversized:{}", size)
+            } else {
+                match cache.get(&key) {
+                    Some(hit) if hit.size == size && hit.mtime_ms == mtime => hit.hash.clone(),
+                    _ => {
+                        let hash = hash_file(entry.path())?;
+                        if mtime < racy_after {
+                            cache.insert(
+                                key.clone(),
+                                CachedFileHash {
+                                    size,
+                                    mtime_ms: mtime,
+                                    hash: hash.clone(),
+                                },
+                            );
+                        } else {
+                            cache.remove(&key);
+                        }
+                        hash
+                    }
+                }
+            };
+            hasher.update(file_hash.as_bytes());
+            seen.insert(key);
         }
     }
 
+    cache.retain(|key, _| seen.contains(key));
     let digest = hasher.finalize();
     Ok(hex::encode(digest))
 }
 
+fn hash_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path).with_context(|| format!("open file {:?}", path))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file
+            .read(&mut buf)
+            .with_context(|| format!("read file {:?}", path))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+pub fn get_hash_max_file_mb(store: &SkillStore) -> u64 {
+    store
+        .get_setting(HASH_MAX_FILE_MB_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_HASH_MAX_FILE_MB)
+}
+
+/// Persist and apply the size limit in MiB; 0 removes it. Skills holding files that cross the
+/// old or new limit get a different digest on their next hash.
+pub fn set_hash_max_file_mb(store: &SkillStore, mb: u64) -> Result<u64> {
+    store.set_setting(HASH_MAX_FILE_MB_KEY, &mb.to_string())?;
+    MAX_HASHED_FILE_BYTES.store(mb.saturating_mul(MB), Ordering::Relaxed);
+    Ok(mb)
+}
+
+/// Apply the stored limit; called once at startup.
+pub fn load_hash_max_file_mb(store: &SkillStore) {
+    let mb = get_hash_max_file_mb(store);
+    MAX_HASHED_FILE_BYTES.store(mb.saturating_mul(MB), Ordering::Relaxed);
+}
+
+fn mtime_ms(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 #[path = "tests/content_hash.rs"]
 mod tests;
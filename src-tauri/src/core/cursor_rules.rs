@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::installer::{install_converted_skill, InstallOptions, InstallResult};
+use super::skill_store::SkillStore;
+
+/// `source_type` of skills converted from a Cursor rule; `source_ref` is the `.mdc` file, so
+/// updates convert it again.
+pub const CURSOR_RULE_SOURCE: &str = "cursor_rule";
+const RULE_EXTENSION: &str = "mdc";
+/// Where Cursor keeps project rules, relative to the project root.
+const PROJECT_RULES_DIR: &str = ".cursor/rules";
+
+/// A `.mdc` rule: frontmatter with `description`, `globs` and `alwaysApply`, then markdown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CursorRule {
+    pub description: Option<String>,
+    pub globs: Vec<String>,
+    pub always_apply: bool,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RuleImportFailure {
+    /// The `.mdc` file.
+    pub rule: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RuleImportReport {
+    /// Names of the skills created, one per rule.
+    pub imported: Vec<String>,
+    pub failed: Vec<RuleImportFailure>,
+}
+
+#[derive(Serialize)]
+struct ConvertedFrontmatter<'a> {
+    name: &'a str,
+    description: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<&'static str, String>,
+}
+
+impl CursorRule {
+    /// Cursor writes `globs: *.ts,*.tsx` unquoted, which is not valid YAML, so the frontmatter
+    /// is read line by line. A file without frontmatter is all body.
+    pub fn parse(text: &str) -> Self {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let mut rule = Self::default();
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some("---") {
+            rule.body = text.to_string();
+            return rule;
+        }
+        let mut block = Vec::new();
+        let mut closed = false;
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                closed = true;
+                break;
+            }
+            block.push(line);
+        }
+        if !closed {
+            rule.body = text.to_string();
+            return rule;
+        }
+        rule.body = lines.collect::<Vec<_>>().join("\n");
+
+        let mut list_key: Option<&str> = None;
+        for line in block {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                if list_key == Some("globs") {
+                    rule.globs.extend(split_globs(item));
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), unquote(value.trim()));
+            list_key = Some(key).filter(|_| value.is_empty());
+            match key {
+                "description" if !value.is_empty() => rule.description = Some(value.to_string()),
+                "globs" => rule.globs.extend(split_globs(value)),
+                "alwaysApply" => rule.always_apply = value.eq_ignore_ascii_case("true"),
+                _ => {}
+            }
+        }
+        rule
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("read rule {:?}", path))?;
+        Ok(Self::parse(&text))
+    }
+
+    /// A SKILL.md named `name`. Rules without a description get one from how Cursor applied
+    /// them; globs and `alwaysApply` are kept under `metadata`.
+    pub fn to_skill_md(&self, name: &str) -> Result<String> {
+        let description = match &self.description {
+            Some(description) => description.clone(),
+            None if self.always_apply => {
+                format!(
+                    "Project rules imported from the Cursor rule {}; always apply them.",
+                    name
+                )
+            }
+            None if !self.globs.is_empty() => format!(
+                "Rules for files matching {}, imported from the Cursor rule {}.",
+                self.globs.join(", "),
+                name
+            ),
+            None => format!("Rules imported from the Cursor rule {}.", name),
+        };
+        let mut metadata = BTreeMap::new();
+        if !self.globs.is_empty() {
+            metadata.insert("cursor-globs", self.globs.join(","));
+        }
+        if self.always_apply {
+            metadata.insert("cursor-always-apply", "true".to_string());
+        }
+        let frontmatter = serde_yaml::to_string(&ConvertedFrontmatter {
+            name,
+            description,
+            metadata,
+        })?;
+        Ok(format!(
+            "---\n{}---\n\n{}\n",
+            frontmatter,
+            self.body.trim_matches('\n')
+        ))
+    }
+
+    /// The `.mdc` text Cursor writes itself: unquoted one-line values, then the body.
+    pub fn to_mdc(&self) -> String {
+        let description = self
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            "---\ndescription: {}\nglobs: {}\nalwaysApply: {}\n---\n\n{}\n",
+            description,
+            self.globs.join(","),
+            self.always_apply,
+            self.body.trim_matches('\n')
+        )
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// `*.ts,*.tsx`, `"*.ts"` or a `["*.ts", "*.tsx"]` flow list.
+fn split_globs(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    value
+        .split(',')
+        .map(|glob| unquote(glob.trim()).trim().to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect()
+}
+
+/// Skill name for the rule at `relative` inside the rules dir: its path without the extension,
+/// lowercased, with anything but letters and digits turned into single hyphens.
+pub fn rule_skill_name(relative: &Path) -> String {
+    let stem = relative.with_extension("");
+    let mut name = String::new();
+    for ch in stem.to_string_lossy().chars() {
+        if ch.is_ascii_alphanumeric() {
+            name.push(ch.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_end_matches('-');
+    if name.is_empty() {
+        "cursor-rule".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// The `.mdc` files under `path` with their skill names. `path` may be a single rule, a rules
+/// directory (searched recursively) or a project whose `.cursor/rules` is used.
+pub fn find_rule_files(path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    if path.is_file() {
+        let name = rule_skill_name(Path::new(path.file_name().unwrap_or_default()));
+        return Ok(vec![(name, path.to_path_buf())]);
+    }
+    if !path.is_dir() {
+        anyhow::bail!("rules path not found: {:?}", path);
+    }
+    let project_rules = path.join(PROJECT_RULES_DIR);
+    let root = if project_rules.is_dir() {
+        project_rules
+    } else {
+        path.to_path_buf()
+    };
+    let mut rules = Vec::new();
+    for entry in WalkDir::new(&root).follow_links(false).sort_by_file_name() {
+        let entry = entry?;
+        let is_rule = entry.file_type().is_file()
+            && entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == RULE_EXTENSION);
+        if !is_rule {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+        rules.push((rule_skill_name(relative), entry.path().to_path_buf()));
+    }
+    Ok(rules)
+}
+
+/// Convert the rule at `rule_file` into a skill directory at `dest`.
+pub fn write_rule_skill(rule_file: &Path, name: &str, dest: &Path) -> Result<()> {
+    let skill_md = CursorRule::read(rule_file)?.to_skill_md(name)?;
+    std::fs::create_dir_all(dest).with_context(|| format!("create {:?}", dest))?;
+    std::fs::write(dest.join("SKILL.md"), skill_md)
+        .with_context(|| format!("write {:?}", dest.join("SKILL.md")))?;
+    Ok(())
+}
+
+/// Convert and install every rule found at `path`. Each rule goes through the normal install
+/// gates; one that fails is reported and the rest still import.
+pub fn import_cursor_rules<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    path: &Path,
+    options: InstallOptions,
+) -> Result<RuleImportReport> {
+    let rules = find_rule_files(path)?;
+    if rules.is_empty() {
+        anyhow::bail!("no .mdc rules found in {:?}", path);
+    }
+    let mut report = RuleImportReport::default();
+    for (name, rule_file) in rules {
+        match import_cursor_rule(app, store, &rule_file, name, options) {
+            Ok(installed) => report.imported.push(installed.name),
+            Err(err) => report.failed.push(RuleImportFailure {
+                rule: rule_file.to_string_lossy().to_string(),
+                error: format!("{:#}", err),
+            }),
+        }
+    }
+    log::info!(
+        "[cursor-rules] imported {} rules from {:?}, {} failed",
+        report.imported.len(),
+        path,
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// Convert one rule in a scratch dir and install it as `name`.
+pub fn import_cursor_rule<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    rule_file: &Path,
+    name: String,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    let scratch = std::env::temp_dir().join(format!("skills-hub-rule-{}", Uuid::new_v4()));
+    let staging = scratch.join(&name);
+    let result = write_rule_skill(rule_file, &name, &staging).and_then(|()| {
+        install_converted_skill(
+            app,
+            store,
+            &staging,
+            name,
+            (
+                CURSOR_RULE_SOURCE.to_string(),
+                Some(rule_file.to_string_lossy().to_string()),
+                None,
+            ),
+            options,
+        )
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+#[cfg(test)]
+#[path = "tests/cursor_rules.rs"]
+mod tests;
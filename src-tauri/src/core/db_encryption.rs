@@ -0,0 +1,239 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+#[cfg(feature = "sqlcipher")]
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::Manager;
+
+use super::environments::{ENVIRONMENTS_KEY, PROXY_URL_KEY};
+use super::skill_files::read_or_create_private_file;
+use super::skill_store::SkillStore;
+use super::webhooks::WEBHOOK_URLS_KEY;
+
+const DB_KEY_FILE: &str = "db.key";
+/// Settings that can hold tokens or passwords: the local API token, proxy URLs with userinfo
+/// (also inside environments) and webhook URLs with secrets in them.
+pub const CREDENTIAL_SETTINGS: [&str; 4] = [
+    "local_api_token",
+    PROXY_URL_KEY,
+    ENVIRONMENTS_KEY,
+    WEBHOOK_URLS_KEY,
+];
+/// Marks a setting value as `base64(nonce || ciphertext || tag)`.
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// This installation's database key: 32 random bytes kept as base64 in the app data dir. It is
+/// the SQLCipher key when built with the `sqlcipher` feature and always encrypts credential
+/// settings.
+#[derive(Clone)]
+pub struct DbKey([u8; 32]);
+
+impl std::fmt::Debug for DbKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DbKey(..)")
+    }
+}
+
+impl DbKey {
+    pub fn generate() -> Result<Self> {
+        let mut bytes = [0u8; 32];
+        getrandom::getrandom(&mut bytes)
+            .map_err(|err| anyhow::anyhow!("generate database key: {}", err))?;
+        Ok(Self(bytes))
+    }
+
+    /// Load the key at `path`, creating it (readable by the owner only) on first use.
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        let raw = read_or_create_private_file(path, || {
+            Ok(STANDARD.encode(Self::generate()?.0).into_bytes())
+        })?;
+        let bytes: [u8; 32] = std::str::from_utf8(&raw)
+            .ok()
+            .and_then(|raw| STANDARD.decode(raw.trim()).ok())
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("invalid database key file: {:?}", path))?;
+        Ok(Self(bytes))
+    }
+
+    fn aead_key(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &self.0).expect("32-byte key"))
+    }
+
+    /// Encrypt the value of setting `name`. The name is authenticated too, so a value cannot be
+    /// moved to another setting.
+    pub fn encrypt(&self, name: &str, value: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce)
+            .map_err(|err| anyhow::anyhow!("generate nonce: {}", err))?;
+        let mut sealed = value.as_bytes().to_vec();
+        self.aead_key()
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(name.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow::anyhow!("failed to encrypt setting {}", name))?;
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&sealed);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(out)))
+    }
+
+    pub fn decrypt(&self, name: &str, value: &str) -> Result<String> {
+        let undecryptable = || {
+            anyhow::anyhow!(
+                "setting {} was encrypted with another database key or is corrupt",
+                name
+            )
+        };
+        let raw = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .and_then(|encoded| STANDARD.decode(encoded).ok())
+            .filter(|raw| raw.len() >= NONCE_LEN)
+            .ok_or_else(undecryptable)?;
+        let (nonce, sealed) = raw.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| undecryptable())?;
+        let mut sealed = sealed.to_vec();
+        let plain = self
+            .aead_key()
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut sealed)
+            .map_err(|_| undecryptable())?;
+        String::from_utf8(plain.to_vec()).map_err(|_| undecryptable())
+    }
+
+    /// The raw-key form of `PRAGMA key` and `ATTACH ... KEY`.
+    #[cfg(feature = "sqlcipher")]
+    fn sqlcipher_key(&self) -> String {
+        format!("x'{}'", hex::encode(self.0))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EncryptionStatus {
+    /// The database file is encrypted with SQLCipher (builds with the `sqlcipher` feature).
+    pub database_encrypted: bool,
+    /// Credential settings that are set and stored encrypted.
+    pub encrypted_settings: Vec<String>,
+    /// Credential settings still in plaintext; encrypted at the next launch or save.
+    pub plaintext_settings: Vec<String>,
+}
+
+pub fn db_key_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .context("failed to resolve app data dir")?;
+    Ok(app_dir.join(DB_KEY_FILE))
+}
+
+pub fn is_credential_setting(key: &str) -> bool {
+    CREDENTIAL_SETTINGS.contains(&key)
+}
+
+pub fn is_encrypted_value(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Whether `path` starts with the plaintext SQLite header; an SQLCipher database does not.
+pub(crate) fn is_plaintext_db(path: &Path) -> Result<bool> {
+    let mut header = [0u8; 16];
+    let mut file = std::fs::File::open(path).with_context(|| format!("open {:?}", path))?;
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header == SQLITE_HEADER),
+        // Empty or truncated: SQLite treats it as a new database.
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(true),
+        Err(err) => Err(err).with_context(|| format!("read {:?}", path)),
+    }
+}
+
+/// Re-save credential settings still stored in plaintext, e.g. from before encryption or from
+/// a restored backup. Returns how many were encrypted.
+pub fn encrypt_credential_settings(store: &SkillStore) -> Result<usize> {
+    if store.db_key().is_none() {
+        return Ok(0);
+    }
+    let mut encrypted = 0;
+    for key in CREDENTIAL_SETTINGS {
+        if let Some(value) = store.get_setting_raw(key)? {
+            if !is_encrypted_value(&value) {
+                store.set_setting(key, &value)?;
+                encrypted += 1;
+            }
+        }
+    }
+    Ok(encrypted)
+}
+
+pub fn encryption_status(store: &SkillStore) -> Result<EncryptionStatus> {
+    let (mut encrypted_settings, mut plaintext_settings) = (Vec::new(), Vec::new());
+    for key in CREDENTIAL_SETTINGS {
+        match store.get_setting_raw(key)? {
+            Some(value) if is_encrypted_value(&value) => encrypted_settings.push(key.to_string()),
+            Some(_) => plaintext_settings.push(key.to_string()),
+            None => {}
+        }
+    }
+    Ok(EncryptionStatus {
+        database_encrypted: cfg!(feature = "sqlcipher")
+            && store.db_key().is_some()
+            && !is_plaintext_db(store.db_path()).unwrap_or(true),
+        encrypted_settings,
+        plaintext_settings,
+    })
+}
+
+/// Unlock a freshly opened connection; must run before any other statement.
+#[cfg(feature = "sqlcipher")]
+pub(crate) fn apply_db_key(conn: &Connection, key: &DbKey) -> Result<()> {
+    conn.execute_batch(&format!("PRAGMA key = \"{}\";", key.sqlcipher_key()))
+        .context("failed to unlock the database")
+}
+
+/// Convert an existing plaintext database to SQLCipher in place. A missing database is created
+/// encrypted on first open, so there is nothing to do. Returns whether a conversion happened.
+#[cfg(feature = "sqlcipher")]
+pub fn encrypt_db_if_needed(db_path: &Path, key: &DbKey) -> Result<bool> {
+    if !db_path.exists() || !is_plaintext_db(db_path)? {
+        return Ok(false);
+    }
+    let encrypted = db_path.with_extension("db.encrypting");
+    if encrypted.exists() {
+        std::fs::remove_file(&encrypted).with_context(|| format!("remove {:?}", encrypted))?;
+    }
+    let conn =
+        Connection::open(db_path).with_context(|| format!("failed to open db at {:?}", db_path))?;
+    export_conn(&conn, &encrypted, Some(key))?;
+    drop(conn);
+    std::fs::rename(&encrypted, db_path)
+        .with_context(|| format!("replace {:?} with its encrypted copy", db_path))?;
+    Ok(true)
+}
+
+/// Copy the database open on `conn` to `dest`, encrypted with `dest_key` or in plaintext.
+/// `sqlcipher_export` leaves out `user_version`, so it is copied explicitly.
+#[cfg(feature = "sqlcipher")]
+pub(crate) fn export_conn(conn: &Connection, dest: &Path, dest_key: Option<&DbKey>) -> Result<()> {
+    let dest_key = dest_key.map(DbKey::sqlcipher_key).unwrap_or_default();
+    conn.execute(
+        "ATTACH DATABASE ?1 AS export KEY ?2",
+        params![dest.to_string_lossy(), dest_key],
+    )
+    .with_context(|| format!("attach {:?}", dest))?;
+    let exported = (|| -> Result<()> {
+        conn.query_row("SELECT sqlcipher_export('export')", [], |_| Ok(()))?;
+        let version: i32 = conn.query_row("PRAGMA main.user_version;", [], |row| row.get(0))?;
+        conn.execute_batch(&format!("PRAGMA export.user_version = {};", version))?;
+        Ok(())
+    })();
+    conn.execute_batch("DETACH DATABASE export;")?;
+    exported.with_context(|| format!("export db to {:?}", dest))
+}
+
+#[cfg(test)]
+#[path = "tests/db_encryption.rs"]
+mod tests;
@@ -0,0 +1,135 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serde::Serialize;
+use tauri::{Emitter, Url};
+
+pub const DEEP_LINK_SCHEME: &str = "skillshub";
+/// Emitted whenever a new install link was queued; the UI drains the queue with
+/// `take_pending_deep_links` so links received before it loaded are not lost.
+pub const DEEP_LINK_EVENT: &str = "deep-link-install";
+
+/// `skillshub://install?repo=owner/repo&path=skills/foo`, validated but not yet confirmed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeepLinkInstall {
+    pub repo_url: String,
+    /// Skill folder inside the repo; `None` lets the user pick from every skill found.
+    pub subpath: Option<String>,
+}
+
+/// Links received from the OS, waiting for the UI to pick them up.
+#[derive(Clone, Default)]
+pub struct DeepLinkQueue {
+    pending: Arc<Mutex<Vec<DeepLinkInstall>>>,
+}
+
+impl DeepLinkQueue {
+    pub fn push(&self, request: DeepLinkInstall) {
+        self.lock().push(request);
+    }
+
+    pub fn take(&self) -> Vec<DeepLinkInstall> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<DeepLinkInstall>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Parse and validate an install link. Only GitHub repos are accepted so a link clicked in a
+/// browser can never point the installer at a local path or an arbitrary remote.
+pub fn parse_deep_link(raw: &str) -> Result<DeepLinkInstall> {
+    let url = Url::parse(raw.trim()).map_err(|err| anyhow::anyhow!("invalid link: {}", err))?;
+    if url.scheme() != DEEP_LINK_SCHEME {
+        anyhow::bail!("unsupported link scheme: {}", url.scheme());
+    }
+    // `skillshub://install?..` puts the action in the host, `skillshub:install?..` in the path.
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/');
+    if action != "install" {
+        anyhow::bail!("unsupported link action: {}", action);
+    }
+
+    let mut repo = None;
+    let mut path = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "repo" => repo = Some(value.trim().to_string()),
+            "path" => path = Some(value.trim().trim_matches('/').to_string()),
+            _ => {}
+        }
+    }
+    let repo = repo
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("link is missing the repo parameter"))?;
+
+    Ok(DeepLinkInstall {
+        repo_url: github_repo_url(&repo)?,
+        subpath: match path.filter(|p| !p.is_empty()) {
+            Some(path) => Some(validate_subpath(&path)?),
+            None => None,
+        },
+    })
+}
+
+/// Queue a link received from the OS and tell the UI about it. Invalid links are only logged:
+/// they come from outside the app and there is no dialog to report them in yet.
+pub fn handle_deep_link<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    queue: &DeepLinkQueue,
+    raw: &str,
+) {
+    match parse_deep_link(raw) {
+        Ok(request) => {
+            log::info!("[deep-link] install request for {}", request.repo_url);
+            queue.push(request.clone());
+            if let Err(err) = app.emit(DEEP_LINK_EVENT, &request) {
+                log::warn!("[deep-link] failed to emit {}: {}", DEEP_LINK_EVENT, err);
+            }
+        }
+        Err(err) => log::warn!("[deep-link] ignored {}: {:#}", raw, err),
+    }
+}
+
+pub(crate) fn github_repo_url(repo: &str) -> Result<String> {
+    let rest = repo
+        .strip_prefix("https://github.com/")
+        .unwrap_or(repo)
+        .trim_end_matches('/')
+        .trim_end_matches(".git");
+    let mut parts = rest.split('/');
+    let (Some(owner), Some(name), None) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("repo must be owner/repo or a github.com URL: {}", repo);
+    };
+    let valid = |s: &str| {
+        !s.is_empty()
+            && s != "."
+            && s != ".."
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    if !valid(owner) || !valid(name) {
+        anyhow::bail!("repo must be owner/repo or a github.com URL: {}", repo);
+    }
+    Ok(format!("https://github.com/{}/{}", owner, name))
+}
+
+pub(crate) fn validate_subpath(path: &str) -> Result<String> {
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    if parts
+        .iter()
+        .any(|p| *p == "." || *p == ".." || *p == ".git" || p.contains('\\'))
+    {
+        anyhow::bail!("INVALID_PATH|{}", path);
+    }
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+#[path = "tests/deep_link.rs"]
+mod tests;
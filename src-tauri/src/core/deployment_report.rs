@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::content_hash::hash_dir;
+use super::skill_store::{SkillProjection, SkillRecord, SkillStore, SkillTargetRecord};
+use super::sync_engine::SyncMode;
+use super::tool_adapters::adapter_by_key;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Markdown,
+    Json,
+}
+
+/// Whether what a tool loads is the skill as it is in the Central Repo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashCheck {
+    /// A link resolving to the central dir, or a copy with the central content hash.
+    Match,
+    /// A copy edited or left behind by an older version, or a link pointing elsewhere.
+    Mismatch,
+    /// Nothing at the target path, or a link whose destination is gone.
+    Missing,
+    /// A copy whose skill has no content hash yet, or that could not be read.
+    Unknown,
+}
+
+impl HashCheck {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::Mismatch => "MISMATCH",
+            Self::Missing => "MISSING",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeployedSkill {
+    pub skill_id: String,
+    pub skill: String,
+    pub target_path: String,
+    pub mode: String,
+    pub status: String,
+    pub synced_at: Option<i64>,
+    pub hash: HashCheck,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ToolDeployment {
+    pub tool: String,
+    pub display_name: String,
+    pub skills: Vec<DeployedSkill>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeploymentReport {
+    pub generated_at: i64,
+    pub central_repo: String,
+    pub skill_count: usize,
+    pub target_count: usize,
+    /// Tools with at least one target, by key.
+    pub tools: Vec<ToolDeployment>,
+    /// Managed skills synced to no tool.
+    pub undeployed: Vec<String>,
+}
+
+/// What every tool has deployed, checked against the filesystem: each target is looked at,
+/// and copies are hashed.
+pub fn build_deployment_report(
+    store: &SkillStore,
+    central: &Path,
+    now: i64,
+) -> Result<DeploymentReport> {
+    let mut skills = store.list_skills_with(SkillProjection::Summary)?;
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut by_tool: BTreeMap<String, Vec<DeployedSkill>> = BTreeMap::new();
+    let mut undeployed = Vec::new();
+    let mut target_count = 0;
+    for skill in &skills {
+        let targets = store.list_skill_targets(&skill.id)?;
+        if targets.is_empty() {
+            undeployed.push(skill.name.clone());
+        }
+        for target in targets {
+            target_count += 1;
+            by_tool
+                .entry(target.tool.clone())
+                .or_default()
+                .push(DeployedSkill {
+                    skill_id: skill.id.clone(),
+                    skill: skill.name.clone(),
+                    hash: check_target(skill, &target),
+                    target_path: target.target_path,
+                    mode: target.mode,
+                    status: target.status,
+                    synced_at: target.synced_at,
+                });
+        }
+    }
+    let tools = by_tool
+        .into_iter()
+        .map(|(tool, skills)| ToolDeployment {
+            display_name: adapter_by_key(&tool)
+                .map(|adapter| adapter.display_name.to_string())
+                .unwrap_or_else(|| tool.clone()),
+            tool,
+            skills,
+        })
+        .collect();
+    Ok(DeploymentReport {
+        generated_at: now,
+        central_repo: central.to_string_lossy().to_string(),
+        skill_count: skills.len(),
+        target_count,
+        tools,
+        undeployed,
+    })
+}
+
+fn check_target(skill: &SkillRecord, target: &SkillTargetRecord) -> HashCheck {
+    let path = Path::new(&target.target_path);
+    if std::fs::symlink_metadata(path).is_err() {
+        return HashCheck::Missing;
+    }
+    if target.mode != SyncMode::Copy.as_key() {
+        let Ok(resolved) = path.canonicalize() else {
+            return HashCheck::Missing;
+        };
+        return match Path::new(&skill.central_path).canonicalize() {
+            Ok(central) if central == resolved => HashCheck::Match,
+            Ok(_) => HashCheck::Mismatch,
+            Err(_) => HashCheck::Unknown,
+        };
+    }
+    let Some(expected) = &skill.content_hash else {
+        return HashCheck::Unknown;
+    };
+    match hash_dir(path) {
+        Ok(hash) if &hash == expected => HashCheck::Match,
+        Ok(_) => HashCheck::Mismatch,
+        Err(_) => HashCheck::Unknown,
+    }
+}
+
+pub fn render_deployment_report(report: &DeploymentReport, format: ReportFormat) -> Result<String> {
+    match format {
+        ReportFormat::Json => Ok(serde_json::to_string_pretty(report)? + "\n"),
+        ReportFormat::Markdown => Ok(render_markdown(report)),
+    }
+}
+
+fn render_markdown(report: &DeploymentReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Skills Hub deployment report\n");
+    let _ = writeln!(out, "- Generated: {}", format_utc(report.generated_at));
+    let _ = writeln!(out, "- Central Repo: `{}`", report.central_repo);
+    let _ = writeln!(
+        out,
+        "- {} skills, {} deployments to {} tools",
+        report.skill_count,
+        report.target_count,
+        report.tools.len()
+    );
+    for tool in &report.tools {
+        let _ = writeln!(out, "\n## {} (`{}`)\n", tool.display_name, tool.tool);
+        let _ = writeln!(out, "| Skill | Mode | Status | Last sync | Hash | Path |");
+        let _ = writeln!(out, "| --- | --- | --- | --- | --- | --- |");
+        for skill in &tool.skills {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | `{}` |",
+                escape_cell(&skill.skill),
+                skill.mode,
+                skill.status,
+                skill
+                    .synced_at
+                    .map(format_utc)
+                    .unwrap_or_else(|| "never".to_string()),
+                skill.hash.label(),
+                escape_cell(&skill.target_path)
+            );
+        }
+    }
+    if !report.undeployed.is_empty() {
+        let _ = writeln!(out, "\n## Not deployed\n");
+        for name in &report.undeployed {
+            let _ = writeln!(out, "- {}", name);
+        }
+    }
+    out
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// `2024-05-01 09:30 UTC` for epoch milliseconds.
+pub(crate) fn format_utc(ms: i64) -> String {
+    let secs = ms.div_euclid(1000);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60
+    )
+}
+
+/// Build the report and, with `dest`, also write it there.
+pub fn export_deployment_report(
+    store: &SkillStore,
+    central: &Path,
+    format: ReportFormat,
+    dest: Option<&Path>,
+    now: i64,
+) -> Result<String> {
+    let report = build_deployment_report(store, central, now)?;
+    let text = render_deployment_report(&report, format)?;
+    if let Some(dest) = dest {
+        if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+        }
+        std::fs::write(dest, &text).with_context(|| format!("write {:?}", dest))?;
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+#[path = "tests/deployment_report.rs"]
+mod tests;
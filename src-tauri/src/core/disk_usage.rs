@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::app_stats::dir_size;
+use super::skill_store::{SkillDiskUsageRecord, SkillRecord, SkillStore};
+use super::sync_engine::SyncMode;
+
+/// Cached sizes older than this are measured again for the largest-skills view.
+const DISK_USAGE_MAX_AGE_MS: i64 = 60 * 60 * 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillDiskUsage {
+    pub skill_id: String,
+    pub name: String,
+    pub central_bytes: u64,
+    /// Copies in tool and project directories; symlinked targets take no extra space.
+    pub target_bytes: u64,
+    pub total_bytes: u64,
+    pub computed_at: i64,
+}
+
+/// Measure the skill's central directory and copy targets and cache the result.
+pub fn measure_skill_disk_usage(
+    store: &SkillStore,
+    skill: &SkillRecord,
+    now: i64,
+) -> Result<SkillDiskUsageRecord> {
+    let copy = SyncMode::Copy.as_key();
+    let mut target_bytes = 0;
+    for target in store.list_skill_targets(&skill.id)? {
+        if target.mode == copy {
+            target_bytes += dir_size(Path::new(&target.target_path));
+        }
+    }
+    for target in store.list_skill_project_targets(&skill.id)? {
+        if target.mode == copy {
+            target_bytes += dir_size(Path::new(&target.target_path));
+        }
+    }
+    let record = SkillDiskUsageRecord {
+        skill_id: skill.id.clone(),
+        central_bytes: dir_size(Path::new(&skill.central_path)) as i64,
+        target_bytes: target_bytes as i64,
+        computed_at: now,
+    };
+    store.upsert_skill_disk_usage(&record)?;
+    Ok(record)
+}
+
+/// Skills by total size, largest first, at most `limit` of them. Sizes not measured yet or
+/// older than an hour are measured again; `refresh` measures every skill.
+pub fn largest_skills(
+    store: &SkillStore,
+    limit: Option<usize>,
+    refresh: bool,
+    now: i64,
+) -> Result<Vec<SkillDiskUsage>> {
+    let cached: HashMap<String, SkillDiskUsageRecord> = store
+        .list_skill_disk_usage()?
+        .into_iter()
+        .map(|r| (r.skill_id.clone(), r))
+        .collect();
+    let mut usage = Vec::new();
+    for skill in store.list_skills()? {
+        let record = match cached.get(&skill.id) {
+            Some(record) if !refresh && now - record.computed_at < DISK_USAGE_MAX_AGE_MS => {
+                record.clone()
+            }
+            _ => measure_skill_disk_usage(store, &skill, now)?,
+        };
+        let (central_bytes, target_bytes) =
+            (record.central_bytes as u64, record.target_bytes as u64);
+        usage.push(SkillDiskUsage {
+            skill_id: skill.id,
+            name: skill.name,
+            central_bytes,
+            target_bytes,
+            total_bytes: central_bytes + target_bytes,
+            computed_at: record.computed_at,
+        });
+    }
+    usage.sort_by(|a, b| {
+        b.total_bytes
+            .cmp(&a.total_bytes)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    if let Some(limit) = limit {
+        usage.truncate(limit);
+    }
+    Ok(usage)
+}
+
+/// Last measured total size by skill id, for listings; skills never measured are absent.
+pub fn cached_disk_usage(store: &SkillStore) -> Result<HashMap<String, u64>> {
+    Ok(store
+        .list_skill_disk_usage()?
+        .into_iter()
+        .map(|r| (r.skill_id, (r.central_bytes + r.target_bytes) as u64))
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "tests/disk_usage.rs"]
+mod tests;
@@ -0,0 +1,452 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::app_stats::dir_size;
+use super::cache_cleanup::CACHE_DIR_NAME;
+use super::cloud_sync::scan_cloud_sync;
+use super::environments::with_proxy;
+use super::skill_store::{SkillStore, SCHEMA_VERSION};
+
+const GITHUB_URL: &str = "https://github.com";
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckCategory {
+    Database,
+    CentralRepo,
+    Tools,
+    Targets,
+    Network,
+    Cache,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DoctorCheck {
+    pub category: CheckCategory,
+    /// Stable identifier, e.g. `db_schema`, for the UI to localize.
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What the user can do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DoctorReport {
+    /// Worst status among `checks`.
+    pub status: CheckStatus,
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn new(checks: Vec<DoctorCheck>) -> Self {
+        let status = checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(CheckStatus::Ok);
+        Self { status, checks }
+    }
+}
+
+impl DoctorCheck {
+    fn new(category: CheckCategory, name: &str, status: CheckStatus, message: String) -> Self {
+        Self {
+            category,
+            name: name.to_string(),
+            status,
+            message,
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Local checks that need no network. `tool_dirs` are the skills directories of installed
+/// tools, keyed by tool; tools sharing a directory should be listed once.
+pub fn run_local_checks(
+    store: &SkillStore,
+    central_repo: &Path,
+    cache_dir: &Path,
+    tool_dirs: &[(String, PathBuf)],
+) -> Vec<DoctorCheck> {
+    let mut checks = vec![check_database(store)];
+    checks.push(check_dir(
+        CheckCategory::CentralRepo,
+        "central_repo",
+        central_repo,
+        true,
+    ));
+    checks.extend(check_cloud_sync(central_repo));
+    for (tool, dir) in tool_dirs {
+        checks.push(check_tool_dir(tool, dir));
+    }
+    checks.extend(check_targets(store));
+    checks.push(check_cache(cache_dir));
+    checks
+}
+
+/// Whether github.com answers over HTTPS (through the configured proxy, else the proxy
+/// environment variables).
+pub fn check_github() -> DoctorCheck {
+    check_github_at(GITHUB_URL, NETWORK_TIMEOUT)
+}
+
+fn check_github_at(url: &str, timeout: Duration) -> DoctorCheck {
+    let result = with_proxy(reqwest::blocking::Client::builder().timeout(timeout))
+        .and_then(|builder| builder.build())
+        .context("build http client")
+        .and_then(|client| {
+            client
+                .head(url)
+                .header("User-Agent", "skills-hub")
+                .send()
+                .context("request failed")
+        });
+    match result {
+        // Any HTTP answer (even a rate limit) proves the network path works.
+        Ok(resp) => DoctorCheck::new(
+            CheckCategory::Network,
+            "github",
+            CheckStatus::Ok,
+            format!("{} reachable (HTTP {})", url, resp.status().as_u16()),
+        ),
+        Err(err) => DoctorCheck::new(
+            CheckCategory::Network,
+            "github",
+            CheckStatus::Fail,
+            format!("{} unreachable: {:#}", url, err),
+        )
+        .fix("Check your network or proxy (HTTPS_PROXY); git installs and updates need GitHub."),
+    }
+}
+
+fn check_database(store: &SkillStore) -> DoctorCheck {
+    match store.schema_version() {
+        Ok(version) if version == SCHEMA_VERSION => DoctorCheck::new(
+            CheckCategory::Database,
+            "db_schema",
+            CheckStatus::Ok,
+            format!("database schema v{}", version),
+        ),
+        Ok(version) => DoctorCheck::new(
+            CheckCategory::Database,
+            "db_schema",
+            CheckStatus::Fail,
+            format!(
+                "database schema v{} does not match the app (v{})",
+                version, SCHEMA_VERSION
+            ),
+        )
+        .fix(if version > SCHEMA_VERSION {
+            "The database was written by a newer Skills Hub; update the app."
+        } else {
+            "Restart the app to run pending migrations."
+        }),
+        Err(err) => DoctorCheck::new(
+            CheckCategory::Database,
+            "db_schema",
+            CheckStatus::Fail,
+            format!("database cannot be opened: {:#}", err),
+        )
+        .fix(format!(
+            "Check permissions of {:?}, or move it aside to start fresh.",
+            store.db_path()
+        )),
+    }
+}
+
+/// A missing directory is a failure when `required`, otherwise only a warning since syncing
+/// creates it.
+fn check_dir(category: CheckCategory, name: &str, dir: &Path, required: bool) -> DoctorCheck {
+    if !dir.is_dir() {
+        let status = if required {
+            CheckStatus::Fail
+        } else {
+            CheckStatus::Warn
+        };
+        return DoctorCheck::new(category, name, status, format!("{:?} does not exist", dir)).fix(
+            if required {
+                "Open Settings and choose a storage path, or create the directory."
+            } else {
+                "It will be created on the first sync; create it manually if syncing fails."
+            },
+        );
+    }
+    match probe_writable(dir) {
+        Ok(()) => DoctorCheck::new(
+            category,
+            name,
+            CheckStatus::Ok,
+            format!("{:?} is writable", dir),
+        ),
+        Err(err) => DoctorCheck::new(
+            category,
+            name,
+            CheckStatus::Fail,
+            format!("{:?} is not writable: {:#}", dir, err),
+        )
+        .fix("Fix the directory permissions or ownership."),
+    }
+}
+
+/// Only reported when the Central Repo lives in a cloud-synced folder: placeholders look like
+/// missing content and conflict copies like duplicate files.
+fn check_cloud_sync(central_repo: &Path) -> Option<DoctorCheck> {
+    let report = scan_cloud_sync(central_repo).ok()?;
+    let provider = report.provider?;
+    let check = |status, message| {
+        DoctorCheck::new(CheckCategory::CentralRepo, "cloud_sync", status, message)
+    };
+    if report.placeholders.is_empty() && report.conflict_copies.is_empty() {
+        return Some(check(
+            CheckStatus::Ok,
+            format!(
+                "{:?} is synced by {}; all files are downloaded",
+                central_repo,
+                provider.label()
+            ),
+        ));
+    }
+    Some(
+        check(
+            CheckStatus::Warn,
+            format!(
+                "{:?} is synced by {}: {} files not downloaded, {} conflict copies",
+                central_repo,
+                provider.label(),
+                report.placeholders.len(),
+                report.conflict_copies.len()
+            ),
+        )
+        .fix(
+            "Mark the folder as always available offline, then merge or delete the conflict \
+             copies.",
+        ),
+    )
+}
+
+/// Why a tool directory cannot take synced skills, or takes them in a way the tool may not see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessIssue {
+    /// The directory (or the parent it would be created in) has no write permission.
+    ReadOnly,
+    /// Owned by another user, typically after running a tool or installer with sudo.
+    OtherOwner,
+    /// Inside a Flatpak, Snap or macOS app container; the sandboxed tool may not be able to
+    /// follow symlinks out to the Central Repo.
+    Sandboxed(&'static str),
+}
+
+impl AccessIssue {
+    /// Stable identifier for `TARGET_ACCESS|<key>|<path>` and the UI.
+    pub fn key(&self) -> &'static str {
+        match self {
+            AccessIssue::ReadOnly => "read_only",
+            AccessIssue::OtherOwner => "other_owner",
+            AccessIssue::Sandboxed(kind) => kind,
+        }
+    }
+
+    /// Whether writes into the directory fail, as opposed to succeeding but being unusable.
+    pub fn blocks_writes(&self) -> bool {
+        !matches!(self, AccessIssue::Sandboxed(_))
+    }
+
+    fn fix(&self, dir: &Path) -> String {
+        match self {
+            AccessIssue::ReadOnly => format!("Make it writable, e.g. `chmod u+w {:?}`.", dir),
+            AccessIssue::OtherOwner => format!(
+                "Take ownership back, e.g. `sudo chown -R \"$USER\" {:?}`; it was probably created \
+                 by a tool run with sudo.",
+                dir
+            ),
+            AccessIssue::Sandboxed("flatpak") => "Grant the app access to the Central Repo (`flatpak \
+                 override --user --filesystem=~/.skillshub <app-id>`) or sync this tool in copy mode."
+                .to_string(),
+            AccessIssue::Sandboxed("snap") => "Snap confinement hides most of the home directory; \
+                 move the Central Repo under the snap's home or sync this tool in copy mode."
+                .to_string(),
+            AccessIssue::Sandboxed(_) => "The app runs in a macOS sandbox container; sync this tool \
+                 in copy mode so it does not need to follow links out of the container."
+                .to_string(),
+        }
+    }
+}
+
+/// First access problem found for `dir`: sandbox containers by path, then permissions and
+/// ownership of `dir` or, when it does not exist yet, of the parent it would be created in.
+pub fn audit_dir_access(dir: &Path) -> Option<AccessIssue> {
+    let existing = dir.ancestors().find(|p| p.exists())?;
+    let resolved = existing
+        .canonicalize()
+        .map(|p| p.join(dir.strip_prefix(existing).unwrap_or(Path::new(""))))
+        .unwrap_or_else(|_| dir.to_path_buf());
+    if let Some(kind) = sandbox_kind(&resolved, dirs::home_dir().as_deref()) {
+        return Some(AccessIssue::Sandboxed(kind));
+    }
+    let meta = std::fs::metadata(existing).ok()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        let euid = unsafe { libc::geteuid() };
+        if euid != 0 && meta.uid() != euid {
+            return Some(AccessIssue::OtherOwner);
+        }
+        if euid != 0 && meta.mode() & 0o200 == 0 {
+            return Some(AccessIssue::ReadOnly);
+        }
+        None
+    }
+    #[cfg(not(unix))]
+    {
+        meta.permissions()
+            .readonly()
+            .then_some(AccessIssue::ReadOnly)
+    }
+}
+
+fn sandbox_kind(path: &Path, home: Option<&Path>) -> Option<&'static str> {
+    let parts: Vec<_> = path.components().map(|c| c.as_os_str()).collect();
+    let has_pair = |a: &str, b: &str| parts.windows(2).any(|w| w[0] == a && w[1] == b);
+    if has_pair(".var", "app") {
+        Some("flatpak")
+    } else if path.starts_with("/snap") || home.is_some_and(|h| path.starts_with(h.join("snap"))) {
+        Some("snap")
+    } else if has_pair("Library", "Containers") || has_pair("Library", "Group Containers") {
+        Some("macos_container")
+    } else {
+        None
+    }
+}
+
+/// Tool dirs get the access audit first, so a permission problem is reported with its cause
+/// rather than as a bare write failure.
+fn check_tool_dir(tool: &str, dir: &Path) -> DoctorCheck {
+    let name = format!("tool_dir:{}", tool);
+    let Some(issue) = audit_dir_access(dir) else {
+        return check_dir(CheckCategory::Tools, &name, dir, false);
+    };
+    let (status, message) = match issue {
+        AccessIssue::ReadOnly => (CheckStatus::Fail, format!("{:?} is read-only", dir)),
+        AccessIssue::OtherOwner => (
+            CheckStatus::Fail,
+            format!("{:?} is owned by another user", dir),
+        ),
+        AccessIssue::Sandboxed(kind) => (
+            CheckStatus::Warn,
+            format!("{:?} is inside a {} sandbox", dir, kind),
+        ),
+    };
+    DoctorCheck::new(CheckCategory::Tools, &name, status, message).fix(issue.fix(dir))
+}
+
+/// One check per target whose path is gone or whose symlink no longer resolves; a single OK
+/// entry when all are fine.
+fn check_targets(store: &SkillStore) -> Vec<DoctorCheck> {
+    let targets = match store.list_all_skill_target_paths() {
+        Ok(targets) => targets,
+        Err(err) => {
+            return vec![DoctorCheck::new(
+                CheckCategory::Targets,
+                "targets",
+                CheckStatus::Fail,
+                format!("failed to list targets: {:#}", err),
+            )]
+        }
+    };
+    let mut problems = Vec::new();
+    for (tool, path) in &targets {
+        let target = Path::new(path);
+        let name = format!("target:{}", tool);
+        match std::fs::symlink_metadata(target) {
+            Err(_) => problems.push(
+                DoctorCheck::new(
+                    CheckCategory::Targets,
+                    &name,
+                    CheckStatus::Warn,
+                    format!("{} target missing: {}", tool, path),
+                )
+                .fix("Re-sync the skill to this tool, or unsync it."),
+            ),
+            Ok(meta) if meta.file_type().is_symlink() && std::fs::metadata(target).is_err() => {
+                problems.push(
+                    DoctorCheck::new(
+                        CheckCategory::Targets,
+                        &name,
+                        CheckStatus::Fail,
+                        format!("{} symlink is broken: {}", tool, path),
+                    )
+                    .fix("The central copy moved or was deleted; re-sync the skill."),
+                )
+            }
+            Ok(_) => {}
+        }
+    }
+    if problems.is_empty() {
+        problems.push(DoctorCheck::new(
+            CheckCategory::Targets,
+            "targets",
+            CheckStatus::Ok,
+            format!("{} synced targets resolve", targets.len()),
+        ));
+    }
+    problems
+}
+
+fn check_cache(cache_dir: &Path) -> DoctorCheck {
+    if !cache_dir.exists() {
+        // Created on the first git install.
+        return DoctorCheck::new(
+            CheckCategory::Cache,
+            "cache_dir",
+            CheckStatus::Ok,
+            "cache directory not created yet".to_string(),
+        );
+    }
+    if let Err(err) = probe_writable(cache_dir) {
+        return DoctorCheck::new(
+            CheckCategory::Cache,
+            "cache_dir",
+            CheckStatus::Fail,
+            format!("{:?} is not writable: {:#}", cache_dir, err),
+        )
+        .fix("Fix the cache directory permissions; git installs clone into it.");
+    }
+    let size = dir_size(&cache_dir.join(CACHE_DIR_NAME));
+    DoctorCheck::new(
+        CheckCategory::Cache,
+        "cache_dir",
+        CheckStatus::Ok,
+        format!("git cache uses {} bytes", size),
+    )
+}
+
+fn probe_writable(dir: &Path) -> Result<()> {
+    let probe = dir.join(format!(".skills-hub-doctor-{}", Uuid::new_v4()));
+    std::fs::write(&probe, b"").with_context(|| format!("write {:?}", probe))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/doctor.rs"]
+mod tests;
@@ -0,0 +1,274 @@
+use std::path::Path;
+use std::sync::RwLock;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::central_repo::{check_central_repo_location, ensure_central_repo};
+use super::git_host_policy::{
+    clean_git_host_policy, clone_url_host, GitHostPolicy, GIT_HOST_POLICY_KEY,
+};
+use super::skill_store::SkillStore;
+
+pub const ENVIRONMENTS_KEY: &str = "environments";
+pub const ACTIVE_ENVIRONMENT_KEY: &str = "active_environment";
+pub const PROXY_URL_KEY: &str = "proxy_url";
+const CENTRAL_REPO_PATH_KEY: &str = "central_repo_path";
+
+/// The `proxy_url` setting as loaded at startup.
+static PROXY_URL: RwLock<Option<String>> = RwLock::new(None);
+
+/// The settings an environment carries. `None` means the app default: the `~/.skillshub`
+/// Central Repo, no git host restrictions, the proxy inherited from the process environment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentSettings {
+    #[serde(default)]
+    pub central_repo_path: Option<String>,
+    #[serde(default)]
+    pub git_host_policy: Option<GitHostPolicy>,
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Environment {
+    pub id: String,
+    pub name: String,
+    pub settings: EnvironmentSettings,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct EnvironmentList {
+    pub active: Option<String>,
+    pub environments: Vec<Environment>,
+}
+
+pub fn list_environments(store: &SkillStore) -> Result<EnvironmentList> {
+    Ok(EnvironmentList {
+        active: store.get_setting(ACTIVE_ENVIRONMENT_KEY)?,
+        environments: load_environments(store)?,
+    })
+}
+
+/// Create an environment (`id` of `None`) or replace the name and settings of an existing one.
+/// Names are unique, ignoring case. Saving the active environment applies its settings at once.
+pub fn save_environment<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    id: Option<&str>,
+    name: &str,
+    settings: EnvironmentSettings,
+    now: i64,
+) -> Result<Environment> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("environment name is empty");
+    }
+    let mut environments = load_environments(store)?;
+    if environments
+        .iter()
+        .any(|e| e.name.eq_ignore_ascii_case(name) && Some(e.id.as_str()) != id)
+    {
+        anyhow::bail!("an environment named {:?} already exists", name);
+    }
+    let settings = clean_settings(app, settings)?;
+    let environment = match id {
+        Some(id) => {
+            let existing = environments
+                .iter_mut()
+                .find(|e| e.id == id)
+                .ok_or_else(|| anyhow::anyhow!("environment not found"))?;
+            existing.name = name.to_string();
+            existing.settings = settings;
+            existing.updated_at = now;
+            existing.clone()
+        }
+        None => {
+            let environment = Environment {
+                id: Uuid::new_v4().to_string(),
+                name: name.to_string(),
+                settings,
+                created_at: now,
+                updated_at: now,
+            };
+            environments.push(environment.clone());
+            environment
+        }
+    };
+    store_environments(store, &mut environments)?;
+    if store.get_setting(ACTIVE_ENVIRONMENT_KEY)?.as_deref() == Some(environment.id.as_str()) {
+        apply_settings(store, &environment.settings)?;
+    }
+    Ok(environment)
+}
+
+/// Remove an environment. Deleting the active one keeps its settings live and leaves no
+/// environment active.
+pub fn delete_environment(store: &SkillStore, environment_id: &str) -> Result<()> {
+    let mut environments = load_environments(store)?;
+    let before = environments.len();
+    environments.retain(|e| e.id != environment_id);
+    if environments.len() == before {
+        anyhow::bail!("environment not found");
+    }
+    store_environments(store, &mut environments)?;
+    if store.get_setting(ACTIVE_ENVIRONMENT_KEY)?.as_deref() == Some(environment_id) {
+        store.delete_setting(ACTIVE_ENVIRONMENT_KEY)?;
+    }
+    Ok(())
+}
+
+/// Make `environment_id` the active environment. The live settings are first saved into the
+/// environment being left, so changes made while it was active are kept, then the target's
+/// settings replace them; settings it leaves unset go back to their defaults. Managed skills are
+/// not moved: each environment installs into its own Central Repo.
+pub fn switch_environment(
+    store: &SkillStore,
+    environment_id: &str,
+    now: i64,
+) -> Result<Environment> {
+    let mut environments = load_environments(store)?;
+    let target = environments
+        .iter()
+        .find(|e| e.id == environment_id)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("environment not found"))?;
+    let active = store.get_setting(ACTIVE_ENVIRONMENT_KEY)?;
+    if let Some(current) = environments
+        .iter_mut()
+        .find(|e| Some(&e.id) == active.as_ref() && e.id != target.id)
+    {
+        current.settings = live_settings(store)?;
+        current.updated_at = now;
+        store_environments(store, &mut environments)?;
+    }
+    if let Some(path) = &target.settings.central_repo_path {
+        ensure_central_repo(Path::new(path))?;
+    }
+    apply_settings(store, &target.settings)?;
+    store.set_setting(ACTIVE_ENVIRONMENT_KEY, &target.id)?;
+    log::info!("[environments] switched to {:?}", target.name);
+    Ok(target)
+}
+
+/// Load the `proxy_url` setting for git and HTTP requests, at startup and whenever an
+/// environment's settings are applied. The process environment is left alone: each client is
+/// handed the proxy (see `proxy_url`).
+pub fn load_proxy_url(store: &SkillStore) {
+    let proxy = store.get_setting(PROXY_URL_KEY).ok().flatten();
+    *PROXY_URL
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = proxy;
+}
+
+/// The configured proxy. `None` leaves git and reqwest to the proxy variables the app was
+/// started with.
+pub fn proxy_url() -> Option<String> {
+    PROXY_URL
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// `builder` with the configured proxy for every scheme. Loopback hosts are reached directly.
+pub fn with_proxy(
+    builder: reqwest::blocking::ClientBuilder,
+) -> reqwest::Result<reqwest::blocking::ClientBuilder> {
+    match proxy_url() {
+        // reqwest is built without SOCKS support; such a proxy is only used by git.
+        Some(url) if url.to_ascii_lowercase().starts_with("http") => Ok(builder.proxy(
+            reqwest::Proxy::all(url)?
+                .no_proxy(reqwest::NoProxy::from_string("localhost,127.0.0.1,::1")),
+        )),
+        _ => Ok(builder),
+    }
+}
+
+fn load_environments(store: &SkillStore) -> Result<Vec<Environment>> {
+    match store.get_setting(ENVIRONMENTS_KEY)? {
+        Some(raw) => Ok(serde_json::from_str(&raw)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn store_environments(store: &SkillStore, environments: &mut [Environment]) -> Result<()> {
+    environments.sort_by_key(|e| e.name.to_lowercase());
+    store.set_setting(ENVIRONMENTS_KEY, &serde_json::to_string(environments)?)
+}
+
+fn clean_settings<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    settings: EnvironmentSettings,
+) -> Result<EnvironmentSettings> {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let central_repo_path = trimmed(settings.central_repo_path);
+    if let Some(path) = &central_repo_path {
+        let path = Path::new(path);
+        if !path.is_absolute() {
+            anyhow::bail!("storage path must be absolute");
+        }
+        check_central_repo_location(app, path)?;
+    }
+    let proxy_url = trimmed(settings.proxy_url);
+    if let Some(url) = &proxy_url {
+        let scheme = url
+            .split_once("://")
+            .map(|(scheme, _)| scheme.to_ascii_lowercase());
+        if !matches!(
+            scheme.as_deref(),
+            Some("http" | "https" | "socks5" | "socks5h")
+        ) || clone_url_host(url).is_none()
+        {
+            anyhow::bail!("invalid proxy URL: {}", url);
+        }
+    }
+    Ok(EnvironmentSettings {
+        central_repo_path,
+        git_host_policy: settings
+            .git_host_policy
+            .map(clean_git_host_policy)
+            .transpose()?,
+        proxy_url,
+    })
+}
+
+fn live_settings(store: &SkillStore) -> Result<EnvironmentSettings> {
+    Ok(EnvironmentSettings {
+        central_repo_path: store.get_setting(CENTRAL_REPO_PATH_KEY)?,
+        git_host_policy: store
+            .get_setting(GIT_HOST_POLICY_KEY)?
+            .and_then(|raw| serde_json::from_str(&raw).ok()),
+        proxy_url: store.get_setting(PROXY_URL_KEY)?,
+    })
+}
+
+fn apply_settings(store: &SkillStore, settings: &EnvironmentSettings) -> Result<()> {
+    let policy = settings
+        .git_host_policy
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    for (key, value) in [
+        (CENTRAL_REPO_PATH_KEY, settings.central_repo_path.as_ref()),
+        (GIT_HOST_POLICY_KEY, policy.as_ref()),
+        (PROXY_URL_KEY, settings.proxy_url.as_ref()),
+    ] {
+        match value {
+            Some(value) => store.set_setting(key, value)?,
+            None => store.delete_setting(key)?,
+        }
+    }
+    load_proxy_url(store);
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/environments.rs"]
+mod tests;
@@ -1,11 +1,20 @@
+use std::collections::HashMap;
+use std::io::Read;
 use std::path::Path;
 use std::process::Command;
 use std::process::Stdio;
-use std::sync::OnceLock;
+use std::sync::{Mutex, MutexGuard, OnceLock};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use git2::{FetchOptions, Repository};
+use git2::build::RepoBuilder;
+use git2::{FetchOptions, Oid, ProxyOptions, RemoteCallbacks, Repository};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::environments::proxy_url;
+use super::progress::{self, ProgressReporter};
+use super::skill_deps::parse_version;
 
 pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Result<String> {
     // Prefer the system `git` binary if available. It tends to work better on macOS
@@ -35,10 +44,14 @@ pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Resul
                     err
                 );
                 if !allow_fallback {
-                    anyhow::bail!(
-                        "git 命令执行失败（为避免卡死，已停止并不再回退到内置 git）。请检查系统 git/网络/代理；或设置环境变量 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允许回退。\n{:#}",
-                        err
-                    );
+                    // A timeout already explains itself; keep its code.
+                    if err
+                        .chain()
+                        .any(|cause| cause.to_string().starts_with("GIT_TIMEOUT|"))
+                    {
+                        return Err(err);
+                    }
+                    anyhow::bail!("GIT_CLI_FAILED|{:#}", err);
                 }
                 log::warn!(
                     "[git_fetcher] falling back to libgit2 (SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1)"
@@ -54,7 +67,9 @@ pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Resul
         fetch_origin(&repo)?;
         repo
     } else {
-        Repository::clone(repo_url, dest)
+        RepoBuilder::new()
+            .fetch_options(fetch_options_with_progress())
+            .clone(repo_url, dest)
             .with_context(|| format!("clone {} into {:?}", repo_url, dest))?
     };
 
@@ -153,6 +168,9 @@ fn git_bin_works(bin: &str) -> bool {
 fn git_cmd() -> Command {
     let bin = resolve_git_bin().unwrap_or_else(|| "git".to_string());
     let mut cmd = Command::new(bin);
+    if let Some(proxy) = proxy_url() {
+        cmd.arg("-c").arg(format!("http.proxy={}", proxy));
+    }
     // Never block on interactive auth prompts inside a GUI app.
     cmd.env("GIT_TERMINAL_PROMPT", "0")
         .env("GIT_ASKPASS", "echo");
@@ -172,29 +190,110 @@ fn run_cmd_with_timeout(
         .stderr(Stdio::piped());
 
     let mut child = cmd.spawn().with_context(|| context.clone())?;
+    // Drain stderr while the command runs: `--progress` output can outgrow the pipe buffer.
+    let reporter = progress::current();
+    let stderr_reader = child
+        .stderr
+        .take()
+        .map(|pipe| std::thread::spawn(move || read_stderr(pipe, reporter)));
+    let join_stderr = move || {
+        stderr_reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default()
+    };
     let start = Instant::now();
     loop {
         if start.elapsed() > timeout {
             let _ = child.kill();
-            let stderr = child
-                .wait_with_output()
-                .map(|out| String::from_utf8_lossy(&out.stderr).to_string())
-                .unwrap_or_default();
+            let _ = child.wait();
+            let stderr = join_stderr();
             anyhow::bail!(
-                "git 操作超时（{}s）。请检查网络/代理是否可访问 GitHub；也可设置环境变量 SKILLS_HUB_GIT_TIMEOUT_SECS 增大超时。\n{}",
+                "GIT_TIMEOUT|{}|{}",
                 timeout.as_secs(),
-                stderr.trim()
+                String::from_utf8_lossy(&stderr).trim()
             );
         }
 
         match child.try_wait() {
-            Ok(Some(_)) => return child.wait_with_output().with_context(|| context.clone()),
+            Ok(Some(_)) => {
+                let mut out = child.wait_with_output().with_context(|| context.clone())?;
+                out.stderr = join_stderr();
+                return Ok(out);
+            }
             Ok(None) => std::thread::sleep(Duration::from_millis(200)),
             Err(err) => return Err(err).with_context(|| context.clone()),
         }
     }
 }
 
+/// Collect a git child's stderr, forwarding `--progress` lines to `reporter` as they arrive.
+/// Run `git -C <dir> <args>` with the fetch timeout. The exit status is left to the caller.
+pub(crate) fn run_git_in(dir: &Path, args: &[&str]) -> Result<std::process::Output> {
+    let mut cmd = git_cmd();
+    cmd.arg("-C").arg(dir).args(args);
+    run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git {} in {:?}", args.join(" "), dir),
+    )
+}
+
+fn read_stderr(mut pipe: impl Read, reporter: Option<ProgressReporter>) -> Vec<u8> {
+    let mut all = Vec::new();
+    let mut line_start = 0;
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = match pipe.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        all.extend_from_slice(&buf[..n]);
+        let Some(reporter) = &reporter else {
+            continue;
+        };
+        // Progress lines are rewritten in place with `\r`, so split on both line endings.
+        while let Some(pos) = all[line_start..]
+            .iter()
+            .position(|b| *b == b'\r' || *b == b'\n')
+        {
+            let line = String::from_utf8_lossy(&all[line_start..line_start + pos]);
+            if let Some((stage, done, total)) = progress::parse_git_progress(&line) {
+                reporter(stage, done, Some(total));
+            }
+            line_start += pos + 1;
+        }
+    }
+    all
+}
+
+/// Fetch options that forward libgit2 transfer progress to the current reporter, if any.
+fn fetch_options_with_progress<'a>() -> FetchOptions<'a> {
+    let mut opts = FetchOptions::new();
+    opts.proxy_options(proxy_options());
+    if let Some(reporter) = progress::current() {
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.transfer_progress(move |stats| {
+            reporter(
+                "cloning",
+                stats.received_objects() as u64,
+                Some(stats.total_objects() as u64),
+            );
+            true
+        });
+        opts.remote_callbacks(callbacks);
+    }
+    opts
+}
+
+/// The configured proxy for libgit2, which does not read the proxy variables on its own.
+fn proxy_options<'a>() -> ProxyOptions<'a> {
+    let mut proxy = ProxyOptions::new();
+    if let Some(url) = proxy_url() {
+        proxy.url(&url);
+    }
+    proxy
+}
+
 fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>) -> Result<String> {
     // Ensure parent exists so `git clone` can create dest.
     if let Some(parent) = dest.parent() {
@@ -203,11 +302,24 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
     }
 
     if dest.exists() {
-        // Fetch updates.
+        // Refreshes only need the new tip and its tree, so fetch it shallow and blobless like
+        // the initial clone; blobs are fetched on checkout and history is deepened on demand by
+        // `ensure_commit_available`. Caches cloned without a filter become partial here.
         let out = run_cmd_with_timeout(
             {
                 let mut cmd = git_cmd();
-                cmd.arg("-C").arg(dest).args(["fetch", "--prune", "origin"]);
+                cmd.arg("-C").arg(dest).args([
+                    "fetch",
+                    "--prune",
+                    "--depth",
+                    "1",
+                    "--filter=blob:none",
+                    "--no-tags",
+                    "origin",
+                ]);
+                if progress::current().is_some() {
+                    cmd.arg("--progress");
+                }
                 cmd
             },
             git_fetch_timeout(),
@@ -217,8 +329,9 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
             anyhow::bail!("git fetch failed: {}", String::from_utf8_lossy(&out.stderr));
         }
 
-        // Move local HEAD to fetched commit.
-        if let Some(branch) = branch {
+        // Move local HEAD to fetched commit. A tag (a pinned release) has no `origin/<tag>`;
+        // its clone fetches only the tag, so it is reset to `FETCH_HEAD` like the default branch.
+        if let Some(branch) = branch.filter(|branch| has_remote_branch(dest, branch)) {
             let out = run_cmd_with_timeout(
                 {
                     let mut cmd = git_cmd();
@@ -263,6 +376,9 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
         let mut cmd = git_cmd();
         cmd.arg("clone")
             .args(["--depth", "1", "--filter=blob:none", "--no-tags"]);
+        if progress::current().is_some() {
+            cmd.arg("--progress");
+        }
         if let Some(branch) = branch {
             cmd.arg("--branch").arg(branch).arg("--single-branch");
         }
@@ -317,9 +433,436 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
     Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
 }
 
+fn has_remote_branch(dest: &Path, branch: &str) -> bool {
+    let remote_ref = format!("refs/remotes/origin/{}", branch);
+    run_git_in(dest, &["rev-parse", "--verify", "--quiet", &remote_ref])
+        .map(|out| out.status.success())
+        .unwrap_or(false)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RemoteBranch {
+    pub name: String,
+    pub commit: String,
+    /// The branch the remote's HEAD points at, i.e. what a clone without a branch checks out.
+    pub is_default: bool,
+}
+
+/// List the branches of `repo_url` without cloning it, default branch first, then by name.
+pub fn list_remote_branches(repo_url: &str) -> Result<Vec<RemoteBranch>> {
+    if resolve_git_bin().is_none() {
+        return list_remote_branches_via_libgit2(repo_url);
+    }
+    let mut cmd = git_cmd();
+    cmd.args([
+        "ls-remote",
+        "--symref",
+        "--",
+        repo_url,
+        "HEAD",
+        "refs/heads/*",
+    ]);
+    let out = run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git ls-remote {}", repo_url),
+    )?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "git ls-remote {} failed: {}",
+            repo_url,
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(parse_ls_remote_branches(&String::from_utf8_lossy(
+        &out.stdout,
+    )))
+}
+
+/// Parse `git ls-remote --symref` output into branches.
+pub(crate) fn parse_ls_remote_branches(output: &str) -> Vec<RemoteBranch> {
+    let mut default_ref = None;
+    let mut branches = Vec::new();
+    for line in output.lines() {
+        let Some((left, name)) = line.split_once('\t') else {
+            continue;
+        };
+        if let Some(target) = left.strip_prefix("ref: ") {
+            if name == "HEAD" {
+                default_ref = Some(target.to_string());
+            }
+        } else if let Some(branch) = name.strip_prefix("refs/heads/") {
+            branches.push((name.to_string(), branch.to_string(), left.to_string()));
+        }
+    }
+    sort_branches(
+        branches
+            .into_iter()
+            .map(|(full, name, commit)| RemoteBranch {
+                is_default: default_ref.as_deref() == Some(full.as_str()),
+                name,
+                commit,
+            })
+            .collect(),
+    )
+}
+
+fn list_remote_branches_via_libgit2(repo_url: &str) -> Result<Vec<RemoteBranch>> {
+    let mut remote = git2::Remote::create_detached(repo_url)?;
+    let mut connection = remote
+        .connect_auth(git2::Direction::Fetch, None, Some(proxy_options()))
+        .with_context(|| format!("connect to remote {}", repo_url))?;
+    let remote = connection.remote();
+    let default_ref = remote
+        .default_branch()
+        .ok()
+        .and_then(|buf| buf.as_str().map(str::to_string));
+    let branches = remote
+        .list()?
+        .iter()
+        .filter_map(|head| {
+            let name = head.name().strip_prefix("refs/heads/")?;
+            Some(RemoteBranch {
+                name: name.to_string(),
+                commit: head.oid().to_string(),
+                is_default: default_ref.as_deref() == Some(head.name()),
+            })
+        })
+        .collect();
+    Ok(sort_branches(branches))
+}
+
+fn sort_branches(mut branches: Vec<RemoteBranch>) -> Vec<RemoteBranch> {
+    branches.sort_by(|a, b| b.is_default.cmp(&a.is_default).then(a.name.cmp(&b.name)));
+    branches
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RemoteTag {
+    pub name: String,
+    /// The commit the tag points at (peeled for annotated tags).
+    pub commit: String,
+    /// The tag name read as semver (`v1.2` is `1.2.0`), if it is one.
+    pub version: Option<String>,
+    /// Tagger date of annotated tags, commit date of lightweight ones (unix seconds). Unknown
+    /// when listed without a `git` binary or when fetching the tagged commits failed.
+    pub date: Option<i64>,
+}
+
+/// Tag dates already fetched, by repository URL and tag name, with the commit they were read
+/// for.
+type TagDates = HashMap<String, HashMap<String, (String, i64)>>;
+static TAG_DATES: OnceLock<Mutex<TagDates>> = OnceLock::new();
+/// Past this many tags without a date, all tags are fetched with one refspec.
+const MAX_TAG_REFSPECS: usize = 50;
+
+/// List the tags of `repo_url`, newest first: semver tags by version, then the rest by date.
+/// Dates come from fetching only the tagged commits into a scratch repo, and are kept for the
+/// session so later calls fetch only new or moved tags; without them (no `git` binary, or the
+/// fetch failed) the rest are ordered by name.
+pub fn list_remote_tags(repo_url: &str) -> Result<Vec<RemoteTag>> {
+    if resolve_git_bin().is_none() {
+        return list_remote_tags_via_libgit2(repo_url);
+    }
+    let mut cmd = git_cmd();
+    cmd.args(["ls-remote", "--tags", "--", repo_url]);
+    let out = run_cmd_with_timeout(
+        cmd,
+        git_fetch_timeout(),
+        format!("git ls-remote --tags {}", repo_url),
+    )?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "git ls-remote {} failed: {}",
+            repo_url,
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    let mut tags = parse_ls_remote_tags(&String::from_utf8_lossy(&out.stdout));
+    if tags.is_empty() {
+        return Ok(tags);
+    }
+    let missing = tags_without_dates(repo_url, &tags);
+    if !missing.is_empty() {
+        match fetch_tag_dates(repo_url, &missing) {
+            Ok(dates) => {
+                let mut cache = tag_dates();
+                let known = cache.entry(repo_url.to_string()).or_default();
+                for (name, date) in dates {
+                    if let Some(tag) = tags.iter().find(|tag| tag.name == name) {
+                        known.insert(name, (tag.commit.clone(), date));
+                    }
+                }
+            }
+            Err(err) => log::warn!(
+                "[git_fetcher] tag dates of {} unavailable: {:#}",
+                repo_url,
+                err
+            ),
+        }
+    }
+    if let Some(known) = tag_dates().get(repo_url) {
+        for tag in &mut tags {
+            tag.date = known
+                .get(&tag.name)
+                .filter(|(commit, _)| *commit == tag.commit)
+                .map(|(_, date)| *date);
+        }
+    }
+    Ok(sort_tags(tags))
+}
+
+fn tag_dates() -> MutexGuard<'static, TagDates> {
+    TAG_DATES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Names of the tags whose date is not known for their current commit.
+fn tags_without_dates(repo_url: &str, tags: &[RemoteTag]) -> Vec<String> {
+    let cache = tag_dates();
+    let known = cache.get(repo_url);
+    tags.iter()
+        .filter(|tag| {
+            known
+                .and_then(|known| known.get(&tag.name))
+                .map_or(true, |(commit, _)| *commit != tag.commit)
+        })
+        .map(|tag| tag.name.clone())
+        .collect()
+}
+
+/// Parse `git ls-remote --tags` output into tags, without dates.
+pub(crate) fn parse_ls_remote_tags(output: &str) -> Vec<RemoteTag> {
+    collect_tags(output.lines().filter_map(|line| {
+        let (commit, name) = line.split_once('\t')?;
+        Some((name.to_string(), commit.to_string()))
+    }))
+}
+
+/// Tags from `(ref name, object id)` pairs. Annotated tags are listed twice; the `^{}` entry
+/// carries the commit.
+fn collect_tags(refs: impl Iterator<Item = (String, String)>) -> Vec<RemoteTag> {
+    let mut tags: Vec<RemoteTag> = Vec::new();
+    for (full_name, commit) in refs {
+        let Some(name) = full_name.strip_prefix("refs/tags/") else {
+            continue;
+        };
+        let (name, peeled) = match name.strip_suffix("^{}") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        match tags.iter_mut().find(|tag| tag.name == name) {
+            Some(tag) if peeled => tag.commit = commit,
+            Some(_) => {}
+            None => tags.push(RemoteTag {
+                name: name.to_string(),
+                commit,
+                version: parse_version(name).map(|v| v.to_string()),
+                date: None,
+            }),
+        }
+    }
+    sort_tags(tags)
+}
+
+/// `(tag, unix date)` for each of `names`: tagger date of annotated tags, commit date of
+/// lightweight ones. Fetches the tagged commits without trees or history.
+fn fetch_tag_dates(repo_url: &str, names: &[String]) -> Result<Vec<(String, i64)>> {
+    let scratch = std::env::temp_dir().join(format!("skills-hub-tags-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&scratch).with_context(|| format!("create {:?}", scratch))?;
+    let refspecs: Vec<String> = if names.len() > MAX_TAG_REFSPECS {
+        vec!["+refs/tags/*:refs/tags/*".to_string()]
+    } else {
+        names
+            .iter()
+            .map(|name| format!("+refs/tags/{0}:refs/tags/{0}", name))
+            .collect()
+    };
+    let mut fetch = vec![
+        "fetch",
+        "--quiet",
+        "--depth",
+        "1",
+        "--filter=tree:0",
+        "--no-tags",
+        "origin",
+    ];
+    fetch.extend(refspecs.iter().map(String::as_str));
+    let dates = (|| -> Result<Vec<(String, i64)>> {
+        let steps: [&[&str]; 3] = [
+            &["init", "--quiet", "--bare"],
+            &["remote", "add", "origin", repo_url],
+            &fetch,
+        ];
+        for args in steps {
+            let out = run_git_in(&scratch, args)?;
+            if !out.status.success() {
+                anyhow::bail!(
+                    "git {} failed: {}",
+                    args[0],
+                    String::from_utf8_lossy(&out.stderr).trim()
+                );
+            }
+        }
+        let out = run_git_in(
+            &scratch,
+            &[
+                "for-each-ref",
+                "--format=%(refname:strip=2)%09%(creatordate:unix)",
+                "refs/tags",
+            ],
+        )?;
+        Ok(String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (name, date) = line.split_once('\t')?;
+                Some((name.to_string(), date.parse().ok()?))
+            })
+            .collect())
+    })();
+    let _ = std::fs::remove_dir_all(&scratch);
+    dates
+}
+
+fn list_remote_tags_via_libgit2(repo_url: &str) -> Result<Vec<RemoteTag>> {
+    let mut remote = git2::Remote::create_detached(repo_url)?;
+    let mut connection = remote
+        .connect_auth(git2::Direction::Fetch, None, Some(proxy_options()))
+        .with_context(|| format!("connect to remote {}", repo_url))?;
+    let remote = connection.remote();
+    let refs: Vec<(String, String)> = remote
+        .list()?
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid().to_string()))
+        .collect();
+    Ok(collect_tags(refs.into_iter()))
+}
+
+pub(crate) fn sort_tags(mut tags: Vec<RemoteTag>) -> Vec<RemoteTag> {
+    tags.sort_by(|a, b| {
+        let version = |tag: &RemoteTag| tag.version.as_deref().and_then(parse_version);
+        match (version(a), version(b)) {
+            (Some(va), Some(vb)) => vb.cmp(&va).then(b.date.cmp(&a.date)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.date.cmp(&a.date).then(a.name.cmp(&b.name)),
+        }
+    });
+    tags
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CommitSummary {
+    pub id: String,
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    pub time: i64,
+}
+
+/// List commits reachable from `to` but not from `from` (newest first).
+/// When `subpath` is set, only commits that changed that path are returned.
+pub fn log_between(
+    repo_dir: &Path,
+    from: &str,
+    to: &str,
+    subpath: Option<&str>,
+    limit: usize,
+) -> Result<Vec<CommitSummary>> {
+    let from_oid = Oid::from_str(from).with_context(|| format!("invalid revision {}", from))?;
+    let to_oid = Oid::from_str(to).with_context(|| format!("invalid revision {}", to))?;
+    ensure_commit_available(repo_dir, from_oid)?;
+
+    let repo =
+        Repository::open(repo_dir).with_context(|| format!("open repo at {:?}", repo_dir))?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    walk.push(to_oid)?;
+    walk.hide(from_oid)?;
+
+    let mut out = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if let Some(subpath) = subpath {
+            if !commit_touches_path(&commit, Path::new(subpath))? {
+                continue;
+            }
+        }
+        out.push(CommitSummary {
+            id: commit.id().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            message: commit.message().unwrap_or_default().trim().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            time: commit.time().seconds(),
+        });
+        if out.len() >= limit {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+fn commit_touches_path(commit: &git2::Commit, path: &Path) -> Result<bool> {
+    let current = commit.tree()?.get_path(path).ok().map(|e| e.id());
+    if commit.parent_count() == 0 {
+        return Ok(current.is_some());
+    }
+    for parent in commit.parents() {
+        let previous = parent.tree()?.get_path(path).ok().map(|e| e.id());
+        if previous != current {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Cache clones are shallow; deepen them when an older revision is needed for history.
+fn ensure_commit_available(repo_dir: &Path, oid: Oid) -> Result<()> {
+    let has_commit = |dir: &Path| {
+        Repository::open(dir)
+            .map(|repo| repo.find_commit(oid).is_ok())
+            .unwrap_or(false)
+    };
+    if has_commit(repo_dir) {
+        return Ok(());
+    }
+    if resolve_git_bin().is_none() || !repo_dir.join(".git/shallow").exists() {
+        anyhow::bail!("revision {} not found in repository history", oid);
+    }
+
+    let out = run_cmd_with_timeout(
+        {
+            let mut cmd = git_cmd();
+            cmd.arg("-C").arg(repo_dir).args([
+                "fetch",
+                "--unshallow",
+                "--filter=blob:none",
+                "origin",
+            ]);
+            cmd
+        },
+        git_timeout(),
+        format!("git fetch --unshallow in {:?}", repo_dir),
+    )?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "git fetch --unshallow failed: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+    if !has_commit(repo_dir) {
+        anyhow::bail!("revision {} not found in repository history", oid);
+    }
+    Ok(())
+}
+
+/// libgit2 has no partial clone filters, so refreshes are only kept shallow.
 fn fetch_origin(repo: &Repository) -> Result<()> {
     let mut remote = repo.find_remote("origin")?;
-    let mut opts = FetchOptions::new();
+    let mut opts = fetch_options_with_progress();
+    opts.depth(1);
     remote.fetch(
         &["refs/heads/*:refs/remotes/origin/*"],
         Some(&mut opts),
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+
+pub const GIT_HOST_POLICY_KEY: &str = "git_host_policy";
+
+/// Hosts git sources may be fetched from. An entry is a host name (`github.com`) or a
+/// `*.`-prefixed domain matching its subdomains (`*.corp.example`). An empty `allow` list
+/// allows every host not blocked; `block` wins over `allow`. Local paths are never restricted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitHostPolicy {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub block: Vec<String>,
+}
+
+impl GitHostPolicy {
+    pub fn permits(&self, host: &str) -> bool {
+        let listed = |entries: &[String]| entries.iter().any(|e| host_matches(e, host));
+        !listed(&self.block) && (self.allow.is_empty() || listed(&self.allow))
+    }
+}
+
+fn host_matches(entry: &str, host: &str) -> bool {
+    match entry.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.') && sub.len() > 1),
+        None => entry == host,
+    }
+}
+
+/// Lowercased host of a clone URL (`https://`, `ssh://`, `git://` or scp-like
+/// `git@host:path`); `None` for local paths and `file://` URLs.
+pub fn clone_url_host(url: &str) -> Option<String> {
+    let url = url.trim();
+    let authority = match url.split_once("://") {
+        Some((scheme, _)) if scheme.eq_ignore_ascii_case("file") => return None,
+        Some((_, rest)) => rest.split(['/', '?', '#']).next()?,
+        None => {
+            // scp-like syntax: a colon before any slash, and not a Windows drive letter.
+            let (authority, _) = url.split_once(':')?;
+            if authority.contains('/') || authority.contains('\\') || authority.len() < 2 {
+                return None;
+            }
+            authority
+        }
+    };
+    let host = authority.rsplit('@').next()?;
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+pub fn get_git_host_policy(store: &SkillStore) -> GitHostPolicy {
+    store
+        .get_setting(GIT_HOST_POLICY_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the policy with entries trimmed, lowercased and deduplicated. Entries that are not a
+/// bare host or `*.domain` are rejected.
+pub fn set_git_host_policy(store: &SkillStore, policy: GitHostPolicy) -> Result<GitHostPolicy> {
+    let cleaned = clean_git_host_policy(policy)?;
+    store.set_setting(GIT_HOST_POLICY_KEY, &serde_json::to_string(&cleaned)?)?;
+    Ok(cleaned)
+}
+
+pub(crate) fn clean_git_host_policy(policy: GitHostPolicy) -> Result<GitHostPolicy> {
+    let clean = |entries: Vec<String>| -> Result<Vec<String>> {
+        let mut out: Vec<String> = Vec::new();
+        for entry in entries {
+            let entry = entry.trim().to_ascii_lowercase();
+            if entry.is_empty() {
+                continue;
+            }
+            let name = entry.strip_prefix("*.").unwrap_or(&entry);
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            {
+                anyhow::bail!("invalid git host entry: {}", entry);
+            }
+            if !out.contains(&entry) {
+                out.push(entry);
+            }
+        }
+        Ok(out)
+    };
+    Ok(GitHostPolicy {
+        allow: clean(policy.allow)?,
+        block: clean(policy.block)?,
+    })
+}
+
+/// Refuse to fetch from a host the policy does not permit, with `GIT_HOST_BLOCKED|<host>`.
+pub fn ensure_git_host_allowed(store: &SkillStore, clone_url: &str) -> Result<()> {
+    let Some(host) = clone_url_host(clone_url) else {
+        return Ok(());
+    };
+    if !get_git_host_policy(store).permits(&host) {
+        anyhow::bail!("GIT_HOST_BLOCKED|{}", host);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/git_host_policy.rs"]
+mod tests;
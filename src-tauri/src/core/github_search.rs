@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use reqwest::blocking::Client;
 use serde::Deserialize;
 
+use super::environments::with_proxy;
+
 #[derive(Debug, Deserialize)]
 struct SearchResponse {
     items: Vec<RepoItem>,
@@ -36,7 +38,7 @@ fn search_github_repos_inner(
     query: &str,
     limit: usize,
 ) -> Result<Vec<RepoSummary>> {
-    let client = Client::new();
+    let client = with_proxy(Client::builder())?.build()?;
     let base_url = base_url.trim_end_matches('/');
     let url = format!(
         "{}/search/repositories?q={}&per_page={}",
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::content_hash::hash_dir_cached;
+use super::skill_lock::{lock_skill_within, DEFAULT_LOCK_WAIT};
+use super::skill_store::{SkillProjection, SkillStore};
+
+/// How long one attempt waits for the skill lock before the job goes to the back of the queue,
+/// so a long update on one skill doesn't hold up hashing the others.
+const HASH_LOCK_WAIT: Duration = Duration::from_millis(250);
+/// A busy skill is retried for about as long as a regular mutation would wait for it.
+const MAX_HASH_ATTEMPTS: u32 = (DEFAULT_LOCK_WAIT.as_millis() / HASH_LOCK_WAIT.as_millis()) as u32;
+
+struct HashJob {
+    store: SkillStore,
+    skill_id: String,
+    central_path: PathBuf,
+    attempts: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HashOutcome {
+    /// The hash was computed and stored on the skill.
+    Stored(String),
+    /// The skill was deleted or moved in the meantime; nothing was stored.
+    Stale,
+    /// Another operation held the skill for longer than `HASH_LOCK_WAIT`.
+    Busy,
+}
+
+static WORKER: OnceLock<Sender<HashJob>> = OnceLock::new();
+
+/// Hash `central_path` on the background worker and store the result on the skill, which
+/// emits a `content_hashed` store change. Jobs run one at a time in the order they were queued.
+pub fn queue_content_hash(store: &SkillStore, skill_id: &str, central_path: &Path) {
+    send(HashJob {
+        store: store.clone(),
+        skill_id: skill_id.to_string(),
+        central_path: central_path.to_path_buf(),
+        attempts: 0,
+    });
+}
+
+/// Queue every skill without a stored hash, e.g. after a migration cleared them.
+pub fn queue_missing_hashes(store: &SkillStore) {
+    match store.list_skills_with(SkillProjection::Summary) {
+        Ok(skills) => {
+            for skill in skills.iter().filter(|s| s.content_hash.is_none()) {
+                queue_content_hash(store, &skill.id, Path::new(&skill.central_path));
+            }
+        }
+        Err(err) => log::warn!("[hash] failed to list skills to hash: {:#}", err),
+    }
+}
+
+fn send(job: HashJob) {
+    if let Err(err) = WORKER.get_or_init(spawn_worker).send(job) {
+        log::warn!(
+            "[hash] worker unavailable, {} stays unhashed",
+            err.0.skill_id
+        );
+    }
+}
+
+fn spawn_worker() -> Sender<HashJob> {
+    let (tx, rx) = channel::<HashJob>();
+    let spawned = std::thread::Builder::new()
+        .name("content-hash".to_string())
+        .spawn(move || {
+            for mut job in rx {
+                match hash_and_store(&job.store, &job.skill_id, &job.central_path) {
+                    Ok(HashOutcome::Busy) if job.attempts + 1 < MAX_HASH_ATTEMPTS => {
+                        job.attempts += 1;
+                        send(job);
+                    }
+                    Ok(HashOutcome::Busy) => {
+                        log::warn!("[hash] {} stayed busy, leaving it unhashed", job.skill_id);
+                    }
+                    Ok(_) => {}
+                    Err(err) => log::warn!("[hash] failed to hash {}: {:#}", job.skill_id, err),
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        log::warn!("[hash] failed to start worker: {}", err);
+    }
+    tx
+}
+
+/// Hash one skill and persist it. Holds the skill lock so an update can't swap the directory
+/// mid-read, but only waits `HASH_LOCK_WAIT` for it.
+pub fn hash_and_store(
+    store: &SkillStore,
+    skill_id: &str,
+    central_path: &Path,
+) -> Result<HashOutcome> {
+    let Ok(_guard) = lock_skill_within(skill_id, "hash", HASH_LOCK_WAIT) else {
+        return Ok(HashOutcome::Busy);
+    };
+    if !central_path.exists() {
+        return Ok(HashOutcome::Stale);
+    }
+    // Only files whose size or mtime changed since the last run are read again.
+    let mut cache = store.load_file_hashes(skill_id)?;
+    let hash = hash_dir_cached(central_path, &mut cache)?;
+    let stored =
+        store.set_skill_content_hash(skill_id, central_path.to_string_lossy().as_ref(), &hash)?;
+    if !stored {
+        return Ok(HashOutcome::Stale);
+    }
+    store.save_file_hashes(skill_id, &cache)?;
+    Ok(HashOutcome::Stored(hash))
+}
+
+#[cfg(test)]
+#[path = "tests/hash_worker.rs"]
+mod tests;
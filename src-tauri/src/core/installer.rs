@@ -5,17 +5,60 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use tauri::Manager;
 use uuid::Uuid;
+use walkdir::WalkDir;
 
 use super::cache_cleanup::get_git_cache_ttl_secs;
-use super::central_repo::{ensure_central_repo, resolve_central_repo_path};
+use super::central_git::record_central_change;
+use super::central_repo::{
+    ensure_central_repo, repo_id_for_path, resolve_install_repo_path, scan_central_repo, OrphanDir,
+};
 use super::content_hash::hash_dir;
-use super::git_fetcher::clone_or_pull;
-use super::skill_store::{SkillRecord, SkillStore};
-use super::skill_metadata::SkillMetadata;
-use super::sync_engine::copy_dir_recursive;
-use super::sync_engine::sync_dir_copy_with_overwrite;
-use super::tool_adapters::adapter_by_key;
-use super::tool_adapters::is_tool_installed;
+use super::cursor_rules::{write_rule_skill, CURSOR_RULE_SOURCE};
+use super::git_fetcher::{
+    clone_or_pull, list_remote_branches, list_remote_tags, log_between, CommitSummary,
+    RemoteBranch, RemoteTag,
+};
+use super::git_host_policy::ensure_git_host_allowed;
+use super::hash_worker::queue_content_hash;
+use super::lockfile::current_hash;
+use super::secret_scan::scan_skill_dir;
+use super::skill_deps::violations_for_candidate;
+use super::skill_executables::contains_executables;
+use super::skill_files::{ensure_plain_name, resolve_in_skill, write_file_atomic};
+use super::skill_license::detect_license;
+use super::skill_lint::{lint_skill_dir, LintSeverity};
+use super::skill_lock::{lock_skill, SkillLockGuard};
+use super::skill_metadata::{
+    generate_skill_yaml, validate_skill_md, write_metadata_patch, FrontmatterIssue,
+    SkillFrontmatter, SkillMetadata, SkillMetadataPatch,
+};
+use super::skill_signing::ensure_signature_accepted;
+use super::skill_store::{SkillProjection, SkillRecord, SkillStore, SkillTargetRecord};
+use super::skill_sync::{enabled_status, resync_copy_targets};
+use super::sync_engine::{copy_dir_recursive, remove_path_any, sync_dir_for_tool_with_overwrite};
+use super::token_estimate::estimate_skill_tokens;
+use super::url_skill::{fetch_url, write_url_skill, UrlFetch, URL_SOURCE};
+use super::webhooks::{notify_webhooks, WebhookEvent};
+
+/// Never searched for nested skills: VCS metadata and dependency or build output.
+const SCAN_IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    ".venv",
+    "venv",
+    "__pycache__",
+];
+/// How deep below the repo root a skill may sit; `skills/.curated/x` is depth 3.
+const MAX_SCAN_DEPTH: usize = 6;
+/// Directories a repo scan visits before giving up.
+const MAX_SCAN_ENTRIES: usize = 20_000;
+/// Skills a repo scan returns at most; bigger repos can be opened by folder URL.
+const MAX_SCAN_RESULTS: usize = 500;
+/// Secret findings listed in a `SECRETS_FOUND` error; the rest are only counted.
+const MAX_REPORTED_SECRETS: usize = 5;
 
 pub struct InstallResult {
     pub skill_id: String,
@@ -24,128 +67,503 @@ pub struct InstallResult {
     pub content_hash: Option<String>,
 }
 
-/// Detect if a directory contains a .git folder and extract the origin remote URL.
-/// If the path is a symlink, resolves it to the real path first.
-/// Returns (source_type, source_ref, source_revision) tuple.
-fn detect_git_origin(source_path: &Path) -> (String, Option<String>, Option<String>) {
-    // Resolve symlinks to get the real path
-    let resolved_path = std::fs::canonicalize(source_path).unwrap_or_else(|_| source_path.to_path_buf());
+/// What an install does when the central repo already has a directory with the skill's name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnConflict {
+    /// Refuse with `SKILL_EXISTS`.
+    #[default]
+    Fail,
+    /// Keep the directory as it is and manage it; returns the existing skill if already managed.
+    Adopt,
+    /// Replace the directory with the new content, keeping the skill id if it was managed.
+    Replace,
+    /// Install next to it as `<name>-2`, `<name>-3`, ...
+    InstallAsNewName,
+}
 
-    let git_dir = resolved_path.join(".git");
-    if !git_dir.exists() {
-        return (
-            "local".to_string(),
-            Some(source_path.to_string_lossy().to_string()),
-            None,
-        );
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InstallOptions {
+    /// Skip the lint and dependency gates.
+    pub force: bool,
+    pub on_conflict: OnConflict,
+}
+
+impl InstallOptions {
+    pub fn force(force: bool) -> Self {
+        Self {
+            force,
+            ..Self::default()
+        }
     }
+}
+
+/// (source_type, source_ref, source_revision) as stored on the skill record.
+pub(crate) type SkillSource = (String, Option<String>, Option<String>);
+
+enum InstallTarget {
+    /// Copy content to `path`; `replaces` is the record that managed it before, if any.
+    Copy {
+        name: String,
+        path: PathBuf,
+        replaces: Option<SkillRecord>,
+    },
+    /// Keep what is already at `path`.
+    Adopt {
+        name: String,
+        path: PathBuf,
+        existing: Option<SkillRecord>,
+    },
+}
 
-    // Try to open the git repository and get origin URL
-    match git2::Repository::open(&resolved_path) {
-        Ok(repo) => {
-            // Get origin remote URL
-            let origin_url = repo
-                .find_remote("origin")
-                .ok()
-                .and_then(|remote| remote.url().map(|s| s.to_string()));
-
-            // Get current HEAD commit hash
-            let revision = repo
-                .head()
-                .ok()
-                .and_then(|head| head.peel_to_commit().ok())
-                .map(|commit| commit.id().to_string());
-
-            if let Some(url) = origin_url {
-                // It's a git repo with a valid origin
-                ("git".to_string(), Some(url), revision)
-            } else {
-                // Git repo but no origin remote, treat as local
-                (
-                    "local".to_string(),
-                    Some(source_path.to_string_lossy().to_string()),
-                    None,
-                )
+/// Decide where an install named `name` goes, applying `on_conflict` if the directory exists.
+/// The name must be a single path component, since replacing or adopting acts on whatever
+/// directory it names.
+fn plan_install_target(
+    store: &SkillStore,
+    central_dir: &Path,
+    name: String,
+    on_conflict: OnConflict,
+) -> Result<InstallTarget> {
+    ensure_plain_name(&name)?;
+    let path = central_dir.join(&name);
+    if !path.exists() {
+        return Ok(InstallTarget::Copy {
+            name,
+            path,
+            replaces: None,
+        });
+    }
+    let existing = store
+        .list_skills()?
+        .into_iter()
+        .find(|s| Path::new(&s.central_path) == path);
+    match on_conflict {
+        OnConflict::Fail => anyhow::bail!(
+            "SKILL_EXISTS|{}|{}",
+            path.to_string_lossy(),
+            existing.is_some()
+        ),
+        OnConflict::Adopt => {
+            if existing.is_none() && !path.join("SKILL.md").is_file() {
+                anyhow::bail!("SKILL_INVALID|missing_skill_md");
             }
+            Ok(InstallTarget::Adopt {
+                name,
+                path,
+                existing,
+            })
         }
-        Err(_) => {
-            // Failed to open as git repo, treat as local
-            (
-                "local".to_string(),
-                Some(source_path.to_string_lossy().to_string()),
-                None,
-            )
+        OnConflict::Replace => Ok(InstallTarget::Copy {
+            name,
+            path,
+            replaces: existing,
+        }),
+        OnConflict::InstallAsNewName => {
+            let names: Vec<String> = store
+                .list_skills_with(SkillProjection::Summary)?
+                .into_iter()
+                .map(|s| s.name)
+                .collect();
+            let free = (2..)
+                .map(|n| format!("{}-{}", name, n))
+                .find(|candidate| {
+                    !central_dir.join(candidate).exists() && !names.contains(candidate)
+                })
+                .expect("unbounded range");
+            ensure_plain_name(&free)?;
+            Ok(InstallTarget::Copy {
+                path: central_dir.join(&free),
+                name: free,
+                replaces: None,
+            })
         }
     }
 }
 
-pub fn install_local_skill<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    store: &SkillStore,
-    source_path: &Path,
-    name: Option<String>,
-) -> Result<InstallResult> {
-    if !source_path.exists() {
-        anyhow::bail!("source path not found: {:?}", source_path);
+/// Copy `src` to `dest`. A replace copies into a staging dir next to `dest` and swaps it in,
+/// so a failed copy leaves the previous content where it was.
+fn copy_into_central(src: &Path, dest: &Path) -> Result<()> {
+    if !dest.exists() {
+        return copy_dir_recursive(src, dest)
+            .with_context(|| format!("copy {:?} -> {:?}", src, dest));
     }
+    let parent = dest
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?;
+    let staging_dir = parent.join(format!(".skills-hub-install-{}", Uuid::new_v4()));
+    if let Err(err) = copy_dir_recursive(src, &staging_dir)
+        .with_context(|| format!("copy {:?} -> {:?}", src, staging_dir))
+    {
+        let _ = std::fs::remove_dir_all(&staging_dir);
+        return Err(err);
+    }
+    let replaced = parent.join(format!(".skills-hub-replaced-{}", Uuid::new_v4()));
+    swap_in_staged(dest, &staging_dir, &replaced)?;
+    if let Err(err) = remove_path_any(&replaced) {
+        log::warn!("[installer] failed to remove {:?}: {:#}", replaced, err);
+    }
+    Ok(())
+}
 
-    let name = name.unwrap_or_else(|| {
-        source_path
-            .file_name()
-            .map(|v| v.to_string_lossy().to_string())
-            .unwrap_or_else(|| "unnamed-skill".to_string())
-    });
-
-    let central_dir = resolve_central_repo_path(app, store)?;
-    ensure_central_repo(&central_dir)?;
-    let central_path = central_dir.join(&name);
+/// A replace holds the replaced skill's lock so no update or edit runs under it.
+fn lock_replaced(replaces: Option<&SkillRecord>) -> Result<Option<SkillLockGuard<'static>>> {
+    replaces
+        .map(|record| lock_skill(&record.id, "install"))
+        .transpose()
+}
 
-    if central_path.exists() {
-        anyhow::bail!("skill already exists in central repo: {:?}", central_path);
+/// Manage a directory that is already in the central repo. A skill already recorded there is
+/// returned unchanged, which makes adopting idempotent.
+fn adopt_existing(
+    store: &SkillStore,
+    name: String,
+    path: &Path,
+    existing: Option<SkillRecord>,
+    source: SkillSource,
+) -> Result<InstallResult> {
+    if let Some(record) = existing {
+        return Ok(InstallResult {
+            skill_id: record.id,
+            name: record.name,
+            central_path: path.to_path_buf(),
+            content_hash: record.content_hash,
+        });
     }
+    log::info!("[installer] adopting orphan directory {:?}", path);
+    register_installed(store, name, path, source, None)
+}
 
-    copy_dir_recursive(source_path, &central_path)
-        .with_context(|| format!("copy {:?} -> {:?}", source_path, central_path))?;
-
-    let now = now_ms();
-    let content_hash = compute_content_hash(&central_path);
+/// Manage a directory that arrived in the central repo from elsewhere, such as a git pull.
+pub(crate) fn adopt_central_dir(
+    store: &SkillStore,
+    name: String,
+    path: &Path,
+) -> Result<InstallResult> {
+    adopt_existing(store, name, path, None, ("local".to_string(), None, None))
+}
 
-    let metadata = SkillMetadata::load(&central_path);
+/// Manage an orphan listed by `scan_central_repo` under its directory name once its SKILL.md
+/// checks out. `repo_id` picks the repo when more than one holds a directory of that name.
+pub fn adopt_orphan<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    name: &str,
+    repo_id: Option<&str>,
+) -> Result<InstallResult> {
+    let found: Vec<OrphanDir> = scan_central_repo(app, store)?
+        .into_iter()
+        .filter(|o| o.name == name && (repo_id.is_none() || repo_id == Some(&*o.repo_id)))
+        .collect();
+    let orphan = match found.as_slice() {
+        [orphan] => orphan,
+        [] => anyhow::bail!("no unmanaged directory named {:?}", name),
+        _ => anyhow::bail!("several repos hold a directory named {:?}; pick one", name),
+    };
+    let path = PathBuf::from(&orphan.path);
+    ensure_valid_skill_md(&path)?;
+    adopt_central_dir(store, orphan.name.clone(), &path)
+}
 
-    // Smart detection: check if source has .git directory with valid origin
-    let (source_type, source_ref, source_revision) = detect_git_origin(source_path);
+/// Record content already placed at `central_path`. Reinstalling over a managed skill keeps
+/// its id, so synced targets stay attached.
+fn register_installed(
+    store: &SkillStore,
+    name: String,
+    central_path: &Path,
+    source: SkillSource,
+    replaces: Option<&SkillRecord>,
+) -> Result<InstallResult> {
+    let now = now_ms();
+    let content_hash = compute_content_hash(central_path);
+    let metadata = SkillMetadata::load(central_path);
+    let license = detect_license(central_path, metadata.as_ref());
+    let token_estimate = estimate_token_total(central_path);
+    let contains_executables = contains_executables(central_path);
+    let (source_type, source_ref, source_revision) = source;
 
     let record = SkillRecord {
-        id: Uuid::new_v4().to_string(),
+        id: replaces
+            .map(|r| r.id.clone())
+            .unwrap_or_else(|| Uuid::new_v4().to_string()),
         name,
         source_type,
         source_ref,
         source_revision,
         central_path: central_path.to_string_lossy().to_string(),
         content_hash: content_hash.clone(),
-        created_at: now,
+        created_at: replaces.map(|r| r.created_at).unwrap_or(now),
         updated_at: now,
-        last_sync_at: None,
+        last_sync_at: replaces.and_then(|r| r.last_sync_at),
         last_seen_at: now,
         status: "ok".to_string(),
         metadata,
+        license,
+        token_estimate,
+        contains_executables,
+        repo_id: repo_id_for_path(store, central_path)?,
     };
-
     store.upsert_skill(&record)?;
+    queue_missing_hash(store, &record);
+    if replaces.is_some() {
+        // Copies in tool directories still hold the replaced content.
+        resync_copy_targets(store, &record.id, central_path, now)?;
+    }
+    let verb = if replaces.is_some() {
+        "Reinstall"
+    } else {
+        "Install"
+    };
+    let mut message = format!("{} {}\n", verb, record.name);
+    if let Some(source_ref) = &record.source_ref {
+        message.push_str(&format!("\nSource: {}", source_ref));
+    }
+    if let Some(revision) = &record.source_revision {
+        message.push_str(&format!("\nRevision: {}", revision));
+    }
+    record_change(store, central_path, &message);
+    notify_webhooks(store, WebhookEvent::installed(&record));
 
     Ok(InstallResult {
         skill_id: record.id,
         name: record.name,
-        central_path,
+        central_path: central_path.to_path_buf(),
         content_hash,
     })
 }
 
-pub fn install_git_skill<R: tauri::Runtime>(
+/// Commit the Central Repo holding `central_path`, when the library is versioned with git.
+pub(crate) fn record_change(store: &SkillStore, central_path: &Path, message: &str) {
+    if let Some(central_dir) = central_path.parent() {
+        record_central_change(store, central_dir, message);
+    }
+}
+
+/// ` to <short revision>` for commit subjects, or nothing for unversioned sources.
+fn revision_suffix(revision: Option<&str>) -> String {
+    revision
+        .map(|rev| format!(" to {}", rev.chars().take(12).collect::<String>()))
+        .unwrap_or_default()
+}
+
+/// Detect whether a directory lives inside a git checkout and extract an updatable source.
+/// If the path is a symlink, resolves it to the real path first. Skills nested inside a repo
+/// are recorded as GitHub folder URLs so updates only pull that subdirectory.
+/// Returns (source_type, source_ref, source_revision) tuple.
+fn detect_git_origin(source_path: &Path) -> (String, Option<String>, Option<String>) {
+    let local = || {
+        (
+            "local".to_string(),
+            Some(source_path.to_string_lossy().to_string()),
+            None,
+        )
+    };
+
+    // Resolve symlinks to get the real path
+    let resolved_path =
+        std::fs::canonicalize(source_path).unwrap_or_else(|_| source_path.to_path_buf());
+
+    // Never climb into the home directory itself: a dotfiles repo there is not the skill's origin.
+    let ceilings: Vec<PathBuf> = dirs::home_dir().into_iter().collect();
+    let repo = match git2::Repository::open_ext(
+        &resolved_path,
+        git2::RepositoryOpenFlags::empty(),
+        &ceilings,
+    ) {
+        Ok(repo) => repo,
+        Err(_) => return local(),
+    };
+    let Some(workdir) = repo.workdir() else {
+        return local();
+    };
+    let workdir = std::fs::canonicalize(workdir).unwrap_or_else(|_| workdir.to_path_buf());
+    let subpath = match resolved_path.strip_prefix(&workdir) {
+        Ok(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => return local(),
+    };
+
+    let Some(origin_url) = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|remote| remote.url().map(|s| s.to_string()))
+    else {
+        // Git repo but no origin remote, treat as local
+        return local();
+    };
+
+    let revision = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .map(|commit| commit.id().to_string());
+
+    if subpath.is_empty() {
+        return (
+            "git".to_string(),
+            Some(normalize_git_remote(&origin_url)),
+            revision,
+        );
+    }
+
+    // Nested skill: only GitHub folder URLs can express a subpath, and they need a branch name.
+    let branch = current_branch_name(&repo);
+    match (github_repo_url(&origin_url), branch) {
+        (Some(repo_url), Some(branch)) => (
+            "git".to_string(),
+            Some(format!("{}/tree/{}/{}", repo_url, branch, subpath)),
+            revision,
+        ),
+        _ => local(),
+    }
+}
+
+/// `https://github.com/<owner>/<repo>` for GitHub remotes in https or ssh form.
+fn github_repo_url(remote: &str) -> Option<String> {
+    let trimmed = remote.trim().trim_end_matches('/');
+    let rest = trimmed
+        .strip_prefix("git@github.com:")
+        .or_else(|| trimmed.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| trimmed.strip_prefix("https://github.com/"))
+        .or_else(|| trimmed.strip_prefix("http://github.com/"))?;
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+    let mut parts = rest.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    Some(format!("https://github.com/{}/{}", owner, repo))
+}
+
+fn normalize_git_remote(remote: &str) -> String {
+    github_repo_url(remote).unwrap_or_else(|| remote.to_string())
+}
+
+fn current_branch_name(repo: &git2::Repository) -> Option<String> {
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            return head.shorthand().map(|s| s.to_string());
+        }
+    }
+    // Detached HEAD: fall back to the remote default branch.
+    repo.find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|r| r.symbolic_target().map(|s| s.to_string()))
+        .and_then(|t| {
+            t.strip_prefix("refs/remotes/origin/")
+                .map(|s| s.to_string())
+        })
+}
+
+pub fn install_local_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: Option<String>,
+) -> Result<InstallResult> {
+    install_local_skill_with_force(app, store, source_path, name, false)
+}
+
+pub fn install_local_skill_with_force<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: Option<String>,
+    force: bool,
+) -> Result<InstallResult> {
+    install_local_skill_with_options(app, store, source_path, name, InstallOptions::force(force))
+}
+
+pub fn install_local_skill_with_options<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: Option<String>,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    if !source_path.exists() {
+        anyhow::bail!("source path not found: {:?}", source_path);
+    }
+
+    let name = name.unwrap_or_else(|| {
+        source_path
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed-skill".to_string())
+    });
+
+    // Smart detection: check if source has .git directory with valid origin
+    let source = detect_git_origin(source_path);
+    install_from_dir(app, store, source_path, name, source, options)
+}
+
+/// Install a skill directory produced by a converter (e.g. from a Cursor rule), recording
+/// `source_type`/`source_ref` so updates convert the original again.
+pub(crate) fn install_converted_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    staged_dir: &Path,
+    name: String,
+    source: SkillSource,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    install_from_dir(app, store, staged_dir, name, source, options)
+}
+
+/// Copy `source_path` into the central repo as `name` through the install gates.
+fn install_from_dir<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: String,
+    source: SkillSource,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    let central_dir = resolve_install_repo_path(app, store)?;
+    ensure_central_repo(&central_dir)?;
+    let (name, central_path, replaces) =
+        match plan_install_target(store, &central_dir, name, options.on_conflict)? {
+            InstallTarget::Adopt {
+                name,
+                path,
+                existing,
+            } => return adopt_existing(store, name, &path, existing, source),
+            InstallTarget::Copy {
+                name,
+                path,
+                replaces,
+            } => (name, path, replaces),
+        };
+    let _lock = lock_replaced(replaces.as_ref())?;
+    let replaces_id = replaces.as_ref().map(|r| r.id.as_str());
+    ensure_signature_accepted(store, &name, source_path)?;
+    ensure_lint_passes(source_path, options.force)?;
+    ensure_constraints_hold(store, &name, None, source_path, replaces_id, options.force)?;
+    ensure_no_secrets(source_path, options.force)?;
+
+    copy_into_central(source_path, &central_path)?;
+    normalize_if_enabled(store, &central_path);
+
+    register_installed(store, name, &central_path, source, replaces.as_ref())
+}
+
+pub fn install_git_skill_with_force<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    repo_url: &str,
+    name: Option<String>,
+    force: bool,
+) -> Result<InstallResult> {
+    install_git_skill_with_options(app, store, repo_url, name, InstallOptions::force(force))
+}
+
+pub fn install_git_skill_with_options<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     store: &SkillStore,
     repo_url: &str,
     name: Option<String>,
+    options: InstallOptions,
 ) -> Result<InstallResult> {
     let parsed = parse_github_url(repo_url);
     let name = name.unwrap_or_else(|| {
@@ -160,13 +578,25 @@ pub fn install_git_skill<R: tauri::Runtime>(
         }
     });
 
-    let central_dir = resolve_central_repo_path(app, store)?;
+    let central_dir = resolve_install_repo_path(app, store)?;
     ensure_central_repo(&central_dir)?;
-    let central_path = central_dir.join(&name);
-
-    if central_path.exists() {
-        anyhow::bail!("skill already exists in central repo: {:?}", central_path);
-    }
+    let (name, central_path, replaces) =
+        match plan_install_target(store, &central_dir, name, options.on_conflict)? {
+            InstallTarget::Adopt {
+                name,
+                path,
+                existing,
+            } => {
+                // The revision is unknown without cloning; the next update fills it in.
+                let source = ("git".to_string(), Some(repo_url.to_string()), None);
+                return adopt_existing(store, name, &path, existing, source);
+            }
+            InstallTarget::Copy {
+                name,
+                path,
+                replaces,
+            } => (name, path, replaces),
+        };
 
     // Always clone into a temp dir first, then copy the skill directory into central repo.
     // This avoids storing a full git repo (with .git) inside central repo and allows
@@ -174,7 +604,7 @@ pub fn install_git_skill<R: tauri::Runtime>(
     let (repo_dir, rev) = clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
 
     let copy_src = if let Some(subpath) = &parsed.subpath {
-        let sub_src = repo_dir.join(subpath);
+        let sub_src = resolve_subpath(&repo_dir, subpath)?;
         if !sub_src.exists() {
             anyhow::bail!("subpath not found in repo: {:?}", sub_src);
         }
@@ -193,62 +623,46 @@ pub fn install_git_skill<R: tauri::Runtime>(
                 }
             }
             if count >= 2 {
-                anyhow::bail!(
-          "MULTI_SKILLS|该仓库包含多个 Skills，请复制具体 Skill 文件夹链接（例如 GitHub 的 /tree/<branch>/skills/<name>），再导入。"
-        );
+                anyhow::bail!("MULTI_SKILLS|{}|{}", repo_url, count);
             }
         }
         repo_dir.clone()
     };
-
-    copy_dir_recursive(&copy_src, &central_path)
-        .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
-
-    let revision = rev;
-    let now = now_ms();
-    let content_hash = compute_content_hash(&central_path);
-
-    let metadata = SkillMetadata::load(&central_path);
-
-    let record = SkillRecord {
-        id: Uuid::new_v4().to_string(),
-        name,
-        source_type: "git".to_string(),
-        source_ref: Some(repo_url.to_string()),
-        source_revision: Some(revision),
-        central_path: central_path.to_string_lossy().to_string(),
-        content_hash: content_hash.clone(),
-        created_at: now,
-        updated_at: now,
-        last_sync_at: None,
-        last_seen_at: now,
-        status: "ok".to_string(),
-        metadata,
-    };
-
-    store.upsert_skill(&record)?;
-
-    Ok(InstallResult {
-        skill_id: record.id,
-        name: record.name,
-        central_path,
-        content_hash,
-    })
+    let _lock = lock_replaced(replaces.as_ref())?;
+    let replaces_id = replaces.as_ref().map(|r| r.id.as_str());
+    ensure_signature_accepted(store, &name, &copy_src)?;
+    ensure_lint_passes(&copy_src, options.force)?;
+    ensure_constraints_hold(
+        store,
+        &name,
+        Some(repo_url),
+        &copy_src,
+        replaces_id,
+        options.force,
+    )?;
+    ensure_no_secrets(&copy_src, options.force)?;
+
+    copy_into_central(&copy_src, &central_path)?;
+    normalize_if_enabled(store, &central_path);
+
+    let source = ("git".to_string(), Some(repo_url.to_string()), Some(rev));
+    register_installed(store, name, &central_path, source, replaces.as_ref())
 }
 
 #[derive(Clone, Debug)]
-struct ParsedGitSource {
-    clone_url: String,
-    branch: Option<String>,
-    subpath: Option<String>,
+pub(crate) struct ParsedGitSource {
+    pub(crate) clone_url: String,
+    pub(crate) branch: Option<String>,
+    pub(crate) subpath: Option<String>,
 }
 
-fn parse_github_url(input: &str) -> ParsedGitSource {
+pub(crate) fn parse_github_url(input: &str) -> ParsedGitSource {
     // Supports:
     // - https://github.com/owner/repo
     // - https://github.com/owner/repo.git
     // - https://github.com/owner/repo/tree/<branch>/<path>
     // - https://github.com/owner/repo/blob/<branch>/<path>
+    // - <any other remote>#<branch>
     let trimmed = input.trim().trim_end_matches('/');
 
     // Convenience: allow GitHub shorthand inputs like `owner/repo` (and `owner/repo/tree/<branch>/...`).
@@ -268,9 +682,14 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     let trimmed = normalized.trim_end_matches('/');
     let gh_prefix = "https://github.com/";
     if !trimmed.starts_with(gh_prefix) {
+        // Other remotes have no branch in their URL; it follows a `#`, as in npm git URLs.
+        let (clone_url, branch) = match trimmed.rsplit_once('#') {
+            Some((url, branch)) if !branch.is_empty() => (url, Some(branch.to_string())),
+            _ => (trimmed, None),
+        };
         return ParsedGitSource {
-            clone_url: trimmed.to_string(),
-            branch: None,
+            clone_url: clone_url.to_string(),
+            branch,
             subpath: None,
         };
     }
@@ -293,7 +712,8 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     let clone_url = format!("https://github.com/{}/{}.git", owner, repo);
 
     if parts.len() >= 4 && (parts[2] == "tree" || parts[2] == "blob") {
-        let branch = Some(parts[3].to_string());
+        // Branches containing `/` are written with `%2F` (see `git_url_with_branch`).
+        let branch = Some(parts[3].replace("%2F", "/").replace("%2f", "/"));
         let subpath = if parts.len() > 4 {
             Some(parts[4..].join("/"))
         } else {
@@ -313,6 +733,41 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     }
 }
 
+/// `repo_url` pinned to `branch`, so the branch is kept in the skill's source and followed by
+/// updates: a GitHub tree URL, its folder kept, or `<url>#<branch>` for any other remote. A
+/// branch already in the URL is replaced. `parse_github_url` hands it back separately, for
+/// `clone_to_cache`.
+pub fn git_url_with_branch(repo_url: &str, branch: &str) -> String {
+    let branch = branch.trim();
+    if branch.is_empty() {
+        return repo_url.to_string();
+    }
+    let parsed = parse_github_url(repo_url);
+    let Some(base) = github_repo_url(&parsed.clone_url) else {
+        return format!("{}#{}", parsed.clone_url, branch);
+    };
+    let mut url = format!("{}/tree/{}", base, branch.replace('/', "%2F"));
+    if let Some(subpath) = &parsed.subpath {
+        url.push('/');
+        url.push_str(subpath);
+    }
+    url
+}
+
+/// Branches of the repository behind `repo_url` (any git remote), for picking one to install.
+pub fn list_git_branches(store: &SkillStore, repo_url: &str) -> Result<Vec<RemoteBranch>> {
+    let parsed = parse_github_url(repo_url);
+    ensure_git_host_allowed(store, &parsed.clone_url)?;
+    list_remote_branches(&parsed.clone_url)
+}
+
+/// Tags of the repository behind `repo_url`, newest first, for installing a released version.
+pub fn list_git_tags(store: &SkillStore, repo_url: &str) -> Result<Vec<RemoteTag>> {
+    let parsed = parse_github_url(repo_url);
+    ensure_git_host_allowed(store, &parsed.clone_url)?;
+    list_remote_tags(&parsed.clone_url)
+}
+
 fn looks_like_github_shorthand(input: &str) -> bool {
     if input.is_empty() {
         return false;
@@ -381,6 +836,10 @@ fn derive_name_from_repo_url(repo_url: &str) -> String {
     }
 }
 
+fn estimate_token_total(path: &Path) -> Option<i64> {
+    estimate_skill_tokens(path).ok().map(|e| e.total as i64)
+}
+
 fn compute_content_hash(path: &Path) -> Option<String> {
     if should_compute_content_hash() {
         hash_dir(path).ok()
@@ -389,6 +848,14 @@ fn compute_content_hash(path: &Path) -> Option<String> {
     }
 }
 
+/// Release builds skip hashing inline to keep installs fast; the background worker fills the
+/// hash in afterwards.
+fn queue_missing_hash(store: &SkillStore, record: &SkillRecord) {
+    if record.content_hash.is_none() {
+        queue_content_hash(store, &record.id, Path::new(&record.central_path));
+    }
+}
+
 fn should_compute_content_hash() -> bool {
     if cfg!(debug_assertions) {
         return true;
@@ -409,11 +876,13 @@ pub struct UpdateResult {
     pub updated_targets: Vec<String>,
 }
 
-pub fn update_managed_skill_from_source<R: tauri::Runtime>(
+pub fn update_managed_skill_from_source_with_force<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     store: &SkillStore,
     skill_id: &str,
+    force: bool,
 ) -> Result<UpdateResult> {
+    let _lock = lock_skill(skill_id, "update")?;
     let record = store
         .get_skill_by_id(skill_id)?
         .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
@@ -449,7 +918,7 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
         new_revision = Some(rev);
 
         let copy_src = if let Some(subpath) = &parsed.subpath {
-            repo_dir.join(subpath)
+            resolve_subpath(&repo_dir, subpath)?
         } else {
             repo_dir.clone()
         };
@@ -470,26 +939,87 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
         }
         copy_dir_recursive(&source_path, &staging_dir)
             .with_context(|| format!("copy {:?} -> {:?}", source_path, staging_dir))?;
+    } else if record.source_type == CURSOR_RULE_SOURCE {
+        let rule_file = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for cursor rule skill"))?;
+        write_rule_skill(Path::new(rule_file), &record.name, &staging_dir)?;
+    } else if record.source_type == URL_SOURCE {
+        let url = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for url skill"))?;
+        match fetch_url(store, url, record.source_revision.as_deref())? {
+            UrlFetch::NotModified => {
+                return Ok(UpdateResult {
+                    skill_id: record.id,
+                    name: record.name,
+                    central_path,
+                    content_hash: record.content_hash,
+                    source_revision: record.source_revision,
+                    updated_targets: Vec::new(),
+                });
+            }
+            UrlFetch::Fetched { text, revision } => {
+                write_url_skill(&text, &record.name, url, &staging_dir)?;
+                new_revision = revision;
+            }
+        }
     } else {
         anyhow::bail!("unsupported source_type for update: {}", record.source_type);
     }
 
-    // Swap: remove old dir and rename staging into place (best effort).
-    std::fs::remove_dir_all(&central_path)
-        .with_context(|| format!("failed to remove old central dir {:?}", central_path))?;
-    if let Err(err) = std::fs::rename(&staging_dir, &central_path) {
-        // Fallback for cross-device rename: copy then delete staging.
-        copy_dir_recursive(&staging_dir, &central_path)
-            .with_context(|| format!("fallback copy {:?} -> {:?}", staging_dir, central_path))?;
+    if let Err(err) = ensure_signature_accepted(store, &record.name, &staging_dir)
+        .and_then(|()| {
+            ensure_constraints_hold(
+                store,
+                &record.name,
+                record.source_ref.as_deref(),
+                &staging_dir,
+                Some(&record.id),
+                force,
+            )
+        })
+        .and_then(|()| ensure_no_secrets(&staging_dir, force))
+    {
         let _ = std::fs::remove_dir_all(&staging_dir);
-        // Still surface original rename error in logs for troubleshooting.
-        eprintln!("[update] rename warning: {}", err);
+        return Err(err);
+    }
+
+    // Swap: move the old dir aside as a rollback snapshot, then rename staging into place.
+    let snapshot_dir = rollback_snapshot_dir(&central_path, &record.id)?;
+    if snapshot_dir.exists() {
+        std::fs::remove_dir_all(&snapshot_dir)
+            .with_context(|| format!("failed to remove old snapshot {:?}", snapshot_dir))?;
     }
+    std::fs::create_dir_all(&snapshot_dir)
+        .with_context(|| format!("failed to create snapshot dir {:?}", snapshot_dir))?;
+    swap_in_staged(
+        &central_path,
+        &staging_dir,
+        &snapshot_dir.join(ROLLBACK_CONTENT_DIR),
+    )?;
+
+    let snapshot = RollbackSnapshot {
+        source_revision: record.source_revision.clone(),
+        content_hash: record.content_hash.clone(),
+        updated_at: record.updated_at,
+        snapshot_at: now,
+    };
+    std::fs::write(
+        snapshot_dir.join(ROLLBACK_META_FILE),
+        serde_json::to_string(&snapshot)?,
+    )
+    .with_context(|| format!("failed to write snapshot meta in {:?}", snapshot_dir))?;
 
     let content_hash = compute_content_hash(&central_path);
 
     // Update DB skill row.
     let metadata = SkillMetadata::load(&central_path);
+    let license = detect_license(&central_path, metadata.as_ref());
+    let token_estimate = estimate_token_total(&central_path);
+    let contains_executables = contains_executables(&central_path);
 
     let updated = SkillRecord {
         id: record.id.clone(),
@@ -505,38 +1035,26 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
         last_seen_at: now,
         status: "ok".to_string(),
         metadata,
+        license,
+        token_estimate,
+        contains_executables,
+        repo_id: record.repo_id.clone(),
     };
     store.upsert_skill(&updated)?;
+    queue_missing_hash(store, &updated);
 
     // If any targets are "copy", re-sync them so changes propagate. Symlinks update automatically.
-    // Cursor 目前不支持软链/junction，因此无论历史 mode 如何，都需要强制 copy 回灌。
-    let targets = store.list_skill_targets(skill_id)?;
-    let mut updated_targets: Vec<String> = Vec::new();
-    for t in targets {
-        // Skip if tool not installed anymore.
-        if let Some(adapter) = adapter_by_key(&t.tool) {
-            if !is_tool_installed(&adapter).unwrap_or(false) {
-                continue;
-            }
-        }
-        let force_copy = t.mode == "copy" || t.tool == "cursor";
-        if force_copy {
-            let target_path = PathBuf::from(&t.target_path);
-            let sync_res = sync_dir_copy_with_overwrite(&central_path, &target_path, true)?;
-            let record = super::skill_store::SkillTargetRecord {
-                id: t.id.clone(),
-                skill_id: t.skill_id.clone(),
-                tool: t.tool.clone(),
-                target_path: sync_res.target_path.to_string_lossy().to_string(),
-                mode: "copy".to_string(),
-                status: "ok".to_string(),
-                last_error: None,
-                synced_at: Some(now),
-            };
-            store.upsert_skill_target(&record)?;
-            updated_targets.push(t.tool.clone());
-        }
-    }
+    let updated_targets = resync_copy_targets(store, skill_id, &central_path, now)?;
+    record_change(
+        store,
+        &central_path,
+        &format!(
+            "Update {}{}",
+            record.name,
+            revision_suffix(updated.source_revision.as_deref())
+        ),
+    );
+    notify_webhooks(store, WebhookEvent::updated(&updated));
 
     Ok(UpdateResult {
         skill_id: record.id,
@@ -548,20 +1066,493 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
     })
 }
 
-#[derive(Clone, Debug, serde::Serialize)]
-pub struct GitSkillCandidate {
-    pub name: String,
-    pub description: Option<String>,
-    pub subpath: String,
+const CHANGELOG_LIMIT: usize = 200;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillChangelog {
+    pub skill_id: String,
+    pub from_revision: Option<String>,
+    pub to_revision: String,
+    pub commits: Vec<CommitSummary>,
+    pub truncated: bool,
+    /// Deprecation as declared by the upstream head, so it shows up before updating.
+    pub deprecated: bool,
+    pub successor: Option<String>,
 }
 
-#[derive(Clone, Debug, serde::Serialize)]
-pub struct LocalSkillCandidate {
+/// Whether the skill's source has content the central copy lacks. Git sources are fetched
+/// into the cache; a moved branch only counts when the skill's folder changed. Local sources
+/// are hashed in place. Other sources are not checked and report `false`.
+pub(crate) fn source_has_update<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    record: &SkillRecord,
+) -> Result<bool> {
+    let source = match (record.source_type.as_str(), record.source_ref.as_deref()) {
+        ("git", Some(repo_url)) => {
+            let parsed = parse_github_url(repo_url);
+            let (repo_dir, rev) =
+                clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+            if record.source_revision.as_deref() == Some(rev.as_str()) {
+                return Ok(false);
+            }
+            match &parsed.subpath {
+                Some(subpath) => resolve_subpath(&repo_dir, subpath)?,
+                None => repo_dir,
+            }
+        }
+        ("local", Some(source)) => PathBuf::from(source),
+        _ => return Ok(false),
+    };
+    if !source.is_dir() {
+        anyhow::bail!("source path not found: {:?}", source);
+    }
+    Ok(Some(hash_dir(&source)?) != current_hash(store, record)?)
+}
+
+/// Commits between the installed revision and the remote head, scoped to the skill subpath.
+pub fn get_skill_changelog<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<SkillChangelog> {
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    if record.source_type != "git" {
+        anyhow::bail!("changelog is only available for git skills");
+    }
+    let repo_url = record
+        .source_ref
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("missing source_ref for git skill"))?;
+    let parsed = parse_github_url(repo_url);
+
+    let (repo_dir, head) = clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+
+    let commits = match record.source_revision.as_deref() {
+        Some(from) if from == head => Vec::new(),
+        Some(from) => log_between(
+            &repo_dir,
+            from,
+            &head,
+            parsed.subpath.as_deref(),
+            CHANGELOG_LIMIT,
+        )?,
+        None => Vec::new(),
+    };
+
+    let upstream = match &parsed.subpath {
+        Some(subpath) => SkillMetadata::load(resolve_subpath(&repo_dir, subpath)?),
+        None => SkillMetadata::load(&repo_dir),
+    };
+
+    Ok(SkillChangelog {
+        skill_id: record.id,
+        from_revision: record.source_revision,
+        to_revision: head,
+        truncated: commits.len() >= CHANGELOG_LIMIT,
+        commits,
+        deprecated: upstream.as_ref().is_some_and(|m| m.deprecated),
+        successor: upstream.and_then(|m| m.successor),
+    })
+}
+
+pub(crate) const ROLLBACK_DIR_NAME: &str = ".skills-hub-rollback";
+const ROLLBACK_CONTENT_DIR: &str = "content";
+const ROLLBACK_META_FILE: &str = "snapshot.json";
+
+/// The pre-update state kept next to a rollback snapshot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RollbackSnapshot {
+    source_revision: Option<String>,
+    content_hash: Option<String>,
+    updated_at: i64,
+    snapshot_at: i64,
+}
+
+/// Snapshots live in a hidden dir of the central repo so a rename is enough to swap them.
+fn rollback_snapshot_dir(central_path: &Path, skill_id: &str) -> Result<PathBuf> {
+    let central_parent = central_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?;
+    Ok(central_parent.join(ROLLBACK_DIR_NAME).join(skill_id))
+}
+
+/// Move the current content into the snapshot, then `staging_dir` into its place. If the second
+/// move fails the old content goes back, so the skill is never left without a directory.
+fn swap_in_staged(central_path: &Path, staging_dir: &Path, snapshot_content: &Path) -> Result<()> {
+    move_dir(central_path, snapshot_content)
+        .with_context(|| format!("failed to snapshot old central dir {:?}", central_path))?;
+    if let Err(err) = move_dir(staging_dir, central_path)
+        .with_context(|| format!("failed to move update into place {:?}", central_path))
+    {
+        // A copy fallback may have left part of the new content behind.
+        if central_path.exists() {
+            let _ = remove_path_any(central_path);
+        }
+        if let Err(restore_err) = move_dir(snapshot_content, central_path) {
+            log::error!(
+                "[installer] failed to restore {:?} from {:?}: {:#}",
+                central_path,
+                snapshot_content,
+                restore_err
+            );
+        }
+        let _ = std::fs::remove_dir_all(staging_dir);
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Drop the rollback snapshot of a skill that is being removed.
+pub fn remove_rollback_snapshot(central_path: &Path, skill_id: &str) -> Result<()> {
+    let snapshot_dir = rollback_snapshot_dir(central_path, skill_id)?;
+    if snapshot_dir.exists() {
+        std::fs::remove_dir_all(&snapshot_dir)
+            .with_context(|| format!("failed to remove snapshot {:?}", snapshot_dir))?;
+    }
+    Ok(())
+}
+
+pub fn has_rollback_snapshot(central_path: &Path, skill_id: &str) -> bool {
+    rollback_snapshot_dir(central_path, skill_id)
+        .map(|dir| dir.join(ROLLBACK_META_FILE).exists())
+        .unwrap_or(false)
+}
+
+/// Restore the content that was replaced by the last update and re-sync copy targets.
+pub fn rollback_update(store: &SkillStore, skill_id: &str) -> Result<UpdateResult> {
+    let _lock = lock_skill(skill_id, "rollback")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+
+    let central_path = PathBuf::from(record.central_path.clone());
+    let snapshot_dir = rollback_snapshot_dir(&central_path, &record.id)?;
+    let meta_path = snapshot_dir.join(ROLLBACK_META_FILE);
+    let snapshot_content = snapshot_dir.join(ROLLBACK_CONTENT_DIR);
+    if !meta_path.exists() || !snapshot_content.exists() {
+        anyhow::bail!("no previous revision to roll back to");
+    }
+    let raw = std::fs::read_to_string(&meta_path)
+        .with_context(|| format!("failed to read snapshot meta {:?}", meta_path))?;
+    let snapshot: RollbackSnapshot =
+        serde_json::from_str(&raw).context("failed to parse snapshot meta")?;
+
+    let now = now_ms();
+
+    // Move the current content aside first so a failed restore leaves it recoverable.
+    let discarded = snapshot_dir.join(format!("discarded-{}", Uuid::new_v4()));
+    if central_path.exists() {
+        move_dir(&central_path, &discarded)
+            .with_context(|| format!("failed to move current dir {:?}", central_path))?;
+    }
+    move_dir(&snapshot_content, &central_path)
+        .with_context(|| format!("failed to restore snapshot into {:?}", central_path))?;
+    let _ = std::fs::remove_dir_all(&snapshot_dir);
+
+    let content_hash = compute_content_hash(&central_path).or(snapshot.content_hash);
+    let metadata = SkillMetadata::load(&central_path);
+    let license = detect_license(&central_path, metadata.as_ref());
+    let token_estimate = estimate_token_total(&central_path);
+    let contains_executables = contains_executables(&central_path);
+
+    let restored = SkillRecord {
+        source_revision: snapshot.source_revision.clone(),
+        content_hash: content_hash.clone(),
+        updated_at: now,
+        last_seen_at: now,
+        status: "ok".to_string(),
+        metadata,
+        license,
+        token_estimate,
+        contains_executables,
+        ..record.clone()
+    };
+    store.upsert_skill(&restored)?;
+    queue_missing_hash(store, &restored);
+
+    let updated_targets = resync_copy_targets(store, skill_id, &central_path, now)?;
+    record_change(
+        store,
+        &central_path,
+        &format!(
+            "Roll back {}{}",
+            record.name,
+            revision_suffix(snapshot.source_revision.as_deref())
+        ),
+    );
+
+    Ok(UpdateResult {
+        skill_id: record.id,
+        name: record.name,
+        central_path,
+        content_hash,
+        source_revision: snapshot.source_revision,
+        updated_targets,
+    })
+}
+
+/// Apply a metadata edit to the central copy, then refresh the record and copy targets.
+pub fn update_skill_metadata(
+    store: &SkillStore,
+    skill_id: &str,
+    patch: &SkillMetadataPatch,
+) -> Result<UpdateResult> {
+    let _lock = lock_skill(skill_id, "edit_metadata")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let central_path = PathBuf::from(record.central_path.clone());
+    if !central_path.exists() {
+        anyhow::bail!("central path not found: {:?}", central_path);
+    }
+
+    write_metadata_patch(&central_path, patch)?;
+    refresh_after_edit(store, record, central_path)
+}
+
+/// Replace (or create) one file inside a managed skill and propagate it to copy-mode targets.
+pub fn write_skill_content(
+    store: &SkillStore,
+    skill_id: &str,
+    relative_path: &str,
+    content: &str,
+) -> Result<UpdateResult> {
+    let _lock = lock_skill(skill_id, "write_content")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let central_path = PathBuf::from(record.central_path.clone());
+    if !central_path.exists() {
+        anyhow::bail!("central path not found: {:?}", central_path);
+    }
+
+    let path = resolve_in_skill(&central_path, relative_path)?;
+    if path.is_dir() {
+        anyhow::bail!("INVALID_PATH|{}", relative_path);
+    }
+    write_file_atomic(&path, content.as_bytes())?;
+    refresh_after_edit(store, record, central_path)
+}
+
+/// Write a skill.yaml derived from SKILL.md for a managed skill that lacks one.
+pub fn normalize_skill(store: &SkillStore, skill_id: &str) -> Result<UpdateResult> {
+    let _lock = lock_skill(skill_id, "normalize")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let central_path = PathBuf::from(record.central_path.clone());
+    if !central_path.exists() {
+        anyhow::bail!("central path not found: {:?}", central_path);
+    }
+
+    generate_skill_yaml(&central_path)?;
+    refresh_after_edit(store, record, central_path)
+}
+
+/// Re-read hash/metadata after an in-place edit of the central copy and re-sync copy targets.
+pub(crate) fn refresh_after_edit(
+    store: &SkillStore,
+    record: SkillRecord,
+    central_path: PathBuf,
+) -> Result<UpdateResult> {
+    let now = now_ms();
+    let content_hash = compute_content_hash(&central_path);
+    let metadata = SkillMetadata::load(&central_path);
+    let license = detect_license(&central_path, metadata.as_ref());
+    let token_estimate = estimate_token_total(&central_path);
+    let contains_executables = contains_executables(&central_path);
+
+    let edited = SkillRecord {
+        content_hash: content_hash.clone(),
+        updated_at: now,
+        metadata,
+        license,
+        token_estimate,
+        contains_executables,
+        ..record.clone()
+    };
+    store.upsert_skill(&edited)?;
+    queue_missing_hash(store, &edited);
+
+    let updated_targets = resync_copy_targets(store, &record.id, &central_path, now)?;
+
+    Ok(UpdateResult {
+        skill_id: record.id,
+        name: record.name,
+        central_path,
+        content_hash,
+        source_revision: record.source_revision,
+        updated_targets,
+    })
+}
+
+pub const NORMALIZE_ON_INSTALL_KEY: &str = "normalize_on_install";
+
+pub fn get_normalize_on_install(store: &SkillStore) -> bool {
+    matches!(
+        store
+            .get_setting(NORMALIZE_ON_INSTALL_KEY)
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    )
+}
+
+pub fn set_normalize_on_install(store: &SkillStore, enabled: bool) -> Result<bool> {
+    store.set_setting(
+        NORMALIZE_ON_INSTALL_KEY,
+        if enabled { "true" } else { "false" },
+    )?;
+    Ok(enabled)
+}
+
+/// Optional install step: give SKILL.md-only skills a skill.yaml. Never fails the install.
+fn normalize_if_enabled(store: &SkillStore, central_path: &Path) {
+    if !get_normalize_on_install(store) {
+        return;
+    }
+    if let Err(err) = generate_skill_yaml(central_path) {
+        log::warn!(
+            "[installer] normalize {:?} skipped: {:#}",
+            central_path,
+            err
+        );
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SuccessorMigration {
+    pub previous_skill_id: String,
+    pub skill_id: String,
+    pub name: String,
+    /// Tools the successor was synced to in place of the deprecated skill.
+    pub migrated_targets: Vec<String>,
+    pub target_errors: Vec<String>,
+}
+
+/// Install the successor of a deprecated skill, move its tool targets over and remove the old
+/// skill. The old content is kept aside until the install succeeds, so a failed install (or a
+/// successor with the same directory name) leaves the original untouched.
+pub fn migrate_to_successor<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    skill_id: &str,
+    force: bool,
+) -> Result<SuccessorMigration> {
+    let _lock = lock_skill(skill_id, "migrate")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let successor = record
+        .metadata
+        .as_ref()
+        .and_then(|m| m.successor.clone())
+        .ok_or_else(|| anyhow::anyhow!("skill does not declare a successor"))?;
+
+    let central_path = PathBuf::from(record.central_path.clone());
+    let parent = central_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?;
+    let aside = parent.join(format!(".skills-hub-migrate-{}", Uuid::new_v4()));
+    if central_path.exists() {
+        move_dir(&central_path, &aside)
+            .with_context(|| format!("failed to move {:?} aside", central_path))?;
+    }
+
+    let installed = match install_git_skill_with_force(app, store, &successor, None, force) {
+        Ok(installed) => installed,
+        Err(err) => {
+            if aside.exists() {
+                let _ = move_dir(&aside, &central_path);
+            }
+            return Err(err);
+        }
+    };
+
+    let now = now_ms();
+    let mut migrated_targets = Vec::new();
+    let mut target_errors = Vec::new();
+    for target in store.list_skill_targets(&record.id)? {
+        let old_path = PathBuf::from(&target.target_path);
+        let new_path = old_path
+            .parent()
+            .map(|p| p.join(&installed.name))
+            .unwrap_or_else(|| old_path.clone());
+        let outcome = remove_path_any(&old_path).and_then(|_| {
+            sync_dir_for_tool_with_overwrite(&target.tool, &installed.central_path, &new_path, true)
+        });
+        match outcome {
+            Ok(outcome) => {
+                store.upsert_skill_target(&SkillTargetRecord {
+                    id: Uuid::new_v4().to_string(),
+                    skill_id: installed.skill_id.clone(),
+                    tool: target.tool.clone(),
+                    target_path: outcome.target_path.to_string_lossy().to_string(),
+                    mode: outcome.mode_used.as_key().to_string(),
+                    status: enabled_status(&target.status).to_string(),
+                    last_error: None,
+                    synced_at: Some(now),
+                })?;
+                migrated_targets.push(target.tool);
+            }
+            Err(err) => target_errors.push(format!("{}: {:#}", target.tool, err)),
+        }
+    }
+
+    store.delete_skill(&record.id)?;
+    if aside.exists() {
+        let _ = std::fs::remove_dir_all(&aside);
+    }
+    if let Err(err) = remove_rollback_snapshot(&central_path, &record.id) {
+        log::warn!("[installer] {:#}", err);
+    }
+
+    Ok(SuccessorMigration {
+        previous_skill_id: record.id,
+        skill_id: installed.skill_id,
+        name: installed.name,
+        migrated_targets,
+        target_errors,
+    })
+}
+
+/// Rename a directory, falling back to copy + delete across devices.
+pub(crate) fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if let Err(err) = std::fs::rename(from, to) {
+        copy_dir_recursive(from, to)
+            .with_context(|| format!("fallback copy {:?} -> {:?}", from, to))?;
+        std::fs::remove_dir_all(from).with_context(|| format!("cleanup {:?}", from))?;
+        // Still surface original rename error in logs for troubleshooting.
+        log::warn!(
+            "[installer] rename {:?} -> {:?} fell back to copy: {}",
+            from,
+            to,
+            err
+        );
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct GitSkillCandidate {
+    pub name: String,
+    pub description: Option<String>,
+    pub subpath: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct LocalSkillCandidate {
     pub name: String,
     pub description: Option<String>,
     pub subpath: String,
     pub valid: bool,
     pub reason: Option<String>,
+    /// Frontmatter problems with line numbers; valid candidates may still carry warnings.
+    pub issues: Vec<FrontmatterIssue>,
 }
 
 pub fn list_git_skills<R: tauri::Runtime>(
@@ -576,7 +1567,7 @@ pub fn list_git_skills<R: tauri::Runtime>(
 
     // If user provided a folder URL, treat it as a single candidate.
     if let Some(subpath) = &parsed.subpath {
-        let dir = repo_dir.join(subpath);
+        let dir = resolve_subpath(&repo_dir, subpath)?;
         if dir.is_dir() && dir.join("SKILL.md").exists() {
             let (name, desc) = parse_skill_md(&dir.join("SKILL.md")).unwrap_or((
                 dir.file_name()
@@ -605,47 +1596,26 @@ pub fn list_git_skills<R: tauri::Runtime>(
         });
     }
 
-    // Standard discovery locations (subset aligned with add-skill):
-    // skills/*, skills/.curated/*, skills/.experimental/*, skills/.system/*
-    for base in [
-        "skills",
-        "skills/.curated",
-        "skills/.experimental",
-        "skills/.system",
-    ] {
-        let base_dir = repo_dir.join(base);
-        if !base_dir.exists() {
-            continue;
-        }
-        if let Ok(rd) = std::fs::read_dir(&base_dir) {
-            for entry in rd.flatten() {
-                let p = entry.path();
-                if !p.is_dir() {
-                    continue;
-                }
-                let skill_md = p.join("SKILL.md");
-                if !skill_md.exists() {
-                    continue;
-                }
-                let (name, desc) = parse_skill_md(&skill_md).unwrap_or((
-                    p.file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string(),
-                    None,
-                ));
-                let rel = p
-                    .strip_prefix(&repo_dir)
-                    .unwrap_or(&p)
-                    .to_string_lossy()
-                    .to_string();
-                out.push(GitSkillCandidate {
-                    name,
-                    description: desc,
-                    subpath: rel,
-                });
-            }
-        }
+    // Nested skills at any depth, covering the standard add-skill locations (skills/*,
+    // skills/.curated/*, ...) as well as monorepo layouts like packages/*/skills/*.
+    for p in find_nested_skill_dirs(&repo_dir) {
+        let (name, desc) = parse_skill_md(&p.join("SKILL.md")).unwrap_or((
+            p.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            None,
+        ));
+        let rel = p
+            .strip_prefix(&repo_dir)
+            .unwrap_or(&p)
+            .to_string_lossy()
+            .to_string();
+        out.push(GitSkillCandidate {
+            name,
+            description: desc,
+            subpath: rel,
+        });
     }
 
     out.sort_by(|a, b| a.name.cmp(&b.name));
@@ -654,6 +1624,43 @@ pub fn list_git_skills<R: tauri::Runtime>(
     Ok(out)
 }
 
+/// Skill directories below `root` (not `root` itself), in file name order. A skill's own
+/// subdirectories are not searched, and the walk gives up after `MAX_SCAN_ENTRIES` directories
+/// or `MAX_SCAN_RESULTS` skills so huge monorepos still answer quickly.
+pub(crate) fn find_nested_skill_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut visited = 0;
+    let mut walker = WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(MAX_SCAN_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.file_type().is_dir()
+                && !SCAN_IGNORED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        });
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        visited += 1;
+        if visited > MAX_SCAN_ENTRIES || found.len() >= MAX_SCAN_RESULTS {
+            log::warn!(
+                "[installer] stopped scanning {:?} after {} dirs and {} skills",
+                root,
+                visited - 1,
+                found.len()
+            );
+            break;
+        }
+        if entry.path().join("SKILL.md").is_file() {
+            found.push(entry.into_path());
+            walker.skip_current_dir();
+        }
+    }
+    found
+}
+
 pub fn list_local_skills(base_path: &Path) -> Result<Vec<LocalSkillCandidate>> {
     if !base_path.exists() {
         anyhow::bail!("source path not found: {:?}", base_path);
@@ -663,35 +1670,17 @@ pub fn list_local_skills(base_path: &Path) -> Result<Vec<LocalSkillCandidate>> {
 
     let root_skill = base_path.join("SKILL.md");
     if root_skill.exists() {
-        match parse_skill_md_with_reason(&root_skill) {
-            Ok((name, desc)) => {
-                out.push(LocalSkillCandidate {
-                    name,
-                    description: desc,
-                    subpath: ".".to_string(),
-                    valid: true,
-                    reason: None,
-                });
-            }
-            Err(reason) => {
-                let fallback_name = base_path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-                out.push(LocalSkillCandidate {
-                    name: if fallback_name.is_empty() {
-                        "root-skill".to_string()
-                    } else {
-                        fallback_name
-                    },
-                    description: None,
-                    subpath: ".".to_string(),
-                    valid: false,
-                    reason: Some(reason.to_string()),
-                });
-            }
-        }
+        let fallback_name = base_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let fallback_name = if fallback_name.is_empty() {
+            "root-skill".to_string()
+        } else {
+            fallback_name
+        };
+        out.push(local_candidate(&root_skill, ".".to_string(), fallback_name));
     }
 
     for base in [
@@ -716,44 +1705,23 @@ pub fn list_local_skills(base_path: &Path) -> Result<Vec<LocalSkillCandidate>> {
                     .unwrap_or(&p)
                     .to_string_lossy()
                     .to_string();
+                let dir_name = p
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
                 if !skill_md.exists() {
                     out.push(LocalSkillCandidate {
-                        name: p
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string(),
+                        name: dir_name,
                         description: None,
                         subpath: rel,
                         valid: false,
                         reason: Some("missing_skill_md".to_string()),
+                        issues: Vec::new(),
                     });
                     continue;
                 }
-                match parse_skill_md_with_reason(&skill_md) {
-                    Ok((name, desc)) => {
-                        out.push(LocalSkillCandidate {
-                            name,
-                            description: desc,
-                            subpath: rel,
-                            valid: true,
-                            reason: None,
-                        });
-                    }
-                    Err(reason) => {
-                        out.push(LocalSkillCandidate {
-                            name: p
-                                .file_name()
-                                .unwrap_or_default()
-                                .to_string_lossy()
-                                .to_string(),
-                            description: None,
-                            subpath: rel,
-                            valid: false,
-                            reason: Some(reason.to_string()),
-                        });
-                    }
-                }
+                out.push(local_candidate(&skill_md, rel, dir_name));
             }
         }
     }
@@ -764,12 +1732,110 @@ pub fn list_local_skills(base_path: &Path) -> Result<Vec<LocalSkillCandidate>> {
     Ok(out)
 }
 
-pub fn install_git_skill_from_selection<R: tauri::Runtime>(
+/// Candidate for a local SKILL.md; the first frontmatter error, if any, becomes `reason`.
+fn local_candidate(skill_md: &Path, subpath: String, fallback_name: String) -> LocalSkillCandidate {
+    let issues = validate_skill_md(skill_md);
+    let first_error = issues
+        .iter()
+        .find(|i| i.severity == LintSeverity::Error)
+        .map(|i| i.code.clone());
+    match (first_error, parse_skill_md_with_reason(skill_md)) {
+        (None, Ok((name, description))) => LocalSkillCandidate {
+            name,
+            description,
+            subpath,
+            valid: true,
+            reason: None,
+            issues,
+        },
+        (error, parsed) => LocalSkillCandidate {
+            name: fallback_name,
+            description: None,
+            subpath,
+            valid: false,
+            reason: error.or_else(|| parsed.err().map(str::to_string)),
+            issues,
+        },
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropPathKind {
+    /// The folder itself is a skill (has a valid root SKILL.md).
+    SingleSkill,
+    /// Skills live under `skills/`; route to the local selection flow.
+    MultiSkillRepo,
+    /// A git checkout with an `origin` remote; installing via the remote keeps updates working.
+    GitClone,
+    Invalid,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct DropPathInfo {
+    pub path: String,
+    pub kind: DropPathKind,
+    /// Valid skills found by the local discovery rules.
+    pub skill_count: usize,
+    pub origin_url: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// Classify a dragged-in path without installing anything, using the same discovery rules as
+/// `list_local_skills`.
+pub fn classify_drop_path(path: &Path) -> DropPathInfo {
+    let mut info = DropPathInfo {
+        path: path.to_string_lossy().to_string(),
+        kind: DropPathKind::Invalid,
+        skill_count: 0,
+        origin_url: None,
+        reason: None,
+    };
+    if !path.exists() {
+        info.reason = Some("not_found".to_string());
+        return info;
+    }
+    if !path.is_dir() {
+        info.reason = Some("not_a_directory".to_string());
+        return info;
+    }
+
+    let candidates = list_local_skills(path).unwrap_or_default();
+    info.skill_count = candidates.iter().filter(|c| c.valid).count();
+    if info.skill_count == 0 {
+        // Surface why the root SKILL.md was rejected, if there is one.
+        info.reason = candidates
+            .iter()
+            .find(|c| c.subpath == ".")
+            .and_then(|c| c.reason.clone())
+            .or_else(|| Some("no_skills_found".to_string()));
+        return info;
+    }
+
+    // Only the dropped folder itself counts as a clone; a skill nested in some repo is local.
+    let origin = git2::Repository::open(path).ok().and_then(|repo| {
+        repo.find_remote("origin")
+            .ok()
+            .and_then(|remote| remote.url().map(normalize_git_remote))
+    });
+    info.kind = if let Some(origin) = origin {
+        info.origin_url = Some(origin);
+        DropPathKind::GitClone
+    } else if candidates.iter().any(|c| c.subpath == "." && c.valid) {
+        DropPathKind::SingleSkill
+    } else {
+        DropPathKind::MultiSkillRepo
+    };
+    info
+}
+
+pub fn install_git_skill_from_selection_with_options<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     store: &SkillStore,
     repo_url: &str,
     subpath: &str,
     name: Option<String>,
+    options: InstallOptions,
 ) -> Result<InstallResult> {
     let parsed = parse_github_url(repo_url);
     let display_name = name.unwrap_or_else(|| {
@@ -780,87 +1846,238 @@ pub fn install_git_skill_from_selection<R: tauri::Runtime>(
             .unwrap_or_else(|| derive_name_from_repo_url(&parsed.clone_url))
     });
 
-    let central_dir = resolve_central_repo_path(app, store)?;
+    let central_dir = resolve_install_repo_path(app, store)?;
     ensure_central_repo(&central_dir)?;
-    let central_path = central_dir.join(&display_name);
-    if central_path.exists() {
-        anyhow::bail!("skill already exists in central repo: {:?}", central_path);
-    }
+    let (display_name, central_path, replaces) =
+        match plan_install_target(store, &central_dir, display_name, options.on_conflict)? {
+            InstallTarget::Adopt {
+                name,
+                path,
+                existing,
+            } => {
+                let source = ("git".to_string(), Some(repo_url.to_string()), None);
+                return adopt_existing(store, name, &path, existing, source);
+            }
+            InstallTarget::Copy {
+                name,
+                path,
+                replaces,
+            } => (name, path, replaces),
+        };
 
     let (repo_dir, revision) =
         clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
 
-    let copy_src = if subpath == "." {
-        repo_dir.clone()
-    } else {
-        repo_dir.join(subpath)
-    };
+    let copy_src = resolve_subpath(&repo_dir, subpath)?;
     if !copy_src.exists() {
         anyhow::bail!("path not found in repo: {:?}", copy_src);
     }
+    let _lock = lock_replaced(replaces.as_ref())?;
+    let replaces_id = replaces.as_ref().map(|r| r.id.as_str());
+    ensure_signature_accepted(store, &display_name, &copy_src)?;
+    ensure_lint_passes(&copy_src, options.force)?;
+    ensure_constraints_hold(
+        store,
+        &display_name,
+        Some(repo_url),
+        &copy_src,
+        replaces_id,
+        options.force,
+    )?;
+    ensure_no_secrets(&copy_src, options.force)?;
+
+    copy_into_central(&copy_src, &central_path)?;
+    normalize_if_enabled(store, &central_path);
+
+    let source = (
+        "git".to_string(),
+        Some(repo_url.to_string()),
+        Some(revision),
+    );
+    register_installed(
+        store,
+        display_name,
+        &central_path,
+        source,
+        replaces.as_ref(),
+    )
+}
 
-    copy_dir_recursive(&copy_src, &central_path)
-        .with_context(|| format!("copy {:?} -> {:?}", copy_src, central_path))?;
-
-    let now = now_ms();
-    let content_hash = compute_content_hash(&central_path);
-    let metadata = SkillMetadata::load(&central_path);
-
-    let record = SkillRecord {
-        id: Uuid::new_v4().to_string(),
-        name: display_name,
-        source_type: "git".to_string(),
-        source_ref: Some(repo_url.to_string()),
-        source_revision: Some(revision),
-        central_path: central_path.to_string_lossy().to_string(),
-        content_hash: content_hash.clone(),
-        created_at: now,
-        updated_at: now,
-        last_sync_at: None,
-        last_seen_at: now,
-        status: "ok".to_string(),
-        metadata,
-    };
-    store.upsert_skill(&record)?;
+/// Clone `repo_url` into the git cache. Returns the checkout and, for GitHub folder URLs, the
+/// folder inside it the URL points at.
+pub(crate) fn fetch_git_checkout<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    repo_url: &str,
+) -> Result<(PathBuf, Option<String>)> {
+    let parsed = parse_github_url(repo_url);
+    let (repo_dir, _rev) = clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+    Ok((repo_dir, parsed.subpath))
+}
 
-    Ok(InstallResult {
-        skill_id: record.id,
-        name: record.name,
-        central_path,
-        content_hash,
-    })
+/// Install the folder at `subpath` of `repo_url`, whose checkout is `repo_dir`. GitHub folders
+/// are recorded as tree URLs so updates fetch only that folder.
+pub(crate) fn install_git_folder<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    repo_url: &str,
+    repo_dir: &Path,
+    subpath: &str,
+    name: String,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    let parsed = parse_github_url(repo_url);
+    let branch = parsed.branch.clone().or_else(|| {
+        git2::Repository::open(repo_dir)
+            .ok()
+            .and_then(|repo| current_branch_name(&repo))
+    });
+    match (github_repo_url(&parsed.clone_url), branch) {
+        (Some(base), Some(branch)) => install_git_skill_with_options(
+            app,
+            store,
+            &format!("{}/tree/{}/{}", base, branch, subpath),
+            Some(name),
+            options,
+        ),
+        _ => install_git_skill_from_selection_with_options(
+            app,
+            store,
+            repo_url,
+            subpath,
+            Some(name),
+            options,
+        ),
+    }
 }
 
-pub fn install_local_skill_from_selection<R: tauri::Runtime>(
+pub fn install_local_skill_from_selection_with_options<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     store: &SkillStore,
     base_path: &Path,
     subpath: &str,
     name: Option<String>,
+    options: InstallOptions,
 ) -> Result<InstallResult> {
     if !base_path.exists() {
         anyhow::bail!("source path not found: {:?}", base_path);
     }
 
-    let selected_dir = if subpath == "." {
-        base_path.to_path_buf()
-    } else {
-        base_path.join(subpath)
-    };
+    let selected_dir = resolve_subpath(base_path, subpath)?;
     if !selected_dir.exists() {
         anyhow::bail!("source path not found: {:?}", selected_dir);
     }
 
-    let skill_md = selected_dir.join("SKILL.md");
+    let parsed_name = ensure_valid_skill_md(&selected_dir)?;
+    let display_name = name.unwrap_or(parsed_name);
+
+    install_local_skill_with_options(app, store, &selected_dir, Some(display_name), options)
+}
+
+/// Fail unless `dir` has a SKILL.md without frontmatter errors; returns the name it declares.
+fn ensure_valid_skill_md(dir: &Path) -> Result<String> {
+    let skill_md = dir.join("SKILL.md");
     if !skill_md.exists() {
         anyhow::bail!("SKILL_INVALID|missing_skill_md");
     }
+    let errors: Vec<FrontmatterIssue> = validate_skill_md(&skill_md)
+        .into_iter()
+        .filter(|i| i.severity == LintSeverity::Error)
+        .collect();
+    if let Some(first) = errors.first() {
+        // `SKILL_INVALID|<code>|<issues as JSON>` so the UI can point at the offending lines.
+        anyhow::bail!(
+            "SKILL_INVALID|{}|{}",
+            first.code,
+            serde_json::to_string(&errors)?
+        );
+    }
     let (parsed_name, _desc) = parse_skill_md_with_reason(&skill_md)
         .map_err(|reason| anyhow::anyhow!("SKILL_INVALID|{}", reason))?;
+    Ok(parsed_name)
+}
 
-    let display_name = name.unwrap_or(parsed_name);
+/// Resolve a user-supplied subpath ("." for the root itself) under `root`. Anything that would
+/// leave `root`, through `..`, an absolute path or a symlink, fails with `INVALID_PATH|<subpath>`.
+fn resolve_subpath(root: &Path, subpath: &str) -> Result<PathBuf> {
+    if matches!(subpath.trim().trim_matches('/'), "" | ".") {
+        return Ok(root.to_path_buf());
+    }
+    resolve_in_skill(root, subpath)
+}
 
-    install_local_skill(app, store, &selected_dir, Some(display_name))
+/// Pre-install gate: lint errors abort the install unless the caller forces it.
+fn ensure_lint_passes(path: &Path, force: bool) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+    let report = lint_skill_dir(path)?;
+    if report.has_errors() {
+        let summary = report
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == LintSeverity::Error)
+            .map(|d| match &d.file {
+                Some(file) => format!("{} ({})", d.code, file),
+                None => d.code.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!("SKILL_LINT_FAILED|{}", summary);
+    }
+    Ok(())
+}
+
+/// Pre-install gate: content that looks like it holds credentials needs the caller to force the
+/// install, i.e. the user to confirm it. Runs after the other gates, so a confirmed retry only
+/// skips this one.
+fn ensure_no_secrets(path: &Path, force: bool) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+    let findings = scan_skill_dir(path)?;
+    if findings.is_empty() {
+        return Ok(());
+    }
+    let mut summary = findings
+        .iter()
+        .take(MAX_REPORTED_SECRETS)
+        .map(|f| f.describe())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if findings.len() > MAX_REPORTED_SECRETS {
+        summary.push_str(&format!(
+            " (+{} more)",
+            findings.len() - MAX_REPORTED_SECRETS
+        ));
+    }
+    anyhow::bail!("SECRETS_FOUND|{}", summary);
+}
+
+/// Pre-install gate: refuse content whose version breaks a dependency constraint either way.
+fn ensure_constraints_hold(
+    store: &SkillStore,
+    name: &str,
+    source_ref: Option<&str>,
+    content_dir: &Path,
+    replaces: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+    let metadata = SkillMetadata::load(content_dir);
+    let violations =
+        violations_for_candidate(store, name, source_ref, metadata.as_ref(), replaces)?;
+    if !violations.is_empty() {
+        let summary = violations
+            .iter()
+            .map(|v| v.describe())
+            .collect::<Vec<_>>()
+            .join("; ");
+        anyhow::bail!("DEPENDENCY_CONFLICT|{}", summary);
+    }
+    Ok(())
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -871,12 +2088,14 @@ struct RepoCacheMeta {
 
 static GIT_CACHE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
-fn clone_to_cache<R: tauri::Runtime>(
+pub(crate) fn clone_to_cache<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     store: &SkillStore,
     clone_url: &str,
     branch: Option<&str>,
 ) -> Result<(PathBuf, String)> {
+    // Checked before the cache too, so a host blocked later is not served from an old clone.
+    ensure_git_host_allowed(store, clone_url)?;
     let started = std::time::Instant::now();
     let cache_dir = app
         .path()
@@ -966,30 +2185,13 @@ fn parse_skill_md(path: &Path) -> Option<(String, Option<String>)> {
 }
 
 fn parse_skill_md_with_reason(path: &Path) -> Result<(String, Option<String>), &'static str> {
-    let text = std::fs::read_to_string(path).map_err(|_| "read_failed")?;
-    let mut lines = text.lines();
-    if lines.next().map(|v| v.trim()) != Some("---") {
-        return Err("invalid_frontmatter");
-    }
-    let mut name: Option<String> = None;
-    let mut desc: Option<String> = None;
-    let mut found_end = false;
-    for line in lines.by_ref() {
-        let l = line.trim();
-        if l == "---" {
-            found_end = true;
-            break;
-        }
-        if let Some(v) = l.strip_prefix("name:") {
-            name = Some(v.trim().trim_matches('"').to_string());
-        } else if let Some(v) = l.strip_prefix("description:") {
-            desc = Some(v.trim().trim_matches('"').to_string());
-        }
-    }
-    if !found_end {
-        return Err("invalid_frontmatter");
-    }
-    let name = name.ok_or("missing_name")?;
+    let fm = SkillFrontmatter::read(path)?;
+    let name = fm
+        .name
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or("missing_name")?;
+    let desc = fm.description.map(|v| v.trim().to_string());
     Ok((name, desc))
 }
 
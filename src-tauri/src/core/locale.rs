@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use anyhow::Result;
+
+use super::skill_store::SkillStore;
+
+pub const APP_LANGUAGE_KEY: &str = "app_language";
+/// Locale used when neither the preferred language nor its base language has a value.
+pub const FALLBACK_LOCALE: &str = "en";
+/// Language of messages the backend renders itself (error fallbacks, logs). Unset means English.
+pub const BACKEND_LOCALE_KEY: &str = "backend_locale";
+
+/// Languages the backend message catalogue is written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageLocale {
+    En,
+    Zh,
+}
+
+static MESSAGE_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+impl MessageLocale {
+    /// `zh`, `zh-cn`, `zh_TW`, ... map to Chinese; anything else to English.
+    pub fn from_tag(tag: &str) -> Self {
+        match normalize_locale(tag).split('-').next() {
+            Some("zh") => Self::Zh,
+            _ => Self::En,
+        }
+    }
+
+    pub fn as_tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Zh => "zh",
+        }
+    }
+}
+
+/// Locale backend messages are currently rendered in; process-wide so error conversions
+/// need no store access.
+pub fn message_locale() -> MessageLocale {
+    match MESSAGE_LOCALE.load(Ordering::Relaxed) {
+        1 => MessageLocale::Zh,
+        _ => MessageLocale::En,
+    }
+}
+
+fn apply_message_locale(locale: MessageLocale) {
+    let raw = match locale {
+        MessageLocale::En => 0,
+        MessageLocale::Zh => 1,
+    };
+    MESSAGE_LOCALE.store(raw, Ordering::Relaxed);
+}
+
+pub fn get_backend_locale(store: &SkillStore) -> Option<String> {
+    store
+        .get_setting(BACKEND_LOCALE_KEY)
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Persist and apply the backend locale. An empty tag clears it (back to English); tags other
+/// than English or Chinese are rejected since there is no catalogue for them.
+pub fn set_backend_locale(store: &SkillStore, tag: &str) -> Result<Option<String>> {
+    let normalized = normalize_locale(tag);
+    if normalized.is_empty() {
+        store.set_setting(BACKEND_LOCALE_KEY, "")?;
+        apply_message_locale(MessageLocale::En);
+        return Ok(None);
+    }
+    let locale = MessageLocale::from_tag(&normalized);
+    if locale == MessageLocale::En && normalized.split('-').next() != Some("en") {
+        anyhow::bail!("unsupported backend locale: {}", tag);
+    }
+    store.set_setting(BACKEND_LOCALE_KEY, locale.as_tag())?;
+    apply_message_locale(locale);
+    Ok(Some(locale.as_tag().to_string()))
+}
+
+/// Apply the stored backend locale; called once at startup.
+pub fn load_backend_locale(store: &SkillStore) {
+    let locale = get_backend_locale(store)
+        .map(|tag| MessageLocale::from_tag(&tag))
+        .unwrap_or(MessageLocale::En);
+    apply_message_locale(locale);
+}
+
+pub fn get_app_language(store: &SkillStore) -> Option<String> {
+    store
+        .get_setting(APP_LANGUAGE_KEY)
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+}
+
+pub fn set_app_language(store: &SkillStore, language: &str) -> Result<String> {
+    let normalized = normalize_locale(language);
+    if normalized.is_empty()
+        || !normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        anyhow::bail!("invalid language tag: {}", language);
+    }
+    store.set_setting(APP_LANGUAGE_KEY, &normalized)?;
+    Ok(normalized)
+}
+
+/// `zh_CN` / `ZH-cn` -> `zh-cn`.
+pub fn normalize_locale(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Pick the best entry for `lang`: exact tag, then its base language (`zh-cn` -> `zh`), then
+/// any regional variant of the base language, then English.
+pub fn pick_localized<'a>(
+    values: &'a BTreeMap<String, String>,
+    lang: Option<&str>,
+) -> Option<&'a str> {
+    let find = |tag: &str| {
+        values
+            .iter()
+            .find(|(k, _)| normalize_locale(k) == tag)
+            .map(|(_, v)| v.as_str())
+    };
+    if let Some(lang) = lang.map(normalize_locale) {
+        let base = lang.split('-').next().unwrap_or(&lang).to_string();
+        let regional = |(k, v): (&'a String, &'a String)| {
+            normalize_locale(k)
+                .starts_with(&format!("{}-", base))
+                .then_some(v.as_str())
+        };
+        if let Some(v) = find(&lang)
+            .or_else(|| find(&base))
+            .or_else(|| values.iter().find_map(regional))
+        {
+            return Some(v);
+        }
+    }
+    find(FALLBACK_LOCALE)
+}
+
+#[cfg(test)]
+#[path = "tests/locale.rs"]
+mod tests;
@@ -0,0 +1,367 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::content_hash::hash_dir_cached;
+use super::cursor_rules::{import_cursor_rule, CURSOR_RULE_SOURCE};
+use super::installer::{
+    install_git_skill_with_options, install_local_skill_with_options, parse_github_url,
+    update_managed_skill_from_source_with_force, InstallOptions,
+};
+use super::machine_overrides::MachineOverrides;
+use super::skill_executables::ensure_sync_allowed;
+use super::skill_files::{ensure_plain_name, write_file_atomic};
+use super::skill_lock::lock_skill;
+use super::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use super::sync_engine::sync_dir_for_tool_with_overwrite;
+use super::url_skill::{install_url_skill, URL_SOURCE};
+
+pub const LOCKFILE_NAME: &str = "skills.lock";
+const LOCKFILE_VERSION: u32 = 1;
+
+/// One managed skill, without anything machine-specific (ids, paths, timestamps).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    pub name: String,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub revision: Option<String>,
+    /// Folder inside the repo, for GitHub `tree` URLs.
+    pub subpath: Option<String>,
+    pub content_hash: Option<String>,
+    /// Tool keys the skill is synced to, sorted.
+    pub tools: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillsLock {
+    pub version: u32,
+    /// Sorted by name, so the same setup always produces the same file.
+    pub skills: Vec<LockedSkill>,
+}
+
+/// Describe every managed skill. Hashes are recomputed from the Central Repo; a skill whose
+/// directory is missing keeps its last recorded hash.
+pub fn build_lock(store: &SkillStore) -> Result<SkillsLock> {
+    let mut skills = Vec::new();
+    for skill in store.list_skills()? {
+        skills.push(locked_skill(store, skill)?);
+    }
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(SkillsLock {
+        version: LOCKFILE_VERSION,
+        skills,
+    })
+}
+
+/// The lock entry describing one managed skill.
+pub(crate) fn locked_skill(store: &SkillStore, skill: SkillRecord) -> Result<LockedSkill> {
+    let content_hash = current_hash(store, &skill)?.or(skill.content_hash.clone());
+    let mut tools: Vec<String> = store
+        .list_skill_targets(&skill.id)?
+        .into_iter()
+        .map(|t| t.tool)
+        .collect();
+    tools.sort();
+    tools.dedup();
+    let subpath = match (skill.source_type.as_str(), &skill.source_ref) {
+        ("git", Some(source_ref)) => parse_github_url(source_ref).subpath,
+        _ => None,
+    };
+    Ok(LockedSkill {
+        name: skill.name,
+        source_type: skill.source_type,
+        source_ref: skill.source_ref,
+        revision: skill.source_revision,
+        subpath,
+        content_hash,
+        tools,
+    })
+}
+
+/// Write the lock as pretty JSON with a trailing newline, ready to commit. A directory `dest`
+/// gets a `skills.lock` inside it.
+pub fn export_lock(store: &SkillStore, dest: &Path) -> Result<SkillsLock> {
+    let lock = build_lock(store)?;
+    let mut json = serde_json::to_string_pretty(&lock)?;
+    json.push('\n');
+    write_file_atomic(&lock_path(dest), json.as_bytes())?;
+    Ok(lock)
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(LOCKFILE_NAME)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Read a lock written by `export_lock`; a directory `path` is read as `<path>/skills.lock`.
+pub fn read_lock(path: &Path) -> Result<SkillsLock> {
+    let path = lock_path(path);
+    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+    let lock: SkillsLock =
+        serde_json::from_str(&raw).with_context(|| format!("parse {:?}", path))?;
+    if lock.version > LOCKFILE_VERSION {
+        anyhow::bail!(
+            "{} version {} is newer than this app supports",
+            LOCKFILE_NAME,
+            lock.version
+        );
+    }
+    ensure_valid_entries(&lock)?;
+    Ok(lock)
+}
+
+/// Reject a lock naming a skill that is not a single plain directory name. Names become
+/// directories in the Central Repo and tool dirs, and locks can come from anywhere.
+pub fn ensure_valid_entries(lock: &SkillsLock) -> Result<()> {
+    for entry in &lock.skills {
+        ensure_plain_name(&entry.name)?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct ApplyOptions {
+    /// Leave managed skills whose content differs from the lock as they are.
+    #[serde(default)]
+    pub skip_updates: bool,
+    /// Install and update only; create no tool targets.
+    #[serde(default)]
+    pub skip_sync: bool,
+    /// Bypass the lint, dependency and executables gates, as `force` does for single installs.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ApplyFailure {
+    pub skill: String,
+    /// Set when only syncing to this tool failed.
+    pub tool: Option<String>,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ApplyReport {
+    pub installed: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// Targets created, as `<skill>:<tool>`.
+    pub synced: Vec<String>,
+    pub failed: Vec<ApplyFailure>,
+    /// Skills whose content differs from the locked hash, or whose source revision differs
+    /// from the locked one, e.g. because the source has moved past it.
+    pub drifted: Vec<String>,
+    /// Managed skills the lock does not mention; they are left alone.
+    pub extra: Vec<String>,
+    /// Entries, or `<skill>:<tool>` targets, this machine's overrides leave out.
+    pub skipped: Vec<String>,
+}
+
+/// Install missing skills from their source, update those whose content differs from the lock,
+/// and sync each to the locked tools whose skills dir `tool_dir` resolves (see
+/// `tool_adapters::installed_tool_dir`). Failures are collected per skill; one bad entry does
+/// not stop the rest. Sources are installed at their current revision, so a locked revision
+/// that is no longer the source's is reported in `drifted`. A lock with an unsafe skill name is
+/// refused as a whole.
+pub fn apply_lock<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    lock: &SkillsLock,
+    options: ApplyOptions,
+    now: i64,
+    tool_dir: &dyn Fn(&str) -> Option<PathBuf>,
+) -> Result<ApplyReport> {
+    ensure_valid_entries(lock)?;
+    let mut report = ApplyReport::default();
+    let overrides = MachineOverrides::load(store)?;
+    for entry in &lock.skills {
+        if overrides.skips_skill(&entry.name) {
+            report.skipped.push(entry.name.clone());
+            continue;
+        }
+        let skill = match apply_entry(app, store, entry, options, &mut report) {
+            Ok(skill) => skill,
+            Err(err) => {
+                report.failed.push(ApplyFailure {
+                    skill: entry.name.clone(),
+                    tool: None,
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let hash = current_hash(store, &skill)?;
+        let revision_moved = entry.revision.is_some() && skill.source_revision != entry.revision;
+        if (entry.content_hash.is_some() && hash != entry.content_hash) || revision_moved {
+            report.drifted.push(entry.name.clone());
+        }
+        if options.skip_sync {
+            continue;
+        }
+        for tool in &entry.tools {
+            if overrides.skips_tool(&entry.name, tool) {
+                report.skipped.push(format!("{}:{}", entry.name, tool));
+                continue;
+            }
+            let target = |dir: PathBuf| overrides.target_path(&entry.name, tool, dir);
+            match sync_entry(store, &skill, tool, options.force, now, tool_dir, &target) {
+                Ok(true) => report.synced.push(format!("{}:{}", skill.name, tool)),
+                Ok(false) => {}
+                Err(err) => report.failed.push(ApplyFailure {
+                    skill: entry.name.clone(),
+                    tool: Some(tool.clone()),
+                    error: err.to_string(),
+                }),
+            }
+        }
+    }
+
+    let locked: BTreeSet<&str> = lock.skills.iter().map(|s| s.name.as_str()).collect();
+    report.extra = store
+        .list_skills()?
+        .into_iter()
+        .map(|s| s.name)
+        .filter(|name| !locked.contains(name.as_str()))
+        .collect();
+    report.extra.sort();
+    log::info!(
+        "[lockfile] applied: {} installed, {} updated, {} unchanged, {} synced, {} failed",
+        report.installed.len(),
+        report.updated.len(),
+        report.unchanged.len(),
+        report.synced.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// Whether `apply_lock` can install the entry on this machine when it is not managed yet.
+pub(crate) fn source_available(entry: &LockedSkill) -> bool {
+    match (entry.source_type.as_str(), &entry.source_ref) {
+        ("git" | URL_SOURCE, Some(_)) => true,
+        ("local", Some(source_ref)) => Path::new(source_ref).exists(),
+        (CURSOR_RULE_SOURCE, Some(source_ref)) => Path::new(source_ref).is_file(),
+        _ => false,
+    }
+}
+
+/// Make sure the entry is managed and up to date; returns its record afterwards.
+fn apply_entry<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    entry: &LockedSkill,
+    options: ApplyOptions,
+    report: &mut ApplyReport,
+) -> Result<SkillRecord> {
+    let existing = store
+        .list_skills()?
+        .into_iter()
+        .find(|s| s.name == entry.name);
+    let skill_id = match existing {
+        None => {
+            let install_options = InstallOptions::force(options.force);
+            let name = Some(entry.name.clone());
+            let installed = match (entry.source_type.as_str(), &entry.source_ref) {
+                ("git", Some(source_ref)) => {
+                    install_git_skill_with_options(app, store, source_ref, name, install_options)?
+                }
+                ("local", Some(source_ref)) if Path::new(source_ref).exists() => {
+                    install_local_skill_with_options(
+                        app,
+                        store,
+                        Path::new(source_ref),
+                        name,
+                        install_options,
+                    )?
+                }
+                (CURSOR_RULE_SOURCE, Some(source_ref)) if Path::new(source_ref).is_file() => {
+                    import_cursor_rule(
+                        app,
+                        store,
+                        Path::new(source_ref),
+                        entry.name.clone(),
+                        install_options,
+                    )?
+                }
+                (URL_SOURCE, Some(source_ref)) => {
+                    install_url_skill(app, store, source_ref, name, install_options)?
+                }
+                _ => anyhow::bail!("source is not available on this machine"),
+            };
+            report.installed.push(entry.name.clone());
+            installed.skill_id
+        }
+        Some(skill) => {
+            let differs =
+                entry.content_hash.is_some() && current_hash(store, &skill)? != entry.content_hash;
+            if differs && !options.skip_updates {
+                update_managed_skill_from_source_with_force(app, store, &skill.id, options.force)?;
+                report.updated.push(entry.name.clone());
+            } else {
+                report.unchanged.push(entry.name.clone());
+            }
+            skill.id
+        }
+    };
+    store
+        .get_skill_by_id(&skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))
+}
+
+pub(crate) fn current_hash(store: &SkillStore, skill: &SkillRecord) -> Result<Option<String>> {
+    let central = Path::new(&skill.central_path);
+    if !central.is_dir() {
+        return Ok(None);
+    }
+    let mut cache = store.load_file_hashes(&skill.id)?;
+    Ok(Some(hash_dir_cached(central, &mut cache)?))
+}
+
+/// Create the tool target unless one is recorded already. `Ok(false)` when nothing was needed.
+/// `target` maps the default `<tool dir>/<name>` to the path actually used.
+fn sync_entry(
+    store: &SkillStore,
+    skill: &SkillRecord,
+    tool: &str,
+    force: bool,
+    now: i64,
+    tool_dir: &dyn Fn(&str) -> Option<PathBuf>,
+    target: &dyn Fn(PathBuf) -> PathBuf,
+) -> Result<bool> {
+    if store.get_skill_target(&skill.id, tool)?.is_some() {
+        return Ok(false);
+    }
+    let Some(dir) = tool_dir(tool) else {
+        anyhow::bail!("TOOL_NOT_INSTALLED|{}", tool);
+    };
+    let _lock = lock_skill(&skill.id, &format!("sync:{}", tool))?;
+    ensure_sync_allowed(store, skill, force)?;
+    let outcome = sync_dir_for_tool_with_overwrite(
+        tool,
+        Path::new(&skill.central_path),
+        &target(dir.join(&skill.name)),
+        false,
+    )?;
+    store.upsert_skill_target(&SkillTargetRecord {
+        id: Uuid::new_v4().to_string(),
+        skill_id: skill.id.clone(),
+        tool: tool.to_string(),
+        target_path: outcome.target_path.to_string_lossy().to_string(),
+        mode: outcome.mode_used.as_key().to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(now),
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+#[path = "tests/lockfile.rs"]
+mod tests;
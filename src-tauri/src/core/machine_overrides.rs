@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::skill_store::{MachineOverrideRecord, SkillStore};
+use super::tool_adapters::adapter_by_key;
+
+/// This machine's overrides, loaded once for an apply or sync.
+#[derive(Clone, Debug, Default)]
+pub struct MachineOverrides {
+    records: Vec<MachineOverrideRecord>,
+}
+
+impl MachineOverrides {
+    pub fn load(store: &SkillStore) -> Result<Self> {
+        Ok(Self {
+            records: store.list_machine_overrides()?,
+        })
+    }
+
+    /// Whether the skill is left out everywhere on this machine.
+    pub fn skips_skill(&self, skill: &str) -> bool {
+        self.records
+            .iter()
+            .any(|r| r.skip && r.skill == skill && r.tool.is_none())
+    }
+
+    /// Whether syncing the skill to `tool` is left out, on its own or with the whole skill.
+    pub fn skips_tool(&self, skill: &str, tool: &str) -> bool {
+        self.skips_skill(skill)
+            || self
+                .records
+                .iter()
+                .any(|r| r.skip && r.skill == skill && r.tool.as_deref() == Some(tool))
+    }
+
+    /// Where the skill goes for `tool`: the override's path, or `default`.
+    pub fn target_path(&self, skill: &str, tool: &str, default: PathBuf) -> PathBuf {
+        self.records
+            .iter()
+            .find(|r| r.skill == skill && r.tool.as_deref() == Some(tool))
+            .and_then(|r| r.target_path.as_ref())
+            .map(PathBuf::from)
+            .unwrap_or(default)
+    }
+}
+
+/// Save an override, replacing the one for the same skill and tool. One that neither skips nor
+/// moves anything is removed instead. A target path needs a tool and must be absolute.
+pub fn set_machine_override(
+    store: &SkillStore,
+    record: MachineOverrideRecord,
+) -> Result<Option<MachineOverrideRecord>> {
+    let skill = record.skill.trim().to_string();
+    if skill.is_empty() {
+        anyhow::bail!("skill name is empty");
+    }
+    let tool = record
+        .tool
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    if let Some(tool) = &tool {
+        if adapter_by_key(tool).is_none() {
+            anyhow::bail!("unknown tool: {}", tool);
+        }
+    }
+    let target_path = record
+        .target_path
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    if let Some(path) = &target_path {
+        if tool.is_none() {
+            anyhow::bail!("a target path override needs a tool");
+        }
+        if !Path::new(path).is_absolute() {
+            anyhow::bail!("target path must be absolute");
+        }
+    }
+    if !record.skip && target_path.is_none() {
+        store.delete_machine_override(&skill, tool.as_deref())?;
+        return Ok(None);
+    }
+    let cleaned = MachineOverrideRecord {
+        skill,
+        tool,
+        skip: record.skip,
+        target_path,
+    };
+    store.upsert_machine_override(&cleaned)?;
+    Ok(Some(cleaned))
+}
+
+#[cfg(test)]
+#[path = "tests/machine_overrides.rs"]
+mod tests;
@@ -1,12 +1,62 @@
+pub mod app_logs;
+pub mod app_stats;
+pub mod background_mode;
+pub mod backup;
 pub mod cache_cleanup;
+pub mod central_git;
 pub mod central_repo;
+pub mod claude_plugins;
+pub mod clipboard;
+pub mod cloud_sync;
 pub mod content_hash;
+pub mod cursor_rules;
+pub mod db_encryption;
+pub mod deep_link;
+pub mod deployment_report;
+pub mod disk_usage;
+pub mod doctor;
+pub mod environments;
+pub mod skill_convert;
+pub mod skill_deps;
+pub mod skill_diff;
+pub mod skill_executables;
+pub mod skill_export;
+pub mod skill_files;
+pub mod skill_grep;
+pub mod skill_license;
+pub mod skill_lint;
+pub mod skill_lock;
 pub mod skill_metadata;
+pub mod skill_signing;
 pub mod git_fetcher;
+pub mod git_host_policy;
 pub mod github_search;
+pub mod hash_worker;
 pub mod installer;
+pub mod locale;
+pub mod lockfile;
+pub mod log_redact;
+pub mod machine_overrides;
 pub mod onboarding;
+pub mod operations;
+pub mod profiles;
+pub mod project_config;
+pub mod progress;
+pub mod reveal;
+pub mod scheduler;
+pub mod secret_scan;
+pub mod self_repair;
+pub mod setup_snapshots;
 pub mod skill_store;
+pub mod skill_sync;
+pub mod skill_templates;
+pub mod sync_coalesce;
 pub mod sync_engine;
+pub mod team_profiles;
 pub mod temp_cleanup;
+pub mod token_estimate;
 pub mod tool_adapters;
+pub mod tray;
+pub mod url_skill;
+pub mod webhooks;
+pub mod workspaces;
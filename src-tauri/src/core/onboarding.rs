@@ -2,11 +2,16 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+use uuid::Uuid;
 
-use super::central_repo::resolve_central_repo_path;
+use super::central_repo::central_repo_paths;
 use super::content_hash::hash_dir;
-use super::skill_store::SkillStore;
+use super::installer::{install_local_skill, InstallResult};
+use super::skill_store::{DiscoveredSkillRecord, SkillProjection, SkillStore, SkillTargetRecord};
+use super::skill_sync::adopt_as_target;
+use super::sync_engine::remove_path_any;
 use super::tool_adapters::{default_tool_adapters, scan_tool_dir, DetectedSkill};
 
 #[derive(Clone, Debug, Serialize)]
@@ -17,6 +22,8 @@ pub struct OnboardingVariant {
     pub fingerprint: Option<String>,
     pub is_link: bool,
     pub link_target: Option<PathBuf>,
+    /// Project directory the skill was found in; `None` for global tool dirs.
+    pub project: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -31,6 +38,37 @@ pub struct OnboardingPlan {
     pub total_tools_scanned: usize,
     pub total_skills_found: usize,
     pub groups: Vec<OnboardingGroup>,
+    /// In-repo skills found under the configured project roots. They are offered for import
+    /// only; their directories belong to the project and are never adopted as tool targets.
+    pub project_skills: Vec<OnboardingVariant>,
+}
+
+pub const PROJECT_ROOTS_KEY: &str = "onboarding_project_roots";
+/// How deep below a project root we look for project directories (`root/org/repo`).
+const PROJECT_SCAN_DEPTH: usize = 2;
+const PROJECT_SCAN_SKIP_DIRS: [&str; 4] = ["node_modules", "target", "dist", "build"];
+
+pub fn get_project_roots(store: &SkillStore) -> Vec<PathBuf> {
+    store
+        .get_setting(PROJECT_ROOTS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str::<Vec<PathBuf>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_project_roots(store: &SkillStore, roots: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut cleaned: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        if !root.is_absolute() {
+            anyhow::bail!("project root must be absolute: {:?}", root);
+        }
+        if !cleaned.contains(&root) {
+            cleaned.push(root);
+        }
+    }
+    store.set_setting(PROJECT_ROOTS_KEY, &serde_json::to_string(&cleaned)?)?;
+    Ok(cleaned)
 }
 
 pub fn build_onboarding_plan<R: tauri::Runtime>(
@@ -39,7 +77,7 @@ pub fn build_onboarding_plan<R: tauri::Runtime>(
 ) -> Result<OnboardingPlan> {
     let home =
         dirs::home_dir().ok_or_else(|| anyhow::anyhow!("failed to resolve home directory"))?;
-    let central = resolve_central_repo_path(app, store)?;
+    let central_repos = central_repo_paths(app, store)?;
     let managed_targets = store
         .list_all_skill_target_paths()
         .unwrap_or_default()
@@ -49,18 +87,37 @@ pub fn build_onboarding_plan<R: tauri::Runtime>(
     
     // Get names of already-managed skills to exclude from onboarding
     let managed_skill_names: std::collections::HashSet<String> = store
-        .list_skills()
+        .list_skills_with(SkillProjection::Summary)
         .unwrap_or_default()
         .into_iter()
         .map(|s| s.name)
         .collect();
     
-    build_onboarding_plan_in_home(&home, Some(&central), Some(&managed_targets), Some(&managed_skill_names))
+    let mut plan = build_onboarding_plan_in_home(
+        &home,
+        &central_repos,
+        Some(&managed_targets),
+        Some(&managed_skill_names),
+    )?;
+    let global_paths: std::collections::HashSet<PathBuf> = plan
+        .groups
+        .iter()
+        .flat_map(|g| g.variants.iter().map(|v| v.path.clone()))
+        .collect();
+    plan.project_skills = scan_project_roots(
+        &get_project_roots(store),
+        &central_repos,
+        Some(&managed_targets),
+    )?
+    .into_iter()
+    .filter(|v| !global_paths.contains(&v.path) && v.project.as_deref() != Some(home.as_path()))
+    .collect();
+    Ok(plan)
 }
 
 fn build_onboarding_plan_in_home(
     home: &Path,
-    exclude_root: Option<&Path>,
+    exclude_roots: &[PathBuf],
     exclude_managed_targets: Option<&std::collections::HashSet<String>>,
     exclude_managed_names: Option<&std::collections::HashSet<String>>,
 ) -> Result<OnboardingPlan> {
@@ -77,7 +134,7 @@ fn build_onboarding_plan_in_home(
         let detected = scan_tool_dir(adapter, &dir)?;
         all_detected.extend(filter_detected(
             detected,
-            exclude_root,
+            exclude_roots,
             exclude_managed_targets,
         ));
     }
@@ -100,6 +157,7 @@ fn build_onboarding_plan_in_home(
             fingerprint,
             is_link: skill.is_link,
             link_target: skill.link_target.clone(),
+            project: None,
         });
     }
 
@@ -129,21 +187,426 @@ fn build_onboarding_plan_in_home(
         total_tools_scanned: scanned,
         total_skills_found,
         groups,
+        project_skills: Vec::new(),
     })
 }
 
+fn scan_project_roots(
+    roots: &[PathBuf],
+    exclude_roots: &[PathBuf],
+    exclude_managed_targets: Option<&std::collections::HashSet<String>>,
+) -> Result<Vec<OnboardingVariant>> {
+    let adapters = default_tool_adapters();
+    let mut out = Vec::new();
+    for root in roots {
+        for project in find_project_dirs(root) {
+            let mut seen_dirs = std::collections::HashSet::new();
+            for adapter in &adapters {
+                let dir = project.join(adapter.relative_skills_dir);
+                // Several tools share the same skills dir; report each project skill once.
+                if !dir.is_dir() || !seen_dirs.insert(dir.clone()) {
+                    continue;
+                }
+                let detected = scan_tool_dir(adapter, &dir)?;
+                for skill in filter_detected(detected, exclude_roots, exclude_managed_targets) {
+                    out.push(OnboardingVariant {
+                        tool: skill.tool.as_key().to_string(),
+                        fingerprint: hash_dir(&skill.path).ok(),
+                        name: skill.name,
+                        path: skill.path,
+                        is_link: skill.is_link,
+                        link_target: skill.link_target,
+                        project: Some(project.clone()),
+                    });
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The root itself plus visible subdirectories up to `PROJECT_SCAN_DEPTH` levels down.
+fn find_project_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if !root.is_dir() {
+        return dirs;
+    }
+    let mut frontier = vec![root.to_path_buf()];
+    for depth in 0..=PROJECT_SCAN_DEPTH {
+        let mut next = Vec::new();
+        for dir in frontier {
+            if depth < PROJECT_SCAN_DEPTH {
+                if let Ok(rd) = std::fs::read_dir(&dir) {
+                    for entry in rd.flatten() {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                        if is_dir
+                            && !name.starts_with('.')
+                            && !PROJECT_SCAN_SKIP_DIRS.contains(&name.as_str())
+                        {
+                            next.push(entry.path());
+                        }
+                    }
+                }
+            }
+            dirs.push(dir);
+        }
+        frontier = next;
+    }
+    dirs
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OnboardingSelection {
+    pub name: String,
+    /// Variant whose content is copied into the central repo.
+    pub canonical_path: PathBuf,
+    /// How to treat variants whose content differs from the canonical one.
+    #[serde(default)]
+    pub resolution: ConflictResolution,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Leave differing variants untouched.
+    #[default]
+    Skip,
+    /// The canonical variant wins; differing copies are replaced by managed targets.
+    Winner,
+    /// Import each differing variant as its own skill named `<name>-<tool>`.
+    KeepBoth,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct KeptVariant {
+    pub name: String,
+    pub skill_id: String,
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OnboardingImportResult {
+    pub name: String,
+    pub skill_id: Option<String>,
+    pub targets: Vec<String>,
+    /// Variants left in place because their content differs from the canonical one.
+    pub skipped: Vec<PathBuf>,
+    /// Differing variants imported under suffixed names (`keep_both`).
+    pub kept: Vec<KeptVariant>,
+    pub error: Option<String>,
+}
+
+/// Import every selected group in one go. Failures are reported per group and do not
+/// abort the remaining selections.
+pub fn execute_onboarding_plan<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    selections: &[OnboardingSelection],
+) -> Result<Vec<OnboardingImportResult>> {
+    let plan = build_onboarding_plan(app, store)?;
+    Ok(execute_onboarding_selections(app, store, &plan, selections))
+}
+
+fn execute_onboarding_selections<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    plan: &OnboardingPlan,
+    selections: &[OnboardingSelection],
+) -> Vec<OnboardingImportResult> {
+    selections
+        .iter()
+        .map(|selection| {
+            let mut result = OnboardingImportResult {
+                name: selection.name.clone(),
+                skill_id: None,
+                targets: Vec::new(),
+                skipped: Vec::new(),
+                kept: Vec::new(),
+                error: None,
+            };
+            if let Err(err) = import_group(app, store, plan, selection, &mut result) {
+                log::warn!(
+                    "[onboarding] import of {} failed: {:#}",
+                    selection.name,
+                    err
+                );
+                result.error = Some(format!("{:#}", err));
+            }
+            result
+        })
+        .collect()
+}
+
+fn import_group<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    plan: &OnboardingPlan,
+    selection: &OnboardingSelection,
+    result: &mut OnboardingImportResult,
+) -> Result<()> {
+    let group = plan
+        .groups
+        .iter()
+        .find(|g| g.name == selection.name)
+        .ok_or_else(|| anyhow::anyhow!("onboarding group not found: {}", selection.name))?;
+    let canonical = group
+        .variants
+        .iter()
+        .find(|v| v.path == selection.canonical_path)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "canonical path is not a variant of {}: {:?}",
+                selection.name,
+                selection.canonical_path
+            )
+        })?;
+
+    let installed = install_local_skill(
+        app,
+        store,
+        &canonical.path,
+        Some(selection.name.clone()),
+    )?;
+    result.skill_id = Some(installed.skill_id.clone());
+
+    let now = now_ms();
+    let mut kept_by_fingerprint: HashMap<Option<String>, (String, PathBuf)> = HashMap::new();
+    for variant in &group.variants {
+        if variant.fingerprint == canonical.fingerprint
+            || selection.resolution == ConflictResolution::Winner
+        {
+            adopt_as_target(
+                store,
+                &installed.skill_id,
+                &variant.tool,
+                &installed.central_path,
+                &variant.path,
+                now,
+            )?;
+            result.targets.push(variant.tool.clone());
+            continue;
+        }
+        if selection.resolution == ConflictResolution::Skip {
+            result.skipped.push(variant.path.clone());
+            continue;
+        }
+
+        // keep_both: one extra skill per distinct content, re-linked under the suffixed name.
+        let (skill_id, central_path) = match kept_by_fingerprint.get(&variant.fingerprint) {
+            Some(existing) => existing.clone(),
+            None => {
+                let suffixed = format!("{}-{}", selection.name, variant.tool);
+                let kept = install_local_skill(app, store, &variant.path, Some(suffixed.clone()))?;
+                result.kept.push(KeptVariant {
+                    name: suffixed,
+                    skill_id: kept.skill_id.clone(),
+                    path: variant.path.clone(),
+                });
+                let entry = (kept.skill_id, kept.central_path);
+                kept_by_fingerprint.insert(variant.fingerprint.clone(), entry.clone());
+                entry
+            }
+        };
+        let renamed = match (variant.path.parent(), central_path.file_name()) {
+            (Some(parent), Some(file_name)) => parent.join(file_name),
+            _ => anyhow::bail!("invalid variant path: {:?}", variant.path),
+        };
+        adopt_as_target(store, &skill_id, &variant.tool, &central_path, &renamed, now)?;
+        if variant.path != renamed {
+            remove_path_any(&variant.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Emitted with the newly found `DiscoveredSkill`s when a rescan turns up unmanaged skills.
+pub const SKILLS_DISCOVERED_EVENT: &str = "skills-discovered";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DiscoveredSkill {
+    pub id: String,
+    pub name: Option<String>,
+    pub tool: String,
+    pub path: String,
+    pub fingerprint: Option<String>,
+    pub found_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DiscoveryScan {
+    /// Unmanaged skills currently present in tool dirs, one entry per distinct content.
+    pub skills: Vec<DiscoveredSkill>,
+    /// Subset of `skills` whose content was not seen by the previous scan.
+    pub new_skills: Vec<DiscoveredSkill>,
+}
+
+/// Rescan tool directories and refresh the `discovered_skills` table.
+pub fn scan_tools_now<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Result<DiscoveryScan> {
+    let plan = build_onboarding_plan(app, store)?;
+    let scan = refresh_discovered_skills(store, &plan, now_ms())?;
+    if !scan.new_skills.is_empty() {
+        if let Err(err) = app.emit(SKILLS_DISCOVERED_EVENT, &scan.new_skills) {
+            log::warn!("[onboarding] failed to emit discovery event: {}", err);
+        }
+    }
+    Ok(scan)
+}
+
+fn refresh_discovered_skills(
+    store: &SkillStore,
+    plan: &OnboardingPlan,
+    now: i64,
+) -> Result<DiscoveryScan> {
+    let known: std::collections::HashSet<String> = store
+        .list_discovered_skills()?
+        .iter()
+        .map(|r| discovery_key(r.fingerprint.as_deref(), &r.found_path))
+        .collect();
+
+    let records: Vec<DiscoveredSkillRecord> = plan
+        .groups
+        .iter()
+        .flat_map(|g| g.variants.iter())
+        .map(|v| DiscoveredSkillRecord {
+            id: Uuid::new_v4().to_string(),
+            tool: v.tool.clone(),
+            found_path: v.path.to_string_lossy().to_string(),
+            name_guess: Some(v.name.clone()),
+            fingerprint: v.fingerprint.clone(),
+            found_at: now,
+            imported_skill_id: None,
+        })
+        .collect();
+    store.replace_discovered_skills(&records)?;
+
+    let mut seen = std::collections::HashSet::new();
+    let skills: Vec<DiscoveredSkill> = store
+        .list_discovered_skills()?
+        .into_iter()
+        .filter(|r| seen.insert(discovery_key(r.fingerprint.as_deref(), &r.found_path)))
+        .map(|r| DiscoveredSkill {
+            id: r.id,
+            name: r.name_guess,
+            tool: r.tool,
+            path: r.found_path,
+            fingerprint: r.fingerprint,
+            found_at: r.found_at,
+        })
+        .collect();
+    // Earlier records without a fingerprint (hashing failed, or a migration cleared it) can
+    // only be recognised by path.
+    let new_skills = skills
+        .iter()
+        .filter(|s| {
+            !known.contains(&discovery_key(s.fingerprint.as_deref(), &s.path))
+                && !known.contains(&discovery_key(None, &s.path))
+        })
+        .cloned()
+        .collect();
+
+    Ok(DiscoveryScan { skills, new_skills })
+}
+
+fn discovery_key(fingerprint: Option<&str>, path: &str) -> String {
+    match fingerprint {
+        Some(fp) => format!("fp:{fp}"),
+        None => format!("path:{path}"),
+    }
+}
+
+/// Import a single detected skill. With `adopt_tool`, the original directory is replaced by a
+/// managed target of that tool so onboarding stops re-detecting it as an unmanaged duplicate.
+/// Same-named copies with identical content in other tools are recorded as targets too.
+pub fn import_existing_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: Option<String>,
+    adopt_tool: Option<&str>,
+) -> Result<InstallResult> {
+    let name = name.unwrap_or_else(|| {
+        source_path
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unnamed-skill".to_string())
+    });
+    // Look up siblings before the install marks the name as managed and hides the group.
+    let group = build_onboarding_plan(app, store)
+        .ok()
+        .and_then(|plan| plan.groups.into_iter().find(|g| g.name == name));
+    import_detected_skill(app, store, source_path, name, adopt_tool, group.as_ref())
+}
+
+fn import_detected_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    source_path: &Path,
+    name: String,
+    adopt_tool: Option<&str>,
+    group: Option<&OnboardingGroup>,
+) -> Result<InstallResult> {
+    let fingerprint = hash_dir(source_path).ok();
+    let installed = install_local_skill(app, store, source_path, Some(name))?;
+    let now = now_ms();
+    if let Some(tool) = adopt_tool {
+        adopt_as_target(
+            store,
+            &installed.skill_id,
+            tool,
+            &installed.central_path,
+            source_path,
+            now,
+        )?;
+    }
+
+    // Identical copies already match the central content, so they are recorded as copy
+    // targets in place; nothing on disk changes.
+    let (Some(group), Some(fingerprint)) = (group, fingerprint) else {
+        return Ok(installed);
+    };
+    for variant in &group.variants {
+        if variant.fingerprint.as_deref() != Some(fingerprint.as_str())
+            || adopt_tool == Some(variant.tool.as_str())
+        {
+            continue;
+        }
+        store.upsert_skill_target(&SkillTargetRecord {
+            id: Uuid::new_v4().to_string(),
+            skill_id: installed.skill_id.clone(),
+            tool: variant.tool.clone(),
+            target_path: variant.path.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(now),
+        })?;
+    }
+    Ok(installed)
+}
+
+fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
+
 fn filter_detected(
     detected: Vec<DetectedSkill>,
-    exclude_root: Option<&Path>,
+    exclude_roots: &[PathBuf],
     exclude_managed_targets: Option<&std::collections::HashSet<String>>,
 ) -> Vec<DetectedSkill> {
-    if exclude_root.is_none() && exclude_managed_targets.is_none() {
+    if exclude_roots.is_empty() && exclude_managed_targets.is_none() {
         return detected;
     }
     detected
         .into_iter()
         .filter(|skill| {
-            if let Some(exclude_root) = exclude_root {
+            for exclude_root in exclude_roots {
                 if is_under(&skill.path, exclude_root) {
                     return false;
                 }
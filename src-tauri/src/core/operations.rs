@@ -0,0 +1,219 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::Emitter;
+use uuid::Uuid;
+
+use super::progress::{self, ProgressReporter};
+
+/// Emitted with the full `OperationStatus` whenever a running operation reports progress.
+pub const OPERATION_PROGRESS_EVENT: &str = "operation-progress";
+/// Emitted with the final `OperationStatus` once an operation succeeds or fails.
+pub const OPERATION_FINISHED_EVENT: &str = "operation-finished";
+/// Finished operations kept for `get_operation_status` after a UI reload.
+const MAX_FINISHED_OPERATIONS: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OperationStatus {
+    pub id: String,
+    /// Command that started the operation, e.g. `install_git`.
+    pub kind: String,
+    pub state: OperationState,
+    pub stage: Option<String>,
+    pub current: u64,
+    pub total: Option<u64>,
+    /// Serialized return value of the underlying command once it succeeded.
+    pub result: Option<serde_json::Value>,
+    /// Serialized error returned by the command, in the same shape the command would reject with.
+    pub error: Option<serde_json::Value>,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+/// In-memory table of background operations, shared through Tauri state.
+#[derive(Clone, Default)]
+pub struct OperationRegistry {
+    ops: Arc<Mutex<Vec<OperationStatus>>>,
+}
+
+impl OperationRegistry {
+    pub fn begin(&self, kind: &str) -> OperationStatus {
+        let status = OperationStatus {
+            id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            state: OperationState::Running,
+            stage: None,
+            current: 0,
+            total: None,
+            result: None,
+            error: None,
+            started_at: now_ms(),
+            finished_at: None,
+        };
+        self.lock().push(status.clone());
+        status
+    }
+
+    pub fn get(&self, id: &str) -> Option<OperationStatus> {
+        self.lock().iter().find(|op| op.id == id).cloned()
+    }
+
+    /// All known operations, oldest first.
+    pub fn list(&self) -> Vec<OperationStatus> {
+        self.lock().clone()
+    }
+
+    pub fn progress(
+        &self,
+        id: &str,
+        stage: &str,
+        current: u64,
+        total: Option<u64>,
+    ) -> Option<OperationStatus> {
+        let mut ops = self.lock();
+        let op = ops
+            .iter_mut()
+            .find(|op| op.id == id && op.state == OperationState::Running)?;
+        op.stage = Some(stage.to_string());
+        op.current = current;
+        op.total = total;
+        Some(op.clone())
+    }
+
+    pub fn finish(
+        &self,
+        id: &str,
+        outcome: Result<serde_json::Value, serde_json::Value>,
+    ) -> Option<OperationStatus> {
+        let mut ops = self.lock();
+        let op = ops.iter_mut().find(|op| op.id == id)?;
+        match outcome {
+            Ok(value) => {
+                op.state = OperationState::Succeeded;
+                op.result = Some(value);
+            }
+            Err(err) => {
+                op.state = OperationState::Failed;
+                op.error = Some(err);
+            }
+        }
+        op.finished_at = Some(now_ms());
+        let finished = op.clone();
+
+        let done = ops
+            .iter()
+            .filter(|op| op.state != OperationState::Running)
+            .count();
+        let mut excess = done.saturating_sub(MAX_FINISHED_OPERATIONS);
+        ops.retain(|op| {
+            if excess > 0 && op.state != OperationState::Running {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+        Some(finished)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<OperationStatus>> {
+        // A panic while holding the lock cannot leave the list half-updated.
+        self.ops
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Passed to the work closure so it can report progress for its own operation.
+pub struct OperationHandle<R: tauri::Runtime> {
+    app: tauri::AppHandle<R>,
+    registry: OperationRegistry,
+    id: String,
+}
+
+impl<R: tauri::Runtime> OperationHandle<R> {
+    pub fn progress(&self, stage: &str, current: u64, total: Option<u64>) {
+        if let Some(status) = self.registry.progress(&self.id, stage, current, total) {
+            emit(&self.app, OPERATION_PROGRESS_EVENT, &status);
+        }
+    }
+
+    /// Forwards core progress (clone, copy) to this operation.
+    pub fn reporter(&self) -> ProgressReporter {
+        let app = self.app.clone();
+        let registry = self.registry.clone();
+        let id = self.id.clone();
+        Arc::new(move |stage, current, total| {
+            if let Some(status) = registry.progress(&id, stage, current, total) {
+                emit(&app, OPERATION_PROGRESS_EVENT, &status);
+            }
+        })
+    }
+}
+
+/// Run `work` on the blocking pool and return its operation id right away. Progress reported
+/// through `core::progress` while `work` runs is forwarded to the operation. The result (or
+/// error) is stored in `registry` and announced with `OPERATION_FINISHED_EVENT`.
+pub fn spawn_operation<R, T, E, F>(
+    app: &tauri::AppHandle<R>,
+    registry: &OperationRegistry,
+    kind: &str,
+    work: F,
+) -> String
+where
+    R: tauri::Runtime,
+    T: Serialize,
+    E: Serialize + From<String>,
+    F: FnOnce(&OperationHandle<R>) -> Result<T, E> + Send + 'static,
+{
+    let status = registry.begin(kind);
+    emit(app, OPERATION_PROGRESS_EVENT, &status);
+    let handle = OperationHandle {
+        app: app.clone(),
+        registry: registry.clone(),
+        id: status.id.clone(),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let reporter = handle.reporter();
+        let run = || progress::with_reporter(reporter, || work(&handle));
+        let result = match catch_unwind(AssertUnwindSafe(run)) {
+            Ok(result) => result.and_then(|value| {
+                serde_json::to_value(value).map_err(|err| E::from(format!("serialize: {}", err)))
+            }),
+            Err(_) => Err(E::from("operation panicked".to_string())),
+        };
+        let outcome = result.map_err(|err| {
+            serde_json::to_value(err)
+                .unwrap_or_else(|err| serde_json::Value::String(err.to_string()))
+        });
+        if let Some(status) = handle.registry.finish(&handle.id, outcome) {
+            emit(&handle.app, OPERATION_FINISHED_EVENT, &status);
+        }
+    });
+    status.id
+}
+
+fn emit<R: tauri::Runtime>(app: &tauri::AppHandle<R>, event: &str, status: &OperationStatus) {
+    if let Err(err) = app.emit(event, status) {
+        log::warn!("[operations] failed to emit {}: {}", event, err);
+    }
+}
+
+fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
+
+#[cfg(test)]
+#[path = "tests/operations.rs"]
+mod tests;
@@ -0,0 +1,313 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::lockfile::{
+    apply_lock, ensure_valid_entries, locked_skill, source_available, ApplyOptions, ApplyReport,
+    LockedSkill, SkillsLock,
+};
+use super::machine_overrides::MachineOverrides;
+use super::skill_files::write_file_atomic;
+use super::skill_store::{ProfileRecord, SkillProjection, SkillStore};
+
+const PROFILE_FILE_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProfileView {
+    #[serde(flatten)]
+    pub profile: ProfileRecord,
+    /// Tools this profile was last activated for.
+    pub active_tools: Vec<String>,
+}
+
+/// What activating a profile for a tool changes: members without a target are synced, other
+/// managed skills synced to the tool are unsynced. Skills already in place, and members this
+/// machine skips for the tool, are left alone.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ProfilePlan {
+    pub sync: Vec<String>,
+    pub unsync: Vec<String>,
+}
+
+pub fn list_profiles(store: &SkillStore) -> Result<Vec<ProfileView>> {
+    let active = store.list_active_profiles()?;
+    Ok(store
+        .list_profiles()?
+        .into_iter()
+        .map(|profile| ProfileView {
+            active_tools: active
+                .iter()
+                .filter(|(_, id)| **id == profile.id)
+                .map(|(tool, _)| tool.clone())
+                .collect(),
+            profile,
+        })
+        .collect())
+}
+
+/// Create a profile (`id` of `None`) or replace the name and members of an existing one.
+/// Names are unique, ignoring case, and every member must be a managed skill.
+pub fn save_profile(
+    store: &SkillStore,
+    id: Option<&str>,
+    name: &str,
+    skill_ids: &[String],
+    now: i64,
+) -> Result<ProfileRecord> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("profile name is empty");
+    }
+    let profiles = store.list_profiles()?;
+    if profiles
+        .iter()
+        .any(|p| p.name.eq_ignore_ascii_case(name) && Some(p.id.as_str()) != id)
+    {
+        anyhow::bail!("a profile named {:?} already exists", name);
+    }
+    let existing = match id {
+        Some(id) => Some(
+            profiles
+                .into_iter()
+                .find(|p| p.id == id)
+                .ok_or_else(|| anyhow::anyhow!("profile not found"))?,
+        ),
+        None => None,
+    };
+    let mut members = skill_ids.to_vec();
+    members.sort();
+    members.dedup();
+    for skill_id in &members {
+        if store.get_skill_by_id(skill_id)?.is_none() {
+            anyhow::bail!("skill not found: {}", skill_id);
+        }
+    }
+    let record = ProfileRecord {
+        id: existing
+            .as_ref()
+            .map(|p| p.id.clone())
+            .unwrap_or_else(|| Uuid::new_v4().to_string()),
+        name: name.to_string(),
+        skill_ids: members,
+        created_at: existing.as_ref().map_or(now, |p| p.created_at),
+        updated_at: now,
+    };
+    store.upsert_profile(&record)?;
+    Ok(record)
+}
+
+pub fn plan_profile_activation(
+    store: &SkillStore,
+    profile_id: &str,
+    tool: &str,
+) -> Result<ProfilePlan> {
+    let profile = store
+        .get_profile(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("profile not found"))?;
+    let overrides = MachineOverrides::load(store)?;
+    let mut plan = ProfilePlan::default();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        let member = profile.skill_ids.contains(&skill.id);
+        let synced = store.get_skill_target(&skill.id, tool)?.is_some();
+        if member && !synced && !overrides.skips_tool(&skill.name, tool) {
+            plan.sync.push(skill.id);
+        } else if !member && synced {
+            plan.unsync.push(skill.id);
+        }
+    }
+    Ok(plan)
+}
+
+/// A profile as a file to share: its name and, for each member, the source and pinned revision
+/// as in `skills.lock`. Tools are left out; they are chosen when the profile is activated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileFile {
+    pub version: u32,
+    pub name: String,
+    /// Sorted by name.
+    pub skills: Vec<LockedSkill>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    /// A managed skill has the entry's name and is used as it is.
+    Managed,
+    Install,
+    /// Not managed and its source cannot be installed here (a local path that does not exist).
+    Unavailable,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ImportPreviewEntry {
+    pub name: String,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub revision: Option<String>,
+    pub action: ImportAction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProfileImportPreview {
+    pub name: String,
+    /// A profile with this name exists and would have its members replaced.
+    pub replaces_existing: bool,
+    pub skills: Vec<ImportPreviewEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProfileImportReport {
+    pub profile: ProfileRecord,
+    /// The install pass; `extra` is left empty.
+    pub apply: ApplyReport,
+}
+
+/// Write the profile as pretty JSON with a trailing newline.
+pub fn export_profile(store: &SkillStore, profile_id: &str, dest: &Path) -> Result<ProfileFile> {
+    let profile = store
+        .get_profile(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("profile not found"))?;
+    let mut skills = Vec::new();
+    for skill_id in &profile.skill_ids {
+        if let Some(skill) = store.get_skill_by_id(skill_id)? {
+            skills.push(LockedSkill {
+                tools: Vec::new(),
+                ..locked_skill(store, skill)?
+            });
+        }
+    }
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    let file = ProfileFile {
+        version: PROFILE_FILE_VERSION,
+        name: profile.name,
+        skills,
+    };
+    let mut json = serde_json::to_string_pretty(&file)?;
+    json.push('\n');
+    write_file_atomic(dest, json.as_bytes())?;
+    Ok(file)
+}
+
+pub fn read_profile_file(path: &Path) -> Result<ProfileFile> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("read {:?}", path))?;
+    let file: ProfileFile =
+        serde_json::from_str(&raw).with_context(|| format!("parse {:?}", path))?;
+    if file.version > PROFILE_FILE_VERSION {
+        anyhow::bail!(
+            "profile file version {} is newer than this app supports",
+            file.version
+        );
+    }
+    if file.name.trim().is_empty() {
+        anyhow::bail!("profile name is empty");
+    }
+    ensure_valid_entries(&file.as_lock())?;
+    Ok(file)
+}
+
+impl ProfileFile {
+    /// The members as a lock for `apply_lock`.
+    pub(crate) fn as_lock(&self) -> SkillsLock {
+        SkillsLock {
+            version: 1,
+            skills: self.skills.clone(),
+        }
+    }
+}
+
+/// What importing `file` would do, without changing anything.
+pub fn preview_profile_import(
+    store: &SkillStore,
+    file: &ProfileFile,
+) -> Result<ProfileImportPreview> {
+    let managed: Vec<String> = store
+        .list_skills_with(SkillProjection::Summary)?
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    let skills = file
+        .skills
+        .iter()
+        .map(|entry| ImportPreviewEntry {
+            name: entry.name.clone(),
+            source_type: entry.source_type.clone(),
+            source_ref: entry.source_ref.clone(),
+            revision: entry.revision.clone(),
+            action: if managed.contains(&entry.name) {
+                ImportAction::Managed
+            } else if source_available(entry) {
+                ImportAction::Install
+            } else {
+                ImportAction::Unavailable
+            },
+        })
+        .collect();
+    Ok(ProfileImportPreview {
+        name: file.name.trim().to_string(),
+        replaces_existing: find_profile_by_name(store, &file.name)?.is_some(),
+        skills,
+    })
+}
+
+/// Install the members that are not managed yet through the normal install flow (managed skills
+/// are left at their version), then save the profile with every member that is now managed. A
+/// profile with the same name has its members replaced. A file naming a skill that is not a
+/// plain directory name is refused.
+pub fn import_profile<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    file: &ProfileFile,
+    force: bool,
+    now: i64,
+) -> Result<ProfileImportReport> {
+    ensure_valid_entries(&file.as_lock())?;
+    let options = ApplyOptions {
+        skip_updates: true,
+        skip_sync: true,
+        force,
+    };
+    apply_profile_file(app, store, file, None, options, now)
+}
+
+/// Run `file` through `apply_lock` with `options`, then save it as profile `profile_id` (by
+/// default the profile with the file's name, or a new one) with every entry now managed.
+pub(crate) fn apply_profile_file<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    file: &ProfileFile,
+    profile_id: Option<&str>,
+    options: ApplyOptions,
+    now: i64,
+) -> Result<ProfileImportReport> {
+    ensure_valid_entries(&file.as_lock())?;
+    let mut apply = apply_lock(app, store, &file.as_lock(), options, now, &|_| None)?;
+    apply.extra.clear();
+    let mut members = Vec::new();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        if file.skills.iter().any(|entry| entry.name == skill.name) {
+            members.push(skill.id);
+        }
+    }
+    let profile_id = match profile_id {
+        Some(id) => Some(id.to_string()),
+        None => find_profile_by_name(store, &file.name)?.map(|p| p.id),
+    };
+    let profile = save_profile(store, profile_id.as_deref(), &file.name, &members, now)?;
+    Ok(ProfileImportReport { profile, apply })
+}
+
+pub(crate) fn find_profile_by_name(
+    store: &SkillStore,
+    name: &str,
+) -> Result<Option<ProfileRecord>> {
+    Ok(store
+        .list_profiles()?
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name.trim())))
+}
+
+#[cfg(test)]
+#[path = "tests/profiles.rs"]
+mod tests;
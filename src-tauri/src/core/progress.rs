@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Receives `(stage, current, total)` updates from long-running core steps.
+pub type ProgressReporter = Arc<dyn Fn(&str, u64, Option<u64>) + Send + Sync>;
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProgressReporter>> = const { RefCell::new(None) };
+}
+
+/// Run `work` with `reporter` installed for the current thread, so clone and copy steps deep
+/// inside the installer can report progress without threading a callback through every call.
+pub fn with_reporter<T>(reporter: ProgressReporter, work: impl FnOnce() -> T) -> T {
+    struct Restore(Option<ProgressReporter>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(reporter));
+    let _restore = Restore(previous);
+    work()
+}
+
+/// The reporter installed on this thread, if any. Worker threads spawned by a step should
+/// take it before spawning and report through the returned handle.
+pub fn current() -> Option<ProgressReporter> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Parse a git `--progress` line such as `Receiving objects:  45% (450/1000), 1.2 MiB`.
+pub(crate) fn parse_git_progress(line: &str) -> Option<(&'static str, u64, u64)> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let (label, rest) = line.split_once(':')?;
+    let stage = match label.trim() {
+        "Receiving objects" => "cloning",
+        "Resolving deltas" => "resolving_deltas",
+        "Updating files" => "checking_out",
+        _ => return None,
+    };
+    let counts = rest.split_once('(')?.1.split_once(')')?.0;
+    let (done, total) = counts.split_once('/')?;
+    Some((stage, done.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+#[path = "tests/progress.rs"]
+mod tests;
@@ -0,0 +1,448 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::installer::{install_git_skill_with_options, InstallOptions};
+use super::machine_overrides::MachineOverrides;
+use super::skill_executables::ensure_sync_allowed;
+use super::skill_files::ensure_plain_name;
+use super::skill_lock::lock_skill;
+use super::skill_store::{ProjectTargetRecord, SkillProjection, SkillRecord, SkillStore};
+use super::sync_engine::{remove_path_any, sync_dir_for_tool_with_overwrite};
+use super::tool_adapters::{adapter_by_key, default_tool_adapters};
+
+/// Looked up in the project root in this order; the first one found is used.
+pub const PROJECT_CONFIG_FILES: [&str; 2] = [".skillshub.toml", ".skillshub.json"];
+
+/// `"pdf"` names a managed skill, `"https://github.com/…"` is a source to install from; the
+/// table form gives both.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum SkillEntry {
+    Spec(String),
+    Table {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        source: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    tools: Vec<String>,
+    #[serde(default)]
+    skills: Vec<SkillEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RequiredSkill {
+    pub name: String,
+    /// Git URL to install from when no managed skill matches.
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectConfig {
+    pub path: PathBuf,
+    /// Tool keys; empty means every tool whose directory exists in the project.
+    pub tools: Vec<String>,
+    pub skills: Vec<RequiredSkill>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementState {
+    /// No managed skill matches.
+    NotInstalled,
+    /// Managed, but missing from some of the project's tool dirs.
+    NotSynced,
+    Synced,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RequirementStatus {
+    pub name: String,
+    pub source: Option<String>,
+    pub skill_id: Option<String>,
+    pub state: RequirementState,
+    /// Tools the skill still has to be synced to in this project.
+    pub missing_tools: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProjectScan {
+    pub project: String,
+    pub config_path: String,
+    pub tools: Vec<String>,
+    pub skills: Vec<RequirementStatus>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProjectApplyFailure {
+    pub skill: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ProjectApplyReport {
+    /// Names of the skills installed from their source.
+    pub installed: Vec<String>,
+    pub synced: Vec<ProjectTargetRecord>,
+    pub failed: Vec<ProjectApplyFailure>,
+    /// Entries, or `<skill>:<tool>` syncs, this machine's overrides leave out.
+    pub skipped: Vec<String>,
+}
+
+/// Parse a config file; `.json` files are JSON, anything else TOML. Skill names become dirs in
+/// the project and the Central Repo, so each must be a plain directory name.
+pub fn parse_project_config(path: &Path, text: &str) -> Result<ProjectConfig> {
+    let raw: ConfigFile = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(text).with_context(|| format!("parse {:?}", path))?
+    } else {
+        toml::from_str(text).with_context(|| format!("parse {:?}", path))?
+    };
+    for tool in &raw.tools {
+        if adapter_by_key(tool).is_none() {
+            anyhow::bail!("unknown tool {:?} in {:?}", tool, path);
+        }
+    }
+    let mut skills = Vec::new();
+    for entry in raw.skills {
+        let (name, source) = match entry {
+            SkillEntry::Spec(spec) if is_source(&spec) => (None, Some(spec)),
+            SkillEntry::Spec(spec) => (Some(spec), None),
+            SkillEntry::Table { name, source } => (name, source),
+        };
+        let name = name
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .or_else(|| source.as_deref().and_then(source_skill_name))
+            .ok_or_else(|| anyhow::anyhow!("skill entry without a name or source in {:?}", path))?;
+        ensure_plain_name(&name)?;
+        skills.push(RequiredSkill {
+            name,
+            source: source.map(|s| s.trim().to_string()),
+        });
+    }
+    Ok(ProjectConfig {
+        path: path.to_path_buf(),
+        tools: raw.tools,
+        skills,
+    })
+}
+
+pub fn read_project_config(project: &Path) -> Result<ProjectConfig> {
+    let Some(path) = PROJECT_CONFIG_FILES
+        .iter()
+        .map(|name| project.join(name))
+        .find(|path| path.is_file())
+    else {
+        anyhow::bail!(
+            "no {} found in {:?}",
+            PROJECT_CONFIG_FILES.join(" or "),
+            project
+        );
+    };
+    let text = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+    parse_project_config(&path, &text)
+}
+
+fn is_source(spec: &str) -> bool {
+    spec.contains("://") || spec.starts_with("git@")
+}
+
+/// Last path segment of a git URL, without `.git`: the folder for GitHub folder URLs, else the
+/// repository.
+fn source_skill_name(source: &str) -> Option<String> {
+    let name = source
+        .trim()
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()?
+        .trim_end_matches(".git");
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn same_source(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.trim()
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_ascii_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// The managed skill installed from the entry's source, else the one with its name.
+fn find_managed<'a>(
+    skills: &'a [SkillRecord],
+    required: &RequiredSkill,
+) -> Option<&'a SkillRecord> {
+    required
+        .source
+        .as_deref()
+        .and_then(|source| {
+            skills.iter().find(|s| {
+                s.source_ref
+                    .as_deref()
+                    .is_some_and(|r| same_source(r, source))
+            })
+        })
+        .or_else(|| skills.iter().find(|s| s.name == required.name))
+}
+
+/// Tools whose directory (e.g. `.claude`) exists in the project.
+pub fn detect_project_tools(project: &Path) -> Vec<String> {
+    default_tool_adapters()
+        .into_iter()
+        .filter(|adapter| project.join(adapter.relative_detect_dir).is_dir())
+        .map(|adapter| adapter.id.as_key().to_string())
+        .collect()
+}
+
+/// The configured tools, or those detected in the project.
+fn project_tools(project: &Path, config: &ProjectConfig) -> Result<Vec<String>> {
+    if !config.tools.is_empty() {
+        return Ok(config.tools.clone());
+    }
+    let tools = detect_project_tools(project);
+    if tools.is_empty() {
+        anyhow::bail!(
+            "{:?} lists no tools and the project has no tool directories",
+            config.path
+        );
+    }
+    Ok(tools)
+}
+
+fn canonical_project(project: &Path) -> Result<PathBuf> {
+    project
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", project))
+}
+
+/// Which of the skills the project's config requires are installed and synced into it.
+pub fn scan_project(store: &SkillStore, project: &Path) -> Result<ProjectScan> {
+    let project = canonical_project(project)?;
+    let config = read_project_config(&project)?;
+    let tools = project_tools(&project, &config)?;
+    let key = project.to_string_lossy().to_string();
+    let targets = store.list_project_targets(&key)?;
+    let managed = store.list_skills_with(SkillProjection::Summary)?;
+    let skills = config
+        .skills
+        .iter()
+        .map(|required| {
+            let skill = find_managed(&managed, required);
+            let missing_tools: Vec<String> = tools
+                .iter()
+                .filter(|tool| {
+                    !skill.is_some_and(|skill| {
+                        targets.iter().any(|t| {
+                            t.skill_id == skill.id
+                                && &t.tool == *tool
+                                && std::fs::symlink_metadata(&t.target_path).is_ok()
+                        })
+                    })
+                })
+                .cloned()
+                .collect();
+            let state = match skill {
+                None => RequirementState::NotInstalled,
+                Some(_) if !missing_tools.is_empty() => RequirementState::NotSynced,
+                Some(_) => RequirementState::Synced,
+            };
+            RequirementStatus {
+                name: required.name.clone(),
+                source: required.source.clone(),
+                skill_id: skill.map(|s| s.id.clone()),
+                state,
+                missing_tools,
+            }
+        })
+        .collect();
+    Ok(ProjectScan {
+        project: key,
+        config_path: config.path.to_string_lossy().to_string(),
+        tools,
+        skills,
+    })
+}
+
+/// Install what the project's config requires and is not managed yet, then sync every required
+/// skill into the project's tool dirs. A skill that fails is reported and the rest still go on.
+pub fn apply_project_config<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    project: &Path,
+    options: InstallOptions,
+    allow_executables: bool,
+    now: i64,
+) -> Result<ProjectApplyReport> {
+    let scan = scan_project(store, project)?;
+    let project = PathBuf::from(&scan.project);
+    let mut report = ProjectApplyReport::default();
+    let overrides = MachineOverrides::load(store)?;
+    for mut status in scan.skills {
+        if status.state == RequirementState::Synced {
+            continue;
+        }
+        if overrides.skips_skill(&status.name) {
+            report.skipped.push(status.name);
+            continue;
+        }
+        let (skipped, missing): (Vec<String>, Vec<String>) = status
+            .missing_tools
+            .into_iter()
+            .partition(|tool| overrides.skips_tool(&status.name, tool));
+        report.skipped.extend(
+            skipped
+                .iter()
+                .map(|tool| format!("{}:{}", status.name, tool)),
+        );
+        if missing.is_empty() && !skipped.is_empty() {
+            // Every tool it still needs is skipped here; do not install it for nothing.
+            continue;
+        }
+        status.missing_tools = missing;
+        let name = status.name.clone();
+        if let Err(err) = apply_requirement(
+            app,
+            store,
+            &project,
+            status,
+            options,
+            allow_executables,
+            now,
+            &mut report,
+        ) {
+            report.failed.push(ProjectApplyFailure {
+                skill: name,
+                error: format!("{:#}", err),
+            });
+        }
+    }
+    log::info!(
+        "[project] {:?}: {} installed, {} synced, {} failed",
+        project,
+        report.installed.len(),
+        report.synced.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_requirement<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    project: &Path,
+    status: RequirementStatus,
+    options: InstallOptions,
+    allow_executables: bool,
+    now: i64,
+    report: &mut ProjectApplyReport,
+) -> Result<()> {
+    let skill_id = match (status.skill_id, status.source) {
+        (Some(id), _) => id,
+        (None, Some(source)) => {
+            let installed = install_git_skill_with_options(
+                app,
+                store,
+                &source,
+                Some(status.name.clone()),
+                options,
+            )?;
+            report.installed.push(installed.name);
+            installed.skill_id
+        }
+        (None, None) => anyhow::bail!("not a managed skill, and the config gives no source"),
+    };
+    let skill = store
+        .get_skill_by_id(&skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    ensure_sync_allowed(store, &skill, allow_executables)?;
+    for tool in &status.missing_tools {
+        report
+            .synced
+            .push(sync_skill_to_project(store, &skill, project, tool, now)?);
+    }
+    Ok(())
+}
+
+/// Sync `skill` into `<project>/<tool skills dir>/<name>`. Tools sharing a skills dir share the
+/// target: one already recorded for another tool is reused.
+pub fn sync_skill_to_project(
+    store: &SkillStore,
+    skill: &SkillRecord,
+    project: &Path,
+    tool: &str,
+    now: i64,
+) -> Result<ProjectTargetRecord> {
+    ensure_plain_name(&skill.name)?;
+    let _lock = lock_skill(&skill.id, &format!("sync:{}", tool))?;
+    let adapter = adapter_by_key(tool).ok_or_else(|| anyhow::anyhow!("unknown tool: {}", tool))?;
+    let key = project.to_string_lossy().to_string();
+    let target = project.join(adapter.relative_skills_dir).join(&skill.name);
+    let target_path = target.to_string_lossy().to_string();
+    let shared = store
+        .list_project_targets(&key)?
+        .into_iter()
+        .find(|t| t.skill_id == skill.id && t.target_path == target_path)
+        .filter(|_| std::fs::symlink_metadata(&target).is_ok());
+    let mode = match shared {
+        Some(existing) => existing.mode,
+        None => {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+            }
+            sync_dir_for_tool_with_overwrite(tool, Path::new(&skill.central_path), &target, false)?
+                .mode_used
+                .as_key()
+                .to_string()
+        }
+    };
+    let record = ProjectTargetRecord {
+        skill_id: skill.id.clone(),
+        project: key,
+        tool: tool.to_string(),
+        target_path,
+        mode,
+        synced_at: now,
+    };
+    store.upsert_project_target(&record)?;
+    Ok(record)
+}
+
+/// Drop the skill's target for `tool` from the project. Its files are removed unless another
+/// tool's target in the project still uses them.
+pub fn unsync_skill_from_project(
+    store: &SkillStore,
+    skill_id: &str,
+    project: &Path,
+    tool: &str,
+) -> Result<Option<ProjectTargetRecord>> {
+    let key = project.to_string_lossy().to_string();
+    let targets = store.list_project_targets(&key)?;
+    let Some(target) = targets
+        .iter()
+        .find(|t| t.skill_id == skill_id && t.tool == tool)
+    else {
+        return Ok(None);
+    };
+    let shared = targets
+        .iter()
+        .any(|t| t.skill_id == skill_id && t.tool != tool && t.target_path == target.target_path);
+    if !shared {
+        remove_path_any(Path::new(&target.target_path))?;
+    }
+    store.delete_project_target(skill_id, &key, tool)?;
+    Ok(Some(target.clone()))
+}
+
+#[cfg(test)]
+#[path = "tests/project_config.rs"]
+mod tests;
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+
+use super::skill_store::SkillStore;
+
+/// Directory to open for a skill: its central copy, or the synced target for `tool`.
+pub fn resolve_reveal_path(
+    store: &SkillStore,
+    skill_id: &str,
+    tool: Option<&str>,
+) -> Result<PathBuf> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let path = match tool {
+        Some(tool) => {
+            let target = store
+                .get_skill_target(skill_id, tool)?
+                .ok_or_else(|| anyhow::anyhow!("skill is not synced to {}", tool))?;
+            PathBuf::from(target.target_path)
+        }
+        None => PathBuf::from(skill.central_path),
+    };
+    if !path.exists() {
+        anyhow::bail!("path not found: {:?}", path);
+    }
+    Ok(path)
+}
+
+/// Launch the platform terminal with `dir` as its working directory.
+pub fn open_terminal(dir: &Path) -> Result<()> {
+    let mut last_err = None;
+    for mut cmd in terminal_commands(dir) {
+        match cmd.current_dir(dir).spawn() {
+            Ok(_) => return Ok(()),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    match last_err {
+        Some(err) => Err(anyhow::anyhow!("no terminal could be started: {}", err)),
+        None => anyhow::bail!("no terminal available on this platform"),
+    }
+}
+
+/// Candidates tried in order; the first one that spawns wins.
+fn terminal_commands(dir: &Path) -> Vec<Command> {
+    let mut out = Vec::new();
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        cmd.args(["-a", "Terminal"]).arg(dir);
+        out.push(cmd);
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("wt");
+        cmd.arg("-d").arg(dir);
+        out.push(cmd);
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", "", "cmd", "/K", "cd", "/d"])
+            .arg(dir);
+        out.push(cmd);
+    } else {
+        if let Ok(term) = std::env::var("TERMINAL") {
+            out.push(Command::new(term));
+        }
+        for term in [
+            "x-terminal-emulator",
+            "gnome-terminal",
+            "konsole",
+            "xfce4-terminal",
+            "xterm",
+        ] {
+            out.push(Command::new(term));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "tests/reveal.rs"]
+mod tests;
@@ -0,0 +1,345 @@
+use std::collections::HashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::cache_cleanup::cleanup_stale_git_caches;
+use super::setup_snapshots::record_automatic_snapshot;
+use super::skill_store::{JobRunRecord, ScheduledJobRecord, SkillStore};
+use super::team_profiles::check_due_team_profiles;
+use super::tray::check_skill_updates;
+
+/// How often the scheduler thread looks for due jobs.
+const SCHEDULER_POLL: Duration = Duration::from_secs(30);
+/// Runs kept per job.
+const MAX_JOB_RUNS: usize = 50;
+/// Up to this share of the interval is added to each next run, so jobs drift apart instead of
+/// all firing together after a restart.
+const JITTER_DIVISOR: i64 = 10;
+const MIN_INTERVAL_SECS: i64 = 60;
+const MAX_INTERVAL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Runs the job at the given time and reports what it did.
+type JobFn = dyn Fn(i64) -> Result<String> + Send + Sync;
+
+#[derive(Clone)]
+struct Job {
+    id: &'static str,
+    description: &'static str,
+    default_interval_secs: i64,
+    run: Arc<JobFn>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub description: String,
+    pub enabled: bool,
+    pub interval_secs: i64,
+    pub default_interval_secs: i64,
+    /// `None` until the job first runs, which it then does on the next poll.
+    pub next_run_at: Option<i64>,
+    pub running: bool,
+    pub last_run: Option<JobRunRecord>,
+}
+
+/// Periodic background work. Jobs are registered at startup; their schedules and run history
+/// are kept in the database so intervals carry over restarts.
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    jobs: Arc<Mutex<Vec<Job>>>,
+    running: Arc<Mutex<HashSet<&'static str>>>,
+}
+
+impl Scheduler {
+    pub fn register(
+        &self,
+        id: &'static str,
+        description: &'static str,
+        default_interval_secs: i64,
+        run: impl Fn(i64) -> Result<String> + Send + Sync + 'static,
+    ) {
+        self.jobs_guard().push(Job {
+            id,
+            description,
+            default_interval_secs,
+            run: Arc::new(run),
+        });
+    }
+
+    pub fn list(&self, store: &SkillStore) -> Result<Vec<JobStatus>> {
+        let jobs = self.jobs_guard().clone();
+        jobs.iter().map(|job| self.status(store, job)).collect()
+    }
+
+    /// Newest first.
+    pub fn history(
+        &self,
+        store: &SkillStore,
+        job_id: &str,
+        limit: usize,
+    ) -> Result<Vec<JobRunRecord>> {
+        let job = self.job(job_id)?;
+        store.list_job_runs(job.id, limit)
+    }
+
+    /// Run the job now, whatever its schedule, and schedule the next run from now.
+    pub fn run_now(&self, store: &SkillStore, job_id: &str, now: i64) -> Result<JobRunRecord> {
+        let job = self.job(job_id)?;
+        self.execute(store, &job, true, now)?
+            .ok_or_else(|| anyhow::anyhow!("job is already running: {}", job_id))
+    }
+
+    /// Turn the job on or off and set its interval; `None` goes back to the default. A new
+    /// interval counts from now.
+    pub fn set_schedule(
+        &self,
+        store: &SkillStore,
+        job_id: &str,
+        enabled: bool,
+        interval_secs: Option<i64>,
+        now: i64,
+    ) -> Result<JobStatus> {
+        let job = self.job(job_id)?;
+        if let Some(secs) = interval_secs {
+            if !(MIN_INTERVAL_SECS..=MAX_INTERVAL_SECS).contains(&secs) {
+                anyhow::bail!(
+                    "interval seconds must be between {} and {}",
+                    MIN_INTERVAL_SECS,
+                    MAX_INTERVAL_SECS
+                );
+            }
+        }
+        let mut record = schedule_record(store, &job)?;
+        if record.interval_secs != interval_secs {
+            record.next_run_at = Some(next_run(
+                now,
+                interval_secs.unwrap_or(job.default_interval_secs),
+            ));
+        }
+        record.enabled = enabled;
+        record.interval_secs = interval_secs;
+        store.upsert_scheduled_job(&record)?;
+        self.status(store, &job)
+    }
+
+    /// Run every enabled job that is due, one after another.
+    pub fn run_due(&self, store: &SkillStore, now: i64) -> Result<Vec<JobRunRecord>> {
+        let jobs = self.jobs_guard().clone();
+        let mut runs = Vec::new();
+        for job in &jobs {
+            let record = schedule_record(store, job)?;
+            if !record.enabled || record.next_run_at.is_some_and(|at| at > now) {
+                continue;
+            }
+            if let Some(run) = self.execute(store, job, false, now)? {
+                runs.push(run);
+            }
+        }
+        Ok(runs)
+    }
+
+    /// Poll for due jobs on a background thread for the rest of the app's life.
+    pub fn start(&self, store: SkillStore) {
+        let scheduler = self.clone();
+        let spawned = std::thread::Builder::new()
+            .name("scheduler".to_string())
+            .spawn(move || loop {
+                if let Err(err) = scheduler.run_due(&store, now_ms()) {
+                    log::warn!("[scheduler] failed to run due jobs: {:#}", err);
+                }
+                std::thread::sleep(SCHEDULER_POLL);
+            });
+        if let Err(err) = spawned {
+            log::warn!("[scheduler] failed to start scheduler thread: {}", err);
+        }
+    }
+
+    /// `None` when the job is already running. A job that panics is recorded as a failed run.
+    fn execute(
+        &self,
+        store: &SkillStore,
+        job: &Job,
+        manual: bool,
+        now: i64,
+    ) -> Result<Option<JobRunRecord>> {
+        if !self.running_guard().insert(job.id) {
+            return Ok(None);
+        }
+        let running = RunningJob {
+            scheduler: self,
+            id: job.id,
+        };
+        let started = Instant::now();
+        let outcome = catch_unwind(AssertUnwindSafe(|| (job.run)(now)))
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("job panicked")));
+        drop(running);
+        let finished_at = now + started.elapsed().as_millis() as i64;
+
+        let (ok, message) = match outcome {
+            Ok(message) => (true, Some(message).filter(|m| !m.is_empty())),
+            Err(err) => {
+                log::warn!("[scheduler] job {} failed: {:#}", job.id, err);
+                (false, Some(format!("{:#}", err)))
+            }
+        };
+        let mut run = JobRunRecord {
+            id: 0,
+            job_id: job.id.to_string(),
+            manual,
+            started_at: now,
+            finished_at,
+            ok,
+            message,
+        };
+        run.id = store.insert_job_run(&run, MAX_JOB_RUNS)?;
+        let mut record = schedule_record(store, job)?;
+        let interval = record.interval_secs.unwrap_or(job.default_interval_secs);
+        record.next_run_at = Some(next_run(finished_at, interval));
+        store.upsert_scheduled_job(&record)?;
+        Ok(Some(run))
+    }
+
+    fn status(&self, store: &SkillStore, job: &Job) -> Result<JobStatus> {
+        let record = schedule_record(store, job)?;
+        Ok(JobStatus {
+            id: job.id.to_string(),
+            description: job.description.to_string(),
+            enabled: record.enabled,
+            interval_secs: record.interval_secs.unwrap_or(job.default_interval_secs),
+            default_interval_secs: job.default_interval_secs,
+            next_run_at: record.next_run_at,
+            running: self.running_guard().contains(job.id),
+            last_run: store.list_job_runs(job.id, 1)?.into_iter().next(),
+        })
+    }
+
+    fn job(&self, job_id: &str) -> Result<Job> {
+        self.jobs_guard()
+            .iter()
+            .find(|job| job.id == job_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("unknown job: {}", job_id))
+    }
+
+    fn jobs_guard(&self) -> MutexGuard<'_, Vec<Job>> {
+        self.jobs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn running_guard(&self) -> MutexGuard<'_, HashSet<&'static str>> {
+        self.running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Takes a job off the running set when dropped, so an early return cannot leave it stuck.
+struct RunningJob<'a> {
+    scheduler: &'a Scheduler,
+    id: &'static str,
+}
+
+impl Drop for RunningJob<'_> {
+    fn drop(&mut self) {
+        self.scheduler.running_guard().remove(self.id);
+    }
+}
+
+/// The jobs the app runs in the background.
+pub fn register_builtin_jobs<R: tauri::Runtime>(
+    scheduler: &Scheduler,
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) {
+    let (handle, job_store) = (app.clone(), store.clone());
+    scheduler.register(
+        "team_profile_checks",
+        "Pull team profiles whose check interval has passed and report drift",
+        10 * 60,
+        move |now| {
+            let (checked, drifted) = check_due_team_profiles(&handle, &job_store, now)?;
+            Ok(format!("{} checked, {} drifted", checked, drifted))
+        },
+    );
+    let job_store = store.clone();
+    scheduler.register(
+        "setup_snapshot",
+        "Record a setup snapshot when the last one is over a day old and the setup changed",
+        60 * 60,
+        move |now| {
+            Ok(match record_automatic_snapshot(&job_store, now)? {
+                Some(snapshot) => format!("recorded {} targets", snapshot.targets),
+                None => String::new(),
+            })
+        },
+    );
+    let (handle, job_store) = (app.clone(), store.clone());
+    scheduler.register(
+        "git_cache_cleanup",
+        "Remove git caches older than the cleanup age",
+        24 * 60 * 60,
+        move |_| {
+            let removed = cleanup_stale_git_caches(&handle, &job_store)?;
+            Ok(format!("{} removed", removed))
+        },
+    );
+    let (handle, job_store) = (app.clone(), store.clone());
+    scheduler.register(
+        "update_check",
+        "Look for newer content at the sources of git and local skills",
+        6 * 60 * 60,
+        move |now| {
+            let check = check_skill_updates(&handle, &job_store, now)?;
+            Ok(format!(
+                "{} checked, {} with updates, {} failed",
+                check.checked,
+                check.available.len(),
+                check.failed.len()
+            ))
+        },
+    );
+}
+
+fn schedule_record(store: &SkillStore, job: &Job) -> Result<ScheduledJobRecord> {
+    Ok(store
+        .list_scheduled_jobs()?
+        .into_iter()
+        .find(|r| r.job_id == job.id)
+        .unwrap_or_else(|| ScheduledJobRecord {
+            job_id: job.id.to_string(),
+            enabled: true,
+            interval_secs: None,
+            next_run_at: None,
+        }))
+}
+
+fn next_run(from: i64, interval_secs: i64) -> i64 {
+    from + interval_secs * 1000 + jitter_ms(interval_secs * 1000 / JITTER_DIVISOR)
+}
+
+fn jitter_ms(max: i64) -> i64 {
+    if max <= 0 {
+        return 0;
+    }
+    let mut bytes = [0u8; 8];
+    if getrandom::getrandom(&mut bytes).is_err() {
+        return 0;
+    }
+    (u64::from_le_bytes(bytes) % max as u64) as i64
+}
+
+fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
+
+#[cfg(test)]
+#[path = "tests/scheduler.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::skill_lint::relative_key;
+
+/// Larger files are skipped; credentials live in scripts and configs, not in bulk data.
+const MAX_SCANNED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// A line that looks like it holds a credential. The matched text itself is never kept, so
+/// findings are safe to log and show.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SecretFinding {
+    pub rule: &'static str,
+    /// Path relative to the skill root.
+    pub file: String,
+    pub line: usize,
+}
+
+impl SecretFinding {
+    pub fn describe(&self) -> String {
+        format!("{} ({}:{})", self.rule, self.file, self.line)
+    }
+}
+
+fn rules() -> &'static [(&'static str, Regex)] {
+    static RULES: OnceLock<Vec<(&'static str, Regex)>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        [
+            ("aws_access_key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+            (
+                "aws_secret_key",
+                r"(?i)aws_secret_access_key\s*[:=]\s*['\x22]?[A-Za-z0-9/+]{40}\b",
+            ),
+            (
+                "github_token",
+                r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})\b",
+            ),
+            (
+                "private_key",
+                r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY( BLOCK)?-----",
+            ),
+        ]
+        .into_iter()
+        .map(|(rule, pattern)| (rule, Regex::new(pattern).unwrap()))
+        .collect()
+    })
+}
+
+/// Text files under `path` checked line by line against a few well-known credential formats.
+/// Binary and oversized files are skipped.
+pub fn scan_skill_dir(path: &Path) -> Result<Vec<SecretFinding>> {
+    let mut findings = Vec::new();
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let size = entry
+            .metadata()
+            .with_context(|| format!("stat {:?}", entry.path()))?
+            .len();
+        if size > MAX_SCANNED_FILE_BYTES {
+            continue;
+        }
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read {:?}", entry.path()))?;
+        if bytes.contains(&0) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        let file = relative_key(path, entry.path());
+        for (idx, line) in text.lines().enumerate() {
+            for (rule, re) in rules() {
+                if re.is_match(line) {
+                    findings.push(SecretFinding {
+                        rule,
+                        file: file.clone(),
+                        line: idx + 1,
+                    });
+                }
+            }
+        }
+    }
+    Ok(findings)
+}
+
+#[cfg(test)]
+#[path = "tests/secret_scan.rs"]
+mod tests;
@@ -0,0 +1,200 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::cloud_sync::is_offline;
+use super::skill_store::{SkillProjection, SkillStore};
+
+/// Emitted once the startup pass finished; the UI takes the summary with
+/// `take_self_repair_summary` so one produced before it loaded is not lost.
+pub const SELF_REPAIR_EVENT: &str = "self-repair-summary";
+
+pub const STATUS_OK: &str = "ok";
+/// Skill: central directory gone. Target: path gone.
+pub const STATUS_MISSING: &str = "missing";
+/// Skill directory exists but its SKILL.md cannot be read.
+pub const STATUS_UNREADABLE: &str = "unreadable";
+/// Target is a symlink whose destination no longer exists.
+pub const STATUS_BROKEN: &str = "broken";
+/// SKILL.md is a cloud placeholder whose content has not been downloaded to this machine.
+pub const STATUS_OFFLINE: &str = "offline";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RepairIssue {
+    pub skill_id: String,
+    pub skill_name: String,
+    /// Set for target issues.
+    pub tool: Option<String>,
+    pub path: String,
+    pub status: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SelfRepairSummary {
+    pub checked_skills: usize,
+    pub checked_targets: usize,
+    pub issues: Vec<RepairIssue>,
+    /// Skills and targets flagged earlier that are fine again.
+    pub recovered: usize,
+}
+
+/// Latest summary, waiting for the UI to pick it up.
+#[derive(Clone, Default)]
+pub struct SelfRepairState {
+    pending: Arc<Mutex<Option<SelfRepairSummary>>>,
+}
+
+impl SelfRepairState {
+    pub fn put(&self, summary: SelfRepairSummary) {
+        *self.lock() = Some(summary);
+    }
+
+    pub fn take(&self) -> Option<SelfRepairSummary> {
+        self.lock().take()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<SelfRepairSummary>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Check every skill and target against the filesystem and record what was found in their
+/// `status`. Only changed statuses are written. Nothing is deleted: the user decides whether to
+/// re-sync, reinstall or remove.
+pub fn run_self_repair(store: &SkillStore) -> Result<SelfRepairSummary> {
+    let mut summary = SelfRepairSummary::default();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        summary.checked_skills += 1;
+        let status = skill_status(Path::new(&skill.central_path));
+        record(&mut summary, &skill.status, status, || {
+            store.set_skill_status(&skill.id, status)
+        })?;
+        if status != STATUS_OK {
+            summary.issues.push(RepairIssue {
+                skill_id: skill.id.clone(),
+                skill_name: skill.name.clone(),
+                tool: None,
+                path: skill.central_path.clone(),
+                status: status.to_string(),
+            });
+        }
+
+        for target in store.list_skill_targets(&skill.id)? {
+            summary.checked_targets += 1;
+            let status = target_status(Path::new(&target.target_path));
+            let last_error = (status != STATUS_OK).then(|| format!("{} at startup", status));
+            record(&mut summary, &target.status, status, || {
+                store.set_skill_target_status(
+                    &skill.id,
+                    &target.tool,
+                    status,
+                    last_error.as_deref(),
+                )
+            })?;
+            if status != STATUS_OK {
+                summary.issues.push(RepairIssue {
+                    skill_id: skill.id.clone(),
+                    skill_name: skill.name.clone(),
+                    tool: Some(target.tool.clone()),
+                    path: target.target_path.clone(),
+                    status: status.to_string(),
+                });
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Run the pass, keep the summary for the UI and announce it.
+pub fn run_startup_self_repair<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    state: &SelfRepairState,
+) {
+    match run_self_repair(store) {
+        Ok(summary) => {
+            if !summary.issues.is_empty() || summary.recovered > 0 {
+                log::info!(
+                    "[self-repair] {} issues, {} recovered ({} skills, {} targets checked)",
+                    summary.issues.len(),
+                    summary.recovered,
+                    summary.checked_skills,
+                    summary.checked_targets
+                );
+            }
+            state.put(summary.clone());
+            if let Err(err) = app.emit(SELF_REPAIR_EVENT, &summary) {
+                log::warn!(
+                    "[self-repair] failed to emit {}: {}",
+                    SELF_REPAIR_EVENT,
+                    err
+                );
+            }
+        }
+        Err(err) => log::warn!("[self-repair] pass failed: {:#}", err),
+    }
+}
+
+/// Writes `status` when it differs from `current`. Only statuses set by this pass are cleared
+/// again, so unrelated ones (e.g. a failed sync) survive a healthy filesystem.
+fn record(
+    summary: &mut SelfRepairSummary,
+    current: &str,
+    status: &str,
+    write: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    if current == status {
+        return Ok(());
+    }
+    if status == STATUS_OK {
+        if !is_repair_status(current) {
+            return Ok(());
+        }
+        summary.recovered += 1;
+    }
+    write()
+}
+
+fn is_repair_status(status: &str) -> bool {
+    [
+        STATUS_MISSING,
+        STATUS_UNREADABLE,
+        STATUS_BROKEN,
+        STATUS_OFFLINE,
+    ]
+    .contains(&status)
+}
+
+fn skill_status(dir: &Path) -> &'static str {
+    if !dir.is_dir() {
+        return STATUS_MISSING;
+    }
+    let skill_md = dir.join("SKILL.md");
+    // Checked before reading: reading a placeholder starts, and waits for, its download.
+    if is_offline(&skill_md) {
+        return STATUS_OFFLINE;
+    }
+    match std::fs::read(&skill_md) {
+        Ok(_) => STATUS_OK,
+        Err(_) => STATUS_UNREADABLE,
+    }
+}
+
+fn target_status(path: &Path) -> &'static str {
+    match std::fs::symlink_metadata(path) {
+        Err(_) => STATUS_MISSING,
+        Ok(meta) if meta.file_type().is_symlink() && std::fs::metadata(path).is_err() => {
+            STATUS_BROKEN
+        }
+        Ok(_) => STATUS_OK,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/self_repair.rs"]
+mod tests;
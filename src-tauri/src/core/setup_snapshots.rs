@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::skill_store::{SetupSnapshotRecord, SkillProjection, SkillStore};
+use super::skill_sync::STATUS_DISABLED;
+
+/// Automatic snapshots are taken at most this often, and only when the setup changed.
+const AUTOMATIC_SNAPSHOT_INTERVAL_MS: i64 = 24 * 60 * 60 * 1000;
+/// Older automatic snapshots are dropped; the user's own are kept until deleted.
+const MAX_AUTOMATIC_SNAPSHOTS: usize = 30;
+
+/// One skill synced to one tool, globally or in a project.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SnapshotTarget {
+    pub skill: String,
+    pub tool: String,
+    /// Project directory, for targets inside a project.
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub disabled: bool,
+    /// Content hash of the skill at the time.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+/// What was active where.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupState {
+    pub targets: Vec<SnapshotTarget>,
+    /// Active profile name by tool.
+    #[serde(default)]
+    pub active_profiles: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SetupSnapshot {
+    #[serde(flatten)]
+    pub record: SetupSnapshotRecord,
+    pub targets: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TargetChange {
+    pub before: SnapshotTarget,
+    pub after: SnapshotTarget,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActiveProfileChange {
+    pub tool: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SetupDiff {
+    pub added: Vec<SnapshotTarget>,
+    pub removed: Vec<SnapshotTarget>,
+    /// Targets present in both whose content or enabled state differs.
+    pub changed: Vec<TargetChange>,
+    pub active_profiles: Vec<ActiveProfileChange>,
+}
+
+/// The setup as it is now: every global and project target, and the active profiles.
+pub fn current_setup(store: &SkillStore) -> Result<SetupState> {
+    let mut targets = Vec::new();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        for target in store.list_skill_targets(&skill.id)? {
+            targets.push(SnapshotTarget {
+                skill: skill.name.clone(),
+                tool: target.tool,
+                project: None,
+                disabled: target.status == STATUS_DISABLED,
+                content_hash: skill.content_hash.clone(),
+            });
+        }
+        for target in store.list_skill_project_targets(&skill.id)? {
+            targets.push(SnapshotTarget {
+                skill: skill.name.clone(),
+                tool: target.tool,
+                project: Some(target.project),
+                disabled: false,
+                content_hash: skill.content_hash.clone(),
+            });
+        }
+    }
+    targets.sort();
+    let names: BTreeMap<String, String> = store
+        .list_profiles()?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+    let active_profiles = store
+        .list_active_profiles()?
+        .into_iter()
+        .filter_map(|(tool, id)| names.get(&id).map(|name| (tool, name.clone())))
+        .collect();
+    Ok(SetupState {
+        targets,
+        active_profiles,
+    })
+}
+
+pub fn create_setup_snapshot(
+    store: &SkillStore,
+    label: Option<&str>,
+    now: i64,
+) -> Result<SetupSnapshot> {
+    let label = label.map(str::trim).filter(|l| !l.is_empty());
+    save_snapshot(store, &current_setup(store)?, label, false, now)
+}
+
+pub fn list_setup_snapshots(store: &SkillStore) -> Result<Vec<SetupSnapshot>> {
+    store
+        .list_setup_snapshots()?
+        .into_iter()
+        .map(|record| {
+            let targets = parse_state(&record)?.targets.len();
+            Ok(SetupSnapshot { record, targets })
+        })
+        .collect()
+}
+
+pub fn delete_setup_snapshot(store: &SkillStore, snapshot_id: &str) -> Result<()> {
+    if !store.delete_setup_snapshot(snapshot_id)? {
+        anyhow::bail!("snapshot not found");
+    }
+    Ok(())
+}
+
+/// What changed from snapshot `from` to snapshot `to`, or to the current setup when `to` is
+/// `None`.
+pub fn diff_setup_snapshots(store: &SkillStore, from: &str, to: Option<&str>) -> Result<SetupDiff> {
+    let snapshots = store.list_setup_snapshots()?;
+    let load = |id: &str| {
+        snapshots
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| anyhow::anyhow!("snapshot not found"))
+            .and_then(parse_state)
+    };
+    let before = load(from)?;
+    let after = match to {
+        Some(id) => load(id)?,
+        None => current_setup(store)?,
+    };
+    Ok(diff_setups(&before, &after))
+}
+
+/// Take an automatic snapshot when the last one is older than a day and the setup has changed
+/// since, then drop the oldest automatic ones beyond the limit.
+pub fn record_automatic_snapshot(store: &SkillStore, now: i64) -> Result<Option<SetupSnapshot>> {
+    let snapshots = store.list_setup_snapshots()?;
+    let state = current_setup(store)?;
+    if let Some(latest) = snapshots.first() {
+        if now - latest.created_at < AUTOMATIC_SNAPSHOT_INTERVAL_MS || parse_state(latest)? == state
+        {
+            return Ok(None);
+        }
+    }
+    let snapshot = save_snapshot(store, &state, None, true, now)?;
+    for old in snapshots
+        .iter()
+        .filter(|s| s.automatic)
+        .skip(MAX_AUTOMATIC_SNAPSHOTS - 1)
+    {
+        store.delete_setup_snapshot(&old.id)?;
+    }
+    Ok(Some(snapshot))
+}
+
+fn save_snapshot(
+    store: &SkillStore,
+    state: &SetupState,
+    label: Option<&str>,
+    automatic: bool,
+    now: i64,
+) -> Result<SetupSnapshot> {
+    let record = SetupSnapshotRecord {
+        id: Uuid::new_v4().to_string(),
+        label: label.map(str::to_string),
+        automatic,
+        created_at: now,
+        state: serde_json::to_string(state)?,
+    };
+    store.insert_setup_snapshot(&record)?;
+    Ok(SetupSnapshot {
+        record,
+        targets: state.targets.len(),
+    })
+}
+
+fn parse_state(record: &SetupSnapshotRecord) -> Result<SetupState> {
+    Ok(serde_json::from_str(&record.state)?)
+}
+
+fn diff_setups(before: &SetupState, after: &SetupState) -> SetupDiff {
+    let index = |state: &SetupState| -> BTreeMap<_, SnapshotTarget> {
+        state
+            .targets
+            .iter()
+            .map(|t| {
+                (
+                    (t.skill.clone(), t.tool.clone(), t.project.clone()),
+                    t.clone(),
+                )
+            })
+            .collect()
+    };
+    let (old, new) = (index(before), index(after));
+    let mut diff = SetupDiff::default();
+    for (key, target) in &old {
+        match new.get(key) {
+            None => diff.removed.push(target.clone()),
+            Some(now) if now != target => diff.changed.push(TargetChange {
+                before: target.clone(),
+                after: now.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    diff.added = new
+        .iter()
+        .filter(|(key, _)| !old.contains_key(*key))
+        .map(|(_, target)| target.clone())
+        .collect();
+    let tools: BTreeSet<&String> = before
+        .active_profiles
+        .keys()
+        .chain(after.active_profiles.keys())
+        .collect();
+    for tool in tools {
+        let (was, is) = (
+            before.active_profiles.get(tool),
+            after.active_profiles.get(tool),
+        );
+        if was != is {
+            diff.active_profiles.push(ActiveProfileChange {
+                tool: tool.clone(),
+                before: was.cloned(),
+                after: is.cloned(),
+            });
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+#[path = "tests/setup_snapshots.rs"]
+mod tests;
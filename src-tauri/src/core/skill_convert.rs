@@ -0,0 +1,276 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::cursor_rules::CursorRule;
+use super::installer::refresh_after_edit;
+use super::skill_files::{is_binary_file, write_file_atomic};
+use super::skill_lock::lock_skill;
+use super::skill_metadata::{split_frontmatter, SkillFrontmatter};
+use super::skill_store::SkillStore;
+use super::token_estimate::referenced_files;
+
+/// Linked files larger than this are named in a snippet rather than inlined.
+const SNIPPET_MAX_FILE_BYTES: u64 = 16 * 1024;
+/// Default snippet size; chat apps and issue trackers cut or reject much longer pastes.
+pub const SNIPPET_DEFAULT_MAX_BYTES: usize = 64 * 1024;
+
+/// Formats a skill can be rendered in for other ecosystems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillFormat {
+    /// The Agent Skills `SKILL.md` itself, for copying elsewhere.
+    SkillMd,
+    /// A Cursor `.mdc` rule; globs and `alwaysApply` come back from the `cursor-*` metadata
+    /// an imported rule carries.
+    CursorRule,
+    /// Plain markdown with a title, for pasting as a system or custom prompt.
+    Prompt,
+}
+
+impl SkillFormat {
+    /// File name used when the output path is a directory or the skill dir itself.
+    pub fn file_name(self, name: &str) -> String {
+        match self {
+            Self::SkillMd => "SKILL.md".to_string(),
+            Self::CursorRule => format!("{}.mdc", name),
+            Self::Prompt => format!("{}.prompt.md", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConvertedSkill {
+    pub format: SkillFormat,
+    pub path: String,
+    pub bytes: usize,
+    /// The file was written inside the skill, so its hash and copy targets were refreshed.
+    pub in_skill: bool,
+}
+
+/// Render the SKILL.md `text` of the skill `name` as `format`.
+pub fn render_skill(text: &str, name: &str, format: SkillFormat) -> Result<String> {
+    if format == SkillFormat::SkillMd {
+        return Ok(text.to_string());
+    }
+    let frontmatter = SkillFrontmatter::parse(text)
+        .map_err(|reason| anyhow::anyhow!("SKILL.md of {}: {}", name, reason))?;
+    let body = split_frontmatter(text)
+        .map(|(_, body)| body)
+        .unwrap_or(text)
+        .trim_matches('\n');
+    Ok(match format {
+        SkillFormat::CursorRule => to_cursor_rule(&frontmatter, body).to_mdc(),
+        _ => to_prompt(
+            &frontmatter,
+            frontmatter.name.as_deref().unwrap_or(name),
+            body,
+        ),
+    })
+}
+
+fn to_cursor_rule(frontmatter: &SkillFrontmatter, body: &str) -> CursorRule {
+    let metadata = |key: &str| match frontmatter.metadata.get(key) {
+        Some(serde_yaml::Value::String(value)) => Some(value.clone()),
+        Some(serde_yaml::Value::Bool(value)) => Some(value.to_string()),
+        _ => None,
+    };
+    CursorRule {
+        description: frontmatter.description.clone(),
+        globs: metadata("cursor-globs")
+            .map(|globs| {
+                globs
+                    .split(',')
+                    .map(|glob| glob.trim().to_string())
+                    .filter(|glob| !glob.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        always_apply: metadata("cursor-always-apply").as_deref() == Some("true"),
+        body: body.to_string(),
+    }
+}
+
+/// A title and the description, unless the body already opens with its own title.
+fn to_prompt(frontmatter: &SkillFrontmatter, name: &str, body: &str) -> String {
+    let mut prompt = String::new();
+    if !body.starts_with("# ") {
+        prompt.push_str(&format!("# {}\n\n", name));
+        if let Some(description) = &frontmatter.description {
+            prompt.push_str(&format!("{}\n\n", description.trim()));
+        }
+    }
+    prompt.push_str(body);
+    prompt.push('\n');
+    prompt
+}
+
+/// Convert a managed skill to `format`. Without `output` the file is written into the skill dir
+/// (and propagated like any edit); an existing directory as `output` gets the default file
+/// name, anything else is the file to write.
+pub fn convert_skill(
+    store: &SkillStore,
+    skill_id: &str,
+    format: SkillFormat,
+    output: Option<&Path>,
+) -> Result<ConvertedSkill> {
+    let _lock = lock_skill(skill_id, "convert")?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let central_path = PathBuf::from(&record.central_path);
+    let skill_md = central_path.join("SKILL.md");
+    let text =
+        std::fs::read_to_string(&skill_md).with_context(|| format!("read {:?}", skill_md))?;
+    let rendered = render_skill(&text, &record.name, format)?;
+
+    let file_name = format.file_name(&record.name);
+    let dest = match output {
+        Some(path) if path.is_dir() => path.join(file_name),
+        Some(path) => path.to_path_buf(),
+        None if format == SkillFormat::SkillMd => {
+            anyhow::bail!("the skill is already in SKILL.md format; choose an output path")
+        }
+        None => central_path.join(file_name),
+    };
+    let resolved = resolve_output_path(&dest)?;
+    let central = central_path
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", central_path))?;
+    let in_skill = resolved.starts_with(&central);
+    if in_skill && resolved == central.join("SKILL.md") {
+        anyhow::bail!("refusing to overwrite the skill's own SKILL.md");
+    }
+    write_file_atomic(&dest, rendered.as_bytes())?;
+    if in_skill {
+        refresh_after_edit(store, record, central_path)?;
+    }
+    log::info!("[convert] wrote {:?} as {:?}", dest, format);
+    Ok(ConvertedSkill {
+        format,
+        path: dest.to_string_lossy().to_string(),
+        bytes: rendered.len(),
+        in_skill,
+    })
+}
+
+/// `path` with links in the part that exists resolved, so that it compares with the skill dir
+/// whichever way it was spelled. Paths with `..` are refused.
+fn resolve_output_path(path: &Path) -> Result<PathBuf> {
+    if path.components().any(|c| c == Component::ParentDir) {
+        anyhow::bail!("INVALID_PATH|{}", path.display());
+    }
+    for existing in path.ancestors() {
+        let Ok(resolved) = existing.canonicalize() else {
+            continue;
+        };
+        return Ok(match path.strip_prefix(existing) {
+            Ok(rest) if !rest.as_os_str().is_empty() => resolved.join(rest),
+            _ => resolved,
+        });
+    }
+    Ok(path.to_path_buf())
+}
+
+/// A skill as one self-contained markdown document, for pasting into chats and docs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SkillSnippet {
+    pub markdown: String,
+    /// Linked files inlined after SKILL.md.
+    pub inlined: Vec<String>,
+    /// Linked files left out for being large, binary or past the size limit.
+    pub omitted: Vec<String>,
+    /// SKILL.md alone was over the limit and was cut.
+    pub truncated: bool,
+}
+
+/// SKILL.md (frontmatter included) followed by the small text files it links to, each in a
+/// fenced block under its path. `max_bytes` bounds the content; a file that does not fit is
+/// left out whole, and the notes saying what was cut or omitted come on top of the limit.
+pub fn render_skill_snippet(dir: &Path, max_bytes: usize) -> Result<SkillSnippet> {
+    let skill_md = dir.join("SKILL.md");
+    let text =
+        std::fs::read_to_string(&skill_md).with_context(|| format!("read {:?}", skill_md))?;
+    let mut snippet = SkillSnippet {
+        markdown: format!("{}\n", text.trim_end()),
+        ..SkillSnippet::default()
+    };
+    if snippet.markdown.len() > max_bytes {
+        let kept = truncate_at_line(&snippet.markdown, max_bytes).len();
+        let cut = snippet.markdown.len() - kept;
+        snippet.markdown.truncate(kept);
+        snippet.markdown.push_str(&format!(
+            "\n[... truncated: {} more bytes of SKILL.md ...]\n",
+            cut
+        ));
+        snippet.truncated = true;
+    }
+
+    let mut notes = Vec::new();
+    for rel in referenced_files(dir, &text) {
+        let path = dir.join(&rel);
+        let size = std::fs::metadata(&path)
+            .map(|m| m.len())
+            .unwrap_or(u64::MAX);
+        let content = if size > SNIPPET_MAX_FILE_BYTES || is_binary_file(&path) {
+            None
+        } else {
+            std::fs::read_to_string(&path).ok()
+        };
+        let section = content.map(|content| file_section(&rel, &content));
+        match section {
+            Some(section)
+                if !snippet.truncated && snippet.markdown.len() + section.len() <= max_bytes =>
+            {
+                snippet.markdown.push_str(&section);
+                snippet.inlined.push(rel);
+            }
+            _ => {
+                notes.push(format!("`{}`", rel));
+                snippet.omitted.push(rel);
+            }
+        }
+    }
+    if !notes.is_empty() {
+        snippet
+            .markdown
+            .push_str(&format!("\n> Not included: {}\n", notes.join(", ")));
+    }
+    Ok(snippet)
+}
+
+/// The longest prefix of `text` within `max_bytes`, ending after a newline when there is one.
+fn truncate_at_line(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    match text[..end].rfind('\n') {
+        Some(newline) => &text[..newline + 1],
+        None => &text[..end],
+    }
+}
+
+/// `rel` as a heading and its content in a fence longer than any backtick run inside it.
+fn file_section(rel: &str, content: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let language = match Path::new(rel).extension().and_then(|ext| ext.to_str()) {
+        Some("md") => "markdown",
+        Some(ext) => ext,
+        None => "",
+    };
+    format!(
+        "\n---\n\n### {}\n\n{}{}\n{}\n{}\n",
+        rel,
+        fence,
+        language,
+        content.trim_end(),
+        fence
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/skill_convert.rs"]
+mod tests;
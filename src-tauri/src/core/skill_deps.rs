@@ -0,0 +1,378 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::installer::install_git_skill_with_force;
+use super::skill_metadata::{SkillMetadata, UNVERSIONED};
+use super::skill_store::{SkillDependencyRecord, SkillRecord, SkillStore};
+
+/// Upper bound on transitive installs triggered by a single request.
+const MAX_DEPENDENCY_INSTALLS: usize = 32;
+
+/// A dependency entry from skill metadata, e.g. `pdf-tools >= 1.2` or a git URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencySpec {
+    pub raw: String,
+    /// Skill name the dependency resolves to once installed.
+    pub name: String,
+    /// Set when the dependency can be installed from git.
+    pub git_url: Option<String>,
+    /// Anything after the source, e.g. `>= 1.2`.
+    pub constraint: Option<String>,
+}
+
+pub fn parse_dependency(raw: &str) -> DependencySpec {
+    let trimmed = raw.trim();
+    // The constraint starts at the first space or comparison operator, so `foo>=1.2` works too.
+    let split = trimmed
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '=' | '!' | '~' | '^'))
+        .unwrap_or(trimmed.len());
+    let (source, rest) = (&trimmed[..split], trimmed[split..].trim());
+    let constraint = (!rest.is_empty()).then(|| rest.to_string());
+
+    let is_git = source.contains("://") || source.starts_with("git@");
+    let name = source
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(source)
+        .trim_end_matches(".git")
+        .to_string();
+
+    DependencySpec {
+        raw: trimmed.to_string(),
+        name,
+        git_url: is_git.then(|| source.to_string()),
+        constraint,
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DependencyOutcome {
+    pub dependency: String,
+    /// `already_installed`, `installed`, `unresolved` (registry name, install manually) or `failed`.
+    pub status: String,
+    pub skill_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Re-resolve a skill's metadata dependencies against managed skills and store the edges.
+/// Returns the edges; unresolved ones have no `dependency_skill_id`.
+pub fn record_dependencies(
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<Vec<SkillDependencyRecord>> {
+    let Some(skill) = store.get_skill_by_id(skill_id)? else {
+        anyhow::bail!("skill not found");
+    };
+    let specs: Vec<String> = skill
+        .metadata
+        .as_ref()
+        .map(|m| m.dependencies.clone())
+        .unwrap_or_default();
+    let skills = store.list_skills()?;
+
+    let mut edges: Vec<SkillDependencyRecord> = Vec::new();
+    for raw in specs {
+        let spec = parse_dependency(&raw);
+        if spec.raw.is_empty() || edges.iter().any(|e| e.dependency == spec.raw) {
+            continue;
+        }
+        edges.push(SkillDependencyRecord {
+            skill_id: skill_id.to_string(),
+            dependency_skill_id: resolve_installed(&skills, &spec)
+                .filter(|s| s.id != skill_id)
+                .map(|s| s.id.clone()),
+            dependency: spec.raw,
+        });
+    }
+    store.replace_skill_dependencies(skill_id, &edges)?;
+    Ok(edges)
+}
+
+/// Record edges for a freshly installed skill and return its missing dependencies. Skills
+/// that were waiting on this one are re-resolved as well.
+pub fn record_dependencies_after_install(
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<Vec<String>> {
+    let edges = record_dependencies(store, skill_id)?;
+    let waiting: HashSet<String> = store
+        .list_unresolved_dependencies()?
+        .into_iter()
+        .map(|edge| edge.skill_id)
+        .filter(|id| id != skill_id)
+        .collect();
+    for id in waiting {
+        record_dependencies(store, &id)?;
+    }
+    Ok(edges
+        .into_iter()
+        .filter(|edge| edge.dependency_skill_id.is_none())
+        .map(|edge| edge.dependency)
+        .collect())
+}
+
+fn resolve_installed<'a>(
+    skills: &'a [SkillRecord],
+    spec: &DependencySpec,
+) -> Option<&'a SkillRecord> {
+    skills
+        .iter()
+        .find(|s| source_matches(spec, s.source_ref.as_deref()))
+        .or_else(|| skills.iter().find(|s| s.name == spec.name))
+}
+
+fn source_matches(spec: &DependencySpec, source_ref: Option<&str>) -> bool {
+    match (&spec.git_url, source_ref) {
+        (Some(url), Some(source)) => normalize_source(url) == normalize_source(source),
+        _ => false,
+    }
+}
+
+fn normalize_source(value: &str) -> String {
+    let v = value.trim().trim_end_matches('/');
+    let v = v.strip_suffix(".git").unwrap_or(v);
+    let v = v
+        .strip_prefix("https://")
+        .or_else(|| v.strip_prefix("http://"))
+        .unwrap_or(v);
+    v.strip_prefix("github.com/")
+        .unwrap_or(v)
+        .to_ascii_lowercase()
+}
+
+/// Install every missing git dependency of `skill_id`, transitively. Registry names cannot be
+/// installed from the core and are reported as `unresolved` so the UI can prompt for them.
+pub fn install_missing_dependencies<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<Vec<DependencyOutcome>> {
+    let mut outcomes: Vec<DependencyOutcome> = Vec::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::from([skill_id.to_string()]);
+    let mut installs = 0usize;
+
+    while let Some(current) = queue.pop_front() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        for edge in record_dependencies(store, &current)? {
+            if outcomes.iter().any(|o| o.dependency == edge.dependency) {
+                continue;
+            }
+            if let Some(dep_id) = edge.dependency_skill_id {
+                outcomes.push(DependencyOutcome {
+                    dependency: edge.dependency,
+                    status: "already_installed".to_string(),
+                    skill_id: Some(dep_id),
+                    error: None,
+                });
+                continue;
+            }
+            let spec = parse_dependency(&edge.dependency);
+            let Some(url) = spec.git_url.as_deref() else {
+                outcomes.push(DependencyOutcome {
+                    dependency: edge.dependency,
+                    status: "unresolved".to_string(),
+                    skill_id: None,
+                    error: None,
+                });
+                continue;
+            };
+            if installs >= MAX_DEPENDENCY_INSTALLS {
+                anyhow::bail!(
+                    "dependency chain of {} exceeds {} installs",
+                    skill_id,
+                    MAX_DEPENDENCY_INSTALLS
+                );
+            }
+            installs += 1;
+            match install_git_skill_with_force(app, store, url, None, false) {
+                Ok(installed) => {
+                    queue.push_back(installed.skill_id.clone());
+                    outcomes.push(DependencyOutcome {
+                        dependency: edge.dependency,
+                        status: "installed".to_string(),
+                        skill_id: Some(installed.skill_id),
+                        error: None,
+                    });
+                }
+                Err(err) => outcomes.push(DependencyOutcome {
+                    dependency: edge.dependency,
+                    status: "failed".to_string(),
+                    skill_id: None,
+                    error: Some(format!("{:#}", err)),
+                }),
+            }
+        }
+        // Newly installed skills can now satisfy this skill's edges.
+        record_dependencies(store, &current)?;
+    }
+
+    Ok(outcomes)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DependencyViolation {
+    /// Skill declaring the dependency; `None` for a skill that is not installed yet.
+    pub skill_id: Option<String>,
+    pub skill_name: String,
+    pub dependency: String,
+    pub constraint: Option<String>,
+    pub installed_version: Option<String>,
+    /// `unsatisfied`, `invalid_constraint` or `missing`.
+    pub reason: String,
+}
+
+impl DependencyViolation {
+    pub fn describe(&self) -> String {
+        match self.reason.as_str() {
+            "missing" => format!(
+                "{} requires {} (not installed)",
+                self.skill_name, self.dependency
+            ),
+            "invalid_constraint" => format!(
+                "{} has an invalid constraint in {}",
+                self.skill_name, self.dependency
+            ),
+            _ => format!(
+                "{} requires {} (found {})",
+                self.skill_name,
+                self.dependency,
+                self.installed_version.as_deref().unwrap_or(UNVERSIONED)
+            ),
+        }
+    }
+}
+
+/// Lenient semver parse: accepts `v1.2`, `1.2` and `1` by padding missing components.
+pub fn parse_version(value: &str) -> Option<semver::Version> {
+    let v = value.trim().trim_start_matches('v');
+    if let Ok(parsed) = semver::Version::parse(v) {
+        return Some(parsed);
+    }
+    let (core, rest) = match v.find(['-', '+']) {
+        Some(idx) => v.split_at(idx),
+        None => (v, ""),
+    };
+    let parts = core.split('.').count();
+    if parts >= 3 || core.is_empty() {
+        return None;
+    }
+    let padded = format!("{}{}{}", core, ".0".repeat(3 - parts), rest);
+    semver::Version::parse(&padded).ok()
+}
+
+/// Check `version` against a metadata constraint. `Err` means the constraint itself is invalid.
+pub fn satisfies(constraint: &str, version: Option<&str>) -> Result<bool, semver::Error> {
+    let req = semver::VersionReq::parse(constraint)?;
+    let version = version
+        .and_then(parse_version)
+        .unwrap_or(semver::Version::new(0, 0, 0));
+    Ok(req.matches(&version))
+}
+
+fn check_edge(
+    skill_id: Option<&str>,
+    skill_name: &str,
+    spec: &DependencySpec,
+    installed_version: Option<&str>,
+) -> Option<DependencyViolation> {
+    let constraint = spec.constraint.as_deref()?;
+    let reason = match satisfies(constraint, installed_version) {
+        Ok(true) => return None,
+        Ok(false) => "unsatisfied",
+        Err(_) => "invalid_constraint",
+    };
+    Some(DependencyViolation {
+        skill_id: skill_id.map(str::to_string),
+        skill_name: skill_name.to_string(),
+        dependency: spec.raw.clone(),
+        constraint: Some(constraint.to_string()),
+        installed_version: installed_version.map(str::to_string),
+        reason: reason.to_string(),
+    })
+}
+
+fn metadata_version(skill: &SkillRecord) -> Option<&str> {
+    skill.metadata.as_ref().map(|m| m.version.as_str())
+}
+
+fn declared_dependencies(skill: &SkillRecord) -> Vec<DependencySpec> {
+    skill
+        .metadata
+        .as_ref()
+        .map(|m| m.dependencies.iter().map(|d| parse_dependency(d)).collect())
+        .unwrap_or_default()
+}
+
+/// Constraint violations that installing (or updating `replaces`) a skill with `metadata` would
+/// introduce, in both directions: its own requirements and what installed skills require of it.
+pub fn violations_for_candidate(
+    store: &SkillStore,
+    name: &str,
+    source_ref: Option<&str>,
+    metadata: Option<&SkillMetadata>,
+    replaces: Option<&str>,
+) -> Result<Vec<DependencyViolation>> {
+    let skills: Vec<SkillRecord> = store
+        .list_skills()?
+        .into_iter()
+        .filter(|s| Some(s.id.as_str()) != replaces)
+        .collect();
+    let version = metadata.map(|m| m.version.as_str());
+    let mut out = Vec::new();
+
+    for raw in metadata
+        .map(|m| m.dependencies.as_slice())
+        .unwrap_or_default()
+    {
+        let spec = parse_dependency(raw);
+        if let Some(dep) = resolve_installed(&skills, &spec) {
+            out.extend(check_edge(replaces, name, &spec, metadata_version(dep)));
+        }
+    }
+
+    for skill in &skills {
+        for spec in declared_dependencies(skill) {
+            if spec.name == name || source_matches(&spec, source_ref) {
+                out.extend(check_edge(Some(&skill.id), &skill.name, &spec, version));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Every constraint violation and missing dependency among managed skills.
+pub fn check_dependency_health(store: &SkillStore) -> Result<Vec<DependencyViolation>> {
+    let skills = store.list_skills()?;
+    let mut out = Vec::new();
+    for skill in &skills {
+        for spec in declared_dependencies(skill) {
+            match resolve_installed(&skills, &spec).filter(|dep| dep.id != skill.id) {
+                Some(dep) => out.extend(check_edge(
+                    Some(&skill.id),
+                    &skill.name,
+                    &spec,
+                    metadata_version(dep),
+                )),
+                None => out.push(DependencyViolation {
+                    skill_id: Some(skill.id.clone()),
+                    skill_name: skill.name.clone(),
+                    dependency: spec.raw.clone(),
+                    constraint: spec.constraint.clone(),
+                    installed_version: None,
+                    reason: "missing".to_string(),
+                }),
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "tests/skill_deps.rs"]
+mod tests;
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use similar::TextDiff;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+
+/// Files larger than this are compared byte-wise only; no text diff is produced.
+const MAX_TEXT_DIFF_BYTES: usize = 512 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileDiffStatus {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FileDiff {
+    /// Path relative to the skill root, always `/`-separated.
+    pub path: String,
+    pub status: FileDiffStatus,
+    pub binary: bool,
+    /// Unified diff (a -> b) for modified text files.
+    pub unified: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillDiff {
+    pub path_a: PathBuf,
+    pub path_b: PathBuf,
+    pub files: Vec<FileDiff>,
+    pub identical: bool,
+}
+
+pub fn diff_skill_dirs(path_a: &Path, path_b: &Path) -> Result<SkillDiff> {
+    let files_a = list_files(path_a)?;
+    let files_b = list_files(path_b)?;
+
+    let mut all: BTreeMap<&String, (Option<&PathBuf>, Option<&PathBuf>)> = BTreeMap::new();
+    for (rel, abs) in &files_a {
+        all.entry(rel).or_default().0 = Some(abs);
+    }
+    for (rel, abs) in &files_b {
+        all.entry(rel).or_default().1 = Some(abs);
+    }
+
+    let mut files = Vec::with_capacity(all.len());
+    for (rel, (a, b)) in all {
+        let diff = match (a, b) {
+            (Some(_), None) => FileDiff {
+                path: rel.clone(),
+                status: FileDiffStatus::Removed,
+                binary: false,
+                unified: None,
+            },
+            (None, Some(_)) => FileDiff {
+                path: rel.clone(),
+                status: FileDiffStatus::Added,
+                binary: false,
+                unified: None,
+            },
+            (Some(a), Some(b)) => diff_file(rel, a, b)?,
+            (None, None) => continue,
+        };
+        files.push(diff);
+    }
+
+    let identical = files.iter().all(|f| f.status == FileDiffStatus::Unchanged);
+    Ok(SkillDiff {
+        path_a: path_a.to_path_buf(),
+        path_b: path_b.to_path_buf(),
+        files,
+        identical,
+    })
+}
+
+fn diff_file(rel: &str, a: &Path, b: &Path) -> Result<FileDiff> {
+    let bytes_a = std::fs::read(a).with_context(|| format!("read file {:?}", a))?;
+    let bytes_b = std::fs::read(b).with_context(|| format!("read file {:?}", b))?;
+
+    if bytes_a == bytes_b {
+        return Ok(FileDiff {
+            path: rel.to_string(),
+            status: FileDiffStatus::Unchanged,
+            binary: false,
+            unified: None,
+        });
+    }
+
+    let too_large = bytes_a.len().max(bytes_b.len()) > MAX_TEXT_DIFF_BYTES;
+    let (text_a, text_b) = match (std::str::from_utf8(&bytes_a), std::str::from_utf8(&bytes_b)) {
+        (Ok(a), Ok(b)) if !too_large => (a, b),
+        _ => {
+            return Ok(FileDiff {
+                path: rel.to_string(),
+                status: FileDiffStatus::Modified,
+                binary: !too_large,
+                unified: None,
+            })
+        }
+    };
+
+    let unified = TextDiff::from_lines(text_a, text_b)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", rel), &format!("b/{}", rel))
+        .to_string();
+    Ok(FileDiff {
+        path: rel.to_string(),
+        status: FileDiffStatus::Modified,
+        binary: false,
+        unified: Some(unified),
+    })
+}
+
+fn list_files(root: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut out = BTreeMap::new();
+    for entry in WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?;
+        let key = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        out.insert(key, entry.path().to_path_buf());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[path = "tests/skill_diff.rs"]
+mod tests;
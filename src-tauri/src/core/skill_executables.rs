@@ -0,0 +1,161 @@
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::skill_lint::relative_key;
+use super::skill_store::{SkillRecord, SkillStore};
+
+pub const EXECUTABLE_POLICY_KEY: &str = "executable_policy";
+
+/// Extensions of scripts and native binaries a tool might run on the user's behalf.
+const EXECUTABLE_EXTENSIONS: [&str; 21] = [
+    "sh", "bash", "zsh", "fish", "ps1", "psm1", "bat", "cmd", "vbs", "py", "rb", "pl", "js", "mjs",
+    "cjs", "exe", "dll", "so", "dylib", "com", "msi",
+];
+/// Leading bytes of ELF, PE and Mach-O (both byte orders, plus fat) binaries.
+const BINARY_MAGICS: [&[u8]; 6] = [
+    b"\x7fELF",
+    b"MZ",
+    b"\xfe\xed\xfa\xce",
+    b"\xfe\xed\xfa\xcf",
+    b"\xcf\xfa\xed\xfe",
+    b"\xca\xfe\xba\xbe",
+];
+
+/// How syncs of skills that ship executables are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutablePolicy {
+    /// Sync like any other skill.
+    Allow,
+    /// Sync only once the user confirms it.
+    #[default]
+    Confirm,
+    /// Never sync them to tools; for environments that forbid unreviewed scripts.
+    Block,
+}
+
+impl ExecutablePolicy {
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            ExecutablePolicy::Allow => "allow",
+            ExecutablePolicy::Confirm => "confirm",
+            ExecutablePolicy::Block => "block",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "allow" => Some(ExecutablePolicy::Allow),
+            "confirm" => Some(ExecutablePolicy::Confirm),
+            "block" => Some(ExecutablePolicy::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Files under `path` that are scripts or binaries: executable bit set, a script or binary
+/// extension, a shebang, or a native binary header. Paths are relative to `path`.
+pub fn find_executables(path: &Path) -> Result<Vec<String>> {
+    let mut found = Vec::new();
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if entry.file_type().is_file() && is_executable(entry.path())? {
+            found.push(relative_key(path, entry.path()));
+        }
+    }
+    Ok(found)
+}
+
+/// Flag for a skill record. `None` when the scan fails, so the sync gate scans again later.
+pub fn contains_executables(path: &Path) -> Option<bool> {
+    match find_executables(path) {
+        Ok(found) => Some(!found.is_empty()),
+        Err(err) => {
+            log::warn!("[executables] scan of {:?} failed: {:#}", path, err);
+            None
+        }
+    }
+}
+
+fn is_executable(path: &Path) -> Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)
+            .with_context(|| format!("stat {:?}", path))?
+            .permissions()
+            .mode();
+        if mode & 0o111 != 0 {
+            return Ok(true);
+        }
+    }
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if EXECUTABLE_EXTENSIONS.contains(&ext.as_str()) {
+        return Ok(true);
+    }
+
+    let mut head = [0u8; 4];
+    let mut file = std::fs::File::open(path).with_context(|| format!("open {:?}", path))?;
+    let read = file
+        .read(&mut head)
+        .with_context(|| format!("read {:?}", path))?;
+    let head = &head[..read];
+    Ok(head.starts_with(b"#!") || BINARY_MAGICS.iter().any(|magic| head.starts_with(magic)))
+}
+
+pub fn get_executable_policy(store: &SkillStore) -> ExecutablePolicy {
+    store
+        .get_setting(EXECUTABLE_POLICY_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| ExecutablePolicy::from_key(&v))
+        .unwrap_or_default()
+}
+
+pub fn set_executable_policy(store: &SkillStore, key: &str) -> Result<ExecutablePolicy> {
+    let policy = ExecutablePolicy::from_key(key)
+        .ok_or_else(|| anyhow::anyhow!("unknown executable policy: {}", key))?;
+    store.set_setting(EXECUTABLE_POLICY_KEY, policy.as_key())?;
+    Ok(policy)
+}
+
+/// Sync gate for skills that ship scripts or binaries. Under `Confirm` they need `confirmed`
+/// (the user said yes); under `Block` they are refused regardless. Skills recorded before the
+/// flag existed are scanned here and the result stored.
+pub fn ensure_sync_allowed(store: &SkillStore, skill: &SkillRecord, confirmed: bool) -> Result<()> {
+    let policy = get_executable_policy(store);
+    if policy == ExecutablePolicy::Allow {
+        return Ok(());
+    }
+    let contains = match skill.contains_executables {
+        Some(contains) => contains,
+        None => {
+            let found = !find_executables(Path::new(&skill.central_path))?.is_empty();
+            store.set_skill_contains_executables(&skill.id, found)?;
+            found
+        }
+    };
+    match policy {
+        _ if !contains => Ok(()),
+        ExecutablePolicy::Block => anyhow::bail!("EXECUTABLES_BLOCKED|{}", skill.name),
+        ExecutablePolicy::Confirm if !confirmed => {
+            anyhow::bail!("EXECUTABLES_UNCONFIRMED|{}", skill.name)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_executables.rs"]
+mod tests;
@@ -0,0 +1,257 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::content_hash::hash_dir;
+use super::skill_signing::SIGNATURE_FILE;
+use super::skill_store::SkillRecord;
+
+/// gitignore-style exclusions read from the skill root, applied when exporting.
+pub const SKILL_IGNORE_FILE: &str = ".skillignore";
+/// Written at the archive root, next to the skill folder.
+pub const EXPORT_MANIFEST_FILE: &str = "skills-hub-export.json";
+const EXPORT_FORMAT: u32 = 1;
+/// Never exported, whatever `.skillignore` says.
+const ALWAYS_SKIPPED: [&str; 3] = [".git", ".DS_Store", "Thumbs.db"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub format: u32,
+    pub name: String,
+    /// Version from skill.yaml or the frontmatter, when the skill declares one.
+    pub version: Option<String>,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub source_revision: Option<String>,
+    /// Content hash of the skill in the Central Repo, as `hash_dir` computes it.
+    pub content_hash: String,
+    /// Whether `SKILL.minisig` travels with the archive.
+    pub signed: bool,
+    pub exported_at: i64,
+}
+
+struct IgnoreRule {
+    re: Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Parsed `.skillignore`: `#` comments, `!` negation, trailing `/` for dirs only, a leading or
+/// inner `/` anchors to the skill root, `*`, `?` and `**` globs. The last matching rule wins.
+pub(crate) struct SkillIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl SkillIgnore {
+    pub(crate) fn load(skill_dir: &Path) -> Result<Self> {
+        let path = skill_dir.join(SKILL_IGNORE_FILE);
+        if !path.exists() {
+            return Ok(Self { rules: Vec::new() });
+        }
+        let raw = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+        Ok(Self::parse(&raw))
+    }
+
+    pub(crate) fn parse(raw: &str) -> Self {
+        let rules = raw
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let pattern = line.trim_start_matches('/');
+                if pattern.is_empty() {
+                    return None;
+                }
+                let prefix = if anchored { "^" } else { "(?:^|/)" };
+                let re = Regex::new(&format!("{}{}$", prefix, glob_to_regex(pattern))).ok()?;
+                Some(IgnoreRule {
+                    re,
+                    negated,
+                    dir_only,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// `relative` uses `/` separators. Callers skip the contents of ignored dirs themselves.
+    pub(crate) fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if (is_dir || !rule.dir_only) && rule.re.is_match(relative) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+fn glob_to_regex(pattern: &str) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches no directory at all.
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    out.push_str("(?:.*/)?");
+                } else {
+                    out.push_str(".*");
+                }
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out
+}
+
+/// Zip the skill's central dir into `dest` as `<dir name>/...` plus `skills-hub-export.json`.
+/// Entries matched by `.skillignore` and symlinks are left out; if anything was left out, a
+/// `SKILL.minisig` no longer matches the exported files and is dropped.
+pub fn export_skill_archive(skill: &SkillRecord, dest: &Path, now: i64) -> Result<ExportManifest> {
+    let central = PathBuf::from(&skill.central_path);
+    if !central.is_dir() {
+        anyhow::bail!("skill directory not found: {:?}", central);
+    }
+    let folder = central
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| skill.name.clone());
+    let ignore = SkillIgnore::load(&central)?;
+
+    let mut entries: Vec<(String, PathBuf, bool)> = Vec::new();
+    let mut skipped = 0usize;
+    let mut walker = WalkDir::new(&central)
+        .follow_links(false)
+        .sort_by_file_name()
+        .min_depth(1)
+        .into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(&central)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let file_type = entry.file_type();
+        let name = entry.file_name().to_string_lossy();
+        if ALWAYS_SKIPPED.iter().any(|n| *n == name) {
+            if file_type.is_dir() {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if file_type.is_symlink() || ignore.is_ignored(&relative, file_type.is_dir()) {
+            if file_type.is_symlink() {
+                log::warn!("[export] skipping symlink {:?}", entry.path());
+            }
+            skipped += 1;
+            if file_type.is_dir() {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if relative == SIGNATURE_FILE {
+            continue;
+        }
+        entries.push((relative, entry.path().to_path_buf(), file_type.is_dir()));
+    }
+
+    let signature = central.join(SIGNATURE_FILE);
+    let signed = signature.is_file() && skipped == 0;
+    if signature.is_file() && !signed {
+        log::warn!(
+            "[export] {}: {} entries left out by .skillignore or as symlinks; dropping {}",
+            skill.name,
+            skipped,
+            SIGNATURE_FILE
+        );
+    }
+    if signed {
+        entries.push((SIGNATURE_FILE.to_string(), signature, false));
+    }
+
+    let manifest = ExportManifest {
+        format: EXPORT_FORMAT,
+        name: skill.name.clone(),
+        version: skill.metadata.as_ref().map(|m| m.version.clone()),
+        source_type: skill.source_type.clone(),
+        source_ref: skill.source_ref.clone(),
+        source_revision: skill.source_revision.clone(),
+        content_hash: hash_dir(&central)?,
+        signed,
+        exported_at: now,
+    };
+
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    let tmp = dest.with_extension("zip.tmp");
+    let result = write_archive(&tmp, &folder, &entries, &manifest)
+        .and_then(|_| std::fs::rename(&tmp, dest).with_context(|| format!("write {:?}", dest)));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result?;
+    Ok(manifest)
+}
+
+fn write_archive(
+    path: &Path,
+    folder: &str,
+    entries: &[(String, PathBuf, bool)],
+    manifest: &ExportManifest,
+) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("create {:?}", path))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(EXPORT_MANIFEST_FILE, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    zip.add_directory(format!("{}/", folder), options)?;
+    for (relative, source, is_dir) in entries {
+        let name = format!("{}/{}", folder, relative);
+        if *is_dir {
+            zip.add_directory(format!("{}/", name), options)?;
+            continue;
+        }
+        #[cfg(unix)]
+        let options = {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(source)?.permissions().mode();
+            options.unix_permissions(mode & 0o777)
+        };
+        zip.start_file(name, options)?;
+        let mut input = File::open(source).with_context(|| format!("open {:?}", source))?;
+        std::io::copy(&mut input, &mut zip).with_context(|| format!("read {:?}", source))?;
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/skill_export.rs"]
+mod tests;
@@ -0,0 +1,287 @@
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::Serialize;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+
+/// Bytes inspected when deciding whether a file is binary.
+const SNIFF_BYTES: usize = 8 * 1024;
+/// Largest chunk returned by one `read_skill_file` call.
+pub const MAX_READ_BYTES: u64 = 1024 * 1024;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillFileEntry {
+    /// Path relative to the skill root, `/`-separated.
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub is_binary: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    #[default]
+    Utf8,
+    Base64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SkillFileContent {
+    pub path: String,
+    /// Size of the whole file; `offset`/`length` describe the returned chunk.
+    pub size: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub is_binary: bool,
+    pub encoding: ContentEncoding,
+    pub content: String,
+    /// Where the next chunk starts, `None` once the end of the file was returned.
+    pub next_offset: Option<u64>,
+}
+
+/// Refuse a skill name that is not exactly one plain path component, so joining it onto the
+/// Central Repo or a tool dir cannot reach anywhere else.
+pub fn ensure_plain_name(name: &str) -> Result<()> {
+    let mut components = Path::new(name).components();
+    let plain = matches!(components.next(), Some(Component::Normal(part)) if part != ".git")
+        && components.next().is_none()
+        && !name.contains(['/', '\\']);
+    if !plain {
+        anyhow::bail!("INVALID_PATH|{}", name);
+    }
+    Ok(())
+}
+
+/// Resolve `relative` against a skill root, refusing anything that would land outside it:
+/// absolute paths, `..`, `.git`, and symlinks pointing elsewhere. The file itself may not exist.
+pub fn resolve_in_skill(root: &Path, relative: &str) -> Result<PathBuf> {
+    let rel = Path::new(relative.trim());
+    let mut clean = PathBuf::new();
+    for component in rel.components() {
+        match component {
+            Component::Normal(part) if part != ".git" => clean.push(part),
+            Component::CurDir => {}
+            _ => anyhow::bail!("INVALID_PATH|{}", relative),
+        }
+    }
+    if clean.as_os_str().is_empty() {
+        anyhow::bail!("INVALID_PATH|{}", relative);
+    }
+
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("resolve skill root {:?}", root))?;
+    let path = root.join(&clean);
+    // Walk up to the deepest existing ancestor; symlinks there must still resolve inside root.
+    let existing = path
+        .ancestors()
+        .find(|p| p.symlink_metadata().is_ok())
+        .unwrap_or(&root);
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", existing))?;
+    if !resolved.starts_with(&root) {
+        anyhow::bail!("INVALID_PATH|{}", relative);
+    }
+    Ok(path)
+}
+
+/// Write through a temp file in the same directory and rename it into place, so readers never
+/// see a half-written file.
+pub fn write_file_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("no parent directory for {:?}", path))?;
+    fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    let tmp = parent.join(format!(".skills-hub-write-{}", Uuid::new_v4()));
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&tmp).with_context(|| format!("create {:?}", tmp))?;
+        file.write_all(bytes)
+            .with_context(|| format!("write {:?}", tmp))?;
+        file.sync_all().with_context(|| format!("sync {:?}", tmp))?;
+        if let Ok(meta) = fs::metadata(path) {
+            let _ = fs::set_permissions(&tmp, meta.permissions());
+        }
+        fs::rename(&tmp, path).with_context(|| format!("rename {:?} -> {:?}", tmp, path))
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// The content of the private file at `path` (a key), created from `create` when there is none.
+/// A new file is readable by the owner only from the start (mode 0600 on Unix) and appears
+/// complete: it is written under a temporary name and linked into place, so when two callers
+/// race the first link wins and both return its content.
+pub fn read_or_create_private_file(
+    path: &Path,
+    create: impl FnOnce() -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    match fs::read(path) {
+        Ok(bytes) => return Ok(bytes),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err).with_context(|| format!("read {:?}", path)),
+    }
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("no parent directory for {:?}", path))?;
+    fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    let bytes = create()?;
+    let tmp = parent.join(format!(".skills-hub-private-{}", Uuid::new_v4()));
+    let result = (|| -> Result<Vec<u8>> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&tmp)
+            .with_context(|| format!("create {:?}", tmp))?;
+        file.write_all(&bytes)
+            .with_context(|| format!("write {:?}", tmp))?;
+        file.sync_all().with_context(|| format!("sync {:?}", tmp))?;
+        match fs::hard_link(&tmp, path) {
+            Ok(()) => Ok(bytes),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                fs::read(path).with_context(|| format!("read {:?}", path))
+            }
+            Err(err) => Err(err).with_context(|| format!("link {:?} -> {:?}", tmp, path)),
+        }
+    })();
+    let _ = fs::remove_file(&tmp);
+    result
+}
+
+/// Every file and directory under `root` (parents before children, names sorted), skipping
+/// `.git` and OS metadata files. Symlinks are listed but not followed.
+pub fn list_skill_files(root: &Path) -> Result<Vec<SkillFileEntry>> {
+    if !root.is_dir() {
+        anyhow::bail!("skill path not found: {:?}", root);
+    }
+    let mut out = Vec::new();
+    for entry in WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        let path = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let is_dir = entry.file_type().is_dir();
+        let size = if is_dir {
+            0
+        } else {
+            entry
+                .metadata()
+                .with_context(|| format!("stat {:?}", entry.path()))?
+                .len()
+        };
+        let is_binary = entry.file_type().is_file() && is_binary_file(entry.path());
+        out.push(SkillFileEntry {
+            path,
+            is_dir,
+            size,
+            is_binary,
+        });
+    }
+    Ok(out)
+}
+
+/// Sniff the head of a file; unreadable files count as binary so the UI does not open them.
+pub fn is_binary_file(path: &Path) -> bool {
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    match fs::File::open(path).and_then(|f| f.take(SNIFF_BYTES as u64).read_to_end(&mut head)) {
+        Ok(_) => looks_binary(&head),
+        Err(_) => true,
+    }
+}
+
+/// NUL bytes or invalid UTF-8 mean binary. A multi-byte character cut off at the end of the
+/// sample is not counted as invalid.
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => false,
+        Err(err) => err.error_len().is_some(),
+    }
+}
+
+/// Read up to `length` bytes (capped at `MAX_READ_BYTES`) starting at `offset`. Text chunks end
+/// on a character boundary so consecutive reads via `next_offset` never split a character;
+/// binary files are returned base64-encoded.
+pub fn read_skill_file(
+    root: &Path,
+    relative: &str,
+    offset: u64,
+    length: Option<u64>,
+) -> Result<SkillFileContent> {
+    let path = resolve_in_skill(root, relative)?;
+    if !path.is_file() {
+        anyhow::bail!("file not found: {}", relative);
+    }
+    let size = fs::metadata(&path)
+        .with_context(|| format!("stat {:?}", path))?
+        .len();
+    let offset = offset.min(size);
+    let want = length.unwrap_or(MAX_READ_BYTES).min(MAX_READ_BYTES);
+
+    let mut file = fs::File::open(&path).with_context(|| format!("open {:?}", path))?;
+    file.seek(SeekFrom::Start(offset))
+        .with_context(|| format!("seek {:?}", path))?;
+    let mut bytes = Vec::new();
+    file.take(want)
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("read {:?}", path))?;
+
+    let is_binary = is_binary_file(&path);
+    let content = if is_binary {
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    } else {
+        if let Err(err) = std::str::from_utf8(&bytes) {
+            // Leave a character cut by the chunk end for the next read.
+            if err.error_len().is_none() && err.valid_up_to() > 0 {
+                bytes.truncate(err.valid_up_to());
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    let end = offset + bytes.len() as u64;
+    Ok(SkillFileContent {
+        path: relative.trim().trim_start_matches("./").to_string(),
+        size,
+        offset,
+        length: bytes.len() as u64,
+        is_binary,
+        encoding: if is_binary {
+            ContentEncoding::Base64
+        } else {
+            ContentEncoding::Utf8
+        },
+        content,
+        next_offset: (end < size).then_some(end),
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/skill_files.rs"]
+mod tests;
@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::skill_lint::relative_key;
+use super::skill_store::{SkillProjection, SkillStore};
+
+/// Larger files are skipped; skills are prose and scripts, not bulk data.
+const MAX_SEARCHED_FILE_BYTES: u64 = 1024 * 1024;
+const DEFAULT_MAX_HITS: usize = 500;
+/// Longer lines are cut around the first match.
+const MAX_LINE_CHARS: usize = 240;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GrepOptions {
+    /// Match the pattern as plain text rather than as a regex.
+    #[serde(default)]
+    pub literal: bool,
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Search only these skills; all managed skills when empty.
+    #[serde(default)]
+    pub skill_ids: Vec<String>,
+    /// Stop after this many hits; 500 when omitted.
+    #[serde(default)]
+    pub max_hits: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GrepHit {
+    pub skill_id: String,
+    pub skill_name: String,
+    /// Path relative to the skill root.
+    pub file: String,
+    pub line: usize,
+    /// 1-based character column of the first match in the line.
+    pub column: usize,
+    /// The line, cut to a window around the match when long.
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GrepResult {
+    pub hits: Vec<GrepHit>,
+    pub files_searched: usize,
+    /// The hit limit was reached; more matches may exist.
+    pub truncated: bool,
+}
+
+/// Search the text files of managed skills' central directories line by line. Binary and
+/// oversized files are skipped, as are the entries content hashing ignores.
+pub fn grep_skills(store: &SkillStore, pattern: &str, options: &GrepOptions) -> Result<GrepResult> {
+    if pattern.is_empty() {
+        anyhow::bail!("search pattern is empty");
+    }
+    let source = if options.literal {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    let re = RegexBuilder::new(&source)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .with_context(|| format!("invalid pattern: {}", pattern))?;
+    let max_hits = options.max_hits.unwrap_or(DEFAULT_MAX_HITS);
+
+    let mut result = GrepResult::default();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        if !options.skill_ids.is_empty() && !options.skill_ids.contains(&skill.id) {
+            continue;
+        }
+        let root = Path::new(&skill.central_path);
+        if !root.is_dir() {
+            continue;
+        }
+        for entry in WalkDir::new(root)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| !is_ignored(entry))
+        {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_file()
+                || entry
+                    .metadata()
+                    .map_or(true, |meta| meta.len() > MAX_SEARCHED_FILE_BYTES)
+            {
+                continue;
+            }
+            let Ok(bytes) = std::fs::read(entry.path()) else {
+                continue;
+            };
+            if bytes.contains(&0) {
+                continue;
+            }
+            result.files_searched += 1;
+            let text = String::from_utf8_lossy(&bytes);
+            for (idx, line) in text.lines().enumerate() {
+                let Some(found) = re.find(line) else {
+                    continue;
+                };
+                if result.hits.len() == max_hits {
+                    result.truncated = true;
+                    return Ok(result);
+                }
+                result.hits.push(GrepHit {
+                    skill_id: skill.id.clone(),
+                    skill_name: skill.name.clone(),
+                    file: relative_key(root, entry.path()),
+                    line: idx + 1,
+                    column: line[..found.start()].chars().count() + 1,
+                    text: excerpt(line, &re),
+                });
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// `line` trimmed, or for long lines a window of `MAX_LINE_CHARS` starting a little before the
+/// first match.
+fn excerpt(line: &str, re: &Regex) -> String {
+    let line = line.trim_end();
+    let total = line.chars().count();
+    if total <= MAX_LINE_CHARS {
+        return line.trim_start().to_string();
+    }
+    let start = re
+        .find(line)
+        .map_or(0, |m| line[..m.start()].chars().count())
+        .saturating_sub(MAX_LINE_CHARS / 4);
+    let window: String = line.chars().skip(start).take(MAX_LINE_CHARS).collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        window,
+        if start + MAX_LINE_CHARS < total {
+            "..."
+        } else {
+            ""
+        }
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/skill_grep.rs"]
+mod tests;
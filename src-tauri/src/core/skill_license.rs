@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use super::skill_metadata::{SkillFrontmatter, SkillMetadata};
+
+/// SPDX value for a license file whose text we could not identify.
+pub const NOASSERTION: &str = "NOASSERTION";
+
+const LICENSE_FILE_NAMES: [&str; 6] = [
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "COPYING",
+    "COPYING.md",
+];
+
+/// Licenses that allow reuse and modification without copyleft obligations.
+const PERMISSIVE: [&str; 10] = [
+    "MIT",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "0BSD",
+    "Unlicense",
+    "CC0-1.0",
+    "CC-BY-4.0",
+    "Zlib",
+];
+
+/// Ordered so that more specific texts are checked before the ones they contain.
+const TEXT_MARKERS: [(&str, &str); 11] = [
+    ("GNU AFFERO GENERAL PUBLIC LICENSE", "AGPL-3.0"),
+    ("GNU LESSER GENERAL PUBLIC LICENSE", "LGPL-3.0"),
+    ("GNU GENERAL PUBLIC LICENSE", "GPL-3.0"),
+    ("Mozilla Public License Version 2.0", "MPL-2.0"),
+    ("Apache License", "Apache-2.0"),
+    ("Permission is hereby granted, free of charge", "MIT"),
+    (
+        "Redistribution and use in source and binary forms",
+        "BSD-3-Clause",
+    ),
+    ("Permission to use, copy, modify, and/or distribute", "ISC"),
+    ("This is free and unencumbered software", "Unlicense"),
+    ("CC0 1.0 Universal", "CC0-1.0"),
+    ("Attribution 4.0 International", "CC-BY-4.0"),
+];
+
+/// License of a skill directory: declared metadata wins, then SKILL.md frontmatter, then a
+/// LICENSE file identified by its text. `None` when nothing declares one.
+pub fn detect_license(dir: &Path, metadata: Option<&SkillMetadata>) -> Option<String> {
+    if let Some(license) = metadata.and_then(|m| m.license.as_deref()) {
+        return normalize(license);
+    }
+    if let Ok(fm) = SkillFrontmatter::read(&dir.join("SKILL.md")) {
+        if let Some(license) = fm.license.as_deref().and_then(normalize) {
+            return Some(license);
+        }
+    }
+    LICENSE_FILE_NAMES.iter().find_map(|name| {
+        let text = fs::read_to_string(dir.join(name)).ok()?;
+        Some(identify_license_text(&text))
+    })
+}
+
+fn normalize(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+pub fn identify_license_text(text: &str) -> String {
+    let text = text.replace("\r\n", "\n");
+    let id = TEXT_MARKERS
+        .iter()
+        .find(|(marker, _)| text.contains(marker))
+        .map(|(_, id)| *id);
+    match id {
+        Some("GPL-3.0") if text.contains("Version 2, June 1991") => "GPL-2.0".to_string(),
+        // The 2-clause variant lacks the "neither the name" endorsement clause.
+        Some("BSD-3-Clause") if !text.to_ascii_lowercase().contains("neither the name") => {
+            "BSD-2-Clause".to_string()
+        }
+        Some(id) => id.to_string(),
+        None => NOASSERTION.to_string(),
+    }
+}
+
+/// True for SPDX ids (or `OR` expressions containing one) from the permissive list.
+pub fn is_permissive(license: &str) -> bool {
+    license
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(" OR ")
+        .map(str::trim)
+        .any(|id| PERMISSIVE.iter().any(|p| p.eq_ignore_ascii_case(id)))
+}
+
+#[cfg(test)]
+#[path = "tests/skill_license.rs"]
+mod tests;
@@ -0,0 +1,284 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::skill_metadata::SkillFrontmatter;
+
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const MAX_TOTAL_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Files that should never ship inside a skill (credentials, private keys).
+const DISALLOWED_FILE_NAMES: [&str; 5] = [".env", "id_rsa", "id_ed25519", ".npmrc", ".netrc"];
+const DISALLOWED_EXTENSIONS: [&str; 4] = ["pem", "key", "p12", "pfx"];
+/// Directories that bloat a skill and are almost always committed by accident.
+const DISCOURAGED_DIRS: [&str; 3] = ["node_modules", "__pycache__", ".venv"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LintDiagnostic {
+    pub code: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// Path relative to the skill root.
+    pub file: Option<String>,
+    pub line: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LintReport {
+    pub path: PathBuf,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == LintSeverity::Error)
+    }
+
+    fn push(
+        &mut self,
+        code: &str,
+        severity: LintSeverity,
+        message: String,
+        file: Option<String>,
+        line: Option<usize>,
+    ) {
+        self.diagnostics.push(LintDiagnostic {
+            code: code.to_string(),
+            severity,
+            message,
+            file,
+            line,
+        });
+    }
+}
+
+pub fn lint_skill_dir(path: &Path) -> Result<LintReport> {
+    if !path.is_dir() {
+        anyhow::bail!("skill path not found: {:?}", path);
+    }
+    let mut report = LintReport {
+        path: path.to_path_buf(),
+        diagnostics: Vec::new(),
+    };
+
+    lint_skill_md(path, &mut report);
+
+    let mut total: u64 = 0;
+    let mut walker = WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry));
+    while let Some(entry) = walker.next() {
+        let entry = entry?;
+        let rel = relative_key(path, entry.path());
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if entry.file_type().is_dir() {
+            if DISCOURAGED_DIRS.contains(&file_name.as_str()) {
+                report.push(
+                    "discouraged_dir",
+                    LintSeverity::Warning,
+                    format!("{} should not be shipped with a skill", file_name),
+                    Some(rel),
+                    None,
+                );
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let ext = entry
+            .path()
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if DISALLOWED_FILE_NAMES.contains(&file_name.as_str())
+            || DISALLOWED_EXTENSIONS.contains(&ext.as_str())
+        {
+            report.push(
+                "disallowed_file",
+                LintSeverity::Error,
+                format!("{} looks like a credential or private key", file_name),
+                Some(rel.clone()),
+                None,
+            );
+        }
+
+        let size = entry
+            .metadata()
+            .with_context(|| format!("stat {:?}", entry.path()))?
+            .len();
+        total = total.saturating_add(size);
+        if size > MAX_FILE_BYTES {
+            report.push(
+                "file_too_large",
+                LintSeverity::Warning,
+                format!("file is {} bytes (limit {})", size, MAX_FILE_BYTES),
+                Some(rel.clone()),
+                None,
+            );
+        }
+
+        if ext == "md" && size <= MAX_FILE_BYTES {
+            lint_markdown_links(path, entry.path(), &rel, &mut report);
+        }
+    }
+
+    if total > MAX_TOTAL_BYTES {
+        report.push(
+            "skill_too_large",
+            LintSeverity::Error,
+            format!("skill is {} bytes (limit {})", total, MAX_TOTAL_BYTES),
+            None,
+            None,
+        );
+    }
+
+    Ok(report)
+}
+
+fn lint_skill_md(root: &Path, report: &mut LintReport) {
+    let skill_md = root.join("SKILL.md");
+    let file = Some("SKILL.md".to_string());
+    if !skill_md.exists() {
+        report.push(
+            "missing_skill_md",
+            LintSeverity::Error,
+            "SKILL.md not found".to_string(),
+            None,
+            None,
+        );
+        return;
+    }
+    let fm = match SkillFrontmatter::read(&skill_md) {
+        Ok(fm) => fm,
+        Err(reason) => {
+            report.push(
+                reason,
+                LintSeverity::Error,
+                "SKILL.md must start with a `---` delimited YAML frontmatter block".to_string(),
+                file,
+                Some(1),
+            );
+            return;
+        }
+    };
+
+    match fm.name.as_deref().map(str::trim) {
+        None | Some("") => report.push(
+            "missing_name",
+            LintSeverity::Error,
+            "frontmatter is missing `name`".to_string(),
+            file.clone(),
+            None,
+        ),
+        Some(name) if !is_valid_name(name) => report.push(
+            "invalid_name",
+            LintSeverity::Warning,
+            format!(
+                "name `{}` should be lowercase letters, digits and hyphens (max {} chars)",
+                name, MAX_NAME_LEN
+            ),
+            file.clone(),
+            None,
+        ),
+        Some(_) => {}
+    }
+
+    match fm.description.as_deref().map(str::trim) {
+        None | Some("") => report.push(
+            "missing_description",
+            LintSeverity::Warning,
+            "frontmatter is missing `description`".to_string(),
+            file,
+            None,
+        ),
+        Some(desc) if desc.chars().count() > MAX_DESCRIPTION_LEN => report.push(
+            "description_too_long",
+            LintSeverity::Warning,
+            format!(
+                "description is {} chars (limit {})",
+                desc.chars().count(),
+                MAX_DESCRIPTION_LEN
+            ),
+            file,
+            None,
+        ),
+        Some(_) => {}
+    }
+}
+
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    static NAME_RE: OnceLock<Regex> = OnceLock::new();
+    let re = NAME_RE.get_or_init(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap());
+    name.len() <= MAX_NAME_LEN && re.is_match(name)
+}
+
+fn lint_markdown_links(root: &Path, file: &Path, rel: &str, report: &mut LintReport) {
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let re =
+        LINK_RE.get_or_init(|| Regex::new(r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap());
+    let Ok(text) = std::fs::read_to_string(file) else {
+        return;
+    };
+    let base = file.parent().unwrap_or(root);
+    for (idx, line) in text.lines().enumerate() {
+        for cap in re.captures_iter(line) {
+            let target = &cap[1];
+            if target.starts_with('#') || target.starts_with('/') || target.contains("://") {
+                continue;
+            }
+            if target.starts_with("mailto:") || target.starts_with("data:") {
+                continue;
+            }
+            let path_part = target.split(['#', '?']).next().unwrap_or(target);
+            if path_part.is_empty() {
+                continue;
+            }
+            let decoded = urlencoding::decode(path_part)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| path_part.to_string());
+            if !base.join(&decoded).exists() {
+                report.push(
+                    "broken_link",
+                    LintSeverity::Warning,
+                    format!("relative link `{}` does not resolve", target),
+                    Some(rel.to_string()),
+                    Some(idx + 1),
+                );
+            }
+        }
+    }
+}
+
+pub(crate) fn relative_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+#[path = "tests/skill_lint.rs"]
+mod tests;
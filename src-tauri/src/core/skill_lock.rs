@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+/// How long a mutation waits for another one on the same skill; long enough for a slow
+/// git update to finish.
+pub const DEFAULT_LOCK_WAIT: Duration = Duration::from_secs(300);
+
+static SKILL_LOCKS: SkillLocks = SkillLocks::new();
+
+#[derive(Default)]
+struct Slot {
+    running: Option<String>,
+    /// Waiting operations, first come first served.
+    queued: Vec<String>,
+}
+
+/// Per-skill mutual exclusion for operations that touch a skill's central dir or targets.
+/// A second mutation on the same skill waits its turn; asking for an operation that is already
+/// running or queued for that skill fails with `OPERATION_IN_PROGRESS` instead.
+pub struct SkillLocks {
+    slots: Mutex<BTreeMap<String, Slot>>,
+    released: Condvar,
+}
+
+/// Held for the duration of one operation; releasing it lets the next queued one run.
+#[must_use]
+pub struct SkillLockGuard<'a> {
+    locks: &'a SkillLocks,
+    skill_id: String,
+}
+
+/// Take the process-wide lock for `skill_id`. `operation` names the mutation, e.g. `update` or
+/// `sync:cursor`, and is what duplicates are detected by.
+pub fn lock_skill(skill_id: &str, operation: &str) -> Result<SkillLockGuard<'static>> {
+    SKILL_LOCKS.acquire(skill_id, operation, DEFAULT_LOCK_WAIT)
+}
+
+/// Like `lock_skill`, but gives up with `OPERATION_IN_PROGRESS` after `wait`. For background
+/// work that should step aside rather than queue behind a long mutation.
+pub fn lock_skill_within(
+    skill_id: &str,
+    operation: &str,
+    wait: Duration,
+) -> Result<SkillLockGuard<'static>> {
+    SKILL_LOCKS.acquire(skill_id, operation, wait)
+}
+
+impl SkillLocks {
+    pub const fn new() -> Self {
+        Self {
+            slots: Mutex::new(BTreeMap::new()),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn acquire(
+        &self,
+        skill_id: &str,
+        operation: &str,
+        wait: Duration,
+    ) -> Result<SkillLockGuard<'_>> {
+        let mut slots = self.lock();
+        let slot = slots.entry(skill_id.to_string()).or_default();
+        if slot.running.as_deref() == Some(operation) || slot.queued.iter().any(|q| q == operation)
+        {
+            anyhow::bail!("OPERATION_IN_PROGRESS|{}|{}", skill_id, operation);
+        }
+        slot.queued.push(operation.to_string());
+
+        let deadline = Instant::now() + wait;
+        loop {
+            let slot = slots.get_mut(skill_id).expect("slot kept while queued");
+            if slot.running.is_none() && slot.queued.first().map(String::as_str) == Some(operation)
+            {
+                slot.queued.remove(0);
+                slot.running = Some(operation.to_string());
+                return Ok(SkillLockGuard {
+                    locks: self,
+                    skill_id: skill_id.to_string(),
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                let running = slot.running.clone().unwrap_or_default();
+                slot.queued.retain(|q| q != operation);
+                if slot.running.is_none() && slot.queued.is_empty() {
+                    slots.remove(skill_id);
+                }
+                // Our leaving may put someone else at the head of the queue.
+                self.released.notify_all();
+                anyhow::bail!("OPERATION_IN_PROGRESS|{}|{}", skill_id, running);
+            }
+            slots = self
+                .released
+                .wait_timeout(slots, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+    }
+
+    fn release(&self, skill_id: &str) {
+        let mut slots = self.lock();
+        if let Some(slot) = slots.get_mut(skill_id) {
+            slot.running = None;
+            if slot.queued.is_empty() {
+                slots.remove(skill_id);
+            }
+        }
+        self.released.notify_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Slot>> {
+        self.slots
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for SkillLocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SkillLockGuard<'_> {
+    fn drop(&mut self) {
+        self.locks.release(&self.skill_id);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_lock.rs"]
+mod tests;
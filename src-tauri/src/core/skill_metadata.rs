@@ -1,10 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs;
 use log::warn;
+use std::collections::BTreeMap;
+
+use super::locale::pick_localized;
+use super::skill_files::write_file_atomic;
+use super::skill_lint::LintSeverity;
+
+/// Version reported for skills whose manifest/frontmatter does not declare one.
+pub const UNVERSIONED: &str = "0.0.0";
+/// Version written into a generated skill.yaml when the frontmatter has none.
+pub const DEFAULT_GENERATED_VERSION: &str = "0.1.0";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SkillMetadata {
+    /// Default-locale name; per-locale values live in `name_i18n`.
     pub name: String,
     pub version: String,
     #[serde(default)]
@@ -15,13 +26,787 @@ pub struct SkillMetadata {
     pub tags: Vec<String>,
     #[serde(default)]
     pub dependencies: Vec<String>,
+    /// SPDX identifier or expression, e.g. `MIT`.
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Tool keys (`cursor`, `claude_code`, ...) the skill supports; empty means all tools.
+    /// Distinct from the frontmatter `allowed-tools`, which lists agent permissions.
+    #[serde(default, alias = "compatible-tools")]
+    pub compatible_tools: Vec<String>,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Where the replacement lives: a git URL or `owner/repo[/tree/...]` shorthand.
+    #[serde(default)]
+    pub successor: Option<String>,
+    /// Locale tag -> value, from `name: {en: .., zh: ..}` or `name.zh: ..` in the manifest.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub name_i18n: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub description_i18n: BTreeMap<String, String>,
+}
+
+/// Parsed YAML frontmatter block of a `SKILL.md`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SkillFrontmatter {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Accepts either a YAML list or a comma/space separated string.
+    #[serde(
+        default,
+        rename = "allowed-tools",
+        deserialize_with = "deserialize_tool_list"
+    )]
+    pub allowed_tools: Vec<String>,
+    /// Free-form `metadata:` map (author, version, ...) as used by the Agent Skills spec.
+    #[serde(default)]
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+    /// Any other top-level keys, kept so callers can inspect them without re-parsing.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl SkillFrontmatter {
+    /// Parse the `---` delimited block at the top of a SKILL.md.
+    /// Errors are short reason codes (`invalid_frontmatter`) matching the installer's.
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let mut lines = text.lines();
+        if lines.next().map(|v| v.trim()) != Some("---") {
+            return Err("invalid_frontmatter");
+        }
+        let mut block = String::new();
+        let mut found_end = false;
+        for line in lines {
+            if line.trim() == "---" {
+                found_end = true;
+                break;
+            }
+            block.push_str(line);
+            block.push('\n');
+        }
+        if !found_end {
+            return Err("invalid_frontmatter");
+        }
+        if block.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        // Plenty of published skills have unquoted colons in single-line values, which is not
+        // valid YAML; fall back to the historical line-based reading instead of rejecting them.
+        Ok(serde_yaml::from_str(&block).unwrap_or_else(|_| Self::parse_lines(&block)))
+    }
+
+    fn parse_lines(block: &str) -> Self {
+        let mut fm = Self::default();
+        for line in block.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = Some(value.trim().trim_matches('"').to_string());
+            match key {
+                "name" => fm.name = value,
+                "description" => fm.description = value,
+                "version" => fm.version = value,
+                "license" => fm.license = value,
+                _ => {}
+            }
+        }
+        fm
+    }
+
+    pub fn read(path: &Path) -> Result<Self, &'static str> {
+        let text = fs::read_to_string(path).map_err(|_| "read_failed")?;
+        Self::parse(&text)
+    }
+
+    fn metadata_str(&self, key: &str) -> Option<String> {
+        match self.metadata.get(key)? {
+            serde_yaml::Value::String(s) => Some(s.clone()),
+            serde_yaml::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    /// A `metadata:` entry written either as a YAML list or a comma separated string.
+    fn metadata_list(&self, key: &str) -> Vec<String> {
+        match self.metadata.get(key) {
+            Some(serde_yaml::Value::Sequence(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            Some(serde_yaml::Value::String(raw)) => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn lift_localized(map: &mut serde_yaml::Mapping, field: &str) {
+    let mut values: BTreeMap<String, String> = BTreeMap::new();
+    if let Some(serde_yaml::Value::Mapping(localized)) = map.get(field) {
+        for (k, v) in localized {
+            if let (Some(k), Some(v)) = (k.as_str(), v.as_str()) {
+                values.insert(k.to_string(), v.to_string());
+            }
+        }
+    }
+    let prefix = format!("{}.", field);
+    let dotted: Vec<serde_yaml::Value> = map
+        .keys()
+        .filter(|k| k.as_str().is_some_and(|k| k.starts_with(&prefix)))
+        .cloned()
+        .collect();
+    for key in dotted {
+        if let Some(serde_yaml::Value::String(v)) = map.remove(&key) {
+            let locale = &key.as_str().unwrap_or_default()[prefix.len()..];
+            values.insert(locale.to_string(), v);
+        }
+    }
+    if values.is_empty() {
+        return;
+    }
+
+    let plain_missing = !matches!(map.get(field), Some(serde_yaml::Value::String(_)));
+    if plain_missing {
+        let fallback = pick_localized(&values, None)
+            .or_else(|| values.values().next().map(String::as_str))
+            .unwrap_or_default()
+            .to_string();
+        map.insert(field.into(), fallback.into());
+    }
+    let localized: serde_yaml::Mapping = values
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect();
+    map.insert(
+        format!("{}_i18n", field).into(),
+        serde_yaml::Value::Mapping(localized),
+    );
+}
+
+/// Split a SKILL.md into its frontmatter block and the body after the closing `---`.
+pub(crate) fn split_frontmatter(text: &str) -> Option<(&str, &str)> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut offset = 0;
+    let mut block_start = None;
+    for line in text.split_inclusive('\n') {
+        let end = offset + line.len();
+        if line.trim() == "---" {
+            match block_start {
+                None if offset == 0 => block_start = Some(end),
+                Some(start) => return Some((&text[start..offset], &text[end..])),
+                None => return None,
+            }
+        } else if block_start.is_none() {
+            return None;
+        }
+        offset = end;
+    }
+    None
+}
+
+/// Derive a skill.yaml from SKILL.md frontmatter for skills without a manifest. Returns the
+/// written path, or `None` when a skill.yaml/skill.yml/skill.json already exists.
+pub fn generate_skill_yaml(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    if ["skill.yaml", "skill.yml", "skill.json"]
+        .iter()
+        .any(|f| dir.join(f).exists())
+    {
+        return Ok(None);
+    }
+    let skill_md = dir.join("SKILL.md");
+    let fm = SkillFrontmatter::read(&skill_md)
+        .map_err(|reason| anyhow::anyhow!("SKILL_INVALID|{}", reason))?;
+
+    let name = fm
+        .name
+        .clone()
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .ok_or_else(|| anyhow::anyhow!("SKILL_INVALID|missing_name"))?;
+    let version = fm
+        .version
+        .clone()
+        .or_else(|| fm.metadata_str("version"))
+        .unwrap_or_else(|| DEFAULT_GENERATED_VERSION.to_string());
+
+    let mut doc = serde_yaml::Mapping::new();
+    doc.insert("name".into(), name.into());
+    doc.insert("version".into(), version.into());
+    let patch = SkillMetadataPatch {
+        description: fm.description.clone(),
+        version: None,
+        author: fm.metadata_str("author"),
+        tags: Some(fm.metadata_list("tags")).filter(|v| !v.is_empty()),
+        dependencies: Some(fm.metadata_list("dependencies")).filter(|v| !v.is_empty()),
+        license: fm.license.clone(),
+    };
+    for (key, value) in patch.yaml_entries() {
+        doc.insert(key.into(), value);
+    }
+
+    let path = dir.join("skill.yaml");
+    write_file_atomic(&path, serde_yaml::to_string(&doc)?.as_bytes())?;
+    Ok(Some(path))
+}
+
+/// Partial edit of a skill's metadata; `None` fields are left untouched.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SkillMetadataPatch {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub dependencies: Option<Vec<String>>,
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+impl SkillMetadataPatch {
+    fn yaml_entries(&self) -> Vec<(&'static str, serde_yaml::Value)> {
+        let mut out = Vec::new();
+        let string = |v: &String| serde_yaml::Value::String(v.clone());
+        let list = |v: &Vec<String>| {
+            serde_yaml::Value::Sequence(
+                v.iter()
+                    .map(|s| serde_yaml::Value::String(s.clone()))
+                    .collect(),
+            )
+        };
+        if let Some(v) = &self.description {
+            out.push(("description", string(v)));
+        }
+        if let Some(v) = &self.version {
+            out.push(("version", string(v)));
+        }
+        if let Some(v) = &self.author {
+            out.push(("author", string(v)));
+        }
+        if let Some(v) = &self.tags {
+            out.push(("tags", list(v)));
+        }
+        if let Some(v) = &self.dependencies {
+            out.push(("dependencies", list(v)));
+        }
+        if let Some(v) = &self.license {
+            out.push(("license", string(v)));
+        }
+        out
+    }
+}
+
+/// Write `patch` into the file `SkillMetadata::load` reads for `dir` and return its path.
+/// SKILL.md frontmatter keeps `description`/`license` top-level and puts the rest under
+/// `metadata:`, as the Agent Skills spec expects. Only the patched keys are rewritten, so
+/// comments, key order and formatting elsewhere in the file survive.
+pub fn write_metadata_patch(dir: &Path, patch: &SkillMetadataPatch) -> anyhow::Result<PathBuf> {
+    use anyhow::Context;
+
+    for file in ["skill.yaml", "skill.yml"] {
+        let path = dir.join(file);
+        if !path.exists() {
+            continue;
+        }
+        let mut text = fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+        serde_yaml::from_str::<serde_yaml::Mapping>(&text)
+            .with_context(|| format!("parse {:?}", path))?;
+        for (key, value) in patch.yaml_entries() {
+            text = set_yaml_key(&text, None, key, &value)
+                .with_context(|| format!("update {} in {:?}", key, path))?;
+        }
+        write_file_atomic(&path, text.as_bytes())?;
+        return Ok(path);
+    }
+
+    let json_path = dir.join("skill.json");
+    if json_path.exists() {
+        let mut text =
+            fs::read_to_string(&json_path).with_context(|| format!("read {:?}", json_path))?;
+        for (key, value) in patch.yaml_entries() {
+            text = set_json_key(&text, key, &serde_json::to_value(value)?)
+                .with_context(|| format!("update {} in {:?}", key, json_path))?;
+        }
+        write_file_atomic(&json_path, text.as_bytes())?;
+        return Ok(json_path);
+    }
+
+    let skill_md = dir.join("SKILL.md");
+    let raw = fs::read_to_string(&skill_md).with_context(|| format!("read {:?}", skill_md))?;
+    let (block, body) = split_frontmatter(&raw)
+        .ok_or_else(|| anyhow::anyhow!("SKILL.md has no frontmatter block"))?;
+    let doc: serde_yaml::Mapping = if block.trim().is_empty() {
+        serde_yaml::Mapping::new()
+    } else {
+        serde_yaml::from_str(block)
+            .context("SKILL.md frontmatter is not valid YAML; fix it by hand first")?
+    };
+    let mut new_block = block.to_string();
+    for (key, value) in patch.yaml_entries() {
+        let top_level = matches!(key, "description" | "license")
+            || (key == "version" && doc.contains_key("version"));
+        let parent = (!top_level).then_some("metadata");
+        new_block = set_yaml_key(&new_block, parent, key, &value)
+            .with_context(|| format!("update {} in SKILL.md frontmatter", key))?;
+    }
+    // Both slices point into `raw`, so everything around the block is kept byte for byte.
+    let block_start = block.as_ptr() as usize - raw.as_ptr() as usize;
+    let body_start = body.as_ptr() as usize - raw.as_ptr() as usize;
+    let block_end = block_start + block.len();
+    let text = format!(
+        "{}{}{}{}",
+        &raw[..block_start],
+        new_block,
+        &raw[block_end..body_start],
+        body
+    );
+    write_file_atomic(&skill_md, text.as_bytes())?;
+    Ok(skill_md)
+}
+
+/// Set `key` (nested under the top-level mapping `parent`, if given) in a block-style YAML
+/// document by replacing just the lines of its current value, or appending it. The result is
+/// parsed back and refused unless it holds `value`, e.g. for flow-style mappings.
+fn set_yaml_key(
+    text: &str,
+    parent: Option<&str>,
+    key: &str,
+    value: &serde_yaml::Value,
+) -> anyhow::Result<String> {
+    let mut lines: Vec<String> = text.split_inclusive('\n').map(str::to_string).collect();
+    if let Some(last) = lines.last_mut() {
+        if !last.ends_with('\n') {
+            last.push('\n');
+        }
+    }
+
+    let len = lines.len();
+    match parent {
+        None => replace_yaml_entry(&mut lines, 0, len, 0, key, value)?,
+        Some(parent) => match find_yaml_key(&lines, 0, len, 0, parent) {
+            Some(start) => {
+                let inline = lines[start]
+                    .split_once(':')
+                    .map(|(_, rest)| rest)
+                    .unwrap_or("");
+                let inline = inline.split(" #").next().unwrap_or("").trim();
+                if !inline.is_empty() {
+                    anyhow::bail!("`{}` is not a block mapping", parent);
+                }
+                let end = yaml_entry_end(&lines, start, 0);
+                let indent = lines[start + 1..end]
+                    .iter()
+                    .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+                    .map(|l| yaml_indent(l))
+                    .unwrap_or(2);
+                replace_yaml_entry(&mut lines, start + 1, end, indent, key, value)?;
+            }
+            None => {
+                lines.push(format!("{}:\n", parent));
+                let len = lines.len();
+                replace_yaml_entry(&mut lines, len, len, 2, key, value)?;
+            }
+        },
+    }
+
+    let out = lines.concat();
+    let doc: serde_yaml::Value = serde_yaml::from_str(&out)?;
+    let scope = match parent {
+        Some(parent) => doc.get(parent),
+        None => Some(&doc),
+    };
+    if scope.and_then(|m| m.get(key)) != Some(value) {
+        anyhow::bail!("could not update `{}` in place; edit the file by hand", key);
+    }
+    Ok(out)
+}
+
+/// Replace the entry for `key` at `indent` within `lines[from..to]`, or insert it at `to`.
+fn replace_yaml_entry(
+    lines: &mut Vec<String>,
+    from: usize,
+    to: usize,
+    indent: usize,
+    key: &str,
+    value: &serde_yaml::Value,
+) -> anyhow::Result<()> {
+    let mut entry = serde_yaml::Mapping::new();
+    entry.insert(key.into(), value.clone());
+    let pad = " ".repeat(indent);
+    let rendered: Vec<String> = serde_yaml::to_string(&entry)?
+        .lines()
+        .map(|l| format!("{}{}\n", pad, l))
+        .collect();
+    match find_yaml_key(lines, from, to, indent, key) {
+        Some(start) => {
+            let end = yaml_entry_end(lines, start, indent);
+            lines.splice(start..end, rendered);
+        }
+        None => {
+            // Keep trailing blank lines and comments of the section after the new entry.
+            let mut at = to;
+            while at > from && lines[at - 1].trim().is_empty() {
+                at -= 1;
+            }
+            lines.splice(at..at, rendered);
+        }
+    }
+    Ok(())
+}
+
+fn find_yaml_key(
+    lines: &[String],
+    from: usize,
+    to: usize,
+    indent: usize,
+    key: &str,
+) -> Option<usize> {
+    (from..to).find(|&i| {
+        let line = &lines[i];
+        if yaml_indent(line) != indent {
+            return false;
+        }
+        let rest = &line[indent..];
+        let quoted = [format!("\"{}\"", key), format!("'{}'", key)];
+        std::iter::once(key)
+            .chain(quoted.iter().map(String::as_str))
+            .any(|k| {
+                rest.strip_prefix(k)
+                    .and_then(|r| r.strip_prefix(':'))
+                    .is_some_and(|r| r.is_empty() || r.starts_with(char::is_whitespace))
+            })
+    })
+}
+
+/// End (exclusive) of the entry starting at `start`: deeper-indented lines, plus `- ` items at
+/// the same indent, which YAML allows for sequences. Trailing blank lines are not included.
+fn yaml_entry_end(lines: &[String], start: usize, indent: usize) -> usize {
+    let mut end = start + 1;
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        let line_indent = yaml_indent(line);
+        let same_level_item = line_indent == indent && line[indent..].starts_with("- ");
+        if line_indent <= indent && !same_level_item {
+            break;
+        }
+        i += 1;
+        end = i;
+    }
+    end
+}
+
+fn yaml_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Set a top-level `key` of a JSON object by splicing only its value text (or appending the
+/// key before the closing brace); the rest of the file keeps its order and layout.
+fn set_json_key(text: &str, key: &str, value: &serde_json::Value) -> anyhow::Result<String> {
+    let rendered = serde_json::to_string(value)?;
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_start = 0;
+    let mut last_string: Option<(usize, usize)> = None;
+    let mut value_start: Option<usize> = None;
+    let mut closing = None;
+    let mut out = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    last_string = Some((string_start, i + 1));
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                string_start = i;
+            }
+            b':' if depth == 1 => {
+                let (s, e) = last_string.ok_or_else(|| anyhow::anyhow!("malformed JSON"))?;
+                let name: String = serde_json::from_str(&text[s..e])?;
+                value_start = (name == key).then_some(i + 1);
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                if depth == 1 {
+                    if let Some(start) = value_start.take() {
+                        out = Some((start, i));
+                    }
+                    closing = Some(i);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            b',' if depth == 1 => {
+                if let Some(start) = value_start.take() {
+                    out = Some((start, i));
+                }
+            }
+            _ => {}
+        }
+        if out.is_some() {
+            break;
+        }
+    }
+
+    let patched = match (out, closing) {
+        (Some((start, end)), _) => {
+            let old = &text[start..end];
+            let lead = &old[..old.len() - old.trim_start().len()];
+            let trail = &old[old.trim_end().len()..];
+            let head = &text[..start];
+            format!("{}{}{}{}{}", head, lead, rendered, trail, &text[end..])
+        }
+        (None, Some(close)) => {
+            let head = text[..close].trim_end();
+            let sep = if head.ends_with('{') { "" } else { "," };
+            format!(
+                "{}{}\n  {}: {}\n{}",
+                head,
+                sep,
+                serde_json::to_string(key)?,
+                rendered,
+                &text[close..]
+            )
+        }
+        (None, None) => anyhow::bail!("skill.json is not a JSON object"),
+    };
+    let doc: serde_json::Value = serde_json::from_str(&patched)?;
+    if doc.get(key) != Some(value) {
+        anyhow::bail!("could not update `{}` in place; edit the file by hand", key);
+    }
+    Ok(patched)
+}
+
+/// One problem found in a SKILL.md frontmatter block. `code` keeps the historical reason
+/// strings (`invalid_frontmatter`, `missing_name`) so existing callers can still branch on it.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FrontmatterIssue {
+    pub code: String,
+    pub severity: LintSeverity,
+    pub field: Option<String>,
+    /// 1-based line in SKILL.md.
+    pub line: Option<usize>,
+    pub message: String,
+    pub expected: Option<String>,
+}
+
+impl FrontmatterIssue {
+    fn new(code: &str, severity: LintSeverity, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            severity,
+            field: None,
+            line: None,
+            message: message.into(),
+            expected: None,
+        }
+    }
+
+    fn field(mut self, field: &str, line: Option<usize>) -> Self {
+        self.field = Some(field.to_string());
+        self.line = line;
+        self
+    }
+
+    fn expected(mut self, expected: &str) -> Self {
+        self.expected = Some(expected.to_string());
+        self
+    }
+}
+
+/// Check a SKILL.md's frontmatter and explain what is wrong and where. An empty result or
+/// warnings only means the skill can be installed.
+pub fn validate_frontmatter(text: &str) -> Vec<FrontmatterIssue> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.first().map(|l| l.trim()) != Some("---") {
+        return vec![FrontmatterIssue::new(
+            "invalid_frontmatter",
+            LintSeverity::Error,
+            "SKILL.md must start with a frontmatter block",
+        )
+        .field("frontmatter", Some(1))
+        .expected("`---` on the first line, then `name: ...`, then a closing `---`")];
+    }
+    let Some(end) = lines.iter().skip(1).position(|l| l.trim() == "---") else {
+        return vec![FrontmatterIssue::new(
+            "invalid_frontmatter",
+            LintSeverity::Error,
+            "frontmatter block is never closed",
+        )
+        .field("frontmatter", Some(lines.len()))
+        .expected("a line containing only `---` after the metadata")];
+    };
+    let block_lines = &lines[1..=end];
+    let block = block_lines.join("\n");
+    // Line of a top-level key, 1-based in the whole file.
+    let key_line = |key: &str| {
+        block_lines
+            .iter()
+            .position(|l| l.starts_with(&format!("{}:", key)))
+            .map(|idx| idx + 2)
+    };
+
+    let mut issues = Vec::new();
+    let doc = if block.trim().is_empty() {
+        serde_yaml::Mapping::new()
+    } else {
+        match serde_yaml::from_str::<serde_yaml::Value>(&block) {
+            Ok(serde_yaml::Value::Mapping(map)) => map,
+            Ok(_) => {
+                issues.push(
+                    FrontmatterIssue::new(
+                        "invalid_frontmatter",
+                        LintSeverity::Error,
+                        "frontmatter must be a set of `key: value` lines",
+                    )
+                    .field("frontmatter", Some(2))
+                    .expected("a YAML mapping"),
+                );
+                return issues;
+            }
+            Err(err) => {
+                let line = err.location().map(|loc| loc.line() + 1);
+                issues.push(
+                    FrontmatterIssue::new(
+                        "yaml_fallback",
+                        LintSeverity::Warning,
+                        format!(
+                            "frontmatter is not valid YAML ({}); values were read line by line",
+                            err
+                        ),
+                    )
+                    .field("frontmatter", line)
+                    .expected("quote values containing `: `, e.g. description: \"When: ...\""),
+                );
+                let fm = SkillFrontmatter::parse_lines(&block);
+                let mut map = serde_yaml::Mapping::new();
+                for (key, value) in [("name", fm.name), ("description", fm.description)] {
+                    if let Some(value) = value {
+                        map.insert(key.into(), value.into());
+                    }
+                }
+                map
+            }
+        }
+    };
+
+    for field in ["name", "description", "version", "license"] {
+        match doc.get(field) {
+            None | Some(serde_yaml::Value::String(_)) | Some(serde_yaml::Value::Null) => {}
+            Some(serde_yaml::Value::Number(_)) if field == "version" => {}
+            Some(_) => issues.push(
+                FrontmatterIssue::new(
+                    "invalid_field_type",
+                    LintSeverity::Error,
+                    format!("`{}` must be a single text value", field),
+                )
+                .field(field, key_line(field))
+                .expected("a string"),
+            ),
+        }
+    }
+    let text_of = |field: &str| {
+        doc.get(field)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let name_has_issue = issues.iter().any(|i| i.field.as_deref() == Some("name"));
+    if text_of("name").is_none() && !name_has_issue {
+        issues.push(
+            FrontmatterIssue::new("missing_name", LintSeverity::Error, "frontmatter has no `name`")
+                .field("name", key_line("name").or(Some(2)))
+                .expected("name: my-skill"),
+        );
+    }
+    if text_of("description").is_none() {
+        issues.push(
+            FrontmatterIssue::new(
+                "missing_description",
+                LintSeverity::Warning,
+                "frontmatter has no `description`; agents use it to decide when to load the skill",
+            )
+            .field("description", key_line("description"))
+            .expected("description: What the skill does and when to use it"),
+        );
+    }
+    issues
+}
+
+pub fn validate_skill_md(path: &Path) -> Vec<FrontmatterIssue> {
+    match fs::read_to_string(path) {
+        Ok(text) => validate_frontmatter(&text),
+        Err(err) => vec![FrontmatterIssue::new(
+            "read_failed",
+            LintSeverity::Error,
+            format!("cannot read SKILL.md: {}", err),
+        )],
+    }
+}
+
+fn deserialize_tool_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ToolList {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<ToolList>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(ToolList::Many(items)) => items,
+        Some(ToolList::One(raw)) => raw
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+    })
 }
 
 impl SkillMetadata {
+    pub fn supports_tool(&self, tool: &str) -> bool {
+        self.compatible_tools.is_empty()
+            || self
+                .compatible_tools
+                .iter()
+                .any(|t| t.trim().replace('-', "_").eq_ignore_ascii_case(tool))
+    }
+
     pub fn load<P: AsRef<Path>>(path: P) -> Option<Self> {
         let path = path.as_ref();
         
-        // Try skill.yaml first, then skill.json
+        // Try skill.yaml first, then skill.json, then the SKILL.md frontmatter
         let yaml_path = path.join("skill.yaml");
         if yaml_path.exists() {
             return Self::from_yaml(&yaml_path);
@@ -37,12 +822,77 @@ impl SkillMetadata {
             return Self::from_json(&json_path);
         }
 
+        let skill_md = path.join("SKILL.md");
+        if skill_md.exists() {
+            return Self::from_frontmatter(&skill_md);
+        }
+
         None
     }
 
+    fn from_frontmatter(path: &Path) -> Option<Self> {
+        let fm = match SkillFrontmatter::read(path) {
+            Ok(fm) => fm,
+            Err(reason) => {
+                warn!("Failed to parse frontmatter at {:?}: {}", path, reason);
+                return None;
+            }
+        };
+        let version = fm
+            .version
+            .clone()
+            .or_else(|| fm.metadata_str("version"))
+            .unwrap_or_else(|| UNVERSIONED.to_string());
+        let tags = fm.metadata_list("tags");
+        let dependencies = fm.metadata_list("dependencies");
+        let mut compatible_tools = fm.metadata_list("compatible_tools");
+        if compatible_tools.is_empty() {
+            compatible_tools = fm.metadata_list("compatible-tools");
+        }
+        let deprecated = match fm.metadata.get("deprecated") {
+            Some(serde_yaml::Value::Bool(b)) => *b,
+            Some(serde_yaml::Value::String(s)) => s.eq_ignore_ascii_case("true"),
+            _ => false,
+        };
+        let successor = fm.metadata_str("successor");
+        Some(Self {
+            name: fm.name.clone()?,
+            version,
+            author: fm.metadata_str("author"),
+            description: fm.description,
+            tags,
+            dependencies,
+            license: fm.license,
+            compatible_tools,
+            deprecated,
+            successor,
+            name_i18n: BTreeMap::new(),
+            description_i18n: BTreeMap::new(),
+        })
+    }
+
+    pub fn localized_name(&self, lang: Option<&str>) -> &str {
+        pick_localized(&self.name_i18n, lang).unwrap_or(&self.name)
+    }
+
+    pub fn localized_description(&self, lang: Option<&str>) -> Option<&str> {
+        pick_localized(&self.description_i18n, lang).or(self.description.as_deref())
+    }
+
+    /// Parse a manifest document after folding localized `name`/`description` forms into the
+    /// plain field (English or first value) plus its `_i18n` map.
+    fn from_value(mut doc: serde_yaml::Value) -> Result<Self, serde_yaml::Error> {
+        if let serde_yaml::Value::Mapping(map) = &mut doc {
+            for field in ["name", "description"] {
+                lift_localized(map, field);
+            }
+        }
+        serde_yaml::from_value(doc)
+    }
+
     fn from_yaml(path: &Path) -> Option<Self> {
         match fs::read_to_string(path) {
-            Ok(content) => match serde_yaml::from_str(&content) {
+            Ok(content) => match serde_yaml::from_str(&content).and_then(Self::from_value) {
                 Ok(meta) => Some(meta),
                 Err(e) => {
                     warn!("Failed to parse skill.yaml at {:?}: {}", path, e);
@@ -58,7 +908,13 @@ impl SkillMetadata {
 
     fn from_json(path: &Path) -> Option<Self> {
         match fs::read_to_string(path) {
-            Ok(content) => match serde_json::from_str(&content) {
+            Ok(content) => match serde_json::from_str::<serde_json::Value>(&content)
+                .map_err(|e| e.to_string())
+                .and_then(|v| {
+                    serde_yaml::to_value(v)
+                        .and_then(Self::from_value)
+                        .map_err(|e| e.to_string())
+                }) {
                 Ok(meta) => Some(meta),
                 Err(e) => {
                     warn!("Failed to parse skill.json at {:?}: {}", path, e);
@@ -72,3 +928,7 @@ impl SkillMetadata {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "tests/skill_metadata.rs"]
+mod tests;
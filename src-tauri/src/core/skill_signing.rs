@@ -0,0 +1,256 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use blake2::{Blake2b512, Digest};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::content_hash::{hash_dir_limited, FileHashCache};
+use super::skill_files::read_or_create_private_file;
+use super::skill_store::SkillStore;
+
+pub const SIGNING_POLICY_KEY: &str = "signing_policy";
+/// Detached signature shipped inside a skill dir; excluded from the content hash it signs.
+pub const SIGNATURE_FILE: &str = "SKILL.minisig";
+const SIGNING_KEY_FILE: &str = "signing.key";
+
+/// minisign algorithm tags: `Ed` signs the message itself, `ED` its BLAKE2b-512 digest.
+const ALG_LEGACY: [u8; 2] = *b"Ed";
+const ALG_PREHASHED: [u8; 2] = *b"ED";
+const TRUSTED_COMMENT_PREFIX: &str = "trusted comment: ";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedKey {
+    pub name: String,
+    /// minisign public key: the base64 line of a `minisign.pub` file.
+    pub public_key: String,
+}
+
+/// Whose signatures installs accept. With `require_signed` off, unsigned skills and skills
+/// signed by unknown keys install as before; a signature by a trusted key must still verify.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningPolicy {
+    #[serde(default)]
+    pub require_signed: bool,
+    #[serde(default)]
+    pub trusted_keys: Vec<TrustedKey>,
+}
+
+/// This installation's signing key, kept as base64 of key id and seed in the app data dir.
+pub struct SigningKeyPair {
+    key_id: [u8; 8],
+    key: SigningKey,
+}
+
+impl SigningKeyPair {
+    pub fn generate() -> Result<Self> {
+        let mut bytes = [0u8; 40];
+        getrandom::getrandom(&mut bytes)
+            .map_err(|err| anyhow::anyhow!("generate signing key: {}", err))?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    fn from_bytes(bytes: &[u8; 40]) -> Self {
+        let mut key_id = [0u8; 8];
+        key_id.copy_from_slice(&bytes[..8]);
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&bytes[8..]);
+        Self {
+            key_id,
+            key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// Load the key at `path`, creating it (readable by the owner only) on first use.
+    pub fn load_or_create(path: &Path) -> Result<Self> {
+        let raw = read_or_create_private_file(path, || {
+            let pair = Self::generate()?;
+            let mut bytes = pair.key_id.to_vec();
+            bytes.extend_from_slice(pair.key.as_bytes());
+            Ok(STANDARD.encode(bytes).into_bytes())
+        })?;
+        let bytes: [u8; 40] = std::str::from_utf8(&raw)
+            .ok()
+            .and_then(|raw| STANDARD.decode(raw.trim()).ok())
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("invalid signing key file: {:?}", path))?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /// minisign-format public key, as pasted into another installation's trusted keys.
+    pub fn public_key(&self) -> String {
+        let mut bytes = ALG_LEGACY.to_vec();
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(self.key.verifying_key().as_bytes());
+        STANDARD.encode(bytes)
+    }
+}
+
+pub fn signing_key_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .context("failed to resolve app data dir")?;
+    Ok(app_dir.join(SIGNING_KEY_FILE))
+}
+
+/// What is signed: the full content digest of the dir, ignoring the hash size limit so large
+/// files cannot be swapped for others of the same size.
+fn signed_message(dir: &Path) -> Result<String> {
+    hash_dir_limited(dir, &mut FileHashCache::new(), None)
+}
+
+/// Write `SKILL.minisig` into `dir`: a prehashed minisign signature of its content digest that
+/// `minisign -Vm` can also check against a file holding that digest.
+pub fn sign_skill_dir(dir: &Path, pair: &SigningKeyPair, trusted_comment: &str) -> Result<()> {
+    let digest = Blake2b512::digest(signed_message(dir)?.as_bytes());
+    let signature = pair.key.sign(&digest).to_bytes();
+    let mut global = signature.to_vec();
+    global.extend_from_slice(trusted_comment.as_bytes());
+    let global_signature = pair.key.sign(&global).to_bytes();
+
+    let mut line = ALG_PREHASHED.to_vec();
+    line.extend_from_slice(&pair.key_id);
+    line.extend_from_slice(&signature);
+    let content = format!(
+        "untrusted comment: signature from skills hub key {}\n{}\n{}{}\n{}\n",
+        hex::encode_upper(pair.key_id),
+        STANDARD.encode(line),
+        TRUSTED_COMMENT_PREFIX,
+        trusted_comment,
+        STANDARD.encode(global_signature)
+    );
+    let path = dir.join(SIGNATURE_FILE);
+    std::fs::write(&path, content).with_context(|| format!("write {:?}", path))?;
+    Ok(())
+}
+
+/// Key id and key of a minisign public key.
+fn parse_public_key(public_key: &str) -> Option<([u8; 8], VerifyingKey)> {
+    let bytes = STANDARD.decode(public_key.trim()).ok()?;
+    if bytes.len() != 42 || bytes[..2] != ALG_LEGACY {
+        return None;
+    }
+    let key_id: [u8; 8] = bytes[2..10].try_into().ok()?;
+    let key: [u8; 32] = bytes[10..].try_into().ok()?;
+    Some((key_id, VerifyingKey::from_bytes(&key).ok()?))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignatureCheck {
+    Unsigned,
+    /// Verified with the trusted key of this name.
+    Trusted(String),
+    /// Signed by a key that is not trusted; carries the key id in hex.
+    Untrusted(String),
+    /// Malformed, or does not match the content under a trusted key.
+    Invalid,
+}
+
+pub fn check_signature(dir: &Path, trusted_keys: &[TrustedKey]) -> Result<SignatureCheck> {
+    let path = dir.join(SIGNATURE_FILE);
+    if !path.exists() {
+        return Ok(SignatureCheck::Unsigned);
+    }
+    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+    let lines: Vec<&str> = raw.lines().collect();
+    let decoded = |i: usize| lines.get(i).and_then(|l| STANDARD.decode(l.trim()).ok());
+    let (Some(line), Some(global), Some(comment)) = (
+        decoded(1),
+        decoded(3),
+        lines
+            .get(2)
+            .and_then(|l| l.strip_prefix(TRUSTED_COMMENT_PREFIX)),
+    ) else {
+        return Ok(SignatureCheck::Invalid);
+    };
+    if line.len() != 74 || global.len() != 64 {
+        return Ok(SignatureCheck::Invalid);
+    }
+    let key_id = &line[2..10];
+    let Some((name, key)) = trusted_keys.iter().find_map(|trusted| {
+        parse_public_key(&trusted.public_key)
+            .filter(|(id, _)| id == key_id)
+            .map(|(_, key)| (trusted.name.clone(), key))
+    }) else {
+        return Ok(SignatureCheck::Untrusted(hex::encode_upper(key_id)));
+    };
+
+    let message = signed_message(dir)?;
+    let signed = match [line[0], line[1]] {
+        ALG_PREHASHED => Blake2b512::digest(message.as_bytes()).to_vec(),
+        ALG_LEGACY => message.into_bytes(),
+        _ => return Ok(SignatureCheck::Invalid),
+    };
+    let signature = Signature::from_slice(&line[10..])?;
+    let global_signature = Signature::from_slice(&global)?;
+    let mut global_message = line[10..].to_vec();
+    global_message.extend_from_slice(comment.as_bytes());
+    let valid = key.verify(&signed, &signature).is_ok()
+        && key.verify(&global_message, &global_signature).is_ok();
+    Ok(if valid {
+        SignatureCheck::Trusted(name)
+    } else {
+        SignatureCheck::Invalid
+    })
+}
+
+pub fn get_signing_policy(store: &SkillStore) -> SigningPolicy {
+    store
+        .get_setting(SIGNING_POLICY_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the policy; every trusted key must be a minisign Ed25519 public key.
+pub fn set_signing_policy(store: &SkillStore, policy: SigningPolicy) -> Result<SigningPolicy> {
+    let mut trusted_keys: Vec<TrustedKey> = Vec::new();
+    for key in policy.trusted_keys {
+        let public_key = key.public_key.trim().to_string();
+        if parse_public_key(&public_key).is_none() {
+            anyhow::bail!("invalid public key for {}: {}", key.name, public_key);
+        }
+        if !trusted_keys.iter().any(|k| k.public_key == public_key) {
+            trusted_keys.push(TrustedKey {
+                name: key.name.trim().to_string(),
+                public_key,
+            });
+        }
+    }
+    let cleaned = SigningPolicy {
+        require_signed: policy.require_signed,
+        trusted_keys,
+    };
+    store.set_setting(SIGNING_POLICY_KEY, &serde_json::to_string(&cleaned)?)?;
+    Ok(cleaned)
+}
+
+/// Install gate, not bypassed by `force`: a broken signature is always refused, and under
+/// `require_signed` so are unsigned skills (`SIGNATURE_REQUIRED|<name>`) and unknown signers
+/// (`SIGNATURE_UNTRUSTED|<name>|<key id>`).
+pub fn ensure_signature_accepted(store: &SkillStore, name: &str, dir: &Path) -> Result<()> {
+    let policy = get_signing_policy(store);
+    match check_signature(dir, &policy.trusted_keys)? {
+        SignatureCheck::Trusted(signer) => {
+            log::info!("[signing] {} verified, signed by {}", name, signer);
+            Ok(())
+        }
+        SignatureCheck::Invalid => anyhow::bail!("SIGNATURE_INVALID|{}", name),
+        SignatureCheck::Unsigned if policy.require_signed => {
+            anyhow::bail!("SIGNATURE_REQUIRED|{}", name)
+        }
+        SignatureCheck::Untrusted(key_id) if policy.require_signed => {
+            anyhow::bail!("SIGNATURE_UNTRUSTED|{}|{}", name, key_id)
+        }
+        SignatureCheck::Unsigned | SignatureCheck::Untrusted(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_signing.rs"]
+mod tests;
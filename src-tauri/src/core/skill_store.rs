@@ -1,14 +1,25 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
 use tauri::Manager;
 
+use super::content_hash::{CachedFileHash, FileHashCache};
+use super::db_encryption::{is_credential_setting, is_encrypted_value, is_plaintext_db, DbKey};
+
 const DB_FILE_NAME: &str = "skills_hub.db";
 const LEGACY_APP_IDENTIFIERS: &[&str] = &["com.tauri.dev", "com.tauri.dev.skillshub"];
+/// Emitted with a `StoreChange` after every successful write to skills, targets or settings.
+pub const SKILLS_CHANGED_EVENT: &str = "skills-changed";
+
+/// Room for every distinct statement the store prepares, so hot queries are parsed once.
+const STATEMENT_CACHE_CAPACITY: usize = 64;
 
 // Schema versioning: bump when making changes and add a migration step.
-const SCHEMA_VERSION: i32 = 2;
+pub const SCHEMA_VERSION: i32 = 17;
 
 // Minimal schema for MVP: skills, skill_targets, settings, discovered_skills(optional).
 const SCHEMA_V1: &str = r#"
@@ -60,9 +71,244 @@ CREATE INDEX IF NOT EXISTS idx_skills_name ON skills(name);
 CREATE INDEX IF NOT EXISTS idx_skills_updated_at ON skills(updated_at);
 "#;
 
+// `dependency` is the spec as written in metadata; `dependency_skill_id` is set once it
+// resolves to a managed skill.
+const SCHEMA_V3: &str = r#"
+CREATE TABLE IF NOT EXISTS skill_dependencies (
+  skill_id TEXT NOT NULL,
+  dependency TEXT NOT NULL,
+  dependency_skill_id TEXT NULL,
+  PRIMARY KEY(skill_id, dependency),
+  FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE,
+  FOREIGN KEY(dependency_skill_id) REFERENCES skills(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_skill_dependencies_dep ON skill_dependencies(dependency_skill_id);
+"#;
+
+// Size/mtime/digest of each file as last hashed, so `hash_dir_cached` can skip unchanged files.
+const SCHEMA_V6: &str = r#"
+CREATE TABLE IF NOT EXISTS skill_file_hashes (
+  skill_id TEXT NOT NULL,
+  path TEXT NOT NULL,
+  size INTEGER NOT NULL,
+  mtime_ms INTEGER NOT NULL,
+  hash TEXT NOT NULL,
+  PRIMARY KEY(skill_id, path),
+  FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+"#;
+
+// Filters beyond id lookups: duplicate detection by source, status views and per-tool targets.
+const SCHEMA_V7: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_skills_source_ref ON skills(source_ref);
+CREATE INDEX IF NOT EXISTS idx_skills_status ON skills(status);
+CREATE INDEX IF NOT EXISTS idx_skill_targets_tool ON skill_targets(tool);
+"#;
+
+// Named sets of skills, and which one was last activated for each tool.
+const SCHEMA_V9: &str = r#"
+CREATE TABLE IF NOT EXISTS profiles (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL UNIQUE,
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS profile_skills (
+  profile_id TEXT NOT NULL,
+  skill_id TEXT NOT NULL,
+  PRIMARY KEY(profile_id, skill_id),
+  FOREIGN KEY(profile_id) REFERENCES profiles(id) ON DELETE CASCADE,
+  FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS profile_activations (
+  tool TEXT PRIMARY KEY,
+  profile_id TEXT NOT NULL,
+  activated_at INTEGER NOT NULL,
+  FOREIGN KEY(profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+"#;
+
+// Skills synced into a project's own tool dirs, e.g. `<project>/.claude/skills/<name>`. Kept
+// apart from `skill_targets` so one skill can be in any number of projects.
+const SCHEMA_V10: &str = r#"
+CREATE TABLE IF NOT EXISTS project_targets (
+  skill_id TEXT NOT NULL,
+  project TEXT NOT NULL,
+  tool TEXT NOT NULL,
+  target_path TEXT NOT NULL,
+  mode TEXT NOT NULL,
+  synced_at INTEGER NOT NULL,
+  PRIMARY KEY(skill_id, project, tool),
+  FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_targets_project ON project_targets(project);
+"#;
+
+// Project roots the user registered; their skills are the `project_targets` under `path`.
+const SCHEMA_V11: &str = r#"
+CREATE TABLE IF NOT EXISTS workspaces (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  path TEXT NOT NULL UNIQUE,
+  created_at INTEGER NOT NULL
+);
+"#;
+
+// Profiles whose definition is a profile file in a git repo, pulled and compared periodically.
+const SCHEMA_V12: &str = r#"
+CREATE TABLE IF NOT EXISTS team_profiles (
+  profile_id TEXT PRIMARY KEY,
+  url TEXT NOT NULL,
+  revision TEXT NULL,
+  checked_at INTEGER NULL,
+  last_error TEXT NULL,
+  definition TEXT NULL,
+  FOREIGN KEY(profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+"#;
+
+// This machine's adjustments to shared manifests, by skill name; `tool` is '' for every tool.
+const SCHEMA_V13: &str = r#"
+CREATE TABLE IF NOT EXISTS machine_overrides (
+  skill TEXT NOT NULL,
+  tool TEXT NOT NULL DEFAULT '',
+  skip INTEGER NOT NULL DEFAULT 0,
+  target_path TEXT NULL,
+  PRIMARY KEY(skill, tool)
+);
+"#;
+
+// Recorded states of what is synced where; `state` is JSON.
+const SCHEMA_V14: &str = r#"
+CREATE TABLE IF NOT EXISTS setup_snapshots (
+  id TEXT PRIMARY KEY,
+  label TEXT NULL,
+  automatic INTEGER NOT NULL DEFAULT 0,
+  created_at INTEGER NOT NULL,
+  state TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_setup_snapshots_created_at ON setup_snapshots(created_at);
+"#;
+
+// Central repos besides the default one, whose path is the `central_repo_path` setting.
+const SCHEMA_V15: &str = r#"
+CREATE TABLE IF NOT EXISTS central_repos (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  path TEXT NOT NULL UNIQUE,
+  created_at INTEGER NOT NULL
+);
+"#;
+
+// Last measured on-disk size of each skill: its central directory and its copy targets.
+const SCHEMA_V16: &str = r#"
+CREATE TABLE IF NOT EXISTS skill_disk_usage (
+  skill_id TEXT PRIMARY KEY,
+  central_bytes INTEGER NOT NULL,
+  target_bytes INTEGER NOT NULL,
+  computed_at INTEGER NOT NULL,
+  FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+"#;
+
+// Schedules of the background jobs (a NULL interval uses the job's default) and their runs.
+const SCHEMA_V17: &str = r#"
+CREATE TABLE IF NOT EXISTS scheduled_jobs (
+  job_id TEXT PRIMARY KEY,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  interval_secs INTEGER NULL,
+  next_run_at INTEGER NULL
+);
+
+CREATE TABLE IF NOT EXISTS job_runs (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  job_id TEXT NOT NULL,
+  manual INTEGER NOT NULL DEFAULT 0,
+  started_at INTEGER NOT NULL,
+  finished_at INTEGER NOT NULL,
+  ok INTEGER NOT NULL,
+  message TEXT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_job_runs_job_id ON job_runs(job_id, started_at);
+"#;
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let found: i64 = conn.query_row(
+        "SELECT count(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+    Ok(found > 0)
+}
+
 #[derive(Clone, Debug)]
 pub struct SkillStore {
     db_path: PathBuf,
+    /// Opened on first use and shared by clones; queries take turns on it.
+    conn: Arc<Mutex<Option<Connection>>>,
+    listener: ChangeListener,
+    /// Encrypts credential settings, and the whole file in `sqlcipher` builds.
+    db_key: Option<DbKey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    SkillUpdated,
+    SkillDeleted,
+    TargetUpdated,
+    TargetDeleted,
+    SettingChanged,
+    /// A background content hash was stored; the rest of the record is unchanged.
+    ContentHashed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StoreChange {
+    pub kind: ChangeKind,
+    pub skill_ids: Vec<String>,
+    /// Set for target changes.
+    pub tool: Option<String>,
+    /// Set for setting changes.
+    pub key: Option<String>,
+}
+
+impl StoreChange {
+    fn skill(kind: ChangeKind, skill_id: &str) -> Self {
+        Self {
+            kind,
+            skill_ids: vec![skill_id.to_string()],
+            tool: None,
+            key: None,
+        }
+    }
+
+    fn target(kind: ChangeKind, skill_id: &str, tool: &str) -> Self {
+        Self {
+            tool: Some(tool.to_string()),
+            ..Self::skill(kind, skill_id)
+        }
+    }
+}
+
+type ChangeFn = dyn Fn(&StoreChange) + Send + Sync;
+
+/// Shared by every clone of a store, so changes made from any command reach the same listener.
+#[derive(Clone, Default)]
+struct ChangeListener(Option<Arc<ChangeFn>>);
+
+impl std::fmt::Debug for ChangeListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "ChangeListener(Some)"
+        } else {
+            "ChangeListener(None)"
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -80,6 +326,24 @@ pub struct SkillRecord {
     pub last_seen_at: i64,
     pub status: String,
     pub metadata: Option<crate::core::skill_metadata::SkillMetadata>,
+    pub license: Option<String>,
+    /// Estimated tokens of SKILL.md plus the files it links to.
+    pub token_estimate: Option<i64>,
+    /// Whether scripts or binaries ship with the skill; `None` until it is scanned.
+    pub contains_executables: Option<bool>,
+    /// Central repo holding the skill; `None` for the default one.
+    pub repo_id: Option<String>,
+}
+
+/// How much of each row `list_skills_with` loads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillProjection {
+    /// Every column, with `metadata` parsed from its JSON.
+    #[default]
+    Full,
+    /// Skips the metadata column, leaving `metadata` as `None`. Enough for names and paths.
+    Summary,
 }
 
 #[derive(Clone, Debug)]
@@ -94,9 +358,161 @@ pub struct SkillTargetRecord {
     pub synced_at: Option<i64>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkillDependencyRecord {
+    pub skill_id: String,
+    pub dependency: String,
+    pub dependency_skill_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProjectTargetRecord {
+    pub skill_id: String,
+    /// Canonical project directory.
+    pub project: String,
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+    pub synced_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CentralRepoRecord {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillDiskUsageRecord {
+    pub skill_id: String,
+    pub central_bytes: i64,
+    pub target_bytes: i64,
+    pub computed_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ScheduledJobRecord {
+    pub job_id: String,
+    pub enabled: bool,
+    /// `None` runs the job at its default interval.
+    pub interval_secs: Option<i64>,
+    pub next_run_at: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct JobRunRecord {
+    pub id: i64,
+    pub job_id: String,
+    /// Triggered by the user rather than the schedule.
+    pub manual: bool,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub ok: bool,
+    /// What the job reported, or its error.
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WorkspaceRecord {
+    pub id: String,
+    pub name: String,
+    /// Canonical project directory, matching `ProjectTargetRecord::project`.
+    pub path: String,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProfileRecord {
+    pub id: String,
+    pub name: String,
+    /// Member skills, sorted.
+    pub skill_ids: Vec<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TeamProfileRecord {
+    pub profile_id: String,
+    /// Git URL of the profile file, e.g. a GitHub `blob` URL.
+    pub url: String,
+    /// Commit the file was last read at.
+    pub revision: Option<String>,
+    pub checked_at: Option<i64>,
+    pub last_error: Option<String>,
+    /// The profile file as last read, as JSON.
+    #[serde(skip)]
+    pub definition: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SetupSnapshotRecord {
+    pub id: String,
+    pub label: Option<String>,
+    /// Taken by the app rather than by the user.
+    pub automatic: bool,
+    pub created_at: i64,
+    /// The recorded state, as JSON.
+    #[serde(skip)]
+    pub state: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineOverrideRecord {
+    /// Skill name, as manifests and profile files refer to skills.
+    pub skill: String,
+    /// `None` applies to every tool.
+    pub tool: Option<String>,
+    /// Leave the skill out of applies (or, with a tool, out of syncs to it) on this machine.
+    #[serde(default)]
+    pub skip: bool,
+    /// Sync the skill to this path for `tool` instead of `<tool skills dir>/<name>`.
+    #[serde(default)]
+    pub target_path: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiscoveredSkillRecord {
+    pub id: String,
+    pub tool: String,
+    pub found_path: String,
+    pub name_guess: Option<String>,
+    pub fingerprint: Option<String>,
+    pub found_at: i64,
+    pub imported_skill_id: Option<String>,
+}
+
 impl SkillStore {
     pub fn new(db_path: PathBuf) -> Self {
-        Self { db_path }
+        Self {
+            db_path,
+            conn: Arc::default(),
+            listener: ChangeListener::default(),
+            db_key: None,
+        }
+    }
+
+    /// Encrypt credential settings with `key`; with the `sqlcipher` feature it also unlocks
+    /// the database file, which must then be encrypted with it (see `encrypt_db_if_needed`).
+    pub fn with_db_key(mut self, key: DbKey) -> Self {
+        self.db_key = Some(key);
+        self
+    }
+
+    pub fn db_key(&self) -> Option<&DbKey> {
+        self.db_key.as_ref()
+    }
+
+    /// Call `listener` after each successful write. The app uses this to emit
+    /// `SKILLS_CHANGED_EVENT`; tests can record changes directly.
+    pub fn with_change_listener(
+        mut self,
+        listener: impl Fn(&StoreChange) + Send + Sync + 'static,
+    ) -> Self {
+        self.listener = ChangeListener(Some(Arc::new(listener)));
+        self
     }
 
     #[allow(dead_code)]
@@ -109,30 +525,121 @@ impl SkillStore {
             conn.execute_batch("PRAGMA foreign_keys = ON;")?;
 
             let user_version: i32 = conn.query_row("PRAGMA user_version;", [], |row| row.get(0))?;
-            if user_version == 0 {
-                conn.execute_batch(SCHEMA_V1)?;
-                // V1 -> V2: Add metadata column
-                conn.execute_batch("ALTER TABLE skills ADD COLUMN metadata TEXT NULL;")?;
-                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
-            } else if user_version == 1 {
-                 // V1 -> V2
-                 conn.execute_batch("ALTER TABLE skills ADD COLUMN metadata TEXT NULL;")?;
-                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
-            } else if user_version > SCHEMA_VERSION {
+            if user_version > SCHEMA_VERSION {
                 anyhow::bail!(
                     "database schema version {} is newer than app supports {}",
                     user_version,
                     SCHEMA_VERSION
                 );
             }
+            if user_version < 1 {
+                conn.execute_batch(SCHEMA_V1)?;
+            }
+            if user_version < 2 {
+                // V1 -> V2: Add metadata column
+                conn.execute_batch("ALTER TABLE skills ADD COLUMN metadata TEXT NULL;")?;
+            }
+            if user_version < 3 {
+                // V2 -> V3: dependency edges between skills
+                conn.execute_batch(SCHEMA_V3)?;
+            }
+            if user_version < 4 {
+                // V3 -> V4: detected license (SPDX id)
+                conn.execute_batch("ALTER TABLE skills ADD COLUMN license TEXT NULL;")?;
+            }
+            if user_version < 5 {
+                // V4 -> V5: estimated context footprint
+                conn.execute_batch("ALTER TABLE skills ADD COLUMN token_estimate INTEGER NULL;")?;
+            }
+            if user_version < 6 {
+                // V5 -> V6: per-file hash cache. The directory digest now combines per-file
+                // hashes, so hashes computed the old way would never match again; drop them and
+                // let the hash worker refill skills, and the next scan refill fingerprints.
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(
+                    "UPDATE skills SET content_hash = NULL;
+                     UPDATE discovered_skills SET fingerprint = NULL;",
+                )?;
+            }
+            if user_version < 7 {
+                // V6 -> V7: indices for source, status and tool filters
+                conn.execute_batch(SCHEMA_V7)?;
+            }
+            if user_version < 8 && !has_column(conn, "skills", "contains_executables")? {
+                // V7 -> V8: executable content flag, NULL until the skill is next scanned
+                conn.execute_batch(
+                    "ALTER TABLE skills ADD COLUMN contains_executables INTEGER NULL;",
+                )?;
+            }
+            if user_version < 9 {
+                // V8 -> V9: skill profiles
+                conn.execute_batch(SCHEMA_V9)?;
+            }
+            if user_version < 10 {
+                // V9 -> V10: project-scoped targets
+                conn.execute_batch(SCHEMA_V10)?;
+            }
+            if user_version < 11 {
+                // V10 -> V11: registered workspaces
+                conn.execute_batch(SCHEMA_V11)?;
+            }
+            if user_version < 12 {
+                // V11 -> V12: team profiles tracked from git
+                conn.execute_batch(SCHEMA_V12)?;
+            }
+            if user_version < 13 {
+                // V12 -> V13: machine-specific overrides
+                conn.execute_batch(SCHEMA_V13)?;
+            }
+            if user_version < 14 {
+                // V13 -> V14: setup snapshots
+                conn.execute_batch(SCHEMA_V14)?;
+            }
+            if user_version < 15 {
+                // V14 -> V15: additional central repos; skills without a repo use the default
+                conn.execute_batch(SCHEMA_V15)?;
+                if !has_column(conn, "skills", "repo_id")? {
+                    conn.execute_batch("ALTER TABLE skills ADD COLUMN repo_id TEXT NULL;")?;
+                }
+            }
+            if user_version < 16 {
+                // V15 -> V16: cached disk usage per skill
+                conn.execute_batch(SCHEMA_V16)?;
+            }
+            if user_version < 17 {
+                // V16 -> V17: scheduled background jobs
+                conn.execute_batch(SCHEMA_V17)?;
+            }
+            if user_version < SCHEMA_VERSION {
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            }
 
             Ok(())
         })
     }
 
+    /// `PRAGMA user_version` of the database file, i.e. the last migration applied.
+    pub fn schema_version(&self) -> Result<i32> {
+        self.with_conn(|conn| Ok(conn.query_row("PRAGMA user_version;", [], |row| row.get(0))?))
+    }
+
+    /// The value of `key`, decrypted when it is an encrypted credential setting.
     pub fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        match self.get_setting_raw(key)? {
+            Some(value) if is_encrypted_value(&value) => {
+                let db_key = self.db_key.as_ref().with_context(|| {
+                    format!("setting {} is encrypted and no database key is loaded", key)
+                })?;
+                db_key.decrypt(key, &value).map(Some)
+            }
+            value => Ok(value),
+        }
+    }
+
+    /// The value of `key` as stored, without decrypting it.
+    pub fn get_setting_raw(&self, key: &str) -> Result<Option<String>> {
         self.with_conn(|conn| {
-            let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")?;
+            let mut stmt = conn.prepare_cached("SELECT value FROM settings WHERE key = ?1")?;
             let mut rows = stmt.query(params![key])?;
             Ok(rows
                 .next()?
@@ -141,14 +648,64 @@ impl SkillStore {
         })
     }
 
+    /// Store `value` for `key`; credential settings are encrypted when a database key is loaded.
     pub fn set_setting(&self, key: &str, value: &str) -> Result<()> {
+        let stored = match &self.db_key {
+            Some(db_key) if is_credential_setting(key) => db_key.encrypt(key, value)?,
+            _ => value.to_string(),
+        };
         self.with_conn(|conn| {
             conn.execute(
                 "INSERT INTO settings (key, value) VALUES (?1, ?2)
          ON CONFLICT(key) DO UPDATE SET value = excluded.value",
-                params![key, value],
+                params![key, stored],
             )?;
             Ok(())
+        })?;
+        self.notify(StoreChange {
+            kind: ChangeKind::SettingChanged,
+            skill_ids: Vec::new(),
+            tool: None,
+            key: Some(key.to_string()),
+        });
+        Ok(())
+    }
+
+    /// Drop the setting so its default applies again.
+    pub fn delete_setting(&self, key: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+            Ok(())
+        })?;
+        self.notify(StoreChange {
+            kind: ChangeKind::SettingChanged,
+            skill_ids: Vec::new(),
+            tool: None,
+            key: Some(key.to_string()),
+        });
+        Ok(())
+    }
+
+    /// Every setting as stored; encrypted credential settings are not decrypted.
+    pub fn list_settings(&self) -> Result<Vec<(String, String)>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT key, value FROM settings ORDER BY key")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    /// Write a consistent copy of the database to `dest`, which must not exist yet. The copy of
+    /// an SQLCipher database is plaintext so it restores on other installations.
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        self.with_conn(|conn| {
+            #[cfg(feature = "sqlcipher")]
+            if self.db_key.is_some() {
+                return super::db_encryption::export_conn(conn, dest, None);
+            }
+            conn.execute("VACUUM INTO ?1", params![dest.to_string_lossy()])
+                .with_context(|| format!("back up db to {:?}", dest))?;
+            Ok(())
         })
     }
 
@@ -170,10 +727,12 @@ impl SkillStore {
             conn.execute(
                 "INSERT INTO skills (
           id, name, source_type, source_ref, source_revision, central_path, content_hash,
-          created_at, updated_at, last_sync_at, last_seen_at, status, metadata
+          created_at, updated_at, last_sync_at, last_seen_at, status, metadata, license,
+          token_estimate, contains_executables, repo_id
         ) VALUES (
           ?1, ?2, ?3, ?4, ?5, ?6, ?7,
-          ?8, ?9, ?10, ?11, ?12, ?13
+          ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+          ?15, ?16, ?17
         )
         ON CONFLICT(id) DO UPDATE SET
           name = excluded.name,
@@ -187,7 +746,11 @@ impl SkillStore {
           last_sync_at = excluded.last_sync_at,
           last_seen_at = excluded.last_seen_at,
           status = excluded.status,
-          metadata = excluded.metadata",
+          metadata = excluded.metadata,
+          license = excluded.license,
+          token_estimate = excluded.token_estimate,
+          contains_executables = excluded.contains_executables,
+          repo_id = excluded.repo_id",
                 params![
                     record.id,
                     record.name,
@@ -201,11 +764,17 @@ impl SkillStore {
                     record.last_sync_at,
                     record.last_seen_at,
                     record.status,
-                    metadata_json
+                    metadata_json,
+                    record.license,
+                    record.token_estimate,
+                    record.contains_executables,
+                    record.repo_id
                 ],
             )?;
             Ok(())
-        })
+        })?;
+        self.notify(StoreChange::skill(ChangeKind::SkillUpdated, &record.id));
+        Ok(())
     }
 
     pub fn upsert_skill_target(&self, record: &SkillTargetRecord) -> Result<()> {
@@ -234,17 +803,39 @@ impl SkillStore {
                 ],
             )?;
             Ok(())
-        })
+        })?;
+        self.notify(StoreChange::target(
+            ChangeKind::TargetUpdated,
+            &record.skill_id,
+            &record.tool,
+        ));
+        Ok(())
     }
 
     pub fn list_skills(&self) -> Result<Vec<SkillRecord>> {
-        self.with_conn(|conn| {
-            let mut stmt = conn.prepare(
-        "SELECT id, name, source_type, source_ref, source_revision, central_path, content_hash,
-                created_at, updated_at, last_sync_at, last_seen_at, status, metadata
+        self.list_skills_with(SkillProjection::Full)
+    }
+
+    pub fn list_skills_with(&self, projection: SkillProjection) -> Result<Vec<SkillRecord>> {
+        // Summary selects NULL in place of metadata so both shapes share the row mapping.
+        let sql = match projection {
+            SkillProjection::Full => {
+                "SELECT id, name, source_type, source_ref, source_revision, central_path,
+                content_hash, created_at, updated_at, last_sync_at, last_seen_at, status,
+                metadata, license, token_estimate, contains_executables, repo_id
          FROM skills
-         ORDER BY updated_at DESC",
-      )?;
+         ORDER BY updated_at DESC"
+            }
+            SkillProjection::Summary => {
+                "SELECT id, name, source_type, source_ref, source_revision, central_path,
+                content_hash, created_at, updated_at, last_sync_at, last_seen_at, status,
+                NULL, license, token_estimate, contains_executables, repo_id
+         FROM skills
+         ORDER BY updated_at DESC"
+            }
+        };
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(sql)?;
             let rows = stmt.query_map([], |row| {
                 let metadata_json: Option<String> = row.get(12)?;
                 let metadata = match metadata_json {
@@ -266,6 +857,10 @@ impl SkillStore {
                     last_seen_at: row.get(10)?,
                     status: row.get(11)?,
                     metadata,
+                    license: row.get(13)?,
+                    token_estimate: row.get(14)?,
+                    contains_executables: row.get(15)?,
+                    repo_id: row.get(16)?,
                 })
             })?;
 
@@ -279,9 +874,10 @@ impl SkillStore {
 
     pub fn get_skill_by_id(&self, skill_id: &str) -> Result<Option<SkillRecord>> {
         self.with_conn(|conn| {
-            let mut stmt = conn.prepare(
+            let mut stmt = conn.prepare_cached(
         "SELECT id, name, source_type, source_ref, source_revision, central_path, content_hash,
-                created_at, updated_at, last_sync_at, last_seen_at, status, metadata
+                created_at, updated_at, last_sync_at, last_seen_at, status, metadata, license,
+                token_estimate, contains_executables, repo_id
          FROM skills
          WHERE id = ?1
          LIMIT 1",
@@ -307,6 +903,10 @@ impl SkillStore {
                     last_seen_at: row.get(10)?,
                     status: row.get(11)?,
                     metadata,
+                    license: row.get(13)?,
+                    token_estimate: row.get(14)?,
+                    contains_executables: row.get(15)?,
+                    repo_id: row.get(16)?,
                 }))
             } else {
                 Ok(None)
@@ -314,16 +914,75 @@ impl SkillStore {
         })
     }
 
+    pub fn set_skill_token_estimate(&self, skill_id: &str, tokens: Option<i64>) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET token_estimate = ?2 WHERE id = ?1",
+                params![skill_id, tokens],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::skill(ChangeKind::SkillUpdated, skill_id));
+        Ok(())
+    }
+
+    pub fn set_skill_contains_executables(&self, skill_id: &str, contains: bool) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET contains_executables = ?2 WHERE id = ?1",
+                params![skill_id, contains],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::skill(ChangeKind::SkillUpdated, skill_id));
+        Ok(())
+    }
+
+    pub fn set_skill_status(&self, skill_id: &str, status: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET status = ?2 WHERE id = ?1",
+                params![skill_id, status],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::skill(ChangeKind::SkillUpdated, skill_id));
+        Ok(())
+    }
+
+    /// Store a hash computed after the fact. Only applies while the skill still lives at
+    /// `central_path`; returns whether a row was updated.
+    pub fn set_skill_content_hash(
+        &self,
+        skill_id: &str,
+        central_path: &str,
+        content_hash: &str,
+    ) -> Result<bool> {
+        let updated = self.with_conn(|conn| {
+            Ok(conn.execute(
+                "UPDATE skills SET content_hash = ?3 WHERE id = ?1 AND central_path = ?2",
+                params![skill_id, central_path, content_hash],
+            )?)
+        })?;
+        if updated > 0 {
+            self.notify(StoreChange::skill(ChangeKind::ContentHashed, skill_id));
+        }
+        Ok(updated > 0)
+    }
+
     pub fn delete_skill(&self, skill_id: &str) -> Result<()> {
         self.with_conn(|conn| {
             conn.execute("DELETE FROM skills WHERE id = ?1", params![skill_id])?;
             Ok(())
-        })
+        })?;
+        // Targets and dependencies go with the skill (ON DELETE CASCADE).
+        self.notify(StoreChange::skill(ChangeKind::SkillDeleted, skill_id));
+        Ok(())
     }
 
     pub fn list_skill_targets(&self, skill_id: &str) -> Result<Vec<SkillTargetRecord>> {
         self.with_conn(|conn| {
-            let mut stmt = conn.prepare(
+            let mut stmt = conn.prepare_cached(
                 "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at
          FROM skill_targets
          WHERE skill_id = ?1
@@ -352,7 +1011,7 @@ impl SkillStore {
 
     pub fn list_all_skill_target_paths(&self) -> Result<Vec<(String, String)>> {
         self.with_conn(|conn| {
-            let mut stmt = conn.prepare(
+            let mut stmt = conn.prepare_cached(
                 "SELECT tool, target_path
          FROM skill_targets",
             )?;
@@ -366,13 +1025,33 @@ impl SkillStore {
         })
     }
 
+    /// Targets per tool key, counted from `idx_skill_targets_tool` alone.
+    pub fn count_skill_targets_by_tool(&self) -> Result<BTreeMap<String, u64>> {
+        self.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare_cached("SELECT tool, COUNT(*) FROM skill_targets GROUP BY tool")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok(rows.collect::<rusqlite::Result<_>>()?)
+        })
+    }
+
+    /// Skills per `status`, counted from `idx_skills_status` alone.
+    pub fn count_skills_by_status(&self) -> Result<BTreeMap<String, u64>> {
+        self.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare_cached("SELECT status, COUNT(*) FROM skills GROUP BY status")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok(rows.collect::<rusqlite::Result<_>>()?)
+        })
+    }
+
     pub fn get_skill_target(
         &self,
         skill_id: &str,
         tool: &str,
     ) -> Result<Option<SkillTargetRecord>> {
         self.with_conn(|conn| {
-            let mut stmt = conn.prepare(
+            let mut stmt = conn.prepare_cached(
                 "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at
          FROM skill_targets
          WHERE skill_id = ?1 AND tool = ?2",
@@ -395,6 +1074,29 @@ impl SkillStore {
         })
     }
 
+    pub fn set_skill_target_status(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        status: &str,
+        last_error: Option<&str>,
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skill_targets SET status = ?3, last_error = ?4
+                 WHERE skill_id = ?1 AND tool = ?2",
+                params![skill_id, tool, status, last_error],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::target(
+            ChangeKind::TargetUpdated,
+            skill_id,
+            tool,
+        ));
+        Ok(())
+    }
+
     pub fn delete_skill_target(&self, skill_id: &str, tool: &str) -> Result<()> {
         self.with_conn(|conn| {
             conn.execute(
@@ -402,19 +1104,772 @@ impl SkillStore {
                 params![skill_id, tool],
             )?;
             Ok(())
-        })
-    }
-
-    fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
-        let conn = Connection::open(&self.db_path)
-            .with_context(|| format!("failed to open db at {:?}", self.db_path))?;
-        // Enforce foreign key constraints on every connection (rusqlite PRAGMA is per-connection).
-        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
-        f(&conn)
+        })?;
+        self.notify(StoreChange::target(
+            ChangeKind::TargetDeleted,
+            skill_id,
+            tool,
+        ));
+        Ok(())
     }
-}
 
-pub fn default_db_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
+    pub fn replace_skill_dependencies(
+        &self,
+        skill_id: &str,
+        records: &[SkillDependencyRecord],
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM skill_dependencies WHERE skill_id = ?1",
+                params![skill_id],
+            )?;
+            for record in records {
+                tx.execute(
+                    "INSERT OR REPLACE INTO skill_dependencies (skill_id, dependency, dependency_skill_id)
+         VALUES (?1, ?2, ?3)",
+                    params![skill_id, record.dependency, record.dependency_skill_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::skill(ChangeKind::SkillUpdated, skill_id));
+        Ok(())
+    }
+
+    pub fn load_file_hashes(&self, skill_id: &str) -> Result<FileHashCache> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT path, size, mtime_ms, hash FROM skill_file_hashes WHERE skill_id = ?1",
+            )?;
+            let rows = stmt.query_map(params![skill_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    CachedFileHash {
+                        size: row.get::<_, i64>(1)? as u64,
+                        mtime_ms: row.get(2)?,
+                        hash: row.get(3)?,
+                    },
+                ))
+            })?;
+            Ok(rows.collect::<rusqlite::Result<FileHashCache>>()?)
+        })
+    }
+
+    /// Replace the skill's file hash cache. A cache only, so no change is announced.
+    pub fn save_file_hashes(&self, skill_id: &str, cache: &FileHashCache) -> Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM skill_file_hashes WHERE skill_id = ?1",
+                params![skill_id],
+            )?;
+            for (path, entry) in cache {
+                tx.execute(
+                    "INSERT INTO skill_file_hashes (skill_id, path, size, mtime_ms, hash)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        skill_id,
+                        path,
+                        entry.size as i64,
+                        entry.mtime_ms,
+                        entry.hash
+                    ],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    pub fn list_skill_dependencies(&self, skill_id: &str) -> Result<Vec<SkillDependencyRecord>> {
+        self.query_dependencies(
+            "SELECT skill_id, dependency, dependency_skill_id
+         FROM skill_dependencies
+         WHERE skill_id = ?1
+         ORDER BY dependency ASC",
+            skill_id,
+        )
+    }
+
+    /// Edges pointing at `skill_id`, i.e. the skills that depend on it.
+    pub fn list_skill_dependents(&self, skill_id: &str) -> Result<Vec<SkillDependencyRecord>> {
+        self.query_dependencies(
+            "SELECT skill_id, dependency, dependency_skill_id
+         FROM skill_dependencies
+         WHERE dependency_skill_id = ?1
+         ORDER BY skill_id ASC",
+            skill_id,
+        )
+    }
+
+    pub fn list_unresolved_dependencies(&self) -> Result<Vec<SkillDependencyRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT skill_id, dependency, dependency_skill_id
+         FROM skill_dependencies
+         WHERE dependency_skill_id IS NULL
+         ORDER BY skill_id ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(SkillDependencyRecord {
+                    skill_id: row.get(0)?,
+                    dependency: row.get(1)?,
+                    dependency_skill_id: row.get(2)?,
+                })
+            })?;
+
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    fn query_dependencies(&self, sql: &str, skill_id: &str) -> Result<Vec<SkillDependencyRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(sql)?;
+            let rows = stmt.query_map(params![skill_id], |row| {
+                Ok(SkillDependencyRecord {
+                    skill_id: row.get(0)?,
+                    dependency: row.get(1)?,
+                    dependency_skill_id: row.get(2)?,
+                })
+            })?;
+
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    pub fn upsert_project_target(&self, record: &ProjectTargetRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO project_targets (
+          skill_id, project, tool, target_path, mode, synced_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    record.skill_id,
+                    record.project,
+                    record.tool,
+                    record.target_path,
+                    record.mode,
+                    record.synced_at
+                ],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::target(
+            ChangeKind::TargetUpdated,
+            &record.skill_id,
+            &record.tool,
+        ));
+        Ok(())
+    }
+
+    pub fn list_project_targets(&self, project: &str) -> Result<Vec<ProjectTargetRecord>> {
+        self.query_project_targets(
+            "SELECT skill_id, project, tool, target_path, mode, synced_at
+         FROM project_targets
+         WHERE project = ?1
+         ORDER BY skill_id ASC, tool ASC",
+            project,
+        )
+    }
+
+    pub fn delete_project_target(&self, skill_id: &str, project: &str, tool: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "DELETE FROM project_targets WHERE skill_id = ?1 AND project = ?2 AND tool = ?3",
+                params![skill_id, project, tool],
+            )?;
+            Ok(())
+        })?;
+        self.notify(StoreChange::target(
+            ChangeKind::TargetDeleted,
+            skill_id,
+            tool,
+        ));
+        Ok(())
+    }
+
+    /// Every project `skill_id` is synced into.
+    pub fn list_skill_project_targets(&self, skill_id: &str) -> Result<Vec<ProjectTargetRecord>> {
+        self.query_project_targets(
+            "SELECT skill_id, project, tool, target_path, mode, synced_at
+         FROM project_targets
+         WHERE skill_id = ?1
+         ORDER BY project ASC, tool ASC",
+            skill_id,
+        )
+    }
+
+    fn query_project_targets(&self, sql: &str, key: &str) -> Result<Vec<ProjectTargetRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(sql)?;
+            let rows = stmt.query_map(params![key], |row| {
+                Ok(ProjectTargetRecord {
+                    skill_id: row.get(0)?,
+                    project: row.get(1)?,
+                    tool: row.get(2)?,
+                    target_path: row.get(3)?,
+                    mode: row.get(4)?,
+                    synced_at: row.get(5)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn insert_central_repo(&self, record: &CentralRepoRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO central_repos (id, name, path, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![record.id, record.name, record.path, record.created_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_central_repos(&self) -> Result<Vec<CentralRepoRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, name, path, created_at FROM central_repos ORDER BY name ASC, path ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(CentralRepoRecord {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn delete_central_repo(&self, repo_id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM central_repos WHERE id = ?1", params![repo_id])?;
+            Ok(())
+        })
+    }
+
+    pub fn upsert_skill_disk_usage(&self, record: &SkillDiskUsageRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO skill_disk_usage (skill_id, central_bytes, target_bytes, computed_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(skill_id) DO UPDATE SET
+          central_bytes = excluded.central_bytes,
+          target_bytes = excluded.target_bytes,
+          computed_at = excluded.computed_at",
+                params![
+                    record.skill_id,
+                    record.central_bytes,
+                    record.target_bytes,
+                    record.computed_at
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_skill_disk_usage(&self) -> Result<Vec<SkillDiskUsageRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT skill_id, central_bytes, target_bytes, computed_at FROM skill_disk_usage",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(SkillDiskUsageRecord {
+                    skill_id: row.get(0)?,
+                    central_bytes: row.get(1)?,
+                    target_bytes: row.get(2)?,
+                    computed_at: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn list_scheduled_jobs(&self) -> Result<Vec<ScheduledJobRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT job_id, enabled, interval_secs, next_run_at FROM scheduled_jobs",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(ScheduledJobRecord {
+                    job_id: row.get(0)?,
+                    enabled: row.get(1)?,
+                    interval_secs: row.get(2)?,
+                    next_run_at: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn upsert_scheduled_job(&self, record: &ScheduledJobRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO scheduled_jobs (job_id, enabled, interval_secs, next_run_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(job_id) DO UPDATE SET
+          enabled = excluded.enabled,
+          interval_secs = excluded.interval_secs,
+          next_run_at = excluded.next_run_at",
+                params![
+                    record.job_id,
+                    record.enabled,
+                    record.interval_secs,
+                    record.next_run_at
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Record a run and drop the job's runs beyond the newest `keep`. Returns the run's id.
+    pub fn insert_job_run(&self, record: &JobRunRecord, keep: usize) -> Result<i64> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO job_runs (job_id, manual, started_at, finished_at, ok, message)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    record.job_id,
+                    record.manual,
+                    record.started_at,
+                    record.finished_at,
+                    record.ok,
+                    record.message
+                ],
+            )?;
+            let id = conn.last_insert_rowid();
+            conn.execute(
+                "DELETE FROM job_runs WHERE job_id = ?1 AND id NOT IN (
+           SELECT id FROM job_runs WHERE job_id = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+                params![record.job_id, keep as i64],
+            )?;
+            Ok(id)
+        })
+    }
+
+    /// Newest first.
+    pub fn list_job_runs(&self, job_id: &str, limit: usize) -> Result<Vec<JobRunRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, job_id, manual, started_at, finished_at, ok, message
+         FROM job_runs
+         WHERE job_id = ?1
+         ORDER BY id DESC
+         LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![job_id, limit as i64], |row| {
+                Ok(JobRunRecord {
+                    id: row.get(0)?,
+                    job_id: row.get(1)?,
+                    manual: row.get(2)?,
+                    started_at: row.get(3)?,
+                    finished_at: row.get(4)?,
+                    ok: row.get(5)?,
+                    message: row.get(6)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    /// The most recent failed run of any job.
+    pub fn latest_failed_job_run(&self) -> Result<Option<JobRunRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, job_id, manual, started_at, finished_at, ok, message
+         FROM job_runs
+         WHERE ok = 0
+         ORDER BY finished_at DESC, id DESC
+         LIMIT 1",
+            )?;
+            let mut rows = stmt.query_map([], |row| {
+                Ok(JobRunRecord {
+                    id: row.get(0)?,
+                    job_id: row.get(1)?,
+                    manual: row.get(2)?,
+                    started_at: row.get(3)?,
+                    finished_at: row.get(4)?,
+                    ok: row.get(5)?,
+                    message: row.get(6)?,
+                })
+            })?;
+            Ok(rows.next().transpose()?)
+        })
+    }
+
+    pub fn insert_workspace(&self, record: &WorkspaceRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO workspaces (id, name, path, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![record.id, record.name, record.path, record.created_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_workspaces(&self) -> Result<Vec<WorkspaceRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, name, path, created_at FROM workspaces ORDER BY name ASC, path ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(WorkspaceRecord {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    /// Only the registration goes; skills synced into the project stay.
+    pub fn delete_workspace(&self, workspace_id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "DELETE FROM workspaces WHERE id = ?1",
+                params![workspace_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Insert or rename the profile and replace its members.
+    pub fn upsert_profile(&self, record: &ProfileRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                "INSERT INTO profiles (id, name, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(id) DO UPDATE SET name = excluded.name, updated_at = excluded.updated_at",
+                params![record.id, record.name, record.created_at, record.updated_at],
+            )?;
+            tx.execute(
+                "DELETE FROM profile_skills WHERE profile_id = ?1",
+                params![record.id],
+            )?;
+            for skill_id in &record.skill_ids {
+                tx.execute(
+                    "INSERT OR IGNORE INTO profile_skills (profile_id, skill_id) VALUES (?1, ?2)",
+                    params![record.id, skill_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    pub fn list_profiles(&self) -> Result<Vec<ProfileRecord>> {
+        let mut profiles = self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, name, created_at, updated_at FROM profiles ORDER BY name ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(ProfileRecord {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    skill_ids: Vec::new(),
+                    created_at: row.get(2)?,
+                    updated_at: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })?;
+        for profile in &mut profiles {
+            profile.skill_ids = self.list_profile_skill_ids(&profile.id)?;
+        }
+        Ok(profiles)
+    }
+
+    pub fn get_profile(&self, profile_id: &str) -> Result<Option<ProfileRecord>> {
+        Ok(self
+            .list_profiles()?
+            .into_iter()
+            .find(|profile| profile.id == profile_id))
+    }
+
+    fn list_profile_skill_ids(&self, profile_id: &str) -> Result<Vec<String>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT skill_id FROM profile_skills WHERE profile_id = ?1 ORDER BY skill_id ASC",
+            )?;
+            let rows = stmt.query_map(params![profile_id], |row| row.get(0))?;
+            Ok(rows.collect::<rusqlite::Result<Vec<String>>>()?)
+        })
+    }
+
+    /// Members and activations go with the profile (ON DELETE CASCADE).
+    pub fn delete_profile(&self, profile_id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM profiles WHERE id = ?1", params![profile_id])?;
+            Ok(())
+        })
+    }
+
+    pub fn set_active_profile(&self, tool: &str, profile_id: &str, now: i64) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO profile_activations (tool, profile_id, activated_at)
+         VALUES (?1, ?2, ?3)",
+                params![tool, profile_id, now],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Profile last activated for each tool, keyed by tool.
+    pub fn list_active_profiles(&self) -> Result<BTreeMap<String, String>> {
+        self.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare_cached("SELECT tool, profile_id FROM profile_activations")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok(rows.collect::<rusqlite::Result<BTreeMap<String, String>>>()?)
+        })
+    }
+
+    pub fn upsert_team_profile(&self, record: &TeamProfileRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO team_profiles
+         (profile_id, url, revision, checked_at, last_error, definition)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    record.profile_id,
+                    record.url,
+                    record.revision,
+                    record.checked_at,
+                    record.last_error,
+                    record.definition
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_team_profiles(&self) -> Result<Vec<TeamProfileRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT profile_id, url, revision, checked_at, last_error, definition
+         FROM team_profiles",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(TeamProfileRecord {
+                    profile_id: row.get(0)?,
+                    url: row.get(1)?,
+                    revision: row.get(2)?,
+                    checked_at: row.get(3)?,
+                    last_error: row.get(4)?,
+                    definition: row.get(5)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    /// Stop tracking; the profile itself stays as a local one.
+    pub fn delete_team_profile(&self, profile_id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "DELETE FROM team_profiles WHERE profile_id = ?1",
+                params![profile_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn upsert_machine_override(&self, record: &MachineOverrideRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO machine_overrides (skill, tool, skip, target_path)
+         VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    record.skill,
+                    record.tool.as_deref().unwrap_or(""),
+                    record.skip,
+                    record.target_path
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_machine_overrides(&self) -> Result<Vec<MachineOverrideRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT skill, tool, skip, target_path FROM machine_overrides ORDER BY skill, tool",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let tool: String = row.get(1)?;
+                Ok(MachineOverrideRecord {
+                    skill: row.get(0)?,
+                    tool: (!tool.is_empty()).then_some(tool),
+                    skip: row.get(2)?,
+                    target_path: row.get(3)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn delete_machine_override(&self, skill: &str, tool: Option<&str>) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "DELETE FROM machine_overrides WHERE skill = ?1 AND tool = ?2",
+                params![skill, tool.unwrap_or("")],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn insert_setup_snapshot(&self, record: &SetupSnapshotRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO setup_snapshots (id, label, automatic, created_at, state)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record.id,
+                    record.label,
+                    record.automatic,
+                    record.created_at,
+                    record.state
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Newest first.
+    pub fn list_setup_snapshots(&self) -> Result<Vec<SetupSnapshotRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, label, automatic, created_at, state FROM setup_snapshots
+         ORDER BY created_at DESC, rowid DESC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(SetupSnapshotRecord {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    automatic: row.get(2)?,
+                    created_at: row.get(3)?,
+                    state: row.get(4)?,
+                })
+            })?;
+            Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+        })
+    }
+
+    pub fn delete_setup_snapshot(&self, id: &str) -> Result<bool> {
+        self.with_conn(|conn| {
+            let removed = conn.execute("DELETE FROM setup_snapshots WHERE id = ?1", params![id])?;
+            Ok(removed > 0)
+        })
+    }
+
+    pub fn list_discovered_skills(&self) -> Result<Vec<DiscoveredSkillRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id, tool, found_path, name_guess, fingerprint, found_at, imported_skill_id
+         FROM discovered_skills
+         ORDER BY found_at DESC, found_path ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(DiscoveredSkillRecord {
+                    id: row.get(0)?,
+                    tool: row.get(1)?,
+                    found_path: row.get(2)?,
+                    name_guess: row.get(3)?,
+                    fingerprint: row.get(4)?,
+                    found_at: row.get(5)?,
+                    imported_skill_id: row.get(6)?,
+                })
+            })?;
+
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    /// Replace the discovered set with a fresh scan. Rows already known by (tool, found_path)
+    /// keep their id and `found_at`; rows no longer present are dropped.
+    pub fn replace_discovered_skills(&self, records: &[DiscoveredSkillRecord]) -> Result<()> {
+        let existing = self.list_discovered_skills()?;
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute("DELETE FROM discovered_skills", [])?;
+            for record in records {
+                let prev = existing
+                    .iter()
+                    .find(|e| e.tool == record.tool && e.found_path == record.found_path);
+                tx.execute(
+                    "INSERT INTO discovered_skills (
+          id, tool, found_path, name_guess, fingerprint, found_at, imported_skill_id
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        prev.map(|p| p.id.as_str()).unwrap_or(&record.id),
+                        record.tool,
+                        record.found_path,
+                        record.name_guess,
+                        record.fingerprint,
+                        prev.map(|p| p.found_at).unwrap_or(record.found_at),
+                        record.imported_skill_id
+                    ],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    fn notify(&self, change: StoreChange) {
+        if let Some(listener) = &self.listener.0 {
+            listener(&change);
+        }
+    }
+
+    fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        // A panic in another query can't leave the connection half-used: open transactions
+        // roll back when dropped.
+        let mut slot = self
+            .conn
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let conn = match slot.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = Connection::open(&self.db_path)
+                    .with_context(|| format!("failed to open db at {:?}", self.db_path))?;
+                #[cfg(feature = "sqlcipher")]
+                if let Some(key) = &self.db_key {
+                    super::db_encryption::apply_db_key(&conn, key)?;
+                }
+                // Enforce foreign key constraints (rusqlite PRAGMA is per-connection).
+                conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+                conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+                slot.insert(conn)
+            }
+        };
+        f(conn)
+    }
+}
+
+pub fn default_db_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
     let app_dir = app
         .path()
         .app_data_dir()
@@ -481,6 +1936,10 @@ fn db_has_any_skills(db_path: &Path) -> Result<bool> {
     if !db_path.exists() {
         return Ok(false);
     }
+    // An encrypted database can't be read without its key; it is never replaced.
+    if !is_plaintext_db(db_path)? {
+        return Ok(true);
+    }
 
     let conn =
         Connection::open(db_path).with_context(|| format!("failed to open db at {:?}", db_path))?;
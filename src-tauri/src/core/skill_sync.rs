@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use super::skill_store::{ProjectTargetRecord, SkillStore, SkillTargetRecord};
+use super::sync_engine::{sync_dir_copy_with_overwrite, sync_dir_for_tool_with_overwrite};
+use super::tool_adapters::{adapter_by_key, is_tool_installed};
+
+/// Status of a target moved aside by `set_target_enabled`.
+pub const STATUS_DISABLED: &str = "disabled";
+/// Disabled targets sit in this directory next to the enabled ones. Tools load any directory
+/// holding a SKILL.md, so renaming the target in place would not hide it.
+const DISABLED_DIR: &str = ".disabled";
+
+/// Re-copy the central content into every copy-mode target of a skill.
+/// Symlink/junction targets follow the central dir automatically and are left alone.
+/// Cursor 目前不支持软链/junction，因此无论历史 mode 如何，都需要强制 copy 回灌。
+pub fn resync_copy_targets(
+    store: &SkillStore,
+    skill_id: &str,
+    central_path: &Path,
+    now: i64,
+) -> Result<Vec<String>> {
+    let targets = store.list_skill_targets(skill_id)?;
+    let mut updated_targets: Vec<String> = Vec::new();
+    for t in targets {
+        // Skip if tool not installed anymore.
+        if let Some(adapter) = adapter_by_key(&t.tool) {
+            if !is_tool_installed(&adapter).unwrap_or(false) {
+                continue;
+            }
+        }
+        let force_copy = t.mode == "copy" || t.tool == "cursor";
+        if !force_copy {
+            continue;
+        }
+        let target_path = PathBuf::from(&t.target_path);
+        let sync_res = sync_dir_copy_with_overwrite(central_path, &target_path, true)?;
+        let record = SkillTargetRecord {
+            id: t.id.clone(),
+            skill_id: t.skill_id.clone(),
+            tool: t.tool.clone(),
+            target_path: sync_res.target_path.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: enabled_status(&t.status).to_string(),
+            last_error: None,
+            synced_at: Some(now),
+        };
+        store.upsert_skill_target(&record)?;
+        updated_targets.push(t.tool.clone());
+    }
+    // Copies in projects follow as well; the list returned names global targets only.
+    for t in store.list_skill_project_targets(skill_id)? {
+        if t.mode != "copy" || !Path::new(&t.project).is_dir() {
+            continue;
+        }
+        sync_dir_copy_with_overwrite(central_path, Path::new(&t.target_path), true)?;
+        store.upsert_project_target(&ProjectTargetRecord {
+            synced_at: now,
+            ..t
+        })?;
+    }
+    Ok(updated_targets)
+}
+
+/// Replace a tool's original copy of a skill with a managed target pointing at the central dir.
+/// The original directory is removed, so callers must have imported its content first.
+pub fn adopt_as_target(
+    store: &SkillStore,
+    skill_id: &str,
+    tool: &str,
+    central_path: &Path,
+    original_path: &Path,
+    now: i64,
+) -> Result<SkillTargetRecord> {
+    let sync_res = sync_dir_for_tool_with_overwrite(tool, central_path, original_path, true)?;
+    let record = SkillTargetRecord {
+        id: Uuid::new_v4().to_string(),
+        skill_id: skill_id.to_string(),
+        tool: tool.to_string(),
+        target_path: sync_res.target_path.to_string_lossy().to_string(),
+        mode: sync_res.mode_used.as_key().to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(now),
+    };
+    store.upsert_skill_target(&record)?;
+    Ok(record)
+}
+
+/// Turn a skill off or on for a tool without unsyncing it: the target is moved into a
+/// `.disabled` directory beside it and back, so nothing is deleted or copied again. Tools sharing
+/// the target path follow along. Returns the tools whose target changed; none when the target
+/// already is in the requested state.
+pub fn set_target_enabled(
+    store: &SkillStore,
+    skill_id: &str,
+    tool: &str,
+    enabled: bool,
+) -> Result<Vec<String>> {
+    let target = store
+        .get_skill_target(skill_id, tool)?
+        .ok_or_else(|| anyhow::anyhow!("skill is not synced to {}", tool))?;
+    if (target.status != STATUS_DISABLED) == enabled {
+        return Ok(Vec::new());
+    }
+    let from = PathBuf::from(&target.target_path);
+    let to = if enabled {
+        enabled_path(&from)
+    } else {
+        disabled_path(&from)
+    }
+    .ok_or_else(|| anyhow::anyhow!("unexpected target path: {}", target.target_path))?;
+    if std::fs::symlink_metadata(&to).is_ok() {
+        anyhow::bail!("TARGET_EXISTS|{}", to.to_string_lossy());
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(&from, &to)
+        .map_err(|err| anyhow::anyhow!("move {} to {}: {}", from.display(), to.display(), err))?;
+    if enabled {
+        // Leave no empty `.disabled` directory behind; one still in use is kept.
+        if let Some(parent) = from.parent() {
+            let _ = std::fs::remove_dir(parent);
+        }
+    }
+
+    let mut toggled = Vec::new();
+    for t in store.list_skill_targets(skill_id)? {
+        if t.target_path != target.target_path {
+            continue;
+        }
+        toggled.push(t.tool.clone());
+        store.upsert_skill_target(&SkillTargetRecord {
+            target_path: to.to_string_lossy().to_string(),
+            status: if enabled { "ok" } else { STATUS_DISABLED }.to_string(),
+            last_error: None,
+            ..t
+        })?;
+    }
+    Ok(toggled)
+}
+
+/// The status to write after refreshing a target: `ok`, unless it stays disabled.
+pub fn enabled_status(current: &str) -> &'static str {
+    if current == STATUS_DISABLED {
+        STATUS_DISABLED
+    } else {
+        "ok"
+    }
+}
+
+fn disabled_path(path: &Path) -> Option<PathBuf> {
+    Some(path.parent()?.join(DISABLED_DIR).join(path.file_name()?))
+}
+
+fn enabled_path(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    if dir.file_name()? != DISABLED_DIR {
+        return None;
+    }
+    Some(dir.parent()?.join(path.file_name()?))
+}
+
+#[cfg(test)]
+#[path = "tests/skill_sync.rs"]
+mod tests;
@@ -0,0 +1,450 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::deployment_report::format_utc;
+use super::installer::{clone_to_cache, install_converted_skill, InstallOptions, InstallResult};
+use super::skill_lint::is_valid_name;
+use super::skill_store::SkillStore;
+
+/// Files of a built-in template, SKILL.md first, as (relative path, text). Variables are filled
+/// in as for user templates; SKILL.md gets its frontmatter prepended.
+type TemplateFiles = &'static [(&'static str, &'static str)];
+
+struct BuiltinTemplate {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    files: TemplateFiles,
+}
+
+const BASIC_SKILL_MD: &str = "# {{title}}
+
+{{description}}
+
+## When to use
+
+- Describe the requests or situations this skill is for.
+
+## Instructions
+
+1. Explain the first step.
+2. Explain the next step.
+
+## Examples
+
+Show an input and the expected result.
+";
+
+const REFERENCES_SKILL_MD: &str = "# {{title}}
+
+{{description}}
+
+## When to use
+
+- Describe the requests or situations this skill is for.
+
+## Instructions
+
+1. Read [the reference](references/REFERENCE.md) for the details this skill relies on.
+2. Explain the steps to follow.
+
+## Resources
+
+- `references/REFERENCE.md`: background material, loaded only when needed.
+";
+
+const REFERENCE_MD: &str = "# {{title}} reference
+
+Put the detailed material here: API notes, schemas, style guides, long examples. Keeping it out
+of SKILL.md keeps the skill's own instructions short.
+";
+
+const AGENT_SKILL_MD: &str = "# {{title}}
+
+{{description}}
+
+## Role
+
+You act as a focused agent for this task. State what you own and what you leave to the user.
+
+## Workflow
+
+1. Gather the context you need before acting.
+2. Make a short plan and share it.
+3. Carry out the plan step by step, checking each result.
+4. Stop and ask when something is ambiguous or risky.
+
+## Tools
+
+- List the tools or commands this agent may use, and any it must not.
+
+## Output
+
+Describe the form of the final answer or artifact.
+";
+
+const BUILTIN_TEMPLATES: &[BuiltinTemplate] = &[
+    BuiltinTemplate {
+        id: "basic",
+        name: "Basic",
+        description: "A single SKILL.md with usage, instructions and examples",
+        files: &[("SKILL.md", BASIC_SKILL_MD)],
+    },
+    BuiltinTemplate {
+        id: "with_references",
+        name: "With references",
+        description: "SKILL.md plus a references/ folder for material loaded on demand",
+        files: &[
+            ("SKILL.md", REFERENCES_SKILL_MD),
+            ("references/REFERENCE.md", REFERENCE_MD),
+        ],
+    },
+    BuiltinTemplate {
+        id: "agent",
+        name: "Agent",
+        description: "An agent-style skill with a role, workflow, tools and output",
+        files: &[("SKILL.md", AGENT_SKILL_MD)],
+    },
+];
+
+/// Setting holding the user's template sources as a JSON list of directories and git URLs.
+const TEMPLATE_SOURCES_KEY: &str = "skill_template_sources";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillTemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Relative paths of the files the template creates.
+    pub files: Vec<String>,
+    /// The directory or git URL of a user template; `None` for built-in ones.
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CreateSkillOptions {
+    /// Frontmatter description; a placeholder asking for one is used when empty.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `{{author}}`; git's `user.name` when omitted.
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Tools to sync the new skill to right away.
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+/// Values substituted for `{{name}}`, `{{title}}`, `{{description}}`, `{{author}}` and
+/// `{{date}}` in template files and paths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateVars {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    /// `YYYY-MM-DD`, UTC.
+    pub date: String,
+}
+
+impl TemplateVars {
+    pub fn new(name: &str, description: &str, author: &str, now: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title_case(name),
+            description: description.to_string(),
+            author: author.to_string(),
+            date: format_utc(now)[..10].to_string(),
+        }
+    }
+
+    fn render(&self, text: &str) -> String {
+        text.replace("{{name}}", &self.name)
+            .replace("{{title}}", &self.title)
+            .replace("{{description}}", &self.description)
+            .replace("{{author}}", &self.author)
+            .replace("{{date}}", &self.date)
+    }
+}
+
+enum Template {
+    Builtin(&'static BuiltinTemplate),
+    /// A directory with a SKILL.md, copied with variables substituted.
+    Directory(PathBuf),
+}
+
+#[derive(Serialize)]
+struct Frontmatter<'a> {
+    name: &'a str,
+    description: &'a str,
+}
+
+pub fn list_template_sources(store: &SkillStore) -> Vec<String> {
+    store
+        .get_setting(TEMPLATE_SOURCES_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Register a template source: a directory holding template folders (or one template with a
+/// SKILL.md at its root), or a git URL of such a repository.
+pub fn add_template_source(store: &SkillStore, source: &str) -> Result<Vec<String>> {
+    let source = source.trim();
+    if !is_git_source(source) && !Path::new(source).is_dir() {
+        anyhow::bail!("not a directory or git URL: {}", source);
+    }
+    let mut sources = list_template_sources(store);
+    if !sources.iter().any(|s| s == source) {
+        sources.push(source.to_string());
+        store.set_setting(TEMPLATE_SOURCES_KEY, &serde_json::to_string(&sources)?)?;
+    }
+    Ok(sources)
+}
+
+pub fn remove_template_source(store: &SkillStore, source: &str) -> Result<Vec<String>> {
+    let mut sources = list_template_sources(store);
+    sources.retain(|s| s != source.trim());
+    store.set_setting(TEMPLATE_SOURCES_KEY, &serde_json::to_string(&sources)?)?;
+    Ok(sources)
+}
+
+/// Built-in templates and those found in the user's sources. A user template whose id matches a
+/// built-in one replaces it; a source that cannot be read is logged and skipped.
+pub fn list_skill_templates<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Vec<SkillTemplateInfo> {
+    let user = user_templates(app, store);
+    let mut templates: Vec<SkillTemplateInfo> = BUILTIN_TEMPLATES
+        .iter()
+        .filter(|t| !user.iter().any(|u| u.info.id == t.id))
+        .map(|t| SkillTemplateInfo {
+            id: t.id.to_string(),
+            name: t.name.to_string(),
+            description: t.description.to_string(),
+            files: t.files.iter().map(|(path, _)| path.to_string()).collect(),
+            source: None,
+        })
+        .collect();
+    templates.extend(user.into_iter().map(|u| u.info));
+    templates
+}
+
+/// Write the skill directory for `template` at `dest`.
+fn write_template(template: &Template, vars: &TemplateVars, dest: &Path) -> Result<()> {
+    match template {
+        Template::Builtin(template) => {
+            for (relative, text) in template.files {
+                let mut content = vars.render(text);
+                if *relative == "SKILL.md" {
+                    let frontmatter = serde_yaml::to_string(&Frontmatter {
+                        name: &vars.name,
+                        description: &vars.description,
+                    })?;
+                    content = format!("---\n{}---\n\n{}", frontmatter, content);
+                }
+                write_file(&dest.join(relative), content.as_bytes())?;
+            }
+        }
+        Template::Directory(root) => {
+            for relative in template_files(root) {
+                let bytes = std::fs::read(root.join(&relative))
+                    .with_context(|| format!("read {:?}", root.join(&relative)))?;
+                let path = dest.join(vars.render(&relative));
+                match String::from_utf8(bytes) {
+                    Ok(text) => write_file(&path, vars.render(&text).as_bytes())?,
+                    Err(err) => write_file(&path, err.as_bytes())?,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Create a skill named `name` in the install repo from a template and manage it as a local
+/// skill. An existing skill of that name is an error (`SKILL_EXISTS`).
+pub fn create_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    name: &str,
+    template: &str,
+    options: &CreateSkillOptions,
+    now: i64,
+) -> Result<InstallResult> {
+    let name = name.trim();
+    if !is_valid_name(name) {
+        anyhow::bail!(
+            "skill name must be lowercase letters, digits and hyphens: {:?}",
+            name
+        );
+    }
+    let resolved = match user_templates(app, store)
+        .into_iter()
+        .find(|u| u.info.id == template)
+    {
+        Some(user) => Template::Directory(user.dir),
+        None => Template::Builtin(
+            BUILTIN_TEMPLATES
+                .iter()
+                .find(|t| t.id == template)
+                .ok_or_else(|| anyhow::anyhow!("unknown template: {}", template))?,
+        ),
+    };
+    let description = options
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Describe what {} does and when to use it.", name));
+    let author = options
+        .author
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(default_author);
+    let vars = TemplateVars::new(name, &description, &author, now);
+    let scratch = std::env::temp_dir().join(format!("skills-hub-new-{}", Uuid::new_v4()));
+    let staging = scratch.join(name);
+    let result = write_template(&resolved, &vars, &staging).and_then(|()| {
+        install_converted_skill(
+            app,
+            store,
+            &staging,
+            name.to_string(),
+            ("local".to_string(), None, None),
+            InstallOptions::default(),
+        )
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+struct UserTemplate {
+    dir: PathBuf,
+    info: SkillTemplateInfo,
+}
+
+fn user_templates<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+) -> Vec<UserTemplate> {
+    let mut templates: Vec<UserTemplate> = Vec::new();
+    for source in list_template_sources(store) {
+        let root = if is_git_source(&source) {
+            match clone_to_cache(app, store, &source, None) {
+                Ok((dir, _)) => dir,
+                Err(err) => {
+                    log::warn!("[templates] failed to fetch {}: {:#}", source, err);
+                    continue;
+                }
+            }
+        } else {
+            PathBuf::from(&source)
+        };
+        for (id, dir) in template_dirs(&root, &source) {
+            // The first source to define an id wins.
+            if templates.iter().any(|t| t.info.id == id) {
+                continue;
+            }
+            let info = SkillTemplateInfo {
+                name: id.clone(),
+                id,
+                description: format!("From {}", source),
+                files: template_files(&dir),
+                source: Some(source.clone()),
+            };
+            templates.push(UserTemplate { dir, info });
+        }
+    }
+    templates
+}
+
+/// `root` itself when it holds a SKILL.md, named after the source's last path segment, else its
+/// subdirectories that do, by directory name.
+fn template_dirs(root: &Path, source: &str) -> Vec<(String, PathBuf)> {
+    if root.join("SKILL.md").is_file() {
+        let name = source
+            .trim_end_matches('/')
+            .rsplit(['/', '\\', ':'])
+            .next()
+            .unwrap_or(source);
+        let name = name.strip_suffix(".git").unwrap_or(name);
+        return vec![(name.to_string(), root.to_path_buf())];
+    }
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .filter(|(name, path)| !name.starts_with('.') && path.join("SKILL.md").is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Relative paths of the files in a template directory, skipping hidden entries such as `.git`.
+fn template_files(root: &Path) -> Vec<String> {
+    let mut files: Vec<String> = WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    std::fs::write(path, bytes).with_context(|| format!("write {:?}", path))
+}
+
+pub(crate) fn is_git_source(source: &str) -> bool {
+    source.contains("://") || source.starts_with("git@")
+}
+
+/// git's `user.name`, else the login name.
+fn default_author() -> String {
+    git2::Config::open_default()
+        .and_then(|config| config.get_string("user.name"))
+        .ok()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_default()
+}
+
+/// `pdf-tools` -> `Pdf Tools`.
+fn title_case(name: &str) -> String {
+    name.split('-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+#[path = "tests/skill_templates.rs"]
+mod tests;
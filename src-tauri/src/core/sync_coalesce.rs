@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+/// How long a follow-up pass waits for the burst that queued it to settle.
+pub const DEFAULT_SYNC_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Outcome shared with every request that joined a pass. Errors keep their context chain,
+/// outermost first, so coded messages survive the copy.
+type Shared<T> = std::result::Result<T, Vec<String>>;
+
+struct Pass<T> {
+    outcome: Mutex<Option<Shared<T>>>,
+    ready: Condvar,
+}
+
+struct Lane<T> {
+    running: bool,
+    /// The follow-up pass later requests join while one is running.
+    next: Option<Arc<Pass<T>>>,
+    last_joined: Instant,
+}
+
+/// Collapses repeated identical requests, e.g. syncing the same skill to the same tool.
+/// An idle key runs at once. Requests arriving while it runs share a single follow-up pass,
+/// which starts once the current one is done and no request has joined for the debounce
+/// window, so it still sees every change made before the last request.
+pub struct SyncCoalescer<T> {
+    lanes: Mutex<BTreeMap<String, Lane<T>>>,
+    idle: Condvar,
+    debounce: Duration,
+}
+
+impl<T: Clone> SyncCoalescer<T> {
+    pub const fn new(debounce: Duration) -> Self {
+        Self {
+            lanes: Mutex::new(BTreeMap::new()),
+            idle: Condvar::new(),
+            debounce,
+        }
+    }
+
+    pub fn run(&self, key: &str, work: impl FnOnce() -> Result<T>) -> Result<T> {
+        let mut lanes = self.lock();
+        let now = Instant::now();
+        let lane = lanes.entry(key.to_string()).or_insert_with(|| Lane {
+            running: false,
+            next: None,
+            last_joined: now,
+        });
+        if !lane.running && lane.next.is_none() {
+            lane.running = true;
+            drop(lanes);
+            let _done = Running {
+                coalescer: self,
+                key,
+                pass: None,
+            };
+            return work();
+        }
+
+        lane.last_joined = now;
+        if let Some(pass) = lane.next.clone() {
+            drop(lanes);
+            return wait_for(&pass);
+        }
+        let pass = Arc::new(Pass {
+            outcome: Mutex::new(None),
+            ready: Condvar::new(),
+        });
+        lane.next = Some(pass.clone());
+
+        // This request leads the follow-up pass: wait for the running one, then for the burst.
+        loop {
+            let lane = lanes
+                .get_mut(key)
+                .expect("lane kept while a pass is queued");
+            if lane.running {
+                lanes = self.idle.wait(lanes).unwrap_or_else(|err| err.into_inner());
+                continue;
+            }
+            let settled = lane.last_joined.elapsed();
+            if settled < self.debounce {
+                drop(lanes);
+                std::thread::sleep(self.debounce - settled);
+                lanes = self.lock();
+                continue;
+            }
+            lane.running = true;
+            lane.next = None;
+            break;
+        }
+        drop(lanes);
+
+        let _done = Running {
+            coalescer: self,
+            key,
+            pass: Some(pass.clone()),
+        };
+        let result = work();
+        pass.publish(match &result {
+            Ok(value) => Ok(value.clone()),
+            Err(err) => Err(err.chain().map(|cause| cause.to_string()).collect()),
+        });
+        result
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Lane<T>>> {
+        self.lanes.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<T> Pass<T> {
+    fn publish(&self, outcome: Shared<T>) {
+        let mut slot = self.outcome.lock().unwrap_or_else(|err| err.into_inner());
+        if slot.is_none() {
+            *slot = Some(outcome);
+        }
+        self.ready.notify_all();
+    }
+}
+
+/// Frees the lane when a pass ends, even by panic, so joined requests are never left waiting.
+struct Running<'a, T: Clone> {
+    coalescer: &'a SyncCoalescer<T>,
+    key: &'a str,
+    pass: Option<Arc<Pass<T>>>,
+}
+
+impl<T: Clone> Drop for Running<'_, T> {
+    fn drop(&mut self) {
+        if let Some(pass) = &self.pass {
+            pass.publish(Err(vec!["sync pass aborted".to_string()]));
+        }
+        let mut lanes = self.coalescer.lock();
+        if let Some(lane) = lanes.get_mut(self.key) {
+            lane.running = false;
+            if lane.next.is_none() {
+                lanes.remove(self.key);
+            }
+        }
+        self.coalescer.idle.notify_all();
+    }
+}
+
+fn wait_for<T: Clone>(pass: &Pass<T>) -> Result<T> {
+    let mut outcome = pass.outcome.lock().unwrap_or_else(|err| err.into_inner());
+    while outcome.is_none() {
+        outcome = pass
+            .ready
+            .wait(outcome)
+            .unwrap_or_else(|err| err.into_inner());
+    }
+    match outcome.as_ref().expect("outcome set") {
+        Ok(value) => Ok(value.clone()),
+        Err(chain) => {
+            let mut causes = chain.iter().rev();
+            let innermost = causes.next().cloned().unwrap_or_default();
+            Err(causes.fold(anyhow::anyhow!(innermost), |err, context| {
+                err.context(context.clone())
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sync_coalesce.rs"]
+mod tests;
@@ -1,7 +1,11 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use anyhow::{Context, Result};
 
+use super::progress;
+
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub enum SyncMode {
@@ -11,6 +15,17 @@ pub enum SyncMode {
     Copy,
 }
 
+impl SyncMode {
+    pub fn as_key(&self) -> &'static str {
+        match self {
+            SyncMode::Auto => "auto",
+            SyncMode::Symlink => "symlink",
+            SyncMode::Junction => "junction",
+            SyncMode::Copy => "copy",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SyncOutcome {
     pub mode_used: SyncMode,
@@ -134,7 +149,7 @@ fn ensure_parent_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn remove_path_any(path: &Path) -> Result<()> {
+pub(crate) fn remove_path_any(path: &Path) -> Result<()> {
     let meta = match std::fs::symlink_metadata(path) {
         Ok(meta) => meta,
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
@@ -192,17 +207,28 @@ fn should_skip_copy(entry: &walkdir::DirEntry) -> bool {
     entry.file_name() == ".git"
 }
 
+/// Upper bound on copy threads; past this the disk, not the CPU, is the bottleneck.
+const MAX_COPY_WORKERS: usize = 8;
+/// Below this many files, spawning threads costs more than it saves.
+const PARALLEL_COPY_MIN_FILES: usize = 64;
+
 pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
     let profile = std::env::var("SKILLS_HUB_PROFILE_IO")
         .ok()
         .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
         .unwrap_or(false);
     let started = std::time::Instant::now();
-    let mut copied_files: u64 = 0;
-    let mut copied_bytes: u64 = 0;
 
+    // Links are resolved against the real root, which may itself sit behind a link.
+    let root = source
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", source))?;
+    // Directories are created during the walk, so the workers only ever copy files. Sorted so
+    // a failure always names the same file.
+    let mut files: Vec<(PathBuf, PathBuf)> = Vec::new();
     for entry in walkdir::WalkDir::new(source)
         .follow_links(false)
+        .sort_by_file_name()
         .into_iter()
         .filter_entry(|entry| !should_skip_copy(entry))
     {
@@ -217,21 +243,18 @@ pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
             std::fs::create_dir_all(&target_path)
                 .with_context(|| format!("create dir {:?}", target_path))?;
         } else if entry.file_type().is_file() {
-            if let Some(parent) = target_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            let bytes = std::fs::copy(entry.path(), &target_path)
-                .with_context(|| format!("copy file {:?} -> {:?}", entry.path(), target_path))?;
-            if profile {
-                copied_files += 1;
-                copied_bytes = copied_bytes.saturating_add(bytes);
+            files.push((entry.path().to_path_buf(), target_path));
+        } else if entry.file_type().is_symlink() {
+            if let Some(resolved) = resolve_file_link(&root, entry.path()) {
+                files.push((resolved, target_path));
             }
         }
     }
+    let copied_bytes = copy_files(&files)?;
     if profile {
         log::info!(
             "[sync_engine] copy_dir_recursive {} files, {} bytes in {}s (src={:?} dst={:?})",
-            copied_files,
+            files.len(),
             copied_bytes,
             started.elapsed().as_secs_f32(),
             source,
@@ -241,6 +264,104 @@ pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Where a symlink met during a copy should be read from: its target when that is a file inside
+/// `root`. Links leaving the skill (a repo shipping `keys -> ~/.ssh`) or pointing at directories
+/// (which could loop) are skipped with a warning rather than followed.
+fn resolve_file_link(root: &Path, link: &Path) -> Option<PathBuf> {
+    let resolved = match link.canonicalize() {
+        Ok(resolved) => resolved,
+        Err(err) => {
+            log::warn!("[sync_engine] skip dangling link {:?}: {}", link, err);
+            return None;
+        }
+    };
+    if !resolved.starts_with(root) {
+        log::warn!(
+            "[sync_engine] skip link {:?} -> {:?}: target is outside {:?}",
+            link,
+            resolved,
+            root
+        );
+        return None;
+    }
+    if !resolved.is_file() {
+        log::warn!("[sync_engine] skip directory link {:?}", link);
+        return None;
+    }
+    Some(resolved)
+}
+
+/// Copy `(from, to)` pairs on a bounded pool of scoped threads and return the bytes copied.
+/// Files are claimed in order and a failure stops further claims, so the error returned is
+/// the first failing file in walk order, just as with a sequential copy.
+fn copy_files(files: &[(PathBuf, PathBuf)]) -> Result<u64> {
+    // Worker threads do not inherit the caller's reporter; take it here and share it.
+    let reporter = progress::current();
+    let total_files = files.len() as u64;
+    let report_every = (total_files / 100).max(1);
+    let done = AtomicU64::new(0);
+    let copy_one = |(from, to): &(PathBuf, PathBuf)| {
+        let bytes =
+            std::fs::copy(from, to).with_context(|| format!("copy file {:?} -> {:?}", from, to))?;
+        if let Some(reporter) = &reporter {
+            let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+            if finished % report_every == 0 || finished == total_files {
+                reporter("copying", finished, Some(total_files));
+            }
+        }
+        Ok::<_, anyhow::Error>(bytes)
+    };
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_COPY_WORKERS);
+    if workers <= 1 || files.len() < PARALLEL_COPY_MIN_FILES {
+        let mut total: u64 = 0;
+        for pair in files {
+            total = total.saturating_add(copy_one(pair)?);
+        }
+        return Ok(total);
+    }
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let copied = AtomicU64::new(0);
+    let errors: Mutex<Vec<(usize, anyhow::Error)>> = Mutex::new(Vec::new());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while !failed.load(Ordering::Relaxed) {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(pair) = files.get(idx) else {
+                        break;
+                    };
+                    match copy_one(pair) {
+                        Ok(bytes) => {
+                            copied.fetch_add(bytes, Ordering::Relaxed);
+                        }
+                        Err(err) => {
+                            failed.store(true, Ordering::Relaxed);
+                            errors
+                                .lock()
+                                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                .push((idx, err));
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    // Every file before the first failure was claimed earlier and ran to completion.
+    let errors = errors
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match errors.into_iter().min_by_key(|(idx, _)| *idx) {
+        Some((_, err)) => Err(err),
+        None => Ok(copied.into_inner()),
+    }
+}
+
 #[cfg(test)]
 #[path = "tests/sync_engine.rs"]
 mod tests;
@@ -0,0 +1,300 @@
+use std::path::{Component, Path};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tauri::Emitter;
+
+use super::installer::{clone_to_cache, parse_github_url};
+use super::lockfile::{current_hash, ensure_valid_entries, ApplyOptions};
+use super::profiles::{
+    apply_profile_file, find_profile_by_name, read_profile_file, ProfileFile, ProfileImportReport,
+};
+use super::skill_store::{SkillProjection, SkillStore, TeamProfileRecord};
+
+pub const TEAM_PROFILE_CHECK_HOURS_KEY: &str = "team_profile_check_hours";
+pub const DEFAULT_TEAM_PROFILE_CHECK_HOURS: i64 = 6;
+const MAX_TEAM_PROFILE_CHECK_HOURS: i64 = 24 * 7;
+/// Emitted with the statuses of the team profiles a background check found drifted.
+pub const TEAM_PROFILE_DRIFT_EVENT: &str = "team-profile-drift";
+
+/// Differences between a team profile file and the local state, by skill name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TeamProfileDrift {
+    /// Entries no managed skill has the name of.
+    pub missing: Vec<String>,
+    /// Managed entries whose content differs from the pinned hash, or whose source is not at the
+    /// pinned revision.
+    pub outdated: Vec<String>,
+    /// Managed entries that are not members of the local profile.
+    pub not_members: Vec<String>,
+    /// Members of the local profile the file no longer lists.
+    pub extra: Vec<String>,
+}
+
+impl TeamProfileDrift {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+            && self.outdated.is_empty()
+            && self.not_members.is_empty()
+            && self.extra.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TeamProfileStatus {
+    #[serde(flatten)]
+    pub source: TeamProfileRecord,
+    pub profile_name: String,
+    /// Against the file as last read; empty before the first successful read.
+    pub drift: TeamProfileDrift,
+    pub in_sync: bool,
+}
+
+pub fn get_team_profile_check_hours(store: &SkillStore) -> i64 {
+    store
+        .get_setting(TEAM_PROFILE_CHECK_HOURS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| raw.trim().parse::<i64>().ok())
+        .filter(|hours| (0..=MAX_TEAM_PROFILE_CHECK_HOURS).contains(hours))
+        .unwrap_or(DEFAULT_TEAM_PROFILE_CHECK_HOURS)
+}
+
+/// `0` turns the background checks off.
+pub fn set_team_profile_check_hours(store: &SkillStore, hours: i64) -> Result<i64> {
+    if !(0..=MAX_TEAM_PROFILE_CHECK_HOURS).contains(&hours) {
+        anyhow::bail!(
+            "check interval hours must be between 0 and {}",
+            MAX_TEAM_PROFILE_CHECK_HOURS
+        );
+    }
+    store.set_setting(TEAM_PROFILE_CHECK_HOURS_KEY, &hours.to_string())?;
+    Ok(hours)
+}
+
+/// Read the profile file at `url` and import it like `import_profile`, then keep the profile
+/// tied to the URL. The URL names a file: a GitHub `blob` URL or `<repo url>#<path>`. A file
+/// naming a skill that is not a plain directory name is refused.
+pub fn track_team_profile<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    url: &str,
+    force: bool,
+    now: i64,
+) -> Result<ProfileImportReport> {
+    let url = url.trim();
+    let (file, revision) = fetch_team_profile(app, store, url)?;
+    ensure_valid_entries(&file.as_lock())?;
+    if let Some(profile) = find_profile_by_name(store, &file.name)? {
+        let tracked = store
+            .list_team_profiles()?
+            .into_iter()
+            .find(|t| t.profile_id == profile.id);
+        if tracked.is_some_and(|t| t.url != url) {
+            anyhow::bail!("profile {:?} already follows another URL", profile.name);
+        }
+    }
+    let options = ApplyOptions {
+        skip_updates: true,
+        skip_sync: true,
+        force,
+    };
+    let report = apply_profile_file(app, store, &file, None, options, now)?;
+    store.upsert_team_profile(&TeamProfileRecord {
+        profile_id: report.profile.id.clone(),
+        url: url.to_string(),
+        revision: Some(revision),
+        checked_at: Some(now),
+        last_error: None,
+        definition: Some(serde_json::to_string(&file)?),
+    })?;
+    Ok(report)
+}
+
+/// Every tracked profile with its drift against the file as last read. Nothing is fetched.
+pub fn list_team_profiles(store: &SkillStore) -> Result<Vec<TeamProfileStatus>> {
+    let mut statuses = Vec::new();
+    for record in store.list_team_profiles()? {
+        statuses.push(team_profile_status(store, record)?);
+    }
+    statuses.sort_by(|a, b| a.profile_name.cmp(&b.profile_name));
+    Ok(statuses)
+}
+
+/// Pull the file again and report the drift. A failed read is recorded in `last_error`.
+pub fn check_team_profile<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    profile_id: &str,
+    now: i64,
+) -> Result<TeamProfileStatus> {
+    let mut record = get_team_profile(store, profile_id)?;
+    record.checked_at = Some(now);
+    match fetch_team_profile(app, store, &record.url) {
+        Ok((file, revision)) => {
+            record.revision = Some(revision);
+            record.last_error = None;
+            record.definition = Some(serde_json::to_string(&file)?);
+        }
+        Err(err) => record.last_error = Some(format!("{:#}", err)),
+    }
+    store.upsert_team_profile(&record)?;
+    team_profile_status(store, record)
+}
+
+/// Pull the file and make the local state match it: missing skills are installed, outdated ones
+/// updated from their source, and the profile's members replaced with the file's entries. The
+/// file is checked like on `track_team_profile` each time, since it may have changed upstream.
+pub fn reconcile_team_profile<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    profile_id: &str,
+    force: bool,
+    now: i64,
+) -> Result<ProfileImportReport> {
+    let mut record = get_team_profile(store, profile_id)?;
+    let (file, revision) = fetch_team_profile(app, store, &record.url)?;
+    ensure_valid_entries(&file.as_lock())?;
+    let options = ApplyOptions {
+        skip_updates: false,
+        skip_sync: true,
+        force,
+    };
+    let report = apply_profile_file(app, store, &file, Some(profile_id), options, now)?;
+    record.revision = Some(revision);
+    record.checked_at = Some(now);
+    record.last_error = None;
+    record.definition = Some(serde_json::to_string(&file)?);
+    store.upsert_team_profile(&record)?;
+    Ok(report)
+}
+
+/// Check the team profiles whose last check is older than the configured interval, and emit
+/// `team-profile-drift` with those that drifted. Returns how many were checked and how many
+/// drifted. Run by the scheduler.
+pub fn check_due_team_profiles<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    now: i64,
+) -> Result<(usize, usize)> {
+    let hours = get_team_profile_check_hours(store);
+    if hours == 0 {
+        return Ok((0, 0));
+    }
+    let due_before = now - hours * 60 * 60 * 1000;
+    let (mut checked, mut drifted) = (0, Vec::new());
+    for record in store.list_team_profiles()? {
+        if record.checked_at.is_some_and(|at| at > due_before) {
+            continue;
+        }
+        checked += 1;
+        match check_team_profile(app, store, &record.profile_id, now) {
+            Ok(status) if !status.in_sync => drifted.push(status),
+            Ok(_) => {}
+            Err(err) => log::warn!("[team-profiles] check failed: {:#}", err),
+        }
+    }
+    if !drifted.is_empty() {
+        if let Err(err) = app.emit(TEAM_PROFILE_DRIFT_EVENT, &drifted) {
+            log::warn!("[team-profiles] failed to emit drift event: {}", err);
+        }
+    }
+    Ok((checked, drifted.len()))
+}
+
+fn get_team_profile(store: &SkillStore, profile_id: &str) -> Result<TeamProfileRecord> {
+    store
+        .list_team_profiles()?
+        .into_iter()
+        .find(|t| t.profile_id == profile_id)
+        .ok_or_else(|| anyhow::anyhow!("team profile not found"))
+}
+
+/// The file at `url` and the commit it was read at.
+fn fetch_team_profile<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    url: &str,
+) -> Result<(ProfileFile, String)> {
+    let (repo, fragment) = match url.split_once('#') {
+        Some((repo, path)) => (repo, Some(path.to_string())),
+        None => (url, None),
+    };
+    let parsed = parse_github_url(repo);
+    let file = fragment
+        .or(parsed.subpath)
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| {
+            anyhow::anyhow!("the URL must name a profile file (a GitHub blob URL or <repo>#<path>)")
+        })?;
+    if Path::new(&file)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        anyhow::bail!("invalid profile file path: {}", file);
+    }
+    let (dir, revision) = clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())
+        .with_context(|| format!("fetch {}", repo))?;
+    Ok((read_profile_file(&dir.join(&file))?, revision))
+}
+
+fn team_profile_status(store: &SkillStore, record: TeamProfileRecord) -> Result<TeamProfileStatus> {
+    let profile = store
+        .get_profile(&record.profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("profile not found"))?;
+    let drift = match &record.definition {
+        Some(raw) => {
+            let file: ProfileFile = serde_json::from_str(raw)?;
+            team_profile_drift(store, &profile.skill_ids, &file)?
+        }
+        None => TeamProfileDrift::default(),
+    };
+    Ok(TeamProfileStatus {
+        in_sync: record.definition.is_some() && drift.is_empty(),
+        profile_name: profile.name,
+        drift,
+        source: record,
+    })
+}
+
+fn team_profile_drift(
+    store: &SkillStore,
+    members: &[String],
+    file: &ProfileFile,
+) -> Result<TeamProfileDrift> {
+    let skills = store.list_skills_with(SkillProjection::Summary)?;
+    let mut drift = TeamProfileDrift::default();
+    for entry in &file.skills {
+        let Some(skill) = skills.iter().find(|s| s.name == entry.name) else {
+            drift.missing.push(entry.name.clone());
+            continue;
+        };
+        if !members.contains(&skill.id) {
+            drift.not_members.push(entry.name.clone());
+        }
+        if entry.content_hash.is_some() || entry.revision.is_some() {
+            let skill = store
+                .get_skill_by_id(&skill.id)?
+                .context("skill not found")?;
+            let revision_moved =
+                entry.revision.is_some() && skill.source_revision != entry.revision;
+            if revision_moved
+                || (entry.content_hash.is_some()
+                    && current_hash(store, &skill)? != entry.content_hash)
+            {
+                drift.outdated.push(entry.name.clone());
+            }
+        }
+    }
+    for skill in skills.iter().filter(|s| members.contains(&s.id)) {
+        if !file.skills.iter().any(|entry| entry.name == skill.name) {
+            drift.extra.push(skill.name.clone());
+        }
+    }
+    drift.extra.sort();
+    Ok(drift)
+}
+
+#[cfg(test)]
+#[path = "tests/team_profiles.rs"]
+mod tests;
@@ -0,0 +1,51 @@
+use std::fs;
+
+use super::read_recent_logs;
+
+const SAMPLE: &str = "\
+[2026-01-01][10:00:00][app][INFO] started
+[2026-01-01][10:00:01][app::core][WARN] [installer] rename fell back to copy
+[2026-01-01][10:00:02][app][ERROR] clone failed:
+caused by: timeout
+[2026-01-01][10:00:03][app][DEBUG] noisy
+[2026-01-01][10:00:04][app][INFO] done
+";
+
+#[test]
+fn tails_and_filters_by_level() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    fs::write(&path, SAMPLE).unwrap();
+
+    let all = read_recent_logs(&path, None, None).unwrap();
+    assert_eq!(all.lines.len(), 6);
+
+    let last = read_recent_logs(&path, Some(2), None).unwrap();
+    assert_eq!(
+        last.lines,
+        vec![
+            "[2026-01-01][10:00:03][app][DEBUG] noisy",
+            "[2026-01-01][10:00:04][app][INFO] done",
+        ]
+    );
+
+    let warnings = read_recent_logs(&path, None, Some("warn")).unwrap();
+    assert_eq!(warnings.lines.len(), 3);
+    assert!(warnings.lines[0].contains("[WARN]"));
+    assert_eq!(warnings.lines[2], "caused by: timeout");
+
+    let info = read_recent_logs(&path, Some(10), Some("INFO")).unwrap();
+    assert_eq!(info.lines.len(), 5);
+    assert!(info.lines.iter().all(|l| !l.contains("[DEBUG]")));
+}
+
+#[test]
+fn missing_file_and_bad_level() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("none.log");
+    assert!(read_recent_logs(&path, None, None)
+        .unwrap()
+        .lines
+        .is_empty());
+    assert!(read_recent_logs(&path, None, Some("loud")).is_err());
+}
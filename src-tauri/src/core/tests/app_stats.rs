@@ -0,0 +1,85 @@
+use std::fs;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{collect_app_stats, dir_size};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn skill(id: &str, source_type: &str) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: id.to_string(),
+        source_type: source_type.to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: format!("/central/{}", id),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    }
+}
+
+fn target(skill_id: &str, tool: &str) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: format!("{}-{}", skill_id, tool),
+        skill_id: skill_id.to_string(),
+        tool: tool.to_string(),
+        target_path: format!("/targets/{}/{}", tool, skill_id),
+        mode: "symlink".to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: None,
+    }
+}
+
+#[test]
+fn counts_skills_targets_and_sizes() {
+    let (dir, store) = make_store();
+    for (id, source_type) in [("a", "local"), ("b", "git"), ("c", "git")] {
+        store.upsert_skill(&skill(id, source_type)).unwrap();
+    }
+    for (id, tool) in [("a", "cursor"), ("b", "cursor"), ("b", "codex")] {
+        store.upsert_skill_target(&target(id, tool)).unwrap();
+    }
+
+    let central = tempfile::tempdir().unwrap();
+    fs::create_dir_all(central.path().join("a")).unwrap();
+    fs::write(central.path().join("a/SKILL.md"), vec![b'x'; 100]).unwrap();
+    fs::write(central.path().join("a/extra.txt"), vec![b'y'; 20]).unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    fs::create_dir_all(cache.path().join("skills-hub-git-cache/repo")).unwrap();
+    fs::write(cache.path().join("skills-hub-git-cache/repo/f"), [0u8; 7]).unwrap();
+    fs::write(cache.path().join("unrelated"), [0u8; 50]).unwrap();
+
+    let stats = collect_app_stats(&store, central.path(), cache.path()).unwrap();
+    assert_eq!(stats.total_skills, 3);
+    assert_eq!(stats.skills_by_source_type.get("git"), Some(&2));
+    assert_eq!(stats.skills_by_source_type.get("local"), Some(&1));
+    assert_eq!(stats.skills_by_status.get("ok"), Some(&3));
+    assert_eq!(stats.targets_by_tool.get("cursor"), Some(&2));
+    assert_eq!(stats.targets_by_tool.get("codex"), Some(&1));
+    assert_eq!(stats.central_repo_bytes, 120);
+    assert_eq!(stats.git_cache_bytes, 7);
+    assert!(stats.db_bytes >= fs::metadata(dir.path().join("test.db")).unwrap().len());
+    assert!(stats.db_bytes > 0);
+}
+
+#[test]
+fn missing_directories_count_as_zero() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(dir_size(&dir.path().join("nope")), 0);
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn background_flag_and_login_item() {
+    let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert!(launched_in_background(
+        args(&["skills-hub", "--background"]).into_iter()
+    ));
+    assert!(!launched_in_background(args(&["skills-hub"]).into_iter()));
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    assert!(!background_mode_enabled(&store));
+
+    #[cfg(not(windows))]
+    {
+        let item = login_item(Path::new("/opt/Skills \"Hub\"/app"));
+        assert!(item.contains(BACKGROUND_ARG));
+        if cfg!(target_os = "macos") {
+            assert!(item.contains("<string>/opt/Skills \"Hub\"/app</string>"));
+        } else {
+            assert!(item.contains("Exec=\"/opt/Skills \\\"Hub\\\"/app\" --background"));
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use std::fs;
+
+use super::*;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn make_skill(store: &SkillStore, central: &Path, id: &str, name: &str) -> SkillRecord {
+    let dir = central.join(name);
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    fs::write(dir.join("SKILL.md"), format!("---\nname: {}\n---\n", name)).unwrap();
+    fs::write(dir.join("scripts/run.sh"), "echo hi\n").unwrap();
+    let record = SkillRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: dir.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    };
+    store.upsert_skill(&record).unwrap();
+    record
+}
+
+#[test]
+fn backup_restores_on_a_new_machine_and_relinks_targets() {
+    let old = tempfile::tempdir().unwrap();
+    let old_store = make_store(old.path());
+    let old_central = old.path().join("central");
+    let skill = make_skill(&old_store, &old_central, "s1", "pdf");
+    fs::write(old_central.join("notes.txt"), "unmanaged").unwrap();
+    old_store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: skill.id.clone(),
+            tool: "claude_code".to_string(),
+            target_path: "/old/home/.claude/skills/pdf".to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+        })
+        .unwrap();
+    old_store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t2".to_string(),
+            skill_id: skill.id.clone(),
+            tool: "cursor".to_string(),
+            target_path: "/old/home/.cursor/skills/pdf".to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+        })
+        .unwrap();
+    old_store
+        .set_setting("central_repo_path", "/old/central")
+        .unwrap();
+    old_store.set_setting("executable_policy", "block").unwrap();
+    old_store.set_setting("app_language", "zh").unwrap();
+    old_store.set_setting("local_api_port", "9999").unwrap();
+
+    let archive = old.path().join("out/backup.zip");
+    let manifest = create_backup(&old_store, &old_central, &archive, 7).unwrap();
+    assert_eq!(manifest.skill_count, 1);
+
+    let new = tempfile::tempdir().unwrap();
+    let store = make_store(new.path());
+    store.set_setting("executable_policy", "allow").unwrap();
+    let central = new.path().join("repo");
+    let claude_dir = new.path().join("claude-skills");
+    let tool_dir = |key: &str| (key == "claude_code").then(|| claude_dir.clone());
+    let report = restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Skip,
+        9,
+        &tool_dir,
+    )
+    .unwrap();
+
+    assert_eq!(report.restored, vec!["pdf".to_string()]);
+    assert_eq!(report.relinked, 1);
+    assert_eq!(report.unlinked.len(), 1);
+    assert_eq!(report.unlinked[0].tool, "cursor");
+    assert_eq!(report.unlinked[0].reason, "tool_not_installed");
+
+    let restored = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert_eq!(Path::new(&restored.central_path), central.join("pdf"));
+    assert_eq!(
+        fs::read_to_string(central.join("pdf/scripts/run.sh")).unwrap(),
+        "echo hi\n"
+    );
+    assert_eq!(
+        fs::read_to_string(central.join("notes.txt")).unwrap(),
+        "unmanaged"
+    );
+    assert!(claude_dir.join("pdf/SKILL.md").exists());
+    let target = store
+        .get_skill_target("s1", "claude_code")
+        .unwrap()
+        .unwrap();
+    assert_eq!(Path::new(&target.target_path), claude_dir.join("pdf"));
+    assert_eq!(target.synced_at, Some(9));
+
+    assert_eq!(restored.contains_executables, Some(true));
+    assert_eq!(store.get_setting("central_repo_path").unwrap(), None);
+    assert_eq!(store.get_setting("local_api_port").unwrap(), None);
+    assert_eq!(
+        store.get_setting("app_language").unwrap().as_deref(),
+        Some("zh")
+    );
+    assert_eq!(
+        store.get_setting("executable_policy").unwrap().as_deref(),
+        Some("allow")
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let exec = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        fs::set_permissions(
+            old_central.join("pdf/scripts/run.sh"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        create_backup(&old_store, &old_central, &archive, 8).unwrap();
+        restore_backup(
+            &store,
+            &central,
+            &archive,
+            RestoreStrategy::Overwrite,
+            10,
+            &tool_dir,
+        )
+        .unwrap();
+        assert_eq!(exec(&central.join("pdf/scripts/run.sh")), 0o755);
+    }
+}
+
+#[test]
+fn skip_keeps_local_skills_and_overwrite_replaces_them() {
+    let old = tempfile::tempdir().unwrap();
+    let old_store = make_store(old.path());
+    let old_central = old.path().join("central");
+    make_skill(&old_store, &old_central, "s1", "pdf");
+    let archive = old.path().join("backup.zip");
+    create_backup(&old_store, &old_central, &archive, 1).unwrap();
+
+    let new = tempfile::tempdir().unwrap();
+    let store = make_store(new.path());
+    let central = new.path().join("repo");
+    make_skill(&store, &central, "local-pdf", "pdf");
+    fs::write(central.join("pdf/SKILL.md"), "local edit").unwrap();
+    let no_tools = |_: &str| None;
+
+    let report = restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Skip,
+        2,
+        &no_tools,
+    )
+    .unwrap();
+    assert_eq!(report.skipped, vec!["pdf".to_string()]);
+    assert!(store.get_skill_by_id("local-pdf").unwrap().is_some());
+    assert_eq!(
+        fs::read_to_string(central.join("pdf/SKILL.md")).unwrap(),
+        "local edit"
+    );
+
+    let report = restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Overwrite,
+        3,
+        &no_tools,
+    )
+    .unwrap();
+    assert_eq!(report.restored, vec!["pdf".to_string()]);
+    assert!(store.get_skill_by_id("local-pdf").unwrap().is_none());
+    assert!(store.get_skill_by_id("s1").unwrap().is_some());
+    assert_eq!(
+        fs::read_to_string(central.join("pdf/SKILL.md")).unwrap(),
+        "---\nname: pdf\n---\n"
+    );
+
+    let not_backup = new.path().join("plain.zip");
+    fs::write(&not_backup, "nope").unwrap();
+    assert!(restore_backup(
+        &store,
+        &central,
+        &not_backup,
+        RestoreStrategy::Skip,
+        4,
+        &no_tools
+    )
+    .is_err());
+}
+
+#[test]
+fn relinks_follow_the_local_executable_policy_not_the_backup() {
+    let old = tempfile::tempdir().unwrap();
+    let old_store = make_store(old.path());
+    let old_central = old.path().join("central");
+    let mut skill = make_skill(&old_store, &old_central, "s1", "pdf");
+    // A crafted backup claims the skill has no scripts and allows them anyway.
+    skill.contains_executables = Some(false);
+    old_store.upsert_skill(&skill).unwrap();
+    old_store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: skill.id.clone(),
+            tool: "claude_code".to_string(),
+            target_path: "/old/home/.claude/skills/pdf".to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+        })
+        .unwrap();
+    old_store.set_setting("executable_policy", "allow").unwrap();
+    let archive = old.path().join("backup.zip");
+    create_backup(&old_store, &old_central, &archive, 1).unwrap();
+
+    let new = tempfile::tempdir().unwrap();
+    let store = make_store(new.path());
+    let central = new.path().join("repo");
+    let claude_dir = new.path().join("claude-skills");
+    let tool_dir = |key: &str| (key == "claude_code").then(|| claude_dir.clone());
+    let report = restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Overwrite,
+        2,
+        &tool_dir,
+    )
+    .unwrap();
+
+    assert_eq!(report.restored, vec!["pdf".to_string()]);
+    assert_eq!(report.relinked, 0);
+    assert!(report.unlinked[0]
+        .reason
+        .starts_with("EXECUTABLES_UNCONFIRMED|"));
+    assert!(!claude_dir.join("pdf").exists());
+    let restored = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert_eq!(restored.contains_executables, Some(true));
+}
+
+#[test]
+fn corrupt_archive_leaves_local_skills_untouched() {
+    let old = tempfile::tempdir().unwrap();
+    let old_store = make_store(old.path());
+    let old_central = old.path().join("central");
+    make_skill(&old_store, &old_central, "s1", "pdf");
+    let big: String = (0..5000)
+        .map(|i| format!("{} ", i * 7919 % 10007))
+        .collect();
+    fs::write(old_central.join("pdf/big.txt"), big).unwrap();
+    let archive = old.path().join("backup.zip");
+    create_backup(&old_store, &old_central, &archive, 1).unwrap();
+    let mut bytes = fs::read(&archive).unwrap();
+    let name = b"central/pdf/big.txt";
+    let pos = bytes.windows(name.len()).position(|w| w == name).unwrap();
+    for byte in &mut bytes[pos + name.len() + 200..pos + name.len() + 260] {
+        *byte ^= 0xff;
+    }
+    fs::write(&archive, bytes).unwrap();
+
+    let new = tempfile::tempdir().unwrap();
+    let store = make_store(new.path());
+    let central = new.path().join("repo");
+    make_skill(&store, &central, "local-pdf", "pdf");
+    fs::write(central.join("pdf/SKILL.md"), "local edit").unwrap();
+    let no_tools = |_: &str| None;
+
+    assert!(restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Overwrite,
+        2,
+        &no_tools,
+    )
+    .is_err());
+    assert!(store.get_skill_by_id("local-pdf").unwrap().is_some());
+    assert_eq!(
+        fs::read_to_string(central.join("pdf/SKILL.md")).unwrap(),
+        "local edit"
+    );
+    let leftovers: Vec<_> = fs::read_dir(new.path())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().contains("-restore-"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn skills_in_added_repos_are_backed_up_and_restored() {
+    use crate::core::skill_store::CentralRepoRecord;
+
+    let repo = |id: &str, path: &Path| CentralRepoRecord {
+        id: id.to_string(),
+        name: "Work".to_string(),
+        path: path.to_string_lossy().to_string(),
+        created_at: 1,
+    };
+    let old = tempfile::tempdir().unwrap();
+    let old_store = make_store(old.path());
+    let old_central = old.path().join("central");
+    let old_work = old.path().join("work");
+    make_skill(&old_store, &old_central, "s1", "pdf");
+    old_store
+        .insert_central_repo(&repo("work-id", &old_work))
+        .unwrap();
+    let lint = make_skill(&old_store, &old_work, "s2", "lint");
+    old_store
+        .upsert_skill(&SkillRecord {
+            repo_id: Some("work-id".to_string()),
+            ..lint
+        })
+        .unwrap();
+    let archive = old.path().join("backup.zip");
+    let manifest = create_backup(&old_store, &old_central, &archive, 1).unwrap();
+    assert_eq!(manifest.skill_count, 2);
+    assert_eq!(manifest.repos.len(), 1);
+    let no_tools = |_: &str| None;
+
+    // Without the repo here, its skills join the default one.
+    let new = tempfile::tempdir().unwrap();
+    let store = make_store(new.path());
+    let central = new.path().join("repo");
+    let report = restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Skip,
+        2,
+        &no_tools,
+    )
+    .unwrap();
+    assert_eq!(report.restored.len(), 2);
+    assert!(report.skipped.is_empty());
+    let restored = store.get_skill_by_id("s2").unwrap().unwrap();
+    assert_eq!(Path::new(&restored.central_path), central.join("lint"));
+    assert_eq!(restored.repo_id, None);
+    assert!(central.join("lint/SKILL.md").is_file());
+
+    // With a repo of the same id, they go back into it.
+    let other = tempfile::tempdir().unwrap();
+    let store = make_store(other.path());
+    let central = other.path().join("repo");
+    let work = other.path().join("work");
+    store.insert_central_repo(&repo("work-id", &work)).unwrap();
+    restore_backup(
+        &store,
+        &central,
+        &archive,
+        RestoreStrategy::Skip,
+        3,
+        &no_tools,
+    )
+    .unwrap();
+    let restored = store.get_skill_by_id("s2").unwrap().unwrap();
+    assert_eq!(Path::new(&restored.central_path), work.join("lint"));
+    assert_eq!(restored.repo_id.as_deref(), Some("work-id"));
+    assert!(work.join("lint/scripts/run.sh").is_file());
+    assert!(!central.join("lint").exists());
+    assert!(central.join("pdf/SKILL.md").is_file());
+}
@@ -0,0 +1,50 @@
+use std::fs;
+use std::time::Duration;
+
+use super::*;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+#[test]
+fn cleanup_delay_defaults_and_rejects_out_of_range() {
+    let (_dir, store) = make_store();
+    assert_eq!(
+        get_git_cache_cleanup_delay_secs(&store),
+        DEFAULT_GIT_CACHE_CLEANUP_DELAY_SECS
+    );
+    assert_eq!(set_git_cache_cleanup_delay_secs(&store, 0).unwrap(), 0);
+    assert_eq!(get_git_cache_cleanup_delay_secs(&store), 0);
+    assert!(set_git_cache_cleanup_delay_secs(&store, -1).is_err());
+    assert!(
+        set_git_cache_cleanup_delay_secs(&store, MAX_GIT_CACHE_CLEANUP_DELAY_SECS + 1).is_err()
+    );
+}
+
+#[test]
+fn throttled_cleanup_removes_only_stale_caches() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join(CACHE_DIR_NAME);
+    for (name, fetched) in [("stale", 1), ("fresh", now_ms())] {
+        fs::create_dir_all(root.join(name).join(".git")).unwrap();
+        fs::write(
+            root.join(name).join(CACHE_META_FILE),
+            format!("{{\"last_fetched_ms\":{}}}", fetched),
+        )
+        .unwrap();
+    }
+
+    let removed = cleanup_git_cache_dirs_in(
+        dir.path(),
+        Duration::from_secs(24 * 60 * 60),
+        Duration::from_millis(1),
+    )
+    .unwrap();
+    assert_eq!(removed, 1);
+    assert!(!root.join("stale").exists());
+    assert!(root.join("fresh").exists());
+}
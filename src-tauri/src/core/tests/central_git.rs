@@ -0,0 +1,168 @@
+use std::fs;
+
+use super::*;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn head_message(central: &Path) -> String {
+    let repo = Repository::open(central).unwrap();
+    let commit = repo.head().unwrap().peel_to_commit().unwrap();
+    commit.message().unwrap().to_string()
+}
+
+fn commit_count(central: &Path) -> usize {
+    let repo = Repository::open(central).unwrap();
+    let mut walk = repo.revwalk().unwrap();
+    walk.push_head().unwrap();
+    walk.count()
+}
+
+#[test]
+fn enabling_commits_the_library_and_changes_follow() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    fs::create_dir_all(central.join("pdf")).unwrap();
+    fs::write(central.join("pdf/SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+
+    record_central_change(&store, &central, "ignored while disabled");
+    assert!(Repository::open(&central).is_err());
+
+    assert!(set_central_git_enabled(&store, &central, true).unwrap());
+    assert!(get_central_git_enabled(&store));
+    assert_eq!(head_message(&central), "Start tracking skill library");
+    assert!(fs::read_to_string(central.join(".gitignore"))
+        .unwrap()
+        .contains(".skills-hub-*"));
+
+    // Skills Hub's own scratch dirs are never committed; an unchanged tree makes no commit.
+    fs::create_dir_all(central.join(".skills-hub-update-x")).unwrap();
+    fs::write(central.join(".skills-hub-update-x/a"), "tmp").unwrap();
+    record_central_change(&store, &central, "nothing");
+    assert_eq!(commit_count(&central), 1);
+
+    fs::remove_dir_all(central.join("pdf")).unwrap();
+    record_central_change(&store, &central, "Delete pdf");
+    assert_eq!(head_message(&central), "Delete pdf");
+    let repo = Repository::open(&central).unwrap();
+    let tree = repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(tree.get_path(Path::new("pdf/SKILL.md")).is_err());
+}
+
+#[test]
+fn installs_commit_with_a_descriptive_message() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    set_central_git_enabled(&store, &central, true).unwrap();
+
+    let source = tempfile::tempdir().unwrap();
+    fs::write(source.path().join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+    crate::core::installer::install_local_skill(
+        app.handle(),
+        &store,
+        source.path(),
+        Some("notes".to_string()),
+    )
+    .unwrap();
+
+    let message = head_message(&central);
+    assert!(message.starts_with("Install notes\n"), "{}", message);
+    assert_eq!(commit_count(&central), 2);
+}
+
+fn versioned_library(dir: &Path, remote: &Path, skill: &str) -> (SkillStore, std::path::PathBuf) {
+    let store = make_store(dir);
+    let central = dir.join("central");
+    fs::create_dir_all(central.join(skill)).unwrap();
+    fs::write(
+        central.join(skill).join("SKILL.md"),
+        format!("---\nname: {}\n---\n", skill),
+    )
+    .unwrap();
+    set_central_git_enabled(&store, &central, true).unwrap();
+    set_central_remote(&store, &central, &remote.to_string_lossy()).unwrap();
+    (store, central)
+}
+
+#[test]
+fn two_machines_share_one_library_through_a_remote() {
+    let remote_dir = tempfile::tempdir().unwrap();
+    let remote = remote_dir.path().join("library.git");
+    Repository::init_bare(&remote).unwrap();
+    let a = tempfile::tempdir().unwrap();
+    let b = tempfile::tempdir().unwrap();
+    let (store_a, central_a) = versioned_library(a.path(), &remote, "pdf");
+    let (store_b, central_b) = versioned_library(b.path(), &remote, "docs");
+    assert_eq!(
+        get_central_remote(&central_a).as_deref(),
+        Some(remote.to_string_lossy().as_ref())
+    );
+
+    push_central_repo(&store_a, &central_a).unwrap();
+
+    // B started its own history, so the pull merges; A's skill is adopted into B's database.
+    let report = pull_central_repo(&store_b, &central_b).unwrap();
+    assert!(report.merged);
+    assert_eq!(report.added, vec!["pdf".to_string()]);
+    assert!(central_b.join("pdf/SKILL.md").exists());
+    assert!(central_b.join("docs/SKILL.md").exists());
+    let adopted = store_b.list_skills().unwrap();
+    assert_eq!(adopted.len(), 1);
+    assert_eq!(adopted[0].name, "pdf");
+    push_central_repo(&store_b, &central_b).unwrap();
+
+    let report = pull_central_repo(&store_a, &central_a).unwrap();
+    assert!(!report.merged);
+    assert_eq!(report.added, vec!["docs".to_string()]);
+    assert_eq!(
+        pull_central_repo(&store_a, &central_a).unwrap(),
+        PullReport::default()
+    );
+
+    // Both edit the same skill: B's push is rejected and its pull names the skill.
+    fs::write(
+        central_a.join("pdf/SKILL.md"),
+        "---\nname: pdf\n---\nfrom a\n",
+    )
+    .unwrap();
+    push_central_repo(&store_a, &central_a).unwrap();
+    fs::write(
+        central_b.join("pdf/SKILL.md"),
+        "---\nname: pdf\n---\nfrom b\n",
+    )
+    .unwrap();
+    let err = push_central_repo(&store_b, &central_b).unwrap_err();
+    assert!(err.to_string().starts_with("SYNC_REJECTED|"), "{:#}", err);
+    let err = pull_central_repo(&store_b, &central_b).unwrap_err();
+    assert_eq!(err.to_string(), "SYNC_CONFLICT|pdf");
+    assert_eq!(
+        fs::read_to_string(central_b.join("pdf/SKILL.md")).unwrap(),
+        "---\nname: pdf\n---\nfrom b\n"
+    );
+}
+
+#[test]
+fn remote_requires_versioning_and_empty_url_removes_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    assert!(set_central_remote(&store, &central, "/tmp/remote.git").is_err());
+
+    set_central_git_enabled(&store, &central, true).unwrap();
+    assert_eq!(
+        set_central_remote(&store, &central, " /tmp/remote.git ").unwrap(),
+        Some("/tmp/remote.git".to_string())
+    );
+    assert_eq!(set_central_remote(&store, &central, "").unwrap(), None);
+    assert_eq!(get_central_remote(&central), None);
+    assert!(pull_central_repo(&store, &central).is_err());
+}
@@ -1,7 +1,13 @@
 use std::path::PathBuf;
 
-use crate::core::central_repo::{ensure_central_repo, resolve_central_repo_path};
-use crate::core::skill_store::SkillStore;
+use crate::core::central_repo::{
+    add_central_repo, central_repo_paths, check_location_in, ensure_central_repo, ensure_writable,
+    list_central_repos, move_central_repo, move_skill_to_repo, remove_central_repo,
+    resolve_central_repo_path, scan_central_repo, set_install_repo, DEFAULT_REPO_ID,
+};
+use crate::core::installer::{adopt_orphan, install_local_skill};
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::sync_engine::copy_dir_recursive;
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -31,3 +37,247 @@ fn ensure_central_repo_creates_dir() {
     ensure_central_repo(&p).unwrap();
     assert!(p.exists());
 }
+
+#[test]
+fn unsafe_locations_are_refused() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let home = dir.path().join("home");
+    let tool_dir = home.join(".claude/skills");
+    let cache = dir.path().join("cache");
+    std::fs::create_dir_all(&tool_dir).unwrap();
+    std::fs::create_dir_all(&cache).unwrap();
+    let tools = vec![tool_dir.clone()];
+    let check = |p: PathBuf| check_location_in(&p, Some(&home), &tools, Some(&cache));
+
+    let root = dir.path().ancestors().last().unwrap().to_path_buf();
+    for (path, reason) in [
+        (root, "filesystem root"),
+        (home.clone(), "home directory"),
+        (home.join("x/.."), "home directory"),
+        (tool_dir.clone(), "tool skills directory"),
+        (tool_dir.join("nested/repo"), "tool skills directory"),
+        (cache.join("skills-hub-git-cache/repo"), "app cache"),
+    ] {
+        let err = check(path.clone()).unwrap_err().to_string();
+        assert!(err.contains(reason), "{:?}: {}", path, err);
+    }
+
+    check(home.join(".skillshub")).unwrap();
+    check(home.join(".claude/skillshub")).unwrap();
+}
+
+#[test]
+fn writability_is_probed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    ensure_writable(dir.path()).unwrap();
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    assert!(ensure_writable(&dir.path().join("missing")).is_err());
+}
+
+fn add_skill(store: &SkillStore, central: &std::path::Path, name: &str) -> PathBuf {
+    let path = central.join(name);
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(path.join("SKILL.md"), format!("# {}", name)).unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: path.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    path
+}
+
+fn add_target(store: &SkillStore, name: &str, tool: &str, path: &std::path::Path, mode: &str) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", name, tool),
+            skill_id: format!("id-{}", name),
+            tool: tool.to_string(),
+            target_path: path.to_string_lossy().to_string(),
+            mode: mode.to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn move_central_repo_carries_skills_and_targets() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let old_base = dir.path().join("old");
+    store
+        .set_setting("central_repo_path", old_base.to_string_lossy().as_ref())
+        .unwrap();
+    let pdf = add_skill(&store, &old_base, "pdf");
+    add_skill(&store, &old_base, "docs");
+    let snapshot = old_base.join(".skills-hub-rollback/id-pdf");
+    std::fs::create_dir_all(&snapshot).unwrap();
+    std::fs::write(snapshot.join("snapshot.json"), "{}").unwrap();
+    let link = dir.path().join("claude/pdf");
+    std::fs::create_dir_all(link.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(&pdf, &link).unwrap();
+    add_target(&store, "pdf", "claude_code", &link, "symlink");
+    let copy = dir.path().join("cursor/docs");
+    copy_dir_recursive(&old_base.join("docs"), &copy).unwrap();
+    add_target(&store, "docs", "cursor", &copy, "copy");
+
+    let new_base = dir.path().join("new");
+    let report = move_central_repo(app.handle(), &store, &new_base, 5).unwrap();
+    assert_eq!(report.moved.len(), 2);
+    assert_eq!(report.retargeted.len(), 2);
+    assert!(report.failed.is_empty() && report.kept.is_empty());
+    assert!(!old_base.exists());
+    assert_eq!(std::fs::read_link(&link).unwrap(), new_base.join("pdf"));
+    assert!(copy.join("SKILL.md").is_file());
+    assert!(new_base
+        .join(".skills-hub-rollback/id-pdf/snapshot.json")
+        .is_file());
+    let skill = store.get_skill_by_id("id-docs").unwrap().unwrap();
+    assert_eq!(skill.central_path, new_base.join("docs").to_string_lossy());
+    assert_eq!(
+        resolve_central_repo_path(app.handle(), &store).unwrap(),
+        new_base
+    );
+}
+
+#[test]
+fn move_central_repo_changes_nothing_when_a_destination_exists() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let old_base = dir.path().join("old");
+    store
+        .set_setting("central_repo_path", old_base.to_string_lossy().as_ref())
+        .unwrap();
+    let pdf = add_skill(&store, &old_base, "pdf");
+    let new_base = dir.path().join("new");
+    std::fs::create_dir_all(new_base.join("pdf")).unwrap();
+
+    let err = move_central_repo(app.handle(), &store, &new_base, 5).unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    assert!(pdf.join("SKILL.md").is_file());
+    assert_eq!(
+        resolve_central_repo_path(app.handle(), &store).unwrap(),
+        old_base
+    );
+    let inside = old_base.join("nested");
+    assert!(move_central_repo(app.handle(), &store, &inside, 5).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn installs_and_moves_follow_the_chosen_repo() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let default_base = dir.path().join("default");
+    store
+        .set_setting("central_repo_path", default_base.to_string_lossy().as_ref())
+        .unwrap();
+    let work_base = dir.path().join("work");
+    assert!(add_central_repo(app.handle(), &store, "Work", &default_base.join("x"), 1).is_err());
+    let work = add_central_repo(app.handle(), &store, " Work ", &work_base, 1).unwrap();
+    assert_eq!(work.name, "Work");
+    assert!(add_central_repo(app.handle(), &store, "work", &dir.path().join("w2"), 1).is_err());
+    assert_eq!(
+        central_repo_paths(app.handle(), &store).unwrap(),
+        vec![default_base.clone(), work_base.clone()]
+    );
+
+    set_install_repo(&store, &work.id).unwrap();
+    let source = dir.path().join("src/pdf");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::write(source.join("SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+    let installed = install_local_skill(app.handle(), &store, &source, None).unwrap();
+    assert_eq!(installed.central_path, work_base.join("pdf"));
+    let skill = store.get_skill_by_id(&installed.skill_id).unwrap().unwrap();
+    assert_eq!(skill.repo_id.as_deref(), Some(work.id.as_str()));
+    assert!(remove_central_repo(&store, &work.id).is_err());
+
+    let link = dir.path().join("claude/pdf");
+    std::fs::create_dir_all(link.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(&installed.central_path, &link).unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "pdf-claude".to_string(),
+            skill_id: installed.skill_id.clone(),
+            tool: "claude_code".to_string(),
+            target_path: link.to_string_lossy().to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+    let report = move_skill_to_repo(
+        app.handle(),
+        &store,
+        &installed.skill_id,
+        DEFAULT_REPO_ID,
+        2,
+    )
+    .unwrap();
+    assert_eq!(report.retargeted, vec!["pdf:claude_code".to_string()]);
+    assert_eq!(std::fs::read_link(&link).unwrap(), default_base.join("pdf"));
+    assert!(!work_base.join("pdf").exists());
+    let skill = store.get_skill_by_id(&installed.skill_id).unwrap().unwrap();
+    assert_eq!(skill.repo_id, None);
+
+    remove_central_repo(&store, &work.id).unwrap();
+    let repos = list_central_repos(app.handle(), &store).unwrap();
+    assert_eq!(repos.len(), 1);
+    assert!(repos[0].install_target);
+    assert_eq!(repos[0].skills, 1);
+    assert!(remove_central_repo(&store, DEFAULT_REPO_ID).is_err());
+}
+
+#[test]
+fn orphan_directories_are_listed_and_adopted() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let base = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", base.to_string_lossy().as_ref())
+        .unwrap();
+    add_skill(&store, &base, "managed");
+    std::fs::create_dir_all(base.join(".skills-hub-rollback/x")).unwrap();
+    std::fs::create_dir_all(base.join("notes")).unwrap();
+    let copied = base.join("copied");
+    std::fs::create_dir_all(&copied).unwrap();
+    std::fs::write(copied.join("SKILL.md"), "---\nname: copied\n---\n").unwrap();
+
+    let orphans = scan_central_repo(app.handle(), &store).unwrap();
+    let found: Vec<_> = orphans
+        .iter()
+        .map(|o| (o.name.as_str(), o.has_skill_md))
+        .collect();
+    assert_eq!(found, vec![("copied", true), ("notes", false)]);
+    assert_eq!(orphans[0].repo_id, DEFAULT_REPO_ID);
+
+    let err = adopt_orphan(app.handle(), &store, "notes", None)
+        .err()
+        .expect("a directory without SKILL.md is refused");
+    assert!(err.to_string().starts_with("SKILL_INVALID|"));
+    assert!(adopt_orphan(app.handle(), &store, "managed", None).is_err());
+    let adopted = adopt_orphan(app.handle(), &store, "copied", None).unwrap();
+    let skill = store.get_skill_by_id(&adopted.skill_id).unwrap().unwrap();
+    assert_eq!(skill.central_path, copied.to_string_lossy());
+    assert_eq!(skill.source_type, "local");
+    assert_eq!(scan_central_repo(app.handle(), &store).unwrap().len(), 1);
+}
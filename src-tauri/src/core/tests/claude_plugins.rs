@@ -0,0 +1,138 @@
+use std::fs;
+
+use super::*;
+
+fn write_skill(dir: &Path, name: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!(
+            "---\nname: {}\ndescription: {} skill\n---\nbody\n",
+            name, name
+        ),
+    )
+    .unwrap();
+}
+
+fn make_plugin(dir: &Path, manifest: &str) {
+    fs::create_dir_all(dir.join(".claude-plugin")).unwrap();
+    fs::write(dir.join(PLUGIN_MANIFEST), manifest).unwrap();
+    write_skill(&dir.join("skills/pdf"), "pdf");
+    write_skill(&dir.join("skills/xlsx"), "xlsx");
+    fs::create_dir_all(dir.join("agents")).unwrap();
+    fs::write(dir.join("agents/reviewer.md"), "agent").unwrap();
+    fs::create_dir_all(dir.join("commands")).unwrap();
+    fs::write(dir.join("commands/ship.md"), "command").unwrap();
+}
+
+#[test]
+fn plugin_skills_come_from_the_skills_dir_and_manifest_paths() {
+    let root = tempfile::tempdir().unwrap();
+    make_plugin(
+        root.path(),
+        r#"{"name": "office", "version": "1.0.0", "skills": ["./extra/docx", "./more"]}"#,
+    );
+    write_skill(&root.path().join("extra/docx"), "docx");
+    write_skill(&root.path().join("more/pptx"), "pptx");
+
+    let scan = scan_plugin_package(root.path(), &[]).unwrap();
+    assert_eq!(scan.plugins, vec!["office"]);
+    let subpaths: Vec<&str> = scan.skills.iter().map(|s| s.subpath.as_str()).collect();
+    assert_eq!(
+        subpaths,
+        vec!["skills/pdf", "skills/xlsx", "extra/docx", "more/pptx"]
+    );
+    assert!(scan.skills.iter().all(|s| s.plugin == "office"));
+
+    fs::write(
+        root.path().join(PLUGIN_MANIFEST),
+        r#"{"name": "office", "skills": "../outside"}"#,
+    )
+    .unwrap();
+    assert!(scan_plugin_package(root.path(), &[]).is_err());
+}
+
+#[test]
+fn marketplaces_list_local_plugins_and_report_external_ones() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir_all(root.path().join(".claude-plugin")).unwrap();
+    fs::write(
+        root.path().join(MARKETPLACE_MANIFEST),
+        r#"{
+            "name": "team",
+            "owner": {"name": "Team"},
+            "metadata": {"pluginRoot": "./plugins"},
+            "plugins": [
+                {"name": "office", "source": "./plugins/office"},
+                {"name": "review", "source": "review"},
+                {"name": "remote", "source": {"source": "github", "repo": "acme/remote-plugin"}}
+            ]
+        }"#,
+    )
+    .unwrap();
+    make_plugin(&root.path().join("plugins/office"), r#"{"name": "office"}"#);
+    write_skill(&root.path().join("plugins/review/skills/lint"), "lint");
+
+    let scan = scan_plugin_package(root.path(), &[]).unwrap();
+    assert_eq!(scan.plugins, vec!["office", "review"]);
+    let subpaths: Vec<&str> = scan.skills.iter().map(|s| s.subpath.as_str()).collect();
+    assert_eq!(
+        subpaths,
+        vec![
+            "plugins/office/skills/pdf",
+            "plugins/office/skills/xlsx",
+            "plugins/review/skills/lint"
+        ]
+    );
+    assert_eq!(
+        scan.external,
+        vec![ExternalPlugin {
+            name: "remote".to_string(),
+            source: "https://github.com/acme/remote-plugin".to_string(),
+        }]
+    );
+
+    let scan = scan_plugin_package(root.path(), &["review".to_string()]).unwrap();
+    assert_eq!(scan.plugins, vec!["review"]);
+    assert!(scan.external.is_empty());
+}
+
+#[test]
+fn installing_a_local_plugin_manages_only_its_skills() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    let plugin = dir.path().join("office-plugin");
+    make_plugin(&plugin, r#"{"name": "office"}"#);
+
+    let report = install_plugin_package(
+        app.handle(),
+        &store,
+        &plugin.to_string_lossy(),
+        &[],
+        InstallOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(report.installed, vec!["pdf", "xlsx"]);
+    assert!(report.failed.is_empty());
+    assert!(central.join("pdf/SKILL.md").exists());
+    assert!(!central.join("agents").exists());
+    assert!(!central.join("reviewer").exists());
+    assert_eq!(store.list_skills().unwrap().len(), 2);
+
+    let not_a_plugin = dir.path().join("plain");
+    fs::create_dir_all(&not_a_plugin).unwrap();
+    assert!(install_plugin_package(
+        app.handle(),
+        &store,
+        &not_a_plugin.to_string_lossy(),
+        &[],
+        InstallOptions::default(),
+    )
+    .is_err());
+}
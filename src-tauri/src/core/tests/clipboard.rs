@@ -0,0 +1,91 @@
+use super::*;
+
+fn install(
+    repo: &str,
+    branch: Option<&str>,
+    subpath: Option<&str>,
+) -> (String, Option<String>, Option<String>) {
+    (
+        repo.to_string(),
+        branch.map(str::to_string),
+        subpath.map(str::to_string),
+    )
+}
+
+fn parsed(text: &str) -> Option<(String, Option<String>, Option<String>)> {
+    parse_install_text(text, true).map(|i| (i.repo_url, i.branch, i.subpath))
+}
+
+#[test]
+fn recognizes_github_links_shorthands_and_install_links() {
+    assert_eq!(
+        parsed("Try this skill: https://github.com/acme/skills/tree/main/skills/pdf."),
+        Some(install(
+            "https://github.com/acme/skills",
+            Some("main"),
+            Some("skills/pdf")
+        ))
+    );
+    assert_eq!(
+        parsed("(https://github.com/acme/skills/blob/v2/pdf/SKILL.md?plain=1)"),
+        Some(install(
+            "https://github.com/acme/skills",
+            Some("v2"),
+            Some("pdf")
+        ))
+    );
+    assert_eq!(
+        parsed("https://www.github.com/acme/one-skill.git"),
+        Some(install("https://github.com/acme/one-skill", None, None))
+    );
+    assert_eq!(
+        parsed("  acme/skills\n"),
+        Some(install("https://github.com/acme/skills", None, None))
+    );
+    assert_eq!(
+        parsed("skillshub://install?repo=acme/skills&path=pdf"),
+        Some(install("https://github.com/acme/skills", None, Some("pdf")))
+    );
+    let found = parse_install_text("see https://github.com/acme/skills, thanks", false).unwrap();
+    assert_eq!(found.text, "https://github.com/acme/skills");
+}
+
+#[test]
+fn shorthands_need_asking_and_github_names() {
+    assert_eq!(parse_install_text("and/or", false), None);
+    assert_eq!(parse_install_text("acme/skills", false), None);
+    assert_eq!(
+        parsed("my-org/skills_v2"),
+        Some(install("https://github.com/my-org/skills_v2", None, None))
+    );
+    for text in [
+        "-acme/skills",
+        "acme-/skills",
+        "ac--me/skills",
+        "acme/..",
+        "acme/skills!",
+    ] {
+        assert_eq!(parsed(text), None, "{:?}", text);
+    }
+}
+
+#[test]
+fn ignores_text_without_an_installable_source() {
+    for text in [
+        "",
+        "hello world",
+        "read the docs/guide later",
+        "https://example.com/acme/skills",
+        "https://github.com/acme",
+        "https://github.com/acme/skills/tree/main/../../etc",
+        "/usr/local/bin",
+        "C:\\Users\\me",
+    ] {
+        assert_eq!(parse_install_text(text, true), None, "{:?}", text);
+    }
+    let long = format!(
+        "{} https://github.com/acme/skills",
+        "x".repeat(MAX_CLIPBOARD_CHARS)
+    );
+    assert_eq!(parse_install_text(&long, true), None);
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn providers_are_recognised_by_their_folder_names() {
+    let provider = |p: &str| detect_cloud_provider(Path::new(p));
+    assert_eq!(
+        provider("/Users/u/Dropbox/skills"),
+        Some(CloudProvider::Dropbox)
+    );
+    assert_eq!(
+        provider("/Users/u/Library/CloudStorage/Dropbox-Work/skills"),
+        Some(CloudProvider::Dropbox)
+    );
+    assert_eq!(
+        provider("/Users/u/Library/Mobile Documents/com~apple~CloudDocs/skills"),
+        Some(CloudProvider::ICloud)
+    );
+    assert_eq!(
+        provider("/Users/u/OneDrive - Contoso/skills"),
+        Some(CloudProvider::OneDrive)
+    );
+    assert_eq!(
+        provider("/Users/u/Library/CloudStorage/GoogleDrive-u@example.com/My Drive/skills"),
+        Some(CloudProvider::GoogleDrive)
+    );
+    assert_eq!(provider("/home/u/.skillshub"), None);
+    assert_eq!(provider("/home/u/dropbox-notes/skills"), None);
+}
+
+#[test]
+fn conflict_copies_are_recognised() {
+    assert!(is_conflict_copy("SKILL (conflicted copy 2024-01-02).md"));
+    assert!(is_conflict_copy(
+        "SKILL (Ana's conflicted copy 2024-01-02).md"
+    ));
+    assert!(is_conflict_copy("pdf (Case Conflict)"));
+    assert!(is_conflict_copy(
+        "SKILL.sync-conflict-20240102-120000-ABC.md"
+    ));
+    assert!(!is_conflict_copy("SKILL.md"));
+    assert!(!is_conflict_copy("conflicts.md"));
+}
+
+#[test]
+fn scan_reports_icloud_stubs_and_conflict_copies_per_skill() {
+    let root = tempfile::tempdir().unwrap();
+    let central = root
+        .path()
+        .join("Mobile Documents/com~apple~CloudDocs/skills");
+    fs::create_dir_all(central.join("pdf/scripts")).unwrap();
+    fs::write(central.join("pdf/SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+    fs::write(central.join("pdf/scripts/.run.sh.icloud"), "stub").unwrap();
+    fs::write(
+        central.join("pdf/SKILL (conflicted copy 2024-01-02).md"),
+        "---\nname: pdf\n---\nother\n",
+    )
+    .unwrap();
+    fs::create_dir_all(central.join("docs")).unwrap();
+    fs::write(central.join("docs/.SKILL.md.icloud"), "stub").unwrap();
+    fs::create_dir_all(central.join(".skills-hub-update-x")).unwrap();
+    fs::write(central.join(".skills-hub-update-x/.a.icloud"), "stub").unwrap();
+
+    let report = scan_cloud_sync(&central).unwrap();
+    assert_eq!(report.provider, Some(CloudProvider::ICloud));
+    let paths = |files: &[CloudFile]| -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|f| (f.skill.clone(), f.path.replace('\\', "/")))
+            .collect()
+    };
+    assert_eq!(
+        paths(&report.placeholders),
+        vec![
+            ("docs".to_string(), "docs/.SKILL.md.icloud".to_string()),
+            ("pdf".to_string(), "pdf/scripts/.run.sh.icloud".to_string()),
+        ]
+    );
+    assert_eq!(
+        paths(&report.conflict_copies),
+        vec![(
+            "pdf".to_string(),
+            "pdf/SKILL (conflicted copy 2024-01-02).md".to_string()
+        )]
+    );
+
+    assert!(is_offline(&central.join("docs/SKILL.md")));
+    assert!(!is_offline(&central.join("pdf/SKILL.md")));
+    assert!(!is_offline(&central.join("pdf/missing.md")));
+    assert!(crate::core::content_hash::hash_dir(&central.join("docs")).is_err());
+    assert!(crate::core::content_hash::hash_dir(&central.join("pdf/SKILL.md")).is_ok());
+}
@@ -1,6 +1,9 @@
 use std::fs;
+use std::time::{Duration, SystemTime};
 
-use crate::core::content_hash::hash_dir;
+use sha2::{Digest, Sha256};
+
+use crate::core::content_hash::{hash_dir, hash_dir_cached, hash_dir_limited, FileHashCache};
 
 #[test]
 fn hash_changes_with_content_and_ignores_git_dir() {
@@ -22,3 +25,86 @@ fn hash_changes_with_content_and_ignores_git_dir() {
     let h3 = hash_dir(root).unwrap();
     assert_ne!(h2, h3);
 }
+
+#[test]
+fn cached_hash_matches_and_skips_unchanged_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    fs::create_dir_all(root.join("sub")).unwrap();
+    fs::write(root.join("a.txt"), b"hello").unwrap();
+    fs::write(root.join("sub/b.txt"), b"world").unwrap();
+    // Old enough to be trusted by the cache.
+    let old = SystemTime::now() - Duration::from_secs(3600);
+    let set_mtime = |rel: &str, time: SystemTime| {
+        fs::File::options()
+            .write(true)
+            .open(root.join(rel))
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    };
+    set_mtime("a.txt", old);
+    set_mtime("sub/b.txt", old);
+
+    let mut cache = FileHashCache::new();
+    let h1 = hash_dir_cached(root, &mut cache).unwrap();
+    assert_eq!(h1, hash_dir(root).unwrap());
+    assert_eq!(cache.len(), 2);
+
+    // Same size and mtime: the cached digest is used without reading the file.
+    fs::write(root.join("a.txt"), b"HELLO").unwrap();
+    set_mtime("a.txt", old);
+    assert_eq!(hash_dir_cached(root, &mut cache).unwrap(), h1);
+
+    // A new mtime invalidates the entry.
+    set_mtime("a.txt", old + Duration::from_secs(1));
+    let h2 = hash_dir_cached(root, &mut cache).unwrap();
+    assert_ne!(h2, h1);
+    assert_eq!(h2, hash_dir(root).unwrap());
+
+    // Freshly written files are hashed but not cached; deleted ones are dropped.
+    fs::remove_file(root.join("sub/b.txt")).unwrap();
+    fs::write(root.join("c.txt"), b"new").unwrap();
+    assert_eq!(
+        hash_dir_cached(root, &mut cache).unwrap(),
+        hash_dir(root).unwrap()
+    );
+    assert_eq!(cache.keys().collect::<Vec<_>>(), vec!["a.txt"]);
+}
+
+#[test]
+fn files_larger_than_the_buffer_hash_like_a_single_read() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let content: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(dir.path().join("big.bin"), &content).unwrap();
+
+    let mut expected = Sha256::new();
+    expected.update(b"big.bin");
+    expected.update(hex::encode(Sha256::digest(&content)).as_bytes());
+    assert_eq!(
+        hash_dir_limited(dir.path(), &mut FileHashCache::new(), None).unwrap(),
+        hex::encode(expected.finalize())
+    );
+}
+
+#[test]
+fn oversized_files_contribute_only_their_size() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    fs::write(root.join("model.bin"), b"0123456789abcdef").unwrap();
+    fs::write(root.join("SKILL.md"), b"# s").unwrap();
+    let mut cache = FileHashCache::new();
+
+    let h1 = hash_dir_limited(root, &mut cache, Some(8)).unwrap();
+    assert!(!cache.contains_key("model.bin"));
+
+    fs::write(root.join("model.bin"), b"fedcba9876543210").unwrap();
+    assert_eq!(hash_dir_limited(root, &mut cache, Some(8)).unwrap(), h1);
+
+    fs::write(root.join("model.bin"), b"0123456789abcdefg").unwrap();
+    assert_ne!(hash_dir_limited(root, &mut cache, Some(8)).unwrap(), h1);
+    assert_ne!(
+        hash_dir_limited(root, &mut cache, None).unwrap(),
+        hash_dir_limited(root, &mut cache, Some(8)).unwrap()
+    );
+}
@@ -0,0 +1,135 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_metadata::SkillFrontmatter;
+
+#[test]
+fn mdc_frontmatter_is_read_even_when_it_is_not_yaml() {
+    let rule = CursorRule::parse(
+        "---\ndescription: React component conventions\nglobs: *.tsx,src/**/*.ts\nalwaysApply: false\n---\n# Components\n\nUse hooks.\n",
+    );
+    assert_eq!(
+        rule.description.as_deref(),
+        Some("React component conventions")
+    );
+    assert_eq!(rule.globs, vec!["*.tsx", "src/**/*.ts"]);
+    assert!(!rule.always_apply);
+    assert_eq!(rule.body, "# Components\n\nUse hooks.");
+
+    let rule = CursorRule::parse(
+        "---\ndescription:\nglobs:\n  - \"*.py\"\n  - tests/*.py\nalwaysApply: true\n---\nbody",
+    );
+    assert_eq!(rule.description, None);
+    assert_eq!(rule.globs, vec!["*.py", "tests/*.py"]);
+    assert!(rule.always_apply);
+
+    let rule = CursorRule::parse("---\nglobs: [\"*.go\", '*.mod']\n---\n");
+    assert_eq!(rule.globs, vec!["*.go", "*.mod"]);
+
+    let rule = CursorRule::parse("Plain rule text.\n");
+    assert_eq!(rule.body, "Plain rule text.\n");
+}
+
+#[test]
+fn converted_skill_md_has_valid_frontmatter() {
+    let rule = CursorRule {
+        description: None,
+        globs: vec!["*.tsx".to_string()],
+        always_apply: false,
+        body: "Use hooks: always.\n".to_string(),
+    };
+    let text = rule.to_skill_md("react").unwrap();
+    let fm = SkillFrontmatter::parse(&text).unwrap();
+    assert_eq!(fm.name.as_deref(), Some("react"));
+    assert_eq!(
+        fm.description.as_deref(),
+        Some("Rules for files matching *.tsx, imported from the Cursor rule react.")
+    );
+    assert_eq!(
+        fm.metadata.get("cursor-globs"),
+        Some(&serde_yaml::Value::String("*.tsx".to_string()))
+    );
+    assert!(text.ends_with("---\n\nUse hooks: always.\n"));
+}
+
+#[test]
+fn rule_names_follow_skill_naming() {
+    assert_eq!(
+        rule_skill_name(Path::new("React Components.mdc")),
+        "react-components"
+    );
+    assert_eq!(
+        rule_skill_name(Path::new("frontend/api_client.mdc")),
+        "frontend-api-client"
+    );
+    assert_eq!(rule_skill_name(Path::new("__.mdc")), "cursor-rule");
+}
+
+#[test]
+fn imports_a_project_rules_dir_and_updates_from_the_rule() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+
+    let project = dir.path().join("project");
+    let rules = project.join(".cursor/rules");
+    fs::create_dir_all(rules.join("backend")).unwrap();
+    fs::write(
+        rules.join("style.mdc"),
+        "---\ndescription: Code style\nalwaysApply: true\n---\nUse tabs.\n",
+    )
+    .unwrap();
+    fs::write(
+        rules.join("backend/db.mdc"),
+        "---\nglobs: *.sql\n---\nNo SELECT *.\n",
+    )
+    .unwrap();
+    fs::write(rules.join("notes.md"), "not a rule").unwrap();
+
+    let report =
+        import_cursor_rules(app.handle(), &store, &project, InstallOptions::default()).unwrap();
+    assert!(report.failed.is_empty(), "{:?}", report.failed);
+    assert_eq!(report.imported, vec!["backend-db", "style"]);
+    let skill_md = fs::read_to_string(central.join("style/SKILL.md")).unwrap();
+    assert!(skill_md.contains("description: Code style"));
+    assert!(skill_md.ends_with("Use tabs.\n"));
+
+    let record = store
+        .list_skills()
+        .unwrap()
+        .into_iter()
+        .find(|s| s.name == "style")
+        .unwrap();
+    assert_eq!(record.source_type, CURSOR_RULE_SOURCE);
+    assert_eq!(
+        record.source_ref.as_deref().map(Path::new),
+        Some(rules.join("style.mdc").as_path())
+    );
+
+    fs::write(
+        rules.join("style.mdc"),
+        "---\ndescription: Code style\nalwaysApply: true\n---\nUse spaces.\n",
+    )
+    .unwrap();
+    crate::core::installer::update_managed_skill_from_source_with_force(
+        app.handle(),
+        &store,
+        &record.id,
+        false,
+    )
+    .unwrap();
+    assert!(fs::read_to_string(central.join("style/SKILL.md"))
+        .unwrap()
+        .ends_with("Use spaces.\n"));
+
+    // Importing again without a conflict policy reports each rule instead of aborting.
+    let report =
+        import_cursor_rules(app.handle(), &store, &rules, InstallOptions::default()).unwrap();
+    assert!(report.imported.is_empty());
+    assert_eq!(report.failed.len(), 2);
+}
@@ -0,0 +1,52 @@
+use super::{parse_deep_link, DeepLinkInstall, DeepLinkQueue};
+
+#[test]
+fn parses_install_links() {
+    assert_eq!(
+        parse_deep_link("skillshub://install?repo=owner/repo&path=skills/foo").unwrap(),
+        DeepLinkInstall {
+            repo_url: "https://github.com/owner/repo".to_string(),
+            subpath: Some("skills/foo".to_string()),
+        }
+    );
+    assert_eq!(
+        parse_deep_link("skillshub://install/?repo=https%3A%2F%2Fgithub.com%2Fo%2Fr.git").unwrap(),
+        DeepLinkInstall {
+            repo_url: "https://github.com/o/r".to_string(),
+            subpath: None,
+        }
+    );
+    assert_eq!(
+        parse_deep_link("skillshub:install?repo=o/r&path=/a/b/")
+            .unwrap()
+            .subpath
+            .as_deref(),
+        Some("a/b")
+    );
+}
+
+#[test]
+fn rejects_unsafe_or_unknown_links() {
+    for raw in [
+        "https://example.com/install?repo=o/r",
+        "skillshub://remove?repo=o/r",
+        "skillshub://install",
+        "skillshub://install?repo=o",
+        "skillshub://install?repo=o/r/extra",
+        "skillshub://install?repo=/etc/passwd",
+        "skillshub://install?repo=https%3A%2F%2Fevil.example%2Fo%2Fr",
+        "skillshub://install?repo=../r",
+        "skillshub://install?repo=o/r&path=../../etc",
+        "skillshub://install?repo=o/r&path=.git/hooks",
+    ] {
+        assert!(parse_deep_link(raw).is_err(), "{} should be rejected", raw);
+    }
+}
+
+#[test]
+fn queue_is_drained_once() {
+    let queue = DeepLinkQueue::default();
+    queue.push(parse_deep_link("skillshub://install?repo=o/r").unwrap());
+    assert_eq!(queue.take().len(), 1);
+    assert!(queue.take().is_empty());
+}
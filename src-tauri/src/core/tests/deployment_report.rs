@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::Path;
+
+use super::*;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn add_skill(store: &SkillStore, name: &str, central_path: &Path) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: name.to_string(),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central_path.to_string_lossy().to_string(),
+            content_hash: Some(hash_dir(central_path).unwrap()),
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str, mode: &str, path: &Path) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: path.to_string_lossy().to_string(),
+            mode: mode.to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1_714_555_800_000),
+        })
+        .unwrap();
+}
+
+fn write_skill(dir: &Path, body: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("SKILL.md"), body).unwrap();
+}
+
+#[test]
+fn copies_are_hashed_against_the_central_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    write_skill(&central.join("pdf"), "---\nname: pdf\n---\nbody\n");
+    write_skill(&central.join("docs"), "---\nname: docs\n---\nbody\n");
+    write_skill(&central.join("idle"), "---\nname: idle\n---\nbody\n");
+    add_skill(&store, "pdf", &central.join("pdf"));
+    add_skill(&store, "docs", &central.join("docs"));
+    add_skill(&store, "idle", &central.join("idle"));
+
+    let fresh = dir.path().join("cursor/pdf");
+    write_skill(&fresh, "---\nname: pdf\n---\nbody\n");
+    let edited = dir.path().join("cursor/docs");
+    write_skill(&edited, "---\nname: docs\n---\nedited\n");
+    add_target(&store, "pdf", "cursor", "copy", &fresh);
+    add_target(&store, "docs", "cursor", "copy", &edited);
+    add_target(
+        &store,
+        "pdf",
+        "codex",
+        "copy",
+        &dir.path().join("codex/pdf"),
+    );
+
+    let report = build_deployment_report(&store, &central, 0).unwrap();
+    assert_eq!(report.skill_count, 3);
+    assert_eq!(report.target_count, 3);
+    assert_eq!(report.undeployed, vec!["idle".to_string()]);
+    let tools: Vec<&str> = report.tools.iter().map(|t| t.tool.as_str()).collect();
+    assert_eq!(tools, vec!["codex", "cursor"]);
+    assert_eq!(report.tools[0].skills[0].hash, HashCheck::Missing);
+    let cursor: Vec<(&str, HashCheck)> = report.tools[1]
+        .skills
+        .iter()
+        .map(|s| (s.skill.as_str(), s.hash))
+        .collect();
+    assert_eq!(
+        cursor,
+        vec![("docs", HashCheck::Mismatch), ("pdf", HashCheck::Match)]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn links_match_when_they_resolve_to_the_central_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    write_skill(&central.join("pdf"), "---\nname: pdf\n---\nbody\n");
+    write_skill(&dir.path().join("elsewhere"), "---\nname: pdf\n---\nbody\n");
+    add_skill(&store, "pdf", &central.join("pdf"));
+
+    fs::create_dir_all(dir.path().join("tools")).unwrap();
+    let linked = dir.path().join("tools/claude-pdf");
+    std::os::unix::fs::symlink(central.join("pdf"), &linked).unwrap();
+    let stray = dir.path().join("tools/codex-pdf");
+    std::os::unix::fs::symlink(dir.path().join("elsewhere"), &stray).unwrap();
+    let broken = dir.path().join("tools/amp-pdf");
+    std::os::unix::fs::symlink(dir.path().join("gone"), &broken).unwrap();
+    add_target(&store, "pdf", "claude_code", "symlink", &linked);
+    add_target(&store, "pdf", "codex", "symlink", &stray);
+    add_target(&store, "pdf", "amp", "symlink", &broken);
+
+    let report = build_deployment_report(&store, &central, 0).unwrap();
+    let checks: Vec<(&str, HashCheck)> = report
+        .tools
+        .iter()
+        .map(|t| (t.tool.as_str(), t.skills[0].hash))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![
+            ("amp", HashCheck::Missing),
+            ("claude_code", HashCheck::Match),
+            ("codex", HashCheck::Mismatch),
+        ]
+    );
+}
+
+#[test]
+fn markdown_and_json_exports_are_written_to_the_destination() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    write_skill(&central.join("pdf"), "---\nname: pdf\n---\nbody\n");
+    add_skill(&store, "pdf", &central.join("pdf"));
+    let target = dir.path().join("cursor/pdf");
+    write_skill(&target, "---\nname: pdf\n---\nbody\n");
+    add_target(&store, "pdf", "cursor", "copy", &target);
+
+    let dest = dir.path().join("reports/deployments.md");
+    let markdown = export_deployment_report(
+        &store,
+        &central,
+        ReportFormat::Markdown,
+        Some(&dest),
+        1_714_555_800_000,
+    )
+    .unwrap();
+    assert_eq!(fs::read_to_string(&dest).unwrap(), markdown);
+    assert!(markdown.contains("- Generated: 2024-05-01 09:30 UTC"));
+    assert!(markdown.contains("## Cursor (`cursor`)"));
+    assert!(markdown.contains("| pdf | copy | ok | 2024-05-01 09:30 UTC | match |"));
+    assert!(!markdown.contains("Not deployed"));
+
+    let json = export_deployment_report(&store, &central, ReportFormat::Json, None, 0).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["target_count"], 1);
+    assert_eq!(value["tools"][0]["skills"][0]["hash"], "match");
+    assert_eq!(value["tools"][0]["skills"][0]["mode"], "copy");
+}
@@ -0,0 +1,106 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::{ProjectTargetRecord, SkillTargetRecord};
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn add_skill(store: &SkillStore, dir: &Path, name: &str, bytes: usize) -> SkillRecord {
+    let central = dir.join("central").join(name);
+    fs::create_dir_all(&central).unwrap();
+    fs::write(central.join("SKILL.md"), vec![b'x'; bytes]).unwrap();
+    let record = SkillRecord {
+        id: format!("id-{}", name),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    };
+    store.upsert_skill(&record).unwrap();
+    record
+}
+
+#[test]
+fn copies_count_and_stale_sizes_are_measured_again() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let big = add_skill(&store, dir.path(), "big", 100);
+    add_skill(&store, dir.path(), "small", 10);
+    let copy = dir.path().join("cursor/big");
+    fs::create_dir_all(&copy).unwrap();
+    fs::write(copy.join("SKILL.md"), vec![b'x'; 100]).unwrap();
+    let project_copy = dir.path().join("project/.claude/skills/big");
+    fs::create_dir_all(&project_copy).unwrap();
+    fs::write(project_copy.join("SKILL.md"), vec![b'x'; 50]).unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "big-cursor".to_string(),
+            skill_id: big.id.clone(),
+            tool: "cursor".to_string(),
+            target_path: copy.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "big-claude".to_string(),
+            skill_id: big.id.clone(),
+            tool: "claude_code".to_string(),
+            target_path: dir.path().join("claude/big").to_string_lossy().to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+    store
+        .upsert_project_target(&ProjectTargetRecord {
+            skill_id: big.id.clone(),
+            project: dir.path().join("project").to_string_lossy().to_string(),
+            tool: "claude_code".to_string(),
+            target_path: project_copy.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            synced_at: 1,
+        })
+        .unwrap();
+
+    assert!(cached_disk_usage(&store).unwrap().is_empty());
+    let largest = largest_skills(&store, Some(1), false, 0).unwrap();
+    assert_eq!(largest.len(), 1);
+    assert_eq!(largest[0].name, "big");
+    assert_eq!(
+        (largest[0].central_bytes, largest[0].target_bytes),
+        (100, 150)
+    );
+    assert_eq!(cached_disk_usage(&store).unwrap()["id-small"], 10);
+
+    fs::write(
+        Path::new(&big.central_path).join("extra.md"),
+        vec![b'x'; 40],
+    )
+    .unwrap();
+    let cached = largest_skills(&store, None, false, DISK_USAGE_MAX_AGE_MS - 1).unwrap();
+    assert_eq!(cached[0].total_bytes, 250);
+    let stale = largest_skills(&store, None, false, DISK_USAGE_MAX_AGE_MS).unwrap();
+    assert_eq!(stale[0].total_bytes, 290);
+    assert_eq!(stale[1].total_bytes, 10);
+}
@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{
+    audit_dir_access, check_github_at, run_local_checks, sandbox_kind, AccessIssue, CheckCategory,
+    CheckStatus, DoctorReport,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str, target_path: &str) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: skill_id.to_string(),
+            name: skill_id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: format!("/central/{}", skill_id),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: target_path.to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn healthy_setup_reports_ok() {
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let tool_dir = tempfile::tempdir().unwrap();
+
+    let checks = run_local_checks(
+        &store,
+        central.path(),
+        cache.path(),
+        &[("cursor".to_string(), tool_dir.path().to_path_buf())],
+    );
+    let report = DoctorReport::new(checks);
+    assert_eq!(report.status, CheckStatus::Ok, "{:?}", report.checks);
+    assert!(report
+        .checks
+        .iter()
+        .any(|c| c.name == "db_schema" && c.category == CheckCategory::Database));
+    assert!(report.checks.iter().any(|c| c.name == "tool_dir:cursor"));
+}
+
+#[cfg(unix)]
+#[test]
+fn reports_missing_dirs_and_broken_targets() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    let live = root.path().join("live");
+    fs::create_dir_all(&live).unwrap();
+    let broken = root.path().join("broken-link");
+    std::os::unix::fs::symlink(root.path().join("gone"), &broken).unwrap();
+    add_target(&store, "a", "cursor", live.to_str().unwrap());
+    add_target(&store, "b", "codex", broken.to_str().unwrap());
+    add_target(
+        &store,
+        "c",
+        "amp",
+        root.path().join("missing").to_str().unwrap(),
+    );
+
+    let checks = run_local_checks(
+        &store,
+        &root.path().join("no-central"),
+        root.path(),
+        &[("claude_code".to_string(), root.path().join("no-tool-dir"))],
+    );
+    let find = |name: &str| checks.iter().find(|c| c.name == name).unwrap();
+
+    assert_eq!(find("central_repo").status, CheckStatus::Fail);
+    assert!(find("central_repo").fix.is_some());
+    assert_eq!(find("tool_dir:claude_code").status, CheckStatus::Warn);
+    assert_eq!(find("target:codex").status, CheckStatus::Fail);
+    assert_eq!(find("target:amp").status, CheckStatus::Warn);
+    assert!(checks.iter().all(|c| c.name != "target:cursor"));
+    assert_eq!(DoctorReport::new(checks).status, CheckStatus::Fail);
+}
+
+#[test]
+fn sandboxed_homes_are_recognised() {
+    let home = Path::new("/home/u");
+    let kind = |p: &str| sandbox_kind(Path::new(p), Some(home));
+    assert_eq!(
+        kind("/home/u/.var/app/com.cursor.Cursor/skills"),
+        Some("flatpak")
+    );
+    assert_eq!(kind("/home/u/snap/code/current/.skills"), Some("snap"));
+    assert_eq!(
+        kind("/Users/u/Library/Containers/com.example/Data/skills"),
+        Some("macos_container")
+    );
+    assert_eq!(kind("/home/u/.cursor/skills"), None);
+    assert_eq!(kind("/home/u/projects/snap/skills"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn tool_dir_checks_explain_access_problems() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    let central = root.path().join("central");
+    fs::create_dir_all(&central).unwrap();
+    let flatpak = root.path().join(".var/app/com.example.Tool/skills");
+    fs::create_dir_all(&flatpak).unwrap();
+    let locked = root.path().join("locked");
+    fs::create_dir_all(&locked).unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o555)).unwrap();
+
+    let checks = run_local_checks(
+        &store,
+        &central,
+        root.path(),
+        &[
+            ("flatpak_tool".to_string(), flatpak.clone()),
+            ("locked_tool".to_string(), locked.join("skills")),
+        ],
+    );
+    let sandboxed = checks
+        .iter()
+        .find(|c| c.name == "tool_dir:flatpak_tool")
+        .unwrap();
+    assert_eq!(sandboxed.status, CheckStatus::Warn);
+    assert!(sandboxed
+        .fix
+        .as_deref()
+        .unwrap()
+        .contains("flatpak override"));
+    assert_eq!(
+        audit_dir_access(&flatpak.join("new-skill")),
+        Some(AccessIssue::Sandboxed("flatpak"))
+    );
+
+    // Root writes through missing permission bits, so only other users see a read-only dir.
+    let is_root = unsafe { libc::geteuid() } == 0;
+    let locked_check = checks
+        .iter()
+        .find(|c| c.name == "tool_dir:locked_tool")
+        .unwrap();
+    if is_root {
+        assert_eq!(audit_dir_access(&locked.join("skills")), None);
+    } else {
+        assert_eq!(
+            audit_dir_access(&locked.join("skills")),
+            Some(AccessIssue::ReadOnly)
+        );
+        assert_eq!(locked_check.status, CheckStatus::Fail);
+        assert!(locked_check.fix.as_deref().unwrap().contains("chmod u+w"));
+    }
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn network_check_accepts_any_http_answer() {
+    let mut server = mockito::Server::new();
+    let _m = server.mock("HEAD", "/").with_status(429).create();
+    let check = check_github_at(&server.url(), Duration::from_secs(5));
+    assert_eq!(check.status, CheckStatus::Ok, "{}", check.message);
+
+    let check = check_github_at("http://127.0.0.1:1", Duration::from_secs(2));
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert!(check.fix.is_some());
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn corp_settings(central: &Path) -> EnvironmentSettings {
+    EnvironmentSettings {
+        central_repo_path: Some(central.to_string_lossy().to_string()),
+        git_host_policy: Some(GitHostPolicy {
+            allow: vec![" GIT.Corp.example ".to_string()],
+            block: Vec::new(),
+        }),
+        proxy_url: Some("http://proxy.corp.example:8080".to_string()),
+    }
+}
+
+#[test]
+fn environments_are_validated_on_save() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let work = save_environment(
+        app.handle(),
+        &store,
+        None,
+        " Work ",
+        corp_settings(&dir.path().join("corp")),
+        1,
+    )
+    .unwrap();
+    assert_eq!(work.name, "Work");
+    assert_eq!(
+        work.settings.git_host_policy.unwrap().allow,
+        vec!["git.corp.example".to_string()]
+    );
+
+    let err =
+        save_environment(app.handle(), &store, None, "work", Default::default(), 2).unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    let relative = EnvironmentSettings {
+        central_repo_path: Some("skills".to_string()),
+        ..Default::default()
+    };
+    assert!(save_environment(app.handle(), &store, None, "a", relative, 2).is_err());
+    let bad_proxy = EnvironmentSettings {
+        proxy_url: Some("ftp://proxy".to_string()),
+        ..Default::default()
+    };
+    let err = save_environment(app.handle(), &store, None, "b", bad_proxy, 2).unwrap_err();
+    assert!(err.to_string().contains("invalid proxy URL"));
+    assert_eq!(list_environments(&store).unwrap().environments.len(), 1);
+}
+
+#[test]
+fn switching_keeps_each_environments_settings_apart() {
+    let (dir, store) = make_store();
+    let app = tauri::test::mock_app();
+    let corp = dir.path().join("corp");
+    let work =
+        save_environment(app.handle(), &store, None, "Work", corp_settings(&corp), 1).unwrap();
+    let personal = save_environment(
+        app.handle(),
+        &store,
+        None,
+        "Personal",
+        Default::default(),
+        1,
+    )
+    .unwrap();
+
+    switch_environment(&store, &work.id, 2).unwrap();
+    assert!(corp.is_dir());
+    assert_eq!(
+        store.get_setting("central_repo_path").unwrap(),
+        Some(corp.to_string_lossy().to_string())
+    );
+    assert!(store.get_setting(GIT_HOST_POLICY_KEY).unwrap().is_some());
+    // A change made while Work is active belongs to Work.
+    store
+        .set_setting(PROXY_URL_KEY, "http://proxy2.corp.example:3128")
+        .unwrap();
+
+    switch_environment(&store, &personal.id, 3).unwrap();
+    for key in ["central_repo_path", GIT_HOST_POLICY_KEY, PROXY_URL_KEY] {
+        assert_eq!(store.get_setting(key).unwrap(), None, "{}", key);
+    }
+    assert_eq!(proxy_url(), None);
+    let list = list_environments(&store).unwrap();
+    assert_eq!(list.active, Some(personal.id.clone()));
+    let saved = list.environments.iter().find(|e| e.id == work.id).unwrap();
+    assert_eq!(
+        saved.settings.proxy_url.as_deref(),
+        Some("http://proxy2.corp.example:3128")
+    );
+
+    switch_environment(&store, &work.id, 4).unwrap();
+    assert_eq!(
+        store.get_setting(PROXY_URL_KEY).unwrap().as_deref(),
+        Some("http://proxy2.corp.example:3128")
+    );
+    // Git and HTTP clients are handed the proxy.
+    assert_eq!(
+        proxy_url().as_deref(),
+        Some("http://proxy2.corp.example:3128")
+    );
+    assert!(with_proxy(reqwest::blocking::Client::builder()).is_ok());
+    delete_environment(&store, &work.id).unwrap();
+    assert_eq!(list_environments(&store).unwrap().active, None);
+    assert!(store.get_setting(PROXY_URL_KEY).unwrap().is_some());
+    assert!(switch_environment(&store, &work.id, 5).is_err());
+    switch_environment(&store, &personal.id, 6).unwrap();
+    assert_eq!(proxy_url(), None);
+}
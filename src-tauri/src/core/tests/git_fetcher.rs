@@ -1,6 +1,9 @@
 use std::fs;
 
-use crate::core::git_fetcher::clone_or_pull;
+use crate::core::git_fetcher::{
+    clone_or_pull, list_remote_branches, list_remote_tags, log_between, parse_ls_remote_branches,
+    parse_ls_remote_tags, sort_tags, tags_without_dates, RemoteBranch, RemoteTag,
+};
 
 fn commit_file(repo: &git2::Repository, path: &str, content: &[u8], msg: &str) -> git2::Oid {
     let workdir = repo.workdir().expect("workdir");
@@ -42,3 +45,215 @@ fn clone_then_pull_updates_head() {
     let h2 = clone_or_pull(origin_dir.path().to_string_lossy().as_ref(), &dest, None).unwrap();
     assert_eq!(h2, c3.to_string(), "再次调用应更新到最新提交");
 }
+
+#[test]
+fn refresh_fetches_only_the_new_tip() {
+    if super::resolve_git_bin().is_none() {
+        return;
+    }
+    let origin_dir = tempfile::tempdir().unwrap();
+    let origin = git2::Repository::init(origin_dir.path()).unwrap();
+    commit_file(&origin, "a.txt", b"v1", "c1");
+    let url = origin_dir.path().to_string_lossy().to_string();
+
+    let dest_dir = tempfile::tempdir().unwrap();
+    let dest = dest_dir.path().join("clone");
+    clone_or_pull(&url, &dest, None).unwrap();
+
+    commit_file(&origin, "a.txt", b"v2", "c2");
+    let c3 = commit_file(&origin, "b.txt", b"v3", "c3");
+    assert_eq!(clone_or_pull(&url, &dest, None).unwrap(), c3.to_string());
+
+    let shallow = fs::read_to_string(dest.join(".git/shallow")).unwrap();
+    assert_eq!(shallow.trim(), c3.to_string());
+    let repo = git2::Repository::open(&dest).unwrap();
+    let filter = repo
+        .config()
+        .unwrap()
+        .get_string("remote.origin.partialclonefilter")
+        .unwrap();
+    assert_eq!(filter, "blob:none");
+}
+
+#[test]
+fn lists_remote_branches_default_first() {
+    let origin_dir = tempfile::tempdir().unwrap();
+    let origin = git2::Repository::init(origin_dir.path()).unwrap();
+    let c1 = commit_file(&origin, "a.txt", b"v1", "c1");
+    let head = origin.find_commit(c1).unwrap();
+    origin.branch("feature/x", &head, false).unwrap();
+    origin.branch("dev", &head, false).unwrap();
+    let default = origin.head().unwrap().shorthand().unwrap().to_string();
+
+    let branches = list_remote_branches(origin_dir.path().to_string_lossy().as_ref()).unwrap();
+    let names: Vec<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec![default.as_str(), "dev", "feature/x"]);
+    assert!(branches[0].is_default);
+    assert!(branches[1..].iter().all(|b| !b.is_default));
+    assert!(branches.iter().all(|b| b.commit == c1.to_string()));
+}
+
+#[test]
+fn parses_ls_remote_output() {
+    let output = "ref: refs/heads/main\tHEAD\n\
+        aaa\tHEAD\n\
+        bbb\trefs/heads/dev\n\
+        aaa\trefs/heads/main\n\
+        ccc\trefs/tags/v1\n";
+    let branch = |name: &str, commit: &str, is_default| RemoteBranch {
+        name: name.to_string(),
+        commit: commit.to_string(),
+        is_default,
+    };
+    assert_eq!(
+        parse_ls_remote_branches(output),
+        vec![branch("main", "aaa", true), branch("dev", "bbb", false)]
+    );
+    // Without a symref line (e.g. an empty remote HEAD) nothing is marked default.
+    assert!(parse_ls_remote_branches("bbb\trefs/heads/dev\n")
+        .iter()
+        .all(|b| !b.is_default));
+}
+
+#[test]
+fn lists_remote_tags_newest_version_first() {
+    if super::resolve_git_bin().is_none() {
+        return;
+    }
+    let origin_dir = tempfile::tempdir().unwrap();
+    let origin = git2::Repository::init(origin_dir.path()).unwrap();
+    let c1 = commit_file(&origin, "a.txt", b"v1", "c1");
+    let c2 = commit_file(&origin, "a.txt", b"v2", "c2");
+    let sig = git2::Signature::now("t", "t@example.com").unwrap();
+    let first = origin.find_object(c1, None).unwrap();
+    let second = origin.find_object(c2, None).unwrap();
+    origin.tag_lightweight("v0.9", &first, false).unwrap();
+    origin
+        .tag("v1.2.0", &second, &sig, "release", false)
+        .unwrap();
+    origin.tag_lightweight("nightly", &second, false).unwrap();
+
+    let tags = list_remote_tags(origin_dir.path().to_string_lossy().as_ref()).unwrap();
+    let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["v1.2.0", "v0.9", "nightly"]);
+    assert_eq!(tags[0].commit, c2.to_string());
+    assert_eq!(tags[0].version.as_deref(), Some("1.2.0"));
+    assert_eq!(tags[1].commit, c1.to_string());
+    assert_eq!(tags[1].version.as_deref(), Some("0.9.0"));
+    assert!(tags[2].version.is_none());
+    assert!(tags.iter().all(|t| t.date.is_some()));
+
+    let url = origin_dir.path().to_string_lossy().to_string();
+    assert!(tags_without_dates(&url, &tags).is_empty());
+    origin.tag_lightweight("v1.3.0", &second, false).unwrap();
+    origin.tag_lightweight("nightly", &first, true).unwrap();
+    let tags = list_remote_tags(&url).unwrap();
+    assert_eq!(
+        tags_without_dates(&url, &tags),
+        Vec::<String>::new(),
+        "new and moved tags are dated on the next call"
+    );
+    let moved = tags.iter().find(|t| t.name == "nightly").unwrap();
+    assert_eq!(moved.commit, c1.to_string());
+    assert!(tags.iter().all(|t| t.date.is_some()));
+}
+
+#[test]
+fn sorts_tags_by_version_then_date() {
+    let tag = |name: &str, version: Option<&str>, date: Option<i64>| RemoteTag {
+        name: name.to_string(),
+        commit: String::new(),
+        version: version.map(str::to_string),
+        date,
+    };
+    let sorted = sort_tags(vec![
+        tag("old", None, Some(1)),
+        tag("v1.0.0", Some("1.0.0"), Some(5)),
+        tag("v1.10.0", Some("1.10.0"), Some(3)),
+        tag("v2.0.0-rc.1", Some("2.0.0-rc.1"), Some(4)),
+        tag("new", None, Some(9)),
+        tag("v1.2.0", Some("1.2.0"), Some(2)),
+    ]);
+    let names: Vec<&str> = sorted.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["v2.0.0-rc.1", "v1.10.0", "v1.2.0", "v1.0.0", "new", "old"]
+    );
+}
+
+#[test]
+fn parses_ls_remote_tags_with_peeled_commits() {
+    let output = "aaa\trefs/tags/v1.0.0\n\
+        ccc\trefs/tags/v1.0.0^{}\n\
+        bbb\trefs/tags/latest\n";
+    let tags = parse_ls_remote_tags(output);
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].name, "v1.0.0");
+    assert_eq!(tags[0].commit, "ccc");
+    assert_eq!(tags[1].name, "latest");
+    assert_eq!(tags[1].commit, "bbb");
+    assert!(tags.iter().all(|t| t.date.is_none()));
+}
+
+#[test]
+fn refreshes_a_clone_pinned_to_a_tag() {
+    if super::resolve_git_bin().is_none() {
+        return;
+    }
+    let origin_dir = tempfile::tempdir().unwrap();
+    let origin = git2::Repository::init(origin_dir.path()).unwrap();
+    let c1 = commit_file(&origin, "a.txt", b"v1", "c1");
+    let sig = git2::Signature::now("t", "t@example.com").unwrap();
+    let first = origin.find_object(c1, None).unwrap();
+    origin
+        .tag("v1.0.0", &first, &sig, "release", false)
+        .unwrap();
+    commit_file(&origin, "a.txt", b"v2", "c2");
+    let url = format!("file://{}", origin_dir.path().to_string_lossy());
+
+    let dest_dir = tempfile::tempdir().unwrap();
+    let dest = dest_dir.path().join("clone");
+    assert_eq!(
+        clone_or_pull(&url, &dest, Some("v1.0.0")).unwrap(),
+        c1.to_string()
+    );
+    assert_eq!(
+        clone_or_pull(&url, &dest, Some("v1.0.0")).unwrap(),
+        c1.to_string()
+    );
+}
+
+#[test]
+fn log_between_lists_commits_touching_subpath() {
+    let origin_dir = tempfile::tempdir().unwrap();
+    let origin = git2::Repository::init(origin_dir.path()).unwrap();
+    let c1 = commit_file(&origin, "skills/a/SKILL.md", b"v1", "add a");
+    let _c2 = commit_file(&origin, "skills/b/SKILL.md", b"v1", "add b");
+    let c3 = commit_file(&origin, "skills/a/SKILL.md", b"v2", "update a");
+
+    let all = log_between(
+        origin_dir.path(),
+        &c1.to_string(),
+        &c3.to_string(),
+        None,
+        10,
+    )
+    .unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].summary, "update a");
+
+    let scoped = log_between(
+        origin_dir.path(),
+        &c1.to_string(),
+        &c3.to_string(),
+        Some("skills/a"),
+        10,
+    )
+    .unwrap();
+    assert_eq!(scoped.len(), 1);
+    assert_eq!(scoped[0].id, c3.to_string());
+
+    let limited =
+        log_between(origin_dir.path(), &c1.to_string(), &c3.to_string(), None, 1).unwrap();
+    assert_eq!(limited.len(), 1);
+}
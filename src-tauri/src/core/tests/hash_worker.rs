@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::content_hash::hash_dir;
+use crate::core::skill_lock::lock_skill;
+use crate::core::skill_store::{ChangeKind, SkillRecord, SkillStore};
+
+use super::{hash_and_store, queue_content_hash, HashOutcome};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn add_skill(store: &SkillStore, id: &str, central_path: &Path) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central_path.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn stored_hash(store: &SkillStore, id: &str) -> Option<String> {
+    store.get_skill_by_id(id).unwrap().unwrap().content_hash
+}
+
+#[test]
+fn hash_and_store_persists_hash_and_reports_change() {
+    let (dir, store) = make_store();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let store = store.with_change_listener(move |change| sink.lock().unwrap().push(change.clone()));
+
+    let skill = dir.path().join("skill");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), b"---\nname: s\n---\n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(skill.join("SKILL.md"))
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(3600))
+        .unwrap();
+    add_skill(&store, "s1", &skill);
+    seen.lock().unwrap().clear();
+
+    let hash = hash_dir(&skill).unwrap();
+    assert_eq!(
+        hash_and_store(&store, "s1", &skill).unwrap(),
+        HashOutcome::Stored(hash.clone())
+    );
+    assert_eq!(stored_hash(&store, "s1"), Some(hash.clone()));
+    let kinds: Vec<ChangeKind> = seen.lock().unwrap().iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, vec![ChangeKind::ContentHashed]);
+    assert!(store
+        .load_file_hashes("s1")
+        .unwrap()
+        .contains_key("SKILL.md"));
+
+    // A job for a path the skill no longer lives at must not overwrite its hash.
+    let moved = dir.path().join("moved");
+    fs::create_dir_all(&moved).unwrap();
+    fs::write(moved.join("other.md"), b"x").unwrap();
+    assert_eq!(
+        hash_and_store(&store, "s1", &moved).unwrap(),
+        HashOutcome::Stale
+    );
+    assert_eq!(
+        hash_and_store(&store, "s1", &dir.path().join("gone")).unwrap(),
+        HashOutcome::Stale
+    );
+    assert_eq!(stored_hash(&store, "s1"), Some(hash));
+
+    store.delete_skill("s1").unwrap();
+    assert!(store.load_file_hashes("s1").unwrap().is_empty());
+}
+
+#[test]
+fn queued_hash_is_filled_in_by_worker() {
+    let (dir, store) = make_store();
+    let skill = dir.path().join("skill");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), b"---\nname: q\n---\n").unwrap();
+    add_skill(&store, "q1", &skill);
+
+    queue_content_hash(&store, "q1", &skill);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while stored_hash(&store, "q1").is_none() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(stored_hash(&store, "q1"), Some(hash_dir(&skill).unwrap()));
+}
+
+#[test]
+fn busy_skill_is_requeued_instead_of_blocking_the_worker() {
+    let (dir, store) = make_store();
+    let skill = dir.path().join("skill");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), b"---\nname: b\n---\n").unwrap();
+    add_skill(&store, "busy1", &skill);
+
+    let held = lock_skill("busy1", "update").unwrap();
+    let started = Instant::now();
+    assert_eq!(
+        hash_and_store(&store, "busy1", &skill).unwrap(),
+        HashOutcome::Busy
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // The queued job keeps retrying in the background and lands once the lock is released.
+    queue_content_hash(&store, "busy1", &skill);
+    std::thread::sleep(Duration::from_millis(400));
+    assert_eq!(stored_hash(&store, "busy1"), None);
+    drop(held);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while stored_hash(&store, "busy1").is_none() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(
+        stored_hash(&store, "busy1"),
+        Some(hash_dir(&skill).unwrap())
+    );
+}
@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+
+use crate::core::locale::{
+    get_app_language, get_backend_locale, normalize_locale, pick_localized, set_app_language,
+    set_backend_locale, MessageLocale,
+};
+use crate::core::skill_store::SkillStore;
+
+fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn picks_exact_then_base_then_regional_then_english() {
+    let map = values(&[("en", "Notes"), ("zh", "笔记"), ("pt-BR", "Notas")]);
+    assert_eq!(pick_localized(&map, Some("zh_CN")), Some("笔记"));
+    assert_eq!(pick_localized(&map, Some("pt")), Some("Notas"));
+    assert_eq!(pick_localized(&map, Some("fr")), Some("Notes"));
+    assert_eq!(pick_localized(&map, None), Some("Notes"));
+    assert_eq!(pick_localized(&values(&[("zh", "笔记")]), Some("fr")), None);
+}
+
+#[test]
+fn app_language_setting_is_normalized_and_validated() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert!(get_app_language(&store).is_none());
+    assert_eq!(set_app_language(&store, "zh_CN").unwrap(), "zh-cn");
+    assert_eq!(get_app_language(&store).as_deref(), Some("zh-cn"));
+    assert!(set_app_language(&store, "../x").is_err());
+    assert_eq!(normalize_locale(" EN-us "), "en-us");
+}
+
+#[test]
+fn backend_locale_accepts_catalogue_languages_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert_eq!(MessageLocale::from_tag("zh_TW"), MessageLocale::Zh);
+    assert_eq!(MessageLocale::from_tag("fr"), MessageLocale::En);
+    // Only English is applied here: the locale is process-wide and other tests read it.
+    assert_eq!(
+        set_backend_locale(&store, "en-US").unwrap().as_deref(),
+        Some("en")
+    );
+    assert_eq!(get_backend_locale(&store).as_deref(), Some("en"));
+    assert!(set_backend_locale(&store, "fr").is_err());
+    assert_eq!(set_backend_locale(&store, " ").unwrap(), None);
+    assert!(get_backend_locale(&store).is_none());
+}
@@ -0,0 +1,209 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::{SkillRecord, SkillTargetRecord};
+
+fn add_skill(store: &SkillStore, dir: &Path, id: &str, name: &str, source_ref: Option<&str>) {
+    let central = dir.join(name);
+    fs::create_dir_all(&central).unwrap();
+    fs::write(
+        central.join("SKILL.md"),
+        format!("---\nname: {}\n---\n", name),
+    )
+    .unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+            source_type: if source_ref.is_some() { "git" } else { "local" }.to_string(),
+            source_ref: source_ref.map(str::to_string),
+            source_revision: source_ref.map(|_| "abc123".to_string()),
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: format!("/home/u/.{}/skills/x", tool),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(5),
+        })
+        .unwrap();
+}
+
+#[test]
+fn lock_is_sorted_machine_independent_and_stable() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    add_skill(
+        &store,
+        dir.path(),
+        "s2",
+        "pdf",
+        Some("https://github.com/o/skills/tree/main/docs/pdf"),
+    );
+    add_skill(&store, dir.path(), "s1", "notes", None);
+    add_target(&store, "s2", "cursor");
+    add_target(&store, "s2", "claude_code");
+
+    let dest = dir.path().join("dotfiles/skills.lock");
+    let lock = export_lock(&store, &dest).unwrap();
+    let names: Vec<&str> = lock.skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["notes", "pdf"]);
+    let pdf = &lock.skills[1];
+    assert_eq!(pdf.subpath.as_deref(), Some("docs/pdf"));
+    assert_eq!(pdf.revision.as_deref(), Some("abc123"));
+    assert_eq!(pdf.tools, vec!["claude_code", "cursor"]);
+    assert_eq!(
+        pdf.content_hash.as_deref(),
+        Some(
+            crate::core::content_hash::hash_dir(&dir.path().join("pdf"))
+                .unwrap()
+                .as_str()
+        )
+    );
+
+    let raw = fs::read_to_string(&dest).unwrap();
+    assert!(!raw.contains(&dir.path().to_string_lossy().to_string()));
+    assert!(raw.ends_with('\n'));
+    let parsed: SkillsLock = serde_json::from_str(&raw).unwrap();
+    assert_eq!(parsed, lock);
+
+    export_lock(&store, dest.parent().unwrap()).unwrap();
+    assert_eq!(fs::read_to_string(&dest).unwrap(), raw);
+}
+
+#[test]
+fn apply_installs_updates_and_syncs_to_match_the_lock() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    add_skill(&store, &central, "g1", "gamma", None);
+
+    let source = dir.path().join("src/alpha");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("SKILL.md"), "---\nname: alpha\n---\nv1\n").unwrap();
+    let claude_dir = dir.path().join("claude-skills");
+    let tool_dir = |key: &str| (key == "claude_code").then(|| claude_dir.clone());
+
+    let locked = |hash: Option<String>| LockedSkill {
+        name: "alpha".to_string(),
+        source_type: "local".to_string(),
+        source_ref: Some(source.to_string_lossy().to_string()),
+        revision: None,
+        subpath: None,
+        content_hash: hash,
+        tools: vec!["claude_code".to_string(), "cursor".to_string()],
+    };
+    let mut lock = SkillsLock {
+        version: 1,
+        skills: vec![
+            locked(None),
+            LockedSkill {
+                name: "beta".to_string(),
+                source_type: "registry".to_string(),
+                source_ref: Some("beta".to_string()),
+                revision: None,
+                subpath: None,
+                content_hash: None,
+                tools: Vec::new(),
+            },
+        ],
+    };
+
+    let report = apply_lock(
+        app.handle(),
+        &store,
+        &lock,
+        ApplyOptions::default(),
+        5,
+        &tool_dir,
+    )
+    .unwrap();
+    assert_eq!(report.installed, vec!["alpha".to_string()]);
+    assert_eq!(report.synced, vec!["alpha:claude_code".to_string()]);
+    assert_eq!(report.extra, vec!["gamma".to_string()]);
+    let failures: Vec<(&str, Option<&str>)> = report
+        .failed
+        .iter()
+        .map(|f| (f.skill.as_str(), f.tool.as_deref()))
+        .collect();
+    assert_eq!(failures, vec![("alpha", Some("cursor")), ("beta", None)]);
+    assert!(claude_dir.join("alpha/SKILL.md").exists());
+
+    // The lock moves on: alpha is updated from its source, the target already exists.
+    fs::write(source.join("SKILL.md"), "---\nname: alpha\n---\nv2\n").unwrap();
+    lock.skills = vec![locked(Some(
+        crate::core::content_hash::hash_dir(&source).unwrap(),
+    ))];
+    let report = apply_lock(
+        app.handle(),
+        &store,
+        &lock,
+        ApplyOptions::default(),
+        6,
+        &tool_dir,
+    )
+    .unwrap();
+    assert_eq!(report.updated, vec!["alpha".to_string()]);
+    assert!(report.synced.is_empty());
+    assert!(report.drifted.is_empty());
+    assert_eq!(
+        fs::read_to_string(central.join("alpha/SKILL.md")).unwrap(),
+        "---\nname: alpha\n---\nv2\n"
+    );
+
+    lock.skills = vec![locked(Some("other".to_string()))];
+    let options = ApplyOptions {
+        skip_updates: true,
+        skip_sync: true,
+        force: false,
+    };
+    let report = apply_lock(app.handle(), &store, &lock, options, 7, &tool_dir).unwrap();
+    assert_eq!(report.unchanged, vec!["alpha".to_string()]);
+    assert_eq!(report.drifted, vec!["alpha".to_string()]);
+
+    // Same content, but the lock pins a revision the installed source is not at.
+    let mut pinned = locked(Some(crate::core::content_hash::hash_dir(&source).unwrap()));
+    pinned.revision = Some("deadbeef".to_string());
+    lock.skills = vec![pinned];
+    let report = apply_lock(app.handle(), &store, &lock, options, 8, &tool_dir).unwrap();
+    assert_eq!(report.unchanged, vec!["alpha".to_string()]);
+    assert_eq!(report.drifted, vec!["alpha".to_string()]);
+
+    // A name that leaves the Central Repo refuses the whole lock.
+    let mut escaping = locked(None);
+    escaping.name = "../../escaped".to_string();
+    lock.skills = vec![escaping];
+    assert!(apply_lock(app.handle(), &store, &lock, options, 9, &tool_dir).is_err());
+    assert!(!dir.path().join("escaped").exists());
+
+    let path = dir.path().join("dotfiles");
+    fs::create_dir_all(&path).unwrap();
+    export_lock(&store, &path).unwrap();
+    assert_eq!(read_lock(&path).unwrap().skills.len(), 2);
+}
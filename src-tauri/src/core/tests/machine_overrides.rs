@@ -0,0 +1,131 @@
+use std::fs;
+
+use super::*;
+use crate::core::lockfile::{apply_lock, ApplyOptions, LockedSkill, SkillsLock};
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn record(
+    skill: &str,
+    tool: Option<&str>,
+    skip: bool,
+    target: Option<&str>,
+) -> MachineOverrideRecord {
+    MachineOverrideRecord {
+        skill: skill.to_string(),
+        tool: tool.map(str::to_string),
+        skip,
+        target_path: target.map(str::to_string),
+    }
+}
+
+#[test]
+fn overrides_are_validated_and_cleared_when_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let target = dir.path().join("elsewhere/pdf");
+    let target = target.to_string_lossy();
+
+    assert!(set_machine_override(&store, record(" ", None, true, None)).is_err());
+    assert!(set_machine_override(&store, record("pdf", Some("vim"), true, None)).is_err());
+    let err = set_machine_override(&store, record("pdf", None, false, Some(&target))).unwrap_err();
+    assert!(err.to_string().contains("needs a tool"));
+    assert!(
+        set_machine_override(&store, record("pdf", Some("cursor"), false, Some("rel"))).is_err()
+    );
+
+    set_machine_override(
+        &store,
+        record(" pdf ", Some("cursor"), false, Some(&target)),
+    )
+    .unwrap();
+    set_machine_override(&store, record("docs", None, true, None)).unwrap();
+    let overrides = MachineOverrides::load(&store).unwrap();
+    assert!(overrides.skips_skill("docs"));
+    assert!(overrides.skips_tool("docs", "cursor"));
+    assert!(!overrides.skips_tool("pdf", "cursor"));
+    assert_eq!(
+        overrides.target_path("pdf", "cursor", PathBuf::from("/default")),
+        PathBuf::from(target.as_ref())
+    );
+    assert_eq!(
+        overrides.target_path("pdf", "codex", PathBuf::from("/default")),
+        PathBuf::from("/default")
+    );
+
+    let cleared = set_machine_override(&store, record("docs", None, false, None)).unwrap();
+    assert!(cleared.is_none());
+    assert_eq!(store.list_machine_overrides().unwrap().len(), 1);
+}
+
+#[test]
+fn applying_a_manifest_honours_skips_and_target_paths() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    store
+        .set_setting(
+            "central_repo_path",
+            &dir.path().join("central").to_string_lossy(),
+        )
+        .unwrap();
+    let entry = |name: &str| {
+        let source = dir.path().join("src").join(name);
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join("SKILL.md"),
+            format!("---\nname: {}\n---\n", name),
+        )
+        .unwrap();
+        LockedSkill {
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: Some(source.to_string_lossy().to_string()),
+            revision: None,
+            subpath: None,
+            content_hash: None,
+            tools: vec!["claude_code".to_string(), "cursor".to_string()],
+        }
+    };
+    let lock = SkillsLock {
+        version: 1,
+        skills: vec![entry("alpha"), entry("beta")],
+    };
+    let moved = dir.path().join("work-cursor/alpha");
+    set_machine_override(&store, record("beta", None, true, None)).unwrap();
+    set_machine_override(&store, record("alpha", Some("claude_code"), true, None)).unwrap();
+    set_machine_override(
+        &store,
+        record(
+            "alpha",
+            Some("cursor"),
+            false,
+            Some(&moved.to_string_lossy()),
+        ),
+    )
+    .unwrap();
+
+    let cursor_dir = dir.path().join("cursor-skills");
+    let tool_dir = |_: &str| Some(cursor_dir.clone());
+    let report = apply_lock(
+        app.handle(),
+        &store,
+        &lock,
+        ApplyOptions::default(),
+        1,
+        &tool_dir,
+    )
+    .unwrap();
+    assert_eq!(report.installed, vec!["alpha".to_string()]);
+    assert_eq!(report.synced, vec!["alpha:cursor".to_string()]);
+    assert_eq!(
+        report.skipped,
+        vec!["alpha:claude_code".to_string(), "beta".to_string()]
+    );
+    assert!(moved.join("SKILL.md").exists());
+    assert!(!cursor_dir.join("alpha").exists());
+}
@@ -2,6 +2,8 @@ use std::fs;
 
 use super::build_onboarding_plan_in_home;
 
+const SKILL_MD: &str = "---\nname: foo\ndescription: test skill\n---\n";
+
 #[test]
 fn groups_by_name_and_detects_conflicts_by_fingerprint() {
     let home = tempfile::tempdir().unwrap();
@@ -20,7 +22,7 @@ fn groups_by_name_and_detects_conflicts_by_fingerprint() {
     fs::create_dir_all(home.path().join(".codex/skills/.system")).unwrap();
     fs::write(home.path().join(".codex/skills/.system/SKILL.md"), b"x").unwrap();
 
-    let plan = build_onboarding_plan_in_home(home.path(), None, None, None).unwrap();
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
     assert_eq!(plan.total_tools_scanned, 2);
     assert_eq!(plan.total_skills_found, 2);
     assert_eq!(plan.groups.len(), 1);
@@ -46,7 +48,7 @@ fn excludes_central_repo_path() {
     let link_path = home.path().join(".cursor/skills/skill-a");
     symlink(central.join("skill-a"), &link_path).unwrap();
 
-    let plan = build_onboarding_plan_in_home(home.path(), Some(&central), None, None).unwrap();
+    let plan = build_onboarding_plan_in_home(home.path(), &[central], None, None).unwrap();
     assert_eq!(plan.total_skills_found, 0);
 }
 
@@ -65,6 +67,276 @@ fn excludes_managed_skill_targets() {
         &home.path().join(".cursor/skills/foo"),
     ));
 
-    let plan = build_onboarding_plan_in_home(home.path(), None, Some(&exclude), None).unwrap();
+    let plan = build_onboarding_plan_in_home(home.path(), &[], Some(&exclude), None).unwrap();
     assert_eq!(plan.total_skills_found, 0);
 }
+
+#[test]
+fn execute_imports_group_and_adopts_identical_variants() {
+    let app = tauri::test::mock_app();
+    let db_dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(db_dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    for (tool, content) in [
+        (".cursor", "same"),
+        (".codex", "same"),
+        (".claude", "different"),
+    ] {
+        let dir = home.path().join(tool).join("skills/foo");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), SKILL_MD).unwrap();
+        fs::write(dir.join("notes.txt"), content).unwrap();
+    }
+
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
+    let results = super::execute_onboarding_selections(
+        app.handle(),
+        &store,
+        &plan,
+        &[super::OnboardingSelection {
+            name: "foo".to_string(),
+            canonical_path: home.path().join(".codex/skills/foo"),
+            resolution: super::ConflictResolution::Skip,
+        }],
+    );
+    assert_eq!(results.len(), 1);
+    let res = &results[0];
+    assert!(res.error.is_none(), "{:?}", res.error);
+    let skill_id = res.skill_id.clone().unwrap();
+    assert_eq!(res.skipped, vec![home.path().join(".claude/skills/foo")]);
+
+    let mut tools: Vec<String> = store
+        .list_skill_targets(&skill_id)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.tool)
+        .collect();
+    tools.sort();
+    assert_eq!(tools, vec!["codex".to_string(), "cursor".to_string()]);
+    assert!(central.path().join("foo/SKILL.md").exists());
+    assert_eq!(
+        fs::read_to_string(home.path().join(".claude/skills/foo/notes.txt")).unwrap(),
+        "different"
+    );
+}
+
+#[test]
+fn execute_resolves_conflicts_with_winner_or_keep_both() {
+    use super::{ConflictResolution, OnboardingSelection};
+
+    let app = tauri::test::mock_app();
+    let db_dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(db_dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    for (tool, name, content) in [
+        (".cursor", "win", "a"),
+        (".codex", "win", "b"),
+        (".cursor", "both", "a"),
+        (".codex", "both", "b"),
+    ] {
+        let dir = home.path().join(tool).join("skills").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), SKILL_MD).unwrap();
+        fs::write(dir.join("notes.txt"), content).unwrap();
+    }
+
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
+    let results = super::execute_onboarding_selections(
+        app.handle(),
+        &store,
+        &plan,
+        &[
+            OnboardingSelection {
+                name: "win".to_string(),
+                canonical_path: home.path().join(".cursor/skills/win"),
+                resolution: ConflictResolution::Winner,
+            },
+            OnboardingSelection {
+                name: "both".to_string(),
+                canonical_path: home.path().join(".cursor/skills/both"),
+                resolution: ConflictResolution::KeepBoth,
+            },
+        ],
+    );
+    assert!(results.iter().all(|r| r.error.is_none()), "{:?}", results);
+
+    let win = &results[0];
+    assert_eq!(win.targets.len(), 2);
+    assert_eq!(
+        fs::read_to_string(home.path().join(".codex/skills/win/notes.txt")).unwrap(),
+        "a",
+        "winner content should replace the losing variant"
+    );
+
+    let both = &results[1];
+    assert_eq!(both.kept.len(), 1);
+    assert_eq!(both.kept[0].name, "both-codex");
+    assert_eq!(
+        fs::read_to_string(central.path().join("both-codex/notes.txt")).unwrap(),
+        "b"
+    );
+    assert!(!home.path().join(".codex/skills/both").exists());
+    assert_eq!(
+        fs::read_to_string(home.path().join(".codex/skills/both-codex/notes.txt")).unwrap(),
+        "b"
+    );
+    let kept_targets = store.list_skill_targets(&both.kept[0].skill_id).unwrap();
+    assert_eq!(kept_targets.len(), 1);
+    assert_eq!(kept_targets[0].tool, "codex");
+}
+
+#[test]
+fn import_existing_skill_can_adopt_original_in_place() {
+    let app = tauri::test::mock_app();
+    let db_dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(db_dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    let original = home.path().join(".codex/skills/foo");
+    fs::create_dir_all(&original).unwrap();
+    fs::write(original.join("SKILL.md"), SKILL_MD).unwrap();
+
+    let res =
+        super::import_existing_skill(app.handle(), &store, &original, None, Some("codex")).unwrap();
+
+    let target = store
+        .get_skill_target(&res.skill_id, "codex")
+        .unwrap()
+        .expect("adopted target");
+    assert_eq!(target.target_path, original.to_string_lossy());
+    assert_eq!(
+        fs::read_to_string(original.join("SKILL.md")).unwrap(),
+        SKILL_MD
+    );
+    #[cfg(unix)]
+    assert!(fs::symlink_metadata(&original)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+}
+
+#[test]
+fn refresh_discovered_skills_dedupes_and_reports_new_content() {
+    let db_dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(db_dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    for tool in [".cursor", ".codex"] {
+        let dir = home.path().join(tool).join("skills/foo");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "same").unwrap();
+    }
+
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
+    let first = super::refresh_discovered_skills(&store, &plan, 1).unwrap();
+    assert_eq!(
+        first.skills.len(),
+        1,
+        "identical copies dedupe by fingerprint"
+    );
+    assert_eq!(first.new_skills.len(), 1);
+    assert_eq!(store.list_discovered_skills().unwrap().len(), 2);
+
+    let again = super::refresh_discovered_skills(&store, &plan, 2).unwrap();
+    assert!(again.new_skills.is_empty());
+    assert_eq!(again.skills[0].found_at, 1, "found_at survives rescans");
+
+    let dir = home.path().join(".codex/skills/bar");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("SKILL.md"), "bar").unwrap();
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
+    let third = super::refresh_discovered_skills(&store, &plan, 3).unwrap();
+    assert_eq!(third.new_skills.len(), 1);
+    assert_eq!(third.new_skills[0].name.as_deref(), Some("bar"));
+}
+
+#[test]
+fn scans_project_roots_for_in_repo_skills() {
+    let root = tempfile::tempdir().unwrap();
+    let project = root.path().join("org/app");
+    fs::create_dir_all(project.join(".claude/skills/proj-skill")).unwrap();
+    fs::write(project.join(".claude/skills/proj-skill/SKILL.md"), "x").unwrap();
+    // Dependencies are never scanned.
+    fs::create_dir_all(root.path().join("node_modules/pkg/.claude/skills/dep")).unwrap();
+    // Too deep below the root.
+    fs::create_dir_all(root.path().join("a/b/c/.claude/skills/deep")).unwrap();
+
+    let found = super::scan_project_roots(&[root.path().to_path_buf()], &[], None).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "proj-skill");
+    assert_eq!(found[0].tool, "claude_code");
+    assert_eq!(found[0].project.as_deref(), Some(project.as_path()));
+}
+
+#[test]
+fn import_records_identical_variants_as_targets() {
+    let app = tauri::test::mock_app();
+    let db_dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(db_dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = tempfile::tempdir().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            central.path().to_string_lossy().as_ref(),
+        )
+        .unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    for (tool, content) in [
+        (".cursor", "same"),
+        (".codex", "same"),
+        (".claude", "other"),
+    ] {
+        let dir = home.path().join(tool).join("skills/foo");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), SKILL_MD).unwrap();
+        fs::write(dir.join("notes.txt"), content).unwrap();
+    }
+    let plan = build_onboarding_plan_in_home(home.path(), &[], None, None).unwrap();
+    let group = plan.groups.iter().find(|g| g.name == "foo");
+
+    let source = home.path().join(".codex/skills/foo");
+    let res = super::import_detected_skill(
+        app.handle(),
+        &store,
+        &source,
+        "foo".to_string(),
+        None,
+        group,
+    )
+    .unwrap();
+
+    let targets = store.list_skill_targets(&res.skill_id).unwrap();
+    let tools: Vec<&str> = targets.iter().map(|t| t.tool.as_str()).collect();
+    assert_eq!(tools, vec!["codex", "cursor"]);
+    assert!(targets.iter().all(|t| t.mode == "copy"));
+    assert!(source.join("SKILL.md").exists(), "originals stay in place");
+}
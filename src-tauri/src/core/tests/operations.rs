@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use super::{spawn_operation, OperationRegistry, OperationState, OperationStatus};
+
+fn wait_finished(registry: &OperationRegistry, id: &str) -> OperationStatus {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let status = registry.get(id).expect("operation");
+        if status.state != OperationState::Running {
+            return status;
+        }
+        assert!(Instant::now() < deadline, "operation did not finish");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn spawned_operation_reports_progress_and_result() {
+    let app = tauri::test::mock_app();
+    let registry = OperationRegistry::default();
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+
+    let id = spawn_operation(app.handle(), &registry, "demo", move |op| {
+        op.progress("copying", 1, Some(2));
+        rx.recv().unwrap();
+        Ok::<_, String>(42)
+    });
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while registry.get(&id).unwrap().stage.is_none() {
+        assert!(Instant::now() < deadline);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let running = registry.get(&id).unwrap();
+    assert_eq!(running.state, OperationState::Running);
+    assert_eq!(running.kind, "demo");
+    assert_eq!(running.stage.as_deref(), Some("copying"));
+    assert_eq!((running.current, running.total), (1, Some(2)));
+
+    tx.send(()).unwrap();
+    let done = wait_finished(&registry, &id);
+    assert_eq!(done.state, OperationState::Succeeded);
+    assert_eq!(done.result, Some(serde_json::json!(42)));
+    assert!(done.finished_at.is_some());
+}
+
+#[test]
+fn failed_and_panicking_operations_are_recorded() {
+    let app = tauri::test::mock_app();
+    let registry = OperationRegistry::default();
+
+    let id = spawn_operation(app.handle(), &registry, "fail", |_| {
+        Err::<(), _>("TARGET_EXISTS|/tmp/x".to_string())
+    });
+    let status = wait_finished(&registry, &id);
+    assert_eq!(status.state, OperationState::Failed);
+    assert_eq!(
+        status.error,
+        Some(serde_json::json!("TARGET_EXISTS|/tmp/x"))
+    );
+
+    let id = spawn_operation(
+        app.handle(),
+        &registry,
+        "panic",
+        |_| -> Result<(), String> { panic!("boom") },
+    );
+    let status = wait_finished(&registry, &id);
+    assert_eq!(status.state, OperationState::Failed);
+    assert_eq!(status.error, Some(serde_json::json!("operation panicked")));
+}
+
+#[test]
+fn registry_prunes_oldest_finished_operations() {
+    let registry = OperationRegistry::default();
+    let running = registry.begin("long");
+    let mut ids = Vec::new();
+    for _ in 0..60 {
+        let op = registry.begin("short");
+        registry.finish(&op.id, Ok(serde_json::Value::Null));
+        ids.push(op.id);
+    }
+    let all = registry.list();
+    assert_eq!(all.len(), 51);
+    assert!(registry.get(&running.id).is_some());
+    assert!(registry.get(&ids[0]).is_none());
+    assert!(registry.get(&ids[59]).is_some());
+    assert!(registry.progress(&ids[59], "late", 1, None).is_none());
+}
@@ -0,0 +1,201 @@
+use super::*;
+use crate::core::skill_store::{SkillRecord, SkillTargetRecord};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn add_skill(store: &SkillStore, id: &str) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: format!("/tmp/central/{}", id),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: format!("/tmp/{}/{}", tool, skill_id),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+        })
+        .unwrap();
+}
+
+fn ids(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn profiles_are_saved_renamed_and_validated() {
+    let (_dir, store) = make_store();
+    add_skill(&store, "a");
+    add_skill(&store, "b");
+
+    let created = save_profile(&store, None, " Client work ", &ids(&["b", "a", "b"]), 10).unwrap();
+    assert_eq!(created.name, "Client work");
+    assert_eq!(created.skill_ids, ids(&["a", "b"]));
+
+    let err = save_profile(&store, None, "client WORK", &[], 11).unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    let err = save_profile(&store, None, "Open source", &ids(&["nope"]), 11).unwrap_err();
+    assert!(err.to_string().contains("skill not found"));
+    assert!(save_profile(&store, None, "  ", &[], 11).is_err());
+
+    let renamed = save_profile(&store, Some(&created.id), "Client", &ids(&["a"]), 20).unwrap();
+    assert_eq!(renamed.created_at, 10);
+    assert_eq!(renamed.updated_at, 20);
+    assert_eq!(store.get_profile(&created.id).unwrap(), Some(renamed));
+
+    // Deleting a skill drops it from profiles.
+    store.delete_skill("a").unwrap();
+    assert!(store.list_profiles().unwrap()[0].skill_ids.is_empty());
+}
+
+#[test]
+fn activation_syncs_members_and_unsyncs_the_rest() {
+    let (_dir, store) = make_store();
+    for id in ["member-synced", "member-new", "other-synced", "other-idle"] {
+        add_skill(&store, id);
+    }
+    add_target(&store, "member-synced", "cursor");
+    add_target(&store, "other-synced", "cursor");
+    add_target(&store, "other-synced", "codex");
+    let profile = save_profile(
+        &store,
+        None,
+        "work",
+        &ids(&["member-synced", "member-new"]),
+        1,
+    )
+    .unwrap();
+
+    let plan = plan_profile_activation(&store, &profile.id, "cursor").unwrap();
+    assert_eq!(plan.sync, ids(&["member-new"]));
+    assert_eq!(plan.unsync, ids(&["other-synced"]));
+    assert!(plan_profile_activation(&store, "missing", "cursor").is_err());
+
+    store.set_active_profile("cursor", &profile.id, 2).unwrap();
+    let views = list_profiles(&store).unwrap();
+    assert_eq!(views[0].active_tools, ids(&["cursor"]));
+    store.delete_profile(&profile.id).unwrap();
+    assert!(store.list_active_profiles().unwrap().is_empty());
+}
+
+#[test]
+fn exported_profiles_import_through_the_install_flow() {
+    let (dir, store) = make_store();
+    let source = dir.path().join("src/alpha");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::write(source.join("SKILL.md"), "---\nname: alpha\n---\n").unwrap();
+    for (id, source_type, source_ref) in [
+        ("alpha", "local", source.to_string_lossy().to_string()),
+        ("beta", "registry", "beta".to_string()),
+    ] {
+        store
+            .upsert_skill(&SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                source_type: source_type.to_string(),
+                source_ref: Some(source_ref),
+                source_revision: None,
+                central_path: dir
+                    .path()
+                    .join("central")
+                    .join(id)
+                    .to_string_lossy()
+                    .to_string(),
+                content_hash: Some(format!("hash-{}", id)),
+                created_at: 1,
+                updated_at: 1,
+                last_sync_at: None,
+                last_seen_at: 1,
+                status: "ok".to_string(),
+                metadata: None,
+                license: None,
+                token_estimate: None,
+                contains_executables: None,
+                repo_id: None,
+            })
+            .unwrap();
+    }
+    add_target(&store, "alpha", "cursor");
+    let profile = save_profile(&store, None, "Team", &ids(&["beta", "alpha"]), 1).unwrap();
+    let path = dir.path().join("team.profile.json");
+    let file = export_profile(&store, &profile.id, &path).unwrap();
+    assert!(file.skills.iter().all(|s| s.tools.is_empty()));
+    assert_eq!(file.skills[0].content_hash.as_deref(), Some("hash-alpha"));
+    let file = read_profile_file(&path).unwrap();
+
+    // A teammate's machine, with nothing managed yet.
+    let other = tempfile::tempdir().unwrap();
+    let other_store = SkillStore::new(other.path().join("test.db"));
+    other_store.ensure_schema().unwrap();
+    let central = other.path().join("central");
+    other_store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    let preview = preview_profile_import(&other_store, &file).unwrap();
+    assert!(!preview.replaces_existing);
+    let actions: Vec<(&str, ImportAction)> = preview
+        .skills
+        .iter()
+        .map(|s| (s.name.as_str(), s.action))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            ("alpha", ImportAction::Install),
+            ("beta", ImportAction::Unavailable)
+        ]
+    );
+    assert!(other_store.list_skills().unwrap().is_empty());
+
+    let app = tauri::test::mock_app();
+    let report = import_profile(app.handle(), &other_store, &file, false, 2).unwrap();
+    assert_eq!(report.apply.installed, ids(&["alpha"]));
+    assert_eq!(report.apply.failed[0].skill, "beta");
+    assert!(central.join("alpha/SKILL.md").exists());
+    assert_eq!(report.profile.name, "Team");
+    assert_eq!(report.profile.skill_ids.len(), 1);
+
+    let preview = preview_profile_import(&other_store, &file).unwrap();
+    assert!(preview.replaces_existing);
+    assert_eq!(preview.skills[0].action, ImportAction::Managed);
+    let again = import_profile(app.handle(), &other_store, &file, false, 3).unwrap();
+    assert_eq!(again.profile.id, report.profile.id);
+    assert_eq!(again.apply.unchanged, ids(&["alpha"]));
+
+    // Entry names become dirs under the Central Repo; a file escaping it is refused.
+    let mut escaping = file.clone();
+    escaping.skills[0].name = "../alpha".to_string();
+    let err = import_profile(app.handle(), &other_store, &escaping, false, 4).unwrap_err();
+    assert!(err.to_string().starts_with("INVALID_PATH|"));
+    std::fs::write(&path, serde_json::to_string(&escaping).unwrap()).unwrap();
+    assert!(read_profile_file(&path).is_err());
+}
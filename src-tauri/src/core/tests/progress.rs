@@ -0,0 +1,41 @@
+use std::sync::{Arc, Mutex};
+
+use super::*;
+
+type Seen = Arc<Mutex<Vec<(String, u64, Option<u64>)>>>;
+
+#[test]
+fn reporter_is_scoped_to_the_closure() {
+    let seen: Seen = Arc::default();
+    let sink = seen.clone();
+    with_reporter(
+        Arc::new(move |stage: &str, current, total| {
+            sink.lock()
+                .unwrap()
+                .push((stage.to_string(), current, total));
+        }),
+        || current().expect("reporter installed")("copying", 3, Some(10)),
+    );
+    assert!(current().is_none());
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![("copying".to_string(), 3, Some(10))]
+    );
+}
+
+#[test]
+fn parses_git_progress_lines() {
+    assert_eq!(
+        parse_git_progress("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s"),
+        Some(("cloning", 450, 1000))
+    );
+    assert_eq!(
+        parse_git_progress("Resolving deltas: 100% (12/12), done."),
+        Some(("resolving_deltas", 12, 12))
+    );
+    assert_eq!(
+        parse_git_progress("remote: Counting objects: 10% (1/10)"),
+        None
+    );
+    assert_eq!(parse_git_progress("Cloning into 'repo'..."), None);
+}
@@ -0,0 +1,167 @@
+use std::fs;
+
+use super::*;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn add_skill(store: &SkillStore, name: &str, source_ref: Option<&str>, central: &Path) {
+    fs::create_dir_all(central).unwrap();
+    fs::write(
+        central.join("SKILL.md"),
+        format!("---\nname: {}\n---\nbody\n", name),
+    )
+    .unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: name.to_string(),
+            name: name.to_string(),
+            source_type: "git".to_string(),
+            source_ref: source_ref.map(str::to_string),
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: Some(false),
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn toml_and_json_configs_name_skills_by_name_or_source() {
+    let toml_config = parse_project_config(
+        Path::new(".skillshub.toml"),
+        r#"
+tools = ["claude_code"]
+skills = [
+  "pdf",
+  "https://github.com/acme/skills/tree/main/review/",
+  { name = "docs", source = "git@github.com:acme/docs.git" },
+]
+"#,
+    )
+    .unwrap();
+    assert_eq!(toml_config.tools, vec!["claude_code".to_string()]);
+    let entries: Vec<(&str, Option<&str>)> = toml_config
+        .skills
+        .iter()
+        .map(|s| (s.name.as_str(), s.source.as_deref()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            ("pdf", None),
+            (
+                "review",
+                Some("https://github.com/acme/skills/tree/main/review/")
+            ),
+            ("docs", Some("git@github.com:acme/docs.git")),
+        ]
+    );
+
+    let json_config = parse_project_config(
+        Path::new(".skillshub.json"),
+        r#"{"skills": ["https://github.com/acme/tidy.git"]}"#,
+    )
+    .unwrap();
+    assert!(json_config.tools.is_empty());
+    assert_eq!(json_config.skills[0].name, "tidy");
+
+    let err =
+        parse_project_config(Path::new(".skillshub.toml"), "tools = [\"vim\"]\n").unwrap_err();
+    assert!(err.to_string().contains("unknown tool"));
+    for skills in [r#"["../outside"]"#, r#"[{ name = "a/b" }]"#] {
+        let err = parse_project_config(
+            Path::new(".skillshub.toml"),
+            &format!("skills = {}\n", skills),
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("INVALID_PATH|"), "{}", err);
+    }
+}
+
+#[test]
+fn scan_reports_missing_skills_and_apply_syncs_into_the_project() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    add_skill(&store, "pdf", None, &central.join("pdf"));
+    add_skill(
+        &store,
+        "review-kit",
+        Some("https://github.com/acme/skills/tree/main/review"),
+        &central.join("review-kit"),
+    );
+    let project = dir.path().join("project");
+    fs::create_dir_all(project.join(".claude")).unwrap();
+    fs::write(
+        project.join(".skillshub.toml"),
+        "skills = [\"pdf\", \"https://github.com/acme/skills/tree/main/review/\", \"absent\"]\n",
+    )
+    .unwrap();
+
+    let scan = scan_project(&store, &project).unwrap();
+    assert_eq!(scan.tools, vec!["claude_code".to_string()]);
+    let states: Vec<(&str, RequirementState)> = scan
+        .skills
+        .iter()
+        .map(|s| (s.name.as_str(), s.state))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("pdf", RequirementState::NotSynced),
+            ("review", RequirementState::NotSynced),
+            ("absent", RequirementState::NotInstalled),
+        ]
+    );
+    assert_eq!(scan.skills[1].skill_id.as_deref(), Some("review-kit"));
+
+    let app = tauri::test::mock_app();
+    let report = apply_project_config(
+        app.handle(),
+        &store,
+        &project,
+        InstallOptions::default(),
+        false,
+        5,
+    )
+    .unwrap();
+    assert!(report.installed.is_empty());
+    assert_eq!(report.synced.len(), 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].skill, "absent");
+    assert!(project.join(".claude/skills/pdf/SKILL.md").exists());
+    assert!(project.join(".claude/skills/review-kit/SKILL.md").exists());
+
+    let scan = scan_project(&store, &project).unwrap();
+    assert_eq!(scan.skills[0].state, RequirementState::Synced);
+    assert_eq!(scan.skills[1].state, RequirementState::Synced);
+    assert_eq!(
+        store.list_skill_project_targets("pdf").unwrap()[0].project,
+        scan.project
+    );
+}
+
+#[test]
+fn a_project_without_config_or_tools_is_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let err = scan_project(&store, dir.path()).unwrap_err();
+    assert!(err.to_string().contains(".skillshub.toml"));
+
+    fs::write(dir.path().join(".skillshub.json"), r#"{"skills": ["pdf"]}"#).unwrap();
+    let err = scan_project(&store, dir.path()).unwrap_err();
+    assert!(err.to_string().contains("no tool directories"));
+}
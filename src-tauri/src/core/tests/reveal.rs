@@ -0,0 +1,64 @@
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::resolve_reveal_path;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+#[test]
+fn resolves_central_and_target_paths() {
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    let target = tempfile::tempdir().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "S1".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.path().to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "s1".to_string(),
+            tool: "cursor".to_string(),
+            target_path: target.path().to_string_lossy().to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        resolve_reveal_path(&store, "s1", None).unwrap(),
+        central.path()
+    );
+    assert_eq!(
+        resolve_reveal_path(&store, "s1", Some("cursor")).unwrap(),
+        target.path()
+    );
+    assert!(resolve_reveal_path(&store, "s1", Some("codex")).is_err());
+    assert!(resolve_reveal_path(&store, "missing", None).is_err());
+
+    drop(target);
+    assert!(resolve_reveal_path(&store, "s1", Some("cursor")).is_err());
+}
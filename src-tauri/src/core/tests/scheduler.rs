@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::*;
+
+const HOUR: i64 = 60 * 60;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+#[test]
+fn due_jobs_run_and_are_rescheduled_with_jitter() {
+    let (_dir, store) = make_store();
+    let scheduler = Scheduler::default();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    scheduler.register("count", "Count", HOUR, move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Ok("counted".to_string())
+    });
+    scheduler.register("fail", "Fail", HOUR, |_| anyhow::bail!("boom"));
+
+    let runs = scheduler.run_due(&store, 1_000).unwrap();
+    assert_eq!(runs.len(), 2);
+    assert!(runs[0].ok && !runs[0].manual);
+    assert_eq!(runs[1].message.as_deref(), Some("boom"));
+    assert!(!runs[1].ok);
+    let status = &scheduler.list(&store).unwrap()[0];
+    let next = status.next_run_at.unwrap();
+    assert!((1_000 + HOUR * 1000..1_000 + HOUR * 1100).contains(&next));
+    assert_eq!(
+        status.last_run.as_ref().unwrap().message.as_deref(),
+        Some("counted")
+    );
+
+    let before_any = 1_000 + HOUR * 1000 - 1;
+    assert!(scheduler.run_due(&store, before_any).unwrap().is_empty());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        scheduler
+            .run_due(&store, 1_000 + HOUR * 1100)
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(scheduler.history(&store, "count", 10).unwrap().len(), 2);
+    assert!(scheduler.history(&store, "missing", 10).is_err());
+
+    // A panicking job is a failed run and does not stay marked as running.
+    scheduler.register("panic", "Panic", HOUR, |_| panic!("boom"));
+    let run = scheduler.run_now(&store, "panic", 2_000).unwrap();
+    assert!(!run.ok);
+    assert_eq!(run.message.as_deref(), Some("job panicked"));
+    let status = scheduler.list(&store).unwrap();
+    assert!(!status.iter().find(|s| s.id == "panic").unwrap().running);
+    assert!(scheduler.run_now(&store, "panic", 3_000).is_ok());
+}
+
+#[test]
+fn disabled_jobs_only_run_on_demand() {
+    let (_dir, store) = make_store();
+    let scheduler = Scheduler::default();
+    scheduler.register("job", "Job", HOUR, |now| Ok(now.to_string()));
+
+    assert!(scheduler
+        .set_schedule(&store, "job", true, Some(1), 0)
+        .is_err());
+    let status = scheduler
+        .set_schedule(&store, "job", false, Some(2 * HOUR), 0)
+        .unwrap();
+    assert!(!status.enabled);
+    assert_eq!(status.interval_secs, 2 * HOUR);
+    assert!(scheduler.run_due(&store, i64::MAX / 2).unwrap().is_empty());
+
+    let run = scheduler.run_now(&store, "job", 5).unwrap();
+    assert!(run.manual && run.ok);
+    assert_eq!(run.message.as_deref(), Some("5"));
+    let status = scheduler
+        .set_schedule(&store, "job", true, None, 10)
+        .unwrap();
+    assert_eq!(status.interval_secs, HOUR);
+    assert!(status.next_run_at.unwrap() >= 10 + HOUR * 1000);
+}
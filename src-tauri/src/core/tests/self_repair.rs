@@ -0,0 +1,150 @@
+use std::fs;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{
+    run_self_repair, STATUS_BROKEN, STATUS_MISSING, STATUS_OFFLINE, STATUS_OK, STATUS_UNREADABLE,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn add_skill(store: &SkillStore, id: &str, central_path: &std::path::Path) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central_path.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: STATUS_OK.to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str, path: &std::path::Path) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: path.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: STATUS_OK.to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+}
+
+fn skill_status(store: &SkillStore, id: &str) -> String {
+    store.get_skill_by_id(id).unwrap().unwrap().status
+}
+
+#[test]
+fn flags_missing_unreadable_and_recovers() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    let healthy = root.path().join("healthy");
+    fs::create_dir_all(&healthy).unwrap();
+    fs::write(healthy.join("SKILL.md"), b"---\nname: healthy\n---\n").unwrap();
+    let no_manifest = root.path().join("no-manifest");
+    fs::create_dir_all(&no_manifest).unwrap();
+    add_skill(&store, "healthy", &healthy);
+    add_skill(&store, "gone", &root.path().join("gone"));
+    add_skill(&store, "no-manifest", &no_manifest);
+    add_target(&store, "healthy", "cursor", &healthy);
+    add_target(&store, "healthy", "codex", &root.path().join("no-target"));
+
+    let summary = run_self_repair(&store).unwrap();
+    assert_eq!(summary.checked_skills, 3);
+    assert_eq!(summary.checked_targets, 2);
+    assert_eq!(summary.issues.len(), 3);
+    assert_eq!(summary.recovered, 0);
+    assert_eq!(skill_status(&store, "healthy"), STATUS_OK);
+    assert_eq!(skill_status(&store, "gone"), STATUS_MISSING);
+    assert_eq!(skill_status(&store, "no-manifest"), STATUS_UNREADABLE);
+    let codex = store.get_skill_target("healthy", "codex").unwrap().unwrap();
+    assert_eq!(codex.status, STATUS_MISSING);
+    assert!(codex.last_error.is_some());
+    assert!(summary
+        .issues
+        .iter()
+        .any(|i| i.tool.as_deref() == Some("codex") && i.skill_id == "healthy"));
+
+    fs::write(no_manifest.join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
+    fs::create_dir_all(root.path().join("no-target")).unwrap();
+    let summary = run_self_repair(&store).unwrap();
+    assert_eq!(summary.recovered, 2);
+    assert_eq!(summary.issues.len(), 1);
+    assert_eq!(skill_status(&store, "no-manifest"), STATUS_OK);
+    let codex = store.get_skill_target("healthy", "codex").unwrap().unwrap();
+    assert_eq!(codex.status, STATUS_OK);
+    assert!(codex.last_error.is_none());
+}
+
+#[test]
+fn evicted_cloud_files_are_offline_not_unreadable() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join(".SKILL.md.icloud"), b"stub").unwrap();
+    add_skill(&store, "a", root.path());
+
+    let summary = run_self_repair(&store).unwrap();
+    assert_eq!(summary.issues[0].status, STATUS_OFFLINE);
+    assert_eq!(skill_status(&store, "a"), STATUS_OFFLINE);
+
+    fs::write(root.path().join("SKILL.md"), b"---\nname: a\n---\n").unwrap();
+    let summary = run_self_repair(&store).unwrap();
+    assert_eq!(summary.recovered, 1);
+    assert_eq!(skill_status(&store, "a"), STATUS_OK);
+}
+
+#[test]
+fn leaves_unrelated_statuses_alone() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("SKILL.md"), b"---\nname: a\n---\n").unwrap();
+    add_skill(&store, "a", root.path());
+    add_target(&store, "a", "cursor", root.path());
+    store
+        .set_skill_target_status("a", "cursor", "error", Some("sync failed"))
+        .unwrap();
+
+    let summary = run_self_repair(&store).unwrap();
+    assert!(summary.issues.is_empty());
+    let target = store.get_skill_target("a", "cursor").unwrap().unwrap();
+    assert_eq!(target.status, "error");
+    assert_eq!(target.last_error.as_deref(), Some("sync failed"));
+}
+
+#[cfg(unix)]
+#[test]
+fn flags_broken_symlink_targets() {
+    let (_dir, store) = make_store();
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("SKILL.md"), b"---\nname: a\n---\n").unwrap();
+    add_skill(&store, "a", root.path());
+    let link = root.path().join("link");
+    std::os::unix::fs::symlink(root.path().join("nowhere"), &link).unwrap();
+    add_target(&store, "a", "cursor", &link);
+
+    let summary = run_self_repair(&store).unwrap();
+    assert_eq!(summary.issues.len(), 1);
+    assert_eq!(summary.issues[0].status, STATUS_BROKEN);
+}
@@ -0,0 +1,136 @@
+use super::*;
+use crate::core::skill_store::{ProfileRecord, SkillRecord, SkillTargetRecord};
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn add_skill(store: &SkillStore, id: &str, hash: &str) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: format!("/tmp/central/{}", id),
+            content_hash: Some(hash.to_string()),
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+fn add_target(store: &SkillStore, skill_id: &str, tool: &str, status: &str) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-{}", skill_id, tool),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            target_path: format!("/tmp/{}/{}", tool, skill_id),
+            mode: "symlink".to_string(),
+            status: status.to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn diff_reports_added_removed_and_changed_targets() {
+    let (_dir, store) = make_store();
+    add_skill(&store, "pdf", "h1");
+    add_skill(&store, "docs", "h1");
+    add_target(&store, "pdf", "cursor", "ok");
+    add_target(&store, "docs", "cursor", "ok");
+    store
+        .upsert_profile(&ProfileRecord {
+            id: "p1".to_string(),
+            name: "Writing".to_string(),
+            skill_ids: vec!["pdf".to_string()],
+            created_at: 1,
+            updated_at: 1,
+        })
+        .unwrap();
+    let week_ago = create_setup_snapshot(&store, Some(" known good "), 1).unwrap();
+    assert_eq!(week_ago.record.label.as_deref(), Some("known good"));
+    assert_eq!(week_ago.targets, 2);
+
+    add_skill(&store, "pdf", "h2");
+    add_target(&store, "pdf", "claude_code", "ok");
+    add_target(&store, "docs", "cursor", STATUS_DISABLED);
+    store.delete_skill_target("pdf", "cursor").unwrap();
+    store.set_active_profile("cursor", "p1", 2).unwrap();
+
+    let diff = diff_setup_snapshots(&store, &week_ago.record.id, None).unwrap();
+    let added: Vec<_> = diff.added.iter().map(|t| (&*t.skill, &*t.tool)).collect();
+    assert_eq!(added, vec![("pdf", "claude_code")]);
+    let removed: Vec<_> = diff.removed.iter().map(|t| (&*t.skill, &*t.tool)).collect();
+    assert_eq!(removed, vec![("pdf", "cursor")]);
+    assert_eq!(diff.changed.len(), 1);
+    assert!(!diff.changed[0].before.disabled && diff.changed[0].after.disabled);
+    assert_eq!(
+        diff.active_profiles,
+        vec![ActiveProfileChange {
+            tool: "cursor".to_string(),
+            before: None,
+            after: Some("Writing".to_string()),
+        }]
+    );
+
+    let now = create_setup_snapshot(&store, None, 3).unwrap();
+    let same = diff_setup_snapshots(&store, &now.record.id, None).unwrap();
+    assert_eq!(same, SetupDiff::default());
+    let back = diff_setup_snapshots(&store, &now.record.id, Some(&week_ago.record.id)).unwrap();
+    assert_eq!(back.added, diff.removed);
+
+    delete_setup_snapshot(&store, &week_ago.record.id).unwrap();
+    assert!(diff_setup_snapshots(&store, &week_ago.record.id, None).is_err());
+    assert_eq!(list_setup_snapshots(&store).unwrap().len(), 1);
+}
+
+#[test]
+fn automatic_snapshots_are_daily_and_only_on_change() {
+    let (_dir, store) = make_store();
+    add_skill(&store, "pdf", "h1");
+    add_target(&store, "pdf", "cursor", "ok");
+
+    assert!(record_automatic_snapshot(&store, DAY).unwrap().is_some());
+    add_target(&store, "pdf", "codex", "ok");
+    assert!(record_automatic_snapshot(&store, DAY + 1)
+        .unwrap()
+        .is_none());
+    let taken = record_automatic_snapshot(&store, 2 * DAY).unwrap().unwrap();
+    assert!(taken.record.automatic);
+    assert!(record_automatic_snapshot(&store, 4 * DAY)
+        .unwrap()
+        .is_none());
+
+    for day in 0..MAX_AUTOMATIC_SNAPSHOTS as i64 {
+        store.delete_skill_target("pdf", "codex").unwrap();
+        if day % 2 == 0 {
+            add_target(&store, "pdf", "codex", "ok");
+        }
+        record_automatic_snapshot(&store, (5 + day) * DAY).unwrap();
+    }
+    create_setup_snapshot(&store, Some("mine"), 0).unwrap();
+    let snapshots = list_setup_snapshots(&store).unwrap();
+    assert_eq!(
+        snapshots.iter().filter(|s| s.record.automatic).count(),
+        MAX_AUTOMATIC_SNAPSHOTS
+    );
+    assert!(snapshots.iter().any(|s| !s.record.automatic));
+}
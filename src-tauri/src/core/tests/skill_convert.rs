@@ -0,0 +1,161 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillRecord;
+
+const SKILL_MD: &str = "---\nname: react\ndescription: React component\n  conventions\nmetadata:\n  cursor-globs: \"*.tsx,src/**/*.ts\"\n  cursor-always-apply: \"false\"\n---\n\nUse hooks.\n";
+
+#[test]
+fn cursor_rules_round_trip_through_a_skill() {
+    let mdc = render_skill(SKILL_MD, "react", SkillFormat::CursorRule).unwrap();
+    assert_eq!(
+        mdc,
+        "---\ndescription: React component conventions\nglobs: *.tsx,src/**/*.ts\nalwaysApply: false\n---\n\nUse hooks.\n"
+    );
+    let rule = CursorRule::parse(&mdc);
+    assert_eq!(rule.globs, vec!["*.tsx", "src/**/*.ts"]);
+    assert_eq!(rule.body.trim(), "Use hooks.");
+
+    let plain = render_skill("---\nname: x\n---\nBody\n", "x", SkillFormat::CursorRule).unwrap();
+    assert_eq!(
+        plain,
+        "---\ndescription: \nglobs: \nalwaysApply: false\n---\n\nBody\n"
+    );
+}
+
+#[test]
+fn prompts_get_a_title_unless_the_body_has_one() {
+    assert_eq!(
+        render_skill(SKILL_MD, "react", SkillFormat::Prompt).unwrap(),
+        "# react\n\nReact component conventions\n\nUse hooks.\n"
+    );
+    assert_eq!(
+        render_skill("---\nname: x\n---\n# X\n\nBody\n", "x", SkillFormat::Prompt).unwrap(),
+        "# X\n\nBody\n"
+    );
+    assert_eq!(
+        render_skill(SKILL_MD, "react", SkillFormat::SkillMd).unwrap(),
+        SKILL_MD
+    );
+}
+
+#[test]
+fn converting_writes_into_the_skill_or_a_chosen_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central/react");
+    fs::create_dir_all(&central).unwrap();
+    fs::write(central.join("SKILL.md"), SKILL_MD).unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "react".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: Some("old".to_string()),
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+
+    let converted = convert_skill(&store, "s1", SkillFormat::CursorRule, None).unwrap();
+    assert!(converted.in_skill);
+    assert!(central.join("react.mdc").is_file());
+    let record = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert_ne!(record.content_hash.as_deref(), Some("old"));
+
+    let out = dir.path().join("out");
+    fs::create_dir_all(&out).unwrap();
+    let converted = convert_skill(&store, "s1", SkillFormat::Prompt, Some(&out)).unwrap();
+    assert!(!converted.in_skill);
+    assert_eq!(
+        converted.path,
+        out.join("react.prompt.md").to_string_lossy()
+    );
+    let copy = out.join("copy.md");
+    convert_skill(&store, "s1", SkillFormat::SkillMd, Some(&copy)).unwrap();
+    assert_eq!(fs::read_to_string(&copy).unwrap(), SKILL_MD);
+
+    assert!(convert_skill(&store, "s1", SkillFormat::SkillMd, None).is_err());
+    assert!(convert_skill(&store, "s1", SkillFormat::SkillMd, Some(&central)).is_err());
+
+    let sneaky = out.join("../central/react/SKILL.md");
+    let err = convert_skill(&store, "s1", SkillFormat::SkillMd, Some(&sneaky)).unwrap_err();
+    assert!(err.to_string().starts_with("INVALID_PATH|"));
+    assert_eq!(
+        fs::read_to_string(central.join("SKILL.md")).unwrap(),
+        SKILL_MD
+    );
+
+    #[cfg(unix)]
+    {
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&central, &link).unwrap();
+        let err = convert_skill(
+            &store,
+            "s1",
+            SkillFormat::Prompt,
+            Some(&link.join("SKILL.md")),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("own SKILL.md"));
+        let converted = convert_skill(
+            &store,
+            "s1",
+            SkillFormat::Prompt,
+            Some(&link.join("notes/p.md")),
+        )
+        .unwrap();
+        assert!(converted.in_skill);
+        assert!(central.join("notes/p.md").is_file());
+    }
+}
+
+#[test]
+fn snippets_inline_small_linked_files_and_mark_what_was_cut() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = dir.path();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: api\n---\nSee [usage](usage.md), [big](big.txt) and [logo](logo.png).\n",
+    )
+    .unwrap();
+    fs::write(skill.join("usage.md"), "Run ```api``` first.\n").unwrap();
+    fs::write(skill.join("big.txt"), "x".repeat(20 * 1024)).unwrap();
+    fs::write(skill.join("logo.png"), [0u8, 1, 2]).unwrap();
+
+    let snippet = render_skill_snippet(skill, SNIPPET_DEFAULT_MAX_BYTES).unwrap();
+    assert!(snippet.markdown.starts_with("---\nname: api\n---\n"));
+    assert!(snippet
+        .markdown
+        .contains("### usage.md\n\n````markdown\nRun ```api``` first.\n````\n"));
+    assert_eq!(snippet.inlined, vec!["usage.md"]);
+    assert_eq!(snippet.omitted, vec!["big.txt", "logo.png"]);
+    assert!(snippet
+        .markdown
+        .ends_with("> Not included: `big.txt`, `logo.png`\n"));
+    assert!(!snippet.truncated);
+
+    // Room for SKILL.md but not the linked file.
+    let snippet = render_skill_snippet(skill, 100).unwrap();
+    assert!(snippet.inlined.is_empty());
+    assert!(!snippet.truncated);
+
+    let snippet = render_skill_snippet(skill, 20).unwrap();
+    assert!(snippet.truncated);
+    assert!(snippet
+        .markdown
+        .starts_with("---\nname: api\n---\n\n[... truncated: "));
+    assert_eq!(snippet.omitted.len(), 3);
+}
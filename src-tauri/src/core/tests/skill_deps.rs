@@ -0,0 +1,148 @@
+use crate::core::skill_deps::{
+    check_dependency_health, parse_dependency, parse_version, record_dependencies_after_install,
+    satisfies, violations_for_candidate,
+};
+use crate::core::skill_metadata::SkillMetadata;
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_skill(id: &str, name: &str, source_ref: Option<&str>, deps: &[&str]) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        source_type: "git".to_string(),
+        source_ref: source_ref.map(str::to_string),
+        source_revision: None,
+        central_path: format!("/central/{}", id),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: Some(SkillMetadata {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            author: None,
+            tags: Vec::new(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            license: None,
+            compatible_tools: Vec::new(),
+            deprecated: false,
+            successor: None,
+            name_i18n: Default::default(),
+            description_i18n: Default::default(),
+        }),
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    }
+}
+
+#[test]
+fn parses_registry_names_and_git_sources() {
+    let plain = parse_dependency("pdf-tools");
+    assert_eq!(plain.name, "pdf-tools");
+    assert!(plain.git_url.is_none());
+
+    let url = parse_dependency("https://github.com/owner/web-utils >= 1.2");
+    assert_eq!(url.name, "web-utils");
+    assert_eq!(
+        url.git_url.as_deref(),
+        Some("https://github.com/owner/web-utils")
+    );
+    assert_eq!(url.constraint.as_deref(), Some(">= 1.2"));
+
+    let tight = parse_dependency("pdf-tools>=1.2");
+    assert_eq!(tight.name, "pdf-tools");
+    assert_eq!(tight.constraint.as_deref(), Some(">=1.2"));
+    let caret = parse_dependency("pdf-tools^1");
+    assert_eq!(caret.name, "pdf-tools");
+    assert_eq!(caret.constraint.as_deref(), Some("^1"));
+
+    // A slash alone is not a clone URL.
+    let scoped = parse_dependency("team/pdf-tools");
+    assert_eq!(scoped.name, "pdf-tools");
+    assert!(scoped.git_url.is_none());
+
+    let ssh = parse_dependency("git@github.com:owner/helper.git");
+    assert_eq!(ssh.name, "helper");
+    assert!(ssh.git_url.is_some());
+}
+
+#[test]
+fn resolves_edges_by_name_and_source_and_refreshes_waiters() {
+    let (_dir, store) = make_store();
+    store
+        .upsert_skill(&make_skill(
+            "a",
+            "a",
+            None,
+            &["helper", "https://github.com/owner/repo.git", "unknown"],
+        ))
+        .unwrap();
+    store
+        .upsert_skill(&make_skill(
+            "r",
+            "repo-skill",
+            Some("https://github.com/owner/repo"),
+            &[],
+        ))
+        .unwrap();
+
+    let missing = record_dependencies_after_install(&store, "a").unwrap();
+    assert_eq!(missing, vec!["helper".to_string(), "unknown".to_string()]);
+    assert_eq!(store.list_skill_dependents("r").unwrap().len(), 1);
+
+    store
+        .upsert_skill(&make_skill("h", "helper", None, &[]))
+        .unwrap();
+    let missing = record_dependencies_after_install(&store, "h").unwrap();
+    assert!(missing.is_empty());
+    assert_eq!(store.list_skill_dependents("h").unwrap()[0].skill_id, "a");
+}
+
+#[test]
+fn constraints_use_lenient_versions() {
+    assert_eq!(
+        parse_version("v1.2").unwrap(),
+        semver::Version::new(1, 2, 0)
+    );
+    assert_eq!(parse_version("3").unwrap(), semver::Version::new(3, 0, 0));
+    assert!(parse_version("latest").is_none());
+
+    assert!(satisfies(">= 1.2", Some("1.10.0")).unwrap());
+    assert!(!satisfies(">= 1.2", Some("1.1")).unwrap());
+    assert!(!satisfies(">= 1.2", None).unwrap());
+    assert!(satisfies("not a constraint", Some("1.0.0")).is_err());
+}
+
+#[test]
+fn health_reports_unsatisfied_and_missing_dependencies() {
+    let (_dir, store) = make_store();
+    store
+        .upsert_skill(&make_skill("a", "a", None, &["b >= 2", "c"]))
+        .unwrap();
+    store
+        .upsert_skill(&make_skill("b", "b", None, &[]))
+        .unwrap();
+
+    let violations = check_dependency_health(&store).unwrap();
+    let reasons: Vec<(&str, &str)> = violations
+        .iter()
+        .map(|v| (v.dependency.as_str(), v.reason.as_str()))
+        .collect();
+    assert_eq!(reasons, vec![("b >= 2", "unsatisfied"), ("c", "missing")]);
+
+    let blocked = violations_for_candidate(&store, "b", None, None, Some("b")).unwrap();
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].skill_id.as_deref(), Some("a"));
+}
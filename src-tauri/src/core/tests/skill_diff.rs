@@ -0,0 +1,51 @@
+use std::fs;
+
+use super::{diff_skill_dirs, FileDiffStatus};
+
+#[test]
+fn reports_added_removed_and_modified_files() {
+    let a = tempfile::tempdir().unwrap();
+    let b = tempfile::tempdir().unwrap();
+
+    fs::write(a.path().join("SKILL.md"), "line1\nline2\n").unwrap();
+    fs::write(b.path().join("SKILL.md"), "line1\nchanged\n").unwrap();
+    fs::write(a.path().join("same.txt"), "x").unwrap();
+    fs::write(b.path().join("same.txt"), "x").unwrap();
+    fs::write(a.path().join("only_a.txt"), "a").unwrap();
+    fs::create_dir_all(b.path().join("docs")).unwrap();
+    fs::write(b.path().join("docs/only_b.txt"), "b").unwrap();
+    fs::write(a.path().join("bin.dat"), [0xff, 0xfe, 0x00]).unwrap();
+    fs::write(b.path().join("bin.dat"), [0xff, 0x00, 0x01]).unwrap();
+
+    let diff = diff_skill_dirs(a.path(), b.path()).unwrap();
+    assert!(!diff.identical);
+
+    let status = |p: &str| diff.files.iter().find(|f| f.path == p).unwrap().clone();
+    assert_eq!(status("same.txt").status, FileDiffStatus::Unchanged);
+    assert_eq!(status("only_a.txt").status, FileDiffStatus::Removed);
+    assert_eq!(status("docs/only_b.txt").status, FileDiffStatus::Added);
+
+    let skill_md = status("SKILL.md");
+    assert_eq!(skill_md.status, FileDiffStatus::Modified);
+    let unified = skill_md.unified.unwrap();
+    assert!(unified.contains("-line2"));
+    assert!(unified.contains("+changed"));
+
+    let bin = status("bin.dat");
+    assert!(bin.binary);
+    assert!(bin.unified.is_none());
+}
+
+#[test]
+fn identical_dirs_ignore_git_metadata() {
+    let a = tempfile::tempdir().unwrap();
+    let b = tempfile::tempdir().unwrap();
+    fs::write(a.path().join("SKILL.md"), "same").unwrap();
+    fs::write(b.path().join("SKILL.md"), "same").unwrap();
+    fs::create_dir_all(a.path().join(".git")).unwrap();
+    fs::write(a.path().join(".git/HEAD"), "ref").unwrap();
+
+    let diff = diff_skill_dirs(a.path(), b.path()).unwrap();
+    assert!(diff.identical);
+    assert_eq!(diff.files.len(), 1);
+}
@@ -0,0 +1,106 @@
+use std::fs;
+
+use super::*;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_skill(store: &SkillStore, central: &Path) -> SkillRecord {
+    let record = SkillRecord {
+        id: "s1".to_string(),
+        name: "S1".to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    };
+    store.upsert_skill(&record).unwrap();
+    record
+}
+
+#[test]
+fn finds_scripts_and_binaries() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+    fs::create_dir_all(dir.path().join("scripts")).unwrap();
+    fs::write(dir.path().join("scripts/fill.py"), "print('hi')\n").unwrap();
+    fs::write(dir.path().join("scripts/run"), "#!/bin/sh\necho hi\n").unwrap();
+    fs::write(dir.path().join("tool.bin"), b"\x7fELF\x02\x01").unwrap();
+    fs::write(dir.path().join("notes.txt"), "#not a shebang\n").unwrap();
+    fs::create_dir_all(dir.path().join(".git/hooks")).unwrap();
+    fs::write(dir.path().join(".git/hooks/pre-commit.sh"), "#!/bin/sh\n").unwrap();
+
+    assert_eq!(
+        find_executables(dir.path()).unwrap(),
+        vec!["scripts/fill.py", "scripts/run", "tool.bin"]
+    );
+    assert_eq!(contains_executables(dir.path()), Some(true));
+
+    fs::remove_dir_all(dir.path().join("scripts")).unwrap();
+    fs::remove_file(dir.path().join("tool.bin")).unwrap();
+    assert_eq!(contains_executables(dir.path()), Some(false));
+}
+
+#[cfg(unix)]
+#[test]
+fn executable_bit_counts() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("helper"), "echo hi\n").unwrap();
+    assert!(find_executables(dir.path()).unwrap().is_empty());
+    fs::set_permissions(dir.path().join("helper"), fs::Permissions::from_mode(0o755)).unwrap();
+    assert_eq!(find_executables(dir.path()).unwrap(), vec!["helper"]);
+}
+
+#[test]
+fn sync_gate_follows_the_policy_and_backfills_the_flag() {
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    fs::write(central.path().join("run.sh"), "echo hi\n").unwrap();
+    let skill = make_skill(&store, central.path());
+
+    assert_eq!(get_executable_policy(&store), ExecutablePolicy::Confirm);
+    let err = ensure_sync_allowed(&store, &skill, false).unwrap_err();
+    assert_eq!(err.to_string(), "EXECUTABLES_UNCONFIRMED|S1");
+    assert_eq!(
+        store
+            .get_skill_by_id("s1")
+            .unwrap()
+            .unwrap()
+            .contains_executables,
+        Some(true)
+    );
+    ensure_sync_allowed(&store, &skill, true).unwrap();
+
+    set_executable_policy(&store, "block").unwrap();
+    let err = ensure_sync_allowed(&store, &skill, true).unwrap_err();
+    assert_eq!(err.to_string(), "EXECUTABLES_BLOCKED|S1");
+
+    set_executable_policy(&store, "allow").unwrap();
+    ensure_sync_allowed(&store, &skill, false).unwrap();
+    assert!(set_executable_policy(&store, "sometimes").is_err());
+
+    set_executable_policy(&store, "block").unwrap();
+    let clean = SkillRecord {
+        contains_executables: Some(false),
+        repo_id: None,
+        ..skill
+    };
+    ensure_sync_allowed(&store, &clean, false).unwrap();
+}
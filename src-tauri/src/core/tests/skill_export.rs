@@ -0,0 +1,108 @@
+use std::fs;
+use std::io::Read;
+
+use super::*;
+use crate::core::skill_signing::{sign_skill_dir, SigningKeyPair};
+
+fn make_record(central: &Path) -> SkillRecord {
+    SkillRecord {
+        id: "id-1".to_string(),
+        name: "pdf".to_string(),
+        source_type: "git".to_string(),
+        source_ref: Some("https://github.com/o/r".to_string()),
+        source_revision: Some("abc123".to_string()),
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
+    }
+}
+
+fn archive_names(path: &Path) -> Vec<String> {
+    let archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn skillignore_patterns_follow_gitignore_rules() {
+    let ignore =
+        SkillIgnore::parse("# comment\n*.log\nbuild/\n/notes.md\ndocs/**/draft-*.md\n!keep.log\n");
+    assert!(ignore.is_ignored("a.log", false));
+    assert!(ignore.is_ignored("sub/b.log", false));
+    assert!(!ignore.is_ignored("keep.log", false));
+    assert!(ignore.is_ignored("build", true));
+    assert!(!ignore.is_ignored("build", false));
+    assert!(ignore.is_ignored("notes.md", false));
+    assert!(!ignore.is_ignored("sub/notes.md", false));
+    assert!(ignore.is_ignored("docs/draft-1.md", false));
+    assert!(ignore.is_ignored("docs/a/b/draft-2.md", false));
+    assert!(!ignore.is_ignored("docs/final.md", false));
+}
+
+#[test]
+fn export_zips_the_skill_with_a_manifest_and_honors_skillignore() {
+    let central = tempfile::tempdir().unwrap();
+    let skill_dir = central.path().join("pdf");
+    fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+    fs::create_dir_all(skill_dir.join("build")).unwrap();
+    fs::create_dir_all(skill_dir.join(".git")).unwrap();
+    fs::write(skill_dir.join("SKILL.md"), "---\nname: pdf\n---\nbody\n").unwrap();
+    fs::write(skill_dir.join("scripts/run.sh"), "echo hi\n").unwrap();
+    fs::write(skill_dir.join("build/out.bin"), "x").unwrap();
+    fs::write(skill_dir.join("debug.log"), "x").unwrap();
+    fs::write(skill_dir.join(".git/HEAD"), "ref").unwrap();
+    fs::write(skill_dir.join(SKILL_IGNORE_FILE), "build/\n*.log\n").unwrap();
+    let pair = SigningKeyPair::generate().unwrap();
+    sign_skill_dir(&skill_dir, &pair, "timestamp:1").unwrap();
+
+    let out = tempfile::tempdir().unwrap();
+    let dest = out.path().join("exports/pdf.zip");
+    let manifest = export_skill_archive(&make_record(&skill_dir), &dest, 42).unwrap();
+    assert_eq!(manifest.name, "pdf");
+    assert_eq!(manifest.source_revision.as_deref(), Some("abc123"));
+    assert_eq!(manifest.content_hash, hash_dir(&skill_dir).unwrap());
+    assert!(
+        !manifest.signed,
+        "files were left out, so the signature cannot verify"
+    );
+
+    assert_eq!(
+        archive_names(&dest),
+        vec![
+            "pdf/",
+            "pdf/.skillignore",
+            "pdf/SKILL.md",
+            "pdf/scripts/",
+            "pdf/scripts/run.sh",
+            EXPORT_MANIFEST_FILE,
+        ]
+    );
+    let mut archive = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+    let mut raw = String::new();
+    archive
+        .by_name(EXPORT_MANIFEST_FILE)
+        .unwrap()
+        .read_to_string(&mut raw)
+        .unwrap();
+    let parsed: ExportManifest = serde_json::from_str(&raw).unwrap();
+    assert_eq!(parsed, manifest);
+    assert!(!out.path().join("exports/pdf.zip.tmp").exists());
+
+    // Without exclusions the signature is shipped.
+    fs::remove_file(skill_dir.join(SKILL_IGNORE_FILE)).unwrap();
+    fs::remove_dir_all(skill_dir.join("build")).unwrap();
+    fs::remove_file(skill_dir.join("debug.log")).unwrap();
+    let manifest = export_skill_archive(&make_record(&skill_dir), &dest, 43).unwrap();
+    assert!(manifest.signed);
+    assert!(archive_names(&dest).contains(&format!("pdf/{}", SIGNATURE_FILE)));
+}
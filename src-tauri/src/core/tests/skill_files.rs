@@ -0,0 +1,162 @@
+use std::fs;
+
+use super::{
+    ensure_plain_name, list_skill_files, looks_binary, read_or_create_private_file,
+    read_skill_file, resolve_in_skill, write_file_atomic, ContentEncoding, MAX_READ_BYTES,
+};
+
+#[test]
+fn plain_names_are_single_components() {
+    for name in ["pdf", "PDF Tools", "my-skill.v2"] {
+        assert!(ensure_plain_name(name).is_ok(), "{}", name);
+    }
+    for name in [
+        "",
+        ".",
+        "..",
+        "../../.ssh",
+        "a/b",
+        "a\\b",
+        "/etc",
+        ".git",
+        "pdf/",
+    ] {
+        assert!(ensure_plain_name(name).is_err(), "{}", name);
+    }
+}
+
+#[test]
+fn resolve_in_skill_rejects_escapes() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("skill");
+    fs::create_dir_all(root.join("docs")).unwrap();
+
+    let path = resolve_in_skill(&root, "./docs/new.md").unwrap();
+    assert_eq!(path, root.canonicalize().unwrap().join("docs/new.md"));
+
+    for bad in ["", "..", "docs/../../x", "/abs", ".git/HEAD"] {
+        let err = resolve_in_skill(&root, bad).unwrap_err();
+        assert!(err.to_string().starts_with("INVALID_PATH|"), "{}", bad);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn resolve_in_skill_rejects_symlinks_leaving_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("skill");
+    let outside = dir.path().join("outside");
+    fs::create_dir_all(&root).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+    let err = resolve_in_skill(&root, "link/file.md").unwrap_err();
+    assert!(err.to_string().starts_with("INVALID_PATH|"));
+}
+
+#[test]
+fn write_file_atomic_replaces_content_without_leftovers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested/SKILL.md");
+    write_file_atomic(&path, b"one").unwrap();
+    write_file_atomic(&path, b"two").unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"two");
+    let entries: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+    assert_eq!(entries.len(), 1);
+}
+
+#[test]
+fn private_files_are_created_once_and_owner_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("keys/secret.key");
+    let first = read_or_create_private_file(&path, || Ok(b"one".to_vec())).unwrap();
+    let again =
+        read_or_create_private_file(&path, || panic!("an existing file is not recreated")).unwrap();
+    assert_eq!(
+        (first.as_slice(), again.as_slice()),
+        (&b"one"[..], &b"one"[..])
+    );
+    let entries: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+    assert_eq!(entries.len(), 1);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
+
+#[test]
+fn lists_files_with_sizes_and_binary_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("assets")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join("SKILL.md"), "---\nname: a\n---\n").unwrap();
+    fs::write(
+        root.join("assets/logo.png"),
+        [0x89, b'P', b'N', b'G', 0, 0, 1],
+    )
+    .unwrap();
+    fs::write(root.join("assets/notes.txt"), "héllo").unwrap();
+    fs::write(root.join(".git/HEAD"), "ref").unwrap();
+
+    let files = list_skill_files(root).unwrap();
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["SKILL.md", "assets", "assets/logo.png", "assets/notes.txt"]
+    );
+    assert!(files[1].is_dir);
+    assert!(files[2].is_binary);
+    assert_eq!(files[2].size, 7);
+    assert!(!files[3].is_binary);
+    assert!(!files[0].is_binary);
+}
+
+#[test]
+fn looks_binary_tolerates_truncated_utf8() {
+    let text = "日本語".as_bytes();
+    assert!(!looks_binary(&text[..text.len() - 1]));
+    assert!(looks_binary(&[0xff, 0xfe, b'a']));
+    assert!(looks_binary(b"a\0b"));
+}
+
+#[test]
+fn reads_text_in_chunks_without_splitting_characters() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("doc.md"), "ab日本").unwrap();
+
+    let first = read_skill_file(dir.path(), "doc.md", 0, Some(4)).unwrap();
+    assert_eq!(first.content, "ab");
+    assert_eq!(first.size, 8);
+    assert_eq!(first.encoding, ContentEncoding::Utf8);
+    assert_eq!(first.next_offset, Some(2));
+
+    let second = read_skill_file(dir.path(), "doc.md", 2, Some(100)).unwrap();
+    assert_eq!(second.content, "日本");
+    assert_eq!(second.next_offset, None);
+
+    let whole = read_skill_file(dir.path(), "./doc.md", 0, None).unwrap();
+    assert_eq!(whole.path, "doc.md");
+    assert_eq!(whole.length, 8);
+}
+
+#[test]
+fn reads_binary_as_base64_and_caps_length() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("img.bin"), [0u8, 1, 2, 255]).unwrap();
+    let content = read_skill_file(dir.path(), "img.bin", 1, None).unwrap();
+    assert!(content.is_binary);
+    assert_eq!(content.encoding, ContentEncoding::Base64);
+    assert_eq!(content.content, "AQL/");
+
+    let big = vec![b'a'; (MAX_READ_BYTES + 10) as usize];
+    fs::write(dir.path().join("big.txt"), &big).unwrap();
+    let chunk = read_skill_file(dir.path(), "big.txt", 0, Some(u64::MAX)).unwrap();
+    assert_eq!(chunk.length, MAX_READ_BYTES);
+    assert_eq!(chunk.next_offset, Some(MAX_READ_BYTES));
+
+    assert!(read_skill_file(dir.path(), "missing.md", 0, None).is_err());
+    assert!(read_skill_file(dir.path(), "../x", 0, None).is_err());
+}
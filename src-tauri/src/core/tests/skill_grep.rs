@@ -0,0 +1,98 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillRecord;
+
+fn add_skill(store: &SkillStore, dir: &Path, name: &str, files: &[(&str, &[u8])]) {
+    let central = dir.join("central").join(name);
+    for (file, content) in files {
+        let path = central.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    store
+        .upsert_skill(&SkillRecord {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn finds_lines_across_skills_with_regex_and_literal_patterns() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let long_line = format!("{}call billing.v2(x){}", "a".repeat(300), "b".repeat(300));
+    add_skill(
+        &store,
+        dir.path(),
+        "billing",
+        &[
+            ("SKILL.md", b"# Billing\nUse billing.v2 for invoices.\n"),
+            ("scripts/run.sh", long_line.as_bytes()),
+            ("logo.png", b"billing.v2\0binary"),
+        ],
+    );
+    add_skill(
+        &store,
+        dir.path(),
+        "notes",
+        &[("SKILL.md", b"nothing here\n  See Billing.V2 docs\n")],
+    );
+
+    let literal = GrepOptions {
+        literal: true,
+        ..Default::default()
+    };
+    let result = grep_skills(&store, "billing.v2", &literal).unwrap();
+    let hits: Vec<_> = result
+        .hits
+        .iter()
+        .map(|h| (h.skill_name.as_str(), h.file.as_str(), h.line))
+        .collect();
+    assert_eq!(
+        hits,
+        vec![("billing", "SKILL.md", 2), ("billing", "scripts/run.sh", 1)]
+    );
+    assert_eq!(result.hits[0].column, 5);
+    assert_eq!(result.files_searched, 3);
+    let excerpt = &result.hits[1].text;
+    assert!(excerpt.starts_with("...") && excerpt.ends_with("..."));
+    assert!(excerpt.contains("call billing.v2(x)"));
+
+    let ignoring_case = GrepOptions {
+        ignore_case: true,
+        skill_ids: vec!["id-notes".to_string()],
+        ..Default::default()
+    };
+    let result = grep_skills(&store, r"billing\.v\d", &ignoring_case).unwrap();
+    assert_eq!(result.hits.len(), 1);
+    assert_eq!(result.hits[0].text, "See Billing.V2 docs");
+
+    let limited = GrepOptions {
+        ignore_case: true,
+        max_hits: Some(2),
+        ..Default::default()
+    };
+    let result = grep_skills(&store, "billing", &limited).unwrap();
+    assert!(result.truncated && result.hits.len() == 2);
+
+    assert!(grep_skills(&store, "(", &GrepOptions::default()).is_err());
+    assert!(grep_skills(&store, "", &GrepOptions::default()).is_err());
+}
@@ -0,0 +1,57 @@
+use std::fs;
+
+use crate::core::skill_license::{
+    detect_license, identify_license_text, is_permissive, NOASSERTION,
+};
+use crate::core::skill_metadata::SkillMetadata;
+
+#[test]
+fn identifies_common_license_texts() {
+    assert_eq!(
+        identify_license_text("MIT License\n\nPermission is hereby granted, free of charge, ..."),
+        "MIT"
+    );
+    assert_eq!(
+        identify_license_text("Apache License\nVersion 2.0, January 2004"),
+        "Apache-2.0"
+    );
+    assert_eq!(
+        identify_license_text("Redistribution and use in source and binary forms ..."),
+        "BSD-2-Clause"
+    );
+    assert_eq!(identify_license_text("All rights reserved."), NOASSERTION);
+}
+
+#[test]
+fn metadata_and_frontmatter_win_over_license_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("LICENSE"), "GNU GENERAL PUBLIC LICENSE\n").unwrap();
+    assert_eq!(detect_license(dir.path(), None).as_deref(), Some("GPL-3.0"));
+
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: x\nlicense: Apache-2.0\n---\n",
+    )
+    .unwrap();
+    assert_eq!(
+        detect_license(dir.path(), None).as_deref(),
+        Some("Apache-2.0")
+    );
+
+    let meta = SkillMetadata::load(dir.path());
+    assert_eq!(
+        detect_license(dir.path(), meta.as_ref()).as_deref(),
+        Some("Apache-2.0")
+    );
+
+    let empty = tempfile::tempdir().unwrap();
+    assert!(detect_license(empty.path(), None).is_none());
+}
+
+#[test]
+fn permissive_check_handles_or_expressions() {
+    assert!(is_permissive("MIT"));
+    assert!(is_permissive("(GPL-3.0 OR mit)"));
+    assert!(!is_permissive("GPL-3.0"));
+    assert!(!is_permissive(NOASSERTION));
+}
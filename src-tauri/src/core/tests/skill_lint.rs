@@ -0,0 +1,74 @@
+use std::fs;
+
+use super::{lint_skill_dir, LintSeverity};
+
+fn codes(report: &super::LintReport) -> Vec<&str> {
+    report.diagnostics.iter().map(|d| d.code.as_str()).collect()
+}
+
+#[test]
+fn clean_skill_has_no_diagnostics() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: pdf-tools\ndescription: Work with PDFs\n---\n\nSee [ref](docs/ref.md#usage).\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("docs")).unwrap();
+    fs::write(
+        dir.path().join("docs/ref.md"),
+        "[home](https://example.com)",
+    )
+    .unwrap();
+
+    let report = lint_skill_dir(dir.path()).unwrap();
+    assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn reports_frontmatter_files_and_links() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: Bad Name\n---\n\nline\n[missing](scripts/run.sh)\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join(".env"), "TOKEN=1").unwrap();
+    fs::create_dir_all(dir.path().join("node_modules/x")).unwrap();
+    fs::write(dir.path().join("node_modules/x/server.key"), "k").unwrap();
+
+    let report = lint_skill_dir(dir.path()).unwrap();
+    let found = codes(&report);
+    assert!(found.contains(&"invalid_name"));
+    assert!(found.contains(&"missing_description"));
+    assert!(found.contains(&"disallowed_file"));
+    assert!(found.contains(&"discouraged_dir"));
+    assert_eq!(
+        found.iter().filter(|c| **c == "disallowed_file").count(),
+        1,
+        "files under skipped dirs are not inspected"
+    );
+
+    let link = report
+        .diagnostics
+        .iter()
+        .find(|d| d.code == "broken_link")
+        .unwrap();
+    assert_eq!(link.file.as_deref(), Some("SKILL.md"));
+    assert_eq!(link.line, Some(6));
+    assert_eq!(link.severity, LintSeverity::Warning);
+    assert!(report.has_errors());
+}
+
+#[test]
+fn missing_or_invalid_skill_md_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = lint_skill_dir(dir.path()).unwrap();
+    assert_eq!(codes(&report), vec!["missing_skill_md"]);
+
+    fs::write(dir.path().join("SKILL.md"), "no frontmatter").unwrap();
+    let report = lint_skill_dir(dir.path()).unwrap();
+    assert_eq!(codes(&report), vec!["invalid_frontmatter"]);
+    assert!(report.has_errors());
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::SkillLocks;
+
+#[test]
+fn second_operation_waits_for_the_first() {
+    let locks = Arc::new(SkillLocks::new());
+    let guard = locks.acquire("a", "update", Duration::ZERO).unwrap();
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let waiter = {
+        let locks = locks.clone();
+        let finished = finished.clone();
+        std::thread::spawn(move || {
+            let _guard = locks
+                .acquire("a", "sync:cursor", Duration::from_secs(10))
+                .unwrap();
+            finished.store(true, Ordering::SeqCst);
+        })
+    };
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(!finished.load(Ordering::SeqCst));
+    // Other skills are not blocked.
+    drop(locks.acquire("b", "update", Duration::ZERO).unwrap());
+
+    drop(guard);
+    waiter.join().unwrap();
+    assert!(finished.load(Ordering::SeqCst));
+}
+
+#[test]
+fn duplicate_or_timed_out_requests_fail() {
+    let locks = SkillLocks::new();
+    let guard = locks.acquire("a", "update", Duration::ZERO).unwrap();
+
+    let err = locks
+        .acquire("a", "update", Duration::from_secs(10))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "OPERATION_IN_PROGRESS|a|update");
+
+    let err = locks
+        .acquire("a", "delete", Duration::from_millis(50))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "OPERATION_IN_PROGRESS|a|update");
+
+    drop(guard);
+    assert!(locks.lock().is_empty());
+    drop(locks.acquire("a", "delete", Duration::ZERO).unwrap());
+}
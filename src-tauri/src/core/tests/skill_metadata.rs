@@ -0,0 +1,288 @@
+use std::fs;
+
+use super::{
+    generate_skill_yaml, validate_frontmatter, write_metadata_patch, SkillFrontmatter,
+    SkillMetadata, SkillMetadataPatch, DEFAULT_GENERATED_VERSION, UNVERSIONED,
+};
+use crate::core::skill_lint::LintSeverity;
+
+#[test]
+fn parses_multiline_and_quoted_frontmatter() {
+    let fm = SkillFrontmatter::parse(
+        r#"---
+name: pdf-tools
+description: >
+  Extract text: tables, forms
+  and images from PDFs.
+version: "1.2.0"
+license: MIT
+allowed-tools: Read, Bash(git:*)
+metadata:
+  author: someone
+x-custom: true
+---
+
+# body
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(fm.name.as_deref(), Some("pdf-tools"));
+    assert_eq!(
+        fm.description.as_deref(),
+        Some("Extract text: tables, forms and images from PDFs.\n")
+    );
+    assert_eq!(fm.version.as_deref(), Some("1.2.0"));
+    assert_eq!(fm.license.as_deref(), Some("MIT"));
+    assert_eq!(fm.allowed_tools, vec!["Read", "Bash(git:*)"]);
+    assert!(fm.extra.contains_key("x-custom"));
+}
+
+#[test]
+fn allowed_tools_accepts_yaml_list() {
+    let fm = SkillFrontmatter::parse("---\nname: a\nallowed-tools:\n  - Read\n  - Write\n---\n")
+        .unwrap();
+    assert_eq!(fm.allowed_tools, vec!["Read", "Write"]);
+}
+
+#[test]
+fn rejects_missing_or_broken_frontmatter() {
+    assert_eq!(
+        SkillFrontmatter::parse("# no frontmatter"),
+        Err("invalid_frontmatter")
+    );
+    assert_eq!(
+        SkillFrontmatter::parse("---\nname: a\n"),
+        Err("invalid_frontmatter")
+    );
+}
+
+#[test]
+fn invalid_yaml_falls_back_to_line_values() {
+    let fm =
+        SkillFrontmatter::parse("---\nname: helper\ndescription: Use when: the user asks\n---\n")
+            .unwrap();
+    assert_eq!(fm.name.as_deref(), Some("helper"));
+    assert_eq!(fm.description.as_deref(), Some("Use when: the user asks"));
+}
+
+#[test]
+fn metadata_falls_back_to_skill_md_frontmatter() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: demo\ndescription: Demo skill\nmetadata:\n  version: 2.0.1\n  author: me\n---\n",
+    )
+    .unwrap();
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.name, "demo");
+    assert_eq!(meta.version, "2.0.1");
+    assert_eq!(meta.author.as_deref(), Some("me"));
+
+    fs::write(dir.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+    assert_eq!(
+        SkillMetadata::load(dir.path()).unwrap().version,
+        UNVERSIONED
+    );
+}
+
+#[test]
+fn metadata_patch_rewrites_frontmatter_and_keeps_body() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: pdf-tools\ndescription: old\nx-custom: true\n---\n\n# Body\n",
+    )
+    .unwrap();
+
+    let patch = SkillMetadataPatch {
+        description: Some("Extract text from PDFs".to_string()),
+        tags: Some(vec!["pdf".to_string(), "docs".to_string()]),
+        version: Some("1.1.0".to_string()),
+        ..Default::default()
+    };
+    let written = write_metadata_patch(dir.path(), &patch).unwrap();
+    assert_eq!(written, dir.path().join("SKILL.md"));
+
+    let text = fs::read_to_string(&written).unwrap();
+    assert!(text.ends_with("---\n\n# Body\n"), "{text}");
+    let fm = SkillFrontmatter::parse(&text).unwrap();
+    assert!(fm.extra.contains_key("x-custom"));
+
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.description.as_deref(), Some("Extract text from PDFs"));
+    assert_eq!(meta.version, "1.1.0");
+    assert_eq!(meta.tags, vec!["pdf", "docs"]);
+}
+
+#[test]
+fn metadata_patch_prefers_skill_yaml() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("SKILL.md"), "---\nname: a\n---\n").unwrap();
+    fs::write(dir.path().join("skill.yaml"), "name: a\nversion: 1.0.0\n").unwrap();
+
+    let patch = SkillMetadataPatch {
+        license: Some("MIT".to_string()),
+        ..Default::default()
+    };
+    write_metadata_patch(dir.path(), &patch).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("SKILL.md")).unwrap(),
+        "---\nname: a\n---\n"
+    );
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.license.as_deref(), Some("MIT"));
+}
+
+#[test]
+fn metadata_patch_keeps_comments_order_and_untouched_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\n# owned by docs team\nname: notes\ndescription: old # inline note\nmetadata:\n  author: me\n  tags:\n  - a\n  - b\n  custom: keep\n---\nBody\n",
+    )
+    .unwrap();
+    let patch = SkillMetadataPatch {
+        description: Some("new".to_string()),
+        tags: Some(vec!["x".to_string()]),
+        dependencies: Some(vec!["pdf-tools".to_string()]),
+        ..Default::default()
+    };
+    write_metadata_patch(dir.path(), &patch).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("SKILL.md")).unwrap(),
+        "---\n# owned by docs team\nname: notes\ndescription: new\nmetadata:\n  author: me\n  tags:\n  - x\n  custom: keep\n  dependencies:\n  - pdf-tools\n---\nBody\n"
+    );
+
+    fs::write(
+        dir.path().join("skill.yaml"),
+        "# manifest\nname: notes\nversion: 1.0.0 # bump on release\nauthor: me\n",
+    )
+    .unwrap();
+    let patch = SkillMetadataPatch {
+        version: Some("1.1.0".to_string()),
+        license: Some("MIT".to_string()),
+        ..Default::default()
+    };
+    write_metadata_patch(dir.path(), &patch).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("skill.yaml")).unwrap(),
+        "# manifest\nname: notes\nversion: 1.1.0\nauthor: me\nlicense: MIT\n"
+    );
+    fs::remove_file(dir.path().join("skill.yaml")).unwrap();
+
+    fs::write(
+        dir.path().join("skill.json"),
+        "{\n  \"version\": \"1.0.0\",\n  \"name\": \"notes\",\n  \"tags\": [\"a\"]\n}\n",
+    )
+    .unwrap();
+    write_metadata_patch(dir.path(), &patch).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("skill.json")).unwrap(),
+        "{\n  \"version\": \"1.1.0\",\n  \"name\": \"notes\",\n  \"tags\": [\"a\"],\n  \"license\": \"MIT\"\n}\n"
+    );
+}
+
+#[test]
+fn generates_skill_yaml_from_frontmatter_once() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: notes\ndescription: Take notes\nlicense: MIT\nmetadata:\n  tags: a, b\n---\n",
+    )
+    .unwrap();
+
+    let path = generate_skill_yaml(dir.path()).unwrap().unwrap();
+    assert_eq!(path, dir.path().join("skill.yaml"));
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.name, "notes");
+    assert_eq!(meta.version, DEFAULT_GENERATED_VERSION);
+    assert_eq!(meta.description.as_deref(), Some("Take notes"));
+    assert_eq!(meta.tags, vec!["a", "b"]);
+    assert_eq!(meta.license.as_deref(), Some("MIT"));
+
+    assert!(generate_skill_yaml(dir.path()).unwrap().is_none());
+}
+
+#[test]
+fn compatible_tools_limit_supported_tools() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "---\nname: a\nmetadata:\n  compatible-tools: [cursor, claude-code]\n---\n",
+    )
+    .unwrap();
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert!(meta.supports_tool("cursor"));
+    assert!(meta.supports_tool("claude_code"));
+    assert!(!meta.supports_tool("codex"));
+
+    fs::write(dir.path().join("skill.yaml"), "name: a\nversion: 1.0.0\n").unwrap();
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert!(meta.compatible_tools.is_empty());
+    assert!(meta.supports_tool("codex"));
+}
+
+#[test]
+fn localized_manifest_fields_resolve_by_language() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("skill.yaml"),
+        "name:\n  en: Notes\n  zh: 笔记\nversion: 1.0.0\ndescription: Take notes\ndescription.zh: 记笔记\n",
+    )
+    .unwrap();
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.name, "Notes");
+    assert_eq!(meta.localized_name(Some("zh-CN")), "笔记");
+    assert_eq!(meta.localized_name(Some("fr")), "Notes");
+    assert_eq!(meta.localized_description(Some("zh")), Some("记笔记"));
+    assert_eq!(meta.localized_description(Some("en")), Some("Take notes"));
+
+    fs::remove_file(dir.path().join("skill.yaml")).unwrap();
+    fs::write(
+        dir.path().join("skill.json"),
+        r#"{"name": {"zh": "笔记"}, "version": "1.0.0"}"#,
+    )
+    .unwrap();
+    let meta = SkillMetadata::load(dir.path()).unwrap();
+    assert_eq!(meta.name, "笔记");
+}
+
+#[test]
+fn validate_frontmatter_reports_field_and_line() {
+    let issues = validate_frontmatter("# no frontmatter\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "invalid_frontmatter");
+    assert_eq!(issues[0].line, Some(1));
+
+    let issues = validate_frontmatter("---\nname: a\ndescription: b\n");
+    assert_eq!(issues[0].code, "invalid_frontmatter");
+    assert_eq!(issues[0].line, Some(3));
+
+    let issues = validate_frontmatter("---\ndescription: d\nname: [a, b]\n---\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "invalid_field_type");
+    assert_eq!(issues[0].field.as_deref(), Some("name"));
+    assert_eq!(issues[0].line, Some(3));
+    assert_eq!(issues[0].severity, LintSeverity::Error);
+
+    let issues = validate_frontmatter("---\ndescription: d\n---\nbody\n");
+    assert_eq!(issues[0].code, "missing_name");
+
+    assert!(validate_frontmatter("---\nname: a\ndescription: b\nversion: 1.2\n---\n").is_empty());
+}
+
+#[test]
+fn validate_frontmatter_downgrades_yaml_fallback_to_warnings() {
+    let issues = validate_frontmatter("---\nname: a\ndescription: Use when: asked\n---\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "yaml_fallback");
+    assert_eq!(issues[0].severity, LintSeverity::Warning);
+    assert!(issues[0].line.is_some());
+
+    let issues = validate_frontmatter("---\nname: a\n---\n");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "missing_description");
+    assert_eq!(issues[0].severity, LintSeverity::Warning);
+}
@@ -0,0 +1,150 @@
+use std::fs;
+
+use super::*;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_skill() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("SKILL.md"), "---\nname: s\n---\nbody\n").unwrap();
+    dir
+}
+
+fn trusted(pair: &SigningKeyPair) -> TrustedKey {
+    TrustedKey {
+        name: "team".to_string(),
+        public_key: pair.public_key(),
+    }
+}
+
+#[test]
+fn key_pair_is_created_once_and_reloaded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("keys/signing.key");
+    let first = SigningKeyPair::load_or_create(&path).unwrap();
+    let again = SigningKeyPair::load_or_create(&path).unwrap();
+    assert_eq!(first.public_key(), again.public_key());
+    assert!(parse_public_key(&first.public_key()).is_some());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
+
+#[test]
+fn signatures_verify_only_for_unchanged_content_and_trusted_keys() {
+    let skill = make_skill();
+    let pair = SigningKeyPair::generate().unwrap();
+    let other = SigningKeyPair::generate().unwrap();
+
+    assert_eq!(
+        check_signature(skill.path(), &[trusted(&pair)]).unwrap(),
+        SignatureCheck::Unsigned
+    );
+    let hash_before = crate::core::content_hash::hash_dir(skill.path()).unwrap();
+    sign_skill_dir(skill.path(), &pair, "timestamp:1\tskill:s").unwrap();
+    assert_eq!(
+        crate::core::content_hash::hash_dir(skill.path()).unwrap(),
+        hash_before,
+        "the signature file must not change the digest it signs"
+    );
+
+    assert_eq!(
+        check_signature(skill.path(), &[trusted(&other), trusted(&pair)]).unwrap(),
+        SignatureCheck::Trusted("team".to_string())
+    );
+    assert!(matches!(
+        check_signature(skill.path(), &[trusted(&other)]).unwrap(),
+        SignatureCheck::Untrusted(_)
+    ));
+
+    fs::write(skill.path().join("extra.md"), "added later").unwrap();
+    assert_eq!(
+        check_signature(skill.path(), &[trusted(&pair)]).unwrap(),
+        SignatureCheck::Invalid
+    );
+    fs::remove_file(skill.path().join("extra.md")).unwrap();
+
+    // Editing the trusted comment breaks the global signature.
+    let sig_path = skill.path().join(SIGNATURE_FILE);
+    let tampered = fs::read_to_string(&sig_path)
+        .unwrap()
+        .replace("skill:s", "skill:t");
+    fs::write(&sig_path, tampered).unwrap();
+    assert_eq!(
+        check_signature(skill.path(), &[trusted(&pair)]).unwrap(),
+        SignatureCheck::Invalid
+    );
+
+    fs::write(&sig_path, "not a signature").unwrap();
+    assert_eq!(
+        check_signature(skill.path(), &[trusted(&pair)]).unwrap(),
+        SignatureCheck::Invalid
+    );
+}
+
+#[test]
+fn install_gate_follows_the_policy() {
+    let (_dir, store) = make_store();
+    let skill = make_skill();
+    let pair = SigningKeyPair::generate().unwrap();
+
+    ensure_signature_accepted(&store, "s", skill.path()).unwrap();
+    assert!(set_signing_policy(
+        &store,
+        SigningPolicy {
+            require_signed: true,
+            trusted_keys: vec![TrustedKey {
+                name: "bad".to_string(),
+                public_key: "bm90IGEga2V5".to_string(),
+            }],
+        },
+    )
+    .is_err());
+
+    let saved = set_signing_policy(
+        &store,
+        SigningPolicy {
+            require_signed: true,
+            trusted_keys: vec![trusted(&pair), trusted(&pair)],
+        },
+    )
+    .unwrap();
+    assert_eq!(saved.trusted_keys.len(), 1);
+    assert_eq!(get_signing_policy(&store), saved);
+
+    let err = ensure_signature_accepted(&store, "s", skill.path()).unwrap_err();
+    assert_eq!(err.to_string(), "SIGNATURE_REQUIRED|s");
+
+    let stranger = SigningKeyPair::generate().unwrap();
+    sign_skill_dir(skill.path(), &stranger, "timestamp:1").unwrap();
+    let err = ensure_signature_accepted(&store, "s", skill.path()).unwrap_err();
+    assert!(err.to_string().starts_with("SIGNATURE_UNTRUSTED|s|"));
+
+    sign_skill_dir(skill.path(), &pair, "timestamp:1").unwrap();
+    ensure_signature_accepted(&store, "s", skill.path()).unwrap();
+
+    fs::write(
+        skill.path().join("SKILL.md"),
+        "---\nname: s\n---\nchanged\n",
+    )
+    .unwrap();
+    set_signing_policy(
+        &store,
+        SigningPolicy {
+            require_signed: false,
+            ..saved
+        },
+    )
+    .unwrap();
+    let err = ensure_signature_accepted(&store, "s", skill.path()).unwrap_err();
+    assert_eq!(err.to_string(), "SIGNATURE_INVALID|s");
+}
@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::skill_metadata::SkillMetadata;
+use crate::core::skill_store::{
+    DiscoveredSkillRecord, SkillDependencyRecord, SkillProjection, SkillRecord, SkillStore,
+    SkillTargetRecord,
+};
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -24,6 +28,11 @@ fn make_skill(id: &str, name: &str, central_path: &str, updated_at: i64) -> Skil
         last_sync_at: None,
         last_seen_at: 1,
         status: "ok".to_string(),
+        metadata: None,
+        license: None,
+        token_estimate: None,
+        contains_executables: None,
+        repo_id: None,
     }
 }
 
@@ -33,6 +42,157 @@ fn schema_is_idempotent() {
     store.ensure_schema().expect("ensure_schema again");
 }
 
+#[test]
+fn v6_migration_clears_hashes_from_the_old_digest() {
+    let (dir, store) = make_store();
+    let mut skill = make_skill("s1", "S1", "/tmp/central/s1", 1);
+    skill.content_hash = Some("old-digest".to_string());
+    store.upsert_skill(&skill).unwrap();
+    store
+        .replace_discovered_skills(&[DiscoveredSkillRecord {
+            id: "d1".to_string(),
+            tool: "cursor".to_string(),
+            found_path: "/tmp/tool/s1".to_string(),
+            name_guess: Some("s1".to_string()),
+            fingerprint: Some("old-digest".to_string()),
+            found_at: 1,
+            imported_skill_id: None,
+        }])
+        .unwrap();
+
+    let conn = rusqlite::Connection::open(dir.path().join("test.db")).unwrap();
+    conn.pragma_update(None, "user_version", 5).unwrap();
+    drop(conn);
+    store.ensure_schema().unwrap();
+
+    assert_eq!(store.schema_version().unwrap(), 17);
+    assert_eq!(
+        store.get_skill_by_id("s1").unwrap().unwrap().content_hash,
+        None
+    );
+    assert_eq!(store.list_discovered_skills().unwrap()[0].fingerprint, None);
+
+    // Hashes written after the migration are kept.
+    skill.content_hash = Some("new-digest".to_string());
+    store.upsert_skill(&skill).unwrap();
+    store.ensure_schema().unwrap();
+    assert_eq!(
+        store.get_skill_by_id("s1").unwrap().unwrap().content_hash,
+        Some("new-digest".to_string())
+    );
+}
+
+#[test]
+fn queries_reuse_one_connection_across_clones() {
+    let (_dir, store) = make_store();
+    // Temp tables live on a single connection, so a clone only sees this one if it's shared.
+    store
+        .with_conn(|conn| Ok(conn.execute_batch("CREATE TEMP TABLE probe (x INTEGER);")?))
+        .unwrap();
+    let clone = store.clone();
+    let probes: i64 = clone
+        .with_conn(|conn| {
+            Ok(conn.query_row(
+                "SELECT count(*) FROM temp.sqlite_master WHERE name = 'probe'",
+                [],
+                |row| row.get(0),
+            )?)
+        })
+        .unwrap();
+    assert_eq!(probes, 1);
+    let foreign_keys: i64 = clone
+        .with_conn(|conn| Ok(conn.query_row("PRAGMA foreign_keys;", [], |row| row.get(0))?))
+        .unwrap();
+    assert_eq!(foreign_keys, 1);
+}
+
+#[test]
+fn filter_queries_use_indices() {
+    let (_dir, store) = make_store();
+    let plan = |sql: &str| -> String {
+        store
+            .with_conn(|conn| {
+                let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+                let details = stmt
+                    .query_map([], |row| row.get::<_, String>(3))?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(details.join("; "))
+            })
+            .unwrap()
+    };
+
+    for (sql, index) in [
+        (
+            "SELECT id FROM skills WHERE source_ref = 'x'",
+            "idx_skills_source_ref",
+        ),
+        (
+            "SELECT id FROM skills WHERE status = 'ok'",
+            "idx_skills_status",
+        ),
+        (
+            "SELECT status, COUNT(*) FROM skills GROUP BY status",
+            "idx_skills_status",
+        ),
+        (
+            "SELECT skill_id FROM skill_targets WHERE tool = 'cursor'",
+            "idx_skill_targets_tool",
+        ),
+        (
+            "SELECT tool, COUNT(*) FROM skill_targets GROUP BY tool",
+            "idx_skill_targets_tool",
+        ),
+    ] {
+        let plan = plan(sql);
+        assert!(plan.contains(index), "{}: {}", sql, plan);
+        // A bare SCAN is a full table scan; scanning a covering index is fine.
+        assert!(
+            !plan
+                .split("; ")
+                .any(|step| step == "SCAN skills" || step == "SCAN skill_targets"),
+            "{}: {}",
+            sql,
+            plan
+        );
+    }
+}
+
+#[test]
+fn counts_skills_by_status_and_targets_by_tool() {
+    let (_dir, store) = make_store();
+    store
+        .upsert_skill(&make_skill("a", "A", "/central/a", 1))
+        .unwrap();
+    let mut missing = make_skill("b", "B", "/central/b", 2);
+    missing.status = "missing".to_string();
+    store.upsert_skill(&missing).unwrap();
+    for (id, skill_id, tool) in [
+        ("t1", "a", "cursor"),
+        ("t2", "b", "cursor"),
+        ("t3", "a", "codex"),
+    ] {
+        store
+            .upsert_skill_target(&SkillTargetRecord {
+                id: id.to_string(),
+                skill_id: skill_id.to_string(),
+                tool: tool.to_string(),
+                target_path: format!("/tools/{}/{}", tool, skill_id),
+                mode: "copy".to_string(),
+                status: "ok".to_string(),
+                last_error: None,
+                synced_at: None,
+            })
+            .unwrap();
+    }
+
+    let by_status = store.count_skills_by_status().unwrap();
+    assert_eq!(by_status.get("ok"), Some(&1));
+    assert_eq!(by_status.get("missing"), Some(&1));
+    let by_tool = store.count_skill_targets_by_tool().unwrap();
+    assert_eq!(by_tool.get("cursor"), Some(&2));
+    assert_eq!(by_tool.get("codex"), Some(&1));
+}
+
 #[test]
 fn settings_roundtrip_and_update() {
     let (_dir, store) = make_store();
@@ -89,6 +249,31 @@ fn skills_upsert_list_get_delete() {
     assert!(store.get_skill_by_id("a").unwrap().is_none());
 }
 
+#[test]
+fn summary_projection_leaves_metadata_unset() {
+    let (_dir, store) = make_store();
+    let mut skill = make_skill("a", "A", "/central/a", 10);
+    let metadata: SkillMetadata = serde_json::from_value(serde_json::json!({
+        "name": "A",
+        "version": "1.0.0",
+        "description": "does things",
+    }))
+    .unwrap();
+    skill.metadata = Some(metadata);
+    skill.license = Some("MIT".to_string());
+    store.upsert_skill(&skill).unwrap();
+
+    let full = store.list_skills_with(SkillProjection::Full).unwrap();
+    assert!(full[0].metadata.is_some());
+
+    let summary = store.list_skills_with(SkillProjection::Summary).unwrap();
+    assert_eq!(summary.len(), 1);
+    assert!(summary[0].metadata.is_none());
+    assert_eq!(summary[0].name, "A");
+    assert_eq!(summary[0].central_path, "/central/a");
+    assert_eq!(summary[0].license.as_deref(), Some("MIT"));
+}
+
 #[test]
 fn skill_targets_upsert_unique_constraint_and_list_order() {
     let (_dir, store) = make_store();
@@ -177,6 +362,37 @@ fn deleting_skill_cascades_targets() {
     assert_eq!(store.list_skill_targets("s1").unwrap().len(), 0);
 }
 
+#[test]
+fn dependency_edges_replace_and_null_on_delete() {
+    let (_dir, store) = make_store();
+    store.upsert_skill(&make_skill("a", "A", "/central/a", 1)).unwrap();
+    store.upsert_skill(&make_skill("b", "B", "/central/b", 1)).unwrap();
+
+    let edges = vec![
+        SkillDependencyRecord {
+            skill_id: "a".to_string(),
+            dependency: "b".to_string(),
+            dependency_skill_id: Some("b".to_string()),
+        },
+        SkillDependencyRecord {
+            skill_id: "a".to_string(),
+            dependency: "missing".to_string(),
+            dependency_skill_id: None,
+        },
+    ];
+    store.replace_skill_dependencies("a", &edges).unwrap();
+    assert_eq!(store.list_skill_dependencies("a").unwrap().len(), 2);
+    assert_eq!(store.list_skill_dependents("b").unwrap()[0].skill_id, "a");
+    assert_eq!(store.list_unresolved_dependencies().unwrap().len(), 1);
+
+    store.delete_skill("b").unwrap();
+    assert!(store.list_skill_dependents("b").unwrap().is_empty());
+    assert_eq!(store.list_unresolved_dependencies().unwrap().len(), 2);
+
+    store.replace_skill_dependencies("a", &edges[1..]).unwrap();
+    assert_eq!(store.list_skill_dependencies("a").unwrap().len(), 1);
+}
+
 #[test]
 fn error_context_includes_db_path() {
     let store = SkillStore::new(PathBuf::from("/this/path/should/not/exist/test.db"));
@@ -184,3 +400,66 @@ fn error_context_includes_db_path() {
     let msg = format!("{:#}", err);
     assert!(msg.contains("failed to open db at"), "{msg}");
 }
+
+#[test]
+fn writes_notify_change_listener() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::core::skill_store::{ChangeKind, StoreChange};
+
+    let dir = tempfile::tempdir().unwrap();
+    let seen: Arc<Mutex<Vec<StoreChange>>> = Arc::default();
+    let sink = seen.clone();
+    let store = SkillStore::new(dir.path().join("test.db"))
+        .with_change_listener(move |change| sink.lock().unwrap().push(change.clone()));
+    store.ensure_schema().unwrap();
+
+    store.set_setting("k", "v").unwrap();
+    store
+        .upsert_skill(&make_skill("s1", "S1", "/central/s1", 1))
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "s1".to_string(),
+            tool: "cursor".to_string(),
+            target_path: "/target/1".to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+    // Clones share the listener.
+    store.clone().delete_skill_target("s1", "cursor").unwrap();
+    store.delete_skill("s1").unwrap();
+    // Failed writes are not reported.
+    assert!(store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t2".to_string(),
+            skill_id: "missing".to_string(),
+            tool: "cursor".to_string(),
+            target_path: "/target/2".to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .is_err());
+
+    let seen = seen.lock().unwrap();
+    let kinds: Vec<ChangeKind> = seen.iter().map(|c| c.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ChangeKind::SettingChanged,
+            ChangeKind::SkillUpdated,
+            ChangeKind::TargetUpdated,
+            ChangeKind::TargetDeleted,
+            ChangeKind::SkillDeleted,
+        ]
+    );
+    assert_eq!(seen[0].key.as_deref(), Some("k"));
+    assert_eq!(seen[2].skill_ids, vec!["s1".to_string()]);
+    assert_eq!(seen[2].tool.as_deref(), Some("cursor"));
+}
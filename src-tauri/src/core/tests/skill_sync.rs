@@ -0,0 +1,126 @@
+use std::fs;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::skill_sync::{resync_copy_targets, set_target_enabled, STATUS_DISABLED};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_target(id: &str, tool: &str, path: &std::path::Path, mode: &str) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: id.to_string(),
+        skill_id: "s1".to_string(),
+        tool: tool.to_string(),
+        target_path: path.to_string_lossy().to_string(),
+        mode: mode.to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: None,
+    }
+}
+
+fn add_skill(store: &SkillStore, central: &std::path::Path) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "S1".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn resync_copy_targets_only_touches_copy_targets() {
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    fs::write(central.path().join("a.txt"), b"new").unwrap();
+
+    add_skill(&store, central.path());
+
+    let targets = tempfile::tempdir().unwrap();
+    let copy_target = targets.path().join("copy");
+    let link_target = targets.path().join("link");
+    fs::create_dir_all(&copy_target).unwrap();
+    fs::write(copy_target.join("a.txt"), b"old").unwrap();
+    store
+        .upsert_skill_target(&make_target("t1", "tool_a", &copy_target, "copy"))
+        .unwrap();
+    store
+        .upsert_skill_target(&make_target("t2", "tool_b", &link_target, "symlink"))
+        .unwrap();
+
+    let updated = resync_copy_targets(&store, "s1", central.path(), 42).unwrap();
+    assert_eq!(updated, vec!["tool_a".to_string()]);
+    assert_eq!(fs::read(copy_target.join("a.txt")).unwrap(), b"new");
+    assert!(!link_target.exists(), "symlink 目标不应被复制");
+    assert_eq!(
+        store
+            .get_skill_target("s1", "tool_a")
+            .unwrap()
+            .unwrap()
+            .synced_at,
+        Some(42)
+    );
+}
+
+#[test]
+fn disabling_a_target_moves_it_aside_and_back() {
+    let (_dir, store) = make_store();
+    let central = tempfile::tempdir().unwrap();
+    fs::write(central.path().join("SKILL.md"), b"# S1").unwrap();
+    add_skill(&store, central.path());
+    let skills_dir = tempfile::tempdir().unwrap();
+    let target = skills_dir.path().join("s1");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("SKILL.md"), b"# S1").unwrap();
+    // Two tools sharing one skills directory share the target.
+    for (id, tool) in [("t1", "tool_a"), ("t2", "tool_b")] {
+        store
+            .upsert_skill_target(&make_target(id, tool, &target, "copy"))
+            .unwrap();
+    }
+
+    let toggled = set_target_enabled(&store, "s1", "tool_a", false).unwrap();
+    assert_eq!(toggled.len(), 2);
+    let aside = skills_dir.path().join(".disabled/s1");
+    assert!(!target.exists());
+    assert!(aside.join("SKILL.md").exists());
+    let record = store.get_skill_target("s1", "tool_b").unwrap().unwrap();
+    assert_eq!(record.status, STATUS_DISABLED);
+    assert_eq!(record.target_path, aside.to_string_lossy());
+    assert!(set_target_enabled(&store, "s1", "tool_a", false)
+        .unwrap()
+        .is_empty());
+
+    // Refreshing copies keeps a disabled target where it is.
+    fs::write(central.path().join("SKILL.md"), b"# S1 v2").unwrap();
+    resync_copy_targets(&store, "s1", central.path(), 2).unwrap();
+    assert_eq!(fs::read(aside.join("SKILL.md")).unwrap(), b"# S1 v2");
+    let record = store.get_skill_target("s1", "tool_a").unwrap().unwrap();
+    assert_eq!(record.status, STATUS_DISABLED);
+
+    set_target_enabled(&store, "s1", "tool_b", true).unwrap();
+    assert_eq!(fs::read(target.join("SKILL.md")).unwrap(), b"# S1 v2");
+    assert!(!skills_dir.path().join(".disabled").exists());
+    let record = store.get_skill_target("s1", "tool_a").unwrap().unwrap();
+    assert_eq!(record.status, "ok");
+    assert!(set_target_enabled(&store, "s1", "tool_c", true).is_err());
+}
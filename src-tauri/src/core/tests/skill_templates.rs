@@ -0,0 +1,118 @@
+use super::*;
+use crate::core::skill_lint::lint_skill_dir;
+use crate::core::skill_metadata::SkillFrontmatter;
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+        .set_setting("central_repo_path", &dir.join("central").to_string_lossy())
+        .unwrap();
+    store
+}
+
+#[test]
+fn builtin_templates_render_lint_clean_skills() {
+    let dir = tempfile::tempdir().unwrap();
+    let vars = TemplateVars::new("pdf-tools", "Fill: PDF forms", "Ada", DAY);
+    assert_eq!(vars.date, "1970-01-02");
+    for template in BUILTIN_TEMPLATES {
+        let dest = dir.path().join(template.id).join("pdf-tools");
+        write_template(&Template::Builtin(template), &vars, &dest).unwrap();
+        for (file, _) in template.files {
+            let text = std::fs::read_to_string(dest.join(file)).unwrap();
+            assert!(!text.contains("{{"), "{} in {}", file, template.id);
+        }
+        let skill_md = std::fs::read_to_string(dest.join("SKILL.md")).unwrap();
+        let fm = SkillFrontmatter::parse(&skill_md).unwrap();
+        assert_eq!(fm.name.as_deref(), Some("pdf-tools"));
+        assert_eq!(fm.description.as_deref(), Some("Fill: PDF forms"));
+        assert!(skill_md.contains("# Pdf Tools"));
+        assert!(!lint_skill_dir(&dest).unwrap().has_errors());
+    }
+}
+
+#[test]
+fn created_skills_are_managed_and_names_are_checked() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let options = CreateSkillOptions::default();
+
+    let created = create_skill(
+        app.handle(),
+        &store,
+        "notes",
+        "with_references",
+        &options,
+        1,
+    )
+    .unwrap();
+    let record = store.get_skill_by_id(&created.skill_id).unwrap().unwrap();
+    assert_eq!(record.source_type, "local");
+    assert!(created
+        .central_path
+        .join("references/REFERENCE.md")
+        .is_file());
+
+    let again = create_skill(app.handle(), &store, "notes", "basic", &options, 1)
+        .err()
+        .expect("duplicate name");
+    assert!(format!("{:#}", again).starts_with("SKILL_EXISTS|"));
+    assert!(create_skill(app.handle(), &store, "Bad Name", "basic", &options, 1).is_err());
+    assert!(create_skill(app.handle(), &store, "other", "nope", &options, 1).is_err());
+}
+
+#[test]
+fn user_templates_substitute_variables_and_replace_builtins() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let house = dir.path().join("house");
+    let basic = house.join("basic");
+    std::fs::create_dir_all(basic.join("docs")).unwrap();
+    std::fs::write(
+        basic.join("SKILL.md"),
+        "---\nname: {{name}}\ndescription: {{description}}\n---\n\n# {{title}}\n\nBy {{author}}, {{date}}.\n",
+    )
+    .unwrap();
+    std::fs::write(basic.join("docs/{{name}}.md"), "Notes for {{name}}\n").unwrap();
+    std::fs::create_dir_all(basic.join(".git")).unwrap();
+    std::fs::write(basic.join(".git/HEAD"), "ref").unwrap();
+    std::fs::create_dir_all(house.join("not-a-template")).unwrap();
+
+    assert!(add_template_source(&store, &dir.path().join("missing").to_string_lossy()).is_err());
+    let source = house.to_string_lossy().to_string();
+    assert_eq!(
+        add_template_source(&store, &source).unwrap(),
+        vec![source.clone()]
+    );
+    assert_eq!(add_template_source(&store, &source).unwrap().len(), 1);
+
+    let templates = list_skill_templates(app.handle(), &store);
+    let ids: Vec<&str> = templates.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, vec!["with_references", "agent", "basic"]);
+    let house_basic = templates.last().unwrap();
+    assert_eq!(house_basic.source.as_deref(), Some(source.as_str()));
+    assert_eq!(house_basic.files, vec!["SKILL.md", "docs/{{name}}.md"]);
+
+    let options = CreateSkillOptions {
+        description: Some("Review pull requests".to_string()),
+        author: Some("Ada".to_string()),
+        tools: Vec::new(),
+    };
+    let created = create_skill(app.handle(), &store, "review", "basic", &options, DAY).unwrap();
+    let skill_md = std::fs::read_to_string(created.central_path.join("SKILL.md")).unwrap();
+    assert!(skill_md.contains("name: review\ndescription: Review pull requests\n"));
+    assert!(skill_md.contains("# Review\n\nBy Ada, 1970-01-02."));
+    assert_eq!(
+        std::fs::read_to_string(created.central_path.join("docs/review.md")).unwrap(),
+        "Notes for review\n"
+    );
+    assert!(!created.central_path.join(".git").exists());
+
+    assert!(remove_template_source(&store, &source).unwrap().is_empty());
+    assert_eq!(list_skill_templates(app.handle(), &store)[0].id, "basic");
+}
@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Barrier;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use super::*;
+
+#[test]
+fn requests_during_a_pass_share_one_follow_up_pass() {
+    let coalescer = SyncCoalescer::new(Duration::from_millis(20));
+    let runs = AtomicUsize::new(0);
+    let started = Barrier::new(2);
+    let work = || {
+        let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+        if run == 1 {
+            started.wait();
+        }
+        thread::sleep(Duration::from_millis(100));
+        Ok(run)
+    };
+
+    let results: Vec<usize> = thread::scope(|scope| {
+        let first = scope.spawn(|| coalescer.run("s1:cursor", work).unwrap());
+        started.wait();
+        let later: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| coalescer.run("s1:cursor", work).unwrap()))
+            .collect();
+        let mut out = vec![first.join().unwrap()];
+        out.extend(later.into_iter().map(|h| h.join().unwrap()));
+        out
+    });
+
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert_eq!(results, vec![1, 2, 2, 2, 2]);
+    assert_eq!(coalescer.run("s1:cursor", || Ok(7)).unwrap(), 7);
+}
+
+#[test]
+fn joined_requests_keep_the_error_code() {
+    let coalescer = SyncCoalescer::<()>::new(Duration::from_millis(10));
+    let started = Barrier::new(2);
+
+    let joined: Vec<_> = thread::scope(|scope| {
+        scope.spawn(|| {
+            coalescer.run("s1:cursor", || {
+                started.wait();
+                thread::sleep(Duration::from_millis(50));
+                Ok(())
+            })
+        });
+        started.wait();
+        let later: Vec<_> = (0..2)
+            .map(|_| {
+                scope.spawn(|| {
+                    coalescer.run("s1:cursor", || {
+                        Err(anyhow::anyhow!("TARGET_EXISTS|/tmp/t")).context("sync s1")
+                    })
+                })
+            })
+            .collect();
+        later.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for result in joined {
+        let err = result.unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "TARGET_EXISTS|/tmp/t");
+        assert_eq!(err.to_string(), "sync s1");
+    }
+}
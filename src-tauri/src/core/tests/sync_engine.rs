@@ -96,3 +96,76 @@ fn copy_overwrite_replaces_broken_symlink_target() {
     assert!(target.join("s/a.txt").exists());
     assert_eq!(fs::read(target.join("s/a.txt")).unwrap(), b"ok");
 }
+
+#[test]
+fn copy_dir_recursive_copies_many_files_and_reports_first_failure() {
+    let src_dir = tempfile::tempdir().unwrap();
+    for i in 0..200 {
+        let rel = format!("d{}/f{:03}.txt", i % 7, i);
+        fs::create_dir_all(src_dir.path().join(&rel).parent().unwrap()).unwrap();
+        fs::write(src_dir.path().join(&rel), format!("content {}", i)).unwrap();
+    }
+
+    let dst_dir = tempfile::tempdir().unwrap();
+    copy_dir_recursive(src_dir.path(), dst_dir.path()).unwrap();
+    for i in 0..200 {
+        let rel = format!("d{}/f{:03}.txt", i % 7, i);
+        assert_eq!(
+            fs::read_to_string(dst_dir.path().join(&rel)).unwrap(),
+            format!("content {}", i)
+        );
+    }
+
+    // Directories in the way make both copies fail; the earlier file in walk order is reported.
+    let blocked = tempfile::tempdir().unwrap();
+    fs::create_dir_all(blocked.path().join("d1/f001.txt")).unwrap();
+    fs::create_dir_all(blocked.path().join("d6/f195.txt")).unwrap();
+    for _ in 0..5 {
+        let err = copy_dir_recursive(src_dir.path(), blocked.path()).unwrap_err();
+        assert!(format!("{}", err).contains("f001.txt"), "{}", err);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_dir_recursive_never_follows_links_out_of_the_source() {
+    use std::os::unix::fs::symlink;
+
+    let outside = tempfile::tempdir().unwrap();
+    fs::create_dir_all(outside.path().join(".ssh")).unwrap();
+    fs::write(outside.path().join(".ssh/id_rsa"), b"secret").unwrap();
+
+    let src_dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(src_dir.path().join("docs")).unwrap();
+    fs::write(src_dir.path().join("docs/guide.md"), b"guide").unwrap();
+    symlink(
+        src_dir.path().join("docs/guide.md"),
+        src_dir.path().join("GUIDE.md"),
+    )
+    .unwrap();
+    symlink(src_dir.path().join("docs"), src_dir.path().join("more")).unwrap();
+    symlink(outside.path().join(".ssh"), src_dir.path().join("keys")).unwrap();
+    symlink(
+        outside.path().join(".ssh/id_rsa"),
+        src_dir.path().join("id_rsa"),
+    )
+    .unwrap();
+    symlink("../../../etc/passwd", src_dir.path().join("docs/passwd")).unwrap();
+
+    let dst_dir = tempfile::tempdir().unwrap();
+    let target = dst_dir.path().join("t");
+    copy_dir_recursive(src_dir.path(), &target).unwrap();
+
+    assert_eq!(fs::read(target.join("GUIDE.md")).unwrap(), b"guide");
+    assert!(!fs::symlink_metadata(target.join("GUIDE.md"))
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    for skipped in ["more", "keys", "id_rsa", "docs/passwd"] {
+        assert!(
+            fs::symlink_metadata(target.join(skipped)).is_err(),
+            "{} should not be copied",
+            skipped
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::fs;
+
+use super::*;
+use crate::core::content_hash::hash_dir;
+
+fn commit_all(repo: &git2::Repository, msg: &str) {
+    let sig = git2::Signature::now("t", "t@example.com").unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo
+        .head()
+        .ok()
+        .and_then(|h| h.target())
+        .and_then(|oid| repo.find_commit(oid).ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &parents)
+        .unwrap();
+}
+
+fn write_team_file(repo_dir: &Path, source: &Path) {
+    let file = serde_json::json!({
+        "version": 1,
+        "name": "Frontend team",
+        "skills": [{
+            "name": "alpha",
+            "source_type": "local",
+            "source_ref": source.to_string_lossy(),
+            "revision": null,
+            "subpath": null,
+            "content_hash": hash_dir(source).unwrap(),
+            "tools": [],
+        }],
+    });
+    fs::write(repo_dir.join("team.json"), file.to_string()).unwrap();
+}
+
+#[test]
+fn team_profiles_follow_their_file_and_reconcile_drift() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central");
+    store
+        .set_setting("central_repo_path", &central.to_string_lossy())
+        .unwrap();
+    store.set_setting("git_cache_ttl_secs", "0").unwrap();
+
+    let source = dir.path().join("src/alpha");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("SKILL.md"), "---\nname: alpha\n---\nv1\n").unwrap();
+    let repo_dir = dir.path().join("team-repo");
+    fs::create_dir_all(&repo_dir).unwrap();
+    write_team_file(&repo_dir, &source);
+    let repo = git2::Repository::init(&repo_dir).unwrap();
+    commit_all(&repo, "init");
+
+    let repo_url = repo_dir.to_string_lossy().to_string();
+    let err = track_team_profile(app.handle(), &store, &repo_url, false, 1).unwrap_err();
+    assert!(err.to_string().contains("must name a profile file"));
+    let url = format!("{}#team.json", repo_url);
+    let report = track_team_profile(app.handle(), &store, &url, false, 1).unwrap();
+    assert_eq!(report.apply.installed, vec!["alpha".to_string()]);
+    let statuses = list_team_profiles(&store).unwrap();
+    assert_eq!(statuses[0].profile_name, "Frontend team");
+    assert!(statuses[0].in_sync, "{:?}", statuses[0].drift);
+
+    // The team moves alpha on and a local member is added by hand.
+    fs::write(source.join("SKILL.md"), "---\nname: alpha\n---\nv2\n").unwrap();
+    write_team_file(&repo_dir, &source);
+    commit_all(&repo, "bump alpha");
+    let status = check_team_profile(app.handle(), &store, &report.profile.id, 2).unwrap();
+    assert_eq!(status.drift.outdated, vec!["alpha".to_string()]);
+    assert!(!status.in_sync);
+    assert_ne!(status.source.revision, statuses[0].source.revision);
+
+    let reconciled =
+        reconcile_team_profile(app.handle(), &store, &report.profile.id, false, 3).unwrap();
+    assert_eq!(reconciled.apply.updated, vec!["alpha".to_string()]);
+    assert!(list_team_profiles(&store).unwrap()[0].in_sync);
+    assert_eq!(
+        fs::read_to_string(central.join("alpha/SKILL.md")).unwrap(),
+        "---\nname: alpha\n---\nv2\n"
+    );
+
+    let other = format!("{}#other.json", repo_url);
+    assert!(track_team_profile(app.handle(), &store, &other, false, 4).is_err());
+
+    // The team pins a revision the installed source is not at.
+    let mut file: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(repo_dir.join("team.json")).unwrap()).unwrap();
+    file["skills"][0]["revision"] = "deadbeef".into();
+    fs::write(repo_dir.join("team.json"), file.to_string()).unwrap();
+    commit_all(&repo, "pin alpha");
+    let status = check_team_profile(app.handle(), &store, &report.profile.id, 5).unwrap();
+    assert_eq!(status.drift.outdated, vec!["alpha".to_string()]);
+
+    // A file that names a skill outside the Central Repo is refused on every pull.
+    file["skills"][0]["name"] = "../escaped".into();
+    fs::write(repo_dir.join("team.json"), file.to_string()).unwrap();
+    commit_all(&repo, "escape");
+    assert!(reconcile_team_profile(app.handle(), &store, &report.profile.id, false, 6).is_err());
+    assert!(!dir.path().join("escaped").exists());
+}
@@ -0,0 +1,124 @@
+use std::fs;
+
+use crate::core::skill_store::{ProfileRecord, SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::token_estimate::{
+    context_budget, estimate_skill_tokens, estimate_text_tokens, set_context_budget_tokens,
+    tool_token_totals, DEFAULT_CONTEXT_BUDGET_TOKENS,
+};
+
+#[test]
+fn text_estimate_counts_words_punctuation_and_cjk() {
+    assert_eq!(estimate_text_tokens(""), 0);
+    assert_eq!(estimate_text_tokens("hello world"), 4);
+    assert_eq!(estimate_text_tokens("a, b."), 4);
+    assert_eq!(estimate_text_tokens("技能"), 2);
+}
+
+#[test]
+fn skill_estimate_includes_linked_text_files_only() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("docs")).unwrap();
+    fs::write(
+        dir.path().join("SKILL.md"),
+        "See [guide](docs/guide.md), [img](logo.png), [web](https://x.y) and [gone](nope.md).\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("docs/guide.md"), "word ".repeat(10)).unwrap();
+    fs::write(dir.path().join("logo.png"), [0xffu8, 0xfe, 0x00, 0x80]).unwrap();
+
+    let estimate = estimate_skill_tokens(dir.path()).unwrap();
+    assert_eq!(estimate.referenced_files, vec!["docs/guide.md"]);
+    assert_eq!(estimate.referenced, 10);
+    assert_eq!(estimate.total, estimate.skill_md + 10);
+}
+
+fn add_skill(store: &SkillStore, id: &str, tokens: i64, tools: &[&str]) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: format!("/tmp/central/{}", id),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: Some(tokens),
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    for tool in tools {
+        store
+            .upsert_skill_target(&SkillTargetRecord {
+                id: format!("{}-{}", id, tool),
+                skill_id: id.to_string(),
+                tool: tool.to_string(),
+                target_path: format!("/tmp/{}/{}", tool, id),
+                mode: "symlink".to_string(),
+                status: "ok".to_string(),
+                last_error: None,
+                synced_at: None,
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn context_budget_sums_a_tool_or_profile_and_flags_overruns() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    add_skill(&store, "small", 500, &["cursor"]);
+    add_skill(&store, "large", 19_800, &["cursor", "claude_code"]);
+    add_skill(&store, "idle", 7_000, &[]);
+    store
+        .upsert_profile(&ProfileRecord {
+            id: "p1".to_string(),
+            name: "Writing".to_string(),
+            skill_ids: vec!["idle".to_string(), "small".to_string()],
+            created_at: 1,
+            updated_at: 1,
+        })
+        .unwrap();
+
+    let cursor = context_budget(&store, Some("cursor"), None).unwrap();
+    assert_eq!(cursor.tokens, 20_300);
+    assert_eq!(cursor.budget, DEFAULT_CONTEXT_BUDGET_TOKENS);
+    assert!(cursor.over_budget);
+    let names: Vec<_> = cursor.skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["large", "small"]);
+    assert!(
+        !context_budget(&store, Some("claude_code"), None)
+            .unwrap()
+            .over_budget
+    );
+
+    let profile = context_budget(&store, None, Some("p1")).unwrap();
+    assert_eq!(profile.tokens, 7_500);
+    assert!(!profile.over_budget);
+    set_context_budget_tokens(&store, 5_000).unwrap();
+    assert!(
+        context_budget(&store, None, Some("p1"))
+            .unwrap()
+            .over_budget
+    );
+    let totals = tool_token_totals(&store).unwrap();
+    assert!(totals.iter().all(|t| t.over_budget));
+
+    set_context_budget_tokens(&store, 0).unwrap();
+    assert!(
+        !context_budget(&store, Some("cursor"), None)
+            .unwrap()
+            .over_budget
+    );
+    assert!(set_context_budget_tokens(&store, -1).is_err());
+    assert!(context_budget(&store, None, None).is_err());
+    assert!(context_budget(&store, Some("cursor"), Some("p1")).is_err());
+}
@@ -1,7 +1,8 @@
 use std::fs;
 
 use crate::core::tool_adapters::{
-    adapter_by_key, adapters_sharing_skills_dir, scan_tool_dir, ToolAdapter, ToolId,
+    adapter_by_key, adapters_sharing_skills_dir, detect_dir_exists, invalidate_tool_install_cache,
+    scan_tool_dir, ToolAdapter, ToolId,
 };
 
 #[test]
@@ -81,3 +82,17 @@ fn scan_tool_dir_skips_app_support_path() {
     let out = scan_tool_dir(&tool, &root).unwrap();
     assert!(out.is_empty());
 }
+
+#[test]
+fn detect_dir_checks_are_cached_until_invalidated() {
+    let dir = tempfile::tempdir().unwrap();
+    let detect = dir.path().join(".tool");
+    fs::create_dir(&detect).unwrap();
+    assert!(detect_dir_exists(&detect));
+
+    fs::remove_dir(&detect).unwrap();
+    assert!(detect_dir_exists(&detect));
+
+    invalidate_tool_install_cache();
+    assert!(!detect_dir_exists(&detect));
+}
@@ -0,0 +1,111 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::{JobRunRecord, SkillRecord};
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn add_skill(store: &SkillStore, dir: &Path, name: &str, with_content: bool) {
+    let central = dir.join("central").join(name);
+    if with_content {
+        fs::create_dir_all(&central).unwrap();
+        fs::write(central.join("SKILL.md"), "# skill").unwrap();
+    }
+    store
+        .upsert_skill(&SkillRecord {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: None,
+            repo_id: None,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: format!("{}-cursor", name),
+            skill_id: format!("id-{}", name),
+            tool: "cursor".to_string(),
+            target_path: dir.join("cursor").join(name).to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn quick_sync_records_failures_and_summary_reports_them() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    add_skill(&store, dir.path(), "pdf", true);
+    add_skill(&store, dir.path(), "gone", false);
+
+    let report = quick_sync_all(&store, 5).unwrap();
+    assert_eq!(report.synced, vec!["pdf:cursor".to_string()]);
+    assert_eq!(report.failed.len(), 1);
+    assert!(report.failed[0].starts_with("gone:cursor: "));
+    assert!(dir.path().join("cursor/pdf/SKILL.md").exists());
+    let synced = store.get_skill_target("id-pdf", "cursor").unwrap().unwrap();
+    assert_eq!(synced.synced_at, Some(5));
+
+    add_skill(&store, dir.path(), "deploy", true);
+    fs::write(dir.path().join("central/deploy/run.sh"), "echo hi\n").unwrap();
+    let report = quick_sync_all(&store, 6).unwrap();
+    assert!(report
+        .failed
+        .contains(&"deploy: EXECUTABLES_UNCONFIRMED|deploy".to_string()));
+    assert!(!dir.path().join("cursor/deploy").exists());
+    store.delete_skill("id-deploy").unwrap();
+
+    let summary = tray_summary(&store).unwrap();
+    assert_eq!(summary.skills, 2);
+    assert_eq!(summary.target_errors, 1);
+    assert_eq!(summary.pending_updates, None);
+    assert_eq!(summary.last_error, None);
+
+    store
+        .insert_job_run(
+            &JobRunRecord {
+                id: 0,
+                job_id: "update_check".to_string(),
+                manual: false,
+                started_at: 6,
+                finished_at: 7,
+                ok: false,
+                message: Some("offline".to_string()),
+            },
+            10,
+        )
+        .unwrap();
+    let check = UpdateCheck {
+        checked_at: 8,
+        checked: 2,
+        available: vec!["pdf".to_string()],
+        failed: Vec::new(),
+    };
+    store
+        .set_setting(UPDATE_CHECK_KEY, &serde_json::to_string(&check).unwrap())
+        .unwrap();
+    let summary = tray_summary(&store).unwrap();
+    assert_eq!(summary.pending_updates, Some(1));
+    assert_eq!(summary.update_checked_at, Some(8));
+    assert_eq!(summary.last_error.as_deref(), Some("update_check: offline"));
+    assert_eq!(summary.last_error_at, Some(7));
+}
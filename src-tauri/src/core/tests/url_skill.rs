@@ -0,0 +1,160 @@
+use super::*;
+use crate::core::installer::update_managed_skill_from_source_with_force;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+        .set_setting("central_repo_path", &dir.join("central").to_string_lossy())
+        .unwrap();
+    store
+}
+
+#[test]
+fn github_pages_map_to_raw_files_and_names_come_from_the_url() {
+    assert_eq!(
+        raw_url("https://github.com/acme/skills/blob/main/pdf/SKILL.md"),
+        "https://raw.githubusercontent.com/acme/skills/main/pdf/SKILL.md"
+    );
+    assert_eq!(
+        raw_url("https://example.com/a/b.md"),
+        "https://example.com/a/b.md"
+    );
+
+    assert_eq!(
+        url_skill_name("https://example.com/skills/pdf/SKILL.md", ""),
+        "pdf"
+    );
+    assert_eq!(
+        url_skill_name("https://example.com/Code Review.md?raw=1", ""),
+        "code-review"
+    );
+    assert_eq!(
+        url_skill_name("https://example.com/x.md", "---\nname: Tidy Docs\n---\n"),
+        "tidy-docs"
+    );
+}
+
+#[test]
+fn frontmatter_is_synthesized_only_when_missing() {
+    let with = "---\nname: pdf\ndescription: PDFs\n---\nbody\n";
+    assert_eq!(scaffold_skill_md(with, "x", "u").unwrap(), with);
+
+    let text = "# Release notes\n\nWrite release notes from\nthe merged PRs.\n\nMore.\n";
+    let skill_md = scaffold_skill_md(text, "release-notes", "u").unwrap();
+    let fm = SkillFrontmatter::parse(&skill_md).unwrap();
+    assert_eq!(fm.name.as_deref(), Some("release-notes"));
+    assert_eq!(
+        fm.description.as_deref(),
+        Some("Write release notes from the merged PRs.")
+    );
+    assert!(skill_md.ends_with(
+        "---\n\n# Release notes\n\nWrite release notes from\nthe merged PRs.\n\nMore.\n"
+    ));
+
+    let fm =
+        SkillFrontmatter::parse(&scaffold_skill_md("# Title\n", "t", "https://x/t.md").unwrap())
+            .unwrap();
+    assert_eq!(
+        fm.description.as_deref(),
+        Some("Imported from https://x/t.md")
+    );
+}
+
+#[test]
+fn url_skills_install_and_update_with_conditional_requests() {
+    let app = tauri::test::mock_app();
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let mut server = mockito::Server::new();
+    let url = format!("{}/skills/notes.md", server.url());
+
+    let first = server
+        .mock("GET", "/skills/notes.md")
+        .with_status(200)
+        .with_header("etag", "\"v1\"")
+        .with_body("Keep short notes.\n")
+        .create();
+    let installed =
+        install_url_skill(app.handle(), &store, &url, None, InstallOptions::default()).unwrap();
+    first.assert();
+    assert_eq!(installed.name, "notes");
+    let record = store.get_skill_by_id(&installed.skill_id).unwrap().unwrap();
+    assert_eq!(record.source_type, URL_SOURCE);
+    assert_eq!(record.source_ref.as_deref(), Some(url.as_str()));
+    assert_eq!(record.source_revision.as_deref(), Some("etag:\"v1\""));
+
+    let unchanged = server
+        .mock("GET", "/skills/notes.md")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .create();
+    let result =
+        update_managed_skill_from_source_with_force(app.handle(), &store, &record.id, false)
+            .unwrap();
+    unchanged.assert();
+    assert_eq!(result.content_hash, record.content_hash);
+    unchanged.remove();
+
+    server
+        .mock("GET", "/skills/notes.md")
+        .with_status(200)
+        .with_header("last-modified", "Wed, 01 Jan 2025 00:00:00 GMT")
+        .with_body("Keep shorter notes.\n")
+        .create();
+    let result =
+        update_managed_skill_from_source_with_force(app.handle(), &store, &record.id, false)
+            .unwrap();
+    assert_eq!(
+        result.source_revision.as_deref(),
+        Some("modified:Wed, 01 Jan 2025 00:00:00 GMT")
+    );
+    let skill_md = std::fs::read_to_string(installed.central_path.join("SKILL.md")).unwrap();
+    assert!(
+        skill_md.contains("description: Keep shorter notes."),
+        "{}",
+        skill_md
+    );
+}
+
+#[test]
+fn redirects_are_checked_against_the_host_policy() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    crate::core::git_host_policy::set_git_host_policy(
+        &store,
+        crate::core::git_host_policy::GitHostPolicy {
+            allow: Vec::new(),
+            block: vec!["blocked.example".to_string()],
+        },
+    )
+    .unwrap();
+    let mut server = mockito::Server::new();
+
+    server
+        .mock("GET", "/moved.md")
+        .with_status(301)
+        .with_header("location", "/skills/notes.md")
+        .create();
+    server
+        .mock("GET", "/skills/notes.md")
+        .with_status(200)
+        .with_body("Keep short notes.\n")
+        .create();
+    let fetched = fetch_url(&store, &format!("{}/moved.md", server.url()), None).unwrap();
+    assert_eq!(
+        fetched,
+        UrlFetch::Fetched {
+            text: "Keep short notes.\n".to_string(),
+            revision: None,
+        }
+    );
+
+    server
+        .mock("GET", "/escape.md")
+        .with_status(302)
+        .with_header("location", "http://blocked.example/notes.md")
+        .create();
+    let err = fetch_url(&store, &format!("{}/escape.md", server.url()), None).unwrap_err();
+    assert_eq!(err.to_string(), "GIT_HOST_BLOCKED|blocked.example");
+}
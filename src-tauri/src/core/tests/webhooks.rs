@@ -0,0 +1,68 @@
+use mockito::Matcher;
+use serde_json::json;
+
+use super::*;
+
+fn make_store(dir: &std::path::Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+#[test]
+fn urls_are_validated_and_deduplicated() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    assert!(get_webhook_urls(&store).is_empty());
+
+    let urls = set_webhook_urls(
+        &store,
+        vec![
+            " https://hooks.slack.com/services/T0/B0/x ".to_string(),
+            String::new(),
+            "https://hooks.slack.com/services/T0/B0/x".to_string(),
+            "http://localhost:8080/audit".to_string(),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        urls,
+        vec![
+            "https://hooks.slack.com/services/T0/B0/x",
+            "http://localhost:8080/audit"
+        ]
+    );
+    assert_eq!(get_webhook_urls(&store), urls);
+
+    assert!(set_webhook_urls(&store, vec!["ftp://example.com/x".to_string()]).is_err());
+    assert!(set_webhook_urls(&store, vec!["hooks.example.com".to_string()]).is_err());
+    assert_eq!(get_webhook_urls(&store), urls);
+    assert!(set_webhook_urls(&store, Vec::new()).unwrap().is_empty());
+}
+
+#[test]
+fn events_are_posted_as_json_and_failures_do_not_stop_delivery() {
+    let mut server = mockito::Server::new();
+    let ok = server
+        .mock("POST", "/ok")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::PartialJson(json!({
+            "event": "sync.failed",
+            "skill": "pdf",
+            "tool": "cursor",
+            "error": "TARGET_EXISTS|/x",
+            "text": "Skills Hub: syncing pdf to cursor failed: TARGET_EXISTS|/x",
+        })))
+        .with_status(200)
+        .create();
+    let failing = server.mock("POST", "/down").with_status(500).create();
+
+    let event = WebhookEvent::sync_failed("s1", "pdf", "cursor", "TARGET_EXISTS|/x");
+    let urls = vec![
+        format!("{}/down", server.url()),
+        format!("{}/ok", server.url()),
+    ];
+    assert_eq!(deliver(&urls, &event), 1);
+    ok.assert();
+    failing.assert();
+}
@@ -0,0 +1,119 @@
+use std::fs;
+
+use super::*;
+use crate::core::profiles::save_profile;
+use crate::core::skill_store::SkillRecord;
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    store
+}
+
+fn add_skill(store: &SkillStore, name: &str, central: &Path) {
+    fs::create_dir_all(central).unwrap();
+    fs::write(
+        central.join("SKILL.md"),
+        format!("---\nname: {}\n---\nbody\n", name),
+    )
+    .unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: name.to_string(),
+            name: name.to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            metadata: None,
+            license: None,
+            token_estimate: None,
+            contains_executables: Some(false),
+            repo_id: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn workspaces_are_registered_once_by_canonical_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let project = dir.path().join("client-app");
+    fs::create_dir_all(&project).unwrap();
+
+    let added = add_workspace(&store, &project, None, 3).unwrap();
+    assert_eq!(added.name, "client-app");
+    assert_eq!(
+        added.path,
+        project.canonicalize().unwrap().to_string_lossy()
+    );
+    let err = add_workspace(&store, &project.join("."), Some("again".into()), 4).unwrap_err();
+    assert!(err.to_string().contains("already a workspace"));
+    assert!(add_workspace(&store, &dir.path().join("missing"), None, 4).is_err());
+
+    store.delete_workspace(&added.id).unwrap();
+    assert!(list_workspaces(&store).unwrap().is_empty());
+}
+
+#[test]
+fn applying_a_profile_syncs_members_and_removes_the_rest() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central");
+    for name in ["pdf", "review", "legacy"] {
+        add_skill(&store, name, &central.join(name));
+    }
+    let project = dir.path().join("oss");
+    fs::create_dir_all(project.join(".claude")).unwrap();
+    let workspace = add_workspace(&store, &project, Some("OSS".into()), 1).unwrap();
+    let project = Path::new(&workspace.path);
+
+    let legacy = store.get_skill_by_id("legacy").unwrap().unwrap();
+    sync_skill_to_project(&store, &legacy, project, "claude_code", 1).unwrap();
+    assert!(project.join(".claude/skills/legacy").exists());
+
+    let profile = save_profile(
+        &store,
+        None,
+        "open source",
+        &["pdf".to_string(), "review".to_string()],
+        1,
+    )
+    .unwrap();
+    let report =
+        apply_profile_to_workspace(&store, &workspace.id, &profile.id, &[], false, 2).unwrap();
+    assert!(report.failed.is_empty());
+    let mut synced: Vec<&str> = report.synced.iter().map(|t| t.skill_id.as_str()).collect();
+    synced.sort();
+    assert_eq!(synced, vec!["pdf", "review"]);
+    assert_eq!(report.removed.len(), 1);
+    assert!(!project.join(".claude/skills/legacy").exists());
+    assert!(project.join(".claude/skills/pdf/SKILL.md").exists());
+
+    let views = list_workspaces(&store).unwrap();
+    assert_eq!(views[0].workspace.name, "OSS");
+    assert!(views[0].exists);
+    let skills: Vec<(&str, Vec<String>)> = views[0]
+        .skills
+        .iter()
+        .map(|s| (s.name.as_str(), s.tools.clone()))
+        .collect();
+    assert_eq!(
+        skills,
+        vec![
+            ("pdf", vec!["claude_code".to_string()]),
+            ("review", vec!["claude_code".to_string()]),
+        ]
+    );
+
+    // Applying again changes nothing.
+    let again =
+        apply_profile_to_workspace(&store, &workspace.id, &profile.id, &[], false, 3).unwrap();
+    assert_eq!(again, WorkspaceProfileReport::default());
+}
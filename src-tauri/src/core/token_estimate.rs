@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+
+use super::skill_store::{SkillProjection, SkillRecord, SkillStore};
+use super::skill_sync::STATUS_DISABLED;
+
+/// Referenced files larger than this are assumed not to be loaded into context.
+const MAX_REFERENCED_BYTES: u64 = 1024 * 1024;
+/// Average characters per token for Latin-script words with common BPE vocabularies.
+const CHARS_PER_TOKEN: usize = 4;
+pub const CONTEXT_BUDGET_KEY: &str = "context_budget_tokens";
+pub const DEFAULT_CONTEXT_BUDGET_TOKENS: i64 = 20_000;
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TokenEstimate {
+    pub skill_md: u64,
+    /// Text files linked from SKILL.md with relative paths.
+    pub referenced: u64,
+    pub referenced_files: Vec<String>,
+    pub total: u64,
+}
+
+/// Rough BPE token count: word runs cost about one token per four characters, each
+/// punctuation mark and each CJK/other non-ASCII character costs one, whitespace is free.
+pub fn estimate_text_tokens(text: &str) -> u64 {
+    let mut tokens = 0u64;
+    let mut run = 0usize;
+    let flush = |run: &mut usize, tokens: &mut u64| {
+        if *run > 0 {
+            *tokens += run.div_ceil(CHARS_PER_TOKEN) as u64;
+            *run = 0;
+        }
+    };
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            run += 1;
+            continue;
+        }
+        flush(&mut run, &mut tokens);
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    flush(&mut run, &mut tokens);
+    tokens
+}
+
+pub fn estimate_skill_tokens(dir: &Path) -> Result<TokenEstimate> {
+    let skill_md = dir.join("SKILL.md");
+    let text =
+        std::fs::read_to_string(&skill_md).with_context(|| format!("read {:?}", skill_md))?;
+    let mut estimate = TokenEstimate {
+        skill_md: estimate_text_tokens(&text),
+        ..Default::default()
+    };
+
+    for rel in referenced_files(dir, &text) {
+        let path = dir.join(&rel);
+        let small = std::fs::metadata(&path)
+            .map(|m| m.is_file() && m.len() <= MAX_REFERENCED_BYTES)
+            .unwrap_or(false);
+        if !small {
+            continue;
+        }
+        // Binary files (images, archives) are not read into context as text.
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            estimate.referenced += estimate_text_tokens(&content);
+            estimate.referenced_files.push(rel);
+        }
+    }
+    estimate.total = estimate.skill_md + estimate.referenced;
+    Ok(estimate)
+}
+
+/// Relative link targets in `text` that resolve to files inside `dir`.
+pub(crate) fn referenced_files(dir: &Path, text: &str) -> BTreeSet<String> {
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let re = LINK_RE.get_or_init(|| Regex::new(r"\]\(\s*<?([^)\s>]+)>?[^)]*\)").unwrap());
+    let root = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    let mut out = BTreeSet::new();
+    for cap in re.captures_iter(text) {
+        let target = &cap[1];
+        if target.starts_with('#') || target.starts_with('/') || target.contains(':') {
+            continue;
+        }
+        let path_part = target.split(['#', '?']).next().unwrap_or(target);
+        let decoded = urlencoding::decode(path_part)
+            .map(|v| v.into_owned())
+            .unwrap_or_else(|_| path_part.to_string());
+        let resolved: PathBuf = match dir.join(&decoded).canonicalize() {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+        if resolved.starts_with(&root) && resolved.is_file() {
+            out.insert(decoded.trim_start_matches("./").to_string());
+        }
+    }
+    out
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ToolTokenTotal {
+    pub tool: String,
+    pub skills: usize,
+    pub tokens: i64,
+    pub over_budget: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ContextBudgetSkill {
+    pub skill_id: String,
+    pub name: String,
+    pub tokens: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ContextBudgetReport {
+    pub tool: Option<String>,
+    pub profile_id: Option<String>,
+    /// Largest first.
+    pub skills: Vec<ContextBudgetSkill>,
+    pub tokens: i64,
+    /// `0` when no budget is set.
+    pub budget: i64,
+    pub over_budget: bool,
+}
+
+/// Sum the estimates of every skill synced to each tool and not disabled there. Skills installed before estimates
+/// existed are measured on the fly and the result is stored.
+pub fn tool_token_totals(store: &SkillStore) -> Result<Vec<ToolTokenTotal>> {
+    let budget = get_context_budget_tokens(store);
+    let mut totals: BTreeMap<String, ToolTokenTotal> = BTreeMap::new();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        let tokens = stored_skill_tokens(store, &skill)?;
+        for target in store.list_skill_targets(&skill.id)? {
+            if target.status == STATUS_DISABLED {
+                continue;
+            }
+            let entry = totals
+                .entry(target.tool.clone())
+                .or_insert_with(|| ToolTokenTotal {
+                    tool: target.tool,
+                    skills: 0,
+                    tokens: 0,
+                    over_budget: false,
+                });
+            entry.skills += 1;
+            entry.tokens += tokens;
+        }
+    }
+    for total in totals.values_mut() {
+        total.over_budget = exceeds(total.tokens, budget);
+    }
+    Ok(totals.into_values().collect())
+}
+
+pub fn get_context_budget_tokens(store: &SkillStore) -> i64 {
+    store
+        .get_setting(CONTEXT_BUDGET_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| raw.trim().parse::<i64>().ok())
+        .filter(|tokens| *tokens >= 0)
+        .unwrap_or(DEFAULT_CONTEXT_BUDGET_TOKENS)
+}
+
+/// `0` turns the budget off.
+pub fn set_context_budget_tokens(store: &SkillStore, tokens: i64) -> Result<i64> {
+    if tokens < 0 {
+        anyhow::bail!("context budget must not be negative");
+    }
+    store.set_setting(CONTEXT_BUDGET_KEY, &tokens.to_string())?;
+    Ok(tokens)
+}
+
+/// The estimated context cost of the skills enabled for `tool`, or of the members of `profile_id`,
+/// with each skill's share. Exactly one of the two is given.
+pub fn context_budget(
+    store: &SkillStore,
+    tool: Option<&str>,
+    profile_id: Option<&str>,
+) -> Result<ContextBudgetReport> {
+    let skills = store.list_skills_with(SkillProjection::Summary)?;
+    let included: Vec<_> = match (tool, profile_id) {
+        (Some(tool), None) => {
+            let mut included = Vec::new();
+            for skill in skills {
+                if store
+                    .list_skill_targets(&skill.id)?
+                    .iter()
+                    .any(|t| t.tool == tool && t.status != STATUS_DISABLED)
+                {
+                    included.push(skill);
+                }
+            }
+            included
+        }
+        (None, Some(profile_id)) => {
+            let profile = store
+                .get_profile(profile_id)?
+                .ok_or_else(|| anyhow::anyhow!("profile not found"))?;
+            skills
+                .into_iter()
+                .filter(|s| profile.skill_ids.contains(&s.id))
+                .collect()
+        }
+        _ => anyhow::bail!("give either a tool or a profile"),
+    };
+    let mut entries = Vec::new();
+    for skill in &included {
+        entries.push(ContextBudgetSkill {
+            skill_id: skill.id.clone(),
+            name: skill.name.clone(),
+            tokens: stored_skill_tokens(store, skill)?,
+        });
+    }
+    entries.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.name.cmp(&b.name)));
+    let tokens = entries.iter().map(|e| e.tokens).sum();
+    let budget = get_context_budget_tokens(store);
+    Ok(ContextBudgetReport {
+        tool: tool.map(str::to_string),
+        profile_id: profile_id.map(str::to_string),
+        skills: entries,
+        tokens,
+        budget,
+        over_budget: exceeds(tokens, budget),
+    })
+}
+
+fn exceeds(tokens: i64, budget: i64) -> bool {
+    budget > 0 && tokens > budget
+}
+
+/// The stored estimate, measured and stored first for skills installed before estimates existed.
+fn stored_skill_tokens(store: &SkillStore, skill: &SkillRecord) -> Result<i64> {
+    if let Some(tokens) = skill.token_estimate {
+        return Ok(tokens);
+    }
+    let tokens = estimate_skill_tokens(Path::new(&skill.central_path))
+        .map(|e| e.total as i64)
+        .unwrap_or(0);
+    store.set_skill_token_estimate(&skill.id, Some(tokens))?;
+    Ok(tokens)
+}
+
+#[cfg(test)]
+#[path = "tests/token_estimate.rs"]
+mod tests;
@@ -1,7 +1,17 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 
+/// How long an `is_tool_installed` answer is reused, so bulk updates and syncs stat each
+/// tool's detect dir once instead of once per target.
+const INSTALLED_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Detect dir -> (checked at, existed).
+static INSTALLED_CACHE: Mutex<BTreeMap<PathBuf, (Instant, bool)>> = Mutex::new(BTreeMap::new());
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ToolId {
     Cursor,
@@ -420,8 +430,41 @@ pub fn resolve_detect_path(adapter: &ToolAdapter) -> Result<PathBuf> {
     Ok(home.join(adapter.relative_detect_dir))
 }
 
+/// Whether the tool's detect dir exists. Answers are cached for `INSTALLED_CACHE_TTL`; call
+/// `invalidate_tool_install_cache` when a fresh answer is required.
 pub fn is_tool_installed(adapter: &ToolAdapter) -> Result<bool> {
-    Ok(resolve_detect_path(adapter)?.exists())
+    Ok(detect_dir_exists(&resolve_detect_path(adapter)?))
+}
+
+/// Skills dir of the tool with this key, if it is installed on this machine.
+pub fn installed_tool_dir(key: &str) -> Option<PathBuf> {
+    let adapter = adapter_by_key(key)?;
+    if !is_tool_installed(&adapter).unwrap_or(false) {
+        return None;
+    }
+    resolve_default_path(&adapter).ok()
+}
+
+/// Drop every cached `is_tool_installed` answer.
+pub fn invalidate_tool_install_cache() {
+    INSTALLED_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+pub(crate) fn detect_dir_exists(path: &Path) -> bool {
+    let mut cache = INSTALLED_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&(checked_at, exists)) = cache.get(path) {
+        if checked_at.elapsed() < INSTALLED_CACHE_TTL {
+            return exists;
+        }
+    }
+    let exists = path.exists();
+    cache.insert(path.to_path_buf(), (Instant::now(), exists));
+    exists
 }
 
 pub fn scan_tool_dir(tool: &ToolAdapter, dir: &Path) -> Result<Vec<DetectedSkill>> {
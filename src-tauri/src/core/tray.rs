@@ -0,0 +1,167 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::installer::source_has_update;
+use super::self_repair::STATUS_OK;
+use super::skill_executables::ensure_sync_allowed;
+use super::skill_lock::lock_skill;
+use super::skill_store::{SkillStore, SkillTargetRecord};
+use super::skill_sync::STATUS_DISABLED;
+use super::sync_engine::sync_dir_for_tool_with_overwrite;
+
+/// Setting holding the result of the last update check, as JSON.
+const UPDATE_CHECK_KEY: &str = "last_update_check";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCheck {
+    pub checked_at: i64,
+    /// Skills whose source was compared.
+    pub checked: usize,
+    /// Names of skills whose source has newer content.
+    pub available: Vec<String>,
+    /// Skills whose source could not be reached, with the error.
+    pub failed: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct QuickSyncReport {
+    /// Targets re-created, as `name:tool`.
+    pub synced: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// What a tray menu shows at a glance.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TraySummary {
+    pub skills: usize,
+    /// From the last update check; `None` before the first one.
+    pub pending_updates: Option<usize>,
+    pub update_checked_at: Option<i64>,
+    /// Targets whose last sync failed.
+    pub target_errors: usize,
+    /// The newest failed background job, as `<job>: <error>`.
+    pub last_error: Option<String>,
+    pub last_error_at: Option<i64>,
+}
+
+/// Compare every git and local skill with its source and keep the result for the summary.
+pub fn check_skill_updates<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    now: i64,
+) -> Result<UpdateCheck> {
+    let mut check = UpdateCheck {
+        checked_at: now,
+        ..Default::default()
+    };
+    for skill in store.list_skills()? {
+        if !matches!(skill.source_type.as_str(), "git" | "local") {
+            continue;
+        }
+        check.checked += 1;
+        match source_has_update(app, store, &skill) {
+            Ok(true) => check.available.push(skill.name),
+            Ok(false) => {}
+            Err(err) => check.failed.push(format!("{}: {:#}", skill.name, err)),
+        }
+    }
+    store.set_setting(UPDATE_CHECK_KEY, &serde_json::to_string(&check)?)?;
+    Ok(check)
+}
+
+/// The last `check_skill_updates` result, if any.
+pub fn last_update_check(store: &SkillStore) -> Option<UpdateCheck> {
+    let raw = store.get_setting(UPDATE_CHECK_KEY).ok().flatten()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Re-create every enabled global target from the central repo. A failure is recorded on the
+/// target and does not stop the others. Skills the executable policy holds back without a
+/// confirmation are left alone and reported as failed, as `<name>: <error>`.
+pub fn quick_sync_all(store: &SkillStore, now: i64) -> Result<QuickSyncReport> {
+    let mut report = QuickSyncReport::default();
+    for skill in store.list_skills()? {
+        let central = Path::new(&skill.central_path);
+        let targets: Vec<SkillTargetRecord> = store
+            .list_skill_targets(&skill.id)?
+            .into_iter()
+            .filter(|target| target.status != STATUS_DISABLED)
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+        if let Err(err) = ensure_sync_allowed(store, &skill, false) {
+            report.failed.push(format!("{}: {:#}", skill.name, err));
+            continue;
+        }
+        for target in targets {
+            let label = format!("{}:{}", skill.name, target.tool);
+            let synced =
+                lock_skill(&skill.id, &format!("sync:{}", target.tool)).and_then(|_lock| {
+                    sync_dir_for_tool_with_overwrite(
+                        &target.tool,
+                        central,
+                        Path::new(&target.target_path),
+                        true,
+                    )
+                });
+            match synced {
+                Ok(outcome) => {
+                    store.upsert_skill_target(&SkillTargetRecord {
+                        mode: outcome.mode_used.as_key().to_string(),
+                        status: STATUS_OK.to_string(),
+                        last_error: None,
+                        synced_at: Some(now),
+                        ..target
+                    })?;
+                    report.synced.push(label);
+                }
+                Err(err) => {
+                    let message = format!("{:#}", err);
+                    store.set_skill_target_status(
+                        &skill.id,
+                        &target.tool,
+                        &target.status,
+                        Some(&message),
+                    )?;
+                    report.failed.push(format!("{}: {}", label, message));
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+pub fn tray_summary(store: &SkillStore) -> Result<TraySummary> {
+    let skills = store.list_skills()?;
+    let mut target_errors = 0;
+    for skill in &skills {
+        target_errors += store
+            .list_skill_targets(&skill.id)?
+            .iter()
+            .filter(|t| t.status != STATUS_DISABLED && t.last_error.is_some())
+            .count();
+    }
+    let check = last_update_check(store);
+    let failed_run = store.latest_failed_job_run()?;
+    Ok(TraySummary {
+        skills: skills.len(),
+        pending_updates: check.as_ref().map(|c| c.available.len()),
+        update_checked_at: check.map(|c| c.checked_at),
+        target_errors,
+        last_error_at: failed_run.as_ref().map(|r| r.finished_at),
+        last_error: failed_run.map(|r| {
+            format!(
+                "{}: {}",
+                r.job_id,
+                r.message.unwrap_or_else(|| "failed".to_string())
+            )
+        }),
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/tray.rs"]
+mod tests;
@@ -0,0 +1,227 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use reqwest::header::{
+    HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION,
+};
+use reqwest::redirect;
+use reqwest::StatusCode;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::cursor_rules::rule_skill_name;
+use super::environments::with_proxy;
+use super::git_host_policy::ensure_git_host_allowed;
+use super::installer::{install_converted_skill, InstallOptions, InstallResult};
+use super::skill_metadata::SkillFrontmatter;
+use super::skill_store::SkillStore;
+
+/// `source_type` of skills downloaded from a single markdown URL; `source_ref` is the URL and
+/// `source_revision` the validator the server sent (`etag:<value>` or `modified:<date>`).
+pub const URL_SOURCE: &str = "url";
+/// A skill is one markdown file; anything bigger is not what the user pointed at.
+const MAX_DOWNLOAD_BYTES: usize = 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 10;
+/// Longest description synthesized from the first paragraph.
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+#[derive(Serialize)]
+struct SynthesizedFrontmatter<'a> {
+    name: &'a str,
+    description: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum UrlFetch {
+    /// The server confirmed the copy matching the revision sent is still current.
+    NotModified,
+    Fetched {
+        text: String,
+        revision: Option<String>,
+    },
+}
+
+/// GitHub page URLs (`github.com/<owner>/<repo>/blob/<ref>/<path>`) point at HTML; fetch the raw
+/// file instead. Other URLs are used as given.
+pub fn raw_url(url: &str) -> String {
+    let url = url.trim();
+    let Some(rest) = url.strip_prefix("https://github.com/") else {
+        return url.to_string();
+    };
+    let parts: Vec<&str> = rest.splitn(4, '/').collect();
+    match parts.as_slice() {
+        [owner, repo, "blob", path] => format!(
+            "https://raw.githubusercontent.com/{}/{}/{}",
+            owner, repo, path
+        ),
+        _ => url.to_string(),
+    }
+}
+
+/// Download `url`. With a `revision` from an earlier fetch the request is conditional, and a
+/// `304 Not Modified` comes back as `UrlFetch::NotModified`. Redirects are followed by hand so
+/// that every hop is checked against the git host policy.
+pub fn fetch_url(store: &SkillStore, url: &str, revision: Option<&str>) -> Result<UrlFetch> {
+    let mut url = raw_url(url);
+    let client = with_proxy(
+        Client::builder()
+            .timeout(DOWNLOAD_TIMEOUT)
+            .redirect(redirect::Policy::none()),
+    )?
+    .build()?;
+    let mut hops = 0;
+    let response = loop {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            anyhow::bail!("not an http(s) URL: {}", url);
+        }
+        ensure_git_host_allowed(store, &url)?;
+        let mut request = client.get(&url).header("User-Agent", "skills-hub");
+        match revision.and_then(|r| r.split_once(':')) {
+            Some(("etag", etag)) => request = request.header(IF_NONE_MATCH, etag),
+            Some(("modified", date)) => request = request.header(IF_MODIFIED_SINCE, date),
+            _ => {}
+        }
+        let response = request
+            .send()
+            .with_context(|| format!("download {}", url))?;
+        if !response.status().is_redirection() || response.status() == StatusCode::NOT_MODIFIED {
+            break response;
+        }
+        hops += 1;
+        if hops > MAX_REDIRECTS {
+            anyhow::bail!("download {}: too many redirects", url);
+        }
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| anyhow::anyhow!("download {}: redirect without a location", url))?;
+        url = response
+            .url()
+            .join(location)
+            .with_context(|| format!("download {}: bad redirect {:?}", url, location))?
+            .to_string();
+    };
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(UrlFetch::NotModified);
+    }
+    let response = response
+        .error_for_status()
+        .with_context(|| format!("download {}", url))?;
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let revision = header(ETAG)
+        .map(|etag| format!("etag:{}", etag))
+        .or_else(|| header(LAST_MODIFIED).map(|date| format!("modified:{}", date)));
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOWNLOAD_BYTES as u64)
+    {
+        anyhow::bail!("{} is larger than {} bytes", url, MAX_DOWNLOAD_BYTES);
+    }
+    let bytes = response.bytes()?;
+    if bytes.len() > MAX_DOWNLOAD_BYTES {
+        anyhow::bail!("{} is larger than {} bytes", url, MAX_DOWNLOAD_BYTES);
+    }
+    let text = String::from_utf8(bytes.to_vec())
+        .map_err(|_| anyhow::anyhow!("{} is not a UTF-8 text file", url))?;
+    Ok(UrlFetch::Fetched { text, revision })
+}
+
+/// Skill name for a downloaded file: its frontmatter `name`, else the file name without the
+/// extension, or the enclosing folder for files named `SKILL.md`/`README.md`.
+pub fn url_skill_name(url: &str, text: &str) -> String {
+    if let Some(name) = SkillFrontmatter::parse(text).ok().and_then(|fm| fm.name) {
+        return rule_skill_name(Path::new(&name));
+    }
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let segments: Vec<&str> = path.trim_end_matches('/').rsplit('/').take(2).collect();
+    let file = segments.first().copied().unwrap_or_default();
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    let generic = stem.eq_ignore_ascii_case("skill") || stem.eq_ignore_ascii_case("readme");
+    let base = match segments.get(1) {
+        Some(parent) if generic => parent,
+        _ => stem,
+    };
+    rule_skill_name(Path::new(base))
+}
+
+/// SKILL.md text for a downloaded file: kept as is when it has frontmatter, otherwise given one
+/// with `name` and a description taken from its first paragraph.
+pub fn scaffold_skill_md(text: &str, name: &str, url: &str) -> Result<String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    if SkillFrontmatter::parse(text).is_ok() {
+        return Ok(text.to_string());
+    }
+    let paragraph = text
+        .split("\n\n")
+        .map(str::trim)
+        .find(|p| !p.is_empty() && !p.starts_with('#') && !p.starts_with("```"))
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "));
+    let description = match paragraph {
+        Some(p) if p.chars().count() > MAX_DESCRIPTION_CHARS => {
+            let cut: String = p.chars().take(MAX_DESCRIPTION_CHARS - 3).collect();
+            format!("{}...", cut.trim_end())
+        }
+        Some(p) => p,
+        None => format!("Imported from {}", url),
+    };
+    let frontmatter = serde_yaml::to_string(&SynthesizedFrontmatter { name, description })?;
+    Ok(format!(
+        "---\n{}---\n\n{}\n",
+        frontmatter,
+        text.trim_matches('\n')
+    ))
+}
+
+/// Write the skill directory for a downloaded file at `dest`.
+pub fn write_url_skill(text: &str, name: &str, url: &str, dest: &Path) -> Result<()> {
+    let skill_md = scaffold_skill_md(text, name, url)?;
+    std::fs::create_dir_all(dest).with_context(|| format!("create {:?}", dest))?;
+    std::fs::write(dest.join("SKILL.md"), skill_md)
+        .with_context(|| format!("write {:?}", dest.join("SKILL.md")))?;
+    Ok(())
+}
+
+/// Download a markdown file and install it as a skill managed with `source_type = "url"`.
+pub fn install_url_skill<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    store: &SkillStore,
+    url: &str,
+    name: Option<String>,
+    options: InstallOptions,
+) -> Result<InstallResult> {
+    let url = url.trim();
+    let UrlFetch::Fetched { text, revision } = fetch_url(store, url, None)? else {
+        anyhow::bail!("{} answered an unconditional request with 304", url);
+    };
+    let name = name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| url_skill_name(url, &text));
+    let scratch = std::env::temp_dir().join(format!("skills-hub-url-{}", Uuid::new_v4()));
+    let staging = scratch.join(&name);
+    let result = write_url_skill(&text, &name, url, &staging).and_then(|()| {
+        install_converted_skill(
+            app,
+            store,
+            &staging,
+            name,
+            (URL_SOURCE.to_string(), Some(url.to_string()), revision),
+            options,
+        )
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+#[cfg(test)]
+#[path = "tests/url_skill.rs"]
+mod tests;
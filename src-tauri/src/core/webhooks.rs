@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+
+use super::environments::with_proxy;
+use super::git_host_policy::clone_url_host;
+use super::skill_store::{SkillRecord, SkillStore};
+
+pub const WEBHOOK_URLS_KEY: &str = "webhook_urls";
+/// A slow receiver must not hold on to the delivery thread.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum WebhookEventKind {
+    #[serde(rename = "skill.installed")]
+    Installed,
+    #[serde(rename = "skill.updated")]
+    Updated,
+    #[serde(rename = "sync.failed")]
+    SyncFailed,
+}
+
+/// JSON body POSTed to every webhook URL. `text` is a one-line summary, so Slack and similar
+/// incoming webhooks show something readable without a custom template.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub text: String,
+    pub timestamp: i64,
+    pub skill_id: String,
+    pub skill: String,
+    pub source_type: Option<String>,
+    pub source_ref: Option<String>,
+    pub revision: Option<String>,
+    pub tool: Option<String>,
+    pub error: Option<String>,
+}
+
+impl WebhookEvent {
+    fn for_skill(event: WebhookEventKind, text: String, record: &SkillRecord) -> Self {
+        Self {
+            event,
+            text,
+            timestamp: now_ms(),
+            skill_id: record.id.clone(),
+            skill: record.name.clone(),
+            source_type: Some(record.source_type.clone()),
+            source_ref: record.source_ref.clone(),
+            revision: record.source_revision.clone(),
+            tool: None,
+            error: None,
+        }
+    }
+
+    pub fn installed(record: &SkillRecord) -> Self {
+        let text = match &record.source_ref {
+            Some(source) => format!("Skills Hub: installed {} from {}", record.name, source),
+            None => format!("Skills Hub: installed {}", record.name),
+        };
+        Self::for_skill(WebhookEventKind::Installed, text, record)
+    }
+
+    pub fn updated(record: &SkillRecord) -> Self {
+        let text = match &record.source_revision {
+            Some(revision) => format!(
+                "Skills Hub: updated {} to {}",
+                record.name,
+                revision.chars().take(12).collect::<String>()
+            ),
+            None => format!("Skills Hub: updated {}", record.name),
+        };
+        Self::for_skill(WebhookEventKind::Updated, text, record)
+    }
+
+    pub fn sync_failed(skill_id: &str, skill: &str, tool: &str, error: &str) -> Self {
+        Self {
+            event: WebhookEventKind::SyncFailed,
+            text: format!(
+                "Skills Hub: syncing {} to {} failed: {}",
+                skill, tool, error
+            ),
+            timestamp: now_ms(),
+            skill_id: skill_id.to_string(),
+            skill: skill.to_string(),
+            source_type: None,
+            source_ref: None,
+            revision: None,
+            tool: Some(tool.to_string()),
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+pub fn get_webhook_urls(store: &SkillStore) -> Vec<String> {
+    store
+        .get_setting(WEBHOOK_URLS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the URLs trimmed and deduplicated. Only `http(s)://` URLs with a host are accepted;
+/// an empty list turns notifications off.
+pub fn set_webhook_urls(store: &SkillStore, urls: Vec<String>) -> Result<Vec<String>> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for url in urls {
+        let url = url.trim().to_string();
+        if url.is_empty() {
+            continue;
+        }
+        let scheme = url
+            .split_once("://")
+            .map(|(scheme, _)| scheme.to_ascii_lowercase());
+        if !matches!(scheme.as_deref(), Some("http" | "https")) || clone_url_host(&url).is_none() {
+            anyhow::bail!("invalid webhook URL: {}", url);
+        }
+        if !cleaned.contains(&url) {
+            cleaned.push(url);
+        }
+    }
+    store.set_setting(WEBHOOK_URLS_KEY, &serde_json::to_string(&cleaned)?)?;
+    Ok(cleaned)
+}
+
+/// Fire `event` at every configured URL on a background thread. Delivery failures are logged;
+/// the install, update or sync that raised the event is never held up or failed by them.
+pub fn notify_webhooks(store: &SkillStore, event: WebhookEvent) {
+    let urls = get_webhook_urls(store);
+    if urls.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || deliver(&urls, &event));
+}
+
+/// POST `event` to each URL in turn; returns how many accepted it.
+fn deliver(urls: &[String], event: &WebhookEvent) -> usize {
+    let client = match with_proxy(Client::builder().timeout(DELIVERY_TIMEOUT))
+        .and_then(|builder| builder.build())
+    {
+        Ok(client) => client,
+        Err(err) => {
+            log::warn!("[webhooks] client setup failed: {:#}", err);
+            return 0;
+        }
+    };
+    let mut delivered = 0;
+    for url in urls {
+        match post_event(&client, url, event) {
+            Ok(()) => delivered += 1,
+            // URLs often embed a secret token, so only the host is logged.
+            Err(err) => log::warn!(
+                "[webhooks] delivery to {} failed: {:#}",
+                clone_url_host(url).unwrap_or_default(),
+                err
+            ),
+        }
+    }
+    delivered
+}
+
+fn post_event(client: &Client, url: &str, event: &WebhookEvent) -> Result<()> {
+    client
+        .post(url)
+        .header("User-Agent", "skills-hub")
+        .json(event)
+        .send()
+        .context("webhook request failed")?
+        .error_for_status()
+        .context("webhook returned error")?;
+    Ok(())
+}
+
+fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
+
+#[cfg(test)]
+#[path = "tests/webhooks.rs"]
+mod tests;
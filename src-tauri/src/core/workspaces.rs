@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::machine_overrides::MachineOverrides;
+use super::project_config::{
+    detect_project_tools, sync_skill_to_project, unsync_skill_from_project, ProjectApplyFailure,
+};
+use super::skill_executables::ensure_sync_allowed;
+use super::skill_store::{ProjectTargetRecord, SkillProjection, SkillStore, WorkspaceRecord};
+use super::tool_adapters::adapter_by_key;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WorkspaceSkill {
+    pub skill_id: String,
+    pub name: String,
+    /// Tools the skill is synced to inside the workspace.
+    pub tools: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WorkspaceView {
+    #[serde(flatten)]
+    pub workspace: WorkspaceRecord,
+    /// False once the directory is gone; the registration is kept until removed.
+    pub exists: bool,
+    pub skills: Vec<WorkspaceSkill>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct WorkspaceProfileReport {
+    pub synced: Vec<ProjectTargetRecord>,
+    pub removed: Vec<ProjectTargetRecord>,
+    pub failed: Vec<ProjectApplyFailure>,
+}
+
+/// Register a project directory. `name` defaults to the directory name; a directory can be
+/// registered once.
+pub fn add_workspace(
+    store: &SkillStore,
+    path: &Path,
+    name: Option<String>,
+    now: i64,
+) -> Result<WorkspaceRecord> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", path))?;
+    if !path.is_dir() {
+        anyhow::bail!("not a directory: {:?}", path);
+    }
+    let key = path.to_string_lossy().to_string();
+    if store.list_workspaces()?.iter().any(|w| w.path == key) {
+        anyhow::bail!("{:?} is already a workspace", path);
+    }
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .or_else(|| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| key.clone());
+    let record = WorkspaceRecord {
+        id: Uuid::new_v4().to_string(),
+        name,
+        path: key,
+        created_at: now,
+    };
+    store.insert_workspace(&record)?;
+    Ok(record)
+}
+
+/// Registered workspaces with the skills synced into each.
+pub fn list_workspaces(store: &SkillStore) -> Result<Vec<WorkspaceView>> {
+    let names: BTreeMap<String, String> = store
+        .list_skills_with(SkillProjection::Summary)?
+        .into_iter()
+        .map(|skill| (skill.id, skill.name))
+        .collect();
+    let mut views = Vec::new();
+    for workspace in store.list_workspaces()? {
+        let mut skills: BTreeMap<String, WorkspaceSkill> = BTreeMap::new();
+        for target in store.list_project_targets(&workspace.path)? {
+            let entry = skills
+                .entry(target.skill_id.clone())
+                .or_insert_with(|| WorkspaceSkill {
+                    name: names.get(&target.skill_id).cloned().unwrap_or_default(),
+                    skill_id: target.skill_id.clone(),
+                    tools: Vec::new(),
+                });
+            entry.tools.push(target.tool);
+        }
+        let mut skills: Vec<WorkspaceSkill> = skills.into_values().collect();
+        skills.sort_by(|a, b| a.name.cmp(&b.name));
+        views.push(WorkspaceView {
+            exists: Path::new(&workspace.path).is_dir(),
+            workspace,
+            skills,
+        });
+    }
+    Ok(views)
+}
+
+/// Make the workspace's tool dirs hold exactly the profile's skills: members missing for a
+/// tool are synced into the project, other skills synced there are removed. `tools` defaults
+/// to those whose directory exists in the project. Members this machine skips for a tool are
+/// left as they are. A skill that fails is reported and the rest still go on.
+pub fn apply_profile_to_workspace(
+    store: &SkillStore,
+    workspace_id: &str,
+    profile_id: &str,
+    tools: &[String],
+    allow_executables: bool,
+    now: i64,
+) -> Result<WorkspaceProfileReport> {
+    let workspace = store
+        .list_workspaces()?
+        .into_iter()
+        .find(|w| w.id == workspace_id)
+        .ok_or_else(|| anyhow::anyhow!("workspace not found"))?;
+    let profile = store
+        .get_profile(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("profile not found"))?;
+    let project = Path::new(&workspace.path);
+    if !project.is_dir() {
+        anyhow::bail!("workspace directory not found: {:?}", project);
+    }
+    let tools = if tools.is_empty() {
+        detect_project_tools(project)
+    } else {
+        tools.to_vec()
+    };
+    if tools.is_empty() {
+        anyhow::bail!("no tools given and the workspace has no tool directories");
+    }
+    if let Some(tool) = tools.iter().find(|tool| adapter_by_key(tool).is_none()) {
+        anyhow::bail!("unknown tool: {}", tool);
+    }
+
+    let targets = store.list_project_targets(&workspace.path)?;
+    let overrides = MachineOverrides::load(store)?;
+    let mut report = WorkspaceProfileReport::default();
+    for skill in store.list_skills_with(SkillProjection::Summary)? {
+        let member = profile.skill_ids.contains(&skill.id);
+        for tool in &tools {
+            if member && overrides.skips_tool(&skill.name, tool) {
+                continue;
+            }
+            let synced = targets.iter().any(|t| {
+                t.skill_id == skill.id
+                    && &t.tool == tool
+                    && std::fs::symlink_metadata(&t.target_path).is_ok()
+            });
+            let result = match (member, synced) {
+                (true, false) => ensure_sync_allowed(store, &skill, allow_executables)
+                    .and_then(|()| sync_skill_to_project(store, &skill, project, tool, now))
+                    .map(|record| report.synced.push(record)),
+                (false, _) => unsync_skill_from_project(store, &skill.id, project, tool)
+                    .map(|removed| report.removed.extend(removed)),
+                (true, true) => Ok(()),
+            };
+            if let Err(err) = result {
+                report.failed.push(ProjectApplyFailure {
+                    skill: skill.name.clone(),
+                    error: format!("{}: {:#}", tool, err),
+                });
+            }
+        }
+    }
+    log::info!(
+        "[workspaces] profile {:?} applied to {:?}: {} synced, {} removed, {} failed",
+        profile.name,
+        workspace.path,
+        report.synced.len(),
+        report.removed.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "tests/workspaces.rs"]
+mod tests;
@@ -2,32 +2,116 @@ mod commands;
 mod core;
 
 use core::skill_store::{default_db_path, migrate_legacy_db_if_needed, SkillStore};
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_log::{Target, TargetKind};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first: a second launch (e.g. from a skillshub:// link) forwards its
+        // URL to the running app through the deep-link plugin and then exits.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            core::background_mode::show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .setup(|app| {
-            app.handle().plugin(
-                tauri_plugin_log::Builder::default()
-                    .level(log::LevelFilter::Info)
-                    .targets([
-                        Target::new(TargetKind::LogDir { file_name: None }),
-                        #[cfg(desktop)]
-                        Target::new(TargetKind::Stdout),
-                    ])
-                    .build(),
-            )?;
+            // The plugin's logger is installed behind a redacting wrapper rather than directly.
+            let (log_plugin, max_level, logger) = tauri_plugin_log::Builder::default()
+                .level(log::LevelFilter::Info)
+                .targets([
+                    Target::new(TargetKind::LogDir { file_name: None }),
+                    #[cfg(desktop)]
+                    Target::new(TargetKind::Stdout),
+                ])
+                .split(app.handle())?;
+            app.handle().plugin(log_plugin)?;
+            core::log_redact::install(logger, max_level).map_err(tauri::Error::from)?;
 
             let db_path = default_db_path(app.handle()).map_err(tauri::Error::from)?;
             migrate_legacy_db_if_needed(&db_path).map_err(tauri::Error::from)?;
-            let store = SkillStore::new(db_path);
+            let db_key_path =
+                core::db_encryption::db_key_path(app.handle()).map_err(tauri::Error::from)?;
+            let db_key = core::db_encryption::DbKey::load_or_create(&db_key_path)
+                .map_err(tauri::Error::from)?;
+            #[cfg(feature = "sqlcipher")]
+            if core::db_encryption::encrypt_db_if_needed(&db_path, &db_key)
+                .map_err(tauri::Error::from)?
+            {
+                log::info!("[store] encrypted the database with SQLCipher");
+            }
+            let handle = app.handle().clone();
+            let store = SkillStore::new(db_path)
+                .with_db_key(db_key)
+                .with_change_listener(move |change| {
+                    if let Err(err) = handle.emit(core::skill_store::SKILLS_CHANGED_EVENT, change) {
+                        log::warn!("[store] failed to emit change event: {}", err);
+                    }
+                });
             store.ensure_schema().map_err(tauri::Error::from)?;
+            if let Err(err) = core::db_encryption::encrypt_credential_settings(&store) {
+                log::warn!("[store] failed to encrypt credential settings: {:#}", err);
+            }
+            core::locale::load_backend_locale(&store);
+            core::content_hash::load_hash_max_file_mb(&store);
+            core::environments::load_proxy_url(&store);
             app.manage(store.clone());
+            // The window starts hidden (see tauri.conf.json) and stays so when the login item
+            // launched the app in background mode.
+            let launch = core::background_mode::BackgroundLaunch(
+                core::background_mode::launched_in_background(std::env::args()),
+            );
+            app.manage(launch);
+            if !launch.0 {
+                core::background_mode::show_main_window(app.handle());
+            }
+            let operations = core::operations::OperationRegistry::default();
+            app.manage(operations.clone());
+            let self_repair = core::self_repair::SelfRepairState::default();
+            app.manage(self_repair.clone());
+            let local_api = commands::local_api::LocalApiState::default();
+            app.manage(local_api.clone());
+            commands::local_api::start_if_enabled(app.handle(), &store, &local_api);
+
+            // skillshub://install links, both the one the app was launched with and later ones.
+            let deep_links = core::deep_link::DeepLinkQueue::default();
+            app.manage(deep_links.clone());
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(err) = app.deep_link().register_all() {
+                log::warn!("[deep-link] failed to register url scheme: {}", err);
+            }
+            let handle = app.handle().clone();
+            let queue = deep_links.clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    core::deep_link::handle_deep_link(&handle, &queue, url.as_str());
+                }
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    core::deep_link::handle_deep_link(app.handle(), &deep_links, url.as_str());
+                }
+            }
+
+            // Copied GitHub links, when the user opted in to watching the clipboard.
+            let clipboard = core::clipboard::ClipboardWatcher::default();
+            clipboard.start_if_enabled(app.handle(), &store);
+            app.manage(clipboard);
+
+            // Stale git caches are removed later, once the app has settled.
+            core::cache_cleanup::schedule_startup_cleanup(
+                app.handle().clone(),
+                store.clone(),
+                operations.clone(),
+            );
+            // Periodic jobs: team profile checks, setup snapshots, git cache cleanup.
+            let scheduler = core::scheduler::Scheduler::default();
+            core::scheduler::register_builtin_jobs(&scheduler, app.handle(), &store);
+            scheduler.start(store.clone());
+            app.manage(scheduler);
 
             // Best-effort cleanup of our own old git temp directories.
             // Safety:
@@ -46,49 +130,205 @@ pub fn run() {
                     log::info!("cleaned up {} old git temp dirs", removed);
                 }
 
-                let cleanup_days =
-                    core::cache_cleanup::get_git_cache_cleanup_days(&store_for_cleanup);
-                if cleanup_days > 0 {
-                    let max_age =
-                        std::time::Duration::from_secs(cleanup_days as u64 * 24 * 60 * 60);
-                    let removed =
-                        core::cache_cleanup::cleanup_git_cache_dirs(&handle, max_age).unwrap_or(0);
-                    if removed > 0 {
-                        log::info!("cleaned up {} git cache dirs", removed);
-                    }
-                }
+                // Flag skills and targets whose files disappeared while the app was closed.
+                core::self_repair::run_startup_self_repair(
+                    &handle,
+                    &store_for_cleanup,
+                    &self_repair,
+                );
+                // Fill in hashes skipped at install time or cleared by a migration.
+                core::hash_worker::queue_missing_hashes(&store_for_cleanup);
             });
 
             Ok(())
         })
+        // In background mode closing the window hides it, so scheduled jobs keep running.
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                let store = window.state::<SkillStore>();
+                if core::background_mode::background_mode_enabled(&store) {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             commands::get_central_repo_path,
             commands::set_central_repo_path,
+            commands::move_central_repo,
+            commands::list_central_repos,
+            commands::add_central_repo,
+            commands::remove_central_repo,
+            commands::set_install_repo,
+            commands::move_skill_to_repo,
+            commands::scan_central_repo,
+            commands::adopt_orphan,
             commands::get_tool_status,
             commands::get_git_cache_cleanup_days,
             commands::get_git_cache_ttl_secs,
             commands::set_git_cache_cleanup_days,
+            commands::get_git_cache_cleanup_delay_secs,
+            commands::set_git_cache_cleanup_delay_secs,
             commands::set_git_cache_ttl_secs,
             commands::clear_git_cache_now,
             commands::get_onboarding_plan,
+            commands::execute_onboarding_plan,
+            commands::diff_onboarding_variants,
+            commands::scan_tools_now,
+            commands::get_project_roots,
+            commands::set_project_roots,
             commands::install_local,
             commands::list_local_skills_cmd,
+            commands::validate_drop_paths,
             commands::install_local_selection,
             commands::install_git,
             commands::list_git_skills_cmd,
+            commands::list_git_branches,
+            commands::list_git_tags,
             commands::install_git_selection,
+            commands::start_install_local,
+            commands::start_install_git,
+            commands::start_install_git_selection,
+            commands::start_update_managed_skill,
+            commands::start_sync_skill_to_tools,
+            commands::get_operation_status,
+            commands::list_operations,
+            commands::batch::run_batch,
+            commands::take_pending_deep_links,
+            commands::parse_clipboard_for_install,
+            commands::get_clipboard_watch,
+            commands::set_clipboard_watch,
+            commands::take_self_repair_summary,
+            commands::lint_skill,
             commands::sync_skill_dir,
             commands::sync_skill_to_tool,
             commands::unsync_skill_from_tool,
+            commands::set_skill_target_enabled,
             commands::update_managed_skill,
+            commands::rollback_update,
+            commands::update_skill_metadata,
+            commands::write_skill_content,
+            commands::list_skill_files,
+            commands::grep_skills,
+            commands::reveal_skill,
+            commands::get_app_stats,
+            commands::get_largest_skills,
+            commands::list_scheduled_jobs,
+            commands::get_job_history,
+            commands::run_scheduled_job,
+            commands::set_job_schedule,
+            commands::quick_sync_all,
+            commands::quick_check_updates,
+            commands::get_tray_summary,
+            commands::get_background_mode,
+            commands::set_background_mode,
+            commands::get_encryption_status,
+            commands::get_recent_logs,
+            commands::run_doctor,
+            commands::normalize_skill,
+            commands::migrate_to_successor,
+            commands::get_app_language,
+            commands::set_app_language,
+            commands::get_backend_locale,
+            commands::set_backend_locale,
+            commands::get_normalize_on_install,
+            commands::set_normalize_on_install,
+            commands::get_hash_max_file_mb,
+            commands::set_hash_max_file_mb,
+            commands::get_executable_policy,
+            commands::set_executable_policy,
+            commands::get_git_host_policy,
+            commands::set_git_host_policy,
+            commands::get_signing_policy,
+            commands::set_signing_policy,
+            commands::get_signing_public_key,
+            commands::sign_skill,
+            commands::export_skill,
+            commands::create_backup,
+            commands::restore_backup,
+            commands::export_manifest,
+            commands::apply_manifest,
+            commands::get_central_git_enabled,
+            commands::set_central_git_enabled,
+            commands::get_central_remote,
+            commands::set_central_remote,
+            commands::push_central_repo,
+            commands::pull_central_repo,
+            commands::scan_cloud_sync,
+            commands::import_cursor_rules,
+            commands::install_plugin_package,
+            commands::convert_skill,
+            commands::get_skill_snippet,
+            commands::get_webhook_urls,
+            commands::set_webhook_urls,
+            commands::install_url_skill,
+            commands::list_skill_templates,
+            commands::list_template_sources,
+            commands::add_template_source,
+            commands::remove_template_source,
+            commands::create_skill,
+            commands::local_api::get_local_api_status,
+            commands::local_api::set_local_api_enabled,
+            commands::local_api::regenerate_local_api_token,
+            commands::export_deployment_report,
+            commands::list_profiles,
+            commands::save_profile,
+            commands::delete_profile,
+            commands::activate_profile,
+            commands::export_profile,
+            commands::preview_profile_file,
+            commands::import_profile,
+            commands::list_team_profiles,
+            commands::track_team_profile,
+            commands::check_team_profile,
+            commands::reconcile_team_profile,
+            commands::untrack_team_profile,
+            commands::get_team_profile_check_hours,
+            commands::set_team_profile_check_hours,
+            commands::list_machine_overrides,
+            commands::set_machine_override,
+            commands::delete_machine_override,
+            commands::list_setup_snapshots,
+            commands::create_setup_snapshot,
+            commands::delete_setup_snapshot,
+            commands::diff_setup_snapshots,
+            commands::scan_project,
+            commands::apply_project_config,
+            commands::list_workspaces,
+            commands::add_workspace,
+            commands::remove_workspace,
+            commands::apply_profile_to_workspace,
+            commands::list_environments,
+            commands::save_environment,
+            commands::delete_environment,
+            commands::switch_environment,
+            commands::get_skill_changelog,
             commands::search_github,
             commands::import_existing_skill,
             commands::get_managed_skills,
             commands::delete_managed_skill,
+            commands::check_dependency_health,
+            commands::get_tool_token_totals,
+            commands::estimate_skill_tokens,
+            commands::get_context_budget,
+            commands::get_context_budget_tokens,
+            commands::set_context_budget_tokens,
+            commands::get_skill_dependencies,
+            commands::get_skill_dependents,
+            commands::install_skill_dependencies,
             commands::read_skill_content,
             commands::search_skills_registry,
             commands::install_from_registry
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Clicking the dock icon of a hidden app does not start a second instance.
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Reopen { .. } = event {
+                core::background_mode::show_main_window(app);
+            }
+            #[cfg(not(target_os = "macos"))]
+            let _ = (app, event);
+        });
 }
@@ -0,0 +1,60 @@
+import i18n from '../../i18n'
+import type { SkillsHubError } from './types'
+
+const isSkillsHubError = (err: unknown): err is SkillsHubError =>
+  typeof err === 'object' &&
+  err !== null &&
+  typeof (err as SkillsHubError).code === 'string' &&
+  typeof (err as SkillsHubError).message === 'string'
+
+// Lists (dependents, compatible tools, cleanup failures) become text for interpolation.
+const toParams = (details: SkillsHubError['details']): Record<string, string> => {
+  const params: Record<string, string> = {}
+  if (!details) return params
+  for (const [key, value] of Object.entries(details)) {
+    if (value === null || value === undefined) continue
+    params[key] = Array.isArray(value)
+      ? value.map(String).join(key === 'failures' ? '\n- ' : ', ')
+      : String(value)
+  }
+  return params
+}
+
+// Backend errors carry a stable code plus params; translate them in the UI language and fall
+// back to the backend-rendered message for codes without a translation.
+const localizeError = (err: SkillsHubError): string => {
+  const params = toParams(err.details)
+  const keys = [`errors.codes.${err.code}`]
+  if (params.reason) keys.unshift(`errors.codes.${err.code}_${params.reason}`)
+  const key = keys.find((k) => i18n.exists(k))
+  return key ? i18n.t(key, params) : err.message
+}
+
+export const getErrorMessage = (err: unknown): string => {
+  if (isSkillsHubError(err)) return localizeError(err)
+  if (err instanceof Error) return err.message
+  return String(err)
+}
+
+export const getErrorCode = (err: unknown): string | undefined =>
+  isSkillsHubError(err) ? err.code : undefined
+
+// Codes for content the user may still accept: secrets found on install/update, scripts or
+// binaries on sync. The backend checks them last, so running again with `confirmed` set (as
+// `force` or `allowExecutables`) only skips that check.
+const CONFIRMATION_KEYS: Record<string, string> = {
+  SECRETS_FOUND: 'errors.confirmSecrets',
+  EXECUTABLES_UNCONFIRMED: 'errors.confirmExecutables',
+}
+
+export const withConfirmation = async <T>(run: (confirmed: boolean) => Promise<T>): Promise<T> => {
+  try {
+    return await run(false)
+  } catch (err) {
+    const key = CONFIRMATION_KEYS[getErrorCode(err) ?? '']
+    if (!key) throw err
+    const message = getErrorMessage(err)
+    if (!window.confirm(i18n.t(key, { message }))) throw err
+    return run(true)
+  }
+}
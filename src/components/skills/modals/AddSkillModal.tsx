@@ -6,13 +6,18 @@ import { useAppStore } from '../../../stores/useAppStore'
 import { useSkillStore } from '../../../stores/useSkillStore'
 import { useSkillsManager } from '../../../hooks/useSkillsManager'
 import GitPickModal from './GitPickModal'
-import type { GitSkillCandidate } from '../types'
+import type { GitSkillCandidate, InstallResultDto } from '../types'
+import { withConfirmation, getErrorMessage, getErrorCode } from '../errors'
+import { progressMessage, runOperation } from '../operations'
 
 type AddSkillModalProps = {
   onClose: () => void
+  // Pre-filled from a skillshub://install link; installing still needs the user's confirmation.
+  initialGitUrl?: string
+  initialSubpath?: string | null
 }
 
-const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
+const AddSkillModal = ({ onClose, initialGitUrl, initialSubpath }: AddSkillModalProps) => {
   const { t } = useTranslation()
   const { tools, installedToolIds, status: toolStatus } = useToolStore()
   const { isLoading, setLoading, setSuccess, setError } = useAppStore()
@@ -23,7 +28,8 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
   const [activeTab, setActiveTab] = useState<'local' | 'git'>('git')
   const [localPath, setLocalPath] = useState('')
   const [localName, setLocalName] = useState('')
-  const [gitUrl, setGitUrl] = useState('')
+  const [gitUrl, setGitUrl] = useState(initialGitUrl ?? '')
+  const [gitSubpath, setGitSubpath] = useState(initialSubpath ?? '')
   const [gitName, setGitName] = useState('')
   const [syncTargets, setSyncTargets] = useState<Record<string, boolean>>({})
 
@@ -80,7 +86,14 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
           return
         }
 
-        if (candidates.length === 1) {
+        if (gitSubpath) {
+          const match = candidates.find(c => c.subpath.replace(/\/+$/, '') === gitSubpath)
+          if (!match) {
+            setError(t('errors.deepLinkSkillNotFound', { path: gitSubpath }))
+            return
+          }
+          await installGitSkills(url, [match])
+        } else if (candidates.length === 1) {
           // Single skill - check if already exists
           const existingNames = new Set(skills.map(s => s.name.toLowerCase()))
           if (existingNames.has(candidates[0].name.toLowerCase())) {
@@ -107,8 +120,8 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
           setShowGitPick(true)
         }
       } catch (err) {
-        const msg = err instanceof Error ? err.message : String(err)
-        if (msg.includes('MULTI_SKILLS')) {
+        const msg = getErrorMessage(err)
+        if (getErrorCode(err) === 'MULTI_SKILLS') {
           setError(t('errors.multiSkillsRepo'))
         } else {
           setError(msg)
@@ -124,18 +137,25 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
     try {
       for (let i = 0; i < skillsToInstall.length; i++) {
         const skill = skillsToInstall[i]
-        setLoading(true, t('actions.importStep', { index: i + 1, total: skillsToInstall.length, name: skill.name }))
+        const message = t('actions.importStep', {
+          index: i + 1,
+          total: skillsToInstall.length,
+          name: skill.name
+        })
+        setLoading(true, message)
 
         // When installing multiple skills, use each skill's own name
         // Only use custom gitName when installing a single skill
         const finalName = skillsToInstall.length === 1 && gitName.trim() ? gitName.trim() : skill.name
 
         // Install using the selection command
-        const result = await invoke<any>('install_git_selection', {
-          repoUrl,
-          subpath: skill.subpath,
-          name: finalName
-        })
+        const result = await withConfirmation((force) =>
+          runOperation<InstallResultDto>(
+            'start_install_git_selection',
+            { repoUrl, subpath: skill.subpath, name: finalName, force },
+            (status) => setLoading(true, progressMessage(message, status))
+          )
+        )
 
         // Sync to selected tools
         const targets = Object.keys(syncTargets).filter(id => syncTargets[id] && installedToolIds.includes(id))
@@ -146,7 +166,7 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
       fetchSkills()
       onClose()
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(getErrorMessage(err))
     } finally {
       setLoading(false)
       setShowGitPick(false)
@@ -241,8 +261,14 @@ const AddSkillModal = ({ onClose }: AddSkillModalProps) => {
                     className="input"
                     placeholder={t('gitUrlPlaceholder')}
                     value={gitUrl}
-                    onChange={(e) => setGitUrl(e.target.value)}
+                    onChange={(e) => {
+                      setGitUrl(e.target.value)
+                      setGitSubpath('')
+                    }}
                   />
+                  {gitSubpath ? (
+                    <div className="helper-text">{t('deepLinkSubpath', { path: gitSubpath })}</div>
+                  ) : null}
                 </div>
                 <div className="form-group">
                   <label className="label">{t('optionalNamePlaceholder')}</label>
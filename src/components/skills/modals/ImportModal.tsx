@@ -4,7 +4,9 @@ import { useTranslation } from 'react-i18next'
 import { invoke } from '@tauri-apps/api/core'
 import { useAppStore } from '../../../stores/useAppStore'
 import { useSkillsManager } from '../../../hooks/useSkillsManager'
-import type { OnboardingPlan } from '../types'
+import type { InstallResultDto, OnboardingPlan } from '../types'
+import { withConfirmation, getErrorMessage } from '../errors'
+import { progressMessage, runOperation } from '../operations'
 
 type ImportModalProps = {
   onClose: () => void
@@ -43,7 +45,7 @@ const ImportModal = ({ onClose, onFinish }: ImportModalProps) => {
         setVariantChoice(defaultChoice)
         setLoading(false)
       } catch (err) {
-        setError(err instanceof Error ? err.message : String(err))
+        setError(getErrorMessage(err))
       }
     }
     load()
@@ -91,18 +93,22 @@ const ImportModal = ({ onClose, onFinish }: ImportModalProps) => {
 
         const sourcePath = selectedPath
 
-        setLoading(true, t('actions.importingStep', {
+        const message = t('actions.importingStep', {
           current,
           total,
           name: group.name
-        }))
+        })
+        setLoading(true, message)
 
         // 1. Install/Import the skill
-        // We use install_local command.
-        const installRes = await invoke<any>('install_local', {
-          sourcePath,
-          name: group.name
-        })
+        // We use the start_install_local operation so copy progress shows up.
+        const installRes = await withConfirmation((force) =>
+          runOperation<InstallResultDto>(
+            'start_install_local',
+            { sourcePath, name: group.name, force },
+            (status) => setLoading(true, progressMessage(message, status))
+          )
+        )
 
         // 2. Sync to other tools if needed
         // If the variant was found in tool A, B, C, we might want to sync back to them
@@ -124,7 +130,7 @@ const ImportModal = ({ onClose, onFinish }: ImportModalProps) => {
       onFinish()
       onClose()
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(getErrorMessage(err))
     } finally {
       setLoading(false)
     }
@@ -1,6 +1,9 @@
 import { memo } from 'react'
 import { useTranslation } from 'react-i18next'
+import { invoke } from '@tauri-apps/api/core'
 import { useAppStore } from '../../../stores/useAppStore'
+import { getErrorMessage } from '../errors'
+import type { RecentLogs } from '../types'
 
 type SettingsModalProps = {
   onClose: () => void
@@ -8,7 +11,17 @@ type SettingsModalProps = {
 
 const SettingsModal = ({ onClose }: SettingsModalProps) => {
   const { t } = useTranslation()
-  const { theme, setTheme, language, setLanguage } = useAppStore()
+  const { theme, setTheme, language, setLanguage, setSuccess, setError } = useAppStore()
+
+  const handleCopyLogs = async () => {
+    try {
+      const logs = await invoke<RecentLogs>('get_recent_logs', { lines: 500 })
+      await navigator.clipboard.writeText(`${logs.path}\n\n${logs.lines.join('\n')}`)
+      setSuccess(t('status.logsCopied'))
+    } catch (err) {
+      setError(getErrorMessage(err))
+    }
+  }
 
   return (
     <div className="modal-backdrop" onClick={onClose}>
@@ -49,6 +62,13 @@ const SettingsModal = ({ onClose }: SettingsModalProps) => {
               >中文</button>
             </div>
           </div>
+
+          <div className="form-group">
+            <label className="label">{t('diagnostics')}</label>
+            <button className="btn btn-secondary" onClick={handleCopyLogs}>
+              {t('copyRecentLogs')}
+            </button>
+          </div>
         </div>
       </div>
     </div>
@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react'
 import { X, Github, Folder, Box, ExternalLink, Copy, Check, FileText } from 'lucide-react'
 import type { TFunction } from 'i18next'
-import type { ManagedSkill, ToolOption } from '../types'
+import type { ManagedSkill, SkillFileContent, ToolOption } from '../types'
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'sonner'
 import ReactMarkdown from 'react-markdown'
@@ -41,10 +41,10 @@ const SkillDetailModal = ({
                 return
             }
             try {
-                const content = await invoke<string>('read_skill_content', {
-                    centralPath: skill.central_path
+                const file = await invoke<SkillFileContent>('read_skill_content', {
+                    skillId: skill.id
                 })
-                setSkillContent(content)
+                setSkillContent(file.content)
             } catch (err) {
                 console.error('Failed to load skill content:', err)
             } finally {
@@ -52,7 +52,7 @@ const SkillDetailModal = ({
             }
         }
         loadContent()
-    }, [skill.central_path])
+    }, [skill.id, skill.central_path])
 
     const handleCopyPath = async () => {
         const path = skill.central_path || skill.source_ref || ''
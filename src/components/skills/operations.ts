@@ -0,0 +1,47 @@
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import i18n from '../../i18n'
+import type { OperationStatus } from './types'
+
+// Start a `start_*` command and resolve with its result once the backend reports it finished.
+// Listeners are attached before the command runs so a fast operation cannot finish unseen.
+export const runOperation = async <T>(
+  command: string,
+  args: Record<string, unknown>,
+  onProgress?: (status: OperationStatus) => void,
+): Promise<T> => {
+  let operationId: string | null = null
+  const finishedBeforeId: OperationStatus[] = []
+  let settle: (status: OperationStatus) => void = () => {}
+  const finished = new Promise<OperationStatus>((resolve) => {
+    settle = resolve
+  })
+
+  const unlistenProgress = await listen<OperationStatus>('operation-progress', (event) => {
+    if (event.payload.id === operationId) onProgress?.(event.payload)
+  })
+  const unlistenFinished = await listen<OperationStatus>('operation-finished', (event) => {
+    if (operationId === null) finishedBeforeId.push(event.payload)
+    else if (event.payload.id === operationId) settle(event.payload)
+  })
+
+  try {
+    operationId = await invoke<string>(command, args)
+    const early = finishedBeforeId.find((status) => status.id === operationId)
+    if (early) settle(early)
+    const status = await finished
+    if (status.state === 'failed') throw status.error
+    return status.result as T
+  } finally {
+    unlistenProgress()
+    unlistenFinished()
+  }
+}
+
+// Loading text for a progress update, e.g. "Updating foo ... Cloning 45%".
+export const progressMessage = (base: string, status: OperationStatus): string => {
+  const key = `actions.stages.${status.stage}`
+  if (!status.stage || !i18n.exists(key)) return base
+  const percent = status.total ? ` ${Math.floor((status.current / status.total) * 100)}%` : ''
+  return `${base} ${i18n.t(key)}${percent}`
+}
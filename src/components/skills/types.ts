@@ -43,6 +43,8 @@ export type ManagedSkill = {
   updated_at: number
   last_sync_at?: number | null
   status: string
+  // Scripts or binaries ship with the skill; syncing it may need confirmation.
+  contains_executables?: boolean | null
   targets: {
     tool: string
     mode: string
@@ -74,6 +76,19 @@ export type InstallResultDto = {
   content_hash?: string | null
 }
 
+export type OperationStatus = {
+  id: string
+  kind: string
+  state: 'running' | 'succeeded' | 'failed'
+  stage?: string | null
+  current: number
+  total?: number | null
+  result?: unknown
+  error?: unknown
+  started_at: number
+  finished_at?: number | null
+}
+
 export type ToolInfoDto = {
   key: string
   label: string
@@ -113,3 +128,120 @@ export type RegistrySkill = {
   install_cmd: string
 }
 
+
+export type SkillsHubError = {
+  code: string
+  message: string
+  details?: Record<string, unknown> | null
+}
+
+export type SkillFileContent = {
+  path: string
+  size: number
+  offset: number
+  length: number
+  is_binary: boolean
+  encoding: 'utf8' | 'base64'
+  content: string
+  next_offset?: number | null
+}
+
+export type DeepLinkInstall = {
+  repo_url: string
+  subpath?: string | null
+}
+
+export type DropPathInfo = {
+  path: string
+  kind: 'single_skill' | 'multi_skill_repo' | 'git_clone' | 'invalid'
+  skill_count: number
+  origin_url?: string | null
+  reason?: string | null
+}
+
+export type RecentLogs = {
+  path: string
+  lines: string[]
+}
+
+export type DoctorCheck = {
+  category: 'database' | 'central_repo' | 'tools' | 'targets' | 'network' | 'cache'
+  name: string
+  status: 'ok' | 'warn' | 'fail'
+  message: string
+  fix?: string | null
+}
+
+export type DoctorReport = {
+  status: DoctorCheck['status']
+  checks: DoctorCheck[]
+}
+
+export type OnConflict = 'fail' | 'adopt' | 'replace' | 'install_as_new_name'
+
+export type BatchSkillRef = { id: string } | { step: number }
+
+export type BatchStep =
+  | {
+      op: 'install_local'
+      source_path: string
+      name?: string | null
+      force?: boolean
+      on_conflict?: OnConflict
+    }
+  | {
+      op: 'install_git'
+      repo_url: string
+      name?: string | null
+      force?: boolean
+      on_conflict?: OnConflict
+    }
+  | {
+      op: 'install_git_selection'
+      repo_url: string
+      subpath: string
+      name?: string | null
+      force?: boolean
+      on_conflict?: OnConflict
+    }
+  | {
+      op: 'sync_to_tool'
+      skill: BatchSkillRef
+      tool: string
+      overwrite?: boolean
+      force?: boolean
+    }
+  | { op: 'unsync_from_tool'; skill: BatchSkillRef; tool: string }
+  | { op: 'update'; skill: BatchSkillRef; force?: boolean }
+  | { op: 'delete'; skill: BatchSkillRef; force?: boolean }
+
+export type BatchPolicy = 'stop_on_error' | 'continue' | 'rollback'
+
+export type BatchStepResult = {
+  index: number
+  op: BatchStep['op']
+  state: 'succeeded' | 'failed' | 'skipped' | 'rolled_back'
+  skill_id?: string | null
+  result?: unknown
+  error?: SkillsHubError | null
+}
+
+export type BatchResult = {
+  ok: boolean
+  steps: BatchStepResult[]
+}
+
+export type RepairIssue = {
+  skill_id: string
+  skill_name: string
+  tool?: string | null
+  path: string
+  status: 'missing' | 'unreadable' | 'broken' | 'offline'
+}
+
+export type SelfRepairSummary = {
+  checked_skills: number
+  checked_targets: number
+  issues: RepairIssue[]
+  recovered: number
+}
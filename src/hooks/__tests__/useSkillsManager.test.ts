@@ -62,13 +62,16 @@ describe('useSkillsManager', () => {
         expect(mockInvoke).toHaveBeenCalledWith('install_local_selection', {
             basePath: '/tmp/skills',
             subpath: 'skill-1',
-            name: 'My Skill'
+            name: 'My Skill',
+            force: false
         })
         expect(mockInvoke).toHaveBeenCalledWith('sync_skill_to_tool', {
             sourcePath: '/central/skill-1',
             skillId: 'skill-id-1',
             tool: 'tool-1',
-            name: 'skill-1'
+            name: 'skill-1',
+            overwrite: true,
+            allowExecutables: false
         })
 
         // Check Store state
@@ -76,6 +79,42 @@ describe('useSkillsManager', () => {
         expect(useAppStore.getState().isLoading).toBe(false)
     })
 
+    it('addLocalSkill retries with force once secrets are confirmed', async () => {
+        const mockInvoke = invoke as any
+        mockInvoke.mockImplementation((cmd: string, args: any) => {
+            if (cmd === 'list_local_skills_cmd') {
+                return Promise.resolve([{ valid: true, name: 'skill-1', subpath: 'skill-1' }])
+            }
+            if (cmd === 'install_local_selection' && !args.force) {
+                return Promise.reject({
+                    code: 'SECRETS_FOUND',
+                    message: 'skill looks like it contains credentials: aws_access_key (run.sh:1)',
+                    details: { summary: 'aws_access_key (run.sh:1)' }
+                })
+            }
+            if (cmd === 'install_local_selection') {
+                return Promise.resolve({ skill_id: 'id-1', central_path: '/central/skill-1', name: 'skill-1' })
+            }
+            return Promise.resolve()
+        })
+        const confirm = vi.spyOn(window, 'confirm').mockReturnValue(true)
+
+        const { result } = renderHook(() => useSkillsManager())
+        await act(async () => {
+            await result.current.addLocalSkill('/tmp/skills', '', {})
+        })
+
+        expect(confirm).toHaveBeenCalledTimes(1)
+        expect(mockInvoke).toHaveBeenCalledWith('install_local_selection', {
+            basePath: '/tmp/skills',
+            subpath: 'skill-1',
+            name: 'skill-1',
+            force: true
+        })
+        expect(useAppStore.getState().successMessage).toBe('status.localSkillCreated')
+        confirm.mockRestore()
+    })
+
     it('addLocalSkill should handle error', async () => {
         const mockInvoke = invoke as any
         mockInvoke.mockRejectedValue(new Error('Tauri Error'))
@@ -5,6 +5,8 @@ import { useAppStore } from '../stores/useAppStore'
 import { useToolStore } from '../stores/useToolStore'
 import { useSkillStore } from '../stores/useSkillStore'
 import type { InstallResultDto, OnboardingPlan } from '../components/skills/types'
+import { withConfirmation, getErrorMessage, getErrorCode } from '../components/skills/errors'
+import { progressMessage, runOperation } from '../components/skills/operations'
 
 export const useSkillsManager = () => {
     const { t } = useTranslation()
@@ -26,6 +28,8 @@ export const useSkillsManager = () => {
         targetToolIds: string[]
     ) => {
         const errors: { title: string; message: string }[] = []
+        // One confirmation covers every tool in this pass.
+        let allowExecutables = false
 
         for (const toolId of targetToolIds) {
             const toolLabel = tools.find(t => t.id === toolId)?.label ?? toolId
@@ -37,15 +41,19 @@ export const useSkillsManager = () => {
             }))
 
             try {
-                await invoke('sync_skill_to_tool', {
-                    sourcePath: skill.central_path,
-                    skillId: skill.skill_id,
-                    tool: toolId,
-                    name: skill.name,
-                    overwrite: true  // Allow replacing existing directories during import
+                await withConfirmation((confirmed) => {
+                    allowExecutables ||= confirmed
+                    return invoke('sync_skill_to_tool', {
+                        sourcePath: skill.central_path,
+                        skillId: skill.skill_id,
+                        tool: toolId,
+                        name: skill.name,
+                        overwrite: true,  // Allow replacing existing directories during import
+                        allowExecutables
+                    })
                 })
             } catch (err) {
-                const raw = err instanceof Error ? err.message : String(err)
+                const raw = getErrorMessage(err)
                 errors.push({
                     title: t('errors.syncFailedTitle', { name: skill.name, tool: toolLabel }),
                     message: raw
@@ -72,11 +80,14 @@ export const useSkillsManager = () => {
                 const candidate = candidates[0]
                 const finalName = name.trim() || candidate.name
 
-                const created = await invoke<InstallResultDto>('install_local_selection', {
-                    basePath,
-                    subpath: candidate.subpath,
-                    name: finalName
-                })
+                const created = await withConfirmation((force) =>
+                    invoke<InstallResultDto>('install_local_selection', {
+                        basePath,
+                        subpath: candidate.subpath,
+                        name: finalName,
+                        force
+                    })
+                )
 
                 const targets = Object.keys(syncTargets).filter(id => syncTargets[id] && installedToolIds.includes(id))
                 const errors = await syncToTools(created, targets)
@@ -93,7 +104,7 @@ export const useSkillsManager = () => {
             }
 
         } catch (err) {
-            setError(err instanceof Error ? err.message : String(err))
+            setError(getErrorMessage(err))
             return { type: 'error' }
         } finally {
             setLoading(false)
@@ -107,20 +118,25 @@ export const useSkillsManager = () => {
             setSuccess(t('status.skillRemoved'))
             fetchSkills()
         } catch (err) {
-            setError(err instanceof Error ? err.message : String(err))
+            setError(getErrorMessage(err))
         } finally {
             setLoading(false)
         }
     }, [t, setLoading, setSuccess, fetchSkills, setError])
 
     const updateSkill = useCallback(async (skillId: string, skillName: string) => {
-        setLoading(true, t('actions.updating', { name: skillName }))
+        const message = t('actions.updating', { name: skillName })
+        setLoading(true, message)
         try {
-            await invoke('update_managed_skill', { skillId })
+            await withConfirmation((force) =>
+                runOperation('start_update_managed_skill', { skillId, force }, (status) =>
+                    setLoading(true, progressMessage(message, status))
+                )
+            )
             setSuccess(t('status.skillUpdated'))
             fetchSkills()
         } catch (err) {
-            setError(err instanceof Error ? err.message : String(err))
+            setError(getErrorMessage(err))
         } finally {
             setLoading(false)
         }
@@ -139,7 +155,7 @@ export const useSkillsManager = () => {
                 setSuccess(t('status.syncDisabled'))
                 fetchSkills()
             } catch (err) {
-                setError(err instanceof Error ? err.message : String(err))
+                setError(getErrorMessage(err))
             } finally {
                 setLoading(false)
             }
@@ -147,17 +163,20 @@ export const useSkillsManager = () => {
             // Sync: add to tool
             setLoading(true, t('actions.syncing', { name: skill.name, tool: toolId }))
             try {
-                await invoke('sync_skill_to_tool', {
-                    sourcePath: skill.central_path,
-                    skillId: skill.id,
-                    tool: toolId,
-                    name: skill.name,
-                    overwrite: false
-                })
+                await withConfirmation((allowExecutables) =>
+                    invoke('sync_skill_to_tool', {
+                        sourcePath: skill.central_path,
+                        skillId: skill.id,
+                        tool: toolId,
+                        name: skill.name,
+                        overwrite: false,
+                        allowExecutables
+                    })
+                )
                 setSuccess(t('status.syncEnabled'))
                 fetchSkills()
             } catch (err) {
-                setError(err instanceof Error ? err.message : String(err))
+                setError(getErrorMessage(err))
             } finally {
                 setLoading(false)
             }
@@ -178,6 +197,7 @@ export const useSkillsManager = () => {
 
         let successCount = 0
         let errorCount = 0
+        let allowExecutables = false
 
         for (const toolId of installedToolIds) {
             const isSynced = skill.targets.some(t => t.tool === toolId)
@@ -188,12 +208,16 @@ export const useSkillsManager = () => {
 
             try {
                 if (sync) {
-                    await invoke('sync_skill_to_tool', {
-                        sourcePath: skill.central_path,
-                        skillId: skill.id,
-                        tool: toolId,
-                        name: skill.name,
-                        overwrite: true
+                    await withConfirmation((confirmed) => {
+                        allowExecutables ||= confirmed
+                        return invoke('sync_skill_to_tool', {
+                            sourcePath: skill.central_path,
+                            skillId: skill.id,
+                            tool: toolId,
+                            name: skill.name,
+                            overwrite: true,
+                            allowExecutables
+                        })
                     })
                 } else {
                     await invoke('unsync_skill_from_tool', { skillId: skill.id, tool: toolId })
@@ -240,10 +264,14 @@ export const useSkillsManager = () => {
                 // Single valid skill - install directly
                 const finalName = name.trim() || candidates[0].name
 
-                const created = await invoke<InstallResultDto>('install_git', {
-                    repoUrl: url,
-                    name: finalName
-                })
+                const created = await withConfirmation((force) =>
+                    runOperation<InstallResultDto>(
+                        'start_install_git',
+                        { repoUrl: url, name: finalName, force },
+                        (status) =>
+                            setLoading(true, progressMessage(t('actions.creatingGitSkill'), status))
+                    )
+                )
 
                 // Sync to selected tools
                 const targets = Object.keys(syncTargets).filter(id => syncTargets[id] && installedToolIds.includes(id))
@@ -262,9 +290,9 @@ export const useSkillsManager = () => {
             }
 
         } catch (err) {
-            const msg = err instanceof Error ? err.message : String(err)
+            const msg = getErrorMessage(err)
             // Handle multi-skill repo error
-            if (msg.includes('MULTI_SKILLS')) {
+            if (getErrorCode(err) === 'MULTI_SKILLS') {
                 setError(t('errors.multiSkillsRepo'))
             } else {
                 setError(msg)
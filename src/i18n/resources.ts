@@ -33,6 +33,8 @@ export const resources = {
       newSkill: 'New Skill',
       settings: 'Settings',
       language: 'Language',
+      diagnostics: 'Diagnostics',
+      copyRecentLogs: 'Copy recent logs',
       interfaceLanguage: 'Interface Language',
       themeMode: 'Appearance',
       themeOptions: {
@@ -66,6 +68,7 @@ export const resources = {
       gitImportTitle: 'Import from Git repository',
       localPathPlaceholder: 'Local folder path',
       gitUrlPlaceholder: 'Git URL',
+      deepLinkSubpath: 'Opened from a link: installs only {{path}}',
       optionalNamePlaceholder: 'Optional display name',
       create: 'Create',
       detectingTools: 'Detecting installed tools...',
@@ -167,6 +170,10 @@ export const resources = {
         targetExistsDetail:
           'Target folder already exists: {{path}}. For safety it was not overwritten.',
         toolNotInstalled: 'The selected tool is not installed. Please refresh and retry.',
+        confirmSecrets:
+          '{{message}}\n\nSynced skills end up in many tool folders and sometimes in dotfiles repos. Install anyway?',
+        confirmExecutables:
+          '{{message}}\n\nTools may run these files on your behalf. Sync it anyway?',
         noSkillsFoundInRepo: 'No skills found in this repository.',
         requireLocalPath: 'Please enter a local path.',
         requireGitUrl: 'Please enter a Git repository URL.',
@@ -178,6 +185,7 @@ export const resources = {
         noSkillsFoundGit: 'No skills found in this repository (SKILL.md not detected).',
         multiSkillsRepo: 'This repository contains multiple skills. Please provide a folder URL to a specific skill.',
         allSkillsExist: 'All skills in this repository are already installed.',
+        deepLinkSkillNotFound: 'No skill found at "{{path}}" in this repository.',
         skillAlreadyExists: 'Skill "{{name}}" already exists. Please rename it.',
         duplicateSelectedSkills:
           'Duplicate skill name "{{name}}" selected. Please select only one.',
@@ -191,6 +199,58 @@ export const resources = {
         importFailedTitle: 'Import failed: {{name}}',
         unsyncedTitle: 'Not synced: {{name}}',
         moreCount: ' (+{{count}} more)',
+        selfRepairIssues:
+          'Startup check found {{count}} problem(s) in: {{names}}{{more}}. Files were moved or deleted outside Skills Hub; re-sync, reinstall or remove them.',
+        codes: {
+          MULTI_SKILLS:
+            'This repository contains multiple skills. Copy the folder URL of one skill (e.g. GitHub /tree/<branch>/skills/<name>) and import that.',
+          TARGET_EXISTS: 'Target already exists: {{path}}',
+          TARGET_ACCESS: '{{path}} is read-only. Make it writable and sync again.',
+          TARGET_ACCESS_other_owner:
+            '{{path}} is owned by another user, probably from running a tool with sudo. Take ownership back (sudo chown -R "$USER" <dir>) and sync again.',
+          SKILL_EXISTS: 'A skill already exists in the central repo at {{path}}.',
+          INVALID_PATH: 'Path is outside the skill or source directory: {{path}}',
+          TOOL_NOT_INSTALLED: 'Tool not installed: {{tool}}',
+          HAS_DEPENDENTS: 'Other skills depend on this one: {{dependents}}',
+          TOOL_INCOMPATIBLE: 'This skill does not support {{tool}} (supports: {{compatible_tools}})',
+          SKILL_INVALID: 'Invalid skill: {{reason}}',
+          SKILL_LINT_FAILED: 'Skill failed lint: {{summary}}',
+          DEPENDENCY_CONFLICT: 'Dependency conflict: {{summary}}',
+          SECRETS_FOUND: 'This skill looks like it contains credentials: {{summary}}',
+          EXECUTABLES_UNCONFIRMED: '{{skill}} contains scripts or binaries.',
+          EXECUTABLES_BLOCKED:
+            '{{skill}} contains scripts or binaries, which the executable policy does not allow syncing.',
+          GIT_HOST_BLOCKED: 'The git host policy does not allow {{host}}.',
+          SIGNATURE_REQUIRED: '{{skill}} is not signed by a trusted key.',
+          SIGNATURE_INVALID: 'The signature of {{skill}} does not match its content.',
+          SIGNATURE_UNTRUSTED: '{{skill}} is signed by an untrusted key ({{key_id}}).',
+          SYNC_CONFLICT:
+            'The remote library changed the same files as this one. Resolve these skills in the central repo, then pull again: {{skills}}',
+          SYNC_REJECTED: 'The remote {{branch}} has newer changes. Pull before pushing.',
+          INVALID_BATCH: 'Invalid batch: {{summary}}',
+          GIT_TIMEOUT:
+            'git timed out ({{secs}}s). Check that GitHub is reachable through your network or proxy, or raise SKILLS_HUB_GIT_TIMEOUT_SECS.\n{{output}}',
+          GIT_CLI_FAILED:
+            'git failed (not falling back to the built-in git to avoid hanging). Check git, network and proxy, or set SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1.\n{{cause}}',
+          CLEANUP_FAILED:
+            'Skill removed, but some tool directories could not be cleaned:\n- {{failures}}',
+          OPERATION_IN_PROGRESS:
+            'Another operation ({{operation}}) is in progress on this skill. Try again when it finishes.',
+          GIT_FETCH_FAILED_tls:
+            'Could not fetch from GitHub: TLS/certificate verification failed.\n\nTry:\n- check whether a network/proxy intercepts HTTPS\n- on a corporate network, install the company root certificate or use a trusted proxy\n- check that `git clone https://github.com/<owner>/<repo>` works in a terminal\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED_auth:
+            'Cannot access this repository: it may be private or need authentication.\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED_not_found:
+            'Repository not found or not accessible (GitHub returned not found).\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED_dns:
+            'Could not resolve GitHub (DNS). Check your network or proxy.\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED_timeout:
+            'Connecting to GitHub timed out. Check your network or proxy.\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED_connection:
+            'Connecting to GitHub failed (refused or reset). Check your network or proxy.\n\nDetails: {{cause}}',
+          GIT_FETCH_FAILED:
+            'Could not fetch from GitHub. Check your network or proxy, or try again later.\n\nDetails: {{cause}}',
+        },
       },
       actions: {
         importExisting: 'Import {{name}} ...',
@@ -204,11 +264,18 @@ export const resources = {
         unsyncing: 'Unsync {{name}} -> {{tool}} ...',
         syncingAll: 'Syncing {{name}} to all tools...',
         unsyncingAll: 'Unsyncing {{name}} from all tools...',
+        stages: {
+          cloning: 'Cloning',
+          resolving_deltas: 'Resolving deltas',
+          checking_out: 'Checking out',
+          copying: 'Copying files',
+        },
       },
       status: {
         importCompleted: 'Import completed.',
         localSkillCreated: 'Local skill created.',
         gitSkillCreated: 'Git skill created.',
+        logsCopied: 'Recent logs copied to clipboard.',
         selectedSkillsInstalled: 'Selected skills installed.',
         skillRemoved: 'Skill removed.',
         skillUpdated: 'Skill updated successfully.',
@@ -345,6 +412,8 @@ export const resources = {
       newSkill: '新建 Skill',
       settings: '设置',
       language: '语言',
+      diagnostics: '诊断',
+      copyRecentLogs: '复制最近日志',
       interfaceLanguage: '界面语言',
       themeMode: '外观模式',
       themeOptions: {
@@ -378,6 +447,7 @@ export const resources = {
       gitImportTitle: 'Git 仓库导入',
       localPathPlaceholder: '本地目录路径',
       gitUrlPlaceholder: 'Git URL',
+      deepLinkSubpath: '通过链接打开：仅安装 {{path}}',
       optionalNamePlaceholder: '可选：显示名称',
       create: '创建',
       detectingTools: '检测已安装工具中...',
@@ -474,6 +544,10 @@ export const resources = {
         targetExistsDetail:
           '目标目录已存在同名 Skill：{{path}}。为安全起见未覆盖。\n你可以：先手动清理该目录后重试。',
         toolNotInstalled: '未检测到该工具已安装，请刷新后重试。',
+        confirmSecrets:
+          '{{message}}\n\n同步后的 Skill 会出现在多个工具目录中，有时还会被提交到 dotfiles 仓库。仍要继续安装吗？',
+        confirmExecutables:
+          '{{message}}\n\n工具可能会代你运行这些文件。仍要同步吗？',
         noSkillsFoundInRepo: '该仓库未发现可导入的 Skills。',
         requireLocalPath: '请输入本地路径',
         requireGitUrl: '请输入 Git 仓库地址',
@@ -483,6 +557,7 @@ export const resources = {
         noSkillsFoundGit: '未在该仓库中发现可导入的 Skills（未找到 SKILL.md）。',
         multiSkillsRepo: '该仓库包含多个 Skills，请提供具体 Skill 文件夹的 URL 链接。',
         allSkillsExist: '该仓库中的所有 Skills 均已安装。',
+        deepLinkSkillNotFound: '该仓库中未找到路径「{{path}}」对应的 Skill。',
         skillAlreadyExists: '技能「{{name}}」已存在，请更换名称后再安装。',
         duplicateSelectedSkills: '所选 Skills 含有重复名称「{{name}}」，请只选择一个。',
         selectAtLeastOneSkill: '请至少选择一个 Skill',
@@ -494,6 +569,51 @@ export const resources = {
         importFailedTitle: '导入失败：{{name}}',
         unsyncedTitle: '未同步：{{name}}',
         moreCount: '（另有{{count}}个）',
+        selfRepairIssues:
+          '启动检查发现 {{count}} 个问题，涉及：{{names}}{{more}}。相关文件已在 Skills Hub 之外被移动或删除，请重新同步、重新安装或删除。',
+        codes: {
+          MULTI_SKILLS:
+            '该仓库包含多个 Skills，请复制具体 Skill 文件夹链接（例如 GitHub 的 /tree/<branch>/skills/<name>），再导入。',
+          TARGET_EXISTS: '目标目录已存在：{{path}}',
+          TARGET_ACCESS: '{{path}} 为只读，请改为可写后再同步。',
+          TARGET_ACCESS_other_owner:
+            '{{path}} 属于其他用户（可能是用 sudo 运行过工具），请先取回所有权（sudo chown -R "$USER" <目录>）再同步。',
+          SKILL_EXISTS: '中央仓库中已存在同名 Skill：{{path}}',
+          INVALID_PATH: '路径超出了 Skill 或来源目录：{{path}}',
+          TOOL_NOT_INSTALLED: '未安装该工具：{{tool}}',
+          HAS_DEPENDENTS: '以下 Skills 依赖于它：{{dependents}}',
+          TOOL_INCOMPATIBLE: '该 Skill 不支持 {{tool}}（支持：{{compatible_tools}}）',
+          SKILL_INVALID: 'Skill 无效：{{reason}}',
+          SKILL_LINT_FAILED: 'Skill 校验未通过：{{summary}}',
+          DEPENDENCY_CONFLICT: '依赖冲突：{{summary}}',
+          SECRETS_FOUND: '该 Skill 中疑似包含凭据：{{summary}}',
+          EXECUTABLES_UNCONFIRMED: '{{skill}} 包含脚本或可执行文件。',
+          EXECUTABLES_BLOCKED: '{{skill}} 包含脚本或可执行文件，当前策略不允许同步。',
+          GIT_HOST_BLOCKED: 'Git 主机策略不允许访问 {{host}}。',
+          SIGNATURE_REQUIRED: '{{skill}} 没有受信任密钥的签名。',
+          SIGNATURE_INVALID: '{{skill}} 的签名与内容不符。',
+          SIGNATURE_UNTRUSTED: '{{skill}} 的签名密钥不受信任（{{key_id}}）。',
+          SYNC_CONFLICT:
+            '远端与本地修改了相同的文件。请先在中央仓库中解决以下 Skills 的冲突，再重新拉取：{{skills}}',
+          SYNC_REJECTED: '远端 {{branch}} 有更新的提交，请先拉取再推送。',
+          INVALID_BATCH: '批量操作无效：{{summary}}',
+          GIT_TIMEOUT:
+            'git 操作超时（{{secs}}s）。请检查网络/代理是否可访问 GitHub；也可设置环境变量 SKILLS_HUB_GIT_TIMEOUT_SECS 增大超时。\n{{output}}',
+          GIT_CLI_FAILED:
+            'git 命令执行失败（为避免卡死，已停止并不再回退到内置 git）。请检查系统 git/网络/代理；或设置环境变量 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允许回退。\n{{cause}}',
+          CLEANUP_FAILED: '已删除托管记录，但清理部分工具目录失败：\n- {{failures}}',
+          OPERATION_IN_PROGRESS: '该 Skill 正在执行其他操作（{{operation}}），请稍后重试。',
+          GIT_FETCH_FAILED_tls:
+            '无法从 GitHub 拉取仓库：TLS/证书校验失败。\n\n建议：\n- 检查网络/代理是否拦截 HTTPS\n- 如在公司网络，可能需要安装公司根证书或使用可信代理\n- 也可在终端确认 `git clone https://github.com/<owner>/<repo>` 是否可用\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED_auth: '无法访问该仓库：可能是私有仓库/权限不足/需要鉴权。\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED_not_found:
+            '仓库不存在或无权限访问（GitHub 返回 not found）。\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED_dns: '无法解析 GitHub 域名（DNS）。请检查网络/代理。\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED_timeout: '连接 GitHub 超时。请检查网络/代理。\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED_connection:
+            '连接 GitHub 失败（连接被拒绝/重置）。请检查网络/代理。\n\n详细：{{cause}}',
+          GIT_FETCH_FAILED: '无法从 GitHub 拉取仓库。请检查网络/代理，或稍后重试。\n\n详细：{{cause}}',
+        },
       },
       actions: {
         importExisting: '导入 {{name}} ...',
@@ -507,11 +627,18 @@ export const resources = {
         unsyncing: '取消生效：{{name}} -> {{tool}} ...',
         syncingAll: '同步 {{name}} 到所有工具...',
         unsyncingAll: '从所有工具取消 {{name}} ...',
+        stages: {
+          cloning: '克隆中',
+          resolving_deltas: '处理差异',
+          checking_out: '检出文件',
+          copying: '复制文件',
+        },
       },
       status: {
         importCompleted: '导入完成。',
         localSkillCreated: '本地技能创建完成。',
         gitSkillCreated: 'Git 技能创建完成。',
+        logsCopied: '最近日志已复制到剪贴板。',
         selectedSkillsInstalled: '选中技能安装完成。',
         skillRemoved: '已删除该技能。',
         skillUpdated: '技能更新成功。',
@@ -4,6 +4,8 @@ import { useSkillStore } from '../stores/useSkillStore'
 import { useToolStore } from '../stores/useToolStore'
 import { useAppStore } from '../stores/useAppStore'
 import { useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import FilterBar from '../components/skills/FilterBar'
 import StatsCards from '../components/skills/StatsCards'
 import SkillsList from '../components/skills/SkillsList'
@@ -11,22 +13,76 @@ import AddSkillModal from '../components/skills/modals/AddSkillModal'
 import SettingsModal from '../components/skills/modals/SettingsModal'
 import ImportModal from '../components/skills/modals/ImportModal'
 import { useSkillsManager } from '../hooks/useSkillsManager'
-import type { OnboardingPlan } from '../components/skills/types'
+import type { DeepLinkInstall, OnboardingPlan, SelfRepairSummary } from '../components/skills/types'
 
 const DashboardPage = () => {
     const { skills, loading: skillsLoading, fetchSkills, searchQuery, sortBy } = useSkillStore()
     const { tools, installedToolIds } = useToolStore()
-    const { modals, closeModal, openModal } = useAppStore()
+    const { modals, closeModal, openModal, setError } = useAppStore()
     const { t } = useTranslation()
     const { deleteSkill, updateSkill, toggleToolSync, syncAllTools, getOnboardingPlan } = useSkillsManager()
 
     const [plan, setPlan] = useState<OnboardingPlan | null>(null)
+    const [deepLink, setDeepLink] = useState<DeepLinkInstall | null>(null)
 
     useEffect(() => {
         fetchSkills()
         getOnboardingPlan().then(setPlan)
     }, [fetchSkills, getOnboardingPlan])
 
+    // The backend emits `skills-changed` after every store write; bulk operations send many,
+    // so refetch once they settle.
+    useEffect(() => {
+        let timer: ReturnType<typeof setTimeout> | undefined
+        const unlisten = listen('skills-changed', () => {
+            clearTimeout(timer)
+            timer = setTimeout(() => { fetchSkills() }, 200)
+        })
+        return () => {
+            clearTimeout(timer)
+            unlisten.then((fn) => fn())
+        }
+    }, [fetchSkills])
+
+    // skillshub://install links are queued by the backend until we pick them up here.
+    useEffect(() => {
+        const takePending = async () => {
+            const pending = await invoke<DeepLinkInstall[]>('take_pending_deep_links')
+            const latest = pending[pending.length - 1]
+            if (!latest) return
+            setDeepLink(latest)
+            openModal('addSkill')
+        }
+        takePending().catch(console.error)
+        const unlisten = listen('deep-link-install', () => {
+            takePending().catch(console.error)
+        })
+        return () => {
+            unlisten.then((fn) => fn())
+        }
+    }, [openModal])
+
+    // The startup self-repair pass may finish before or after we mount; surface its findings once.
+    useEffect(() => {
+        const takeSummary = async () => {
+            const summary = await invoke<SelfRepairSummary | null>('take_self_repair_summary')
+            if (!summary || summary.issues.length === 0) return
+            const names = [...new Set(summary.issues.map((issue) => issue.skill_name))]
+            setError(t('errors.selfRepairIssues', {
+                count: summary.issues.length,
+                names: names.slice(0, 3).join(', '),
+                more: names.length > 3 ? t('errors.moreCount', { count: names.length - 3 }) : '',
+            }))
+        }
+        takeSummary().catch(console.error)
+        const unlisten = listen('self-repair-summary', () => {
+            takeSummary().catch(console.error)
+        })
+        return () => {
+            unlisten.then((fn) => fn())
+        }
+    }, [setError, t])
+
     // Compute stats
     const stats = useMemo(() => {
         // Count unique tools that have at least one skill synced
@@ -121,7 +177,17 @@ const DashboardPage = () => {
             </div>
 
             {/* Modals - Temporarily kept here until we decide if they should be global or routing based */}
-            {modals.addSkill && <AddSkillModal onClose={() => closeModal('addSkill')} />}
+            {modals.addSkill && (
+                <AddSkillModal
+                    key={deepLink ? `${deepLink.repo_url}#${deepLink.subpath ?? ''}` : 'manual'}
+                    initialGitUrl={deepLink?.repo_url}
+                    initialSubpath={deepLink?.subpath}
+                    onClose={() => {
+                        closeModal('addSkill')
+                        setDeepLink(null)
+                    }}
+                />
+            )}
             {modals.settings && <SettingsModal onClose={() => closeModal('settings')} />}
             {modals.import && <ImportModal onClose={() => closeModal('import')} onFinish={() => {
                 fetchSkills()
@@ -4,6 +4,7 @@ import { invoke } from '@tauri-apps/api/core'
 import { useTranslation } from 'react-i18next'
 import { toast } from 'sonner'
 import type { RegistrySkill } from '../components/skills/types'
+import { getErrorMessage } from '../components/skills/errors'
 import './DiscoverPage.css'
 
 const DiscoverPage = () => {
@@ -49,7 +50,7 @@ const DiscoverPage = () => {
             })
             toast.success(t('discover.importSuccess', { name: skill.name }))
         } catch (err: unknown) {
-            const errMsg = err instanceof Error ? err.message : String(err)
+            const errMsg = getErrorMessage(err)
             console.error('Install failed:', errMsg)
             toast.error(t('discover.importFailed'))
         } finally {
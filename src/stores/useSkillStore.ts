@@ -1,6 +1,7 @@
 import { create } from 'zustand'
 import { invoke } from '@tauri-apps/api/core'
 import type { ManagedSkill } from '../components/skills/types'
+import { getErrorMessage } from '../components/skills/errors'
 
 interface SkillState {
     skills: ManagedSkill[]
@@ -29,7 +30,7 @@ export const useSkillStore = create<SkillState>((set) => ({
             const skills = await invoke<ManagedSkill[]>('get_managed_skills')
             set({ skills, loading: false })
         } catch (err) {
-            set({ error: err instanceof Error ? err.message : String(err), loading: false })
+            set({ error: getErrorMessage(err), loading: false })
         }
     },
 
@@ -1,6 +1,7 @@
 import { create } from 'zustand'
 import { invoke } from '@tauri-apps/api/core'
 import type { ToolStatusDto, ToolOption } from '../components/skills/types'
+import { getErrorMessage } from '../components/skills/errors'
 
 interface ToolState {
     status: ToolStatusDto | null
@@ -39,7 +40,7 @@ export const useToolStore = create<ToolState>((set) => ({
             })
         } catch (err) {
             set({
-                error: err instanceof Error ? err.message : String(err),
+                error: getErrorMessage(err),
                 loading: false
             })
         }